@@ -5,7 +5,7 @@ use crate::pages::{Menu, MonitorPage, TimerPage, ParticlesPage, FlappyPage, TicT
 // Supports both simple syntax (below) and full syntax with #[Root(default=Menu)]
 define_app! {
     Menu => menu: Menu,
-    Monitor => monitor: MonitorPage,
+    Monitor => monitor: MonitorPage = MonitorPage::new(cx),
     Timer => timer: TimerPage,
     Particles => particles: ParticlesPage,
     Flappy => flappy: FlappyPage,
@@ -8,10 +8,15 @@ use rat_nexus::Application;
 use crate::app::Root;
 
 fn main() -> anyhow::Result<()> {
-    let app = Application::new();
+    let app = Application::new().quick_switcher(true);
 
-    app.run(move |cx| {
-        cx.set_root(Root::new())?;
+    app.run_with_args(move |cx| {
+        cx.storm_detector().set_overlay(cfg!(debug_assertions));
+        cx.set_presentation_slowdown(2.0);
+        for palette in model::DemoPalette::ALL {
+            cx.register_theme(palette.theme());
+        }
+        cx.set_root(Root::new(cx))?;
         Ok(())
     })
 }
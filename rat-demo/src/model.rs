@@ -1,58 +1,18 @@
 //! Application state models demonstrating Entity reactive state management.
 
+/// Extra themes offered by the demo next to the built-in ones.
+#[derive(Clone, Copy, rat_nexus::Theme)]
+pub enum DemoPalette {
+    #[theme(name = "Ocean", primary = "#0096c8", secondary = "LightBlue", selection = "#003c5a")]
+    Ocean,
+    #[theme(name = "Ember", primary = "#ff8c00", secondary = "LightRed", selection = "#5a2800")]
+    Ember,
+}
+
 /// Global application state shared across all pages.
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct AppState {
     pub counter: i32,
-    pub theme: Theme,
-}
-
-impl Default for AppState {
-    fn default() -> Self {
-        Self {
-            counter: 0,
-            theme: Theme::default(),
-        }
-    }
-}
-
-/// Theme configuration for the application.
-#[derive(Clone, Copy, Default, PartialEq)]
-pub enum Theme {
-    #[default]
-    Cyan,
-    Green,
-    Magenta,
-    Yellow,
-}
-
-impl Theme {
-    pub fn next(&self) -> Self {
-        match self {
-            Theme::Cyan => Theme::Green,
-            Theme::Green => Theme::Magenta,
-            Theme::Magenta => Theme::Yellow,
-            Theme::Yellow => Theme::Cyan,
-        }
-    }
-
-    pub fn color(&self) -> ratatui::style::Color {
-        match self {
-            Theme::Cyan => ratatui::style::Color::Cyan,
-            Theme::Green => ratatui::style::Color::Green,
-            Theme::Magenta => ratatui::style::Color::Magenta,
-            Theme::Yellow => ratatui::style::Color::Yellow,
-        }
-    }
-
-    pub fn name(&self) -> &'static str {
-        match self {
-            Theme::Cyan => "Cyan",
-            Theme::Green => "Green",
-            Theme::Magenta => "Magenta",
-            Theme::Yellow => "Yellow",
-        }
-    }
 }
 
 /// State for the System Monitor page.
@@ -284,7 +284,7 @@ impl Component for FlappyPage {
                     });
                     app.refresh();
                 }
-                tokio::time::sleep(tokio::time::Duration::from_millis(33)).await;
+                tokio::time::sleep(app.animation_interval(tokio::time::Duration::from_millis(33))).await;
             }
         });
         self.tasks.track(handle);
@@ -324,6 +324,7 @@ impl Component for FlappyPage {
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
                 .border_style(Style::default().fg(Color::Cyan)))
+            .marker(cx.capabilities().canvas_marker())
             .x_bounds([0.0, 100.0])
             .y_bounds([0.0, 50.0])
             .paint(move |ctx| {
@@ -3,22 +3,14 @@ use ratatui::widgets::Paragraph;
 use crossterm::event::KeyCode;
 use crate::model::AppState;
 
+#[derive(Default, rat_nexus::ViewState)]
 pub struct Menu {
+    #[view_state]
     selected: usize,
     options: Vec<(&'static str, &'static str, Route)>,  // Will be set in on_mount
     state: Entity<AppState>,
 }
 
-impl Default for Menu {
-    fn default() -> Self {
-        Self {
-            selected: 0,
-            options: Vec::new(),
-            state: Entity::default(),
-        }
-    }
-}
-
 impl Component for Menu {
     fn on_mount(&mut self, cx: &mut Context<Self>) {
         // Initialize options
@@ -34,7 +26,7 @@ impl Component for Menu {
         // Initialize/get shared state
         let state = cx.get_or_insert_with::<Entity<AppState>, _>(|| {
             cx.new_entity(AppState::default())
-        }).expect("Failed to initialize AppState");
+        });
         self.state = state;
     }
 
@@ -52,13 +44,15 @@ impl Component for Menu {
 
     fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
         use ratatui::layout::{Layout, Constraint, Direction, Alignment};
-        use ratatui::widgets::{Block, Borders, List, ListItem, BorderType};
+        use ratatui::widgets::{List, ListItem};
         use ratatui::style::{Style, Modifier, Color};
         use ratatui::text::{Line, Span};
 
         cx.subscribe(&self.state);
         let app_state = self.state.read(|s| s.clone()).unwrap_or_default();
-        let theme_color = app_state.theme.color();
+        let theme = cx.theme();
+        let theme_color = theme.primary;
+        let density = cx.density();
 
         let area = frame.area();
 
@@ -89,7 +83,8 @@ impl Component for Menu {
         let body_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
-            .margin(1)
+            .margin(density.spacing())
+            .spacing(density.spacing())
             .split(main_chunks[1]);
 
         // Menu list with descriptions
@@ -116,10 +111,8 @@ impl Component for Menu {
             .collect();
 
         let list = List::new(items)
-            .block(Block::default()
+            .block(density.block()
                 .title(" Select Module ")
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
                 .border_style(Style::default().fg(theme_color)));
 
         frame.render_widget(list, body_chunks[0]);
@@ -161,26 +154,24 @@ impl Component for Menu {
             ]),
             Line::from(vec![
                 Span::styled(" Theme: ", Style::default().fg(Color::DarkGray)),
-                Span::styled(app_state.theme.name(), Style::default().fg(theme_color)),
+                Span::styled(theme.name.clone(), Style::default().fg(theme_color)),
             ]),
         ];
 
         let info = Paragraph::new(info_lines)
-            .block(Block::default()
+            .block(density.block()
                 .title(" About ")
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
                 .border_style(Style::default().fg(theme_color)));
         frame.render_widget(info, body_chunks[1]);
 
         // Footer
-        let footer = Paragraph::new(" ↑/↓ Navigate │ Enter Select │ T Theme │ Q Quit ")
-            .style(Style::default().bg(theme_color).fg(Color::Black))
+        let footer = Paragraph::new(" ↑/↓ Navigate │ Enter Select │ T Theme │ Z Density │ P Present │ Q Quit ")
+            .style(theme.bar())
             .alignment(Alignment::Center);
         frame.render_widget(footer, main_chunks[2]);
     }
 
-    fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
         match event {
             Event::Key(key) => match key.code {
                 KeyCode::Up | KeyCode::Char('k') => {
@@ -190,6 +181,7 @@ impl Component for Menu {
                         } else {
                             self.selected = self.options.len() - 1;
                         }
+                        cx.notify();
                     }
                     None
                 }
@@ -200,6 +192,7 @@ impl Component for Menu {
                         } else {
                             self.selected = 0;
                         }
+                        cx.notify();
                     }
                     None
                 }
@@ -216,7 +209,15 @@ impl Component for Menu {
                     }
                 }
                 KeyCode::Char('t') => {
-                    let _ = self.state.update(|s| s.theme = s.theme.next());
+                    cx.next_theme();
+                    None
+                }
+                KeyCode::Char('z') => {
+                    cx.set_density(cx.density().next());
+                    None
+                }
+                KeyCode::Char('p') => {
+                    cx.set_presentation_mode(!cx.presentation_mode());
                     None
                 }
                 KeyCode::Char('q') => Some(Action::Quit),
@@ -1,14 +1,14 @@
 //! System Monitor - Real-time system metrics visualization.
 //!
 //! Demonstrates:
-//! - Real-time async data updates with TaskTracker
+//! - Real-time data updates with set_interval
 //! - Chart widget for time series
 //! - Multiple Sparklines
 //! - Table with dynamic data
 //! - Complex layout composition
 
-use rat_nexus::{Component, Context, EventContext, Event, Action, Entity, TaskTracker};
-use crate::model::{AppState, MonitorState};
+use rat_nexus::{AppContext, Component, Context, EventContext, Event, Action, Entity, TimerHandle, push_capped};
+use crate::model::MonitorState;
 use ratatui::{
     layout::{Layout, Constraint, Direction, Alignment, Rect},
     widgets::{
@@ -21,88 +21,68 @@ use ratatui::{
 };
 use crossterm::event::KeyCode;
 
-#[derive(Default)]
 pub struct MonitorPage {
-    app_state: Entity<AppState>,
     state: Entity<MonitorState>,
-    tasks: TaskTracker,
+    ticker: Option<TimerHandle>,
 }
 
 impl Component for MonitorPage {
-    fn on_mount(&mut self, cx: &mut Context<Self>) {
-        // Get or initialize shared AppState
-        let app_state = cx.get_or_insert_with::<Entity<AppState>, _>(|| {
-            cx.new_entity(AppState::default())
-        }).expect("Failed to initialize AppState");
-        self.app_state = app_state;
-
-        // Initialize MonitorState
-        let state = cx.new_entity(MonitorState::default());
-        self.state = Entity::clone(&state);
-
-        // Spawn data simulation task
-        let handle = cx.spawn_detached_task(move |app| async move {
-            use rand::Rng;
-            use rand::SeedableRng;
-            let mut rng = rand::rngs::StdRng::from_entropy();
-
-            loop {
-                let _ = state.update(|s| {
-                    // Simulate CPU usage
-                    s.cpu_history.remove(0);
-                    s.cpu_history.push(rng.gen_range(20..80));
-
-                    // Simulate memory usage
-                    s.memory_history.remove(0);
-                    let last_mem = *s.memory_history.last().unwrap_or(&50);
-                    let delta: i64 = rng.gen_range(-5..6);
-                    s.memory_history.push(((last_mem as i64 + delta).clamp(30, 70)) as u64);
-
-                    // Simulate network
-                    s.network_in.remove(0);
-                    s.network_in.push(rng.gen_range(10..100));
-                    s.network_out.remove(0);
-                    s.network_out.push(rng.gen_range(5..50));
-
-                    // Simulate CPU cores
-                    for core in s.cpu_cores.iter_mut() {
-                        *core = rng.gen_range(10..100);
-                    }
-
-                    // Update disk (slow change)
-                    if rng.gen_bool(0.1) {
-                        let delta: i16 = rng.gen_range(-2..3);
-                        s.disk_usage = (s.disk_usage as i16 + delta).clamp(20, 80) as u16;
-                    }
+    fn on_enter(&mut self, cx: &mut Context<Self>) {
+        use rand::Rng;
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::from_entropy();
+
+        // Simulate new data every half second
+        let state = Entity::clone(&self.state);
+        self.ticker = Some(cx.app().set_interval(std::time::Duration::from_millis(500), move |_| {
+            let _ = state.update(|s| {
+                // Simulate CPU usage
+                push_capped(&mut s.cpu_history, rng.gen_range(20..80), 60);
+
+                // Simulate memory usage
+                let last_mem = *s.memory_history.last().unwrap_or(&50);
+                let delta: i64 = rng.gen_range(-5..6);
+                push_capped(&mut s.memory_history, ((last_mem as i64 + delta).clamp(30, 70)) as u64, 60);
+
+                // Simulate network
+                push_capped(&mut s.network_in, rng.gen_range(10..100), 30);
+                push_capped(&mut s.network_out, rng.gen_range(5..50), 30);
+
+                // Simulate CPU cores
+                for core in s.cpu_cores.iter_mut() {
+                    *core = rng.gen_range(10..100);
+                }
 
-                    // Update processes
-                    for proc in s.processes.iter_mut() {
-                        proc.cpu = (proc.cpu + rng.gen_range(-0.5..0.5)).clamp(0.0, 10.0);
-                        proc.memory = (proc.memory + rng.gen_range(-0.2..0.2)).clamp(0.1, 5.0);
-                    }
+                // Update disk (slow change)
+                if rng.gen_bool(0.1) {
+                    let delta: i16 = rng.gen_range(-2..3);
+                    s.disk_usage = (s.disk_usage as i16 + delta).clamp(20, 80) as u16;
+                }
 
-                    // Uptime
-                    s.uptime_secs += 1;
-                });
+                // Update processes
+                for proc in s.processes.iter_mut() {
+                    proc.cpu = (proc.cpu + rng.gen_range(-0.5..0.5)).clamp(0.0, 10.0);
+                    proc.memory = (proc.memory + rng.gen_range(-0.2..0.2)).clamp(0.1, 5.0);
+                }
 
-                app.refresh();
-                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-            }
-        });
-        self.tasks.track(handle);
+                // Uptime
+                s.uptime_secs += 1;
+            });
+        }));
     }
 
     fn on_exit(&mut self, _cx: &mut Context<Self>) {
-        self.tasks.abort_all();
+        if let Some(ticker) = self.ticker.take() {
+            ticker.cancel();
+        }
     }
 
     fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
         cx.subscribe(&self.state);
-        cx.subscribe(&self.app_state);
 
         let state_data = self.state.read(|s| s.clone()).unwrap_or_default();
-        let app = self.app_state.read(|s| s.clone()).unwrap_or_default();
-        let theme_color = app.theme.color();
+        let theme = cx.theme();
+        let theme_color = theme.primary;
 
         let area = frame.area();
 
@@ -121,7 +101,7 @@ impl Component for MonitorPage {
         let header_text = format!(
             " 📊 System Monitor │ Uptime: {} │ Theme: {} ",
             uptime_str,
-            app.theme.name()
+            theme.name
         );
         let header = Paragraph::new(header_text)
             .style(Style::default().fg(theme_color).add_modifier(Modifier::BOLD))
@@ -139,25 +119,25 @@ impl Component for MonitorPage {
             .split(main_layout[1]);
 
         // Left side: Charts
-        self.render_charts(frame, body_layout[0], &state_data, theme_color);
+        self.render_charts(frame, body_layout[0], &state_data, theme_color, cx.capabilities().chart_marker());
 
         // Right side: Metrics and processes
         self.render_sidebar(frame, body_layout[1], &state_data, theme_color);
 
         // Footer
         let footer = Paragraph::new(" R Reset │ T Theme │ M Menu │ Q Quit │ Mouse: Scroll to adjust ")
-            .style(Style::default().bg(theme_color).fg(Color::Black))
+            .style(theme.bar())
             .alignment(Alignment::Center);
         frame.render_widget(footer, main_layout[2]);
     }
 
-    fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
         match event {
             Event::Key(key) => match key.code {
                 KeyCode::Char('q') => Some(Action::Quit),
                 KeyCode::Char('m') | KeyCode::Esc => Some(Action::Navigate("menu".to_string())),
                 KeyCode::Char('t') => {
-                    let _ = self.app_state.update(|s| s.theme = s.theme.next());
+                    cx.next_theme();
                     None
                 }
                 KeyCode::Char('r') => {
@@ -192,7 +172,7 @@ impl Component for MonitorPage {
                     }
                     MouseEventKind::Down(MouseButton::Left) => {
                         // Left click cycles theme
-                        let _ = self.app_state.update(|s| s.theme = s.theme.next());
+                        cx.next_theme();
                         None
                     }
                     MouseEventKind::Down(MouseButton::Right) => {
@@ -211,7 +191,14 @@ impl Component for MonitorPage {
 }
 
 impl MonitorPage {
-    fn render_charts(&self, frame: &mut ratatui::Frame, area: Rect, state: &MonitorState, theme_color: Color) {
+    pub fn new(cx: &AppContext) -> Self {
+        Self {
+            state: cx.new_entity(MonitorState::default()),
+            ticker: None,
+        }
+    }
+
+    fn render_charts(&self, frame: &mut ratatui::Frame, area: Rect, state: &MonitorState, theme_color: Color, marker: symbols::Marker) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -236,13 +223,13 @@ impl MonitorPage {
         let datasets = vec![
             Dataset::default()
                 .name("CPU")
-                .marker(symbols::Marker::Braille)
+                .marker(marker)
                 .graph_type(GraphType::Line)
                 .style(Style::default().fg(theme_color))
                 .data(&cpu_data),
             Dataset::default()
                 .name("Memory")
-                .marker(symbols::Marker::Braille)
+                .marker(marker)
                 .graph_type(GraphType::Line)
                 .style(Style::default().fg(Color::Green))
                 .data(&mem_data),
@@ -312,8 +299,8 @@ impl MonitorPage {
             let _label = format!("C{}", i);
 
             // Create a vertical gauge effect using text
-            let height = chunk.height as u16;
-            let filled = (usage as u16 * height / 100).min(height);
+            let height = chunk.height;
+            let filled = (usage * height / 100).min(height);
 
             let mut lines = Vec::new();
             for h in (0..height).rev() {
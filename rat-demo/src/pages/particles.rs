@@ -1,7 +1,7 @@
 //! Particles Demo - Animated particle system
-//! Showcases: spawn_task, Entity updates, real-time animation, TaskTracker
+//! Showcases: set_interval, Entity updates, real-time animation
 
-use rat_nexus::{Component, Context, EventContext, Event, Action, Entity, TaskTracker};
+use rat_nexus::{Component, Context, EventContext, Event, Action, Entity};
 use ratatui::{
     layout::{Layout, Constraint, Direction, Alignment},
     widgets::{Block, Borders, Paragraph, BorderType, canvas::{Canvas, Points}},
@@ -31,67 +31,61 @@ pub struct ParticlesState {
 #[derive(Default)]
 pub struct ParticlesPage {
     state: Entity<ParticlesState>,
-    tasks: TaskTracker,
 }
 
 impl Component for ParticlesPage {
     fn on_mount(&mut self, cx: &mut Context<Self>) {
         // Initialize state entity
-        let state = cx.new_entity(ParticlesState { spawn_x: 50.0, spawn_y: 25.0, ..Default::default() });
-        self.state = Entity::clone(&state);
-
-        // Particle physics update loop
-        let handle = cx.spawn_detached_task(move |app| async move {
-            use rand::Rng;
-            use rand::SeedableRng;
-            let mut rng = rand::rngs::StdRng::from_entropy();
-
-            loop {
-                let paused = state.read(|s| s.paused).unwrap_or(false);
-                if !paused {
-                    let _ = state.update(|s| {
-                        // Spawn new particles
-                        for _ in 0..3 {
-                            let angle = rng.gen_range(0.0..std::f64::consts::TAU);
-                            let speed = rng.gen_range(0.5..2.0);
-                            s.particles.push(Particle {
-                                x: s.spawn_x,
-                                y: s.spawn_y,
-                                vx: angle.cos() * speed,
-                                vy: angle.sin() * speed,
-                                life: rng.gen_range(40..80),
-                                color: match rng.gen_range(0..5) {
-                                    0 => Color::Red,
-                                    1 => Color::Yellow,
-                                    2 => Color::Green,
-                                    3 => Color::Cyan,
-                                    _ => Color::Magenta,
-                                },
-                            });
-                            s.total_spawned += 1;
-                        }
-
-                        // Update particles
-                        for p in s.particles.iter_mut() {
-                            p.x += p.vx;
-                            p.y += p.vy;
-                            p.vy -= 0.03; // gravity
-                            p.life = p.life.saturating_sub(1);
-                        }
-
-                        // Remove dead particles
-                        s.particles.retain(|p| p.life > 0);
+        self.state = cx.new_entity(ParticlesState { spawn_x: 50.0, spawn_y: 25.0, ..Default::default() });
+    }
+
+    fn on_enter(&mut self, cx: &mut Context<Self>) {
+        use rand::Rng;
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::from_entropy();
+
+        // Particle physics update loop, stopped when the page exits
+        let period = cx.app().animation_interval(std::time::Duration::from_millis(33));
+        cx.set_interval(period, move |this, _cx| {
+            let _ = this.state.update_when(|s| {
+                if s.paused {
+                    return false;
+                }
+                // Spawn new particles
+                for _ in 0..3 {
+                    let angle = rng.gen_range(0.0..std::f64::consts::TAU);
+                    let speed = rng.gen_range(0.5..2.0);
+                    s.particles.push(Particle {
+                        x: s.spawn_x,
+                        y: s.spawn_y,
+                        vx: angle.cos() * speed,
+                        vy: angle.sin() * speed,
+                        life: rng.gen_range(40..80),
+                        color: match rng.gen_range(0..5) {
+                            0 => Color::Red,
+                            1 => Color::Yellow,
+                            2 => Color::Green,
+                            3 => Color::Cyan,
+                            _ => Color::Magenta,
+                        },
                     });
-                    app.refresh();
+                    s.total_spawned += 1;
                 }
-                tokio::time::sleep(tokio::time::Duration::from_millis(33)).await;
-            }
-        });
-        self.tasks.track(handle);
-    }
 
-    fn on_exit(&mut self, _cx: &mut Context<Self>) {
-        self.tasks.abort_all();
+                // Update particles
+                for p in s.particles.iter_mut() {
+                    p.x += p.vx;
+                    p.y += p.vy;
+                    p.vy -= 0.03; // gravity
+                    p.life = p.life.saturating_sub(1);
+                }
+
+                // Remove dead particles
+                s.particles.retain(|p| p.life > 0);
+                true
+            });
+        })
+        .expect("pages can set timers");
     }
 
     fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
@@ -127,6 +121,7 @@ impl Component for ParticlesPage {
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
                 .border_style(Style::default().fg(Color::Magenta)))
+            .marker(cx.capabilities().canvas_marker())
             .x_bounds([0.0, 100.0])
             .y_bounds([0.0, 50.0])
             .paint(move |ctx| {
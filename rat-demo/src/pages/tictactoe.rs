@@ -1,8 +1,9 @@
 //! Gomoku (Five in a Row) - Human vs AI game
 //! Showcases: Component composition, AI heuristics, State management, Canvas rendering, Mouse support
 
-use rat_nexus::{Component, Context, EventContext, Event, Action, Entity};
+use rat_nexus::{Component, ChildView, Context, EventContext, Event, Action, Entity};
 use ratatui::{
+    symbols::Marker,
     layout::{Layout, Constraint, Direction, Alignment, Rect},
     widgets::{Block, Borders, Paragraph, BorderType, canvas::{Canvas, Line as CanvasLine, Circle}},
     style::{Style, Color, Modifier},
@@ -449,14 +450,16 @@ impl GomokuState {
     }
 }
 
+// ============================================
+// Board Component - mounted as a child so the framework tracks its area
+// ============================================
 #[derive(Default)]
-pub struct TicTacToePage {
+struct GomokuBoard {
     state: Entity<GomokuState>,
-    board_area: Rect,  // Store separately to avoid update in render
 }
 
-impl TicTacToePage {
-    fn render_board(&self, frame: &mut ratatui::Frame, area: Rect, state: &GomokuState) {
+impl GomokuBoard {
+    fn render_board(&self, frame: &mut ratatui::Frame, area: Rect, state: &GomokuState, marker: Marker) {
         let winning_line = state.winning_line.clone();
         let last_move = state.board.last_move;
         let cursor = state.cursor;
@@ -468,6 +471,7 @@ impl TicTacToePage {
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
                 .border_style(Style::default().fg(Color::Cyan)))
+            .marker(marker)
             .x_bounds([0.0, 100.0])
             .y_bounds([0.0, 100.0])
             .paint(move |ctx| {
@@ -515,7 +519,7 @@ impl TicTacToePage {
                         let cy = 100.0 - margin - row as f64 * cell_size;
 
                         // Check if this is part of winning line
-                        let is_winning = winning_line.as_ref().map_or(false, |line| {
+                        let is_winning = winning_line.as_ref().is_some_and(|line| {
                             line.iter().any(|&(r, c)| r == row && c == col)
                         });
 
@@ -563,7 +567,58 @@ impl TicTacToePage {
 
         frame.render_widget(canvas, area);
     }
+}
 
+impl Component for GomokuBoard {
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let state = self.state.read(|s| s.clone()).unwrap_or_default();
+        self.render_board(frame, cx.area(frame), &state, cx.capabilities().canvas_marker());
+    }
+
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+        // Mouse events only arrive here when they hit the board area
+        let (Event::Mouse(mouse), Some(board_area)) = (event, cx.child_area()) else {
+            return None;
+        };
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let _ = self.state.update(|s| {
+                    if let Some((row, col)) = GomokuState::screen_to_cell(mouse.column, mouse.row, board_area) {
+                        s.cursor = (row, col);
+                        if s.make_move_at(row, col) {
+                            s.make_ai_move();
+                        }
+                    }
+                });
+            }
+            MouseEventKind::Down(MouseButton::Right) => {
+                let _ = self.state.update(|s| s.reset());
+            }
+            MouseEventKind::Moved => {
+                // Most moves stay within a cell; don't redraw for those
+                let _ = self.state.update_when(|s| {
+                    match GomokuState::screen_to_cell(mouse.column, mouse.row, board_area) {
+                        Some(cell) if s.status == GameStatus::Playing && s.cursor != cell => {
+                            s.cursor = cell;
+                            true
+                        }
+                        _ => false,
+                    }
+                });
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+#[derive(Default)]
+pub struct TicTacToePage {
+    state: Entity<GomokuState>,
+    board: ChildView<GomokuBoard>,
+}
+
+impl TicTacToePage {
     fn render_info_panel(&self, frame: &mut ratatui::Frame, area: Rect, state: &GomokuState) {
         let status_text = match state.status {
             GameStatus::Playing => {
@@ -645,6 +700,7 @@ impl Component for TicTacToePage {
     fn on_mount(&mut self, cx: &mut Context<Self>) {
         // Initialize state entity
         let state = cx.new_entity(GomokuState::default());
+        self.board = ChildView::new(GomokuBoard { state: state.clone() });
         self.state = state;
     }
 
@@ -688,10 +744,7 @@ impl Component for TicTacToePage {
             .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
             .split(main_layout[1]);
 
-        // Store board area for mouse click detection (no state update needed)
-        self.board_area = content_layout[0];
-
-        self.render_board(frame, content_layout[0], &state_data);
+        cx.mount_child(&mut self.board, frame, content_layout[0]);
         self.render_info_panel(frame, content_layout[1], &state_data);
 
         // Footer
@@ -701,7 +754,7 @@ impl Component for TicTacToePage {
         frame.render_widget(footer, main_layout[2]);
     }
 
-    fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
         match event {
             Event::Key(key) => match key.code {
                 KeyCode::Char('q') => Some(Action::Quit),
@@ -744,38 +797,7 @@ impl Component for TicTacToePage {
                 }
                 _ => None,
             },
-            Event::Mouse(mouse) => {
-                match mouse.kind {
-                    MouseEventKind::Down(MouseButton::Left) => {
-                        let board_area = self.board_area;
-                        let _ = self.state.update(|s| {
-                            if let Some((row, col)) = GomokuState::screen_to_cell(mouse.column, mouse.row, board_area) {
-                                s.cursor = (row, col);
-                                if s.make_move_at(row, col) {
-                                    s.make_ai_move();
-                                }
-                            }
-                        });
-                        None
-                    }
-                    MouseEventKind::Down(MouseButton::Right) => {
-                        let _ = self.state.update(|s| s.reset());
-                        None
-                    }
-                    MouseEventKind::Moved => {
-                        let board_area = self.board_area;
-                        let _ = self.state.update(|s| {
-                            if s.status == GameStatus::Playing {
-                                if let Some((row, col)) = GomokuState::screen_to_cell(mouse.column, mouse.row, board_area) {
-                                    s.cursor = (row, col);
-                                }
-                            }
-                        });
-                        None
-                    }
-                    _ => None,
-                }
-            }
+            Event::Mouse(_) => self.board.handle_event(event, cx),
             _ => None,
         }
     }
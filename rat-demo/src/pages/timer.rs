@@ -1,7 +1,7 @@
 //! Timer Demo - Stopwatch with lap times
-//! Showcases: Entity state, spawn_task, TaskTracker, async updates
+//! Showcases: Entity state, set_interval, async updates
 
-use rat_nexus::{Component, Context, EventContext, Event, Action, Entity, TaskTracker};
+use rat_nexus::{Component, Context, EventContext, Event, Action, Entity, SizeOf, TimerHandle, push_capped};
 use ratatui::{
     layout::{Layout, Constraint, Direction, Alignment},
     widgets::{Block, Borders, Paragraph, List, ListItem, BorderType},
@@ -10,7 +10,10 @@ use ratatui::{
 };
 use crossterm::event::KeyCode;
 
-#[derive(Clone, Default)]
+/// Laps kept; older ones are dropped.
+const MAX_LAPS: usize = 99;
+
+#[derive(Clone, Default, SizeOf)]
 pub struct TimerState {
     pub elapsed_ms: u64,
     pub running: bool,
@@ -20,30 +23,31 @@ pub struct TimerState {
 #[derive(Default)]
 pub struct TimerPage {
     state: Entity<TimerState>,
-    tasks: TaskTracker,
+    ticker: Option<TimerHandle>,
 }
 
 impl Component for TimerPage {
     fn on_mount(&mut self, cx: &mut Context<Self>) {
         // Initialize state entity
-        let state = cx.new_entity(TimerState::default());
-        self.state = Entity::clone(&state);
-
-        let handle = cx.spawn_detached_task(move |app| async move {
-            loop {
-                let running = state.read(|s| s.running).unwrap_or(false);
-                if running {
-                    let _ = state.update(|s| s.elapsed_ms += 10);
-                    app.refresh();
+        self.state = cx.new_entity(TimerState::default()).track_size();
+    }
+
+    fn on_enter(&mut self, cx: &mut Context<Self>) {
+        let state = Entity::clone(&self.state);
+        self.ticker = Some(cx.app().set_interval(std::time::Duration::from_millis(10), move |_| {
+            let _ = state.update_when(|s| {
+                if s.running {
+                    s.elapsed_ms += 10;
                 }
-                tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
-            }
-        });
-        self.tasks.track(handle);
+                s.running
+            });
+        }));
     }
 
     fn on_exit(&mut self, _cx: &mut Context<Self>) {
-        self.tasks.abort_all();
+        if let Some(ticker) = self.ticker.take() {
+            ticker.cancel();
+        }
     }
 
     fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
@@ -117,7 +121,7 @@ impl Component for TimerPage {
                 KeyCode::Char('l') => {
                     let _ = self.state.update(|s| {
                         if s.running || s.elapsed_ms > 0 {
-                            s.laps.push(s.elapsed_ms);
+                            push_capped(&mut s.laps, s.elapsed_ms, MAX_LAPS);
                         }
                     });
                     None
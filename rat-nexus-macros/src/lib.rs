@@ -0,0 +1,105 @@
+//! Procedural macros for rat-nexus.
+//!
+//! These are re-exported from the `rat_nexus` crate; depend on that instead of using
+//! this crate directly.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod size_of;
+mod theme;
+mod view_state;
+
+/// Derive theme helpers for a fieldless enum.
+///
+/// Each variant carries a `#[theme(...)]` attribute. `name = "..."` sets the display
+/// name (defaults to the variant identifier); every other key is a color token that
+/// becomes an accessor returning `ratatui::style::Color`. All variants must declare
+/// the same set of tokens. Colors are ratatui color names (`Cyan`, `LightRed`) or
+/// `#rrggbb` hex strings.
+///
+/// Generates `ALL`, `next()`, `prev()`, `name()` and one method per color token, plus
+/// `theme()` (and `From<&Self>`) converting a variant into a `rat_nexus::theme::Theme`
+/// whose roles are set by the tokens named after them, so it can be registered with
+/// `AppContext::register_theme`.
+///
+/// # Example
+/// ```ignore
+/// #[derive(Clone, Copy, Default, PartialEq, rat_nexus::Theme)]
+/// pub enum Theme {
+///     #[default]
+///     #[theme(color = "Cyan", accent = "#00afaf")]
+///     Cyan,
+///     #[theme(name = "Forest", color = "Green", accent = "LightGreen")]
+///     Green,
+/// }
+///
+/// assert_eq!(Theme::Cyan.next(), Theme::Green);
+/// assert_eq!(Theme::Green.name(), "Forest");
+///
+/// // Tokens named after a role become the role's color
+/// #[derive(Clone, Copy, rat_nexus::Theme)]
+/// enum Palette {
+///     #[theme(name = "Ocean", primary = "#0096c8", text_muted = "Gray")]
+///     Ocean,
+/// }
+/// for palette in Palette::ALL {
+///     cx.register_theme(palette.theme());
+/// }
+/// ```
+#[proc_macro_derive(Theme, attributes(theme))]
+pub fn derive_theme(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    theme::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `rat_nexus::state::SizeOf` for a struct.
+///
+/// The heap size is the sum of the fields' heap sizes. Fields whose type does not
+/// implement `SizeOf` (handles, channels, closures) are marked `#[size_of(skip)]`.
+///
+/// # Example
+/// ```ignore
+/// #[derive(Clone, Default, rat_nexus::SizeOf)]
+/// pub struct LogState {
+///     lines: VecDeque<String>,
+///     #[size_of(skip)]
+///     sink: Option<Sender<String>>,
+/// }
+/// ```
+#[proc_macro_derive(SizeOf, attributes(size_of))]
+pub fn derive_size_of(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    size_of::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `rat_nexus::router::ViewState` for a page struct.
+///
+/// Fields marked `#[view_state]` (selection, scroll offset, cursor, ...) are captured
+/// when the page is left and restored when it is entered again by the Root that
+/// `define_app!` generates. Marked fields must be `Clone + Send + Sync + 'static`.
+///
+/// # Example
+/// ```ignore
+/// #[derive(Default, rat_nexus::ViewState)]
+/// pub struct Menu {
+///     #[view_state]
+///     selected: usize,
+///     #[view_state]
+///     scroll_offset: u16,
+///     options: Vec<String>,
+/// }
+/// ```
+#[proc_macro_derive(ViewState, attributes(view_state))]
+pub fn derive_view_state(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    view_state::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,213 @@
+//! `#[derive(Theme)]` implementation.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, LitStr};
+
+struct VariantTheme {
+    ident: Ident,
+    name: String,
+    tokens: Vec<(Ident, TokenStream)>,
+}
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let enum_ident = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(&input.ident, "#[derive(Theme)] only supports enums"));
+    };
+    if data.variants.is_empty() {
+        return Err(syn::Error::new_spanned(&input.ident, "#[derive(Theme)] requires at least one variant"));
+    }
+
+    let mut variants = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(variant, "#[derive(Theme)] variants must not have fields"));
+        }
+
+        let mut theme = VariantTheme {
+            ident: variant.ident.clone(),
+            name: variant.ident.to_string(),
+            tokens: Vec::new(),
+        };
+        for attr in variant.attrs.iter().filter(|a| a.path().is_ident("theme")) {
+            attr.parse_nested_meta(|meta| {
+                let key = meta.path.get_ident().cloned()
+                    .ok_or_else(|| meta.error("expected an identifier key"))?;
+                let value: LitStr = meta.value()?.parse()?;
+                if key == "name" {
+                    theme.name = value.value();
+                } else {
+                    theme.tokens.push((key, parse_color(&value)?));
+                }
+                Ok(())
+            })?;
+        }
+        variants.push(theme);
+    }
+
+    // Every variant must define the same color tokens, in any order.
+    let mut token_names: Vec<Ident> = variants[0].tokens.iter().map(|(k, _)| k.clone()).collect();
+    token_names.sort();
+    for variant in &variants {
+        let mut names: Vec<Ident> = variant.tokens.iter().map(|(k, _)| k.clone()).collect();
+        names.sort();
+        if names != token_names {
+            let expected: Vec<String> = token_names.iter().map(Ident::to_string).collect();
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                format!("theme variant must define the color tokens: [{}]", expected.join(", ")),
+            ));
+        }
+    }
+
+    let idents: Vec<&Ident> = variants.iter().map(|v| &v.ident).collect();
+    let names: Vec<&String> = variants.iter().map(|v| &v.name).collect();
+    let count = variants.len();
+    let next: Vec<&Ident> = (0..count).map(|i| idents[(i + 1) % count]).collect();
+    let prev: Vec<&Ident> = (0..count).map(|i| idents[(i + count - 1) % count]).collect();
+
+    let accessors = token_names.iter().map(|token| {
+        let arms = variants.iter().map(|v| {
+            let ident = &v.ident;
+            let (_, color) = v.tokens.iter().find(|(k, _)| k == token).expect("validated above");
+            quote! { #enum_ident::#ident => #color }
+        });
+        let doc = format!("The `{}` color of this theme.", token);
+        quote! {
+            #[doc = #doc]
+            pub fn #token(&self) -> ::rat_nexus::ratatui::style::Color {
+                match self {
+                    #(#arms),*
+                }
+            }
+        }
+    });
+
+    let token_strs: Vec<String> = token_names.iter().map(Ident::to_string).collect();
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #enum_ident #ty_generics #where_clause {
+            /// All themes, in declaration order.
+            pub const ALL: &'static [Self] = &[#(#enum_ident::#idents),*];
+
+            /// The next theme, wrapping around to the first.
+            pub fn next(&self) -> Self {
+                match self {
+                    #(#enum_ident::#idents => #enum_ident::#next),*
+                }
+            }
+
+            /// The previous theme, wrapping around to the last.
+            pub fn prev(&self) -> Self {
+                match self {
+                    #(#enum_ident::#idents => #enum_ident::#prev),*
+                }
+            }
+
+            /// The display name of this theme.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    #(#enum_ident::#idents => #names),*
+                }
+            }
+
+            #(#accessors)*
+
+            /// This palette as a `rat_nexus::theme::Theme` named `name()`. Color tokens
+            /// named after a role (`primary`, `text_muted`...) set it; the other roles
+            /// come from the default theme.
+            pub fn theme(&self) -> ::rat_nexus::theme::Theme {
+                let mut theme = ::rat_nexus::theme::Theme {
+                    name: self.name().to_string(),
+                    ..::rat_nexus::theme::Theme::default()
+                };
+                #(
+                    if let Some(color) = theme.role_mut(#token_strs) {
+                        *color = self.#token_names();
+                    }
+                )*
+                theme
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<&#enum_ident #ty_generics> for ::rat_nexus::theme::Theme #where_clause {
+            fn from(palette: &#enum_ident #ty_generics) -> Self {
+                palette.theme()
+            }
+        }
+    })
+}
+
+/// Parse a color literal into a `ratatui::style::Color` expression.
+fn parse_color(lit: &LitStr) -> syn::Result<TokenStream> {
+    let value = lit.value();
+    if let Some(hex) = value.strip_prefix('#') {
+        let parsed = (hex.len() == 6)
+            .then(|| u32::from_str_radix(hex, 16).ok())
+            .flatten()
+            .ok_or_else(|| syn::Error::new_spanned(lit, "expected a #rrggbb hex color"))?;
+        let (r, g, b) = ((parsed >> 16) as u8, (parsed >> 8) as u8, parsed as u8);
+        return Ok(quote! { ::rat_nexus::ratatui::style::Color::Rgb(#r, #g, #b) });
+    }
+
+    const NAMED: &[&str] = &[
+        "Reset", "Black", "Red", "Green", "Yellow", "Blue", "Magenta", "Cyan", "Gray",
+        "DarkGray", "LightRed", "LightGreen", "LightYellow", "LightBlue", "LightMagenta",
+        "LightCyan", "White",
+    ];
+    match NAMED.iter().find(|name| name.eq_ignore_ascii_case(&value.replace(['_', '-', ' '], ""))) {
+        Some(name) => {
+            let ident = Ident::new(name, Span::call_site());
+            Ok(quote! { ::rat_nexus::ratatui::style::Color::#ident })
+        }
+        None => Err(syn::Error::new_spanned(
+            lit,
+            format!("unknown color '{}'; use a ratatui color name or #rrggbb", value),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn error(input: DeriveInput) -> String {
+        expand(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_expands_cycle_names_and_colors() {
+        let code = expand(parse_quote! {
+            enum Palette {
+                #[theme(color = "Cyan", accent = "#00afaf")]
+                Cyan,
+                #[theme(name = "Forest", accent = "light-green", color = "Green")]
+                Green,
+            }
+        })
+        .unwrap()
+        .to_string();
+        assert!(code.contains("Palette :: Cyan => Palette :: Green"));
+        assert!(code.contains("Palette :: Green => \"Forest\""));
+        assert!(code.contains("pub fn accent (& self)"));
+        assert!(code.contains("Color :: Rgb (0u8 , 175u8 , 175u8)"));
+        assert!(code.contains("Color :: LightGreen"));
+        assert!(code.contains("theme . role_mut (\"accent\")"));
+        assert!(code.contains("From < & Palette > for :: rat_nexus :: theme :: Theme"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_themes() {
+        assert_eq!(error(parse_quote! { struct Palette; }), "#[derive(Theme)] only supports enums");
+        assert_eq!(error(parse_quote! { enum Palette { A(u8) } }), "#[derive(Theme)] variants must not have fields");
+        assert_eq!(
+            error(parse_quote! { enum Palette { #[theme(color = "Cyan")] A, B } }),
+            "theme variant must define the color tokens: [color]"
+        );
+        assert_eq!(error(parse_quote! { enum Palette { #[theme(color = "#12345")] A } }), "expected a #rrggbb hex color");
+        assert!(error(parse_quote! { enum Palette { #[theme(color = "teal")] A } }).starts_with("unknown color 'teal'"));
+    }
+}
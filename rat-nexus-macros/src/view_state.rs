@@ -0,0 +1,51 @@
+//! `#[derive(ViewState)]` implementation.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Index, Member};
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(&input.ident, "#[derive(ViewState)] only supports structs"));
+    };
+
+    let mut members = Vec::new();
+    let mut types = Vec::new();
+    for (i, field) in data.fields.iter().enumerate() {
+        let mut marked = false;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("view_state")) {
+            attr.meta.require_path_only()?;
+            marked = true;
+        }
+        if marked {
+            members.push(match &field.ident {
+                Some(name) => Member::Named(name.clone()),
+                None => Member::Unnamed(Index::from(i)),
+            });
+            types.push(&field.ty);
+        }
+    }
+    if members.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "#[derive(ViewState)] requires at least one field marked #[view_state]",
+        ));
+    }
+
+    let indices = (0..members.len()).map(Index::from);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rat_nexus::router::ViewState for #ident #ty_generics #where_clause {
+            type Snapshot = (#(#types,)*);
+
+            fn capture(&self) -> Self::Snapshot {
+                (#(::std::clone::Clone::clone(&self.#members),)*)
+            }
+
+            fn restore(&mut self, snapshot: Self::Snapshot) {
+                #(self.#members = snapshot.#indices;)*
+            }
+        }
+    })
+}
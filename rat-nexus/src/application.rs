@@ -1,33 +1,118 @@
 //! High‑level Application abstraction inspired by GPUI.
 
-use crate::component::traits::{Event, Action, Component, AnyComponent};
+use crate::component::binding::Binding;
+use crate::component::traits::{Event, AppEvent, CustomEvent, Action, Component, AnyComponent};
 use crate::state::{Entity, WeakEntity, EntityId};
+use crate::event_source::{CrosstermEvents, EventSource};
 use ratatui::prelude::*;
-use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event as CrosstermEvent, KeyEventKind},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use crate::backend::{BackendKind, TerminalBackend};
+use crossterm::event::{Event as CrosstermEvent, KeyEventKind};
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
-use std::io::{self, stdout};
+use std::marker::PhantomData;
 use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
-use tokio::runtime::Runtime;
+use tokio::runtime::Builder;
 use tokio::sync::mpsc;
 
 /// Type-erased storage for application-level shared state.
 type StateMap = HashMap<TypeId, Arc<dyn Any + Send + Sync>>;
 
+/// Type-erased storage for keyed application state (several values per type).
+type KeyedStateMap = HashMap<(TypeId, String), Arc<dyn Any + Send + Sync>>;
+
+/// Clone a stored state value out as `T`, or `Error::DowncastFailed` if it is not one.
+fn downcast_clone<T: Clone + 'static>(value: &Arc<dyn Any + Send + Sync>) -> crate::Result<T> {
+    value
+        .downcast_ref::<T>()
+        .cloned()
+        .ok_or(crate::Error::DowncastFailed { expected: std::any::type_name::<T>() })
+}
+
+/// Handlers for app-defined actions, by action type (see `AppContext::on_action`).
+type ActionHandlers = HashMap<TypeId, Arc<dyn Fn(&crate::component::traits::CustomAction, &AppContext) + Send + Sync>>;
+
+/// Handles the errors of `TryComponent` hooks.
+type ErrorHandler = Arc<dyn Fn(crate::component::ComponentError, &AppContext) + Send + Sync>;
+
 pub struct AppContext {
     /// The root component to render, if set by the user.
     root: Arc<Mutex<Option<Entity<dyn AnyComponent>>>>,
     /// Internal: Channel to trigger a re-render.
     re_render_tx: mpsc::UnboundedSender<()>,
+    /// Internal: Channel injecting events (app-defined or custom) into the event loop.
+    inject_tx: mpsc::UnboundedSender<Event>,
     /// Internal: Total frames rendered.
     frame_count: Arc<std::sync::atomic::AtomicU64>,
     /// Application-level shared state storage (TypeMap pattern).
     state: Arc<RwLock<StateMap>>,
+    /// Keyed shared state storage, for multiple values of the same type.
+    keyed_state: Arc<RwLock<KeyedStateMap>>,
+    /// Input latency recorder.
+    metrics: crate::metrics::Metrics,
+    /// Terminal capabilities detected at startup.
+    capabilities: crate::capabilities::Capabilities,
+    /// Canonicalizes key events before they reach components.
+    key_normalizer: Arc<RwLock<crate::keymap::KeyNormalizer>>,
+    /// Watches the refresh rate for runaway render loops.
+    storm: crate::storm::StormDetector,
+    /// Flags components whose render exceeds the time budget (debug builds).
+    budget: crate::budget::FrameBudget,
+    /// Entity subscriptions, one forwarding task per (component, entity).
+    subscriptions: crate::subscription::Subscriptions,
+    /// Interval and timeout callbacks, driven by one task.
+    timers: crate::timer::Timers,
+    /// Current UI density, stored as `Density::to_u8`.
+    density: Arc<std::sync::atomic::AtomicU8>,
+    /// Current color theme and the themes available to switch to.
+    themes: crate::theme::Themes,
+    /// Presentation mode state (key bubble, animation slowdown).
+    presentation: crate::presentation::Presentation,
+    /// Draws deferred until the component tree has rendered (popups).
+    overlays: crate::overlay::Overlays,
+    /// Images to write once the frame is flushed, and those on screen.
+    pub(crate) graphics: crate::graphics::Graphics,
+    /// Open palette and the routes/keybindings it offers.
+    pub(crate) palettes: crate::palette::Palettes,
+    /// Overlay listing captured log records.
+    log_overlay: crate::logs::LogOverlay,
+    /// Overlay showing frame rate, entities, tasks and the route.
+    pub(crate) inspector: crate::inspector::Inspector,
+    /// Generation of cached child renders (see `ChildView::cached`).
+    pub(crate) damage: crate::damage::Damage,
+    /// Recently visited routes and the quick switcher over them.
+    pub(crate) switcher: crate::switcher::Switcher,
+    /// Scans rendered frames for low-contrast text when enabled.
+    contrast: crate::contrast::ContrastAudit,
+    /// Areas of named regions in the last frame, for tests and tooling.
+    regions: Arc<RwLock<HashMap<String, Rect>>>,
+    /// Known routes with display metadata, and the one on screen.
+    routes: crate::router::registry::RouteRegistry,
+    /// Signalled on exit; critical tasks are waited for.
+    shutdown: crate::task::Shutdown,
+    /// Handlers for `Action::Dispatch` reaching the root.
+    actions: Arc<RwLock<ActionHandlers>>,
+    /// Reducers for messages sent with `dispatch`.
+    reducers: crate::dispatch::Reducers,
+    /// The open context menu, if any.
+    pub(crate) context_menus: crate::context_menu::ContextMenus,
+    /// The drag in progress, if any.
+    drags: crate::drag::Drags,
+    /// Presses and recent clicks, for `Event::MouseClick`.
+    clicks: crate::click::Clicks,
+    /// Registered key sequences and the one being typed.
+    chords: crate::keymap::chord::Chords,
+    /// The loading placeholder and the root's `on_mount_async` task.
+    pub(crate) mounts: crate::component::mount::Mounts,
+    /// Handler for `TryComponent` errors; errors are logged without one.
+    error_handler: Arc<RwLock<Option<ErrorHandler>>>,
+    /// The event type declared with `Application::<E>`, checked by `emit_app_event`.
+    app_event: Arc<RwLock<(TypeId, &'static str)>>,
+    /// Live entities and tracked sizes, for diagnostics (see `live_entities`).
+    entities: crate::state::EntityRegistry,
+    /// Live persisted entities, flushed by `save_all`.
+    #[cfg(feature = "persist")]
+    persisted: crate::persist::Persisted,
 }
 
 impl Clone for AppContext {
@@ -35,19 +120,116 @@ impl Clone for AppContext {
         Self {
             root: Arc::clone(&self.root),
             re_render_tx: mpsc::UnboundedSender::clone(&self.re_render_tx),
+            inject_tx: mpsc::UnboundedSender::clone(&self.inject_tx),
             frame_count: Arc::clone(&self.frame_count),
             state: Arc::clone(&self.state),
+            keyed_state: Arc::clone(&self.keyed_state),
+            metrics: crate::metrics::Metrics::clone(&self.metrics),
+            capabilities: self.capabilities,
+            key_normalizer: Arc::clone(&self.key_normalizer),
+            storm: self.storm.clone(),
+            budget: self.budget.clone(),
+            subscriptions: self.subscriptions.clone(),
+            timers: self.timers.clone(),
+            density: Arc::clone(&self.density),
+            themes: self.themes.clone(),
+            presentation: self.presentation.clone(),
+            overlays: self.overlays.clone(),
+            graphics: self.graphics.clone(),
+            palettes: self.palettes.clone(),
+            log_overlay: self.log_overlay.clone(),
+            inspector: self.inspector.clone(),
+            damage: self.damage.clone(),
+            switcher: self.switcher.clone(),
+            contrast: self.contrast.clone(),
+            regions: Arc::clone(&self.regions),
+            routes: self.routes.clone(),
+            shutdown: self.shutdown.clone(),
+            actions: Arc::clone(&self.actions),
+            reducers: self.reducers.clone(),
+            context_menus: self.context_menus.clone(),
+            drags: self.drags.clone(),
+            clicks: self.clicks.clone(),
+            chords: self.chords.clone(),
+            mounts: self.mounts.clone(),
+            error_handler: Arc::clone(&self.error_handler),
+            app_event: Arc::clone(&self.app_event),
+            entities: self.entities.clone(),
+            #[cfg(feature = "persist")]
+            persisted: self.persisted.clone(),
         }
     }
 }
 
 impl AppContext {
-    /// Create a new entity with the given value.
+    /// Create a fresh context along with the receiving ends of its refresh and
+    /// injected-event channels, which the event loop consumes.
+    pub(crate) fn new() -> (Self, mpsc::UnboundedReceiver<()>, mpsc::UnboundedReceiver<Event>) {
+        let (re_render_tx, re_render_rx) = mpsc::unbounded_channel();
+        let (inject_tx, inject_rx) = mpsc::unbounded_channel();
+        let cx = Self {
+            root: Arc::new(Mutex::new(None)),
+            re_render_tx,
+            inject_tx,
+            frame_count: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            state: Arc::new(RwLock::new(HashMap::new())),
+            keyed_state: Arc::new(RwLock::new(HashMap::new())),
+            metrics: crate::metrics::Metrics::default(),
+            capabilities: crate::capabilities::Capabilities::detect(),
+            key_normalizer: Arc::new(RwLock::new(crate::keymap::KeyNormalizer::detect())),
+            storm: crate::storm::StormDetector::default(),
+            budget: crate::budget::FrameBudget::default(),
+            subscriptions: crate::subscription::Subscriptions::default(),
+            timers: crate::timer::Timers::default(),
+            density: Arc::new(std::sync::atomic::AtomicU8::new(crate::density::Density::default().to_u8())),
+            themes: crate::theme::Themes::default(),
+            presentation: crate::presentation::Presentation::default(),
+            overlays: crate::overlay::Overlays::default(),
+            graphics: crate::graphics::Graphics::default(),
+            palettes: crate::palette::Palettes::default(),
+            log_overlay: crate::logs::LogOverlay::default(),
+            inspector: crate::inspector::Inspector::default(),
+            damage: crate::damage::Damage::default(),
+            switcher: crate::switcher::Switcher::default(),
+            contrast: crate::contrast::ContrastAudit::default(),
+            regions: Arc::new(RwLock::new(HashMap::new())),
+            routes: crate::router::registry::RouteRegistry::default(),
+            shutdown: crate::task::Shutdown::default(),
+            actions: Arc::new(RwLock::new(HashMap::new())),
+            reducers: crate::dispatch::Reducers::default(),
+            context_menus: crate::context_menu::ContextMenus::default(),
+            drags: crate::drag::Drags::default(),
+            clicks: crate::click::Clicks::default(),
+            chords: crate::keymap::chord::Chords::default(),
+            mounts: crate::component::mount::Mounts::default(),
+            error_handler: Arc::new(RwLock::new(None)),
+            app_event: Arc::new(RwLock::new((TypeId::of::<()>(), std::any::type_name::<()>()))),
+            entities: crate::state::EntityRegistry::default(),
+            #[cfg(feature = "persist")]
+            persisted: crate::persist::Persisted::default(),
+        };
+        (cx, re_render_rx, inject_rx)
+    }
+
+    /// Create a new entity with the given value, listed with this application's
+    /// entities whichever thread creates it.
     pub fn new_entity<T>(&self, value: T) -> Entity<T>
     where
         T: Send + Sync + 'static,
     {
-        Entity::new(value)
+        Entity::new_in(value, Some(self.entities.clone()))
+    }
+
+    /// IDs and value type names of this application's live entities (see
+    /// `new_entity`). Always empty in release builds without the `soak` feature.
+    pub fn live_entities(&self) -> Vec<(crate::state::EntityId, &'static str)> {
+        self.entities.live_entities()
+    }
+
+    /// This application's entities registered with `Entity::track_size`, with their
+    /// value type names and approximate sizes in bytes, largest first.
+    pub fn entity_sizes(&self) -> Vec<(crate::state::EntityId, &'static str, usize)> {
+        self.entities.entity_sizes()
     }
 
     /// Schedule a task to be executed later.
@@ -62,6 +244,91 @@ impl AppContext {
         });
     }
 
+    /// Run `f` every `period` on the application's timer service, until the returned
+    /// handle is cancelled. Components should prefer `Context::set_interval`, which
+    /// stops with the component. A re-render follows each tick.
+    pub fn set_interval<F>(&self, period: Duration, mut f: F) -> crate::timer::TimerHandle
+    where
+        F: FnMut(&AppContext) + Send + 'static,
+    {
+        self.timers.insert(self, None, period, Some(period), Box::new(move |app| {
+            f(app);
+            true
+        }))
+    }
+
+    /// Run `f` once after `delay`, unless the returned handle is cancelled first.
+    pub fn set_timeout<F>(&self, delay: Duration, f: F) -> crate::timer::TimerHandle
+    where
+        F: FnOnce(&AppContext) + Send + 'static,
+    {
+        let mut f = Some(f);
+        self.timers.insert(self, None, delay, None, Box::new(move |app| {
+            if let Some(f) = f.take() {
+                f(app);
+            }
+            false
+        }))
+    }
+
+    /// Fill `entity` from `fut`: it becomes `Loading` right away, then `Loaded` or
+    /// `Error`, with a redraw on each step and at spinner rate while waiting. Aborting the
+    /// returned handle sets it back to `NotLoaded`.
+    pub fn load<T, E, Fut>(&self, entity: &Entity<crate::state::Loadable<T>>, fut: Fut) -> crate::task::TaskHandle
+    where
+        T: Send + Sync + 'static,
+        E: std::fmt::Display,
+        Fut: std::future::Future<Output = Result<T, E>> + Send + 'static,
+    {
+        use crate::state::Loadable;
+
+        let _ = entity.update(|state| *state = Loadable::Loading);
+        let guard = crate::state::loadable::LoadGuard(Some(Entity::clone(entity)));
+        let app = AppContext::clone(self);
+        let join_handle = tokio::spawn(async move {
+            let guard = guard;
+            tokio::pin!(fut);
+            let mut spinner = tokio::time::interval(crate::state::loadable::SPINNER_INTERVAL);
+            let result = loop {
+                tokio::select! {
+                    result = &mut fut => break result,
+                    _ = spinner.tick() => app.refresh(),
+                }
+            };
+            guard.finish(match result {
+                Ok(value) => Loadable::Loaded(value),
+                Err(err) => Loadable::Error(err.to_string()),
+            });
+            app.refresh();
+        });
+        crate::task::TaskHandle::from_join_handle(join_handle)
+    }
+
+    /// Draw components that are still in `on_mount_async` with `draw` instead of the
+    /// default spinner.
+    ///
+    /// # Example
+    /// ```ignore
+    /// cx.set_loading_placeholder(|frame, area| {
+    ///     frame.render_widget(Paragraph::new("Connecting…").centered(), area);
+    /// });
+    /// ```
+    pub fn set_loading_placeholder(&self, draw: impl Fn(&mut Frame, Rect) + Send + Sync + 'static) {
+        self.mounts.set_placeholder(Arc::new(draw));
+        self.refresh();
+    }
+
+    /// Start a `W::default()` worker on a dedicated thread (see `worker`).
+    /// Its responses arrive as `worker::RESPONSE` custom events.
+    pub fn spawn_worker<W: crate::worker::Worker + Default>(&self) -> crate::worker::WorkerHandle<W> {
+        crate::worker::spawn(self, W::default())
+    }
+
+    /// Start `worker` on a dedicated thread (see `worker`).
+    pub fn spawn_worker_with<W: crate::worker::Worker>(&self, worker: W) -> crate::worker::WorkerHandle<W> {
+        crate::worker::spawn(self, worker)
+    }
+
     /// Spawn a task and return a handle that can be used to cancel it.
     pub fn spawn_task<F, Fut>(&self, f: F) -> crate::task::TaskHandle
     where
@@ -72,7 +339,49 @@ impl AppContext {
         let join_handle = tokio::spawn(async move {
             f(cx).await;
         });
-        crate::task::TaskHandle::new(join_handle.abort_handle())
+        crate::task::TaskHandle::from_join_handle(join_handle)
+    }
+
+    /// A token signalled when the application exits, for tasks to select on so they
+    /// can wind down instead of being dropped with the runtime.
+    pub fn shutdown_token(&self) -> crate::task::CancelToken {
+        self.shutdown.token()
+    }
+
+    /// Spawn a task the application waits for on exit (saving state, say), for up
+    /// to its shutdown grace period (see `Application::shutdown_grace`). The task should
+    /// return once `shutdown_token` is signalled.
+    pub fn spawn_critical<F, Fut>(&self, name: impl Into<String>, f: F)
+    where
+        F: FnOnce(AppContext) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let handle = self.spawn_task(f).named(name);
+        self.shutdown.register(handle);
+    }
+
+    /// Signal `shutdown_token` and wait up to `grace` for the tasks started with
+    /// `spawn_critical`, aborting any still running. `run` does this on exit; call it
+    /// when driving the application through `Application::into_parts`.
+    pub async fn shutdown(&self, grace: Duration) {
+        self.shutdown.run(grace).await;
+    }
+
+    /// Spawn a task that reports its progress, and return a handle carrying it.
+    /// Render it with `widgets::TaskProgress::new(handle.progress().unwrap())`.
+    pub fn spawn_with_progress<F, Fut>(&self, f: F) -> crate::task::TaskHandle
+    where
+        F: FnOnce(AppContext, crate::task::Progress) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let cx = AppContext::clone(self);
+        let progress = crate::task::Progress::new();
+        let reporter = progress.clone();
+        let join_handle = tokio::spawn(async move {
+            let _finished = reporter.finish_guard();
+            f(cx, reporter).await;
+        });
+        crate::task::TaskHandle::from_join_handle(join_handle).with_progress(progress)
     }
 
     /// Set the root component of the application.
@@ -96,461 +405,3268 @@ impl AppContext {
     where
         C: AnyComponent + 'static,
     {
-        let locked = Arc::new(RwLock::new(component));
-        let root = Entity::from_arc(locked as Arc<RwLock<dyn AnyComponent>>);
+        let locked = Arc::new(crate::state::RwLock::new(component));
+        let root = Entity::from_arc(locked as Arc<crate::state::RwLock<dyn AnyComponent>>);
         self.set_root_component(root)
     }
 
-    /// Trigger a re-render.
-    pub fn refresh(&self) {
-        let _ = self.re_render_tx.send(());
-    }
-
-    /// Get the total number of frames rendered.
-    pub fn frame_count(&self) -> u64 {
-        self.frame_count.load(std::sync::atomic::Ordering::Relaxed)
-    }
-
-    /// Store a value in the application state.
-    /// Use this to share state across components.
+    /// Emit an application-defined event.
+    /// The event is delivered to the root component as `Event::App` on the next loop
+    /// iteration; read it with `event.app::<E>()`. Fails
+    /// with `Error::AppEventMismatch` unless `E` is the type declared with
+    /// `Application::<E>`.
     ///
     /// # Example
     /// ```ignore
-    /// let shared = cx.new_entity(AppState::default());
-    /// cx.set(shared);  // Store for later retrieval
-    /// ```
-    pub fn set<T>(&self, value: T)
-    where
-        T: Send + Sync + 'static,
-    {
-        if let Ok(mut guard) = self.state.write() {
-            guard.insert(TypeId::of::<T>(), Arc::new(value));
-        }
-    }
-
-    /// Retrieve a value from the application state.
-    /// Returns None if the type was not previously stored.
+    /// enum MyEvent { DataLoaded(usize) }
     ///
-    /// # Example
-    /// ```ignore
-    /// let shared: Entity<AppState> = cx.get().expect("AppState not set");
+    /// cx.emit_app_event(MyEvent::DataLoaded(42))?;
     /// ```
-    pub fn get<T>(&self) -> Option<T>
+    pub fn emit_app_event<E>(&self, event: E) -> crate::Result<()>
     where
-        T: Clone + Send + Sync + 'static,
+        E: Send + Sync + 'static,
     {
-        self.state
-            .read()
-            .ok()
-            .and_then(|guard| guard.get(&TypeId::of::<T>()).cloned())
-            .and_then(|arc| arc.downcast::<T>().ok())
-            .map(|arc| (*arc).clone())
+        let (expected, name) = self.app_event.read().map(|declared| *declared).map_err(|_| crate::Error::LockPoisoned)?;
+        if expected != TypeId::of::<E>() {
+            return Err(crate::Error::AppEventMismatch { expected: name, found: std::any::type_name::<E>() });
+        }
+        let _ = self.inject_tx.send(Event::App(AppEvent::new(event)));
+        Ok(())
     }
 
-    /// Check if a type is stored in the application state.
-    pub fn has<T: 'static>(&self) -> bool {
-        self.state
-            .read()
-            .map(|guard| guard.contains_key(&TypeId::of::<T>()))
-            .unwrap_or(false)
+    /// Accept app events of type `E` from now on.
+    fn declare_app_event<E: 'static>(&self) {
+        if let Ok(mut declared) = self.app_event.write() {
+            *declared = (TypeId::of::<E>(), std::any::type_name::<E>());
+        }
     }
 
-    /// Get a value from application state, or return a default if not set.
-    /// This is safer than `get().expect()` - no panic possible.
+    /// Handle app-defined actions of type `T` that components return as
+    /// `Action::dispatch(T)` and that reach the root, replacing any earlier handler.
     ///
     /// # Example
     /// ```ignore
-    /// let shared: Entity<AppState> = cx.get_or_default();
-    /// // If AppState was never set, a default instance is created and stored
+    /// struct Save;
+    ///
+    /// cx.on_action(|_: &Save, cx| cx.spawn_critical("save", |cx| async move { save(&cx).await }));
+    /// // In a component:
+    /// KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::dispatch(Save)),
     /// ```
-    pub fn get_or_default<T>(&self) -> Option<T>
-    where
-        T: Clone + Send + Sync + 'static + Default,
-    {
-        match self.get::<T>() {
-            Some(value) => Some(value),
-            None => {
-                let default = T::default();
-                self.set(default.clone());
-                Some(default)
+    pub fn on_action<T: Any + Send + Sync>(&self, handler: impl Fn(&T, &AppContext) + Send + Sync + 'static) {
+        let handler = move |action: &crate::component::traits::CustomAction, cx: &AppContext| {
+            if let Some(action) = action.downcast_ref::<T>() {
+                handler(action, cx);
             }
+        };
+        if let Ok(mut actions) = self.actions.write() {
+            actions.insert(TypeId::of::<T>(), Arc::new(handler));
         }
     }
 
-    /// Get a value from application state, or create one using a closure if not set.
-    /// More flexible than `get_or_default()` when custom initialization is needed.
+    /// Handle the errors returned by `TryComponent` hooks, instead of logging them,
+    /// replacing any earlier handler. The handler runs on the thread of the failing
+    /// hook, often while the component is locked, so it should not touch the component.
     ///
     /// # Example
     /// ```ignore
-    /// let shared: Entity<AppState> = cx.get_or_insert_with(|| {
-    ///     AppState::new_with_config(config)
+    /// // Show the latest error in the status bar
+    /// let status = status.clone();
+    /// cx.on_error(move |error, _| {
+    ///     let _ = status.update(|status| *status = error.to_string());
     /// });
     /// ```
-    pub fn get_or_insert_with<T, F>(&self, f: F) -> Option<T>
-    where
-        T: Clone + Send + Sync + 'static,
-        F: FnOnce() -> T,
-    {
-        match self.get::<T>() {
-            Some(value) => Some(value),
-            None => {
-                let value = f();
-                self.set(value.clone());
-                Some(value)
-            }
+    pub fn on_error(&self, handler: impl Fn(crate::component::ComponentError, &AppContext) + Send + Sync + 'static) {
+        if let Ok(mut current) = self.error_handler.write() {
+            *current = Some(Arc::new(handler));
         }
     }
-}
-
-/// A specialized context passed to component methods.
-/// Inspired by GPUI's Context design - always bound to an entity.
-/// Note: For rendering area, use `frame.area()` instead.
-pub struct Context<V: ?Sized + Send + Sync> {
-    app: AppContext,
-    /// The entity this context is bound to. When the context is "cast" to another type
-    /// (for calling child components), this becomes None. Use `entity()` for self-reference
-    /// and `weak_entity()` for async operations.
-    handle: Option<WeakEntity<V>>,
-}
 
-// Deref to AppContext for convenient access to app methods
-impl<V: ?Sized + Send + Sync> std::ops::Deref for Context<V> {
-    type Target = AppContext;
-
-    fn deref(&self) -> &Self::Target {
-        &self.app
+    /// Pass a failed `define_app!` navigation in `T` to the handler set with `on_error`,
+    /// or log it, like a `TryComponent` error from the `"navigate"` hook.
+    #[doc(hidden)]
+    pub fn report_navigation_error<T: ?Sized>(&self, error: crate::Error) {
+        self.report_error(crate::component::ComponentError::new::<T>("navigate", error));
     }
-}
 
-impl<V: ?Sized + Send + Sync> Context<V> {
-    /// Create a context bound to an entity. This is the primary constructor.
-    pub fn new(app: AppContext, handle: WeakEntity<V>) -> Self {
-        Self {
-            app,
-            handle: Some(handle),
+    /// Pass a `TryComponent` error to the handler set with `on_error`, or log it.
+    pub(crate) fn report_error(&self, error: crate::component::ComponentError) {
+        let handler = self.error_handler.read().ok().and_then(|handler| handler.clone());
+        match handler {
+            Some(handler) => handler(error, self),
+            None => log::error!("{error}"),
         }
     }
 
-    /// Get a reference to the underlying AppContext.
-    /// Use this to access AppContext methods that are shadowed by Context methods
-    /// (like spawn/spawn_task for unbound async tasks).
-    pub fn app(&self) -> &AppContext {
-        &self.app
+    /// Run the handler registered for `action`. Returns `false` if there is none.
+    fn handle_action(&self, action: &crate::component::traits::CustomAction) -> bool {
+        let handler = self.actions.read().ok().and_then(|actions| actions.get(&action.payload_type()).cloned());
+        match handler {
+            Some(handler) => {
+                handler(action, self);
+                true
+            }
+            None => false,
+        }
     }
 
-    /// Subscribe to an entity's changes.
-    pub fn subscribe<T>(&mut self, entity: &Entity<T>)
-    where T: Send + Sync + 'static
+    /// Apply every message of type `M` sent with `dispatch` to `entity`. Several reducers
+    /// may handle the same message type; they run in the order registered.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let counter = app.new_entity(0);
+    /// app.reducer(&counter, |count, msg: &CounterMsg| match msg {
+    ///     CounterMsg::Increment => *count += 1,
+    ///     CounterMsg::Reset => *count = 0,
+    /// });
+    /// ```
+    pub fn reducer<M, S>(&self, entity: &Entity<S>, reduce: impl Fn(&mut S, &M) + Send + Sync + 'static)
+    where
+        M: Any + Send + Sync,
+        S: Send + Sync + 'static,
     {
-        let mut rx = entity.subscribe();
-        let tx = self.app.re_render_tx.clone();
-        tokio::spawn(async move {
-            while rx.changed().await.is_ok() {
-                let _ = tx.send(());
+        self.reducers.register(entity, reduce);
+    }
+
+    /// Send a message to the reducers registered for its type (see `dispatch`).
+    /// It is reduced on the next loop iteration, in order with other injected events.
+    ///
+    /// # Example
+    /// ```ignore
+    /// KeyCode::Char('+') => cx.dispatch(CounterMsg::Increment),
+    /// ```
+    pub fn dispatch<M: Any + Send + Sync>(&self, message: M) {
+        self.emit_custom(crate::dispatch::MESSAGE, crate::dispatch::Message::new(message));
+    }
+
+    /// Reduce `event` if it carries a dispatched message. Returns `false` for other events.
+    pub(crate) fn reduce_message(&self, event: &Event) -> bool {
+        let Event::Custom(custom) = event else { return false };
+        let Some(message) = custom.downcast_ref::<crate::dispatch::Message>() else { return false };
+        if custom.name() != crate::dispatch::MESSAGE {
+            return false;
+        }
+        self.batch(|app| {
+            if app.reducers.reduce(message) > 0 {
+                app.refresh();
             }
         });
+        true
     }
 
-    /// Watch an entity: subscribe to changes and read the current value.
-    /// This is a convenience method that combines `subscribe` and `entity.read`.
-    pub fn watch<T, F, R>(&mut self, entity: &Entity<T>, f: F) -> Option<R>
+    /// Inject a named custom event carrying an arbitrary payload.
+    /// The event is delivered to the root component as `Event::Custom`.
+    /// Use this for external event sources that don't share the app's event type.
+    ///
+    /// # Example
+    /// ```ignore
+    /// cx.emit_custom("file-changed", PathBuf::from("config.toml"));
+    ///
+    /// // In a component:
+    /// if let Some(path) = event.custom::<PathBuf>() { /* reload */ }
+    /// ```
+    pub fn emit_custom<T>(&self, name: impl Into<String>, payload: T)
     where
         T: Send + Sync + 'static,
-        F: FnOnce(&T) -> R,
     {
-        self.subscribe(entity);
-        entity.read(f).ok()
+        let _ = self.inject_tx.send(Event::Custom(CustomEvent::new(name, payload)));
     }
 
-    /// Spawn an async task with access to the entity's WeakEntity.
-    /// This is the GPUI-style spawn that automatically provides a weak reference
-    /// to the entity for safe async access.
+    /// Navigate the `define_app!` root to `route`, from anywhere.
+    /// Delivered as a `router::NAVIGATE` custom event carrying the route name.
     ///
     /// # Example
     /// ```ignore
-    /// fn save_data(&mut self, cx: &mut Context<Self>) {
-    ///     let data = self.data.clone();
-    ///     cx.spawn(|weak_self, app| async move {
-    ///         tokio::time::sleep(Duration::from_secs(1)).await;
-    ///         // Safe: if component was dropped, upgrade() returns None
-    ///         if let Some(entity) = weak_self.upgrade() {
-    ///             entity.update(|this| this.on_save_complete());
-    ///         }
-    ///         app.refresh();
-    ///     });
-    /// }
+    /// app.navigate("Settings");
     /// ```
-    ///
-    /// # Panics
-    /// Panics if the context was not created with a handle (i.e., was cast from another context).
-    pub fn spawn<F, Fut>(&self, f: F)
-    where
-        V: 'static,
-        F: FnOnce(WeakEntity<V>, AppContext) -> Fut + Send + 'static,
-        Fut: std::future::Future<Output = ()> + Send + 'static,
-    {
-        let weak = self.handle.clone()
-            .expect("Context::spawn requires a bound entity. Use AppContext::spawn for unbound contexts.");
-        let app = AppContext::clone(&self.app);
-        tokio::spawn(async move {
-            f(weak, app).await;
-        });
+    pub fn navigate(&self, route: impl Into<String>) {
+        self.emit_custom(crate::router::NAVIGATE, route.into());
     }
 
-    /// Spawn a task and return a handle that can be used to cancel it.
-    /// Use this with `TaskTracker` for proper lifecycle management.
+    /// Queue an event for delivery to the root component, as if it came from the terminal.
+    pub(crate) fn inject_event(&self, event: Event) {
+        let _ = self.inject_tx.send(event);
+    }
+
+    /// Open a fuzzy-searchable palette over `items`, drawn above everything else.
+    /// `on_pick(index, app)` runs when an item is picked; `Esc` closes without picking.
     ///
-    /// # Panics
-    /// Panics if the context was not created with a handle.
-    pub fn spawn_task<F, Fut>(&self, f: F) -> crate::task::TaskHandle
+    /// # Example
+    /// ```ignore
+    /// let files = self.files.clone();
+    /// let state = self.state.clone();
+    /// cx.open_palette(files.clone(), move |i, _| {
+    ///     let _ = state.update(|s| s.open(&files[i]));
+    /// });
+    /// ```
+    pub fn open_palette<I, F>(&self, items: impl IntoIterator<Item = I>, on_pick: F)
     where
-        V: 'static,
-        F: FnOnce(WeakEntity<V>, AppContext) -> Fut + Send + 'static,
-        Fut: std::future::Future<Output = ()> + Send + 'static,
+        I: Into<crate::palette::PaletteItem>,
+        F: FnOnce(usize, &AppContext) + Send + 'static,
     {
-        let weak = self.handle.clone()
-            .expect("Context::spawn_task requires a bound entity. Use AppContext::spawn_task for unbound contexts.");
-        let app = AppContext::clone(&self.app);
-        let join_handle = tokio::spawn(async move {
-            f(weak, app).await;
-        });
-        crate::task::TaskHandle::new(join_handle.abort_handle())
+        let palette = crate::palette::CommandPalette::new("Search", items.into_iter().map(Into::into).collect());
+        self.palettes.open(palette, Box::new(on_pick));
+        self.refresh();
     }
 
-    /// Spawn an unbound async task (no WeakEntity reference).
-    /// Use this for background tasks that don't need to access the component.
-    /// Delegates to `AppContext::spawn`.
-    pub fn spawn_detached<F, Fut>(&self, f: F)
-    where
-        F: FnOnce(AppContext) -> Fut + Send + 'static,
-        Fut: std::future::Future<Output = ()> + Send + 'static,
-    {
-        self.app.spawn(f)
+    /// Open the command palette listing registered routes and keybindings.
+    pub fn open_command_palette(&self) {
+        let (palette, on_pick) = self.palettes.commands();
+        self.palettes.open(palette, on_pick);
+        self.refresh();
     }
 
-    /// Spawn an unbound async task with cancellation handle.
-    /// Use this for background tasks that don't need to access the component.
-    /// Delegates to `AppContext::spawn_task`.
-    pub fn spawn_detached_task<F, Fut>(&self, f: F) -> crate::task::TaskHandle
-    where
-        F: FnOnce(AppContext) -> Fut + Send + 'static,
-        Fut: std::future::Future<Output = ()> + Send + 'static,
-    {
-        self.app.spawn_task(f)
+    /// Close the open palette without picking.
+    pub fn close_palette(&self) {
+        self.palettes.close();
+        self.refresh();
     }
 
-    /// Cast this context to another view type.
-    /// Note: The cast context will NOT have a handle. Use `entity.update_with_cx(cx, ...)`
-    /// pattern for proper child component lifecycle.
-    pub fn cast<U: ?Sized + Send + Sync + 'static>(&self) -> Context<U> {
+    /// Whether a palette is open.
+    pub fn palette_open(&self) -> bool {
+        self.palettes.is_open()
+    }
+
+    /// Open a context menu over `items` with its top-left corner at `position`, drawn
+    /// above everything else. Picking an item delivers a `context_menu::MenuPick` with its
+    /// id as the custom event `context_menu::MENU_PICKED`; `Esc` or a click outside closes.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Right) => {
+    ///     cx.open_context_menu((mouse.column, mouse.row), [
+    ///         MenuItem::new("rename", "Rename").hint("F2"),
+    ///         MenuItem::new("delete", "Delete"),
+    ///     ]);
+    ///     None
+    /// }
+    /// ```
+    pub fn open_context_menu(
+        &self,
+        position: impl Into<ratatui::layout::Position>,
+        items: impl IntoIterator<Item = crate::context_menu::MenuItem>,
+    ) {
+        let menu = crate::context_menu::ContextMenu::new(position, items.into_iter().collect());
+        self.context_menus.open(menu);
+        self.refresh();
+    }
+
+    /// Close the open context menu without picking.
+    pub fn close_context_menu(&self) {
+        self.context_menus.close();
+        self.refresh();
+    }
+
+    /// Whether a context menu is open.
+    pub fn context_menu_open(&self) -> bool {
+        self.context_menus.is_open()
+    }
+
+    /// Start dragging `payload`. The pointer is followed until the mouse button is
+    /// released, then the component under it receives `Event::Drop`; `Esc` cancels.
+    /// Mouse and key events go to the drag, not the component tree, until it ends.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Event::Mouse(mouse) if mouse.kind == MouseEventKind::Drag(MouseButton::Left) && !cx.dragging() => {
+    ///     if let Some(card) = self.card_at(mouse.row) {
+    ///         cx.start_drag(card.id);
+    ///     }
+    ///     None
+    /// }
+    /// // In the target column:
+    /// if let Some(id) = event.dropped::<CardId>() { self.cards.push(*id) }
+    /// ```
+    pub fn start_drag<T: Any + Send + Sync>(&self, payload: T) {
+        self.drags.start(payload, None);
+    }
+
+    /// Start dragging `payload`, showing `label` next to the pointer.
+    pub fn start_drag_labeled<T: Any + Send + Sync>(&self, payload: T, label: impl Into<String>) {
+        self.drags.start(payload, Some(label.into()));
+    }
+
+    /// Whether a drag is in progress.
+    pub fn dragging(&self) -> bool {
+        self.drags.is_active()
+    }
+
+    /// Where the pointer was last seen during the drag in progress, e.g. to highlight
+    /// the drop target.
+    pub fn drag_position(&self) -> Option<ratatui::layout::Position> {
+        self.drags.position()
+    }
+
+    /// Abandon the drag in progress without dropping.
+    pub fn cancel_drag(&self) {
+        self.drags.cancel();
+        self.refresh();
+    }
+
+    /// Set the longest gap between clicks on one cell that still counts them as a double
+    /// (or triple) click in `Event::MouseClick` (400ms by default).
+    pub fn set_double_click_interval(&self, interval: Duration) {
+        self.clicks.set_interval(interval);
+    }
+
+    /// The current double-click interval.
+    pub fn double_click_interval(&self) -> Duration {
+        self.clicks.interval()
+    }
+
+    /// Deliver `Event::Chord(name)` instead of the keys when `keys` are typed in order.
+    /// `keys` uses the notation of `Harness::type_str`: `"gd"`, `"<Space>ff"`, `"<C-x>s"`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// app.register_chord("<Space>ff", "find-file")?;
+    ///
+    /// // In a component:
+    /// Event::Chord(name) if name == "find-file" => self.open_finder(cx),
+    /// ```
+    pub fn register_chord(&self, keys: &str, name: impl Into<String>) -> crate::Result<()> {
+        self.register_chord_scoped(keys, name.into(), None)
+    }
+
+    /// Like `register_chord`, but the sequence only applies in `mode`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// app.register_chord_in(InputMode::Normal, "dd", "delete-line")?;
+    /// app.register_chord_in(InputMode::Insert, "<Esc>", "normal-mode")?;
+    /// ```
+    pub fn register_chord_in(&self, mode: crate::keymap::InputMode, keys: &str, name: impl Into<String>) -> crate::Result<()> {
+        self.register_chord_scoped(keys, name.into(), Some(mode))
+    }
+
+    fn register_chord_scoped(&self, keys: &str, name: String, mode: Option<crate::keymap::InputMode>) -> crate::Result<()> {
+        let sequence = crate::testing::parse_keys(keys)
+            .map_err(|key| crate::Error::InvalidKeySequence { keys: keys.to_string(), key })?;
+        if sequence.is_empty() {
+            return Err(crate::Error::InvalidKeySequence { keys: keys.to_string(), key: String::new() });
+        }
+        self.chords.register(sequence, name, mode);
+        Ok(())
+    }
+
+    /// Switch the app-wide input mode (see `keymap::mode`), abandoning a key sequence
+    /// in progress.
+    pub fn set_mode(&self, mode: crate::keymap::InputMode) {
+        if self.chords.set_mode(mode) {
+            self.refresh();
+        }
+    }
+
+    /// The current input mode, `InputMode::Normal` unless changed.
+    pub fn mode(&self) -> crate::keymap::InputMode {
+        self.chords.mode()
+    }
+
+    /// How long to wait for the next key of a sequence before delivering the keys
+    /// typed so far (one second by default).
+    pub fn set_chord_timeout(&self, timeout: Duration) {
+        self.chords.set_timeout(timeout);
+    }
+
+    /// The keys of the sequence being typed, e.g. `"Space f"`, or an empty string.
+    /// Status bars can show it as a pending-keys indicator.
+    pub fn pending_keys(&self) -> String {
+        let keys: Vec<String> = self.chords.pending().iter().map(crate::keymap::key_label).collect();
+        keys.join(" ")
+    }
+
+    /// Set the key that opens the command palette (`Ctrl+P` by default), or `None` to
+    /// disable it, e.g. when the app uses that key itself.
+    pub fn set_palette_key(&self, key: Option<crossterm::event::KeyEvent>) {
+        self.palettes.set_key(key);
+    }
+
+    /// Set the key opening the quick switcher (`Ctrl+E` by default); `None` disables
+    /// the key. The switcher itself is enabled with `Application::quick_switcher`.
+    pub fn set_switcher_key(&self, key: Option<crossterm::event::KeyEvent>) {
+        self.switcher.set_key(key);
+    }
+
+    /// Whether the quick switcher is open.
+    pub fn switcher_open(&self) -> bool {
+        self.switcher.is_open()
+    }
+
+    /// Set the key toggling the log overlay (`F12` by default), or `None` to disable
+    /// it. The overlay only opens once logs are captured (`Application::capture_logs`).
+    pub fn set_log_panel_key(&self, key: Option<crossterm::event::KeyEvent>) {
+        self.log_overlay.set_key(key);
+    }
+
+    /// The records captured for this application (`Application::capture_logs`), for a
+    /// `LogPanel`.
+    pub fn captured_logs(&self) -> Option<Entity<crate::logs::CapturedLogs>> {
+        self.log_overlay.logs()
+    }
+
+    /// Show or hide the log overlay.
+    pub fn set_log_panel_open(&self, open: bool) {
+        self.log_overlay.set_open(open);
+        self.refresh();
+    }
+
+    /// Whether the log overlay is shown.
+    pub fn log_panel_open(&self) -> bool {
+        self.log_overlay.is_open()
+    }
+
+    /// Set the key toggling the inspector overlay (`F9` by default), or `None` to
+    /// disable it.
+    pub fn set_inspector_key(&self, key: Option<crossterm::event::KeyEvent>) {
+        self.inspector.set_key(key);
+    }
+
+    /// Show or hide the inspector overlay.
+    pub fn set_inspector_open(&self, open: bool) {
+        self.inspector.set_open(open, self);
+        self.refresh();
+    }
+
+    /// Whether the inspector overlay is shown.
+    pub fn inspector_open(&self) -> bool {
+        self.inspector.is_open()
+    }
+
+    /// Routes visited while the quick switcher is enabled, most recent first.
+    pub fn recent_routes(&self) -> Vec<String> {
+        self.switcher.recent()
+    }
+
+    /// Offer routes in the command palette as "Go to …" entries and in navigation
+    /// widgets. `define_app!` and `AppShell` register their routes on mount.
+    pub fn register_routes<S: Into<String>>(&self, routes: impl IntoIterator<Item = S>) {
+        let routes: Vec<String> = routes.into_iter().map(Into::into).collect();
+        self.routes.register_names(&routes);
+        self.palettes.register_routes(routes);
+    }
+
+    /// Register a route with a label and icon for navigation widgets, or update the
+    /// metadata of a registered one.
+    ///
+    /// # Example
+    /// ```ignore
+    /// cx.describe_route(RouteInfo::new("Monitor").icon("📈").label("System monitor"));
+    /// ```
+    pub fn describe_route(&self, info: crate::router::RouteInfo) {
+        self.palettes.register_routes(vec![info.name.clone()]);
+        self.routes.describe(info);
+    }
+
+    /// Every registered route, in registration order.
+    pub fn routes(&self) -> Vec<crate::router::RouteInfo> {
+        self.routes.routes()
+    }
+
+    /// The route the root currently shows, as reported with `set_active_route`.
+    pub fn active_route(&self) -> Option<String> {
+        self.routes.active()
+    }
+
+    /// Report the route on screen. Called by `define_app!` and `AppShell` whenever a
+    /// route is entered; custom root dispatchers call it to keep navigation widgets in sync.
+    pub fn set_active_route(&self, name: &str) {
+        self.routes.set_active(name);
+    }
+
+    /// Announce a route change to the whole tree as a `router::NAVIGATED` custom event.
+    /// Called by `define_app!` and `AppShell` after every change; custom root
+    /// dispatchers call it to feed breadcrumbs and telemetry.
+    pub fn emit_navigation(&self, event: crate::router::NavigationEvent) {
+        self.emit_custom(crate::router::NAVIGATED, event);
+    }
+
+    /// Offer a key in the command palette; picking it delivers the key to the root
+    /// component as if it were pressed.
+    ///
+    /// # Example
+    /// ```ignore
+    /// cx.register_keybinding(KeyEvent::from(KeyCode::Char('t')), "Switch theme");
+    /// ```
+    pub fn register_keybinding(&self, key: crossterm::event::KeyEvent, description: impl Into<String>) {
+        self.palettes.register_keybinding(key, description.into());
+    }
+
+    /// Trigger a re-render.
+    /// Inside `batch`, the request is deferred and coalesced with the others.
+    pub fn refresh(&self) {
+        self.storm.record(None);
+        if !crate::state::batch::defer_refresh(&self.re_render_tx) {
+            let _ = self.re_render_tx.send(());
+        }
+    }
+
+    /// Run a closure with all entity notifications and refreshes deferred until it completes.
+    /// Each changed entity notifies its subscribers once and a single re-render is requested,
+    /// no matter how many updates happened inside the closure. Batches may nest.
+    ///
+    /// # Example
+    /// ```ignore
+    /// cx.batch(|_| {
+    ///     let _ = state.update(|s| s.counter += 1);
+    ///     let _ = state.update(|s| s.history.push(s.counter));
+    ///     let _ = stats.update(|s| s.clicks += 1);
+    /// }); // one notification per entity, one frame
+    /// ```
+    pub fn batch<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&AppContext) -> R,
+    {
+        let _guard = crate::state::batch::begin();
+        f(self)
+    }
+
+    /// Drop the cached renders of every `ChildView::cached` child, so the next frame
+    /// draws the whole tree, e.g. after changing state the children do not subscribe to.
+    /// Resizing the terminal does this automatically.
+    pub fn invalidate_all(&self) {
+        self.damage.invalidate_all();
+        self.refresh();
+    }
+
+    /// Load settings of type `T` from `loader`'s files, environment and arguments, and
+    /// keep them as an entity reachable with `config::<T>()` (see `config`).
+    #[cfg(feature = "config")]
+    pub fn load_config<T: crate::config::Config>(&self, loader: crate::config::ConfigLoader) -> std::io::Result<Entity<T>> {
+        let store = crate::config::ConfigStore::<T>::load(loader)?;
+        let entity = Entity::clone(&store.entity);
+        self.set(store);
+        Ok(entity)
+    }
+
+    /// The settings loaded with `load_config` or `Application::config`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let settings = cx.config::<Settings>().expect("loaded at startup");
+    /// cx.subscribe(&settings);
+    /// ```
+    #[cfg(feature = "config")]
+    pub fn config<T: crate::config::Config>(&self) -> Option<Entity<T>> {
+        self.get::<crate::config::ConfigStore<T>>().ok().map(|store| store.entity)
+    }
+
+    /// Write the settings of type `T` changed since they were loaded to the user's
+    /// configuration file, keeping the other keys it has.
+    #[cfg(feature = "config")]
+    pub fn save_config<T: crate::config::Config>(&self) -> std::io::Result<()> {
+        let store = self
+            .get::<crate::config::ConfigStore<T>>()
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::NotFound, "configuration was not loaded"))?;
+        store.save()
+    }
+
+    /// Command-line arguments left after the framework flags (see `cli`); empty unless
+    /// the app was started with `Application::run_with_args` or `cli_args`.
+    pub fn args(&self) -> Vec<String> {
+        self.get::<crate::cli::CliArgs>().map(|cli| cli.rest).unwrap_or_default()
+    }
+
+    /// Draw the frame rate in the top-right corner of every frame (`--fps`).
+    pub fn set_fps_overlay(&self, enabled: bool) {
+        self.metrics.set_overlay(enabled);
+        self.refresh();
+    }
+
+    /// Get the total number of frames rendered.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Get input-to-frame latency statistics (p50/p99) for the running application.
+    pub fn metrics(&self) -> crate::metrics::MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    /// Count recent frames by render time, one entry per `metrics::FRAME_BUCKETS`
+    /// bound plus one for slower frames.
+    pub fn frame_histogram(&self) -> [usize; crate::metrics::FRAME_BUCKETS.len() + 1] {
+        self.metrics.frame_histogram()
+    }
+
+    /// Get the capabilities of the terminal the application runs in.
+    /// Use this to adapt rendering, e.g. `.marker(cx.capabilities().canvas_marker())`.
+    pub fn capabilities(&self) -> crate::capabilities::Capabilities {
+        self.capabilities
+    }
+
+    /// Get the render storm detector.
+    /// Storms are always logged; enable the on-screen banner or tune the threshold here.
+    ///
+    /// # Example
+    /// ```ignore
+    /// cx.storm_detector().set_threshold(500);
+    /// cx.storm_detector().set_overlay(cfg!(debug_assertions));
+    /// ```
+    pub fn storm_detector(&self) -> &crate::storm::StormDetector {
+        &self.storm
+    }
+
+    /// Get the render time budget monitor.
+    /// In debug builds, components rendering slower than the budget are logged; tune
+    /// the budget or enable the on-screen banner here.
+    ///
+    /// # Example
+    /// ```ignore
+    /// cx.frame_budget().set_budget(Duration::from_millis(8));
+    /// cx.frame_budget().set_overlay(true);
+    /// ```
+    pub fn frame_budget(&self) -> &crate::budget::FrameBudget {
+        &self.budget
+    }
+
+    /// Get the contrast auditor.
+    /// When enabled, rendered frames are checked for low-contrast text; findings are
+    /// logged and available from `issues()`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// cx.contrast_audit().set_enabled(cfg!(debug_assertions));
+    /// cx.contrast_audit().set_overlay(true);
+    /// ```
+    pub fn contrast_audit(&self) -> &crate::contrast::ContrastAudit {
+        &self.contrast
+    }
+
+    /// Get the current UI density.
+    pub fn density(&self) -> crate::density::Density {
+        crate::density::Density::from_u8(self.density.load(std::sync::atomic::Ordering::Relaxed))
+    }
+
+    /// Change the UI density at runtime.
+    /// Re-renders and emits a `density::DENSITY_CHANGED` custom event carrying the new value.
+    ///
+    /// # Example
+    /// ```ignore
+    /// KeyCode::Char('z') => cx.set_density(cx.density().next()),
+    /// ```
+    pub fn set_density(&self, density: crate::density::Density) {
+        let previous = self.density.swap(density.to_u8(), std::sync::atomic::Ordering::Relaxed);
+        if previous != density.to_u8() {
+            self.emit_custom(crate::density::DENSITY_CHANGED, density);
+            self.damage.invalidate_all();
+            self.refresh();
+        }
+    }
+
+    /// Get the current color theme.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let theme = cx.theme();
+    /// let title = Paragraph::new("Inbox").style(theme.title());
+    /// ```
+    pub fn theme(&self) -> crate::theme::Theme {
+        self.themes.current()
+    }
+
+    /// Switch to `theme`, registering it if no available theme has its name.
+    /// Redraws everything and emits a `theme::THEME_CHANGED` custom event carrying it.
+    pub fn set_theme(&self, theme: crate::theme::Theme) {
+        if self.themes.find(&theme.name).is_none() {
+            self.themes.register(theme.clone());
+        }
+        if self.themes.set(theme.clone()) {
+            self.theme_changed(theme);
+        }
+    }
+
+    /// Switch to the available theme named `name`; returns false if there is none.
+    pub fn select_theme(&self, name: &str) -> bool {
+        let Some(theme) = self.themes.find(name) else { return false };
+        self.set_theme(theme);
+        true
+    }
+
+    /// Switch to the next available theme, wrapping around.
+    ///
+    /// # Example
+    /// ```ignore
+    /// KeyCode::Char('t') => cx.next_theme(),
+    /// ```
+    pub fn next_theme(&self) {
+        if let Some(theme) = self.themes.cycle(1) {
+            self.set_theme(theme);
+        }
+    }
+
+    /// Switch to the previous available theme, wrapping around.
+    pub fn prev_theme(&self) {
+        if let Some(theme) = self.themes.cycle(-1) {
+            self.set_theme(theme);
+        }
+    }
+
+    /// Make a user-defined theme available to `select_theme` and `next_theme`.
+    /// A theme with the same name as an available one replaces it, and is applied
+    /// immediately if that theme is active.
+    pub fn register_theme(&self, theme: crate::theme::Theme) {
+        if let Some(current) = self.themes.register(theme) {
+            self.theme_changed(current);
+        }
+    }
+
+    /// The themes available to switch to: the built-in ones, then those registered.
+    pub fn themes(&self) -> Vec<crate::theme::Theme> {
+        self.themes.available()
+    }
+
+    /// Register the themes defined in the file at `path` and switch to the first one
+    /// (see `theme` for the file format).
+    #[cfg(feature = "theme-files")]
+    pub fn load_theme_file(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        crate::theme::apply_file(self, path.as_ref())
+    }
+
+    /// Reload the theme file at `path` whenever it changes, until the returned handle
+    /// is cancelled. Files that fail to parse are logged and skipped.
+    #[cfg(feature = "theme-files")]
+    pub fn watch_theme_file(&self, path: impl Into<std::path::PathBuf>) -> crate::task::TaskHandle {
+        crate::theme::watch_file(self, path.into())
+    }
+
+    fn theme_changed(&self, theme: crate::theme::Theme) {
+        self.emit_custom(crate::theme::THEME_CHANGED, theme);
+        self.damage.invalidate_all();
+        self.refresh();
+    }
+
+    /// Turn presentation mode on or off.
+    /// Shows recently pressed keys in an overlay bubble, slows animations that use
+    /// `animation_interval`, hides debug chrome and switches to `Density::Spacious`
+    /// (restoring the previous density when turned off).
+    ///
+    /// # Example
+    /// ```ignore
+    /// KeyCode::F(9) => cx.set_presentation_mode(!cx.presentation_mode()),
+    /// ```
+    pub fn set_presentation_mode(&self, enabled: bool) {
+        if let Some(density) = self.presentation.set_enabled(enabled, self.density()) {
+            self.set_density(density);
+        }
+        self.refresh();
+    }
+
+    /// Whether presentation mode is on.
+    pub fn presentation_mode(&self) -> bool {
+        self.presentation.is_enabled()
+    }
+
+    /// Set how much presentation mode slows animations (2.0 = half speed). Defaults to 1.0.
+    pub fn set_presentation_slowdown(&self, factor: f64) {
+        self.presentation.set_slowdown(factor);
+    }
+
+    /// Scale an animation tick interval for presentation mode.
+    ///
+    /// # Example
+    /// ```ignore
+    /// tokio::time::sleep(app.animation_interval(Duration::from_millis(33))).await;
+    /// ```
+    pub fn animation_interval(&self, interval: Duration) -> Duration {
+        self.presentation.scale(interval)
+    }
+
+    /// Draw `f` on top of the frame once the whole component tree has rendered.
+    /// Call this from `render` for popups that must not be covered by siblings.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let menu = self.menu_widget();
+    /// cx.overlay(move |frame| frame.render_widget(menu, rect));
+    /// ```
+    pub fn overlay<F>(&self, f: F)
+    where
+        F: FnOnce(&mut Frame) + Send + 'static,
+    {
+        self.overlays.push(Box::new(f));
+    }
+
+    /// The area a named region was rendered into in the last frame.
+    /// Children mounted with `mount_child` are named after their type in snake_case
+    /// (`ChildView<GameBoard>` is `"game_board"`); others can be named with `cx.name_area`.
+    pub fn region(&self, name: &str) -> Option<Rect> {
+        self.regions.read().ok()?.get(name).copied()
+    }
+
+    /// Names of the regions rendered in the last frame.
+    pub(crate) fn region_names(&self) -> Vec<String> {
+        self.regions.read().map(|regions| regions.keys().cloned().collect()).unwrap_or_default()
+    }
+
+    /// Named regions of the last frame with their areas.
+    pub(crate) fn regions(&self) -> Vec<(String, Rect)> {
+        self.regions
+            .read()
+            .map(|regions| regions.iter().map(|(name, area)| (name.clone(), *area)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Forget the regions of the previous frame.
+    pub(crate) fn clear_regions(&self) {
+        if let Ok(mut regions) = self.regions.write() {
+            regions.clear();
+        }
+    }
+
+    /// Run the draws queued with `overlay`.
+    pub(crate) fn render_overlays(&self, frame: &mut Frame) {
+        self.overlays.render(frame);
+    }
+
+    /// Queue an encoded image to be written over `area` once the frame is flushed.
+    /// The caller marks the cells of `area` as skipped so ratatui leaves them alone.
+    pub(crate) fn place_graphic(&self, area: Rect, image: crate::graphics::Encoded) {
+        self.graphics.place(area, image);
+    }
+
+    /// Settle which queued images to write; call last while drawing the frame.
+    pub(crate) fn prepare_graphics(&self, frame: &mut Frame) {
+        self.graphics.prepare(frame);
+    }
+
+    /// Whether debug chrome (diagnostic banners, overlays) should be drawn.
+    /// False while presentation mode is on.
+    pub fn show_debug_chrome(&self) -> bool {
+        !self.presentation.is_enabled()
+    }
+
+    /// Number of live entity subscriptions, for leak diagnostics.
+    pub fn subscription_count(&self) -> usize {
+        self.subscriptions.len()
+    }
+
+    /// Number of pending timers, for leak diagnostics.
+    pub fn timer_count(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// Get the terminal profile used to normalize key events.
+    pub fn terminal_profile(&self) -> crate::keymap::TerminalProfile {
+        self.key_normalizer
+            .read()
+            .map(|n| n.profile())
+            .unwrap_or(crate::keymap::TerminalProfile::Generic)
+    }
+
+    /// Remap a key combination before it reaches components, e.g. to work around
+    /// a terminal that encodes a chord differently.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use crossterm::event::{KeyCode, KeyModifiers};
+    /// cx.remap_key((KeyCode::F(13), KeyModifiers::NONE), (KeyCode::F(1), KeyModifiers::SHIFT));
+    /// ```
+    pub fn remap_key(
+        &self,
+        from: (crossterm::event::KeyCode, crossterm::event::KeyModifiers),
+        to: (crossterm::event::KeyCode, crossterm::event::KeyModifiers),
+    ) {
+        if let Ok(mut normalizer) = self.key_normalizer.write() {
+            normalizer.remap(from, to);
+        }
+    }
+
+    /// Apply the detected terminal's compatibility remaps (see
+    /// `TerminalProfile::remaps`), e.g. Ctrl+J as Ctrl+Enter under tmux. Off by default,
+    /// since each remap hides the key it rewrites from components.
+    pub fn enable_terminal_remaps(&self) {
+        if let Ok(mut normalizer) = self.key_normalizer.write() {
+            normalizer.enable_profile_remaps();
+        }
+    }
+
+    /// Create an entity backed by a JSON file (see `persist`).
+    /// The value is loaded from `path` if it exists, otherwise `T::default()` is used. A
+    /// file that does not deserialize is moved to `<path>.bak` before starting from the
+    /// default. Every update schedules a debounced save. Fails outside a tokio runtime or
+    /// when the file cannot be read or moved aside.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let settings = cx.persisted::<Settings>("settings.json")?;
+    /// ```
+    #[cfg(feature = "persist")]
+    pub fn persisted<T>(&self, path: impl Into<std::path::PathBuf>) -> crate::Result<Entity<T>>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + Default + Send + Sync + 'static,
+    {
+        self.persisted.entity(path.into())
+    }
+
+    /// Flush every live entity persisted through this application (see `persisted`) to
+    /// disk. Called automatically when the application shuts down.
+    #[cfg(feature = "persist")]
+    pub fn save_all(&self) -> crate::Result<()> {
+        self.persisted.save_all()
+    }
+
+    /// Store a value in the application state.
+    /// Use this to share state across components.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let shared = cx.new_entity(AppState::default());
+    /// cx.set(shared);  // Store for later retrieval
+    /// ```
+    pub fn set<T>(&self, value: T)
+    where
+        T: Send + Sync + 'static,
+    {
+        if let Ok(mut guard) = self.state.write() {
+            guard.insert(TypeId::of::<T>(), Arc::new(value));
+        }
+    }
+
+    /// Retrieve a value from the application state.
+    /// Returns `Error::StateMissing` if the type was not previously stored.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let shared: Entity<AppState> = cx.get()?;
+    /// ```
+    pub fn get<T>(&self) -> crate::Result<T>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let guard = self.state.read().map_err(|_| crate::Error::LockPoisoned)?;
+        let value = guard
+            .get(&TypeId::of::<T>())
+            .ok_or(crate::Error::StateMissing { type_name: std::any::type_name::<T>() })?;
+        downcast_clone(value)
+    }
+
+    /// Check if a type is stored in the application state.
+    pub fn has<T: 'static>(&self) -> bool {
+        self.state
+            .read()
+            .map(|guard| guard.contains_key(&TypeId::of::<T>()))
+            .unwrap_or(false)
+    }
+
+    /// Get a value from application state, or return a default if not set.
+    /// This is safer than `get().expect()` - no panic possible.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let shared: Entity<AppState> = cx.get_or_default();
+    /// // If AppState was never set, a default instance is created and stored
+    /// ```
+    pub fn get_or_default<T>(&self) -> T
+    where
+        T: Clone + Send + Sync + 'static + Default,
+    {
+        self.get_or_insert_with(T::default)
+    }
+
+    /// Get a value from application state, or create one using a closure if not set.
+    /// More flexible than `get_or_default()` when custom initialization is needed.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let shared: Entity<AppState> = cx.get_or_insert_with(|| {
+    ///     AppState::new_with_config(config)
+    /// });
+    /// ```
+    pub fn get_or_insert_with<T, F>(&self, f: F) -> T
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> T,
+    {
+        match self.get::<T>() {
+            Ok(value) => value,
+            Err(_) => {
+                let value = f();
+                self.set(value.clone());
+                value
+            }
+        }
+    }
+
+    /// Store a value in the application state under a key.
+    /// Unlike `set`, several values of the same type can coexist under different keys.
+    ///
+    /// # Example
+    /// ```ignore
+    /// cx.set_keyed("primary", cx.new_entity(Connection::open(primary_url)));
+    /// cx.set_keyed("replica", cx.new_entity(Connection::open(replica_url)));
+    /// ```
+    pub fn set_keyed<T>(&self, key: impl Into<String>, value: T)
+    where
+        T: Send + Sync + 'static,
+    {
+        if let Ok(mut guard) = self.keyed_state.write() {
+            guard.insert((TypeId::of::<T>(), key.into()), Arc::new(value));
+        }
+    }
+
+    /// Retrieve a keyed value from the application state.
+    /// Returns `Error::StateMissing` if no value of this type was stored under the key.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let replica: Entity<Connection> = cx.get_keyed("replica")?;
+    /// ```
+    pub fn get_keyed<T>(&self, key: &str) -> crate::Result<T>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let guard = self.keyed_state.read().map_err(|_| crate::Error::LockPoisoned)?;
+        let value = guard
+            .get(&(TypeId::of::<T>(), key.to_string()))
+            .ok_or(crate::Error::StateMissing { type_name: std::any::type_name::<T>() })?;
+        downcast_clone(value)
+    }
+
+    /// Check if a value of this type is stored under the key.
+    pub fn has_keyed<T: 'static>(&self, key: &str) -> bool {
+        self.keyed_state
+            .read()
+            .map(|guard| guard.contains_key(&(TypeId::of::<T>(), key.to_string())))
+            .unwrap_or(false)
+    }
+
+    /// Remove a keyed value. Returns true if a value was removed.
+    pub fn remove_keyed<T: 'static>(&self, key: &str) -> bool {
+        self.keyed_state
+            .write()
+            .map(|mut guard| guard.remove(&(TypeId::of::<T>(), key.to_string())).is_some())
+            .unwrap_or(false)
+    }
+
+    /// List the keys under which values of this type are stored, sorted.
+    pub fn keys<T: 'static>(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.keyed_state
+            .read()
+            .map(|guard| {
+                guard.keys()
+                    .filter(|(type_id, _)| *type_id == TypeId::of::<T>())
+                    .map(|(_, key)| key.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        keys.sort();
+        keys
+    }
+}
+
+/// A specialized context passed to component methods.
+/// Inspired by GPUI's Context design - always bound to an entity.
+/// Note: For rendering area, use `frame.area()` instead.
+pub struct Context<V: ?Sized + Send + Sync> {
+    app: AppContext,
+    /// The entity this context is bound to. When the context is "cast" to another type
+    /// (for calling child components), this becomes None. Use `entity()` for self-reference
+    /// and `weak_entity()` for async operations.
+    handle: Option<WeakEntity<V>>,
+    /// How observers, timers and tasks reach the component when there is no entity of
+    /// type `V`: the root (held as `dyn AnyComponent`), pages and `ChildView` children.
+    binding: Option<Binding>,
+    /// Area assigned by the parent when rendered as a child (see `ChildView`).
+    area: Option<Rect>,
+    /// Styles pushed by this component and inherited from its ancestors, outermost first.
+    styles: Vec<Style>,
+    /// Trackers of the cached children being rendered, collecting what they subscribe to.
+    trackers: Vec<crate::damage::DependencyTracker>,
+}
+
+// Deref to AppContext for convenient access to app methods
+impl<V: ?Sized + Send + Sync> std::ops::Deref for Context<V> {
+    type Target = AppContext;
+
+    fn deref(&self) -> &Self::Target {
+        &self.app
+    }
+}
+
+impl<V: ?Sized + Send + Sync> Context<V> {
+    /// Create a context bound to an entity. This is the primary constructor.
+    pub fn new(app: AppContext, handle: WeakEntity<V>) -> Self {
+        Self {
+            app,
+            handle: Some(handle),
+            binding: None,
+            area: None,
+            styles: Vec::new(),
+            trackers: Vec::new(),
+        }
+    }
+
+    /// Get a reference to the underlying AppContext.
+    /// Use this to access AppContext methods that are shadowed by Context methods
+    /// (like spawn/spawn_task for unbound async tasks).
+    pub fn app(&self) -> &AppContext {
+        &self.app
+    }
+
+    /// Subscribe to an entity's changes, re-rendering whenever it is updated.
+    ///
+    /// Idempotent: calling this every frame from `render` registers a single subscription
+    /// per (component, entity) pair. The subscription ends when either the entity or the
+    /// subscribing component is dropped. Contexts not bound to a component (from `cast`)
+    /// share one subscription per entity that lives as long as the entity. Accepts an
+    /// `Entity` or a `Derived` value.
+    pub fn subscribe<S>(&mut self, source: &S)
+    where S: crate::state::Observable
+    {
+        let entity = crate::state::sealed::Sealed::entity(source);
+        let owner = match &self.handle {
+            Some(handle) => match handle.subscribe() {
+                Some(owner_rx) => Some((handle.entity_id(), owner_rx)),
+                None => return, // the subscribing component is already gone
+            },
+            None => None,
+        };
+        let source = entity.entity_id();
+        for tracker in &self.trackers {
+            tracker.record(source, entity.version_probe(), entity.version());
+        }
+        let key = (owner.as_ref().map(|(id, _)| *id), source, crate::subscription::Kind::Refresh);
+        let tx = self.app.re_render_tx.clone();
+        let storm = self.app.storm.clone();
+        self.app.subscriptions.insert_with(key, || {
+            let mut rx = entity.subscribe();
+            let mut owner_rx = owner.map(|(_, rx)| rx);
+            async move {
+                loop {
+                    tokio::select! {
+                        changed = rx.changed() => {
+                            if changed.is_err() {
+                                break;
+                            }
+                            storm.record(Some(source));
+                            if tx.send(()).is_err() {
+                                break;
+                            }
+                        }
+                        _ = crate::subscription::closed(owner_rx.as_mut()) => break,
+                    }
+                }
+            }
+        });
+    }
+
+    /// Run `f` on this component whenever `source` (an `Entity` or `Derived`) changes
+    /// (GPUI-style observe).
+    ///
+    /// The callback is keyed by this component's `EntityId`, so registering the same
+    /// observation again is a no-op. It is removed automatically when this component or
+    /// the entity is dropped, or when `on_exit` fires. A re-render is requested after
+    /// each callback.
+    ///
+    /// Works in every context the framework hands to a component: the root, `define_app!`
+    /// pages and `ChildView` children (whose callbacks run when the child is next rendered
+    /// or receives an event). Contexts made with `cast` for another component have none
+    /// to call back into and fail with `Error::UnboundContext`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// fn on_mount(&mut self, cx: &mut Context<Self>) {
+    ///     let settings = self.settings.clone();
+    ///     let observed = cx.observe(&self.settings, move |this, _cx| {
+    ///         this.theme = settings.read(|s| s.theme).unwrap_or_default();
+    ///     });
+    ///     if let Err(error) = observed {
+    ///         log::warn!("{error}");
+    ///     }
+    /// }
+    /// ```
+    pub fn observe<S, F>(&mut self, source: &S, f: F) -> crate::Result<()>
+    where
+        V: Sized + 'static,
+        S: crate::state::Observable,
+        F: FnMut(&mut V, &mut Context<V>) + Send + 'static,
+    {
+        let entity = crate::state::sealed::Sealed::entity(source);
+        let binding = self.binding()?;
+        let mut owner_rx = binding.alive();
+        let key = (Some(binding.owner()), entity.entity_id(), crate::subscription::Kind::Observe);
+        let app = AppContext::clone(&self.app);
+        let mut callback = binding.repeated(f);
+        self.app.subscriptions.insert_with(key, || {
+            let mut rx = entity.subscribe();
+            async move {
+                loop {
+                    tokio::select! {
+                        changed = rx.changed() => {
+                            if changed.is_err() {
+                                break;
+                            }
+                            if !callback(&app) {
+                                break;
+                            }
+                            app.refresh();
+                        }
+                        _ = crate::subscription::closed(Some(&mut owner_rx)) => break,
+                    }
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Remove every subscription, observer and timer registered by this component.
+    /// Called automatically after `on_exit` for components driven by the framework.
+    pub fn unsubscribe_all(&self) {
+        if let Some(id) = self.owner() {
+            self.app.subscriptions.remove_owner(id);
+            self.app.timers.remove_owner(id);
+        }
+    }
+
+    /// Run `f` on this component every `period`, from the application's timer service
+    /// instead of a sleep loop of its own. The timer stops when the component exits or
+    /// is dropped, or when the returned handle is cancelled. A re-render follows each tick.
+    ///
+    /// Fails with `Error::UnboundContext` on contexts with no component to call back
+    /// into (see `observe`).
+    ///
+    /// # Example
+    /// ```ignore
+    /// fn on_enter(&mut self, cx: &mut Context<Self>) {
+    ///     self.ticker = cx.set_interval(Duration::from_secs(1), |this, _cx| this.now = Local::now()).ok();
+    /// }
+    /// ```
+    pub fn set_interval<F>(&self, period: Duration, f: F) -> crate::Result<crate::timer::TimerHandle>
+    where
+        V: Sized + 'static,
+        F: FnMut(&mut V, &mut Context<V>) + Send + 'static,
+    {
+        self.owned_timer(period, Some(period), f)
+    }
+
+    /// Run `f` on this component once after `delay`. Cancelled like `set_interval`.
+    pub fn set_timeout<F>(&self, delay: Duration, f: F) -> crate::Result<crate::timer::TimerHandle>
+    where
+        V: Sized + 'static,
+        F: FnOnce(&mut V, &mut Context<V>) + Send + 'static,
+    {
+        let mut f = Some(f);
+        self.owned_timer(delay, None, move |this, cx| {
+            if let Some(f) = f.take() {
+                f(this, cx);
+            }
+        })
+    }
+
+    fn owned_timer<F>(&self, delay: Duration, period: Option<Duration>, f: F) -> crate::Result<crate::timer::TimerHandle>
+    where
+        V: Sized + 'static,
+        F: FnMut(&mut V, &mut Context<V>) + Send + 'static,
+    {
+        let binding = self.binding()?;
+        let owner = Some(binding.owner());
+        let mut callback = binding.repeated(f);
+        Ok(self.app.timers.insert(&self.app, owner, delay, period, Box::new(move |app| {
+            callback(app) && period.is_some()
+        })))
+    }
+
+    /// Watch an entity: subscribe to changes and read the current value.
+    /// This is a convenience method that combines `subscribe` and `entity.read`.
+    pub fn watch<T, F, R>(&mut self, entity: &Entity<T>, f: F) -> Option<R>
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(&T) -> R,
+    {
+        self.subscribe(entity);
+        entity.read(f).ok()
+    }
+
+    /// Spawn an async task with access to the entity's WeakEntity.
+    /// This is the GPUI-style spawn that automatically provides a weak reference
+    /// to the entity for safe async access.
+    ///
+    /// # Example
+    /// ```ignore
+    /// fn save_data(&mut self, cx: &mut Context<Self>) {
+    ///     let data = self.data.clone();
+    ///     cx.spawn(|weak_self, app| async move {
+    ///         tokio::time::sleep(Duration::from_secs(1)).await;
+    ///         // Safe: if component was dropped, upgrade() returns None
+    ///         if let Some(entity) = weak_self.upgrade() {
+    ///             entity.update(|this| this.on_save_complete());
+    ///         }
+    ///         app.refresh();
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the context was not created with a handle (i.e., was cast from another context).
+    pub fn spawn<F, Fut>(&self, f: F)
+    where
+        V: 'static,
+        F: FnOnce(WeakEntity<V>, AppContext) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let weak = self.handle.clone()
+            .expect("Context::spawn requires a bound entity. Use AppContext::spawn for unbound contexts.");
+        let app = AppContext::clone(&self.app);
+        tokio::spawn(async move {
+            f(weak, app).await;
+        });
+    }
+
+    /// Spawn a task and return a handle that can be used to cancel it.
+    /// Use this with `TaskTracker` for proper lifecycle management.
+    ///
+    /// # Panics
+    /// Panics if the context was not created with a handle.
+    pub fn spawn_task<F, Fut>(&self, f: F) -> crate::task::TaskHandle
+    where
+        V: 'static,
+        F: FnOnce(WeakEntity<V>, AppContext) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let weak = self.handle.clone()
+            .expect("Context::spawn_task requires a bound entity. Use AppContext::spawn_task for unbound contexts.");
+        let app = AppContext::clone(&self.app);
+        let join_handle = tokio::spawn(async move {
+            f(weak, app).await;
+        });
+        crate::task::TaskHandle::from_join_handle(join_handle)
+    }
+
+    /// Spawn a task that reports its progress, and return a handle carrying it. Its
+    /// output is handed to `on_done` with this component, like `spawn_result`.
+    ///
+    /// Fails with `Error::UnboundContext` on contexts with no component to call back
+    /// into (see `observe`).
+    ///
+    /// # Example
+    /// ```ignore
+    /// let handle = cx.spawn_with_progress(
+    ///     |_, progress| async move {
+    ///         for (i, file) in files.iter().enumerate() {
+    ///             progress.message(file.display().to_string());
+    ///             copy(file).await;
+    ///             progress.report((i + 1) as f64 / files.len() as f64);
+    ///         }
+    ///     },
+    ///     |this, (), _cx| this.copied = true,
+    /// )?;
+    /// self.gauge = ChildView::new(TaskProgress::new(handle.progress().unwrap()));
+    /// self.tasks.track(handle);
+    /// ```
+    pub fn spawn_with_progress<F, Fut, R, D>(&self, f: F, on_done: D) -> crate::Result<crate::task::TaskHandle>
+    where
+        V: Sized + 'static,
+        F: FnOnce(AppContext, crate::task::Progress) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = R> + Send + 'static,
+        R: Send + 'static,
+        D: FnOnce(&mut V, R, &mut Context<V>) + Send + 'static,
+    {
+        let binding = self.binding()?;
+        Ok(self.app.spawn_with_progress(move |app, progress| async move {
+            let result = f(AppContext::clone(&app), progress).await;
+            if binding.update(&app, move |this, cx| on_done(this, result, cx)) {
+                app.refresh();
+            }
+        }))
+    }
+
+    /// Run `fut` in the background and hand its output to this component: `on_done`
+    /// gets the component, the result and a context once the future completes, and a
+    /// re-render follows. Nothing runs if the component was dropped in the meantime or
+    /// the returned handle was aborted.
+    ///
+    /// Fails with `Error::UnboundContext` on contexts with no component to call back
+    /// into (see `observe`).
+    ///
+    /// # Example
+    /// ```ignore
+    /// let client = self.client.clone();
+    /// let handle = cx.spawn_result(async move { client.fetch_users().await }, |this, result, _cx| {
+    ///     match result {
+    ///         Ok(users) => this.users = users,
+    ///         Err(e) => this.error = Some(e.to_string()),
+    ///     }
+    /// })?;
+    /// self.tasks.track(handle);
+    /// ```
+    pub fn spawn_result<Fut, R, F>(&self, fut: Fut, on_done: F) -> crate::Result<crate::task::TaskHandle>
+    where
+        V: Sized + 'static,
+        Fut: std::future::Future<Output = R> + Send + 'static,
+        R: Send + 'static,
+        F: FnOnce(&mut V, R, &mut Context<V>) + Send + 'static,
+    {
+        let binding = self.binding()?;
+        let app = AppContext::clone(&self.app);
+        let join_handle = tokio::spawn(async move {
+            let result = fut.await;
+            if binding.update(&app, move |this, cx| on_done(this, result, cx)) {
+                app.refresh();
+            }
+        });
+        Ok(crate::task::TaskHandle::from_join_handle(join_handle))
+    }
+
+    /// The task to return from `Component::on_mount_async`: run `fut` in the background,
+    /// then hand its output to `apply` with the component before its first render.
+    ///
+    /// # Example
+    /// ```ignore
+    /// fn on_mount_async(&mut self, cx: &mut Context<Self>) -> Option<MountTask<Self>> {
+    ///     let client = self.client.clone();
+    ///     cx.mount_async(async move { client.fetch_users().await }, |this, users, _cx| {
+    ///         this.users = users.unwrap_or_default();
+    ///     })
+    /// }
+    /// ```
+    pub fn mount_async<Fut, R, F>(&self, fut: Fut, apply: F) -> Option<crate::component::MountTask<V>>
+    where
+        V: 'static,
+        Fut: std::future::Future<Output = R> + Send + 'static,
+        R: Send + 'static,
+        F: FnOnce(&mut V, R, &mut Context<V>) + Send + 'static,
+    {
+        Some(crate::component::MountTask::new(fut, apply))
+    }
+
+    /// Spawn an unbound async task (no WeakEntity reference).
+    /// Use this for background tasks that don't need to access the component.
+    /// Delegates to `AppContext::spawn`.
+    pub fn spawn_detached<F, Fut>(&self, f: F)
+    where
+        F: FnOnce(AppContext) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        self.app.spawn(f)
+    }
+
+    /// Spawn an unbound async task with cancellation handle.
+    /// Use this for background tasks that don't need to access the component.
+    /// Delegates to `AppContext::spawn_task`.
+    pub fn spawn_detached_task<F, Fut>(&self, f: F) -> crate::task::TaskHandle
+    where
+        F: FnOnce(AppContext) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        self.app.spawn_task(f)
+    }
+
+    /// Cast this context to another view type.
+    /// Note: The cast context will NOT have a handle. It keeps reaching this component
+    /// (for `observe`, timers and tasks) only when `U` is this component's type
+    /// or `dyn AnyComponent`. Use `entity.update_with_cx(cx, ...)` pattern for proper
+    /// child component lifecycle.
+    pub fn cast<U: ?Sized + Send + Sync + 'static>(&self) -> Context<U> {
+        Context {
+            app: AppContext::clone(&self.app),
+            handle: None,
+            binding: self.binding.clone().filter(Binding::binds::<U>),
+            area: self.area,
+            styles: self.styles.clone(),
+            trackers: self.trackers.clone(),
+        }
+    }
+
+    /// Cast this context to another view type that renders into `area` (see `area`).
+    pub fn cast_within<U: ?Sized + Send + Sync + 'static>(&self, area: Rect) -> Context<U> {
+        self.child(Some(area))
+    }
+
+    /// The area this component should render into: the area assigned by its parent
+    /// when mounted as a child, or the whole frame.
+    ///
+    /// # Example
+    /// ```ignore
+    /// fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+    ///     let area = cx.area(frame);
+    ///     frame.render_widget(Paragraph::new("hello").block(Block::bordered()), area);
+    /// }
+    /// ```
+    pub fn area(&self, frame: &ratatui::Frame) -> Rect {
+        self.area.unwrap_or_else(|| frame.area())
+    }
+
+    /// The area assigned by the parent, if this component is rendered as a child.
+    pub fn child_area(&self) -> Option<Rect> {
+        self.area
+    }
+
+    /// Position of a screen cell relative to the assigned area, if it lies inside it.
+    /// Without an assigned area, the position is returned unchanged.
+    pub fn local_position(&self, column: u16, row: u16) -> Option<(u16, u16)> {
+        match self.area {
+            Some(area) if area.contains(ratatui::layout::Position::new(column, row)) => {
+                Some((column - area.x, row - area.y))
+            }
+            Some(_) => None,
+            None => Some((column, row)),
+        }
+    }
+
+    /// Push a style that children mounted from now on inherit and merge with.
+    ///
+    /// Colors act as defaults: children that set their own colors keep them. Modifiers
+    /// are applied over the child's output, so a dimmed or crossed-out parent dims every
+    /// child without passing theme parameters down. Styles stack; see `pop_style`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if self.disabled {
+    ///     cx.push_style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM));
+    /// }
+    /// cx.mount_child(&mut self.form, frame, area);
+    /// ```
+    pub fn push_style(&mut self, style: Style) {
+        self.styles.push(style);
+    }
+
+    /// Remove the most recently pushed style. Inherited styles cannot be popped.
+    pub fn pop_style(&mut self) -> Option<Style> {
+        self.styles.pop()
+    }
+
+    /// The effective inherited style: every pushed style merged, innermost last.
+    /// Widgets drawn directly can patch it with their own style.
+    pub fn style(&self) -> Style {
+        self.styles.iter().fold(Style::default(), |acc, style| acc.patch(*style))
+    }
+
+    /// Render a child component into `area`, recording the area for hit-testing.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let [sidebar, main] = Layout::horizontal([Constraint::Length(30), Constraint::Min(0)]).areas(frame.area());
+    /// cx.mount_child(&mut self.sidebar, frame, sidebar);
+    /// cx.mount_child(&mut self.editor, frame, main);
+    /// ```
+    pub fn mount_child<T: Component>(&self, child: &mut crate::component::ChildView<T>, frame: &mut ratatui::Frame, area: Rect) {
+        child.render(frame, area, self);
+    }
+
+    /// Record `area` under `name` for this frame, so tests can address it (see `AppContext::region`).
+    ///
+    /// # Example
+    /// ```ignore
+    /// cx.name_area("board", board_area);
+    /// // in a test
+    /// harness.click("board", 3, 4);
+    /// ```
+    pub fn name_area(&self, name: impl Into<String>, area: Rect) {
+        if let Ok(mut regions) = self.app.regions.write() {
+            regions.insert(name.into(), area);
+        }
+    }
+
+    /// Also collect the entities this context subscribes to into `tracker`.
+    pub(crate) fn track_dependencies(&mut self, tracker: &crate::damage::DependencyTracker) {
+        self.trackers.push(tracker.clone());
+    }
+
+    /// The trackers of the cached renders this context draws inside.
+    pub(crate) fn dependency_trackers(&self) -> &[crate::damage::DependencyTracker] {
+        &self.trackers
+    }
+
+    /// Context for a child rendered into `area`, not bound to it until `with_binding`.
+    pub(crate) fn child<U: ?Sized + Send + Sync + 'static>(&self, area: Option<Rect>) -> Context<U> {
         Context {
             app: AppContext::clone(&self.app),
             handle: None,
+            binding: None,
+            area,
+            styles: self.styles.clone(),
+            trackers: self.trackers.clone(),
+        }
+    }
+
+    /// This context, reaching its component through `binding`.
+    pub(crate) fn with_binding(mut self, binding: Binding) -> Self {
+        self.binding = Some(binding);
+        self
+    }
+
+    /// Context for the component `field` finds in this one, rendered into `area` (or this
+    /// component's area) and bound to it, so its observers, timers and tasks reach it.
+    /// `owner` owns them; wrappers pass their own to share it with the wrapped component.
+    #[doc(hidden)]
+    pub fn project<U>(&self, owner: Option<&crate::component::binding::PageOwner>, area: Option<Rect>, field: fn(&mut V) -> &mut U) -> Context<U>
+    where
+        V: Sized + 'static,
+        U: Send + Sync + 'static,
+    {
+        let cx = self.child(area.or(self.area));
+        match self.binding() {
+            Ok(binding) => {
+                let owner = owner.map_or(binding.owner(), |owner| owner.id());
+                cx.with_binding(binding.project(owner, area, field))
+            }
+            Err(_) => cx,
+        }
+    }
+
+    /// How observers, timers and tasks started from this context reach the component.
+    fn binding(&self) -> crate::Result<Binding>
+    where
+        V: Sized + 'static,
+    {
+        if let Some(binding) = self.binding.as_ref().filter(|binding| binding.binds::<V>()) {
+            return Ok(binding.clone());
+        }
+        let Some(handle) = &self.handle else {
+            return Err(crate::Error::UnboundContext { type_name: std::any::type_name::<V>() });
+        };
+        Binding::entity(handle, TypeId::of::<V>(), |view| view)
+            .ok_or(crate::Error::EntityDropped { entity_id: handle.entity_id().as_u64() })
+    }
+
+    /// Owner of the observers and timers registered through this context.
+    fn owner(&self) -> Option<EntityId> {
+        match &self.binding {
+            Some(binding) => Some(binding.owner()),
+            None => self.entity_id(),
+        }
+    }
+
+    /// Get the entity ID of the component this context is bound to.
+    /// Returns None if the context was cast from another type.
+    pub fn entity_id(&self) -> Option<EntityId> {
+        self.handle.as_ref().map(|h| h.entity_id())
+    }
+
+    /// Get a weak handle to the component this context is bound to.
+    /// Returns None if the context was cast from another type.
+    /// Use this for async operations to safely check if the entity still exists.
+    pub fn weak_entity(&self) -> Option<WeakEntity<V>> {
+        self.handle.clone()
+    }
+
+    /// Get a strong handle to the component this context is bound to.
+    /// Returns None if the context was cast or if the entity was dropped.
+    pub fn entity(&self) -> Option<Entity<V>> {
+        self.handle.as_ref().and_then(|h| h.upgrade())
+    }
+
+    /// Explicitly trigger a re-render.
+    pub fn notify(&self) {
+        self.app.refresh();
+    }
+}
+
+impl Context<dyn AnyComponent> {
+    /// Context for `T`, the component behind this type-erased one, still bound to it.
+    pub(crate) fn downcast<T: Component>(&self) -> Context<T> {
+        let cx = self.cast::<T>();
+        if cx.binding.is_some() {
+            return cx;
+        }
+        match self.handle.as_ref().and_then(|handle| Binding::entity(handle, TypeId::of::<T>(), |view| view)) {
+            Some(binding) => cx.with_binding(binding),
+            None => cx,
+        }
+    }
+}
+
+/// EventContext for event handling, currently identical to Context but renamed for clarity.
+pub type EventContext<V> = Context<V>;
+
+/// Loads one `Config` type into the context before setup.
+#[cfg(feature = "config")]
+type ConfigHook = Box<dyn FnOnce(&AppContext) -> std::io::Result<()> + Send>;
+
+/// Stores a value shared with other applications in the context before setup.
+type ShareHook = Box<dyn FnOnce(&AppContext) + Send>;
+
+/// Takes over the terminal for a run on backend `B`.
+type OpenBackend<B> = Box<dyn FnOnce(&crate::capabilities::Capabilities) -> std::io::Result<B>>;
+
+/// Main application handle.
+///
+/// The type parameter `E` declares the application-defined event type that components
+/// receive as `Event::App`, read with `event.app::<E>()`; `emit_app_event` rejects any
+/// other type. Apps without custom events use the default `()`.
+///
+/// # Example
+/// ```ignore
+/// enum MyEvent { Tick, Loaded(String) }
+///
+/// Application::new().with_app_event::<MyEvent>().run(|cx| {
+///     cx.emit_app_event(MyEvent::Tick)?;
+///     cx.set_root(Root::new(cx))?;
+///     Ok(())
+/// })?;
+/// ```
+pub struct Application<E = ()> {
+    event_source: Option<Box<dyn EventSource>>,
+    quick_switcher: bool,
+    log_level: Option<log::LevelFilter>,
+    splash: Option<crate::splash::Splash>,
+    on_ready: Option<crate::splash::ReadyHook>,
+    record: Option<std::path::PathBuf>,
+    replay: Option<std::path::PathBuf>,
+    #[cfg(feature = "theme-files")]
+    theme_file: Option<std::path::PathBuf>,
+    #[cfg(feature = "config")]
+    configs: Vec<ConfigHook>,
+    cli_args: Option<Vec<String>>,
+    backend: BackendKind,
+    session: Option<crate::session::Session>,
+    shared: Vec<ShareHook>,
+    shutdown_grace: Duration,
+    /// Handed to the context, and entered by the worker threads of the runtime `run` starts.
+    entities: crate::state::EntityRegistry,
+    _event: PhantomData<fn() -> E>,
+}
+
+impl Default for Application {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Application {
+    /// Create a new application instance.
+    pub fn new() -> Self {
+        Self {
+            event_source: None,
+            quick_switcher: false,
+            log_level: None,
+            splash: None,
+            on_ready: None,
+            record: None,
+            replay: None,
+            #[cfg(feature = "theme-files")]
+            theme_file: None,
+            #[cfg(feature = "config")]
+            configs: Vec::new(),
+            cli_args: None,
+            backend: BackendKind::default(),
+            session: None,
+            shared: Vec::new(),
+            shutdown_grace: crate::task::DEFAULT_GRACE,
+            entities: crate::state::EntityRegistry::default(),
+            _event: PhantomData,
+        }
+    }
+
+    /// Declare the application-defined event type delivered as `Event::App`.
+    pub fn with_app_event<E: Send + Sync + 'static>(self) -> Application<E> {
+        Application {
+            event_source: self.event_source,
+            quick_switcher: self.quick_switcher,
+            log_level: self.log_level,
+            splash: self.splash,
+            on_ready: self.on_ready,
+            record: self.record,
+            replay: self.replay,
+            #[cfg(feature = "theme-files")]
+            theme_file: self.theme_file,
+            #[cfg(feature = "config")]
+            configs: self.configs,
+            cli_args: self.cli_args,
+            backend: self.backend,
+            session: self.session,
+            shared: self.shared,
+            shutdown_grace: self.shutdown_grace,
+            entities: self.entities,
+            _event: PhantomData,
+        }
+    }
+}
+
+impl<E: Send + Sync + 'static> Application<E> {
+    /// Read input from `source` instead of the local terminal (see `EventSource`).
+    /// The terminal is still used for output.
+    pub fn event_source(mut self, source: impl EventSource) -> Self {
+        self.event_source = Some(Box::new(source));
+        self
+    }
+
+    /// Enable the quick switcher, an overlay listing recently visited routes that is
+    /// opened with `Ctrl+E` (see `AppContext::set_switcher_key`).
+    pub fn quick_switcher(mut self, enabled: bool) -> Self {
+        self.quick_switcher = enabled;
+        self
+    }
+
+    /// Capture `log` records at `level` and above instead of letting them reach the
+    /// terminal, and enable the log overlay (`F12`, see `logs`).
+    pub fn capture_logs(mut self, level: log::LevelFilter) -> Self {
+        self.log_level = Some(level);
+        self
+    }
+
+    /// Show `component` while the application starts: from before `setup` runs until
+    /// the root no longer reports `PageStatus::Loading`, and for at least `min_duration`.
+    pub fn with_splash(mut self, component: impl Component, min_duration: Duration) -> Self {
+        self.splash = Some(crate::splash::Splash::new(component, min_duration));
+        self
+    }
+
+    /// Run `hook` once, right after the first frame showing the root component
+    /// (after the splash, if any).
+    pub fn on_ready(mut self, hook: impl FnOnce(&AppContext) + Send + 'static) -> Self {
+        self.on_ready = Some(Box::new(hook));
+        self
+    }
+
+    /// Write every terminal event the app receives, with its timing, to a new file at
+    /// `path` (see `recording`).
+    pub fn record_events(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.record = Some(path.into());
+        self
+    }
+
+    /// Run headlessly against the recording at `path` instead of the terminal, exiting
+    /// when it ends (see `recording`).
+    pub fn replay(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.replay = Some(path.into());
+        self
+    }
+
+    /// Load themes from the TOML or JSON file at `path` before `setup` runs, starting
+    /// with the first one, and reload them whenever the file changes (see `theme`).
+    #[cfg(feature = "theme-files")]
+    pub fn theme_file(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.theme_file = Some(path.into());
+        self
+    }
+
+    /// Load settings of type `T` from `loader` before `setup` runs, failing to start if a
+    /// configuration file is invalid. Components reach them with `cx.config::<T>()`.
+    #[cfg(feature = "config")]
+    pub fn config<T: crate::config::Config>(mut self, loader: crate::config::ConfigLoader) -> Self {
+        self.configs.push(Box::new(move |app| app.load_config::<T>(loader).map(drop)));
+        self
+    }
+
+    /// Draw with `kind` instead of crossterm (see `backend`).
+    pub fn backend(mut self, kind: BackendKind) -> Self {
+        self.backend = kind;
+        self
+    }
+
+    /// Run on a remote client's terminal instead of the local one (see `session`).
+    pub fn session(mut self, session: crate::session::Session) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    /// Store `value` in the context before setup, as `cx.set` would. Pass clones of the
+    /// same `Entity` to several applications to give them state in common; nothing else
+    /// is shared between applications in one process.
+    pub fn share<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.shared.push(Box::new(move |app| app.set(value)));
+        self
+    }
+
+    /// How long to wait on exit for the tasks started with `AppContext::spawn_critical`
+    /// (one second by default).
+    pub fn shutdown_grace(mut self, grace: Duration) -> Self {
+        self.shutdown_grace = grace;
+        self
+    }
+
+    /// Handle the framework flags in `args` (see `cli`) and leave the rest to
+    /// `AppContext::args`.
+    pub fn cli_args<S: Into<String>>(mut self, args: impl IntoIterator<Item = S>) -> Self {
+        self.cli_args = Some(args.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Run like `run`, handling the framework flags on the process command line
+    /// (`--route`, `--theme`, `--fps`, `--no-mouse`; see `cli`).
+    pub fn run_with_args<F>(self, setup: F) -> anyhow::Result<()>
+    where
+        F: FnOnce(&AppContext) -> anyhow::Result<()>,
+    {
+        self.cli_args(std::env::args().skip(1)).run(setup)
+    }
+
+    /// Run like `run` on a thread of its own, so several applications (one per
+    /// `session`, say) can run in one process. Join the handle for the result.
+    pub fn spawn<F>(self, setup: F) -> std::thread::JoinHandle<anyhow::Result<()>>
+    where
+        F: FnOnce(&AppContext) -> anyhow::Result<()> + Send + 'static,
+    {
+        std::thread::spawn(move || self.run(setup))
+    }
+
+    /// Run the application with the given closure that receives a context.
+    pub fn run<F>(self, setup: F) -> anyhow::Result<()>
+    where
+        F: FnOnce(&AppContext) -> anyhow::Result<()>,
+    {
+        let entities = self.entities.clone();
+        let rt = Builder::new_multi_thread()
+            .enable_all()
+            .on_thread_start(move || entities.install())
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to start tokio: {}", e))?;
+        let result = self.run_on(rt.handle().clone(), setup);
+
+        // Tasks were told to stop and critical ones joined; don't wait on the rest
+        // (like infinite loops in components, or a blocked input poll)
+        rt.shutdown_background();
+
+        result
+    }
+
+    /// Run like `run`, on an existing runtime instead of one of its own. Blocks the
+    /// calling thread, which must not be one of the runtime's (use `spawn_blocking`
+    /// from async code). A current-thread runtime must be driven elsewhere meanwhile.
+    ///
+    /// Tasks the application leaves behind keep running on the runtime after it exits;
+    /// have them watch `AppContext::shutdown_token`, or spawn them through a
+    /// `TaskTracker` owned by a component, to have them stopped.
+    pub fn run_on<F>(mut self, handle: tokio::runtime::Handle, setup: F) -> anyhow::Result<()>
+    where
+        F: FnOnce(&AppContext) -> anyhow::Result<()>,
+    {
+        if let Some(session) = self.session.take() {
+            return self.run_backend(handle, setup, Box::new(move |caps| session.open(caps)));
+        }
+        match self.backend {
+            BackendKind::Crossterm => {
+                self.run_backend::<CrosstermBackend<std::io::Stdout>, F>(handle, setup, Box::new(TerminalBackend::enter))
+            }
+            #[cfg(feature = "termion")]
+            BackendKind::Termion => self.run_backend::<crate::backend::Termion, F>(handle, setup, Box::new(TerminalBackend::enter)),
+            #[cfg(feature = "termwiz")]
+            BackendKind::Termwiz => self.run_backend::<crate::backend::Termwiz, F>(handle, setup, Box::new(TerminalBackend::enter)),
+        }
+    }
+
+    /// Run on backend `B`, taking over the terminal with `open` once it is needed.
+    fn run_backend<B, F>(mut self, rt: tokio::runtime::Handle, setup: F, open: OpenBackend<B>) -> anyhow::Result<()>
+    where
+        B: TerminalBackend + crate::graphics::GraphicsBackend,
+        F: FnOnce(&AppContext) -> anyhow::Result<()>,
+    {
+        let (mut app_context, re_render_rx, inject_rx) = AppContext::new();
+        let cli = self.prepare(&mut app_context)?;
+        // The loop runs on this thread
+        let _entities = app_context.entities.enter();
+        let soak = crate::soak::SoakConfig::from_env();
+        let replay = match &self.replay {
+            Some(path) => Some(
+                crate::recording::Replay::open(path)
+                    .map_err(|e| anyhow::anyhow!("Failed to read recording {}: {}", path.display(), e))?,
+            ),
+            None => None,
+        };
+        let mut startup = crate::splash::Startup::new(self.splash.take(), self.on_ready.take());
+
+        let _guard = rt.enter();
+        self.configure(&app_context, cli.as_ref())?;
+        // Put the splash up before setup, so slow initialization is not a blank screen
+        let interactive = soak.is_none() && replay.is_none();
+        let splash = startup.waiting() && interactive;
+        let mut open = Some(open);
+        let mut terminal = open.take_if(|_| splash).map(|open| Self::enter_terminal(&app_context, open)).transpose()?;
+        let prepared = match terminal.as_mut() {
+            Some(terminal) => startup.draw_splash(&app_context, terminal),
+            None => Ok(()),
+        }
+        .and_then(|()| setup(&app_context));
+        if let Err(err) = prepared {
+            if let Some(terminal) = terminal {
+                let _ = Self::leave_terminal(&app_context, terminal);
+            }
+            return Err(err);
+        }
+        drop(_guard);
+        if let Some(route) = cli.and_then(|cli| cli.route) {
+            app_context.navigate(route);
+        }
+
+        let actual_root = Self::resolve_root(&app_context)?;
+
+        // The backend's own input may depend on it having the terminal
+        if let (true, None, Some(open)) = (interactive, &terminal, open) {
+            terminal = Some(Self::enter_terminal(&app_context, open)?);
+        }
+        let save_cx = AppContext::clone(&app_context);
+        let headless = replay.as_ref().map(crate::recording::Replay::size);
+        let source: Box<dyn EventSource> = match (replay, self.event_source.take(), &terminal) {
+            (Some(replay), _, _) => Box::new(replay),
+            (None, Some(source), _) => source,
+            (None, None, Some(terminal)) => terminal.backend().events(),
+            (None, None, None) => Box::new(CrosstermEvents),
+        };
+        let source = match self.recorded(source) {
+            Ok(source) => source,
+            Err(err) => {
+                if let Some(terminal) = terminal {
+                    let _ = Self::leave_terminal(&app_context, terminal);
+                }
+                return Err(err);
+            }
+        };
+        let grace = self.shutdown_grace;
+        let result = rt.block_on(async move {
+            match (soak, headless, terminal) {
+                (Some(config), _, _) => crate::soak::run(&self, config, app_context, actual_root, re_render_rx, inject_rx, startup).await,
+                (None, Some(size), _) => {
+                    crate::recording::run(&self, size, source, app_context, actual_root, re_render_rx, inject_rx, startup).await
+                }
+                (None, None, Some(terminal)) => {
+                    self.run_loop(app_context, actual_root, re_render_rx, inject_rx, source, terminal, startup).await
+                }
+                (None, None, None) => unreachable!("interactive runs enter the terminal before the loop"),
+            }
+        });
+        rt.block_on(save_cx.shutdown(grace));
+
+        // Persist state regardless of how the loop ended
+        #[cfg(feature = "persist")]
+        let saved = save_cx.save_all();
+        #[cfg(not(feature = "persist"))]
+        let saved: crate::Result<()> = Ok(());
+        result.and(saved.map_err(anyhow::Error::from))
+    }
+
+    /// Set the application up for `setup` to run on the current tokio runtime, and hand
+    /// back its context and an `AppLoop` to drive it with, instead of `run` taking over
+    /// the thread with a runtime and event loop of its own. Splash, CLI flags (from
+    /// `cli_args`), configuration and theme files are handled as in `run`; the terminal,
+    /// event source, recording and replay are left to the caller.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let (cx, mut app) = Application::new().into_parts(|cx| cx.set_root(Root::new(cx)).map_err(Into::into))?;
+    /// loop {
+    ///     if let Some(event) = my_events.next().await {
+    ///         if app.step(event)? == Step::Quit { break; }
+    ///     } else if app.tick()? == Step::Quit { break; }
+    ///     if app.needs_redraw() { app.draw(&mut terminal)?; }
+    /// }
+    /// cx.shutdown(Duration::from_secs(1)).await;
+    /// cx.save_all()?; // with the `persist` feature
+    /// ```
+    pub fn into_parts<F>(mut self, setup: F) -> anyhow::Result<(AppContext, AppLoop<E>)>
+    where
+        F: FnOnce(&AppContext) -> anyhow::Result<()>,
+    {
+        let (mut app, re_render_rx, inject_rx) = AppContext::new();
+        let cli = self.prepare(&mut app)?;
+        let _entities = app.entities.enter();
+        self.configure(&app, cli.as_ref())?;
+        setup(&app)?;
+        if let Some(route) = cli.and_then(|cli| cli.route) {
+            app.navigate(route);
+        }
+        let root = Self::resolve_root(&app)?;
+        Self::mount_root(&app, &root)?;
+        let startup = crate::splash::Startup::new(self.splash.take(), self.on_ready.take());
+        let app_loop = AppLoop::new(AppContext::clone(&app), root, re_render_rx, inject_rx, startup);
+        Ok((app, app_loop))
+    }
+
+    /// Apply the builder settings that need no runtime to a fresh context, returning the
+    /// parsed command line.
+    fn prepare(&mut self, app: &mut AppContext) -> anyhow::Result<Option<crate::cli::CliArgs>> {
+        app.declare_app_event::<E>();
+        app.entities = self.entities.clone();
+        let cli = match self.cli_args.take() {
+            Some(args) => Some(crate::cli::CliArgs::parse(args)?),
+            None => None,
+        };
+        if let Some(cli) = &cli {
+            app.capabilities.mouse &= cli.mouse;
+            app.metrics.set_overlay(cli.fps);
+            app.set(cli.clone());
+        }
+        for share in self.shared.drain(..) {
+            share(app);
+        }
+        app.switcher.set_enabled(self.quick_switcher);
+        if let Some(level) = self.log_level {
+            let logs = crate::logs::capture(level, crate::logs::DEFAULT_CAPACITY)
+                .map_err(|e| anyhow::anyhow!("Failed to capture logs: {}", e))?;
+            app.log_overlay.set_logs(logs);
+        }
+        Ok(cli)
+    }
+
+    /// Load configuration and themes before setup; must be called within the runtime.
+    fn configure(&mut self, app: &AppContext, cli: Option<&crate::cli::CliArgs>) -> anyhow::Result<()> {
+        #[cfg(feature = "config")]
+        for load in self.configs.drain(..) {
+            load(app).map_err(|e| anyhow::anyhow!("Failed to load configuration: {}", e))?;
+        }
+        #[cfg(feature = "theme-files")]
+        if let Some(path) = &self.theme_file {
+            app.load_theme_file(path)
+                .map_err(|e| anyhow::anyhow!("Failed to load themes from {}: {}", path.display(), e))?;
+            app.watch_theme_file(path);
+        }
+        if let Some(name) = cli.and_then(|cli| cli.theme.as_deref()) {
+            if !app.select_theme(name) {
+                let known: Vec<String> = app.themes().into_iter().map(|theme| theme.name).collect();
+                anyhow::bail!("Unknown theme {name:?}; available: {}", known.join(", "));
+            }
+        }
+        Ok(())
+    }
+
+    /// The root set during setup, or a placeholder if there is none.
+    fn resolve_root(app: &AppContext) -> anyhow::Result<Entity<dyn AnyComponent>> {
+        let guard = app.root.lock().map_err(|_| anyhow::anyhow!("Root mutex poisoned"))?;
+        Ok(guard.as_ref().map(Entity::clone).unwrap_or_else(|| {
+            Entity::from_arc(Arc::new(crate::state::RwLock::new(DummyView)) as Arc<crate::state::RwLock<dyn AnyComponent>>)
+        }))
+    }
+
+    /// Wrap `source` in a `Recorder` if `record_events` was requested.
+    fn recorded(&self, source: Box<dyn EventSource>) -> anyhow::Result<Box<dyn EventSource>> {
+        match &self.record {
+            Some(path) => crate::recording::Recorder::create_boxed(path, source)
+                .map(|recorder| Box::new(recorder) as Box<dyn EventSource>)
+                .map_err(|e| anyhow::anyhow!("Failed to record to {}: {}", path.display(), e)),
+            None => Ok(source),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn run_loop<B: TerminalBackend + crate::graphics::GraphicsBackend>(
+        &self,
+        app: AppContext,
+        root: Entity<dyn AnyComponent>,
+        re_render_rx: mpsc::UnboundedReceiver<()>,
+        inject_rx: mpsc::UnboundedReceiver<Event>,
+        source: Box<dyn EventSource>,
+        mut terminal: Terminal<B>,
+        startup: crate::splash::Startup,
+    ) -> anyhow::Result<()> {
+        Self::mount_root(&app, &root)?;
+
+        // Dedicated event polling task to avoid blocking the main loop
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        tokio::task::spawn_blocking(move || crate::event_source::forward(source, event_tx));
+
+        let result = self
+            .run_app_loop(AppContext::clone(&app), &mut terminal, root, re_render_rx, inject_rx, event_rx, startup, false)
+            .await;
+
+        Self::leave_terminal(&app, terminal)?;
+        result
+    }
+
+    /// Take over the terminal with `open` (see `TerminalBackend::enter`).
+    fn enter_terminal<B: TerminalBackend>(app: &AppContext, open: OpenBackend<B>) -> anyhow::Result<Terminal<B>> {
+        Ok(Terminal::new(open(&app.capabilities())?)?)
+    }
+
+    /// Restore the terminal left by `enter_terminal`.
+    fn leave_terminal<B: TerminalBackend>(app: &AppContext, mut terminal: Terminal<B>) -> anyhow::Result<()> {
+        terminal.backend_mut().leave(&app.capabilities())?;
+        Ok(())
+    }
+
+    /// Lifecycle: Call on_mount (first time) and on_enter (entering view) on the root component.
+    pub(crate) fn mount_root(app: &AppContext, root: &Entity<dyn AnyComponent>) -> anyhow::Result<()> {
+        let weak = root.downgrade();
+        let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), weak);
+        let task = root.update(|comp| {
+            comp.on_mount_any(&mut cx);
+            let task = comp.on_mount_async_any(&mut cx);
+            comp.on_enter_any(&mut cx);
+            task
+        }).map_err(|_| anyhow::anyhow!("Root mutex poisoned during on_mount"))?;
+        if let Some(task) = task {
+            app.mounts.start_root(task, root, app);
+        }
+        Ok(())
+    }
+
+    /// Drive the event/render loop on any backend until quit or the event source closes.
+    /// With `ignore_quit`, `Action::Quit` is swallowed (used by headless soak runs).
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn run_app_loop<B: crate::graphics::GraphicsBackend>(
+        &self,
+        app: AppContext,
+        terminal: &mut Terminal<B>,
+        root: Entity<dyn AnyComponent>,
+        re_render_rx: mpsc::UnboundedReceiver<()>,
+        inject_rx: mpsc::UnboundedReceiver<Event>,
+        mut event_rx: mpsc::UnboundedReceiver<CrosstermEvent>,
+        startup: crate::splash::Startup,
+        ignore_quit: bool,
+    ) -> anyhow::Result<()> {
+        let mut app_loop = AppLoop::<E>::new(app, root, re_render_rx, inject_rx, startup);
+        app_loop.ignore_quit = ignore_quit;
+        let mut splash_tick = tokio::time::interval(crate::splash::SPLASH_TICK);
+
+        loop {
+            tokio::select! {
+                // Prioritize event handling for lower latency
+                biased;
+
+                crossterm_event = event_rx.recv() => {
+                    // The event source is gone; nothing more can drive the app
+                    let Some(crossterm_event) = crossterm_event else {
+                        return Ok(());
+                    };
+                    if app_loop.input(crossterm_event)? == Step::Quit {
+                        return Ok(());
+                    }
+                }
+
+                Some(injected) = app_loop.inject_rx.recv() => {
+                    if app_loop.deliver(injected)? == Step::Quit {
+                        return Ok(());
+                    }
+                }
+
+                _ = app_loop.re_render_rx.recv() => {
+                    // Drain all pending refresh requests to compact them into a single frame
+                    while app_loop.re_render_rx.try_recv().is_ok() {}
+                    app_loop.draw_with(terminal, |app, backend| app.graphics.flush(backend))?;
+                }
+
+                // Re-check readiness while the splash is up
+                _ = splash_tick.tick(), if app_loop.startup.waiting() => {
+                    app_loop.app.refresh();
+                }
+            }
+        }
+    }
+
+    /// Render the root into `frame`, or reuse the cells it drew last if it is memoized
+    /// (see `Component::memoize`) and neither it nor an entity it subscribed to changed.
+    pub(crate) fn render_root(app: &AppContext, root: &Entity<dyn AnyComponent>, frame: &mut Frame, cache: &mut crate::damage::RenderCache) {
+        let area = frame.area();
+        if app.mounts.root_pending() {
+            app.mounts.draw_placeholder(frame, area);
+            return;
+        }
+        let generation = app.damage.generation();
+        let memoize = root.read(|comp| comp.memoize_any()).unwrap_or(false);
+        if memoize && cache.reuse(frame.buffer_mut(), area, Style::default(), generation, &[]) {
+            return;
+        }
+        let tracker = crate::damage::DependencyTracker::default();
+        let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), root.downgrade());
+        if memoize {
+            cx.track_dependencies(&tracker);
+        }
+        root.update(|comp| comp.render_any(frame, &mut cx))
+            .expect("Root mutex poisoned during render");
+        if memoize {
+            // Taken after rendering, which counts as an update; events handled later change it
+            tracker.record(root.entity_id(), root.version_probe(), root.version());
+            cache.store(frame.buffer_mut(), area, Style::default(), generation, &tracker);
+        }
+    }
+
+    /// Deliver an event to the root component.
+    /// Returns `true` when the component requested `Action::Quit`.
+    fn dispatch_event(app: &AppContext, root: &Entity<dyn AnyComponent>, event: Event) -> anyhow::Result<Dispatched> {
+        if let Event::Resize(width, height) = event {
+            let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), root.downgrade());
+            root.update(|comp| comp.on_resize_any(width, height, &mut cx))
+                .map_err(|_| anyhow::anyhow!("Root mutex poisoned during resize"))?;
+        }
+        if matches!(event, Event::FocusGained | Event::FocusLost) {
+            let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), root.downgrade());
+            root.update(|comp| match event {
+                Event::FocusGained => comp.on_focus_any(&mut cx),
+                _ => comp.on_blur_any(&mut cx),
+            })
+            .map_err(|_| anyhow::anyhow!("Root mutex poisoned during focus change"))?;
+        }
+        if app.context_menus.intercept(&event, app) || app.palettes.intercept(&event, app) || app.switcher.intercept(&event, app) || app.log_overlay.intercept(&event)
+            || app.inspector.intercept(&event, app)
+        {
+            app.refresh();
+            return Ok(Dispatched { quit: false, redraw: true });
+        }
+        let dragging = app.drags.is_active();
+        let Some(event) = app.drags.intercept(event) else {
+            app.refresh();
+            return Ok(Dispatched { quit: false, redraw: true });
+        };
+        if dragging && !app.drags.is_active() {
+            // The drag ended: remove the ghost
+            app.refresh();
+        }
+
+        // Registered key sequences arrive as one `Event::Chord`; their keys are held back
+        let chording = !app.chords.pending().is_empty();
+        let events = app.chords.feed(event, app);
+        let chording = chording || !app.chords.pending().is_empty();
+        if chording {
+            // Update the pending-keys indicator
+            app.refresh();
+        }
+        let mut dispatched = Dispatched { quit: false, redraw: chording };
+        for event in events {
+            let delivered = Self::deliver_to_root(app, root, event)?;
+            dispatched = Dispatched { quit: delivered.quit, redraw: dispatched.redraw || delivered.redraw };
+            if dispatched.quit {
+                break;
+            }
+        }
+        Ok(dispatched)
+    }
+
+    /// Deliver an event to the root component, then the click it completes, if any.
+    fn deliver_to_root(app: &AppContext, root: &Entity<dyn AnyComponent>, event: Event) -> anyhow::Result<Dispatched> {
+        if event.is_input() && app.mounts.root_pending() {
+            return Ok(Dispatched { quit: false, redraw: false });
+        }
+        let click = match &event {
+            Event::Mouse(mouse) => app.clicks.track(mouse, std::time::Instant::now()),
+            _ => None,
+        };
+        let weak = root.downgrade();
+        let mut cx = EventContext::<dyn AnyComponent>::new(AppContext::clone(app), weak);
+
+        // Coalesce all updates made while handling the event into one frame, drawn only
+        // if the event may have changed something on screen
+        let (action, redraw) = app.batch(|app| {
+            let action = root.update(|comp| comp.handle_event_any(event, &mut cx));
+            let changed = matches!(action, Ok(Some(_))) || crate::state::batch::notified_besides(root.entity_id());
+            if changed {
+                app.refresh();
+            }
+            // Handlers may also have asked for a frame (`cx.notify()`, `refresh_on_event`)
+            let redraw = changed || crate::state::batch::refresh_pending(&app.re_render_tx);
+            (action, redraw)
+        });
+        let action = action.map_err(|_| anyhow::anyhow!("Root mutex poisoned during event"))?;
+        if let Some(Action::Dispatch(dispatched)) = &action {
+            if !app.handle_action(dispatched) {
+                log::warn!("no handler for action {}", dispatched.type_name());
+            }
+        }
+
+        let dispatched = Dispatched { quit: matches!(action, Some(Action::Quit)), redraw };
+        match click {
+            // Deliver the click the release completed
+            Some(click) if !dispatched.quit => {
+                let clicked = Self::dispatch_event(app, root, click)?;
+                Ok(Dispatched { quit: clicked.quit, redraw: dispatched.redraw || clicked.redraw })
+            }
+            _ => Ok(dispatched),
+        }
+    }
+
+    /// Lifecycle: Call on_shutdown on the root component before the loop exits.
+    fn shutdown_root(app: &AppContext, root: &Entity<dyn AnyComponent>) -> anyhow::Result<()> {
+        let weak = root.downgrade();
+        let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), weak);
+        root.update(|comp| comp.on_shutdown_any(&mut cx))
+            .map_err(|_| anyhow::anyhow!("Root mutex poisoned during shutdown"))
+    }
+}
+
+/// Whether the application is still running after an `AppLoop` step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// Keep feeding events and drawing.
+    Continue,
+    /// The root requested `Action::Quit` and has been shut down.
+    Quit,
+}
+
+/// An application's event and render pipeline, driven from outside rather than by
+/// `run` (see `Application::into_parts`). Feed it terminal events with `step`, let it
+/// catch up on events emitted by tasks with `tick`, and `draw` when `needs_redraw`.
+pub struct AppLoop<E = ()> {
+    app: AppContext,
+    root: Entity<dyn AnyComponent>,
+    re_render_rx: mpsc::UnboundedReceiver<()>,
+    inject_rx: mpsc::UnboundedReceiver<Event>,
+    startup: crate::splash::Startup,
+    root_cache: crate::damage::RenderCache,
+    ignore_quit: bool,
+    _event: PhantomData<fn() -> E>,
+}
+
+impl<E: Send + Sync + 'static> AppLoop<E> {
+    fn new(
+        app: AppContext,
+        root: Entity<dyn AnyComponent>,
+        re_render_rx: mpsc::UnboundedReceiver<()>,
+        inject_rx: mpsc::UnboundedReceiver<Event>,
+        startup: crate::splash::Startup,
+    ) -> Self {
+        // Initial render
+        app.refresh();
+        Self {
+            app,
+            root,
+            re_render_rx,
+            inject_rx,
+            startup,
+            root_cache: crate::damage::RenderCache::default(),
+            ignore_quit: false,
+            _event: PhantomData,
+        }
+    }
+
+    /// The application's context.
+    pub fn context(&self) -> &AppContext {
+        &self.app
+    }
+
+    /// Handle one terminal event, then the events it caused (see `tick`).
+    pub fn step(&mut self, event: CrosstermEvent) -> anyhow::Result<Step> {
+        let _entities = self.app.entities.enter();
+        match self.input(event)? {
+            Step::Continue => self.tick(),
+            Step::Quit => Ok(Step::Quit),
+        }
+    }
+
+    /// Deliver the events emitted since the last step (`navigate`, `emit_custom`,
+    /// `emit_app_event`, from handlers and tasks alike). Events emitted while these are
+    /// handled wait for the next call.
+    pub fn tick(&mut self) -> anyhow::Result<Step> {
+        let _entities = self.app.entities.enter();
+        for _ in 0..self.inject_rx.len() {
+            let Ok(event) = self.inject_rx.try_recv() else { break };
+            if self.deliver(event)? == Step::Quit {
+                return Ok(Step::Quit);
+            }
+        }
+        Ok(Step::Continue)
+    }
+
+    /// Whether a frame was requested since the last `draw`. While a splash is up this
+    /// stays `true`, so readiness is re-checked on every frame.
+    pub fn needs_redraw(&mut self) -> bool {
+        let mut requested = false;
+        while self.re_render_rx.try_recv().is_ok() {
+            requested = true;
+        }
+        requested || self.startup.waiting()
+    }
+
+    /// Draw a frame to `terminal`. Image placements are left pending for
+    /// `write_graphics`.
+    pub fn draw<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> anyhow::Result<()> {
+        let _entities = self.app.entities.enter();
+        self.draw_with(terminal, |_, _| Ok(()))
+    }
+
+    /// Write the image sequences of the last frame (see `widgets::image`) to `out`,
+    /// which should reach the same terminal as the frame.
+    pub fn write_graphics(&self, out: &mut impl std::io::Write) -> std::io::Result<()> {
+        let pending = self.app.graphics.take_pending();
+        if pending.is_empty() {
+            return Ok(());
         }
+        out.write_all(pending.as_bytes())?;
+        out.flush()
     }
 
-    /// Get the entity ID of the component this context is bound to.
-    /// Returns None if the context was cast from another type.
-    pub fn entity_id(&self) -> Option<EntityId> {
-        self.handle.as_ref().map(|h| h.entity_id())
+    /// Handle a terminal event without delivering the events it caused.
+    fn input(&mut self, event: CrosstermEvent) -> anyhow::Result<Step> {
+        let app = &self.app;
+        let event = match event {
+            CrosstermEvent::Key(key) if key.kind == KeyEventKind::Press => {
+                let key = app.key_normalizer.read().map(|n| n.normalize(key)).unwrap_or(key);
+                if app.presentation.record_key(&key) {
+                    // Redraw once the key has faded out of the bubble
+                    let app = AppContext::clone(app);
+                    tokio::spawn(async move {
+                        tokio::time::sleep(crate::presentation::KEY_DISPLAY).await;
+                        app.refresh();
+                    });
+                }
+                Event::Key(key)
+            }
+            CrosstermEvent::Mouse(mouse) => Event::Mouse(mouse),
+            CrosstermEvent::Resize(w, h) => {
+                let (w, h) = app.capabilities.normalize_resize(w, h);
+                app.damage.invalidate_all();
+                Event::Resize(w, h)
+            }
+            CrosstermEvent::FocusGained => Event::FocusGained,
+            CrosstermEvent::FocusLost => Event::FocusLost,
+            CrosstermEvent::Paste(s) => Event::Paste(s),
+            _ => return Ok(Step::Continue),
+        };
+
+        let _span = crate::metrics::pipeline_span!("event", source = "input");
+        let received = app.metrics.event_received("input");
+        let dispatched = if self.startup.waiting() {
+            Dispatched { quit: self.startup.splash_event(app, event)?, redraw: true }
+        } else {
+            Application::<E>::dispatch_event(app, &self.root, event)?
+        };
+        if dispatched.quit && !self.ignore_quit {
+            return self.shut_down();
+        }
+        app.metrics.event_handled(received, dispatched.redraw);
+        Ok(Step::Continue)
     }
 
-    /// Get a weak handle to the component this context is bound to.
-    /// Returns None if the context was cast from another type.
-    /// Use this for async operations to safely check if the entity still exists.
-    pub fn weak_entity(&self) -> Option<WeakEntity<V>> {
-        self.handle.clone()
+    /// Deliver one event emitted through the context.
+    fn deliver(&mut self, injected: Event) -> anyhow::Result<Step> {
+        let _span = crate::metrics::pipeline_span!("event", source = "injected");
+        let received = self.app.metrics.event_received("injected");
+        if self.app.reduce_message(&injected) {
+            self.app.metrics.event_handled(received, true);
+            return Ok(Step::Continue);
+        }
+        let dispatched = Application::<E>::dispatch_event(&self.app, &self.root, injected)?;
+        if dispatched.quit && !self.ignore_quit {
+            return self.shut_down();
+        }
+        self.app.metrics.event_handled(received, dispatched.redraw);
+        Ok(Step::Continue)
     }
 
-    /// Get a strong handle to the component this context is bound to.
-    /// Returns None if the context was cast or if the entity was dropped.
-    pub fn entity(&self) -> Option<Entity<V>> {
-        self.handle.as_ref().and_then(|h| h.upgrade())
+    fn shut_down(&self) -> anyhow::Result<Step> {
+        Application::<E>::shutdown_root(&self.app, &self.root)?;
+        Ok(Step::Quit)
     }
 
-    /// Explicitly trigger a re-render.
-    pub fn notify(&self) {
-        self.app.refresh();
+    /// Draw a frame, calling `flush` with the backend once it is on screen.
+    fn draw_with<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        flush: impl FnOnce(&AppContext, &mut B) -> std::io::Result<()>,
+    ) -> anyhow::Result<()> {
+        let app = &self.app;
+        let _span = crate::metrics::pipeline_span!("frame");
+        let frame_start = std::time::Instant::now();
+        let splash = self.startup.splash_active(app, &self.root);
+        let (startup, root, root_cache) = (&self.startup, &self.root, &mut self.root_cache);
+        terminal.draw(|frame| {
+            let _span = crate::metrics::pipeline_span!("render");
+            app.frame_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            app.clear_regions();
+            app.budget.begin_frame();
+            if splash {
+                startup.render_splash(frame, app);
+            } else {
+                Application::<E>::render_root(app, root, frame, root_cache);
+            }
+            let (palettes, switcher, logs) = (app.palettes.clone(), app.switcher.clone(), app.log_overlay.clone());
+            let (menus, drags) = (app.context_menus.clone(), app.drags.clone());
+            let inspected = AppContext::clone(app);
+            app.overlay(move |frame| {
+                let bounds = frame.area();
+                logs.render(frame.buffer_mut(), bounds);
+                inspected.inspector.render(&inspected, frame.buffer_mut(), bounds);
+                switcher.render(frame.buffer_mut(), bounds);
+                palettes.render(frame.buffer_mut(), bounds);
+                drags.render(frame.buffer_mut(), bounds);
+                menus.render(frame.buffer_mut(), bounds);
+            });
+            app.render_overlays(frame);
+            app.metrics.render_overlay(frame);
+            if app.show_debug_chrome() {
+                app.contrast.audit_frame(frame, &app.regions());
+                app.storm.render_overlay(frame);
+                if cfg!(debug_assertions) {
+                    app.budget.render_overlay(frame);
+                }
+            }
+            app.presentation.render_overlay(frame);
+            app.capabilities.downgrade(frame.buffer_mut());
+            app.prepare_graphics(frame);
+        })?;
+        {
+            let _span = crate::metrics::pipeline_span!("flush");
+            flush(app, terminal.backend_mut())?;
+        }
+        app.metrics.frame_flushed(frame_start);
+        self.startup.frame_drawn(app, splash);
+        Ok(())
     }
 }
 
-/// EventContext for event handling, currently identical to Context but renamed for clarity.
-pub type EventContext<V> = Context<V>;
+/// Outcome of delivering one event.
+struct Dispatched {
+    /// The root requested `Action::Quit`.
+    quit: bool,
+    /// A frame was requested to show the event's effect.
+    redraw: bool,
+}
 
-/// Main application handle.
-pub struct Application;
+struct DummyView;
 
-impl Application {
-    /// Create a new application instance.
-    pub fn new() -> Self {
-        Self
+impl Component for DummyView {
+    fn render(&mut self, frame: &mut ratatui::Frame, _cx: &mut Context<Self>) {
+        let paragraph = ratatui::widgets::Paragraph::new("No component set")
+            .alignment(ratatui::layout::Alignment::Center);
+        frame.render_widget(paragraph, frame.area());
     }
+}
 
-    /// Run the application with the given closure that receives a context.
-    pub fn run<F>(self, setup: F) -> anyhow::Result<()>
-    where
-        F: FnOnce(&AppContext) -> anyhow::Result<()>,
-    {
-        let rt = Runtime::new().map_err(|e| anyhow::anyhow!("Failed to start tokio: {}", e))?;
-        let (re_render_tx, re_render_rx) = mpsc::unbounded_channel();
-        let root = Arc::new(Mutex::new(None));
-        let app_context = AppContext {
-            root: Arc::clone(&root),
-            re_render_tx,
-            frame_count: Arc::new(std::sync::atomic::AtomicU64::new(0)),
-            state: Arc::new(RwLock::new(HashMap::new())),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyed_state() {
+        let (cx, _, _) = AppContext::new();
+        cx.set_keyed("primary", 1u32);
+        cx.set_keyed("replica", 2u32);
+        cx.set_keyed("primary", String::from("other type"));
+
+        assert_eq!(cx.get_keyed::<u32>("primary").unwrap(), 1);
+        assert_eq!(cx.get_keyed::<u32>("replica").unwrap(), 2);
+        assert_eq!(cx.get_keyed::<String>("primary").unwrap(), "other type");
+        assert!(matches!(cx.get_keyed::<u32>("standby"), Err(crate::Error::StateMissing { type_name: "u32" })));
+        assert_eq!(cx.keys::<u32>(), vec!["primary".to_string(), "replica".to_string()]);
+        assert!(!cx.has::<u32>());
+        assert!(matches!(cx.get::<u32>(), Err(crate::Error::StateMissing { type_name: "u32" })));
+
+        assert!(cx.remove_keyed::<u32>("primary"));
+        assert!(!cx.has_keyed::<u32>("primary"));
+        assert!(cx.has_keyed::<String>("primary"));
+    }
+
+    #[tokio::test]
+    async fn test_spawn_result_delivers_output_to_component() {
+        use crate::ChildView;
+
+        #[derive(Default)]
+        struct Fetcher(Option<u32>);
+
+        impl Component for Fetcher {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                frame.render_widget(ratatui::widgets::Paragraph::new(format!("{:?}", self.0)), cx.area(frame));
+            }
+
+            fn handle_event(&mut self, _event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+                cx.spawn_result(async { 6 * 7 }, |this, answer, _cx| this.0 = Some(answer)).unwrap();
+                None
+            }
+        }
+
+        struct Page {
+            answer: Option<u32>,
+            fetcher: ChildView<Fetcher>,
+        }
+
+        impl Component for Page {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                frame.render_widget(ratatui::widgets::Paragraph::new(format!("{:?}", self.answer)), Rect::new(0, 0, 8, 1));
+                cx.mount_child(&mut self.fetcher, frame, Rect::new(0, 1, 8, 1));
+            }
+
+            fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+                cx.spawn_result(async { 7 }, |this, answer, _cx| this.answer = Some(answer)).unwrap();
+                self.fetcher.handle_event(event, cx)
+            }
+        }
+
+        let (_cx, mut app) = Application::new()
+            .into_parts(|cx| cx.set_root(Page { answer: None, fetcher: ChildView::new(Fetcher::default()) }).map_err(Into::into))
+            .unwrap();
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(8, 2)).unwrap();
+        let mut screen = |app: &mut AppLoop| {
+            app.draw(&mut terminal).unwrap();
+            let buffer = terminal.backend().buffer();
+            (0..2).map(|y| (0..8).map(|x| buffer[(x, y)].symbol()).collect::<String>().trim_end().to_string()).collect::<Vec<_>>()
         };
+        assert_eq!(screen(&mut app), ["None", "None"]);
 
-        let _guard = rt.enter();
-        setup(&app_context)?;
-        drop(_guard);
+        app.step(CrosstermEvent::Key(crossterm::event::KeyEvent::from(crossterm::event::KeyCode::Char('x')))).unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(app.needs_redraw());
+        assert_eq!(screen(&mut app), ["Some(7)", "Some(42)"]);
+    }
 
-        let actual_root: Entity<dyn AnyComponent> = {
-            let guard = root.lock().map_err(|_| anyhow::anyhow!("Root mutex poisoned"))?;
-            guard.as_ref().map(Entity::clone).unwrap_or_else(|| {
-                Entity::from_arc(Arc::new(RwLock::new(DummyView)) as Arc<RwLock<dyn AnyComponent>>)
-            })
+    #[tokio::test]
+    async fn test_spawn_with_progress_hands_output_to_component() {
+        struct Copier {
+            copied: Option<u32>,
+            progress: Option<crate::task::Progress>,
+        }
+
+        impl Component for Copier {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                let finished = self.progress.as_ref().map(|progress| progress.get().finished);
+                frame.render_widget(ratatui::widgets::Paragraph::new(format!("{:?} {finished:?}", self.copied)), cx.area(frame));
+            }
+
+            fn handle_event(&mut self, _event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+                let handle = cx
+                    .spawn_with_progress(
+                        |_, progress| async move {
+                            progress.report(0.5);
+                            3
+                        },
+                        |this, files, _cx| this.copied = Some(files),
+                    )
+                    .unwrap();
+                self.progress = handle.progress();
+                None
+            }
+        }
+
+        let (_cx, mut app) = Application::new()
+            .into_parts(|cx| cx.set_root(Copier { copied: None, progress: None }).map_err(Into::into))
+            .unwrap();
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(20, 1)).unwrap();
+        app.step(CrosstermEvent::Key(crossterm::event::KeyEvent::from(crossterm::event::KeyCode::Char('x')))).unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(app.needs_redraw());
+        app.draw(&mut terminal).unwrap();
+        let line: String = (0..20).map(|x| terminal.backend().buffer()[(x, 0)].symbol()).collect();
+        assert_eq!(line.trim_end(), "Some(3) Some(true)");
+    }
+
+    #[tokio::test]
+    async fn test_memoized_root_skips_render_until_inputs_change() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        struct Dashboard {
+            data: Entity<u32>,
+            renders: Arc<AtomicU32>,
+        }
+
+        impl Component for Dashboard {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                self.renders.fetch_add(1, Ordering::SeqCst);
+                let value = cx.watch(&self.data, |v| *v).unwrap_or_default();
+                frame.buffer_mut().set_string(0, 0, value.to_string(), Style::default());
+            }
+
+            fn memoize(&self) -> bool {
+                true
+            }
+        }
+
+        let (app, _refresh, _inject) = AppContext::new();
+        let data = Entity::new(7u32);
+        let renders = Arc::new(AtomicU32::new(0));
+        let dashboard = Dashboard { data: Entity::clone(&data), renders: Arc::clone(&renders) };
+        let root: Entity<dyn AnyComponent> =
+            Entity::from_arc(Arc::new(crate::state::RwLock::new(dashboard)) as Arc<crate::state::RwLock<dyn AnyComponent>>);
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(4, 1)).unwrap();
+        let mut cache = crate::damage::RenderCache::default();
+        let mut draw = || {
+            let frame = terminal.draw(|frame| Application::<()>::render_root(&app, &root, frame, &mut cache)).unwrap();
+            (frame.buffer[(0, 0)].symbol().to_string(), renders.load(Ordering::SeqCst))
         };
 
-        let result = rt.block_on(async move {
-            self.run_loop(app_context, actual_root, re_render_rx).await
-        });
+        assert_eq!(draw(), ("7".to_string(), 1));
+        assert_eq!(draw(), ("7".to_string(), 1));
+        data.update(|v| *v = 8).unwrap();
+        assert_eq!(draw(), ("8".to_string(), 2));
+        assert_eq!(draw(), ("8".to_string(), 2));
+    }
 
-        // Ensure we don't hang forever on background tasks (like infinite loops in components)
-        rt.shutdown_timeout(Duration::from_millis(100));
+    #[tokio::test]
+    async fn test_events_redraw_only_when_something_changed() {
+        struct Page {
+            data: Entity<u32>,
+            cursor: u32,
+            opted_out: bool,
+        }
 
-        result
+        impl Component for Page {
+            fn render(&mut self, _frame: &mut Frame, _cx: &mut Context<Self>) {}
+
+            fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+                match event {
+                    Event::Key(key) if key.code == crossterm::event::KeyCode::Char('u') => {
+                        let _ = self.data.update(|v| *v += 1);
+                    }
+                    Event::Key(key) if key.code == crossterm::event::KeyCode::Char('q') => return Some(Action::Quit),
+                    Event::Key(_) => self.cursor += 1,
+                    _ => {}
+                }
+                None
+            }
+
+            fn refresh_on_event(&self) -> bool {
+                self.opted_out
+            }
+        }
+
+        let (app, mut refresh, _inject) = AppContext::new();
+        let page = Page { data: Entity::new(0), cursor: 0, opted_out: false };
+        let root: Entity<dyn AnyComponent> =
+            Entity::from_arc(Arc::new(crate::state::RwLock::new(page)) as Arc<crate::state::RwLock<dyn AnyComponent>>);
+        let mut press = |c: char| {
+            let key = Event::Key(crossterm::event::KeyEvent::from(crossterm::event::KeyCode::Char(c)));
+            let dispatched = Application::<()>::dispatch_event(&app, &root, key).unwrap();
+            (dispatched.quit, dispatched.redraw, refresh.try_recv().is_ok())
+        };
+
+        assert_eq!(press('x'), (false, false, false));
+        assert_eq!(press('u'), (false, true, true));
+        assert_eq!(press('q'), (true, true, true));
+
+        root.update(|page| (page as &mut dyn Any).downcast_mut::<Page>().unwrap().opted_out = true).unwrap();
+        assert_eq!(press('x'), (false, true, true));
     }
 
-    async fn run_loop(&self, app: AppContext, root: Entity<dyn AnyComponent>, re_render_rx: mpsc::UnboundedReceiver<()>) -> anyhow::Result<()> {
-        enable_raw_mode()?;
-        let mut stdout = stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture, event::EnableFocusChange)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
+    #[test]
+    fn test_dispatched_actions_reach_their_handler() {
+        #[derive(Debug)]
+        struct Save(&'static str);
 
-        // Lifecycle: Call on_mount (first time) and on_enter (entering view) on the root component
-        {
-            let weak = root.downgrade();
-            let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(&app), weak);
-            root.update(|comp| {
-                comp.on_mount_any(&mut cx);
-                comp.on_enter_any(&mut cx);
-            }).map_err(|_| anyhow::anyhow!("Root mutex poisoned during on_mount"))?;
+        struct Editor;
+
+        impl Component for Editor {
+            fn render(&mut self, _frame: &mut Frame, _cx: &mut Context<Self>) {}
+
+            fn handle_event(&mut self, _event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+                Some(Action::dispatch(Save("notes.txt")))
+            }
         }
 
-        let result = self.run_app_loop(app, &mut terminal, root, re_render_rx).await;
+        let (app, _refresh, _inject) = AppContext::new();
+        let saved = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&saved);
+        app.on_action(move |save: &Save, _cx| log.lock().unwrap().push(save.0));
+        let root: Entity<dyn AnyComponent> =
+            Entity::from_arc(Arc::new(crate::state::RwLock::new(Editor)) as Arc<crate::state::RwLock<dyn AnyComponent>>);
+
+        let dispatched = Application::<()>::dispatch_event(&app, &root, Event::FocusGained).unwrap();
+        assert!(!dispatched.quit);
+        assert_eq!(*saved.lock().unwrap(), ["notes.txt"]);
+        let action: Action = Action::dispatch(Save("a"));
+        assert_eq!(action, action.clone());
+        assert_ne!(action, Action::dispatch(Save("a")));
+    }
 
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture,
-            event::DisableFocusChange
-        )?;
-        terminal.show_cursor()?;
+    #[test]
+    fn test_custom_events_are_dispatched_with_typed_payloads() {
+        struct Opener {
+            opened: Vec<String>,
+        }
 
-        result
+        impl Component for Opener {
+            fn render(&mut self, frame: &mut Frame, _cx: &mut Context<Self>) {
+                frame.render_widget(ratatui::widgets::Paragraph::new(self.opened.join(",")), frame.area());
+            }
+
+            fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+                match &event {
+                    Event::Key(_) => cx.emit_custom("opened", std::path::PathBuf::from("notes.txt")),
+                    Event::Custom(custom) if custom.name() == "opened" => {
+                        assert!(custom.is::<std::path::PathBuf>() && event.app::<std::path::PathBuf>().is_none());
+                        let path = event.custom::<std::path::PathBuf>()?;
+                        self.opened.push(path.display().to_string());
+                    }
+                    _ => {}
+                }
+                None
+            }
+        }
+
+        let mut harness = crate::testing::Harness::with_size(Opener { opened: Vec::new() }, 20, 1);
+        harness.type_str("o");
+        assert_eq!(harness.text(), "notes.txt");
     }
 
-    async fn run_app_loop(
-        &self,
-        app: AppContext,
-        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-        root: Entity<dyn AnyComponent>,
-        mut re_render_rx: mpsc::UnboundedReceiver<()>,
-    ) -> anyhow::Result<()> {
-        // Initial render
-        let _ = app.re_render_tx.send(());
+    #[tokio::test]
+    async fn test_typed_app_events_round_trip() {
+        #[derive(Debug)]
+        enum Ping {
+            Hello(&'static str),
+        }
 
-        // Dedicated event polling task to avoid blocking the main loop
-        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
-        tokio::task::spawn_blocking(move || {
-            loop {
-                // Check if the main loop is still interested in events
-                if event_tx.is_closed() {
-                    break;
+        struct Page {
+            heard: Entity<Vec<&'static str>>,
+        }
+
+        impl Component for Page {
+            fn render(&mut self, _frame: &mut Frame, _cx: &mut Context<Self>) {}
+
+            fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+                if let Some(Ping::Hello(from)) = event.app::<Ping>() {
+                    let _ = self.heard.update(|heard| heard.push(from));
                 }
+                if matches!(event, Event::Key(_)) {
+                    cx.emit_app_event(Ping::Hello("key")).unwrap();
+                }
+                None
+            }
+        }
 
-                // Poll at ~60fps (16.67ms) for smooth animations
-                match event::poll(Duration::from_millis(16)) {
-                    Ok(true) => {
-                        if let Ok(e) = event::read() {
-                            if event_tx.send(e).is_err() {
-                                break;
-                            }
-                        }
+        let heard = Entity::new(Vec::new());
+        let page = Page { heard: Entity::clone(&heard) };
+        let (cx, mut app) = Application::new()
+            .with_app_event::<Ping>()
+            .into_parts(|cx| cx.set_root(page).map_err(Into::into))
+            .unwrap();
+        cx.emit_app_event(Ping::Hello("setup")).unwrap();
+        let error = cx.emit_app_event(7u32).unwrap_err();
+        assert!(matches!(error, crate::Error::AppEventMismatch { found: "u32", .. }), "{error}");
+
+        let key = CrosstermEvent::Key(crossterm::event::KeyEvent::from(crossterm::event::KeyCode::Char('x')));
+        app.step(key).unwrap();
+        assert_eq!(heard.read(Vec::clone).unwrap(), ["setup", "key"]);
+    }
+
+    #[tokio::test]
+    async fn test_app_loop_driven_step_by_step() {
+        struct Page {
+            count: u32,
+        }
+
+        impl Component for Page {
+            fn render(&mut self, frame: &mut Frame, _cx: &mut Context<Self>) {
+                frame.render_widget(ratatui::widgets::Paragraph::new(self.count.to_string()), frame.area());
+            }
+
+            fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+                if let Some(count) = event.app::<u32>() {
+                    self.count += count;
+                    return Some(Action::Noop);
+                }
+                match event {
+                    Event::Key(key) if key.code == crossterm::event::KeyCode::Char('u') => {
+                        cx.emit_app_event(1u32).unwrap();
+                        None
                     }
-                    Ok(false) => {}
-                    Err(_) => break,
+                    Event::Key(key) if key.code == crossterm::event::KeyCode::Char('q') => Some(Action::Quit),
+                    _ => None,
                 }
             }
-        });
+        }
 
-        loop {
-            tokio::select! {
-                // Prioritize event handling for lower latency
-                biased;
+        let (_cx, mut app) = Application::new()
+            .with_app_event::<u32>()
+            .into_parts(|cx| cx.set_root(Page { count: 0 }).map_err(Into::into))
+            .unwrap();
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(4, 1)).unwrap();
+        let key = |c: char| CrosstermEvent::Key(crossterm::event::KeyEvent::from(crossterm::event::KeyCode::Char(c)));
+        let mut draw = |app: &mut AppLoop<u32>| {
+            assert!(app.needs_redraw());
+            app.draw(&mut terminal).unwrap();
+            terminal.backend().buffer().content()[0].symbol().to_string()
+        };
 
-                Some(crossterm_event) = event_rx.recv() => {
-                    let internal_event = match crossterm_event {
-                        CrosstermEvent::Key(key) if key.kind == KeyEventKind::Press => Some(Event::Key(key)),
-                        CrosstermEvent::Mouse(mouse) => Some(Event::Mouse(mouse)),
-                        CrosstermEvent::Resize(w, h) => Some(Event::Resize(w, h)),
-                        CrosstermEvent::FocusGained => Some(Event::FocusGained),
-                        CrosstermEvent::FocusLost => Some(Event::FocusLost),
-                        CrosstermEvent::Paste(s) => Some(Event::Paste(s)),
-                        _ => None,
-                    };
+        assert_eq!(draw(&mut app), "0");
+        assert!(!app.needs_redraw());
+        // The app event emitted while handling the key is delivered in the same step
+        assert_eq!(app.step(key('u')).unwrap(), Step::Continue);
+        assert_eq!(draw(&mut app), "1");
+        assert_eq!(app.step(key('q')).unwrap(), Step::Quit);
+    }
 
-                    if let Some(event) = internal_event {
-                        let weak = root.downgrade();
-                        let mut cx = EventContext::<dyn AnyComponent>::new(AppContext::clone(&app), weak);
-
-                        let action = root.update(|comp| {
-                            comp.handle_event_any(event, &mut cx)
-                        }).map_err(|_| anyhow::anyhow!("Root mutex poisoned during event"))?;
-
-                        app.refresh(); // Trigger refresh after any event handling
-
-                        if let Some(action) = action {
-                            match action {
-                                Action::Quit => {
-                                    let weak = root.downgrade();
-                                    let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(&app), weak);
-                                    root.update(|comp| comp.on_shutdown_any(&mut cx))
-                                        .map_err(|_| anyhow::anyhow!("Root mutex poisoned during shutdown"))?;
-                                    return Ok(());
-                                }
-                                _ => {}
-                            }
-                        }
+    #[tokio::test]
+    async fn test_injected_events_are_measured() {
+        struct Page;
+
+        impl Component for Page {
+            fn render(&mut self, _frame: &mut Frame, _cx: &mut Context<Self>) {}
+
+            fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+                event.app::<u32>().map(|_| Action::Noop)
+            }
+        }
+
+        let (cx, mut app) = Application::new()
+            .with_app_event::<u32>()
+            .into_parts(|cx| cx.set_root(Page).map_err(Into::into))
+            .unwrap();
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(4, 1)).unwrap();
+        app.draw(&mut terminal).unwrap();
+        assert_eq!(cx.metrics().samples, 0);
+
+        cx.emit_app_event(1u32).unwrap();
+        app.tick().unwrap();
+        app.draw(&mut terminal).unwrap();
+        assert_eq!(cx.metrics().samples, 1);
+    }
+
+    #[tokio::test]
+    async fn test_drag_card_between_columns() {
+        use crate::ChildView;
+        use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+        #[derive(Default)]
+        struct Column {
+            cards: Vec<char>,
+        }
+
+        impl Component for Column {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                let area = cx.area(frame);
+                let cards: String = self.cards.iter().collect();
+                frame.buffer_mut().set_string(area.x, area.y, cards, ratatui::style::Style::default());
+            }
+
+            fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+                match event {
+                    Event::Mouse(mouse) if mouse.kind == MouseEventKind::Drag(MouseButton::Left) && !cx.dragging() => {
+                        let (column, _) = cx.local_position(mouse.column, mouse.row)?;
+                        let card = self.cards.get(column as usize).copied()?;
+                        self.cards.retain(|c| *c != card);
+                        cx.start_drag(card);
+                        Some(Action::Noop)
                     }
+                    Event::Drop(_) => {
+                        self.cards.push(*event.dropped::<char>()?);
+                        Some(Action::Noop)
+                    }
+                    _ => None,
                 }
+            }
+        }
 
-                _ = re_render_rx.recv() => {
-                    // Drain all pending refresh requests to compact them into a single frame
-                    while re_render_rx.try_recv().is_ok() {}
-
-                    let weak = root.downgrade();
-                    terminal.draw(|frame| {
-                        app.frame_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                        let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(&app), weak);
-                        root.update(|comp| comp.render_any(frame, &mut cx))
-                            .expect("Root mutex poisoned during render");
-                    })?;
+        struct Board {
+            todo: ChildView<Column>,
+            done: ChildView<Column>,
+        }
+
+        impl Component for Board {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                cx.mount_child(&mut self.todo, frame, Rect::new(0, 0, 5, 1));
+                cx.mount_child(&mut self.done, frame, Rect::new(5, 0, 5, 1));
+            }
+
+            fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+                self.todo.handle_event(event.clone(), cx).or(self.done.handle_event(event, cx))
+            }
+        }
+
+        let board = Board { todo: ChildView::new(Column { cards: vec!['a', 'b'] }), done: ChildView::new(Column::default()) };
+        let (cx, mut app) = Application::new().into_parts(|cx| cx.set_root(board).map_err(Into::into)).unwrap();
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(10, 1)).unwrap();
+        let mouse = |kind, column| CrosstermEvent::Mouse(MouseEvent { kind, column, row: 0, modifiers: KeyModifiers::NONE });
+        let mut screen = |app: &mut AppLoop| {
+            app.draw(&mut terminal).unwrap();
+            terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect::<String>()
+        };
+
+        assert_eq!(screen(&mut app), "ab        ");
+        app.step(mouse(MouseEventKind::Drag(MouseButton::Left), 1)).unwrap();
+        assert!(cx.dragging());
+        app.step(mouse(MouseEventKind::Drag(MouseButton::Left), 6)).unwrap();
+        // The ghost follows the pointer
+        assert_eq!(screen(&mut app), "a       ⠿ ");
+        app.step(mouse(MouseEventKind::Up(MouseButton::Left), 6)).unwrap();
+        assert!(!cx.dragging());
+        assert_eq!(screen(&mut app), "a    b    ");
+    }
+
+    #[tokio::test]
+    async fn test_release_on_pressed_cell_delivers_counted_click() {
+        use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+        struct Clicks(Entity<Vec<u8>>);
+
+        impl Component for Clicks {
+            fn render(&mut self, _frame: &mut Frame, _cx: &mut Context<Self>) {}
+
+            fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+                if let Event::MouseClick { count, button: MouseButton::Left, .. } = event {
+                    let _ = self.0.update(|counts| counts.push(count));
                 }
+                None
             }
         }
+
+        let counts = Entity::new(Vec::new());
+        let root = Clicks(counts.clone());
+        let (_cx, mut app) = Application::new().into_parts(|cx| cx.set_root(root).map_err(Into::into)).unwrap();
+        let mouse = |kind, column| CrosstermEvent::Mouse(MouseEvent { kind, column, row: 0, modifiers: KeyModifiers::NONE });
+        for _ in 0..2 {
+            app.step(mouse(MouseEventKind::Down(MouseButton::Left), 3)).unwrap();
+            app.step(mouse(MouseEventKind::Up(MouseButton::Left), 3)).unwrap();
+        }
+        app.step(mouse(MouseEventKind::Down(MouseButton::Left), 3)).unwrap();
+        app.step(mouse(MouseEventKind::Up(MouseButton::Left), 4)).unwrap();
+        assert_eq!(counts.read(Vec::clone).unwrap(), [1, 2]);
     }
-}
 
-struct DummyView;
+    #[tokio::test]
+    async fn test_root_renders_after_async_mount() {
+        use crate::MountTask;
 
-impl Component for DummyView {
-    fn render(&mut self, frame: &mut ratatui::Frame, _cx: &mut Context<Self>) {
-        let paragraph = ratatui::widgets::Paragraph::new("No component set")
-            .alignment(ratatui::layout::Alignment::Center);
-        frame.render_widget(paragraph, frame.area());
+        struct Dashboard {
+            rows: Option<tokio::sync::oneshot::Receiver<u32>>,
+            loaded: u32,
+        }
+
+        impl Component for Dashboard {
+            fn on_mount_async(&mut self, cx: &mut Context<Self>) -> Option<MountTask<Self>> {
+                let rows = self.rows.take()?;
+                cx.mount_async(async move { rows.await.unwrap_or(0) }, |this, rows, _cx| this.loaded = rows)
+            }
+
+            fn render(&mut self, frame: &mut Frame, _cx: &mut Context<Self>) {
+                frame.buffer_mut().set_string(0, 0, format!("{} rows", self.loaded), ratatui::style::Style::default());
+            }
+
+            fn handle_event(&mut self, _event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+                Some(Action::Quit)
+            }
+        }
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let root = Dashboard { rows: Some(rx), loaded: 0 };
+        let (_cx, mut app) = Application::new()
+            .into_parts(|cx| {
+                cx.set_loading_placeholder(|frame, area| frame.buffer_mut().set_string(area.x, area.y, "wait", ratatui::style::Style::default()));
+                cx.set_root(root).map_err(Into::into)
+            })
+            .unwrap();
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(8, 1)).unwrap();
+        let mut screen = |app: &mut AppLoop| {
+            app.draw(&mut terminal).unwrap();
+            terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect::<String>()
+        };
+        assert_eq!(screen(&mut app), "wait    ");
+        let key = CrosstermEvent::Key(crossterm::event::KeyEvent::from(crossterm::event::KeyCode::Char('q')));
+        assert_eq!(app.step(key.clone()).unwrap(), Step::Continue, "input waits for the mount task");
+
+        tx.send(12).unwrap();
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(screen(&mut app), "12 rows ");
+        assert_eq!(app.step(key).unwrap(), Step::Quit);
+    }
+
+    #[tokio::test]
+    async fn test_resize_reaches_children_before_the_event() {
+        use crate::ChildView;
+
+        struct Canvas(Entity<Vec<String>>);
+
+        impl Component for Canvas {
+            fn on_resize(&mut self, width: u16, height: u16, _cx: &mut Context<Self>) {
+                let _ = self.0.update(|log| log.push(format!("canvas {width}x{height}")));
+            }
+
+            fn render(&mut self, _frame: &mut Frame, _cx: &mut Context<Self>) {}
+        }
+
+        struct Page {
+            canvas: ChildView<Canvas>,
+            log: Entity<Vec<String>>,
+        }
+
+        impl Component for Page {
+            fn on_resize(&mut self, width: u16, height: u16, cx: &mut Context<Self>) {
+                self.canvas.on_resize(width, height, cx);
+            }
+
+            fn render(&mut self, _frame: &mut Frame, _cx: &mut Context<Self>) {}
+
+            fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+                if let Event::Resize(width, height) = event {
+                    let _ = self.log.update(|log| log.push(format!("event {width}x{height}")));
+                }
+                None
+            }
+        }
+
+        let log = Entity::new(Vec::new());
+        let root = Page { canvas: ChildView::new(Canvas(log.clone())), log: log.clone() };
+        let (_cx, mut app) = Application::new().into_parts(|cx| cx.set_root(root).map_err(Into::into)).unwrap();
+        app.step(CrosstermEvent::Resize(100, 30)).unwrap();
+        assert_eq!(log.read(Vec::clone).unwrap(), ["canvas 100x30", "event 100x30"]);
+    }
+
+    #[test]
+    fn test_sessions_run_side_by_side_with_shared_state() {
+        struct Counter {
+            hits: Entity<u32>,
+        }
+
+        impl Component for Counter {
+            fn render(&mut self, _frame: &mut Frame, _cx: &mut Context<Self>) {}
+
+            fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+                match event {
+                    Event::Key(key) if key.code == crossterm::event::KeyCode::Char('u') => {
+                        let _ = self.hits.update(|v| *v += 1);
+                        None
+                    }
+                    Event::Key(key) if key.code == crossterm::event::KeyCode::Char('q') => Some(Action::Quit),
+                    _ => None,
+                }
+            }
+        }
+
+        let hits = Entity::new(0u32);
+        let runs: Vec<_> = (0..2)
+            .map(|_| {
+                let (session, handle) = crate::session::Session::new(std::io::sink(), 20, 5);
+                let app = Application::new().session(session).share(Entity::clone(&hits));
+                let run = app.spawn(|cx| {
+                    let hits = cx.get::<Entity<u32>>().expect("shared before setup");
+                    cx.set_root(Counter { hits })?;
+                    Ok(())
+                });
+                handle.input(b"uq");
+                (run, handle)
+            })
+            .collect();
+        for (run, _handle) in runs {
+            run.join().unwrap().unwrap();
+        }
+        assert_eq!(hits.read(|v| *v).unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_applications_list_their_own_entities() {
+        struct Holder {
+            _value: Entity<u32>,
+        }
+
+        impl Component for Holder {
+            fn render(&mut self, _frame: &mut Frame, _cx: &mut Context<Self>) {}
+        }
+
+        let parts = |value| Application::new().into_parts(move |cx| cx.set_root(Holder { _value: Entity::new(value) }).map_err(Into::into)).unwrap();
+        let ((first, _first_loop), (second, _second_loop)) = (parts(1), parts(2));
+        let elsewhere = {
+            let first = first.clone();
+            std::thread::spawn(move || first.new_entity(3u32)).join().unwrap()
+        };
+        let unrelated = Entity::new(4u32);
+        let values = |cx: &AppContext| cx.live_entities().iter().filter(|(_, name)| *name == "u32").count();
+        let expected = |count: usize| if crate::state::TRACK_LIVE { count } else { 0 };
+        assert_eq!(values(&first), expected(2));
+        assert_eq!(values(&second), expected(1));
+        drop((elsewhere, unrelated));
+        assert_eq!(values(&first), expected(1));
+    }
+
+    #[test]
+    fn test_exit_signals_shutdown_and_joins_critical_tasks() {
+        let (session, _) = crate::session::Session::new(std::io::sink(), 20, 5);
+        let saved = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let done = Arc::clone(&saved);
+        Application::new()
+            .session(session)
+            .run(move |cx| {
+                cx.spawn_critical("save", move |cx| async move {
+                    cx.shutdown_token().cancelled().await;
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    done.store(true, std::sync::atomic::Ordering::SeqCst);
+                });
+                Ok(())
+            })
+            .unwrap();
+        assert!(saved.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_run_on_existing_runtime() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        // Without a connection the session's input closes at once, ending the app
+        let (session, _) = crate::session::Session::new(std::io::sink(), 20, 5);
+        let task = Arc::new(Mutex::new(None));
+        let spawned = Arc::clone(&task);
+        Application::new()
+            .session(session)
+            .run_on(rt.handle().clone(), move |_cx| {
+                *spawned.lock().unwrap() = Some(tokio::spawn(async { 7 }));
+                Ok(())
+            })
+            .unwrap();
+        // Tasks ran on the given runtime, which outlives the application
+        let task = task.lock().unwrap().take().unwrap();
+        assert_eq!(rt.block_on(task).unwrap(), 7);
     }
 }
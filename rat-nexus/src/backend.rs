@@ -0,0 +1,539 @@
+//! Terminal backends.
+//!
+//! The event loop draws through a `TerminalBackend`: a ratatui `Backend` that also knows
+//! how to take over and restore the terminal and where its input comes from. Crossterm
+//! is the default; with the `termion` or `termwiz` feature an application can run on
+//! one of those instead (see `Application::backend`), for platforms or setups where
+//! crossterm is unsuitable. Input from every backend is translated to crossterm's event
+//! types, so components see the same `Event`s whichever one draws.
+
+use std::io::{self, Stdout};
+
+use crossterm::event::{DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::{Backend, CrosstermBackend};
+
+use crate::capabilities::Capabilities;
+use crate::event_source::{CrosstermEvents, EventSource};
+use crate::graphics::GraphicsBackend;
+
+/// A ratatui backend the application can run on.
+pub trait TerminalBackend: Backend + Sized + 'static {
+    /// Take over the terminal: raw mode, the alternate screen and, as `capabilities`
+    /// allow, mouse capture and focus reporting.
+    fn enter(capabilities: &Capabilities) -> io::Result<Self>;
+
+    /// Restore the terminal left by `enter`.
+    fn leave(&mut self, capabilities: &Capabilities) -> io::Result<()>;
+
+    /// Write escape sequences (image protocols) straight to the terminal.
+    fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()>;
+
+    /// The input that goes with this backend, used unless the application has its own
+    /// `EventSource`.
+    fn events(&self) -> Box<dyn EventSource>;
+}
+
+/// Which backend `Application` runs on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BackendKind {
+    /// Crossterm, on every platform.
+    #[default]
+    Crossterm,
+    /// Termion, on Unix.
+    #[cfg(feature = "termion")]
+    Termion,
+    /// Termwiz, from the wezterm project.
+    #[cfg(feature = "termwiz")]
+    Termwiz,
+}
+
+impl TerminalBackend for CrosstermBackend<Stdout> {
+    fn enter(capabilities: &Capabilities) -> io::Result<Self> {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        crossterm::execute!(stdout, EnterAlternateScreen)?;
+        if capabilities.mouse {
+            crossterm::execute!(stdout, EnableMouseCapture)?;
+        }
+        if capabilities.focus_events {
+            crossterm::execute!(stdout, EnableFocusChange)?;
+        }
+        Ok(CrosstermBackend::new(stdout))
+    }
+
+    fn leave(&mut self, capabilities: &Capabilities) -> io::Result<()> {
+        disable_raw_mode()?;
+        if capabilities.focus_events {
+            crossterm::execute!(self, DisableFocusChange)?;
+        }
+        if capabilities.mouse {
+            crossterm::execute!(self, DisableMouseCapture)?;
+        }
+        crossterm::execute!(self, LeaveAlternateScreen)?;
+        self.show_cursor()
+    }
+
+    fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()> {
+        GraphicsBackend::write_raw(self, bytes)
+    }
+
+    fn events(&self) -> Box<dyn EventSource> {
+        Box::new(CrosstermEvents)
+    }
+}
+
+#[cfg(feature = "termion")]
+pub use self::termion::{Termion, TermionEvents};
+
+#[cfg(feature = "termion")]
+mod termion {
+    use std::io::Write;
+    use std::sync::mpsc::{self, RecvTimeoutError};
+    use std::time::Duration;
+
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use ratatui::backend::TermionBackend;
+    use ratatui::termion::event::{self as tevent, Key};
+    use ratatui::termion::input::{MouseTerminal, TermRead};
+    use ratatui::termion::raw::IntoRawMode;
+    use ratatui::termion::screen::IntoAlternateScreen;
+
+    use super::*;
+    use crate::event_source::Polled;
+
+    /// The termion backend. Raw mode and the alternate screen end when it is dropped.
+    pub type Termion = TermionBackend<Box<dyn Write + Send>>;
+
+    impl TerminalBackend for Termion {
+        fn enter(capabilities: &Capabilities) -> io::Result<Self> {
+            let screen = io::stdout().into_raw_mode()?.into_alternate_screen()?;
+            let out: Box<dyn Write + Send> = match capabilities.mouse {
+                true => Box::new(MouseTerminal::from(screen)),
+                false => Box::new(screen),
+            };
+            Ok(TermionBackend::new(out))
+        }
+
+        fn leave(&mut self, _capabilities: &Capabilities) -> io::Result<()> {
+            self.show_cursor()?;
+            Backend::flush(self)
+        }
+
+        fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()> {
+            GraphicsBackend::write_raw(self, bytes)
+        }
+
+        fn events(&self) -> Box<dyn EventSource> {
+            Box::new(TermionEvents::spawn())
+        }
+    }
+
+    impl GraphicsBackend for Termion {
+        fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()> {
+            self.write_all(bytes)?;
+            Write::flush(self)
+        }
+    }
+
+    /// Input read from stdin with termion, on a thread of its own. Termion has no
+    /// resize events, so the terminal size is compared on every poll instead.
+    pub struct TermionEvents {
+        rx: mpsc::Receiver<io::Result<tevent::Event>>,
+        size: Option<(u16, u16)>,
+        pressed: MouseButton,
+    }
+
+    impl TermionEvents {
+        /// Start reading stdin. The reader thread lives until stdin closes.
+        pub fn spawn() -> Self {
+            let (tx, rx) = mpsc::channel();
+            std::thread::spawn(move || {
+                for event in io::stdin().events() {
+                    if tx.send(event).is_err() {
+                        break;
+                    }
+                }
+            });
+            Self { rx, size: ratatui::termion::terminal_size().ok(), pressed: MouseButton::Left }
+        }
+
+        fn convert(&mut self, event: tevent::Event) -> Option<Event> {
+            match event {
+                tevent::Event::Key(key) => key_event(key).map(Event::Key),
+                tevent::Event::Mouse(mouse) => Some(Event::Mouse(self.mouse_event(mouse))),
+                tevent::Event::Unsupported(_) => None,
+            }
+        }
+
+        fn mouse_event(&mut self, mouse: tevent::MouseEvent) -> MouseEvent {
+            let (kind, x, y) = match mouse {
+                tevent::MouseEvent::Press(button, x, y) => {
+                    let kind = match button {
+                        tevent::MouseButton::WheelUp => MouseEventKind::ScrollUp,
+                        tevent::MouseButton::WheelDown => MouseEventKind::ScrollDown,
+                        tevent::MouseButton::WheelLeft => MouseEventKind::ScrollLeft,
+                        tevent::MouseButton::WheelRight => MouseEventKind::ScrollRight,
+                        tevent::MouseButton::Left | tevent::MouseButton::Right | tevent::MouseButton::Middle => {
+                            self.pressed = match button {
+                                tevent::MouseButton::Right => MouseButton::Right,
+                                tevent::MouseButton::Middle => MouseButton::Middle,
+                                _ => MouseButton::Left,
+                            };
+                            MouseEventKind::Down(self.pressed)
+                        }
+                    };
+                    (kind, x, y)
+                }
+                tevent::MouseEvent::Release(x, y) => (MouseEventKind::Up(self.pressed), x, y),
+                tevent::MouseEvent::Hold(x, y) => (MouseEventKind::Drag(self.pressed), x, y),
+            };
+            MouseEvent { kind, column: x.saturating_sub(1), row: y.saturating_sub(1), modifiers: KeyModifiers::NONE }
+        }
+
+        fn resized(&mut self) -> Option<Event> {
+            let size = ratatui::termion::terminal_size().ok();
+            if size == self.size {
+                return None;
+            }
+            self.size = size;
+            size.map(|(w, h)| Event::Resize(w, h))
+        }
+    }
+
+    impl EventSource for TermionEvents {
+        fn poll_event(&mut self, timeout: Duration) -> io::Result<Polled> {
+            if let Some(resize) = self.resized() {
+                return Ok(Polled::Event(resize));
+            }
+            match self.rx.recv_timeout(timeout) {
+                Ok(event) => Ok(self.convert(event?).map_or(Polled::Timeout, Polled::Event)),
+                Err(RecvTimeoutError::Timeout) => Ok(Polled::Timeout),
+                Err(RecvTimeoutError::Disconnected) => Ok(Polled::Closed),
+            }
+        }
+    }
+
+    fn key_event(key: Key) -> Option<KeyEvent> {
+        let none = KeyModifiers::NONE;
+        let (code, modifiers) = match key {
+            Key::Backspace => (KeyCode::Backspace, none),
+            Key::Left => (KeyCode::Left, none),
+            Key::ShiftLeft => (KeyCode::Left, KeyModifiers::SHIFT),
+            Key::AltLeft => (KeyCode::Left, KeyModifiers::ALT),
+            Key::CtrlLeft => (KeyCode::Left, KeyModifiers::CONTROL),
+            Key::Right => (KeyCode::Right, none),
+            Key::ShiftRight => (KeyCode::Right, KeyModifiers::SHIFT),
+            Key::AltRight => (KeyCode::Right, KeyModifiers::ALT),
+            Key::CtrlRight => (KeyCode::Right, KeyModifiers::CONTROL),
+            Key::Up => (KeyCode::Up, none),
+            Key::ShiftUp => (KeyCode::Up, KeyModifiers::SHIFT),
+            Key::AltUp => (KeyCode::Up, KeyModifiers::ALT),
+            Key::CtrlUp => (KeyCode::Up, KeyModifiers::CONTROL),
+            Key::Down => (KeyCode::Down, none),
+            Key::ShiftDown => (KeyCode::Down, KeyModifiers::SHIFT),
+            Key::AltDown => (KeyCode::Down, KeyModifiers::ALT),
+            Key::CtrlDown => (KeyCode::Down, KeyModifiers::CONTROL),
+            Key::Home => (KeyCode::Home, none),
+            Key::CtrlHome => (KeyCode::Home, KeyModifiers::CONTROL),
+            Key::End => (KeyCode::End, none),
+            Key::CtrlEnd => (KeyCode::End, KeyModifiers::CONTROL),
+            Key::PageUp => (KeyCode::PageUp, none),
+            Key::PageDown => (KeyCode::PageDown, none),
+            Key::BackTab => (KeyCode::BackTab, KeyModifiers::SHIFT),
+            Key::Delete => (KeyCode::Delete, none),
+            Key::Insert => (KeyCode::Insert, none),
+            Key::F(n) => (KeyCode::F(n), none),
+            Key::Char('\n' | '\r') => (KeyCode::Enter, none),
+            Key::Char('\t') => (KeyCode::Tab, none),
+            Key::Char(c) if c.is_uppercase() => (KeyCode::Char(c), KeyModifiers::SHIFT),
+            Key::Char(c) => (KeyCode::Char(c), none),
+            Key::Alt(c) => (KeyCode::Char(c), KeyModifiers::ALT),
+            Key::Ctrl(c) => (KeyCode::Char(c), KeyModifiers::CONTROL),
+            Key::Null => (KeyCode::Null, none),
+            Key::Esc => (KeyCode::Esc, none),
+            _ => return None,
+        };
+        Some(KeyEvent::new(code, modifiers))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_termion_keys_and_mouse() {
+            assert_eq!(key_event(Key::Char('\n')), Some(KeyEvent::from(KeyCode::Enter)));
+            assert_eq!(key_event(Key::Ctrl('c')), Some(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+            assert_eq!(key_event(Key::Char('A')), Some(KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT)));
+
+            let (_tx, rx) = mpsc::channel();
+            let mut events = TermionEvents { rx, size: None, pressed: MouseButton::Left };
+            let down = events.mouse_event(tevent::MouseEvent::Press(tevent::MouseButton::Right, 3, 2));
+            assert_eq!((down.kind, down.column, down.row), (MouseEventKind::Down(MouseButton::Right), 2, 1));
+            let up = events.mouse_event(tevent::MouseEvent::Release(3, 2));
+            assert_eq!(up.kind, MouseEventKind::Up(MouseButton::Right));
+        }
+    }
+}
+
+#[cfg(feature = "termwiz")]
+pub use self::termwiz::{Termwiz, TermwizEvents};
+
+#[cfg(feature = "termwiz")]
+mod termwiz {
+    use std::io::Write;
+    use std::sync::{Arc, Mutex, MutexGuard};
+    use std::time::{Duration, Instant};
+
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use ratatui::backend::{ClearType, TermwizBackend, WindowSize};
+    use ratatui::buffer::Cell;
+    use ratatui::layout::{Position, Size};
+    use ratatui::termwiz::caps::{Capabilities as TermwizCaps, ProbeHints};
+    use ratatui::termwiz::input::{InputEvent, KeyCode as TKey, Modifiers, MouseButtons};
+    use ratatui::termwiz::terminal::buffered::BufferedTerminal;
+    use ratatui::termwiz::terminal::{SystemTerminal, Terminal as _};
+
+    use super::*;
+    use crate::event_source::Polled;
+
+    /// How long input polling waits between checks while the backend is shared.
+    const INPUT_SLICE: Duration = Duration::from_millis(4);
+
+    fn other(err: impl std::fmt::Display) -> io::Error {
+        io::Error::other(err.to_string())
+    }
+
+    /// The termwiz backend. Termwiz reads input through the same terminal it draws
+    /// to, so the backend is shared with its `TermwizEvents`.
+    #[derive(Clone)]
+    pub struct Termwiz {
+        inner: Arc<Mutex<TermwizBackend>>,
+    }
+
+    impl Termwiz {
+        fn lock(&self) -> io::Result<MutexGuard<'_, TermwizBackend>> {
+            self.inner.lock().map_err(|_| other("termwiz backend lock poisoned"))
+        }
+    }
+
+    impl Backend for Termwiz {
+        fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+        where
+            I: Iterator<Item = (u16, u16, &'a Cell)>,
+        {
+            self.lock()?.draw(content)
+        }
+
+        fn append_lines(&mut self, n: u16) -> io::Result<()> {
+            self.lock()?.append_lines(n)
+        }
+
+        fn hide_cursor(&mut self) -> io::Result<()> {
+            self.lock()?.hide_cursor()
+        }
+
+        fn show_cursor(&mut self) -> io::Result<()> {
+            self.lock()?.show_cursor()
+        }
+
+        fn get_cursor_position(&mut self) -> io::Result<Position> {
+            self.lock()?.get_cursor_position()
+        }
+
+        fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+            self.lock()?.set_cursor_position(position)
+        }
+
+        fn clear(&mut self) -> io::Result<()> {
+            self.lock()?.clear()
+        }
+
+        fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+            self.lock()?.clear_region(clear_type)
+        }
+
+        fn size(&self) -> io::Result<Size> {
+            self.lock()?.size()
+        }
+
+        fn window_size(&mut self) -> io::Result<WindowSize> {
+            self.lock()?.window_size()
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Backend::flush(&mut *self.lock()?)
+        }
+    }
+
+    impl TerminalBackend for Termwiz {
+        fn enter(capabilities: &Capabilities) -> io::Result<Self> {
+            let hints = ProbeHints::new_from_env().mouse_reporting(Some(capabilities.mouse));
+            let caps = TermwizCaps::new_with_hints(hints).map_err(other)?;
+            let mut terminal = BufferedTerminal::new(SystemTerminal::new(caps).map_err(other)?).map_err(other)?;
+            terminal.terminal().set_raw_mode().map_err(other)?;
+            terminal.terminal().enter_alternate_screen().map_err(other)?;
+            Ok(Self { inner: Arc::new(Mutex::new(TermwizBackend::with_buffered_terminal(terminal))) })
+        }
+
+        fn leave(&mut self, _capabilities: &Capabilities) -> io::Result<()> {
+            let mut backend = self.lock()?;
+            backend.show_cursor()?;
+            Backend::flush(&mut *backend)?;
+            let terminal = backend.buffered_terminal_mut().terminal();
+            terminal.exit_alternate_screen().map_err(other)?;
+            terminal.set_cooked_mode().map_err(other)
+        }
+
+        fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()> {
+            GraphicsBackend::write_raw(self, bytes)
+        }
+
+        fn events(&self) -> Box<dyn EventSource> {
+            Box::new(TermwizEvents { backend: self.clone(), buttons: MouseButtons::NONE })
+        }
+    }
+
+    impl GraphicsBackend for Termwiz {
+        /// Termwiz offers no raw output; the sequences follow the flushed frame on stdout.
+        fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()> {
+            let mut out = io::stdout();
+            out.write_all(bytes)?;
+            out.flush()
+        }
+    }
+
+    /// Input read through the termwiz terminal shared with the `Termwiz` backend.
+    pub struct TermwizEvents {
+        backend: Termwiz,
+        buttons: MouseButtons,
+    }
+
+    impl EventSource for TermwizEvents {
+        fn poll_event(&mut self, timeout: Duration) -> io::Result<Polled> {
+            // Poll in short slices so drawing is never kept waiting on the lock
+            let deadline = Instant::now() + timeout;
+            loop {
+                let input = {
+                    let mut backend = self.backend.lock()?;
+                    let terminal = backend.buffered_terminal_mut();
+                    let input = terminal.terminal().poll_input(Some(Duration::ZERO)).map_err(other)?;
+                    if let Some(InputEvent::Resized { cols, rows }) = &input {
+                        terminal.resize(*cols, *rows);
+                    }
+                    input
+                };
+                if let Some(event) = input.and_then(|input| self.convert(input)) {
+                    return Ok(Polled::Event(event));
+                }
+                let now = Instant::now();
+                if now >= deadline {
+                    return Ok(Polled::Timeout);
+                }
+                std::thread::sleep(INPUT_SLICE.min(deadline - now));
+            }
+        }
+    }
+
+    impl TermwizEvents {
+        fn convert(&mut self, input: InputEvent) -> Option<Event> {
+            match input {
+                InputEvent::Key(key) => key_event(key.key, key.modifiers).map(Event::Key),
+                InputEvent::Mouse(mouse) => Some(Event::Mouse(self.mouse_event(mouse))),
+                InputEvent::Resized { cols, rows } => Some(Event::Resize(cols as u16, rows as u16)),
+                InputEvent::Paste(text) => Some(Event::Paste(text)),
+                InputEvent::PixelMouse(_) | InputEvent::Wake => None,
+            }
+        }
+
+        /// Termwiz reports which buttons are held; crossterm reports transitions.
+        fn mouse_event(&mut self, mouse: ratatui::termwiz::input::MouseEvent) -> MouseEvent {
+            let buttons = mouse.mouse_buttons;
+            let held = |set: &MouseButtons| {
+                [(MouseButtons::LEFT, MouseButton::Left), (MouseButtons::RIGHT, MouseButton::Right), (MouseButtons::MIDDLE, MouseButton::Middle)]
+                    .into_iter()
+                    .find(|(flag, _)| set.contains(flag.clone()))
+                    .map(|(_, button)| button)
+            };
+            let positive = buttons.contains(MouseButtons::WHEEL_POSITIVE);
+            let kind = if buttons.contains(MouseButtons::VERT_WHEEL) {
+                if positive { MouseEventKind::ScrollUp } else { MouseEventKind::ScrollDown }
+            } else if buttons.contains(MouseButtons::HORZ_WHEEL) {
+                if positive { MouseEventKind::ScrollLeft } else { MouseEventKind::ScrollRight }
+            } else {
+                let kind = match (held(&self.buttons), held(&buttons)) {
+                    (None, Some(button)) => MouseEventKind::Down(button),
+                    (Some(button), None) => MouseEventKind::Up(button),
+                    (Some(old), Some(new)) if old != new => MouseEventKind::Down(new),
+                    (Some(_), Some(button)) => MouseEventKind::Drag(button),
+                    (None, None) => MouseEventKind::Moved,
+                };
+                self.buttons = buttons;
+                kind
+            };
+            MouseEvent {
+                kind,
+                column: mouse.x.saturating_sub(1),
+                row: mouse.y.saturating_sub(1),
+                modifiers: modifiers(mouse.modifiers),
+            }
+        }
+    }
+
+    fn modifiers(mods: Modifiers) -> KeyModifiers {
+        let mut out = KeyModifiers::NONE;
+        for (flags, modifier) in [
+            (Modifiers::SHIFT | Modifiers::LEFT_SHIFT | Modifiers::RIGHT_SHIFT, KeyModifiers::SHIFT),
+            (Modifiers::ALT | Modifiers::LEFT_ALT | Modifiers::RIGHT_ALT, KeyModifiers::ALT),
+            (Modifiers::CTRL | Modifiers::LEFT_CTRL | Modifiers::RIGHT_CTRL, KeyModifiers::CONTROL),
+            (Modifiers::SUPER, KeyModifiers::SUPER),
+        ] {
+            if mods.intersects(flags) {
+                out |= modifier;
+            }
+        }
+        out
+    }
+
+    fn key_event(key: TKey, mods: Modifiers) -> Option<KeyEvent> {
+        let modifiers = modifiers(mods);
+        let code = match key {
+            TKey::Char(c) => KeyCode::Char(c),
+            TKey::Enter => KeyCode::Enter,
+            TKey::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            TKey::Tab => KeyCode::Tab,
+            TKey::Backspace => KeyCode::Backspace,
+            TKey::Escape => KeyCode::Esc,
+            TKey::LeftArrow | TKey::ApplicationLeftArrow => KeyCode::Left,
+            TKey::RightArrow | TKey::ApplicationRightArrow => KeyCode::Right,
+            TKey::UpArrow | TKey::ApplicationUpArrow => KeyCode::Up,
+            TKey::DownArrow | TKey::ApplicationDownArrow => KeyCode::Down,
+            TKey::Home | TKey::KeyPadHome => KeyCode::Home,
+            TKey::End | TKey::KeyPadEnd => KeyCode::End,
+            TKey::PageUp | TKey::KeyPadPageUp => KeyCode::PageUp,
+            TKey::PageDown | TKey::KeyPadPageDown => KeyCode::PageDown,
+            TKey::Insert => KeyCode::Insert,
+            TKey::Delete => KeyCode::Delete,
+            TKey::Function(n) => KeyCode::F(n),
+            _ => return None,
+        };
+        Some(KeyEvent::new(code, modifiers))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_termwiz_keys() {
+            assert_eq!(key_event(TKey::Escape, Modifiers::NONE), Some(KeyEvent::from(KeyCode::Esc)));
+            assert_eq!(
+                key_event(TKey::Char('x'), Modifiers::LEFT_CTRL),
+                Some(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL))
+            );
+            assert_eq!(key_event(TKey::Tab, Modifiers::SHIFT).map(|key| key.code), Some(KeyCode::BackTab));
+            assert_eq!(key_event(TKey::Hyper, Modifiers::NONE), None);
+        }
+    }
+}
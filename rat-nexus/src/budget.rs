@@ -0,0 +1,194 @@
+//! Per-component render time budget.
+//!
+//! In debug builds every component rendered through `ChildView`/`mount_child`, as an
+//! `AnyComponent` (the root, `AppShell` slots) or as a `define_app!` page is timed.
+//! Time spent in measured children is not counted against the parent, so a warning
+//! names the component that is actually slow. A component exceeding the budget is
+//! logged at warn level when it first does and, optionally, named in a banner along
+//! the bottom of the frame. Release builds skip the measurement entirely.
+
+use std::cell::Cell;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+/// Default render budget per component.
+pub const DEFAULT_BUDGET: Duration = Duration::from_millis(4);
+
+thread_local! {
+    /// Render time of the measured children of the component being measured.
+    static CHILDREN: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+}
+
+/// A component that rendered slower than the budget.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetWarning {
+    /// Type name of the component.
+    pub component: &'static str,
+    /// Render time, excluding measured children.
+    pub elapsed: Duration,
+    /// The budget in effect.
+    pub budget: Duration,
+}
+
+impl std::fmt::Display for BudgetWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} rendered in {:.1?} (budget {:?})", self.component, self.elapsed, self.budget)
+    }
+}
+
+struct BudgetInner {
+    budget: Duration,
+    overlay: bool,
+    warnings: Vec<BudgetWarning>,
+    previous: Vec<&'static str>,
+}
+
+/// Shared render time monitor, owned by the `AppContext`.
+#[derive(Clone)]
+pub struct FrameBudget {
+    inner: Arc<Mutex<BudgetInner>>,
+}
+
+impl Default for FrameBudget {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(BudgetInner {
+                budget: DEFAULT_BUDGET,
+                overlay: false,
+                warnings: Vec::new(),
+                previous: Vec::new(),
+            })),
+        }
+    }
+}
+
+impl FrameBudget {
+    /// Set the render time a single component may take.
+    pub fn set_budget(&self, budget: Duration) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.budget = budget;
+        }
+    }
+
+    /// Name over-budget components in a banner along the bottom of the frame.
+    pub fn set_overlay(&self, enabled: bool) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.overlay = enabled;
+        }
+    }
+
+    /// Components over budget in the current (or last completed) frame, slowest first.
+    pub fn warnings(&self) -> Vec<BudgetWarning> {
+        let mut warnings = self.inner.lock().map(|inner| inner.warnings.clone()).unwrap_or_default();
+        warnings.sort_by_key(|warning| std::cmp::Reverse(warning.elapsed));
+        warnings
+    }
+
+    /// Time `render` as the render of component `T`. Use it for children rendered
+    /// without `ChildView`; in release builds it just calls `render`.
+    pub fn measure<T: ?Sized, R>(&self, render: impl FnOnce() -> R) -> R {
+        if cfg!(debug_assertions) {
+            self.measure_named(std::any::type_name::<T>(), render)
+        } else {
+            render()
+        }
+    }
+
+    fn measure_named<R>(&self, component: &'static str, render: impl FnOnce() -> R) -> R {
+        let outer = CHILDREN.replace(Duration::ZERO);
+        let start = Instant::now();
+        let result = render();
+        let elapsed = start.elapsed();
+        let children = CHILDREN.replace(outer + elapsed);
+        self.record(component, elapsed.saturating_sub(children));
+        result
+    }
+
+    /// Start a new frame; warnings of the previous one are dropped.
+    pub(crate) fn begin_frame(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            let inner = &mut *inner;
+            inner.previous = inner.warnings.drain(..).map(|warning| warning.component).collect();
+        }
+    }
+
+    fn record(&self, component: &'static str, elapsed: Duration) {
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        if elapsed <= inner.budget {
+            return;
+        }
+        let warning = BudgetWarning { component, elapsed, budget: inner.budget };
+        // Log when a component goes over, not on every frame it stays over
+        if !inner.previous.contains(&component) {
+            log::warn!("{warning}");
+        }
+        inner.warnings.push(warning);
+    }
+
+    /// Draw the banner if enabled and a component is over budget.
+    pub(crate) fn render_overlay(&self, frame: &mut Frame) {
+        let overlay = self.inner.lock().map(|inner| inner.overlay).unwrap_or(false);
+        let Some(slowest) = overlay.then(|| self.warnings().into_iter().next()).flatten() else {
+            return;
+        };
+        let area = frame.area();
+        if area.height == 0 {
+            return;
+        }
+        let banner = Rect::new(area.x, area.bottom() - 1, area.width, 1);
+        let style = Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD);
+        frame.render_widget(Paragraph::new(format!(" ⏱ {slowest}")).style(style), banner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+    use crate::{ChildView, Component, Context};
+
+    #[derive(Default)]
+    struct Slow;
+
+    impl Component for Slow {
+        fn render(&mut self, _frame: &mut Frame, _cx: &mut Context<Self>) {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[derive(Default)]
+    struct Parent {
+        child: ChildView<Slow>,
+    }
+
+    impl Component for Parent {
+        fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+            let area = cx.area(frame);
+            cx.mount_child(&mut self.child, frame, area);
+        }
+    }
+
+    #[test]
+    fn test_warning_names_slow_child_not_parent() {
+        let mut harness = Harness::with_size(Parent::default(), 40, 2);
+        let budget = harness.app().frame_budget().clone();
+        budget.set_budget(Duration::from_millis(2));
+        harness.render();
+
+        let warnings = budget.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].component, std::any::type_name::<Slow>());
+        assert!(warnings[0].elapsed >= Duration::from_millis(5));
+
+        budget.set_budget(Duration::from_secs(1));
+        harness.render();
+        assert!(budget.warnings().is_empty());
+    }
+}
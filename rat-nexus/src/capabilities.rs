@@ -0,0 +1,385 @@
+//! Terminal capability detection.
+//!
+//! Detected once when the application starts and exposed via `cx.capabilities()`, so
+//! components can adapt to the console they run in. On Windows this also enables
+//! virtual terminal processing and identifies the legacy console host, which lacks
+//! braille glyphs and focus reporting.
+//!
+//! The color depth comes from `COLORTERM` and `TERM` and Unicode support from the
+//! locale. Without `COLORTERM`, any terminal not known to be limited to 16 colors is
+//! assumed to have the 256-color palette. Each frame is downgraded to what the terminal can show before it is
+//! flushed: RGB colors become the nearest 256- or 16-color entry and, without Unicode,
+//! box drawing, blocks, arrows and other glyphs become ASCII. Apps can draw with
+//! truecolor and Unicode everywhere and still look right over plain SSH or tmux.
+
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+use ratatui::symbols::Marker;
+
+use crate::contrast::{extended_rgb, XTERM_PALETTE};
+use crate::graphics::GraphicsProtocol;
+
+/// Environment variable that forces the block-glyph fallback for canvases and charts.
+pub const NO_BRAILLE_ENV: &str = "RAT_NEXUS_NO_BRAILLE";
+
+/// Environment variable that overrides the detected color depth: `16`, `256` or
+/// `truecolor`.
+pub const COLORS_ENV: &str = "RAT_NEXUS_COLORS";
+
+/// Environment variable that forces ASCII fallbacks for non-ASCII glyphs.
+pub const ASCII_ENV: &str = "RAT_NEXUS_ASCII";
+
+/// The 16 named colors, in ANSI order.
+const ANSI_COLORS: [Color; 16] = [
+    Color::Black, Color::Red, Color::Green, Color::Yellow,
+    Color::Blue, Color::Magenta, Color::Cyan, Color::Gray,
+    Color::DarkGray, Color::LightRed, Color::LightGreen, Color::LightYellow,
+    Color::LightBlue, Color::LightMagenta, Color::LightCyan, Color::White,
+];
+
+/// How many colors the terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// The 16 named ANSI colors.
+    Ansi16,
+    /// The xterm 256-color palette.
+    Ansi256,
+    /// 24-bit RGB.
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Parse a `COLORS_ENV` value.
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "16" | "ansi" => Some(ColorDepth::Ansi16),
+            "256" => Some(ColorDepth::Ansi256),
+            "truecolor" | "24bit" | "rgb" => Some(ColorDepth::TrueColor),
+            _ => None,
+        }
+    }
+}
+
+/// The platform family the terminal runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    /// Linux, macOS and other Unix-like systems.
+    Unix,
+    /// Windows with virtual terminal sequence support (Windows Terminal, modern conhost).
+    Windows,
+    /// Windows legacy console without virtual terminal sequences (WinAPI fallback).
+    WindowsLegacy,
+}
+
+/// What the current terminal supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Platform family.
+    pub platform: Platform,
+    /// ANSI/VT escape sequences are interpreted.
+    pub ansi: bool,
+    /// Braille glyphs render correctly (used by Canvas and Chart by default).
+    pub braille: bool,
+    /// Mouse capture is available.
+    pub mouse: bool,
+    /// Focus gained/lost reporting is available.
+    pub focus_events: bool,
+    /// Colors the terminal can show; richer colors are downgraded.
+    pub colors: ColorDepth,
+    /// Non-ASCII glyphs render correctly; without this they fall back to ASCII.
+    pub unicode: bool,
+    /// How images are drawn (see `widgets::Image`).
+    pub graphics: GraphicsProtocol,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            platform: Platform::Unix,
+            ansi: true,
+            braille: true,
+            mouse: true,
+            focus_events: true,
+            colors: ColorDepth::TrueColor,
+            unicode: true,
+            graphics: GraphicsProtocol::HalfBlocks,
+        }
+    }
+}
+
+impl Capabilities {
+    /// Probe the current terminal. On Windows this enables VT processing when possible.
+    pub fn detect() -> Self {
+        // Tries to enable ENABLE_VIRTUAL_TERMINAL_PROCESSING and reports success.
+        #[cfg(windows)]
+        let windows_ansi = Some(crossterm::ansi_support::supports_ansi());
+        #[cfg(not(windows))]
+        let windows_ansi = None;
+
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let mut caps = Self::from_console(windows_ansi, var);
+
+        caps.graphics = if caps.ansi { GraphicsProtocol::detect() } else { GraphicsProtocol::HalfBlocks };
+
+        if std::env::var_os(NO_BRAILLE_ENV).is_some() {
+            caps.braille = false;
+        }
+        caps
+    }
+
+    /// Capabilities of a console from the environment; `var` returns non-empty variables.
+    /// `windows_ansi` is `None` off Windows, and otherwise whether the Windows console
+    /// accepted virtual terminal sequences.
+    fn from_console(windows_ansi: Option<bool>, var: impl Fn(&str) -> Option<String>) -> Self {
+        let mut caps = Self::default();
+        if let Some(ansi) = windows_ansi {
+            caps.ansi = ansi;
+            caps.platform = if ansi { Platform::Windows } else { Platform::WindowsLegacy };
+            // Only Windows Terminal ships fonts with reliable braille coverage.
+            caps.braille = var("WT_SESSION").is_some();
+            caps.focus_events = ansi;
+        }
+        caps.colors = detect_colors(caps.platform, &var);
+        caps.unicode = caps.platform != Platform::WindowsLegacy && detect_unicode(&var);
+        caps.braille &= caps.unicode;
+        caps
+    }
+
+    /// Whether this is any Windows console.
+    pub fn is_windows(&self) -> bool {
+        matches!(self.platform, Platform::Windows | Platform::WindowsLegacy)
+    }
+
+    /// The best canvas marker for this terminal: braille where supported, half blocks otherwise.
+    pub fn canvas_marker(&self) -> Marker {
+        if self.braille { Marker::Braille } else { Marker::HalfBlock }
+    }
+
+    /// The best chart dataset marker for this terminal: braille where supported, dots otherwise.
+    pub fn chart_marker(&self) -> Marker {
+        if self.braille { Marker::Braille } else { Marker::Dot }
+    }
+
+    /// The closest color to `color` this terminal can show.
+    pub fn downgrade_color(&self, color: Color) -> Color {
+        match (self.colors, color) {
+            (ColorDepth::TrueColor, _) => color,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_indexed((r, g, b))),
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_ansi((r, g, b)),
+            (ColorDepth::Ansi16, Color::Indexed(i)) if i < 16 => ANSI_COLORS[usize::from(i)],
+            (ColorDepth::Ansi16, Color::Indexed(i)) => nearest_ansi(extended_rgb(i)),
+            _ => color,
+        }
+    }
+
+    /// Rewrite `buf` with colors and glyphs this terminal can show. Called on every
+    /// frame before it is flushed.
+    pub fn downgrade(&self, buf: &mut Buffer) {
+        if self.colors == ColorDepth::TrueColor && self.unicode {
+            return;
+        }
+        for cell in buf.content.iter_mut() {
+            cell.fg = self.downgrade_color(cell.fg);
+            cell.bg = self.downgrade_color(cell.bg);
+            if !self.unicode {
+                if let Some(ascii) = ascii_fallback(cell.symbol()) {
+                    cell.set_symbol(ascii);
+                }
+            }
+        }
+    }
+
+    /// Normalize a resize event. Windows consoles may report the screen buffer size
+    /// rather than the visible window, so the actual window size is queried instead.
+    pub fn normalize_resize(&self, width: u16, height: u16) -> (u16, u16) {
+        if self.is_windows() {
+            crossterm::terminal::size().unwrap_or((width, height))
+        } else {
+            (width, height)
+        }
+    }
+}
+
+/// Color depth from the environment; `var` returns non-empty variables.
+fn detect_colors(platform: Platform, var: impl Fn(&str) -> Option<String>) -> ColorDepth {
+    if let Some(depth) = var(COLORS_ENV).as_deref().and_then(ColorDepth::parse) {
+        return depth;
+    }
+    if var("COLORTERM").is_some_and(|value| value == "truecolor" || value == "24bit") {
+        return ColorDepth::TrueColor;
+    }
+    match var("TERM") {
+        Some(term) if term.ends_with("-direct") => ColorDepth::TrueColor,
+        // Only terminals known to stop at 16 colors are downgraded that far; everything
+        // else, including a bare `xterm`, gets the 256-color palette every emulator has
+        Some(term) if is_ansi16_term(&term) => ColorDepth::Ansi16,
+        Some(_) => ColorDepth::Ansi256,
+        // Windows Terminal and the modern console host set no TERM but support RGB
+        None if platform == Platform::Windows => ColorDepth::TrueColor,
+        None => ColorDepth::Ansi16,
+    }
+}
+
+/// Whether `TERM` names a terminal limited to the 16 ANSI colors.
+fn is_ansi16_term(term: &str) -> bool {
+    matches!(term, "dumb" | "ansi" | "linux" | "cons25" | "sun")
+        || term.starts_with("vt")
+        || term.ends_with("-16color")
+        || term.ends_with("-8color")
+}
+
+/// Whether the terminal and locale handle Unicode; `var` returns non-empty variables.
+fn detect_unicode(var: impl Fn(&str) -> Option<String>) -> bool {
+    if var(ASCII_ENV).is_some() {
+        return false;
+    }
+    if var("TERM").is_some_and(|term| matches!(term.as_str(), "dumb" | "vt100" | "vt220" | "linux")) {
+        return false;
+    }
+    // The first of these that is set decides the character encoding
+    match ["LC_ALL", "LC_CTYPE", "LANG"].into_iter().find_map(&var) {
+        Some(locale) => {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        None => true,
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).unsigned_abs().pow(2);
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// The nearest named color under the xterm palette.
+fn nearest_ansi(rgb: (u8, u8, u8)) -> Color {
+    let index = (0..16).min_by_key(|&i| distance(rgb, XTERM_PALETTE[i])).unwrap_or(0);
+    ANSI_COLORS[index]
+}
+
+/// The nearest 256-color index in the color cube or the grayscale ramp.
+fn nearest_indexed(rgb: (u8, u8, u8)) -> u8 {
+    let step = |v: u8| match v {
+        0..=47 => 0,
+        48..=114 => 1,
+        v => (v - 35) / 40,
+    };
+    let cube = 16 + 36 * step(rgb.0) + 6 * step(rgb.1) + step(rgb.2);
+    let average = (u16::from(rgb.0) + u16::from(rgb.1) + u16::from(rgb.2)) / 3;
+    let gray = 232 + ((average.saturating_sub(3)) / 10).min(23) as u8;
+    if distance(rgb, extended_rgb(gray)) < distance(rgb, extended_rgb(cube)) { gray } else { cube }
+}
+
+/// An ASCII stand-in for a box drawing, block, arrow, bullet or braille glyph.
+pub fn ascii_fallback(symbol: &str) -> Option<&'static str> {
+    let mut chars = symbol.chars();
+    let c = chars.next()?;
+    if c.is_ascii() || chars.next().is_some() {
+        return None;
+    }
+    Some(match c {
+        '─' | '━' | '═' | '╌' | '╍' | '┄' | '┅' | '┈' | '┉' | '—' | '–' | '╴' | '╶' => "-",
+        '│' | '┃' | '║' | '╎' | '╏' | '┆' | '┇' | '┊' | '┋' | '╵' | '╷' => "|",
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╭' | '╮' | '╯' | '╰' | '╔' | '╗' | '╚'
+        | '╝' | '╠' | '╣' | '╦' | '╩' | '╬' | '┏' | '┓' | '┗' | '┛' | '┣' | '┫' | '┳' | '┻' | '╋' => "+",
+        '█' | '▓' | '▉' | '▊' | '▋' | '▇' | '▆' | '▀' | '▐' | '▌' | '▛' | '▜' | '▙' | '▟' => "#",
+        '▒' | '▅' | '▄' | '▍' | '▎' | '▚' | '▞' => "=",
+        '░' | '▃' | '▂' | '▁' | '▏' | '▖' | '▗' | '▘' | '▝' => ".",
+        '←' | '◀' | '◄' | '‹' | '«' => "<",
+        '→' | '▶' | '►' | '›' | '»' => ">",
+        '↑' | '▲' | '△' => "^",
+        '↓' | '▼' | '▽' => "v",
+        '•' | '●' | '◉' | '★' | '☆' | '■' => "*",
+        '○' | '◦' | '◯' | '□' => "o",
+        '·' | '…' => ".",
+        '✓' | '✔' => "v",
+        '✗' | '✘' | '×' => "x",
+        '“' | '”' => "\"",
+        '‘' | '’' => "'",
+        '\u{2800}' => " ",
+        '\u{2801}'..='\u{28FF}' => ".",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_detects_color_depth_and_unicode() {
+        let unix = Platform::Unix;
+        assert_eq!(detect_colors(unix, env(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")])), ColorDepth::TrueColor);
+        assert_eq!(detect_colors(unix, env(&[("TERM", "screen-256color")])), ColorDepth::Ansi256);
+        assert_eq!(detect_colors(unix, env(&[("TERM", "xterm")])), ColorDepth::Ansi256);
+        assert_eq!(detect_colors(unix, env(&[("TERM", "linux")])), ColorDepth::Ansi16);
+        assert_eq!(detect_colors(unix, env(&[("TERM", "xterm-16color")])), ColorDepth::Ansi16);
+        assert_eq!(detect_colors(unix, env(&[("TERM", "xterm"), (COLORS_ENV, "16")])), ColorDepth::Ansi16);
+        assert_eq!(detect_colors(Platform::Windows, env(&[])), ColorDepth::TrueColor);
+
+        assert!(detect_unicode(env(&[("LANG", "en_US.UTF-8")])));
+        assert!(!detect_unicode(env(&[("LC_ALL", "C"), ("LANG", "en_US.UTF-8")])));
+        assert!(!detect_unicode(env(&[("TERM", "linux")])));
+        assert!(!detect_unicode(env(&[(ASCII_ENV, "1")])));
+        assert!(detect_unicode(env(&[])));
+    }
+
+    #[test]
+    fn test_windows_console_fallbacks() {
+        let legacy = Capabilities::from_console(Some(false), env(&[("LANG", "en_US.UTF-8")]));
+        assert_eq!(legacy.platform, Platform::WindowsLegacy);
+        assert!(!legacy.ansi && !legacy.focus_events);
+        assert!(!legacy.unicode && !legacy.braille);
+
+        let conhost = Capabilities::from_console(Some(true), env(&[]));
+        assert_eq!(conhost.platform, Platform::Windows);
+        assert!(conhost.ansi && conhost.focus_events && conhost.unicode);
+        assert_eq!(conhost.colors, ColorDepth::TrueColor);
+        assert!(!conhost.braille);
+
+        let terminal = Capabilities::from_console(Some(true), env(&[("WT_SESSION", "1")]));
+        assert!(terminal.braille);
+        assert!(!Capabilities::from_console(Some(true), env(&[("WT_SESSION", "1"), (ASCII_ENV, "1")])).braille);
+
+        let unix = Capabilities::from_console(None, env(&[]));
+        assert_eq!(unix.platform, Platform::Unix);
+        assert!(unix.ansi && unix.braille);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_detects_windows_console() {
+        let caps = Capabilities::detect();
+        assert!(caps.is_windows());
+        if !caps.ansi {
+            assert_eq!(caps.platform, Platform::WindowsLegacy);
+            assert!(!caps.unicode && !caps.braille && !caps.focus_events);
+        }
+    }
+
+    #[test]
+    fn test_downgrades_colors_and_glyphs() {
+        let ansi256 = Capabilities { colors: ColorDepth::Ansi256, ..Capabilities::default() };
+        assert_eq!(ansi256.downgrade_color(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+        assert_eq!(ansi256.downgrade_color(Color::Rgb(128, 128, 128)), Color::Indexed(244));
+        assert_eq!(ansi256.downgrade_color(Color::Cyan), Color::Cyan);
+
+        let ansi16 = Capabilities { colors: ColorDepth::Ansi16, unicode: false, ..Capabilities::default() };
+        assert_eq!(ansi16.downgrade_color(Color::Rgb(250, 10, 10)), Color::LightRed);
+        assert_eq!(ansi16.downgrade_color(Color::Indexed(4)), Color::Blue);
+        assert_eq!(ansi16.downgrade_color(Color::Indexed(16)), Color::Black);
+
+        let mut buf = Buffer::empty(ratatui::layout::Rect::new(0, 0, 4, 1));
+        buf.set_string(0, 0, "╭─→✓", ratatui::style::Style::default().fg(Color::Rgb(0, 205, 0)));
+        ansi16.downgrade(&mut buf);
+        let text: String = buf.content().iter().map(|cell| cell.symbol()).collect();
+        assert_eq!(text, "+->v");
+        assert_eq!(buf.content()[0].fg, Color::Green);
+    }
+}
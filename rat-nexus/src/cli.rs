@@ -0,0 +1,78 @@
+//! Command-line flags shared by every rat-nexus app.
+//!
+//! `Application::run_with_args` (or `Application::cli_args` with an explicit list)
+//! understands:
+//!
+//! - `--route <name>`: navigate there once the app is up
+//! - `--theme <name>`: start with that theme (see `theme`)
+//! - `--fps`: draw the frame rate in the top-right corner
+//! - `--no-mouse`: leave mouse capture off, so the terminal keeps text selection
+//!
+//! Values may also be attached with `=` (`--route=settings`). Everything else,
+//! including anything after `--`, is left for the app in `cx.args()`.
+
+use std::io;
+
+/// The framework flags found on the command line, and the remaining arguments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CliArgs {
+    /// Route to show first (`--route`).
+    pub route: Option<String>,
+    /// Theme to start with (`--theme`).
+    pub theme: Option<String>,
+    /// Draw the frame rate badge (`--fps`).
+    pub fps: bool,
+    /// Capture the mouse; `--no-mouse` turns this off.
+    pub mouse: bool,
+    /// Arguments that are not framework flags, in order.
+    pub rest: Vec<String>,
+}
+
+impl CliArgs {
+    /// Parse `args`, without the program name. Fails if `--route` or `--theme` has
+    /// no value.
+    pub fn parse<S: Into<String>>(args: impl IntoIterator<Item = S>) -> io::Result<Self> {
+        let mut parsed = Self { mouse: true, ..Self::default() };
+        let mut args = args.into_iter().map(Into::into);
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+                _ => (arg.clone(), None),
+            };
+            let mut value = |name: &str| {
+                inline.clone().or_else(|| args.next()).ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, format!("{name} needs a value"))
+                })
+            };
+            match flag.as_str() {
+                "--route" => parsed.route = Some(value("--route")?),
+                "--theme" => parsed.theme = Some(value("--theme")?),
+                "--fps" => parsed.fps = true,
+                "--no-mouse" => parsed.mouse = false,
+                "--" => {
+                    parsed.rest.extend(args.by_ref());
+                }
+                _ => parsed.rest.push(arg),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_framework_flags_and_rest() {
+        let args = CliArgs::parse(["--route", "monitor", "input.txt", "--theme=Nord", "--fps", "-v", "--", "--no-mouse"]).unwrap();
+        assert_eq!(args.route.as_deref(), Some("monitor"));
+        assert_eq!(args.theme.as_deref(), Some("Nord"));
+        assert!(args.fps);
+        assert!(args.mouse);
+        assert_eq!(args.rest, ["input.txt", "-v", "--no-mouse"]);
+
+        assert!(!CliArgs::parse(["--no-mouse"]).unwrap().mouse);
+        assert!(CliArgs::parse(["--route"]).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+//! Click counting for `Event::MouseClick`.
+//!
+//! Terminals only report presses and releases. A release on the cell where the same
+//! button went down, with no drag in between, is a click; clicks on the same cell within
+//! the double-click interval of the previous one count up (2 for a double click, 3 for a
+//! triple click).
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
+
+use crate::component::traits::Event;
+
+/// Default longest gap between the clicks of a double click.
+pub(crate) const DEFAULT_INTERVAL: Duration = Duration::from_millis(400);
+
+struct ClickState {
+    interval: Duration,
+    /// The button held down and where it went down, until it is released or dragged.
+    pressed: Option<(MouseButton, Position)>,
+    /// The last click and its count.
+    last: Option<(MouseButton, Position, Instant, u8)>,
+}
+
+/// Press and click history, owned by the `AppContext`.
+#[derive(Clone)]
+pub(crate) struct Clicks {
+    state: Arc<Mutex<ClickState>>,
+}
+
+impl Default for Clicks {
+    fn default() -> Self {
+        Self { state: Arc::new(Mutex::new(ClickState { interval: DEFAULT_INTERVAL, pressed: None, last: None })) }
+    }
+}
+
+impl Clicks {
+    pub(crate) fn set_interval(&self, interval: Duration) {
+        if let Ok(mut state) = self.state.lock() {
+            state.interval = interval;
+        }
+    }
+
+    pub(crate) fn interval(&self) -> Duration {
+        self.state.lock().map(|state| state.interval).unwrap_or(DEFAULT_INTERVAL)
+    }
+
+    /// Follow a mouse event; returns the `Event::MouseClick` it completes, if any.
+    pub(crate) fn track(&self, mouse: &MouseEvent, now: Instant) -> Option<Event> {
+        let mut state = self.state.lock().ok()?;
+        let position = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(button) => state.pressed = Some((button, position)),
+            MouseEventKind::Drag(_) => state.pressed = None,
+            // Some terminals cannot tell which button was released, so trust the press
+            MouseEventKind::Up(_) => {
+                let (button, pressed_at) = state.pressed.take()?;
+                if pressed_at != position {
+                    return None;
+                }
+                let count = match state.last {
+                    Some((last_button, last_position, at, count))
+                        if last_button == button && last_position == position && now.duration_since(at) <= state.interval =>
+                    {
+                        count.saturating_add(1)
+                    }
+                    _ => 1,
+                };
+                state.last = Some((button, position, now, count));
+                return Some(Event::MouseClick { count, button, position });
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn mouse(kind: MouseEventKind, column: u16) -> MouseEvent {
+        MouseEvent { kind, column, row: 0, modifiers: KeyModifiers::NONE }
+    }
+
+    fn click(clicks: &Clicks, column: u16, at: Instant) -> Option<u8> {
+        clicks.track(&mouse(MouseEventKind::Down(MouseButton::Left), column), at);
+        match clicks.track(&mouse(MouseEventKind::Up(MouseButton::Left), column), at)? {
+            Event::MouseClick { count, .. } => Some(count),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_counts_clicks_within_interval() {
+        let clicks = Clicks::default();
+        let start = Instant::now();
+        assert_eq!(click(&clicks, 3, start), Some(1));
+        assert_eq!(click(&clicks, 3, start + Duration::from_millis(200)), Some(2));
+        assert_eq!(click(&clicks, 3, start + Duration::from_millis(400)), Some(3));
+        // Too late, or somewhere else: a new single click
+        assert_eq!(click(&clicks, 3, start + Duration::from_secs(1)), Some(1));
+        assert_eq!(click(&clicks, 4, start + Duration::from_millis(1100)), Some(1));
+
+        clicks.set_interval(Duration::from_millis(50));
+        assert_eq!(click(&clicks, 4, start + Duration::from_millis(1200)), Some(1));
+    }
+
+    #[test]
+    fn test_drags_and_moved_releases_are_not_clicks() {
+        let clicks = Clicks::default();
+        let now = Instant::now();
+        clicks.track(&mouse(MouseEventKind::Down(MouseButton::Left), 1), now);
+        clicks.track(&mouse(MouseEventKind::Drag(MouseButton::Left), 2), now);
+        assert!(clicks.track(&mouse(MouseEventKind::Up(MouseButton::Left), 1), now).is_none());
+        clicks.track(&mouse(MouseEventKind::Down(MouseButton::Right), 1), now);
+        assert!(clicks.track(&mouse(MouseEventKind::Up(MouseButton::Right), 5), now).is_none());
+        assert!(clicks.track(&mouse(MouseEventKind::Up(MouseButton::Left), 1), now).is_none());
+    }
+}
@@ -0,0 +1,228 @@
+//! Reaching a component again after the call that handed out its context returned.
+//!
+//! Observers, timers and tasks started from a `Context` run a callback on their
+//! component later. Only the root is an entity; `define_app!` pages and `ChildView`
+//! children are fields of their parent. So contexts carry a `Binding` that finds the
+//! component from the entity holding it, and parents rebind the contexts they hand
+//! down: the root's in `AnyComponent`, pages and wrapped components through their field
+//! (`Context::project`), and `ChildView` children through an inbox that the
+//! `ChildView` drains when the child is next rendered or receives an event.
+
+use std::any::{Any, TypeId};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use ratatui::layout::Rect;
+use tokio::sync::watch;
+
+use super::traits::{AnyComponent, Component};
+use crate::state::{EntityId, WeakEntity};
+use crate::{AppContext, Context};
+
+/// Runs on the component, given a context bound to it.
+type Update = Box<dyn FnOnce(&mut dyn Any, Context<dyn AnyComponent>) + Send>;
+
+/// Runs an update on the component, now or later; `false` once the component is gone.
+type Reach = dyn Fn(&AppContext, Update) -> bool + Send + Sync;
+
+/// How work started from a context finds its component again.
+#[derive(Clone)]
+pub(crate) struct Binding {
+    /// Owner of the observers and timers the component registers.
+    owner: EntityId,
+    /// The component's type.
+    target: TypeId,
+    /// Closed once the component is dropped.
+    alive: watch::Receiver<()>,
+    reach: Arc<Reach>,
+}
+
+impl Binding {
+    /// The component of type `target` held by `entity`, if the entity is alive.
+    pub(crate) fn entity<E>(entity: &WeakEntity<E>, target: TypeId, as_any: fn(&mut E) -> &mut dyn Any) -> Option<Self>
+    where
+        E: ?Sized + Send + Sync + 'static,
+    {
+        let alive = entity.subscribe()?;
+        let weak = WeakEntity::clone(entity);
+        let reach = move |app: &AppContext, update: Update| {
+            let (Some(entity), Some(binding)) = (weak.upgrade(), Self::entity(&weak, target, as_any)) else {
+                return false;
+            };
+            entity
+                .update_with_cx(app, |view, cx| update(as_any(view), cx.cast::<dyn AnyComponent>().with_binding(binding)))
+                .is_ok()
+        };
+        Some(Self { owner: entity.entity_id(), target, alive, reach: Arc::new(reach) })
+    }
+
+    /// The component `field` finds inside the one this binding reaches, rendered into
+    /// `area` (or the parent's area). `owner` owns its observers and timers.
+    pub(crate) fn project<P, U>(&self, owner: EntityId, area: Option<Rect>, field: fn(&mut P) -> &mut U) -> Self
+    where
+        P: Send + Sync + 'static,
+        U: Send + Sync + 'static,
+    {
+        let parent = self.clone();
+        let reach = move |app: &AppContext, update: Update| {
+            let binding = parent.project(owner, area, field);
+            parent.update(app, move |view: &mut P, cx| {
+                let cx = cx.child::<dyn AnyComponent>(area.or(cx.child_area())).with_binding(binding);
+                update(field(view), cx);
+            })
+        };
+        Self { owner, target: TypeId::of::<U>(), alive: self.alive.clone(), reach: Arc::new(reach) }
+    }
+
+    /// Run `f` on the component, now or once its container applies it; `false` once the
+    /// component is gone.
+    pub(crate) fn update<V, F>(&self, app: &AppContext, f: F) -> bool
+    where
+        V: Send + Sync + 'static,
+        F: FnOnce(&mut V, &mut Context<V>) + Send + 'static,
+    {
+        (self.reach)(app, Box::new(move |view, cx| {
+            if let Some(view) = view.downcast_mut::<V>() {
+                f(view, &mut cx.cast());
+            }
+        }))
+    }
+
+    /// `f` as a callback run on every tick or change, returning `false` once the component
+    /// is gone. A run still waiting in an inbox is not queued again, so a child that is
+    /// not being rendered does not pile them up.
+    pub(crate) fn repeated<V, F>(self, f: F) -> impl FnMut(&AppContext) -> bool + Send + 'static
+    where
+        V: Send + Sync + 'static,
+        F: FnMut(&mut V, &mut Context<V>) + Send + 'static,
+    {
+        let f = Arc::new(Mutex::new(f));
+        let queued = Arc::new(AtomicBool::new(false));
+        move |app| {
+            if queued.swap(true, Ordering::AcqRel) {
+                return true;
+            }
+            let (f, queued) = (Arc::clone(&f), Arc::clone(&queued));
+            self.update(app, move |view, cx| {
+                queued.store(false, Ordering::Release);
+                if let Ok(mut f) = f.lock() {
+                    f(view, cx);
+                }
+            })
+        }
+    }
+
+    /// Owner of the observers and timers the component registers.
+    pub(crate) fn owner(&self) -> EntityId {
+        self.owner
+    }
+
+    /// A receiver closed once the component is dropped.
+    pub(crate) fn alive(&self) -> watch::Receiver<()> {
+        self.alive.clone()
+    }
+
+    /// Whether a context for `U` may keep this binding: `U` is the component's type or
+    /// the type-erased `dyn AnyComponent`.
+    pub(crate) fn binds<U: ?Sized + 'static>(&self) -> bool {
+        let target = TypeId::of::<U>();
+        target == self.target || target == TypeId::of::<dyn AnyComponent>()
+    }
+}
+
+/// Updates for a component that is not held by an entity (a `ChildView` child), kept
+/// until its container applies them.
+pub(crate) struct Inbox {
+    owner: EntityId,
+    pending: Arc<Mutex<Vec<Update>>>,
+    /// Dropped with the container, closing the bindings' `alive` receivers.
+    alive: watch::Sender<()>,
+}
+
+impl Default for Inbox {
+    fn default() -> Self {
+        Self { owner: EntityId::next(), pending: Arc::default(), alive: watch::channel(()).0 }
+    }
+}
+
+impl Inbox {
+    /// A binding for the component of type `target` this inbox belongs to. Updates
+    /// request a re-render, so they are applied on the next frame.
+    pub(crate) fn binding(&self, target: TypeId) -> Binding {
+        let pending = Arc::downgrade(&self.pending);
+        let reach = move |app: &AppContext, update: Update| {
+            let Some(pending) = pending.upgrade() else { return false };
+            let Ok(mut pending) = pending.lock() else { return false };
+            pending.push(update);
+            app.refresh();
+            true
+        };
+        Binding { owner: self.owner, target, alive: self.alive.subscribe(), reach: Arc::new(reach) }
+    }
+
+    /// Apply the pending updates to `view`, with `cx` bound through this inbox.
+    pub(crate) fn deliver(&self, view: &mut dyn Any, cx: &Context<dyn AnyComponent>) -> bool {
+        let updates = match self.pending.lock() {
+            Ok(mut pending) => std::mem::take(&mut *pending),
+            Err(_) => return false,
+        };
+        let delivered = !updates.is_empty();
+        for update in updates {
+            update(view, cx.cast());
+        }
+        delivered
+    }
+}
+
+impl std::fmt::Debug for Inbox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Inbox").field("owner", &self.owner).finish_non_exhaustive()
+    }
+}
+
+/// A type-erased component held by a container that is not an entity (`AppShell` slots,
+/// `Tabs` pages), with the inbox its observers, timers and tasks deliver to.
+pub(crate) struct Held {
+    view: Box<dyn AnyComponent>,
+    inbox: Inbox,
+}
+
+impl Held {
+    pub(crate) fn new(view: impl Component) -> Self {
+        Self { view: Box::new(view), inbox: Inbox::default() }
+    }
+
+    /// Run `f` with the component and a context for it in `area`, after applying the
+    /// callbacks delivered since it last ran.
+    pub(crate) fn with<P, R>(
+        &mut self,
+        cx: &Context<P>,
+        area: Option<Rect>,
+        f: impl FnOnce(&mut dyn AnyComponent, &mut Context<dyn AnyComponent>) -> R,
+    ) -> R
+    where
+        P: ?Sized + Send + Sync,
+    {
+        let target = (&*self.view as &dyn Any).type_id();
+        let mut cx = cx.child(area).with_binding(self.inbox.binding(target));
+        self.inbox.deliver(&mut *self.view, &cx);
+        f(&mut *self.view, &mut cx)
+    }
+}
+
+/// Owner of the observers and timers of a `define_app!` page.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct PageOwner(EntityId);
+
+impl Default for PageOwner {
+    fn default() -> Self {
+        Self(EntityId::next())
+    }
+}
+
+impl PageOwner {
+    pub(crate) fn id(&self) -> EntityId {
+        self.0
+    }
+}
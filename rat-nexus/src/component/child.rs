@@ -0,0 +1,492 @@
+//! Child components with framework-managed render areas.
+//!
+//! `ChildView` wraps a child component and remembers the area it was last rendered
+//! into. Mouse events are only delivered to a child when they hit that area, so parents
+//! no longer need to store layout rects themselves for hit-testing. Inside the child,
+//! `cx.area(frame)` returns the assigned area and `cx.local_position` converts screen
+//! coordinates into area-relative ones. Styles pushed with `cx.push_style` before
+//! mounting are inherited by the child and its descendants.
+//!
+//! Observers, timers and tasks the child starts from its context reach it through the
+//! `ChildView`: their callbacks are applied when the child is next rendered or receives
+//! an event, and stop when the `ChildView` is dropped.
+//!
+//! `ChildView::cached` children are only re-rendered when something they depend on
+//! changed, and otherwise redraw the cells of their previous frame (see `damage`), so a
+//! refresh caused by one panel does not re-render every other panel of a large screen.
+
+use std::any::TypeId;
+
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Modifier, Style};
+
+use super::binding::Inbox;
+use super::mount::Mounting;
+use super::traits::{Action, Component, Event};
+use crate::damage::{DependencyTracker, RenderCache};
+use crate::Context;
+
+/// A child component together with its last render area.
+///
+/// # Example
+/// ```ignore
+/// struct Page {
+///     board: ChildView<Board>,
+///     sidebar: ChildView<Sidebar>,
+/// }
+///
+/// fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+///     let [board, sidebar] = Layout::horizontal([Constraint::Fill(2), Constraint::Fill(1)]).areas(frame.area());
+///     cx.mount_child(&mut self.board, frame, board);
+///     cx.mount_child(&mut self.sidebar, frame, sidebar);
+/// }
+///
+/// fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+///     // Mouse events reach only the child under the pointer
+///     self.board.handle_event(event.clone(), cx)
+///         .or_else(|| self.sidebar.handle_event(event, cx))
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct ChildView<T> {
+    view: T,
+    area: Option<Rect>,
+    /// Created with `cached`, rather than relying on `Component::memoize`.
+    cached: bool,
+    /// The last render of a cached or memoized child.
+    cache: RenderCache,
+    /// The child's `on_mount_async` task, until its output is applied.
+    mounting: Option<Mounting<T>>,
+    /// Callbacks of the child's observers, timers and tasks, until they are applied.
+    inbox: Inbox,
+}
+
+impl<T> ChildView<T> {
+    /// Wrap a child component.
+    pub fn new(view: T) -> Self {
+        Self { view, area: None, cached: false, cache: RenderCache::default(), mounting: None, inbox: Inbox::default() }
+    }
+
+    /// Wrap a child component that is only re-rendered when it may look different:
+    /// after an entity it subscribed to while rendering changed, an event reached it,
+    /// it was borrowed mutably, or its area or inherited style changed. Otherwise the
+    /// cells it drew last are reused.
+    ///
+    /// The child must subscribe to (or `watch`) every entity it reads in `render`, and
+    /// should not draw anything that changes on its own, like a clock or a spinner.
+    pub fn cached(view: T) -> Self {
+        Self { view, area: None, cached: true, cache: RenderCache::default(), mounting: None, inbox: Inbox::default() }
+    }
+
+    /// Render a cached child again on the next frame.
+    pub fn invalidate(&mut self) {
+        self.cache.clear();
+    }
+
+    /// The area the child was last rendered into, if it has been rendered.
+    pub fn area(&self) -> Option<Rect> {
+        self.area
+    }
+
+    /// Whether the screen cell lies inside the child's last render area.
+    pub fn contains(&self, column: u16, row: u16) -> bool {
+        self.area.is_some_and(|area| area.contains(Position::new(column, row)))
+    }
+
+    /// Forget the render area, e.g. when the child is hidden. Mouse events are no longer
+    /// delivered until it is rendered again.
+    pub fn hide(&mut self) {
+        self.area = None;
+    }
+
+    /// Unwrap the child component.
+    pub fn into_inner(self) -> T {
+        self.view
+    }
+}
+
+impl<T: Component> ChildView<T> {
+    /// Render the child into `area`. Prefer `cx.mount_child`.
+    pub fn render<P: ?Sized + Send + Sync>(&mut self, frame: &mut ratatui::Frame, area: Rect, cx: &Context<P>) {
+        self.area = Some(area);
+        cx.name_area(region_name::<T>(), area);
+        if self.still_mounting(cx) {
+            cx.mounts.draw_placeholder(frame, area);
+            return;
+        }
+        self.deliver(cx);
+        let style = cx.style();
+        let generation = cx.damage.generation();
+        let cached = self.cached || self.view.memoize();
+        if cached && self.cache.reuse(frame.buffer_mut(), area, style, generation, cx.dependency_trackers()) {
+            return;
+        }
+        let mut cx = self.child_cx::<P, T>(cx);
+        let tracker = DependencyTracker::default();
+        if cached {
+            cx.track_dependencies(&tracker);
+        }
+        let budget = cx.frame_budget().clone();
+        if style == Style::default() {
+            budget.measure::<T, _>(|| self.view.render(frame, &mut cx));
+        } else {
+            // Inherited colors are defaults the child may override; modifiers always apply
+            let colors = Style { add_modifier: Modifier::empty(), sub_modifier: Modifier::empty(), ..style };
+            frame.buffer_mut().set_style(area, colors);
+            budget.measure::<T, _>(|| self.view.render(frame, &mut cx));
+            let modifiers = Style::default().add_modifier(style.add_modifier).remove_modifier(style.sub_modifier);
+            frame.buffer_mut().set_style(area, modifiers);
+        }
+        if cached {
+            self.cache.store(frame.buffer_mut(), area, style, generation, &tracker);
+        }
+    }
+
+    /// Deliver an event to the child. Mouse, click and drop events are dropped unless they hit
+    /// the child's last render area; all other events are always delivered.
+    pub fn handle_event<P: ?Sized + Send + Sync>(&mut self, event: Event, cx: &Context<P>) -> Option<Action> {
+        if event.position().is_some_and(|position| !self.contains(position.x, position.y)) {
+            return None;
+        }
+        if event.is_input() && self.still_mounting(cx) {
+            return None;
+        }
+        self.deliver(cx);
+        self.invalidate();
+        let mut cx = self.child_cx::<P, T>(cx);
+        let action = self.view.handle_event(event, &mut cx);
+        if self.view.refresh_on_event() {
+            cx.refresh();
+        }
+        action
+    }
+
+    /// Forward `on_mount` to the child, and start its `on_mount_async` task.
+    pub fn on_mount<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        self.invalidate();
+        let mut cx = self.child_cx::<P, T>(cx);
+        self.view.on_mount(&mut cx);
+        if let Some(task) = self.view.on_mount_async(&mut cx) {
+            self.mounting = Some(Mounting::start(task, &cx));
+        }
+    }
+
+    /// Whether the child's mount task is still running. Applies its output once done.
+    fn still_mounting<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) -> bool {
+        let Some(mounting) = &self.mounting else {
+            return false;
+        };
+        let Some(apply) = mounting.finish() else {
+            return true;
+        };
+        self.mounting = None;
+        self.invalidate();
+        let child = self.child_cx(cx);
+        apply(&mut self.view, &child);
+        false
+    }
+
+    /// Context for the child in its area, reaching it through the inbox.
+    fn child_cx<P, U>(&self, cx: &Context<P>) -> Context<U>
+    where
+        P: ?Sized + Send + Sync,
+        U: ?Sized + Send + Sync + 'static,
+    {
+        cx.child(self.area).with_binding(self.inbox.binding(TypeId::of::<T>()))
+    }
+
+    /// Apply the callbacks delivered since the child last ran.
+    fn deliver<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        let child = self.child_cx(cx);
+        if self.inbox.deliver(&mut self.view, &child) {
+            self.invalidate();
+        }
+    }
+
+    /// Forward `on_enter` to the child.
+    pub fn on_enter<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        self.invalidate();
+        self.view.on_enter(&mut self.child_cx(cx));
+    }
+
+    /// Forward `on_exit` to the child, then remove its observers and timers.
+    pub fn on_exit<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        self.invalidate();
+        let mut cx = self.child_cx(cx);
+        self.view.on_exit(&mut cx);
+        cx.unsubscribe_all();
+    }
+
+    /// Forward `on_shutdown` to the child.
+    pub fn on_shutdown<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        self.invalidate();
+        self.view.on_shutdown(&mut self.child_cx(cx));
+    }
+
+    /// Forward `on_resize` to the child.
+    pub fn on_resize<P: ?Sized + Send + Sync>(&mut self, width: u16, height: u16, cx: &Context<P>) {
+        self.invalidate();
+        self.view.on_resize(width, height, &mut self.child_cx(cx));
+    }
+
+    /// Forward `on_focus` to the child.
+    pub fn on_focus<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        self.invalidate();
+        self.view.on_focus(&mut self.child_cx(cx));
+    }
+
+    /// Forward `on_blur` to the child.
+    pub fn on_blur<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        self.invalidate();
+        self.view.on_blur(&mut self.child_cx(cx));
+    }
+}
+
+/// Region name of a child type: the type name without path or generics, in snake_case.
+fn region_name<T>() -> String {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    let name = name.rsplit("::").next().unwrap_or(name);
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+impl<T> std::ops::Deref for ChildView<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.view
+    }
+}
+
+impl<T> std::ops::DerefMut for ChildView<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // The caller may change what the child draws
+        self.invalidate();
+        &mut self.view
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use ratatui::backend::TestBackend;
+    use ratatui::style::Color;
+    use ratatui::Terminal;
+
+    #[derive(Default)]
+    struct Probe {
+        rendered_in: Option<Rect>,
+        clicks: Vec<(u16, u16)>,
+    }
+
+    impl Component for Probe {
+        fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+            self.rendered_in = Some(cx.area(frame));
+        }
+
+        fn handle_event(&mut self, event: Event, cx: &mut Context<Self>) -> Option<Action> {
+            if let Event::Mouse(mouse) = event {
+                self.clicks.extend(cx.local_position(mouse.column, mouse.row));
+            }
+            None
+        }
+    }
+
+    fn click(column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn test_mouse_routed_by_area() {
+        let (app, _refresh, _inject) = crate::AppContext::new();
+        let cx = Context::<Probe>::new(app, crate::Entity::new(Probe::default()).downgrade());
+        let mut left = ChildView::new(Probe::default());
+        let mut right = ChildView::new(Probe::default());
+
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        terminal
+            .draw(|frame| {
+                cx.mount_child(&mut left, frame, Rect::new(0, 0, 10, 5));
+                cx.mount_child(&mut right, frame, Rect::new(10, 0, 10, 5));
+            })
+            .unwrap();
+        assert_eq!(right.rendered_in, Some(Rect::new(10, 0, 10, 5)));
+        assert_eq!(cx.region("probe"), Some(Rect::new(10, 0, 10, 5)));
+
+        left.handle_event(click(12, 3), &cx);
+        right.handle_event(click(12, 3), &cx);
+        assert!(left.clicks.is_empty());
+        assert_eq!(right.clicks, vec![(2, 3)]);
+    }
+
+    struct Label {
+        inherited: Style,
+    }
+
+    impl Component for Label {
+        fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+            let area = cx.area(frame);
+            self.inherited = cx.style();
+            frame.buffer_mut().set_string(area.x, area.y, "a", Style::default().fg(Color::Red));
+            frame.buffer_mut().set_string(area.x + 1, area.y, "b", Style::default());
+        }
+    }
+
+    #[test]
+    fn test_pushed_style_cascades() {
+        let (app, _refresh, _inject) = crate::AppContext::new();
+        let mut cx = Context::<Probe>::new(app, crate::Entity::new(Probe::default()).downgrade());
+        let mut label = ChildView::new(Label { inherited: Style::default() });
+        let dimmed = Style::default().fg(Color::Gray).add_modifier(Modifier::DIM);
+
+        let mut terminal = Terminal::new(TestBackend::new(4, 1)).unwrap();
+        cx.push_style(dimmed);
+        let frame = terminal.draw(|frame| cx.mount_child(&mut label, frame, Rect::new(0, 0, 4, 1))).unwrap();
+        assert_eq!(label.inherited, dimmed);
+        assert_eq!(frame.buffer[(0, 0)].fg, Color::Red);
+        assert_eq!(frame.buffer[(1, 0)].fg, Color::Gray);
+        assert!(frame.buffer[(0, 0)].modifier.contains(Modifier::DIM));
+
+        assert_eq!(cx.pop_style(), Some(dimmed));
+        let frame = terminal.draw(|frame| cx.mount_child(&mut label, frame, Rect::new(0, 0, 4, 1))).unwrap();
+        assert!(!frame.buffer[(0, 0)].modifier.contains(Modifier::DIM));
+    }
+
+    struct Counter {
+        value: crate::Entity<u32>,
+        renders: u32,
+    }
+
+    impl Component for Counter {
+        fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+            self.renders += 1;
+            let area = cx.area(frame);
+            let value = cx.watch(&self.value, |v| *v).unwrap_or_default();
+            frame.buffer_mut().set_string(area.x, area.y, value.to_string(), Style::default());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_child_renders_only_when_dirty() {
+        let (app, _refresh, _inject) = crate::AppContext::new();
+        let cx = Context::<Probe>::new(app.clone(), crate::Entity::new(Probe::default()).downgrade());
+        let value = crate::Entity::new(1u32);
+        let mut counter = ChildView::cached(Counter { value: crate::Entity::clone(&value), renders: 0 });
+        let mut terminal = Terminal::new(TestBackend::new(4, 1)).unwrap();
+        let mut draw = |counter: &mut ChildView<Counter>| {
+            let frame = terminal.draw(|frame| cx.mount_child(counter, frame, Rect::new(0, 0, 4, 1))).unwrap();
+            (frame.buffer[(0, 0)].symbol().to_string(), counter.renders)
+        };
+
+        assert_eq!(draw(&mut counter), ("1".to_string(), 1));
+        assert_eq!(draw(&mut counter), ("1".to_string(), 1));
+        value.update(|v| *v = 2).unwrap();
+        assert_eq!(draw(&mut counter), ("2".to_string(), 2));
+        app.invalidate_all();
+        assert_eq!(draw(&mut counter), ("2".to_string(), 3));
+        counter.handle_event(Event::FocusGained, &cx);
+        assert_eq!(draw(&mut counter), ("2".to_string(), 4));
+        assert_eq!(draw(&mut counter), ("2".to_string(), 4));
+    }
+
+    struct Panel {
+        title: crate::Entity<u32>,
+        counter: ChildView<Counter>,
+    }
+
+    impl Component for Panel {
+        fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+            let area = cx.area(frame);
+            let title = cx.watch(&self.title, |t| *t).unwrap_or_default();
+            frame.buffer_mut().set_string(area.x, area.y, title.to_string(), Style::default());
+            cx.mount_child(&mut self.counter, frame, Rect { x: area.x + 2, width: 2, ..area });
+        }
+    }
+
+    #[tokio::test]
+    async fn test_nested_cached_child_redraws_when_only_inner_changes() {
+        let (app, _refresh, _inject) = crate::AppContext::new();
+        let cx = Context::<Probe>::new(app, crate::Entity::new(Probe::default()).downgrade());
+        let (title, value) = (crate::Entity::new(7u32), crate::Entity::new(1u32));
+        let counter = ChildView::cached(Counter { value: crate::Entity::clone(&value), renders: 0 });
+        let mut panel = ChildView::cached(Panel { title: crate::Entity::clone(&title), counter });
+        let mut terminal = Terminal::new(TestBackend::new(4, 1)).unwrap();
+        let mut draw = |panel: &mut ChildView<Panel>| {
+            let frame = terminal.draw(|frame| cx.mount_child(panel, frame, Rect::new(0, 0, 4, 1))).unwrap();
+            (frame.buffer[(0, 0)].symbol().to_string(), frame.buffer[(2, 0)].symbol().to_string())
+        };
+
+        assert_eq!(draw(&mut panel), ("7".to_string(), "1".to_string()));
+        // The panel redraws around the reused counter, whose dependency it must keep
+        title.update(|t| *t = 8).unwrap();
+        assert_eq!(draw(&mut panel), ("8".to_string(), "1".to_string()));
+        assert_eq!(panel.counter.renders, 1);
+        value.update(|v| *v = 2).unwrap();
+        assert_eq!(draw(&mut panel), ("8".to_string(), "2".to_string()));
+        assert_eq!(panel.counter.renders, 2);
+    }
+
+    struct Greeting {
+        name: Option<tokio::sync::oneshot::Receiver<&'static str>>,
+        text: &'static str,
+        keys: usize,
+    }
+
+    impl Component for Greeting {
+        fn on_mount_async(&mut self, cx: &mut Context<Self>) -> Option<crate::MountTask<Self>> {
+            let name = self.name.take()?;
+            cx.mount_async(async move { name.await.unwrap_or("?") }, |this, name, _cx| this.text = name)
+        }
+
+        fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+            let area = cx.area(frame);
+            frame.buffer_mut().set_string(area.x, area.y, self.text, Style::default());
+        }
+
+        fn handle_event(&mut self, _event: Event, _cx: &mut Context<Self>) -> Option<Action> {
+            self.keys += 1;
+            None
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_mount_shows_placeholder_until_done() {
+        let (app, _refresh, _inject) = crate::AppContext::new();
+        let cx = Context::<Probe>::new(app, crate::Entity::new(Probe::default()).downgrade());
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let mut child = ChildView::new(Greeting { name: Some(rx), text: "", keys: 0 });
+        child.on_mount(&cx);
+
+        let mut terminal = Terminal::new(TestBackend::new(12, 1)).unwrap();
+        let mut draw = |child: &mut ChildView<Greeting>| {
+            terminal.draw(|frame| cx.mount_child(child, frame, frame.area())).unwrap();
+            terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect::<String>()
+        };
+        assert!(draw(&mut child).contains("Loading…"));
+        child.handle_event(Event::Key(crossterm::event::KeyEvent::from(crossterm::event::KeyCode::Enter)), &cx);
+        assert_eq!(child.keys, 0, "input waits for the mount task");
+
+        tx.send("hello").unwrap();
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(draw(&mut child).trim_end(), "hello");
+    }
+}
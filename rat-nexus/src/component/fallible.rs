@@ -0,0 +1,303 @@
+//! Components whose hooks can fail.
+//!
+//! `TryComponent` is `Component` with hooks returning `crate::Result`, so a component
+//! can use `?` instead of `let _ =` or `unwrap` on entity updates and I/O. Every
+//! `TryComponent` is a `Component`. Errors are reported to the handler registered with
+//! `AppContext::on_error` (logged by default); a failed render also shows the error in
+//! the component's area, and a failed `handle_event` returns no action.
+
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Paragraph, Wrap};
+
+use super::mount::MountTask;
+use super::traits::{Action, Component, Event};
+use crate::router::PageStatus;
+use crate::{Context, EventContext};
+
+/// A component whose hooks return `crate::Result`.
+///
+/// # Example
+/// ```ignore
+/// impl TryComponent for Editor {
+///     fn try_handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> rat_nexus::Result<Option<Action>> {
+///         if let Event::Key(key) = event {
+///             if key.code == KeyCode::Char('s') {
+///                 std::fs::write(&self.path, self.text.read(String::clone)?).map_err(Error::other)?;
+///             }
+///         }
+///         Ok(None)
+///     }
+///
+///     fn try_render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) -> rat_nexus::Result<()> {
+///         let text = self.text.read(String::clone)?;
+///         frame.render_widget(Paragraph::new(text), cx.area(frame));
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait TryComponent: Send + Sync + 'static {
+    /// See `Component::on_mount`.
+    fn try_on_mount(&mut self, cx: &mut Context<Self>) -> crate::Result<()> {
+        let _ = cx;
+        Ok(())
+    }
+
+    /// See `Component::on_mount_async`.
+    fn on_mount_async(&mut self, cx: &mut Context<Self>) -> Option<MountTask<Self>>
+    where
+        Self: Sized,
+    {
+        let _ = cx;
+        None
+    }
+
+    /// See `Component::on_enter`.
+    fn try_on_enter(&mut self, cx: &mut Context<Self>) -> crate::Result<()> {
+        let _ = cx;
+        Ok(())
+    }
+
+    /// See `Component::on_exit`.
+    fn try_on_exit(&mut self, cx: &mut Context<Self>) -> crate::Result<()> {
+        let _ = cx;
+        Ok(())
+    }
+
+    /// See `Component::on_shutdown`.
+    fn try_on_shutdown(&mut self, cx: &mut Context<Self>) -> crate::Result<()> {
+        let _ = cx;
+        Ok(())
+    }
+
+    /// See `Component::on_resize`.
+    fn try_on_resize(&mut self, width: u16, height: u16, cx: &mut Context<Self>) -> crate::Result<()> {
+        let _ = (width, height, cx);
+        Ok(())
+    }
+
+    /// See `Component::on_focus`.
+    fn try_on_focus(&mut self, cx: &mut Context<Self>) -> crate::Result<()> {
+        let _ = cx;
+        Ok(())
+    }
+
+    /// See `Component::on_blur`.
+    fn try_on_blur(&mut self, cx: &mut Context<Self>) -> crate::Result<()> {
+        let _ = cx;
+        Ok(())
+    }
+
+    /// See `Component::render`.
+    fn try_render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) -> crate::Result<()>;
+
+    /// See `Component::handle_event`.
+    fn try_handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> crate::Result<Option<Action>> {
+        let _ = (event, cx);
+        Ok(None)
+    }
+
+    /// See `Component::memoize`.
+    fn memoize(&self) -> bool {
+        false
+    }
+
+    /// See `Component::refresh_on_event`.
+    fn refresh_on_event(&self) -> bool {
+        false
+    }
+
+    /// See `Component::status`.
+    fn status(&self) -> PageStatus {
+        PageStatus::Ready
+    }
+}
+
+/// An error returned by a `TryComponent` hook, or by `define_app!` navigation, as
+/// passed to `AppContext::on_error`.
+#[derive(Debug)]
+pub struct ComponentError {
+    component: &'static str,
+    hook: &'static str,
+    error: crate::Error,
+}
+
+impl ComponentError {
+    pub(crate) fn new<T: ?Sized>(hook: &'static str, error: crate::Error) -> Self {
+        Self { component: std::any::type_name::<T>(), hook, error }
+    }
+
+    /// Type name of the component that failed.
+    pub fn component(&self) -> &'static str {
+        self.component
+    }
+
+    /// The hook that failed, such as `"render"` or `"handle_event"`.
+    pub fn hook(&self) -> &'static str {
+        self.hook
+    }
+
+    pub fn error(&self) -> &crate::Error {
+        &self.error
+    }
+
+    pub fn into_error(self) -> crate::Error {
+        self.error
+    }
+}
+
+impl std::fmt::Display for ComponentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} failed in {}: {}", self.component, self.hook, self.error)
+    }
+}
+
+impl std::error::Error for ComponentError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Report the error of `hook`, if any, and return the value otherwise.
+fn reported<T, R, V: ?Sized + Send + Sync>(result: crate::Result<R>, hook: &'static str, cx: &Context<V>) -> Option<R> {
+    result.map_err(|error| cx.report_error(ComponentError::new::<T>(hook, error))).ok()
+}
+
+impl<T: TryComponent> Component for T {
+    fn on_mount(&mut self, cx: &mut Context<Self>) {
+        let result = self.try_on_mount(cx);
+        reported::<T, _, _>(result, "on_mount", cx);
+    }
+
+    fn on_mount_async(&mut self, cx: &mut Context<Self>) -> Option<MountTask<Self>> {
+        TryComponent::on_mount_async(self, cx)
+    }
+
+    fn on_enter(&mut self, cx: &mut Context<Self>) {
+        let result = self.try_on_enter(cx);
+        reported::<T, _, _>(result, "on_enter", cx);
+    }
+
+    fn on_exit(&mut self, cx: &mut Context<Self>) {
+        let result = self.try_on_exit(cx);
+        reported::<T, _, _>(result, "on_exit", cx);
+    }
+
+    fn on_shutdown(&mut self, cx: &mut Context<Self>) {
+        let result = self.try_on_shutdown(cx);
+        reported::<T, _, _>(result, "on_shutdown", cx);
+    }
+
+    fn on_resize(&mut self, width: u16, height: u16, cx: &mut Context<Self>) {
+        let result = self.try_on_resize(width, height, cx);
+        reported::<T, _, _>(result, "on_resize", cx);
+    }
+
+    fn on_focus(&mut self, cx: &mut Context<Self>) {
+        let result = self.try_on_focus(cx);
+        reported::<T, _, _>(result, "on_focus", cx);
+    }
+
+    fn on_blur(&mut self, cx: &mut Context<Self>) {
+        let result = self.try_on_blur(cx);
+        reported::<T, _, _>(result, "on_blur", cx);
+    }
+
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        if let Err(error) = self.try_render(frame, cx) {
+            let message = Paragraph::new(format!("✗ {error}")).style(Style::default().fg(Color::Red)).wrap(Wrap { trim: true });
+            frame.render_widget(message, cx.area(frame));
+            cx.report_error(ComponentError::new::<T>("render", error));
+        }
+    }
+
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+        let result = self.try_handle_event(event, cx);
+        reported::<T, _, _>(result, "handle_event", cx).flatten()
+    }
+
+    fn memoize(&self) -> bool {
+        TryComponent::memoize(self)
+    }
+
+    fn refresh_on_event(&self) -> bool {
+        TryComponent::refresh_on_event(self)
+    }
+
+    fn status(&self) -> PageStatus {
+        TryComponent::status(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+    use crate::Entity;
+    use crossterm::event::KeyCode;
+
+    struct Counter {
+        count: Entity<u32>,
+    }
+
+    impl TryComponent for Counter {
+        fn try_render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) -> crate::Result<()> {
+            let count = self.count.read(|count| *count)?;
+            if count > 1 {
+                return Err(crate::Error::other(format!("count {count} too high")));
+            }
+            frame.render_widget(Paragraph::new(count.to_string()), cx.area(frame));
+            Ok(())
+        }
+
+        fn try_handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> crate::Result<Option<Action>> {
+            match event {
+                Event::Key(key) if key.code == KeyCode::Char('+') => self.count.update(|count| *count += 1)?,
+                Event::Key(_) => return Err(crate::Error::other("unknown key")),
+                _ => {}
+            }
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn test_errors_reach_the_handler() {
+        let mut harness = Harness::with_size(Counter { count: Entity::new(0) }, 24, 1);
+        let errors = Entity::new(Vec::new());
+        let reported = errors.clone();
+        harness.app().on_error(move |error, _| {
+            let _ = reported.update(|errors| errors.push(format!("{} {}", error.hook(), error.error())));
+        });
+        harness.type_str("x++");
+        assert_eq!(harness.text(), "✗ count 2 too high");
+        assert_eq!(errors.read(Vec::clone).unwrap(), ["handle_event unknown key", "render count 2 too high"]);
+    }
+
+    struct Canvas;
+
+    impl TryComponent for Canvas {
+        fn try_on_resize(&mut self, width: u16, height: u16, _cx: &mut Context<Self>) -> crate::Result<()> {
+            Err(crate::Error::other(format!("{width}x{height} too small")))
+        }
+
+        fn try_render(&mut self, _frame: &mut ratatui::Frame, _cx: &mut Context<Self>) -> crate::Result<()> {
+            Ok(())
+        }
+
+        fn memoize(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_lifecycle_hooks_and_memoize_are_forwarded() {
+        let mut harness = Harness::with_size(Canvas, 24, 1);
+        let errors = Entity::new(Vec::new());
+        let reported = errors.clone();
+        harness.app().on_error(move |error, _| {
+            let _ = reported.update(|errors| errors.push(format!("{} {}", error.hook(), error.error())));
+        });
+        harness.resize(8, 2);
+        assert_eq!(errors.read(Vec::clone).unwrap(), ["on_resize 8x2 too small"]);
+        assert!(Component::memoize(&Canvas));
+    }
+}
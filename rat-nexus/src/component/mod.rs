@@ -2,6 +2,16 @@
 //!
 //! Defines the `Component` trait and related utilities.
 
+#[doc(hidden)]
+pub mod binding;
+pub mod child;
+pub mod fallible;
+pub mod mount;
+pub mod styled;
 pub mod traits;
 
+pub use child::ChildView;
+pub use fallible::{ComponentError, TryComponent};
+pub use mount::MountTask;
+pub use styled::Styled;
 pub use traits::Component;
@@ -0,0 +1,152 @@
+//! Async setup after `on_mount`.
+//!
+//! `Component::on_mount_async` returns a `MountTask`: a future run in the background and
+//! a function applying its output to the component. Until it completes, the framework
+//! draws a loading placeholder where the component would be (see
+//! `AppContext::set_loading_placeholder`) and keeps input events from it; the first real
+//! render happens after the output was applied. This works for the root and for
+//! `ChildView` children.
+
+use std::any::Any;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+use ratatui::layout::Rect;
+use ratatui::Frame;
+
+use super::traits::{AnyComponent, Component};
+use crate::state::loadable::SPINNER_INTERVAL;
+use crate::state::{Entity, Loadable};
+use crate::{AppContext, Context};
+
+/// Applies the output of a mount task to the component, given a context to cast.
+type Apply<V> = Box<dyn FnOnce(&mut V, &Context<dyn AnyComponent>) + Send>;
+
+/// Draws the placeholder of a component that is still mounting.
+type Placeholder = Arc<dyn Fn(&mut Frame, Rect) + Send + Sync>;
+
+/// Async setup returned by `Component::on_mount_async`.
+pub struct MountTask<V: ?Sized> {
+    future: Pin<Box<dyn Future<Output = Apply<V>> + Send>>,
+}
+
+impl<V: ?Sized + Send + Sync + 'static> MountTask<V> {
+    /// Run `future` in the background, then hand its output to `apply` together with
+    /// the component, before the component's first render. `Context::mount_async`
+    /// builds one with the component type inferred.
+    pub fn new<Fut, R, F>(future: Fut, apply: F) -> Self
+    where
+        Fut: Future<Output = R> + Send + 'static,
+        R: Send + 'static,
+        F: FnOnce(&mut V, R, &mut Context<V>) + Send + 'static,
+    {
+        let future = async move {
+            let output = future.await;
+            Box::new(move |view: &mut V, cx: &Context<dyn AnyComponent>| apply(view, output, &mut cx.cast())) as Apply<V>
+        };
+        Self { future: Box::pin(future) }
+    }
+
+    /// Wait for the future, redrawing at spinner rate meanwhile.
+    async fn run(self, app: &AppContext) -> Apply<V> {
+        let mut future = self.future;
+        let mut spinner = tokio::time::interval(SPINNER_INTERVAL);
+        loop {
+            tokio::select! {
+                apply = &mut future => break apply,
+                _ = spinner.tick() => app.refresh(),
+            }
+        }
+    }
+}
+
+impl<T: Component> MountTask<T> {
+    /// The task for a type-erased `T`.
+    pub(crate) fn into_any(self) -> MountTask<dyn AnyComponent> {
+        let future = async move {
+            let apply = self.future.await;
+            Box::new(move |view: &mut dyn AnyComponent, cx: &Context<dyn AnyComponent>| {
+                if let Some(view) = (view as &mut dyn Any).downcast_mut::<T>() {
+                    apply(view, &cx.downcast::<T>().cast());
+                }
+            }) as Apply<dyn AnyComponent>
+        };
+        MountTask { future: Box::pin(future) }
+    }
+}
+
+/// A `ChildView` child whose mount task is running.
+pub(crate) struct Mounting<T> {
+    done: Arc<Mutex<Option<Apply<T>>>>,
+}
+
+impl<T: Component> Mounting<T> {
+    pub(crate) fn start(task: MountTask<T>, app: &AppContext) -> Self {
+        let done = Arc::new(Mutex::new(None));
+        let (slot, app) = (Arc::clone(&done), AppContext::clone(app));
+        tokio::spawn(async move {
+            let apply = task.run(&app).await;
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(apply);
+            }
+            app.refresh();
+        });
+        Self { done }
+    }
+
+    /// The output to apply, once the task completed.
+    pub(crate) fn finish(&self) -> Option<Apply<T>> {
+        self.done.lock().ok()?.take()
+    }
+}
+
+impl<T> std::fmt::Debug for Mounting<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Mounting").finish_non_exhaustive()
+    }
+}
+
+/// The loading placeholder and the root's mount task, owned by the `AppContext`.
+#[derive(Clone, Default)]
+pub(crate) struct Mounts {
+    placeholder: Arc<RwLock<Option<Placeholder>>>,
+    root_pending: Arc<AtomicBool>,
+}
+
+impl Mounts {
+    pub(crate) fn set_placeholder(&self, placeholder: Placeholder) {
+        if let Ok(mut current) = self.placeholder.write() {
+            *current = Some(placeholder);
+        }
+    }
+
+    /// Draw the placeholder into `area`: the one set by the app, or a spinner.
+    pub(crate) fn draw_placeholder(&self, frame: &mut Frame, area: Rect) {
+        let placeholder = self.placeholder.read().ok().and_then(|placeholder| placeholder.clone());
+        match placeholder {
+            Some(draw) => draw(frame, area),
+            None => {
+                Loadable::<()>::Loading.render_state(frame, area);
+            }
+        }
+    }
+
+    /// Whether the root's mount task is still running.
+    pub(crate) fn root_pending(&self) -> bool {
+        self.root_pending.load(Ordering::Acquire)
+    }
+
+    /// Run the root's mount task, applying its output to `root` once it completes.
+    pub(crate) fn start_root(&self, task: MountTask<dyn AnyComponent>, root: &Entity<dyn AnyComponent>, app: &AppContext) {
+        self.root_pending.store(true, Ordering::Release);
+        let (pending, root, app) = (Arc::clone(&self.root_pending), Entity::clone(root), AppContext::clone(app));
+        tokio::spawn(async move {
+            let apply = task.run(&app).await;
+            let _ = root.update_with_cx(&app, |view, cx| apply(view, cx));
+            pending.store(false, Ordering::Release);
+            app.refresh();
+        });
+    }
+}
@@ -0,0 +1,223 @@
+//! Borders, titles, padding and margins around any component.
+//!
+//! Most pages start their `render` the same way: build a `Block`, render it, and draw the
+//! content into `block.inner(area)`. `Styled` does that from the outside, so the wrapped
+//! component renders into `cx.area(frame)` as if the frame had no border:
+//!
+//! ```ignore
+//! let page = Styled::new(Counter::default())
+//!     .title(" Counter ")
+//!     .padding(Padding::horizontal(1))
+//!     .margin(Margin::new(2, 1));
+//! ```
+//!
+//! Events are forwarded unchanged, except that mouse, click and drop events outside the
+//! content area are dropped, so `cx.local_position` works inside the wrapped component.
+
+use ratatui::layout::{Margin, Rect};
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Borders, Padding};
+
+use super::traits::{Action, Component, Event};
+use crate::router::PageStatus;
+use crate::{Context, EventContext};
+
+/// A component wrapped in a block (bordered by default) with optional padding and margins.
+pub struct Styled<C> {
+    inner: C,
+    block: Block<'static>,
+    margin: Margin,
+    /// The content area of the last render.
+    area: Option<Rect>,
+}
+
+impl<C: Component> Styled<C> {
+    /// Wrap `inner` in a plain border.
+    pub fn new(inner: C) -> Self {
+        Self { inner, block: Block::bordered(), margin: Margin::new(0, 0), area: None }
+    }
+
+    /// Replace the block entirely, e.g. to keep one defined by the theme.
+    pub fn block(mut self, block: Block<'static>) -> Self {
+        self.block = block;
+        self
+    }
+
+    /// Title shown in the top border.
+    pub fn title(mut self, title: impl Into<Line<'static>>) -> Self {
+        self.block = self.block.title(title);
+        self
+    }
+
+    /// Which sides have a border; `Borders::NONE` keeps only padding and margins.
+    pub fn borders(mut self, borders: Borders) -> Self {
+        self.block = self.block.borders(borders);
+        self
+    }
+
+    pub fn border_type(mut self, border_type: BorderType) -> Self {
+        self.block = self.block.border_type(border_type);
+        self
+    }
+
+    pub fn border_style(mut self, style: impl Into<Style>) -> Self {
+        self.block = self.block.border_style(style);
+        self
+    }
+
+    /// Base style of the whole block, inherited by the content cells.
+    pub fn style(mut self, style: impl Into<Style>) -> Self {
+        self.block = self.block.style(style);
+        self
+    }
+
+    /// Space between the border and the content.
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.block = self.block.padding(padding);
+        self
+    }
+
+    /// Space left empty outside the border.
+    pub fn margin(mut self, margin: Margin) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// The area the wrapped component was last rendered into.
+    pub fn content_area(&self) -> Option<Rect> {
+        self.area
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// A context for the wrapped component, narrowed to its area once it is known and
+    /// sharing this wrapper's observers and timers.
+    fn context(&self, cx: &Context<Self>) -> Context<C> {
+        cx.project(None, self.area, |styled| &mut styled.inner)
+    }
+}
+
+impl<C: Component> Component for Styled<C> {
+    fn on_mount(&mut self, cx: &mut Context<Self>) {
+        self.inner.on_mount(&mut self.context(cx));
+    }
+
+    fn on_enter(&mut self, cx: &mut Context<Self>) {
+        self.inner.on_enter(&mut self.context(cx));
+    }
+
+    fn on_exit(&mut self, cx: &mut Context<Self>) {
+        self.inner.on_exit(&mut self.context(cx));
+    }
+
+    fn on_shutdown(&mut self, cx: &mut Context<Self>) {
+        self.inner.on_shutdown(&mut self.context(cx));
+    }
+
+    fn on_resize(&mut self, width: u16, height: u16, cx: &mut Context<Self>) {
+        self.inner.on_resize(width, height, &mut self.context(cx));
+    }
+
+    fn on_focus(&mut self, cx: &mut Context<Self>) {
+        self.inner.on_focus(&mut self.context(cx));
+    }
+
+    fn on_blur(&mut self, cx: &mut Context<Self>) {
+        self.inner.on_blur(&mut self.context(cx));
+    }
+
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let area = cx.area(frame).inner(self.margin);
+        let content = self.block.inner(area);
+        frame.render_widget(&self.block, area);
+        self.area = Some(content);
+        self.inner.render(frame, &mut cx.cast_within(content));
+    }
+
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+        if let (Some(position), Some(area)) = (event.position(), self.area) {
+            if !area.contains(position) {
+                return None;
+            }
+        }
+        let mut inner = self.context(cx);
+        let action = self.inner.handle_event(event, &mut inner);
+        if self.inner.refresh_on_event() {
+            inner.refresh();
+        }
+        action
+    }
+
+    fn memoize(&self) -> bool {
+        self.inner.memoize()
+    }
+
+    fn refresh_on_event(&self) -> bool {
+        self.inner.refresh_on_event()
+    }
+
+    fn status(&self) -> PageStatus {
+        self.inner.status()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[derive(Default)]
+    struct Probe {
+        rendered_in: Option<Rect>,
+        clicks: Vec<(u16, u16)>,
+    }
+
+    impl Component for Probe {
+        fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+            self.rendered_in = Some(cx.area(frame));
+        }
+
+        fn handle_event(&mut self, event: Event, cx: &mut Context<Self>) -> Option<Action> {
+            if let Event::Mouse(mouse) = event {
+                self.clicks.extend(cx.local_position(mouse.column, mouse.row));
+            }
+            None
+        }
+    }
+
+    fn click(column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent { kind: MouseEventKind::Down(MouseButton::Left), column, row, modifiers: KeyModifiers::NONE })
+    }
+
+    #[test]
+    fn test_wraps_content_in_block() {
+        let (app, _refresh, _inject) = crate::AppContext::new();
+        let mut styled = Styled::new(Probe::default()).title("Log").padding(Padding::uniform(1)).margin(Margin::new(2, 1));
+        let mut cx = Context::<Styled<Probe>>::new(app, crate::Entity::new(Styled::new(Probe::default())).downgrade());
+        let mut terminal = Terminal::new(TestBackend::new(30, 10)).unwrap();
+        terminal.draw(|frame| styled.render(frame, &mut cx)).unwrap();
+
+        let content = Rect::new(4, 3, 22, 4);
+        assert_eq!(styled.inner().rendered_in, Some(content));
+        assert_eq!(styled.content_area(), Some(content));
+        let buffer = terminal.backend().buffer();
+        assert_eq!((buffer[(1, 0)].symbol(), buffer[(2, 1)].symbol(), buffer[(3, 1)].symbol()), (" ", "┌", "L"));
+
+        assert!(styled.handle_event(click(2, 1), &mut cx).is_none());
+        styled.handle_event(click(5, 4), &mut cx);
+        assert_eq!(styled.inner().clicks, [(1, 1)]);
+    }
+}
@@ -1,8 +1,12 @@
 use crate::application::{Context, EventContext};
 use std::any::Any;
+use std::sync::Arc;
 
 /// Event type for component interactions.
+///
+/// New kinds of events are added over time, so matches need a `_` arm.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum Event {
     Key(crossterm::event::KeyEvent),
     Mouse(crossterm::event::MouseEvent),
@@ -10,7 +14,266 @@ pub enum Event {
     FocusGained,
     FocusLost,
     Paste(String),
-    Custom(String),
+    /// A named event with an arbitrary payload, injected via `cx.emit_custom(...)`.
+    Custom(CustomEvent),
+    /// An application-defined event emitted via `cx.emit_app_event(...)`, always of the
+    /// type declared with `Application::<E>`; read it with `event.app::<E>()`.
+    App(AppEvent),
+    /// The payload of a drag started with `cx.start_drag(...)`, released over this point.
+    Drop(DropEvent),
+    /// A press and release of `button` on one cell, delivered after the release.
+    /// `count` is 2 for a double click, 3 for a triple click, and so on (see
+    /// `AppContext::set_double_click_interval`).
+    MouseClick {
+        count: u8,
+        button: crossterm::event::MouseButton,
+        position: ratatui::layout::Position,
+    },
+    /// A key sequence registered with `cx.register_chord(...)` was typed.
+    Chord(String),
+}
+
+impl Event {
+    /// Get the typed application event, if this is an `Event::App` carrying an `E`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if let Some(MyEvent::Saved(path)) = event.app::<MyEvent>() {
+    ///     self.status = format!("Saved {}", path);
+    /// }
+    /// ```
+    pub fn app<E: 'static>(&self) -> Option<&E> {
+        match self {
+            Event::App(app_event) => app_event.downcast_ref::<E>(),
+            _ => None,
+        }
+    }
+
+    /// Get the typed payload, if this is an `Event::Custom` carrying a `T`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if let Some(status) = event.custom::<BuildStatus>() {
+    ///     self.status = status.clone();
+    /// }
+    /// ```
+    pub fn custom<T: 'static>(&self) -> Option<&T> {
+        match self {
+            Event::Custom(custom) => custom.downcast_ref::<T>(),
+            _ => None,
+        }
+    }
+
+    /// The screen cell a mouse, click or drop event happened at.
+    pub fn position(&self) -> Option<ratatui::layout::Position> {
+        match self {
+            Event::Mouse(mouse) => Some(ratatui::layout::Position::new(mouse.column, mouse.row)),
+            Event::MouseClick { position, .. } => Some(*position),
+            Event::Drop(drop) => Some(drop.position()),
+            _ => None,
+        }
+    }
+
+    /// Whether the event comes from the user (keys, mouse, paste) rather than the
+    /// terminal or the application.
+    pub(crate) fn is_input(&self) -> bool {
+        matches!(
+            self,
+            Event::Key(_) | Event::Mouse(_) | Event::Paste(_) | Event::Drop(_) | Event::MouseClick { .. } | Event::Chord(_)
+        )
+    }
+
+    /// Get the typed drag payload, if this is an `Event::Drop` carrying a `T`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if let (Some(card), Event::Drop(drop)) = (event.dropped::<CardId>(), &event) {
+    ///     self.move_card(*card, drop.position());
+    /// }
+    /// ```
+    pub fn dropped<T: 'static>(&self) -> Option<&T> {
+        match self {
+            Event::Drop(drop) => drop.downcast_ref::<T>(),
+            _ => None,
+        }
+    }
+}
+
+/// A named custom event with a type-erased payload.
+#[derive(Clone)]
+pub struct CustomEvent {
+    name: String,
+    payload: Arc<dyn Any + Send + Sync>,
+    type_name: &'static str,
+}
+
+impl CustomEvent {
+    /// Create a custom event with the given name and payload.
+    pub fn new<T: Any + Send + Sync>(name: impl Into<String>, payload: T) -> Self {
+        Self {
+            name: name.into(),
+            payload: Arc::new(payload),
+            type_name: std::any::type_name::<T>(),
+        }
+    }
+
+    /// The event name, useful for routing without downcasting.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Check whether the payload is of type `T`.
+    pub fn is<T: 'static>(&self) -> bool {
+        self.payload.is::<T>()
+    }
+
+    /// Downcast the payload to a reference of type `T`.
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.payload.downcast_ref::<T>()
+    }
+
+    /// The type name of the payload, for diagnostics.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Clone out the shared payload handle.
+    pub fn payload(&self) -> Arc<dyn Any + Send + Sync> {
+        Arc::clone(&self.payload)
+    }
+}
+
+impl std::fmt::Debug for CustomEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CustomEvent({:?}: {})", self.name, self.type_name)
+    }
+}
+
+/// An application event, as declared with `Application::<E>`.
+///
+/// Only `emit_app_event` creates these, after checking the payload against the
+/// declared type, so components can trust `event.app::<E>()`.
+#[derive(Clone)]
+pub struct AppEvent {
+    payload: Arc<dyn Any + Send + Sync>,
+    type_name: &'static str,
+}
+
+impl AppEvent {
+    pub(crate) fn new<E: Any + Send + Sync>(event: E) -> Self {
+        Self { payload: Arc::new(event), type_name: std::any::type_name::<E>() }
+    }
+
+    /// Check whether the payload is of type `E`.
+    pub fn is<E: 'static>(&self) -> bool {
+        self.payload.is::<E>()
+    }
+
+    /// Downcast the payload to a reference of type `E`.
+    pub fn downcast_ref<E: 'static>(&self) -> Option<&E> {
+        self.payload.downcast_ref::<E>()
+    }
+
+    /// The type name of the payload, for diagnostics.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+impl std::fmt::Debug for AppEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AppEvent({})", self.type_name)
+    }
+}
+
+/// A dragged payload released at a screen position.
+#[derive(Clone)]
+pub struct DropEvent {
+    payload: Arc<dyn Any + Send + Sync>,
+    type_name: &'static str,
+    position: ratatui::layout::Position,
+}
+
+impl DropEvent {
+    pub(crate) fn new(payload: Arc<dyn Any + Send + Sync>, type_name: &'static str, position: ratatui::layout::Position) -> Self {
+        Self { payload, type_name, position }
+    }
+
+    /// Where the payload was released, in screen cells.
+    pub fn position(&self) -> ratatui::layout::Position {
+        self.position
+    }
+
+    /// Check whether the payload is of type `T`.
+    pub fn is<T: 'static>(&self) -> bool {
+        self.payload.is::<T>()
+    }
+
+    /// Downcast the payload to a reference of type `T`.
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.payload.downcast_ref::<T>()
+    }
+
+    /// The type name of the payload, for diagnostics.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+impl std::fmt::Debug for DropEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DropEvent({} at {})", self.type_name, self.position)
+    }
+}
+
+/// A type-erased app-defined action, returned as `Action::Dispatch`.
+#[derive(Clone)]
+pub struct CustomAction {
+    payload: Arc<dyn Any + Send + Sync>,
+    type_name: &'static str,
+}
+
+impl CustomAction {
+    /// Wrap a typed action value.
+    pub fn new<T: Any + Send + Sync>(action: T) -> Self {
+        Self {
+            payload: Arc::new(action),
+            type_name: std::any::type_name::<T>(),
+        }
+    }
+
+    /// Check whether the payload is of type `T`.
+    pub fn is<T: 'static>(&self) -> bool {
+        self.payload.is::<T>()
+    }
+
+    /// Downcast the payload to a reference of type `T`.
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.payload.downcast_ref::<T>()
+    }
+
+    /// The type name of the payload, for diagnostics.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The `TypeId` of the payload, for looking up its handler.
+    pub(crate) fn payload_type(&self) -> std::any::TypeId {
+        Any::type_id(&*self.payload)
+    }
+}
+
+impl std::fmt::Debug for CustomAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CustomAction({})", self.type_name)
+    }
+}
+
+/// Two actions are equal only if they are clones of the same dispatch.
+impl PartialEq for CustomAction {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.payload, &other.payload)
+    }
 }
 
 /// Action that a component can return after handling an event.
@@ -20,6 +283,16 @@ pub enum Action<R = String> {
     Back,
     Quit,
     Noop,
+    /// An app-defined action (`Save`, `OpenFile`), passed up through routers and shells
+    /// to the handler registered with `AppContext::on_action`.
+    Dispatch(CustomAction),
+}
+
+impl<R> Action<R> {
+    /// Dispatch an app-defined action.
+    pub fn dispatch<T: Any + Send + Sync>(action: T) -> Self {
+        Action::Dispatch(CustomAction::new(action))
+    }
 }
 
 /// The core Component trait for implementers.
@@ -30,6 +303,18 @@ pub trait Component: Send + Sync + 'static {
         let _ = cx;
     }
 
+    /// Async setup, started right after `on_mount`. Until the returned task completes,
+    /// a loading placeholder is drawn in place of the component and input events are
+    /// not delivered to it; its output is applied before the first `render`. Build the
+    /// task with `cx.mount_async`. Honored for the root and for `ChildView` children.
+    fn on_mount_async(&mut self, cx: &mut Context<Self>) -> Option<super::mount::MountTask<Self>>
+    where
+        Self: Sized,
+    {
+        let _ = cx;
+        None
+    }
+
     /// Called each time the component becomes the active view (navigation entry).
     /// Use this for per-visit initialization like resetting transient state.
     fn on_enter(&mut self, cx: &mut Context<Self>) {
@@ -38,6 +323,7 @@ pub trait Component: Send + Sync + 'static {
 
     /// Called when the component is removed from the active view (e.g. navigation away).
     /// Use this for cleanup like pausing background tasks.
+    /// Observers registered with `cx.observe` are removed once this returns.
     fn on_exit(&mut self, cx: &mut Context<Self>) {
         let _ = cx;
     }
@@ -48,6 +334,26 @@ pub trait Component: Send + Sync + 'static {
         let _ = cx;
     }
 
+    /// Called when the terminal is resized, before `Event::Resize` is delivered.
+    /// Use this to recompute cached layouts or canvases sized to the screen.
+    fn on_resize(&mut self, width: u16, height: u16, cx: &mut Context<Self>) {
+        let _ = (width, height, cx);
+    }
+
+    /// Called when key focus moves to this component: its slot of an `AppShell` or
+    /// `define_app!` root is focused (`F6`, a click, `focus_slot`), or the terminal
+    /// window regains focus while it has key focus. Use this to show a cursor or
+    /// highlight a border. Focus starts on the body, without a call.
+    fn on_focus(&mut self, cx: &mut Context<Self>) {
+        let _ = cx;
+    }
+
+    /// Called when key focus leaves this component, or the terminal window loses focus
+    /// while it has key focus (see `on_focus`).
+    fn on_blur(&mut self, cx: &mut Context<Self>) {
+        let _ = cx;
+    }
+
     /// Render the component into the given area.
     fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>);
 
@@ -57,46 +363,109 @@ pub trait Component: Send + Sync + 'static {
         let _ = cx;
         None
     }
+
+    /// Whether `render` may be skipped while nothing it depends on changed, reusing the
+    /// cells it drew last. Applies to the root component and to `ChildView` children
+    /// (like `ChildView::cached`). A memoized component must subscribe to every entity
+    /// it reads in `render` and must not draw anything that changes on its own.
+    fn memoize(&self) -> bool {
+        false
+    }
+
+    /// Whether every event delivered to this component should redraw the screen.
+    ///
+    /// After an event, a frame is only drawn if a handler returned an action, updated an
+    /// entity or called `cx.notify()`. Components that change plain fields in
+    /// `handle_event` return true here (or call `cx.notify()` when they do).
+    fn refresh_on_event(&self) -> bool {
+        false
+    }
+
+    /// Whether this component, as a routed page, is loading or failed.
+    /// `define_app!` shows the route's loading or error component while it is not ready.
+    fn status(&self) -> crate::router::PageStatus {
+        crate::router::PageStatus::Ready
+    }
 }
 
 /// A dyn-compatible version of the Component trait.
 pub trait AnyComponent: Any + Send + Sync + 'static {
     fn on_mount_any(&mut self, cx: &mut Context<dyn AnyComponent>);
+    fn on_mount_async_any(&mut self, cx: &mut Context<dyn AnyComponent>) -> Option<super::mount::MountTask<dyn AnyComponent>>;
     fn on_enter_any(&mut self, cx: &mut Context<dyn AnyComponent>);
     fn on_exit_any(&mut self, cx: &mut Context<dyn AnyComponent>);
     fn on_shutdown_any(&mut self, cx: &mut Context<dyn AnyComponent>);
+    fn on_resize_any(&mut self, width: u16, height: u16, cx: &mut Context<dyn AnyComponent>);
+    fn on_focus_any(&mut self, cx: &mut Context<dyn AnyComponent>);
+    fn on_blur_any(&mut self, cx: &mut Context<dyn AnyComponent>);
     fn render_any(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<dyn AnyComponent>);
     fn handle_event_any(&mut self, event: Event, cx: &mut EventContext<dyn AnyComponent>) -> Option<Action>;
+    fn status_any(&self) -> crate::router::PageStatus;
+    fn memoize_any(&self) -> bool;
 }
 
 impl<T: Component> AnyComponent for T {
     fn on_mount_any(&mut self, cx: &mut Context<dyn AnyComponent>) {
-        let mut cx = cx.cast::<Self>();
+        let mut cx = cx.downcast::<Self>();
         self.on_mount(&mut cx);
     }
 
+    fn on_mount_async_any(&mut self, cx: &mut Context<dyn AnyComponent>) -> Option<super::mount::MountTask<dyn AnyComponent>> {
+        let mut cx = cx.downcast::<Self>();
+        self.on_mount_async(&mut cx).map(super::mount::MountTask::into_any)
+    }
+
     fn on_enter_any(&mut self, cx: &mut Context<dyn AnyComponent>) {
-        let mut cx = cx.cast::<Self>();
+        let mut cx = cx.downcast::<Self>();
         self.on_enter(&mut cx);
     }
 
     fn on_exit_any(&mut self, cx: &mut Context<dyn AnyComponent>) {
-        let mut cx = cx.cast::<Self>();
-        self.on_exit(&mut cx);
+        let mut cast = cx.downcast::<Self>();
+        self.on_exit(&mut cast);
+        cx.unsubscribe_all();
     }
 
     fn on_shutdown_any(&mut self, cx: &mut Context<dyn AnyComponent>) {
-        let mut cx = cx.cast::<Self>();
+        let mut cx = cx.downcast::<Self>();
         self.on_shutdown(&mut cx);
     }
 
+    fn on_resize_any(&mut self, width: u16, height: u16, cx: &mut Context<dyn AnyComponent>) {
+        let mut cx = cx.downcast::<Self>();
+        self.on_resize(width, height, &mut cx);
+    }
+
+    fn on_focus_any(&mut self, cx: &mut Context<dyn AnyComponent>) {
+        let mut cx = cx.downcast::<Self>();
+        self.on_focus(&mut cx);
+    }
+
+    fn on_blur_any(&mut self, cx: &mut Context<dyn AnyComponent>) {
+        let mut cx = cx.downcast::<Self>();
+        self.on_blur(&mut cx);
+    }
+
     fn render_any(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<dyn AnyComponent>) {
-        let mut cx = cx.cast::<Self>();
-        self.render(frame, &mut cx);
+        let mut cx = cx.downcast::<Self>();
+        let budget = cx.frame_budget().clone();
+        budget.measure::<Self, _>(|| self.render(frame, &mut cx));
     }
 
     fn handle_event_any(&mut self, event: Event, cx: &mut EventContext<dyn AnyComponent>) -> Option<Action> {
-        let mut cx = cx.cast::<Self>();
-        self.handle_event(event, &mut cx)
+        let mut cx = cx.downcast::<Self>();
+        let action = self.handle_event(event, &mut cx);
+        if self.refresh_on_event() {
+            cx.refresh();
+        }
+        action
+    }
+
+    fn status_any(&self) -> crate::router::PageStatus {
+        self.status()
+    }
+
+    fn memoize_any(&self) -> bool {
+        self.memoize()
     }
 }
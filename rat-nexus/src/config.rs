@@ -0,0 +1,448 @@
+//! Application settings loaded from files, the environment and the command line.
+//!
+//! A settings type implements [`Config`] to read its fields from, and write them to, a
+//! [`ConfigMap`] of dotted keys such as `ui.theme`. [`ConfigLoader`] layers, from lowest
+//! to highest precedence:
+//!
+//! 1. `Config::default()`
+//! 2. `<app>/config.toml` in each `$XDG_CONFIG_DIRS` (default `/etc/xdg`)
+//! 3. `<app>/config.toml` in `$XDG_CONFIG_HOME` (default `~/.config`; `%APPDATA%` on
+//!    Windows), or the file given with `--config <path>`
+//! 4. environment variables named after the key: `ui.theme` of app `my-app` is
+//!    `MY_APP_UI_THEME`
+//! 5. command-line arguments: `--ui.theme=Nord`, `--ui.theme Nord`, `--set ui.theme=Nord`,
+//!    or a bare `--ui.show_fps` for `true`; dashes in keys match underscores
+//!
+//! Files are TOML (read with the `toml` crate; `config` feature) whose values are
+//! strings, numbers and booleans; nested tables become dotted keys.
+//! `AppContext::load_config` stores the result as an `Entity<T>`, reachable from any
+//! component with `cx.config::<T>()`, and `AppContext::save_config` writes the keys
+//! changed since loading back to the user file.
+//!
+//! # Example
+//! ```ignore
+//! impl Config for Settings {
+//!     fn apply(&mut self, map: &ConfigMap) {
+//!         map.read_into("ui.theme", &mut self.theme);
+//!         map.read_into("editor.tab_width", &mut self.tab_width);
+//!     }
+//!
+//!     fn to_map(&self) -> ConfigMap {
+//!         ConfigMap::new().with("ui.theme", &self.theme).with("editor.tab_width", self.tab_width)
+//!     }
+//! }
+//!
+//! Application::new().config::<Settings>(ConfigLoader::new("my-app")).run(|cx| { .. })
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use crate::state::Entity;
+
+/// File name looked up in each configuration directory.
+pub const FILE_NAME: &str = "config.toml";
+
+/// Settings read from and written to a [`ConfigMap`].
+pub trait Config: Default + Send + Sync + 'static {
+    /// Read the fields present in `map`, leaving the others unchanged.
+    fn apply(&mut self, map: &ConfigMap);
+
+    /// Every field, keyed as in the configuration file.
+    fn to_map(&self) -> ConfigMap;
+}
+
+/// Dotted keys (`section.key`) to their values, as text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigMap {
+    entries: BTreeMap<String, String>,
+}
+
+impl ConfigMap {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a value, builder style.
+    pub fn with(mut self, key: &str, value: impl Display) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    /// Insert a value.
+    pub fn insert(&mut self, key: &str, value: impl Display) {
+        self.entries.insert(key.to_string(), value.to_string());
+    }
+
+    /// Get and parse a value.
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.entries.get(key).and_then(|v| v.parse().ok())
+    }
+
+    /// Get the raw string value.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    /// Overwrite `field` with the value of `key` if it is present and parses.
+    pub fn read_into<T: FromStr>(&self, key: &str, field: &mut T) {
+        if let Some(value) = self.get(key) {
+            *field = value;
+        }
+    }
+
+    /// Iterate over the entries in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Copy every entry of `other` over this map.
+    pub fn merge(&mut self, other: &ConfigMap) {
+        self.entries.extend(other.entries.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+
+    /// Parse TOML into dotted keys. Values must be strings, numbers or booleans.
+    pub fn parse_toml(data: &str) -> io::Result<Self> {
+        let table: toml::Table = data.parse().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let mut map = Self::new();
+        map.insert_table(None, table)?;
+        Ok(map)
+    }
+
+    fn insert_table(&mut self, prefix: Option<&str>, table: toml::Table) -> io::Result<()> {
+        for (key, value) in table {
+            let key = match prefix {
+                Some(prefix) => format!("{prefix}.{key}"),
+                None => key,
+            };
+            match value {
+                toml::Value::Table(table) => self.insert_table(Some(&key), table)?,
+                toml::Value::String(text) => self.insert(&key, text),
+                toml::Value::Integer(number) => self.insert(&key, number),
+                toml::Value::Float(number) => self.insert(&key, number),
+                toml::Value::Boolean(flag) => self.insert(&key, flag),
+                other => {
+                    let reason = format!("`{key}`: expected a string, number or boolean, found {}", other.type_str());
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, reason));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Format as TOML, one table per key prefix.
+    pub fn to_toml(&self) -> String {
+        let mut root = toml::Table::new();
+        for (key, value) in self.iter() {
+            let (table, key) = match key.split_once('.') {
+                Some((table, key)) => match root.entry(table).or_insert_with(|| toml::Table::new().into()) {
+                    toml::Value::Table(table) => (table, key),
+                    _ => continue,
+                },
+                None => (&mut root, key),
+            };
+            table.insert(key.to_string(), toml_value(value));
+        }
+        root.to_string()
+    }
+}
+
+/// Where settings come from; see the module docs for the precedence.
+#[derive(Debug, Clone)]
+pub struct ConfigLoader {
+    app: String,
+    system_files: Vec<PathBuf>,
+    user_file: Option<PathBuf>,
+    env_prefix: String,
+    vars: Vec<(String, String)>,
+    args: Vec<String>,
+}
+
+impl ConfigLoader {
+    /// Look up the settings of the app named `app` in the standard locations, the
+    /// process environment and `std::env::args`.
+    pub fn new(app: impl Into<String>) -> Self {
+        let app = app.into();
+        let var = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty());
+        let system_dirs: Vec<PathBuf> = match var("XDG_CONFIG_DIRS") {
+            Some(dirs) => std::env::split_paths(&dirs).collect(),
+            None if cfg!(unix) => vec![PathBuf::from("/etc/xdg")],
+            None => Vec::new(),
+        };
+        let user_dir = var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| if cfg!(windows) { var("APPDATA").map(PathBuf::from) } else { None })
+            .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".config")));
+        Self {
+            env_prefix: app.to_ascii_uppercase().replace(['-', '.', ' '], "_"),
+            system_files: system_dirs.into_iter().map(|dir| dir.join(&app).join(FILE_NAME)).collect(),
+            user_file: user_dir.map(|dir| dir.join(&app).join(FILE_NAME)),
+            vars: std::env::vars().collect(),
+            args: std::env::args().skip(1).collect(),
+            app,
+        }
+    }
+
+    /// Read and save the user's settings at `path` instead of the XDG location.
+    pub fn user_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.user_file = Some(path.into());
+        self
+    }
+
+    /// Skip the system-wide configuration directories.
+    pub fn no_system_files(mut self) -> Self {
+        self.system_files.clear();
+        self
+    }
+
+    /// Prefix of environment variable overrides; defaults to the app name in capitals.
+    pub fn env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.env_prefix = prefix.into();
+        self
+    }
+
+    /// Read overrides from `vars` instead of the process environment.
+    pub fn vars<K: Into<String>, V: Into<String>>(mut self, vars: impl IntoIterator<Item = (K, V)>) -> Self {
+        self.vars = vars.into_iter().map(|(k, v)| (k.into(), v.into())).collect();
+        self
+    }
+
+    /// Read overrides from `args` (without the program name) instead of `std::env::args`.
+    pub fn args<S: Into<String>>(mut self, args: impl IntoIterator<Item = S>) -> Self {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The app name settings are looked up under.
+    pub fn app(&self) -> &str {
+        &self.app
+    }
+
+    /// The file user settings are read from and saved to, after `--config`.
+    pub fn user_path(&self) -> Option<PathBuf> {
+        let mut args = self.args.iter();
+        while let Some(arg) = args.next() {
+            if let Some(path) = arg.strip_prefix("--config=") {
+                return Some(PathBuf::from(path));
+            }
+            if arg == "--config" {
+                return args.next().map(PathBuf::from);
+            }
+        }
+        self.user_file.clone()
+    }
+
+    /// Build the settings from every source.
+    pub fn load<T: Config>(&self) -> io::Result<T> {
+        let mut value = T::default();
+        value.apply(&self.merged(&value.to_map())?);
+        Ok(value)
+    }
+
+    /// The files, environment and command line merged over `defaults`.
+    fn merged(&self, defaults: &ConfigMap) -> io::Result<ConfigMap> {
+        let mut map = defaults.clone();
+        for path in self.system_files.iter().cloned().chain(self.user_path()) {
+            if let Some(file) = read_file(&path)? {
+                map.merge(&file);
+            }
+        }
+        let keys: Vec<String> = map.entries.keys().cloned().collect();
+        for key in &keys {
+            let name = format!("{}_{}", self.env_prefix, key.to_ascii_uppercase().replace(['.', '-'], "_"));
+            if let Some((_, value)) = self.vars.iter().find(|(var, _)| *var == name) {
+                map.insert(key, value);
+            }
+        }
+        let known = |name: &str| keys.iter().find(|key| key.replace('-', "_") == name.replace('-', "_")).cloned();
+        let mut args = self.args.iter().peekable();
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.strip_prefix("--") else { continue };
+            let flag = if flag == "set" { args.next().map(String::as_str).unwrap_or_default() } else { flag };
+            let (name, inline) = match flag.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (flag, None),
+            };
+            let Some(key) = known(name) else { continue };
+            let value = match inline {
+                Some(value) => value,
+                None => match args.peek() {
+                    Some(next) if !next.starts_with("--") => args.next().cloned().unwrap_or_default(),
+                    _ => "true".to_string(),
+                },
+            };
+            map.insert(&key, value);
+        }
+        Ok(map)
+    }
+}
+
+/// Parse the file at `path`, or `None` if it does not exist.
+fn read_file(path: &Path) -> io::Result<Option<ConfigMap>> {
+    match std::fs::read_to_string(path) {
+        Ok(data) => ConfigMap::parse_toml(&data)
+            .map(Some)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display()))),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Loaded settings, kept in the `AppContext` state.
+pub(crate) struct ConfigStore<T: Config> {
+    pub(crate) entity: Entity<T>,
+    loader: Arc<ConfigLoader>,
+    /// The values as of the last load or save; only keys changed since are written.
+    baseline: Arc<Mutex<ConfigMap>>,
+}
+
+impl<T: Config> Clone for ConfigStore<T> {
+    fn clone(&self) -> Self {
+        Self { entity: Entity::clone(&self.entity), loader: Arc::clone(&self.loader), baseline: Arc::clone(&self.baseline) }
+    }
+}
+
+impl<T: Config> ConfigStore<T> {
+    pub(crate) fn load(loader: ConfigLoader) -> io::Result<Self> {
+        let value: T = loader.load()?;
+        let baseline = Arc::new(Mutex::new(value.to_map()));
+        Ok(Self { entity: Entity::new(value), loader: Arc::new(loader), baseline })
+    }
+
+    /// Write the keys changed since loading into the user file, keeping its other keys.
+    pub(crate) fn save(&self) -> io::Result<()> {
+        let path = self.loader.user_path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no user configuration directory"))?;
+        let current = self.entity.read(T::to_map).map_err(|e| io::Error::other(e.to_string()))?;
+        let mut baseline = self.baseline.lock().map_err(|_| io::Error::other("configuration lock poisoned"))?;
+        let mut file = read_file(&path)?.unwrap_or_default();
+        for (key, value) in current.iter() {
+            if baseline.get_str(key) != Some(value) {
+                file.insert(key, value);
+            }
+        }
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, file.to_toml())?;
+        std::fs::rename(&tmp, &path)?;
+        *baseline = current;
+        Ok(())
+    }
+}
+
+/// `value` as it is written to a file: a number or boolean if it reads back as one, a
+/// string otherwise.
+fn toml_value(value: &str) -> toml::Value {
+    let numeric = value.chars().all(|c| c.is_ascii_digit() || "+-.eE".contains(c));
+    if let Ok(flag) = value.parse::<bool>() {
+        flag.into()
+    } else if let Some(number) = value.parse::<i64>().ok().filter(|_| numeric) {
+        number.into()
+    } else if let Some(number) = value.parse::<f64>().ok().filter(|_| numeric) {
+        number.into()
+    } else {
+        value.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Settings {
+        theme: String,
+        tab_width: u8,
+        show_fps: bool,
+    }
+
+    impl Default for Settings {
+        fn default() -> Self {
+            Self { theme: "Dark".into(), tab_width: 4, show_fps: false }
+        }
+    }
+
+    impl Config for Settings {
+        fn apply(&mut self, map: &ConfigMap) {
+            map.read_into("ui.theme", &mut self.theme);
+            map.read_into("ui.show_fps", &mut self.show_fps);
+            map.read_into("editor.tab_width", &mut self.tab_width);
+        }
+
+        fn to_map(&self) -> ConfigMap {
+            ConfigMap::new()
+                .with("ui.theme", &self.theme)
+                .with("ui.show_fps", self.show_fps)
+                .with("editor.tab_width", self.tab_width)
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rat-nexus-config-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_toml_roundtrip() {
+        let map = ConfigMap::parse_toml("top = 1\n[ui]\ntheme = \"Nord # not a comment\" # comment\nshow_fps = true\n").unwrap();
+        assert_eq!(map.get_str("ui.theme"), Some("Nord # not a comment"));
+        assert_eq!(map.get::<bool>("ui.show_fps"), Some(true));
+        assert_eq!(map.get::<u8>("top"), Some(1));
+        assert_eq!(ConfigMap::parse_toml(&map.to_toml()).unwrap(), map);
+        assert!(ConfigMap::parse_toml("[ui]\ntheme = Nord").is_err());
+        assert!(ConfigMap::parse_toml("fonts = [\"mono\"]").is_err());
+        assert_eq!(ConfigMap::parse_toml("[ui.colors]\naccent = 'cyan'").unwrap().get_str("ui.colors.accent"), Some("cyan"));
+    }
+
+    #[test]
+    fn test_layers_files_env_and_args() {
+        let dir = temp_dir("layers");
+        std::fs::create_dir_all(dir.join("system/demo")).unwrap();
+        std::fs::write(dir.join("system/demo/config.toml"), "[ui]\ntheme = \"Nord\"\n[editor]\ntab_width = 2\n").unwrap();
+        std::fs::write(dir.join("user.toml"), "[editor]\ntab_width = 8\n").unwrap();
+
+        let loader = |args: &[&str]| ConfigLoader {
+            system_files: vec![dir.join("system/demo/config.toml")],
+            ..ConfigLoader::new("demo")
+                .user_file(dir.join("user.toml"))
+                .vars([("DEMO_UI_THEME", "Gruvbox")])
+                .args(args.iter().copied())
+        };
+        let settings: Settings = loader(&[]).load().unwrap();
+        assert_eq!(settings, Settings { theme: "Gruvbox".into(), tab_width: 8, show_fps: false });
+
+        let settings: Settings = loader(&["--editor.tab-width", "3", "--ui.show_fps", "--set", "ui.theme=Light", "file.txt"]).load().unwrap();
+        assert_eq!(settings, Settings { theme: "Light".into(), tab_width: 3, show_fps: true });
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_save_writes_only_changed_keys() {
+        let dir = temp_dir("save");
+        let path = dir.join("app/config.toml");
+        let loader = ConfigLoader::new("demo")
+            .no_system_files()
+            .user_file(&path)
+            .vars([("DEMO_UI_THEME", "Gruvbox")])
+            .args(Vec::<String>::new());
+        let (app, _refresh, _inject) = crate::AppContext::new();
+        let settings = app.load_config::<Settings>(loader).unwrap();
+        assert_eq!(app.config::<Settings>().unwrap().read(|s| s.theme.clone()).unwrap(), "Gruvbox");
+
+        settings.update(|s| s.tab_width = 2).unwrap();
+        app.save_config::<Settings>().unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(written, "[editor]\ntab_width = 2\n");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
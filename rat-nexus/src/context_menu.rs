@@ -0,0 +1,473 @@
+//! Context menus: a popup list of commands opened at the pointer.
+//!
+//! A component opens one from its right-click handler with
+//! `cx.open_context_menu((mouse.column, mouse.row), items)`. The menu is drawn above
+//! everything else and receives key and mouse events before the root component until it
+//! closes. Picking an item delivers a `MenuPick` carrying the item's id as the custom
+//! event `MENU_PICKED`.
+//!
+//! Keys: `↑/↓` move, `→`/`Enter` open a submenu, `←` closes it, `Enter` picks, `Esc`
+//! closes. Hovering moves the selection and opens submenus; clicking outside closes.
+//!
+//! # Example
+//! ```ignore
+//! Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Right) => {
+//!     cx.open_context_menu((mouse.column, mouse.row), [
+//!         MenuItem::new("copy", "Copy").hint("Ctrl+C"),
+//!         MenuItem::new("paste", "Paste").enabled(self.clipboard.is_some()),
+//!         MenuItem::separator(),
+//!         MenuItem::submenu("Sort by", [MenuItem::new("sort-name", "Name"), MenuItem::new("sort-size", "Size")]),
+//!     ]);
+//!     None
+//! }
+//! // Later, in handle_event:
+//! if let Some(pick) = event.custom::<MenuPick>() { self.run(&pick.id) }
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, Borders, Clear, Widget};
+
+use crate::component::traits::Event;
+use crate::AppContext;
+
+/// Name of the custom event carrying a `MenuPick`.
+pub const MENU_PICKED: &str = "rat_nexus::menu_picked";
+
+/// An entry in a context menu: a command, a submenu or a separator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuItem {
+    id: String,
+    label: String,
+    hint: String,
+    enabled: bool,
+    separator: bool,
+    children: Vec<MenuItem>,
+}
+
+impl MenuItem {
+    /// A command reported as `MenuPick { id, .. }` when picked.
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self { id: id.into(), label: label.into(), hint: String::new(), enabled: true, separator: false, children: Vec::new() }
+    }
+
+    /// An entry opening a nested menu.
+    pub fn submenu(label: impl Into<String>, children: impl IntoIterator<Item = MenuItem>) -> Self {
+        Self { children: children.into_iter().collect(), ..Self::new("", label) }
+    }
+
+    /// A horizontal rule between groups of entries.
+    pub fn separator() -> Self {
+        Self { enabled: false, separator: true, ..Self::new("", "") }
+    }
+
+    /// Dimmed text on the right, e.g. the key that runs the same command.
+    pub fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = hint.into();
+        self
+    }
+
+    /// Grey the entry out and skip it when moving the selection.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// The id reported when picked.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The text shown.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// The entries of the submenu, empty for commands.
+    pub fn children(&self) -> &[MenuItem] {
+        &self.children
+    }
+
+    fn selectable(&self) -> bool {
+        self.enabled && !self.separator
+    }
+}
+
+/// Delivered as the custom event `MENU_PICKED` when a context menu item is picked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuPick {
+    /// The picked item's id.
+    pub id: String,
+    /// Where the menu was opened, e.g. to find the row that was right-clicked.
+    pub position: Position,
+}
+
+/// Result of an event in a `ContextMenu`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuOutcome {
+    /// Still open.
+    Pending,
+    /// The item with this id was picked.
+    Picked(String),
+    /// Dismissed with `Esc` or a click outside.
+    Closed,
+}
+
+/// A popup menu with nested submenus, anchored at a screen position.
+///
+/// Usually opened through `AppContext::open_context_menu`; can also be owned and drawn
+/// by a component directly.
+#[derive(Debug, Clone)]
+pub struct ContextMenu {
+    position: Position,
+    items: Vec<MenuItem>,
+    /// The selected row of each open level, the root menu first.
+    cursors: Vec<usize>,
+    /// Where each open level was last drawn.
+    areas: Vec<Rect>,
+}
+
+impl ContextMenu {
+    /// Create a menu over `items` with its top-left corner at `position`.
+    pub fn new(position: impl Into<Position>, items: Vec<MenuItem>) -> Self {
+        let cursor = first_selectable(&items);
+        Self { position: position.into(), items, cursors: vec![cursor], areas: Vec::new() }
+    }
+
+    /// Where the menu was opened.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// How many levels are open: 1 for the root menu, plus one per open submenu.
+    pub fn depth(&self) -> usize {
+        self.cursors.len()
+    }
+
+    /// The selected item of the innermost open level.
+    pub fn selected(&self) -> Option<&MenuItem> {
+        let level = self.cursors.len() - 1;
+        self.level_items(level).get(self.cursors[level])
+    }
+
+    /// Handle a key: `↑/↓` move, `→` opens a submenu, `←` closes it, `Enter` picks or
+    /// opens, `Esc` closes the innermost level.
+    pub fn handle_key(&mut self, key: KeyEvent) -> MenuOutcome {
+        match key.code {
+            KeyCode::Esc if self.cursors.len() == 1 => return MenuOutcome::Closed,
+            KeyCode::Esc | KeyCode::Left if self.cursors.len() > 1 => {
+                self.cursors.pop();
+            }
+            KeyCode::Up => self.step(-1),
+            KeyCode::Down | KeyCode::Tab => self.step(1),
+            KeyCode::BackTab => self.step(-1),
+            KeyCode::Home => self.set_cursor(first_selectable(self.innermost())),
+            KeyCode::End => {
+                let last = self.innermost().iter().rposition(MenuItem::selectable).unwrap_or(0);
+                self.set_cursor(last);
+            }
+            KeyCode::Right => {
+                self.open_submenu();
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => return self.activate(),
+            _ => {}
+        }
+        MenuOutcome::Pending
+    }
+
+    /// Handle a mouse event: hovering selects, clicking picks or opens a submenu,
+    /// clicking outside closes.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> MenuOutcome {
+        let hit = self.hit(Position::new(mouse.column, mouse.row));
+        match (mouse.kind, hit) {
+            (MouseEventKind::Down(_), None) => return MenuOutcome::Closed,
+            (MouseEventKind::Down(MouseButton::Left), Some((level, row))) if self.select(level, row) => return self.activate(),
+            (MouseEventKind::Moved, Some((level, row))) if self.select(level, row) => {
+                self.open_submenu();
+            }
+            (MouseEventKind::ScrollUp, _) => self.step(-1),
+            (MouseEventKind::ScrollDown, _) => self.step(1),
+            _ => {}
+        }
+        MenuOutcome::Pending
+    }
+
+    /// Draw the open levels, keeping each inside `bounds`.
+    pub fn draw(&mut self, bounds: Rect, buf: &mut Buffer) {
+        self.areas.clear();
+        for level in 0..self.cursors.len() {
+            let items = self.level_items(level);
+            let area = match self.areas.last() {
+                None => place(bounds, items, self.position.x, self.position.y, None),
+                Some(parent) => {
+                    let y = parent.y + 1 + self.cursors[level - 1] as u16;
+                    place(bounds, items, parent.right(), y, Some(parent.x))
+                }
+            };
+            let cursor = self.cursors[level];
+            let active = level + 1 == self.cursors.len();
+            draw_level(items, cursor, active, area, buf);
+            self.areas.push(area);
+        }
+    }
+
+    fn level_items(&self, level: usize) -> &[MenuItem] {
+        let mut items = self.items.as_slice();
+        for &cursor in &self.cursors[..level] {
+            items = &items[cursor].children;
+        }
+        items
+    }
+
+    fn innermost(&self) -> &[MenuItem] {
+        self.level_items(self.cursors.len() - 1)
+    }
+
+    fn set_cursor(&mut self, cursor: usize) {
+        *self.cursors.last_mut().expect("root level is always open") = cursor;
+    }
+
+    /// Move the selection by `delta` selectable items, wrapping around.
+    fn step(&mut self, delta: isize) {
+        let items = self.innermost();
+        let len = items.len() as isize;
+        let mut cursor = *self.cursors.last().expect("root level is always open") as isize;
+        for _ in 0..len {
+            cursor = (cursor + delta).rem_euclid(len);
+            if items[cursor as usize].selectable() {
+                self.set_cursor(cursor as usize);
+                return;
+            }
+        }
+    }
+
+    /// Select `row` of `level`, closing deeper levels. Returns false for rows that
+    /// cannot be selected.
+    fn select(&mut self, level: usize, row: usize) -> bool {
+        if !self.level_items(level).get(row).is_some_and(MenuItem::selectable) {
+            return false;
+        }
+        self.cursors.truncate(level + 1);
+        self.set_cursor(row);
+        true
+    }
+
+    fn open_submenu(&mut self) -> bool {
+        let children = self.selected().filter(|item| item.selectable()).map(|item| item.children.as_slice()).unwrap_or_default();
+        if children.iter().any(MenuItem::selectable) {
+            let cursor = first_selectable(children);
+            self.cursors.push(cursor);
+            return true;
+        }
+        false
+    }
+
+    fn activate(&mut self) -> MenuOutcome {
+        if self.open_submenu() {
+            return MenuOutcome::Pending;
+        }
+        match self.selected() {
+            Some(item) if item.selectable() && item.children.is_empty() => MenuOutcome::Picked(item.id.clone()),
+            _ => MenuOutcome::Pending,
+        }
+    }
+
+    /// The (level, row) under `position`, innermost level first.
+    fn hit(&self, position: Position) -> Option<(usize, usize)> {
+        let (level, area) = self.areas.iter().enumerate().rev().find(|(_, area)| area.contains(position))?;
+        // The top border maps to no row, the bottom border to one past the last
+        let row = position.y.checked_sub(area.y + 1).map_or(usize::MAX, usize::from);
+        Some((level, row))
+    }
+}
+
+fn first_selectable(items: &[MenuItem]) -> usize {
+    items.iter().position(MenuItem::selectable).unwrap_or(0)
+}
+
+/// The area of a menu over `items` at (`x`, `y`). If it would cross the right edge it
+/// opens to the left of `flip_x` (the parent menu's left edge) or of `x`.
+fn place(bounds: Rect, items: &[MenuItem], x: u16, y: u16, flip_x: Option<u16>) -> Rect {
+    let widest = items
+        .iter()
+        .map(|item| {
+            let hint = item.hint.chars().count() + if item.children.is_empty() { 0 } else { 2 };
+            item.label.chars().count() + if hint > 0 { hint + 2 } else { 0 }
+        })
+        .max()
+        .unwrap_or(0) as u16;
+    let width = (widest + 4).min(bounds.width);
+    let height = (items.len() as u16 + 2).min(bounds.height);
+    let x = if x + width > bounds.right() { flip_x.unwrap_or(x).saturating_sub(width).max(bounds.x) } else { x };
+    let y = y.min(bounds.bottom().saturating_sub(height)).max(bounds.y);
+    Rect::new(x, y, width, height)
+}
+
+fn draw_level(items: &[MenuItem], cursor: usize, active: bool, area: Rect, buf: &mut Buffer) {
+    Clear.render(area, buf);
+    let block = Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    block.render(area, buf);
+    for (row, item) in items.iter().enumerate().take(inner.height as usize) {
+        let y = inner.y + row as u16;
+        if item.separator {
+            buf.set_string(inner.x, y, "─".repeat(inner.width as usize), Style::default().fg(Color::DarkGray));
+            continue;
+        }
+        let style = match (row == cursor && item.selectable(), active) {
+            (true, true) => Style::default().fg(Color::Black).bg(Color::Cyan),
+            (true, false) => Style::default().bg(Color::DarkGray),
+            _ if !item.enabled => Style::default().fg(Color::DarkGray),
+            _ => Style::default(),
+        };
+        buf.set_style(Rect::new(inner.x, y, inner.width, 1), style);
+        buf.set_stringn(inner.x + 1, y, &item.label, inner.width.saturating_sub(2) as usize, style);
+        let hint = if item.children.is_empty() { item.hint.clone() } else { "▸".to_string() };
+        let hint_width = hint.chars().count() as u16;
+        if !hint.is_empty() && hint_width + 2 < inner.width {
+            let hint_style = if row == cursor || !item.enabled { style } else { style.fg(Color::DarkGray) };
+            buf.set_string(inner.right() - hint_width - 1, y, hint, hint_style);
+        }
+    }
+}
+
+/// The open context menu, owned by the `AppContext`.
+#[derive(Clone, Default)]
+pub(crate) struct ContextMenus {
+    open: Arc<Mutex<Option<ContextMenu>>>,
+}
+
+impl ContextMenus {
+    pub(crate) fn open(&self, menu: ContextMenu) {
+        if let Ok(mut open) = self.open.lock() {
+            *open = Some(menu);
+        }
+    }
+
+    pub(crate) fn close(&self) {
+        if let Ok(mut open) = self.open.lock() {
+            *open = None;
+        }
+    }
+
+    pub(crate) fn is_open(&self) -> bool {
+        self.open.lock().map(|open| open.is_some()).unwrap_or(false)
+    }
+
+    /// Offer an event to the open menu. Returns true if it was consumed.
+    pub(crate) fn intercept(&self, event: &Event, app: &AppContext) -> bool {
+        let Ok(mut open) = self.open.lock() else {
+            return false;
+        };
+        let Some(menu) = open.as_mut() else {
+            return false;
+        };
+        let outcome = match event {
+            Event::Key(key) => menu.handle_key(*key),
+            Event::Mouse(mouse) => menu.handle_mouse(*mouse),
+            Event::Paste(_) => MenuOutcome::Pending,
+            Event::Resize(..) | Event::FocusLost => {
+                *open = None;
+                return false;
+            }
+            _ => return false,
+        };
+        match outcome {
+            MenuOutcome::Pending => {}
+            MenuOutcome::Closed => *open = None,
+            MenuOutcome::Picked(id) => {
+                let position = menu.position;
+                *open = None;
+                drop(open);
+                app.emit_custom(MENU_PICKED, MenuPick { id, position });
+            }
+        }
+        true
+    }
+
+    /// Draw the open menu, if any.
+    pub(crate) fn render(&self, buf: &mut Buffer, bounds: Rect) {
+        if let Ok(mut open) = self.open.lock() {
+            if let Some(menu) = open.as_mut() {
+                menu.draw(bounds, buf);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn items() -> Vec<MenuItem> {
+        vec![
+            MenuItem::new("copy", "Copy").hint("Ctrl+C"),
+            MenuItem::new("paste", "Paste").enabled(false),
+            MenuItem::separator(),
+            MenuItem::submenu("Sort by", [MenuItem::new("sort-name", "Name"), MenuItem::new("sort-size", "Size")]),
+        ]
+    }
+
+    fn press(menu: &mut ContextMenu, code: KeyCode) -> MenuOutcome {
+        menu.handle_key(KeyEvent::from(code))
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE }
+    }
+
+    #[test]
+    fn test_keyboard_skips_disabled_and_opens_submenus() {
+        let mut menu = ContextMenu::new((5, 2), items());
+        assert_eq!(menu.selected().map(MenuItem::id), Some("copy"));
+        press(&mut menu, KeyCode::Down);
+        assert_eq!(menu.selected().map(MenuItem::label), Some("Sort by"));
+        press(&mut menu, KeyCode::Right);
+        assert_eq!(menu.depth(), 2);
+        press(&mut menu, KeyCode::Down);
+        press(&mut menu, KeyCode::Left);
+        assert_eq!(menu.depth(), 1);
+        assert_eq!(press(&mut menu, KeyCode::Enter), MenuOutcome::Pending);
+        press(&mut menu, KeyCode::Down);
+        assert_eq!(press(&mut menu, KeyCode::Enter), MenuOutcome::Picked("sort-size".into()));
+        press(&mut menu, KeyCode::Esc);
+        assert_eq!(press(&mut menu, KeyCode::Esc), MenuOutcome::Closed);
+    }
+
+    #[test]
+    fn test_mouse_hover_click_and_placement() {
+        let mut menu = ContextMenu::new((35, 18), items());
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 20));
+        menu.draw(buf.area, &mut buf);
+        // Flipped up and left to stay on screen
+        let root = menu.areas[0];
+        assert!(root.right() <= 40 && root.bottom() <= 20);
+        assert_eq!(buf[(root.x + 2, root.y + 1)].symbol(), "C");
+
+        menu.handle_mouse(mouse(MouseEventKind::Moved, root.x + 2, root.y + 4));
+        assert_eq!(menu.depth(), 2);
+        menu.draw(buf.area, &mut buf);
+        let submenu = menu.areas[1];
+        assert!(submenu.right() <= root.x, "submenu opens to the left near the edge");
+        let picked = menu.handle_mouse(mouse(MouseEventKind::Down(MouseButton::Left), submenu.x + 1, submenu.y + 1));
+        assert_eq!(picked, MenuOutcome::Picked("sort-name".into()));
+        assert_eq!(menu.handle_mouse(mouse(MouseEventKind::Down(MouseButton::Left), 0, 0)), MenuOutcome::Closed);
+    }
+
+    #[test]
+    fn test_picking_emits_menu_pick() {
+        let (app, _refresh, mut injected) = AppContext::new();
+        app.open_context_menu((3, 4), items());
+        assert!(app.context_menu_open());
+        assert!(app.context_menus.intercept(&Event::Key(KeyEvent::from(KeyCode::Enter)), &app));
+        assert!(!app.context_menu_open());
+        let event = injected.try_recv().unwrap();
+        assert_eq!(event.custom::<MenuPick>(), Some(&MenuPick { id: "copy".into(), position: Position::new(3, 4) }));
+        assert!(!app.context_menus.intercept(&Event::Key(KeyEvent::from(KeyCode::Enter)), &app));
+    }
+}
@@ -0,0 +1,310 @@
+//! Contrast audit for rendered frames.
+//!
+//! When enabled via `AppContext::contrast_audit`, every rendered frame is scanned for
+//! text whose foreground/background pair falls below a WCAG contrast ratio. Named and
+//! indexed colors are resolved through the terminal palette (xterm defaults unless
+//! configured), `Color::Reset` through the terminal's default colors, so the audit
+//! reflects what a 16-color terminal shows under the app's active theme. Offending
+//! cells are grouped by the named region they lie in (see `AppContext::region`), logged
+//! at warn level when the findings change and, optionally, listed in a banner at the
+//! bottom of the frame with the cells underlined.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+/// WCAG AA minimum for normal text.
+pub const DEFAULT_MIN_RATIO: f64 = 4.5;
+
+/// Number of issues named in the banner.
+const BANNER_ISSUES: usize = 3;
+
+/// The xterm default 16-color palette, in ANSI order.
+pub(crate) const XTERM_PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0), (205, 0, 0), (0, 205, 0), (205, 205, 0),
+    (0, 0, 238), (205, 0, 205), (0, 205, 205), (229, 229, 229),
+    (127, 127, 127), (255, 0, 0), (0, 255, 0), (255, 255, 0),
+    (92, 92, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255),
+];
+
+/// A low-contrast color pair found in a frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastIssue {
+    /// The innermost named region containing the cells, if any.
+    pub region: Option<String>,
+    /// Foreground as rendered (after `REVERSED`).
+    pub fg: Color,
+    /// Background as rendered (after `REVERSED`).
+    pub bg: Color,
+    /// Contrast ratio of the pair, from 1.0 to 21.0.
+    pub ratio: f64,
+    /// Number of offending cells.
+    pub cells: usize,
+    /// Bounding box of the offending cells.
+    pub area: Rect,
+}
+
+impl std::fmt::Display for ContrastIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let region = self.region.as_deref().unwrap_or("unnamed");
+        write!(
+            f,
+            "{region}: {:?} on {:?} is {:.1}:1 ({} cells at {},{} {}x{})",
+            self.fg, self.bg, self.ratio, self.cells, self.area.x, self.area.y, self.area.width, self.area.height
+        )
+    }
+}
+
+struct ContrastInner {
+    enabled: bool,
+    overlay: bool,
+    min_ratio: f64,
+    palette: [(u8, u8, u8); 16],
+    default_fg: (u8, u8, u8),
+    default_bg: (u8, u8, u8),
+    issues: Vec<ContrastIssue>,
+}
+
+/// Shared contrast auditor, owned by the `AppContext`.
+#[derive(Clone)]
+pub struct ContrastAudit {
+    inner: Arc<Mutex<ContrastInner>>,
+}
+
+impl Default for ContrastAudit {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ContrastInner {
+                enabled: false,
+                overlay: false,
+                min_ratio: DEFAULT_MIN_RATIO,
+                palette: XTERM_PALETTE,
+                default_fg: XTERM_PALETTE[7],
+                default_bg: XTERM_PALETTE[0],
+                issues: Vec::new(),
+            })),
+        }
+    }
+}
+
+impl ContrastAudit {
+    /// Scan every rendered frame. Off by default.
+    pub fn set_enabled(&self, enabled: bool) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.enabled = enabled;
+            inner.issues.clear();
+        }
+    }
+
+    /// Whether frames are being scanned.
+    pub fn is_enabled(&self) -> bool {
+        self.inner.lock().map(|inner| inner.enabled).unwrap_or(false)
+    }
+
+    /// Underline offending cells and list the worst issues in a banner.
+    pub fn set_overlay(&self, enabled: bool) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.overlay = enabled;
+        }
+    }
+
+    /// Set the minimum acceptable contrast ratio (default 4.5, WCAG AA).
+    pub fn set_min_ratio(&self, ratio: f64) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.min_ratio = ratio.clamp(1.0, 21.0);
+        }
+    }
+
+    /// Set the RGB values the terminal uses for the 16 named colors, in ANSI order
+    /// (`Black`, `Red`, ..., `Gray`, `DarkGray`, `LightRed`, ..., `White`).
+    pub fn set_palette(&self, palette: [(u8, u8, u8); 16]) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.palette = palette;
+        }
+    }
+
+    /// Set the terminal's default foreground and background, used for `Color::Reset`.
+    pub fn set_default_colors(&self, fg: (u8, u8, u8), bg: (u8, u8, u8)) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.default_fg = fg;
+            inner.default_bg = bg;
+        }
+    }
+
+    /// Issues found in the last scanned frame, worst first.
+    pub fn issues(&self) -> Vec<ContrastIssue> {
+        self.inner.lock().map(|inner| inner.issues.clone()).unwrap_or_default()
+    }
+
+    /// Scan the frame and draw the overlay if enabled.
+    pub(crate) fn audit_frame(&self, frame: &mut Frame, regions: &[(String, Rect)]) {
+        if !self.is_enabled() {
+            return;
+        }
+        let issues = self.scan(frame.buffer_mut(), regions);
+        let overlay = self.inner.lock().map(|inner| inner.overlay).unwrap_or(false);
+        if overlay && !issues.is_empty() {
+            render_overlay(frame, &issues);
+        }
+    }
+
+    fn scan(&self, buf: &Buffer, regions: &[(String, Rect)]) -> Vec<ContrastIssue> {
+        let Ok(mut inner) = self.inner.lock() else {
+            return Vec::new();
+        };
+        let mut found: BTreeMap<(Option<usize>, String, String), ContrastIssue> = BTreeMap::new();
+        for y in buf.area.top()..buf.area.bottom() {
+            for x in buf.area.left()..buf.area.right() {
+                let cell = &buf[(x, y)];
+                if cell.symbol().trim().is_empty() || cell.modifier.contains(Modifier::HIDDEN) {
+                    continue;
+                }
+                let (fg, bg) = if cell.modifier.contains(Modifier::REVERSED) { (cell.bg, cell.fg) } else { (cell.fg, cell.bg) };
+                let ratio = contrast_ratio(inner.resolve(fg, true), inner.resolve(bg, false));
+                if ratio >= inner.min_ratio {
+                    continue;
+                }
+                // The innermost region is the smallest one containing the cell
+                let region = regions
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, r))| r.contains(Position::new(x, y)))
+                    .min_by_key(|(_, (_, r))| r.area())
+                    .map(|(i, _)| i);
+                let cell_area = Rect::new(x, y, 1, 1);
+                found
+                    .entry((region, format!("{fg:?}"), format!("{bg:?}")))
+                    .and_modify(|issue| {
+                        issue.cells += 1;
+                        issue.area = issue.area.union(cell_area);
+                    })
+                    .or_insert_with(|| ContrastIssue {
+                        region: region.map(|i| regions[i].0.clone()),
+                        fg,
+                        bg,
+                        ratio,
+                        cells: 1,
+                        area: cell_area,
+                    });
+            }
+        }
+        let mut issues: Vec<ContrastIssue> = found.into_values().collect();
+        issues.sort_by(|a, b| a.ratio.total_cmp(&b.ratio).then(b.cells.cmp(&a.cells)));
+        if issues != inner.issues {
+            for issue in &issues {
+                log::warn!("low contrast: {issue}");
+            }
+        }
+        inner.issues = issues.clone();
+        issues
+    }
+}
+
+impl ContrastInner {
+    fn resolve(&self, color: Color, foreground: bool) -> (u8, u8, u8) {
+        let named = |i: usize| self.palette[i];
+        match color {
+            Color::Reset if foreground => self.default_fg,
+            Color::Reset => self.default_bg,
+            Color::Black => named(0),
+            Color::Red => named(1),
+            Color::Green => named(2),
+            Color::Yellow => named(3),
+            Color::Blue => named(4),
+            Color::Magenta => named(5),
+            Color::Cyan => named(6),
+            Color::Gray => named(7),
+            Color::DarkGray => named(8),
+            Color::LightRed => named(9),
+            Color::LightGreen => named(10),
+            Color::LightYellow => named(11),
+            Color::LightBlue => named(12),
+            Color::LightMagenta => named(13),
+            Color::LightCyan => named(14),
+            Color::White => named(15),
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Indexed(i) if i < 16 => named(i as usize),
+            Color::Indexed(i) => extended_rgb(i),
+        }
+    }
+}
+
+/// The xterm RGB value of a 256-color index past the first 16: the 6x6x6 color cube,
+/// then the grayscale ramp.
+pub(crate) fn extended_rgb(index: u8) -> (u8, u8, u8) {
+    if index < 232 {
+        let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+        let i = index.saturating_sub(16);
+        (level(i / 36), level(i / 6 % 6), level(i % 6))
+    } else {
+        let v = 8 + (index - 232) * 10;
+        (v, v, v)
+    }
+}
+
+/// WCAG contrast ratio between two sRGB colors.
+fn contrast_ratio(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    fn luminance((r, g, b): (u8, u8, u8)) -> f64 {
+        let channel = |c: u8| {
+            let c = f64::from(c) / 255.0;
+            if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+        };
+        0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+    }
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Underline offending areas and list the worst issues on the last row.
+fn render_overlay(frame: &mut Frame, issues: &[ContrastIssue]) {
+    let buf = frame.buffer_mut();
+    for issue in issues {
+        buf.set_style(issue.area, Style::default().add_modifier(Modifier::UNDERLINED).underline_color(Color::Red));
+    }
+    let area = frame.area();
+    let banner = Rect::new(area.x, area.bottom().saturating_sub(1), area.width, area.height.min(1));
+    let listed: Vec<String> = issues
+        .iter()
+        .take(BANNER_ISSUES)
+        .map(|i| format!("{} {:.1}:1", i.region.as_deref().unwrap_or("unnamed"), i.ratio))
+        .collect();
+    let more = issues.len().saturating_sub(BANNER_ISSUES);
+    let text = if more > 0 { format!(" ◐ low contrast: {} (+{more})", listed.join(", ")) } else { format!(" ◐ low contrast: {}", listed.join(", ")) };
+    let style = Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD);
+    frame.render_widget(Paragraph::new(text).style(style), banner);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contrast_ratio_bounds() {
+        assert!((contrast_ratio((0, 0, 0), (255, 255, 255)) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio((90, 90, 90), (90, 90, 90)) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_scan_groups_by_region() {
+        let audit = ContrastAudit::default();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 3));
+        buf.set_string(0, 0, "fine", Style::default().fg(Color::White).bg(Color::Black));
+        buf.set_string(0, 1, "dim", Style::default().fg(Color::Indexed(236)).bg(Color::Black));
+        buf.set_string(10, 1, "blue", Style::default().fg(Color::Blue));
+        buf.set_string(0, 2, "rev", Style::default().fg(Color::Yellow).bg(Color::White).add_modifier(Modifier::REVERSED));
+        let regions = vec![("page".to_string(), Rect::new(0, 0, 20, 3)), ("sidebar".to_string(), Rect::new(0, 0, 5, 3))];
+
+        let issues = audit.scan(&buf, &regions);
+        let summary: Vec<(Option<&str>, usize)> = issues.iter().map(|i| (i.region.as_deref(), i.cells)).collect();
+        assert_eq!(summary, vec![(Some("sidebar"), 3), (Some("sidebar"), 3), (Some("page"), 4)]);
+        assert_eq!((issues[1].fg, issues[1].bg), (Color::White, Color::Yellow));
+
+        audit.set_min_ratio(1.0);
+        assert!(audit.scan(&buf, &regions).is_empty());
+    }
+}
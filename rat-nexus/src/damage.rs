@@ -0,0 +1,153 @@
+//! Damage tracking for cached child renders.
+//!
+//! A `ChildView::cached` child, or any component whose `Component::memoize` returns
+//! true (the root included), keeps the cells it drew in its last frame along with
+//! its version vector, the versions of the entities it subscribed to while drawing.
+//! On the next frame the cells are copied back instead of calling `render` as long
+//! as nothing it depends on changed: no subscribed entity was updated, the area and
+//! inherited style are the same, no event reached the child and the parent did not
+//! borrow it mutably. A terminal resize, or `AppContext::invalidate_all`, drops every
+//! cache at once.
+//!
+//! A reused child adds its version vector to those of the cached views around it, so
+//! an outer cache is redrawn when only an entity read by a nested child changes.
+//!
+//! A cached child must therefore subscribe (or `watch`) everything it reads; state
+//! read without subscribing, the clock included, does not mark it dirty.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+
+use crate::state::{EntityId, VersionProbe};
+
+/// The generation of all cached renders, bumped to invalidate them together.
+#[derive(Clone, Default)]
+pub(crate) struct Damage {
+    generation: Arc<AtomicU64>,
+}
+
+impl Damage {
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Drop every cached render; the next frame draws everything.
+    pub(crate) fn invalidate_all(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// An entity version a render read.
+#[derive(Debug, Clone)]
+struct Dependency {
+    id: EntityId,
+    probe: VersionProbe,
+    version: u64,
+}
+
+/// Collects the entities subscribed to while a cached child renders.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DependencyTracker {
+    deps: Arc<Mutex<Vec<Dependency>>>,
+}
+
+impl DependencyTracker {
+    /// Record that the render depends on the entity at `version`.
+    pub(crate) fn record(&self, id: EntityId, probe: VersionProbe, version: u64) {
+        if let Ok(mut deps) = self.deps.lock() {
+            if !deps.iter().any(|dep| dep.id == id) {
+                deps.push(Dependency { id, probe, version });
+            }
+        }
+    }
+
+    fn take(&self) -> Vec<Dependency> {
+        self.deps.lock().map(|mut deps| std::mem::take(&mut *deps)).unwrap_or_default()
+    }
+}
+
+/// What a cached render was drawn for. It stays valid while none of it changes.
+#[derive(Debug)]
+pub(crate) struct RenderStamp {
+    area: Rect,
+    style: Style,
+    generation: u64,
+    deps: Vec<Dependency>,
+}
+
+impl RenderStamp {
+    /// Stamp a render into `area` that read the dependencies collected by `tracker`.
+    pub(crate) fn new(area: Rect, style: Style, generation: u64, tracker: &DependencyTracker) -> Self {
+        Self { area, style, generation, deps: tracker.take() }
+    }
+
+    /// Record the dependencies of this render into `trackers`, as if it had just run.
+    pub(crate) fn report(&self, trackers: &[DependencyTracker]) {
+        for tracker in trackers {
+            for dep in &self.deps {
+                tracker.record(dep.id, dep.probe.clone(), dep.version);
+            }
+        }
+    }
+
+    /// Whether rendering into `area` with `style` at `generation` would draw the same.
+    pub(crate) fn is_valid(&self, area: Rect, style: Style, generation: u64) -> bool {
+        self.area == area
+            && self.style == style
+            && self.generation == generation
+            && self.deps.iter().all(|dep| dep.probe.version() == Some(dep.version))
+    }
+}
+
+/// The last render of a cached child.
+#[derive(Debug, Default)]
+pub(crate) struct RenderCache {
+    snapshot: Option<(RenderStamp, Buffer)>,
+}
+
+impl RenderCache {
+    /// Forget the last render.
+    pub(crate) fn clear(&mut self) {
+        self.snapshot = None;
+    }
+
+    /// Copy the last render into `buf` if it is still valid for `area`, `style` and
+    /// `generation`, reporting its dependencies to the enclosing `trackers`; returns
+    /// false if the child must render.
+    pub(crate) fn reuse(
+        &self,
+        buf: &mut Buffer,
+        area: Rect,
+        style: Style,
+        generation: u64,
+        trackers: &[DependencyTracker],
+    ) -> bool {
+        let Some((stamp, cells)) = &self.snapshot else { return false };
+        let valid = stamp.is_valid(area, style, generation);
+        if valid {
+            stamp.report(trackers);
+            for position in area.positions() {
+                if let (Some(cell), Some(target)) = (cells.cell(position), buf.cell_mut(position)) {
+                    *target = cell.clone();
+                }
+            }
+        }
+        valid
+    }
+
+    /// Keep what was drawn into `area` of `buf`, valid until a dependency collected by
+    /// `tracker` changes.
+    pub(crate) fn store(&mut self, buf: &Buffer, area: Rect, style: Style, generation: u64, tracker: &DependencyTracker) {
+        let mut cells = Buffer::empty(area.intersection(buf.area));
+        for position in cells.area.positions() {
+            if let (Some(cell), Some(target)) = (buf.cell(position), cells.cell_mut(position)) {
+                *target = cell.clone();
+            }
+        }
+        self.snapshot = Some((RenderStamp::new(area, style, generation, tracker), cells));
+    }
+}
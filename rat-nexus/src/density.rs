@@ -0,0 +1,121 @@
+//! Application-wide UI density.
+//!
+//! `Density` scales padding, spacing and borders. It is stored on the `AppContext`,
+//! honored by the built-in widgets and available to components in `render`. Changing it
+//! at runtime re-renders and emits a `DENSITY_CHANGED` custom event.
+
+use ratatui::widgets::{Block, BorderType, Borders, Padding};
+
+/// Name of the custom event emitted by `AppContext::set_density`, carrying the new `Density`.
+pub const DENSITY_CHANGED: &str = "rat_nexus::density_changed";
+
+/// How tightly the UI is laid out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Density {
+    /// No padding, plain borders; for small terminals.
+    Compact,
+    /// The default layout.
+    #[default]
+    Normal,
+    /// Extra padding and spacing; for presentations and large screens.
+    Spacious,
+}
+
+impl Density {
+    /// All densities, from tightest to loosest.
+    pub const ALL: [Density; 3] = [Density::Compact, Density::Normal, Density::Spacious];
+
+    /// The next density, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            Density::Compact => Density::Normal,
+            Density::Normal => Density::Spacious,
+            Density::Spacious => Density::Compact,
+        }
+    }
+
+    /// Display name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Density::Compact => "Compact",
+            Density::Normal => "Normal",
+            Density::Spacious => "Spacious",
+        }
+    }
+
+    /// Padding inside bordered containers.
+    pub fn padding(self) -> Padding {
+        match self {
+            Density::Compact => Padding::ZERO,
+            Density::Normal => Padding::horizontal(1),
+            Density::Spacious => Padding::symmetric(2, 1),
+        }
+    }
+
+    /// Gap between adjacent layout areas, in cells.
+    pub fn spacing(self) -> u16 {
+        match self {
+            Density::Compact => 0,
+            Density::Normal => 1,
+            Density::Spacious => 2,
+        }
+    }
+
+    /// Horizontal padding on each side of inline items such as status line segments.
+    pub fn inline_padding(self) -> u16 {
+        match self {
+            Density::Compact => 0,
+            Density::Normal => 1,
+            Density::Spacious => 2,
+        }
+    }
+
+    /// Border type for containers.
+    pub fn border_type(self) -> BorderType {
+        match self {
+            Density::Compact => BorderType::Plain,
+            Density::Normal | Density::Spacious => BorderType::Rounded,
+        }
+    }
+
+    /// A bordered block with this density's padding and border type.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let block = cx.density().block().title(" Log ");
+    /// ```
+    pub fn block<'a>(self) -> Block<'a> {
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(self.border_type())
+            .padding(self.padding())
+    }
+
+    pub(crate) fn to_u8(self) -> u8 {
+        self as u8
+    }
+
+    pub(crate) fn from_u8(value: u8) -> Self {
+        Self::ALL.get(usize::from(value)).copied().unwrap_or_default()
+    }
+}
+
+impl std::fmt::Display for Density {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_density_cycle_and_roundtrip() {
+        for density in Density::ALL {
+            assert_eq!(Density::from_u8(density.to_u8()), density);
+        }
+        assert_eq!(Density::Spacious.next(), Density::Compact);
+        assert!(Density::Compact.spacing() < Density::Spacious.spacing());
+    }
+}
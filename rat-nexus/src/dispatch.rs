@@ -0,0 +1,159 @@
+//! Typed messages reduced into entities, Elm/Redux style.
+//!
+//! Instead of updating shared state in place, a component sends a message with
+//! `cx.dispatch(msg)` and reducers registered once with `AppContext::reducer` apply it to
+//! the entities they own. State changes live in one place and components only describe
+//! what happened. Messages travel through the event loop as the custom event `MESSAGE`,
+//! so they are reduced in the order sent, outside any component's lock, and each message
+//! costs at most one frame.
+//!
+//! Components keep rendering from entities as usual (`cx.subscribe`, `cx.watch`), so
+//! this can be adopted one screen at a time.
+//!
+//! # Example
+//! ```ignore
+//! enum TodoMsg { Add(String), Toggle(usize) }
+//!
+//! let todos = app.new_entity(Vec::<Todo>::new());
+//! app.reducer(&todos, |todos, msg: &TodoMsg| match msg {
+//!     TodoMsg::Add(title) => todos.push(Todo::new(title)),
+//!     TodoMsg::Toggle(i) => todos[*i].done ^= true,
+//! });
+//!
+//! // In a component:
+//! KeyCode::Enter => cx.dispatch(TodoMsg::Add(self.input.take())),
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::state::Entity;
+
+/// Name of the custom event carrying a dispatched message.
+pub const MESSAGE: &str = "rat_nexus::message";
+
+/// A dispatched message, type-erased for the trip through the event loop.
+pub(crate) struct Message {
+    payload: Box<dyn Any + Send + Sync>,
+    type_name: &'static str,
+}
+
+impl Message {
+    pub(crate) fn new<M: Any + Send + Sync>(message: M) -> Self {
+        Self { payload: Box::new(message), type_name: std::any::type_name::<M>() }
+    }
+}
+
+type Reducer = Arc<dyn Fn(&dyn Any) + Send + Sync>;
+
+/// Reducers by message type, in registration order.
+#[derive(Clone, Default)]
+pub(crate) struct Reducers {
+    inner: Arc<RwLock<HashMap<TypeId, Vec<Reducer>>>>,
+}
+
+impl Reducers {
+    /// Apply every `M` message to `entity` with `reduce`.
+    pub(crate) fn register<M, S, F>(&self, entity: &Entity<S>, reduce: F)
+    where
+        M: Any + Send + Sync,
+        S: Send + Sync + 'static,
+        F: Fn(&mut S, &M) + Send + Sync + 'static,
+    {
+        let entity = entity.clone();
+        let reducer: Reducer = Arc::new(move |message: &dyn Any| {
+            if let Some(message) = message.downcast_ref::<M>() {
+                if entity.update(|state| reduce(state, message)).is_err() {
+                    log::error!("reducer for {} skipped: entity lock poisoned", std::any::type_name::<M>());
+                }
+            }
+        });
+        if let Ok(mut reducers) = self.inner.write() {
+            reducers.entry(TypeId::of::<M>()).or_default().push(reducer);
+        }
+    }
+
+    /// Run the reducers registered for `message`'s type. Returns how many ran.
+    pub(crate) fn reduce(&self, message: &Message) -> usize {
+        let payload = &*message.payload;
+        // Clone the list out so reducers may register more reducers
+        let reducers = self.inner.read().ok().and_then(|reducers| reducers.get(&payload.type_id()).cloned());
+        let reducers = reducers.unwrap_or_default();
+        for reducer in &reducers {
+            reducer(payload);
+        }
+        if reducers.is_empty() {
+            log::warn!("no reducer for message {}", message.type_name);
+        }
+        reducers.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    enum CounterMsg {
+        Add(i32),
+        Reset,
+    }
+
+    #[test]
+    fn test_reducers_apply_messages_in_registration_order() {
+        let reducers = Reducers::default();
+        let count = Entity::new(0);
+        let log = Entity::new(Vec::new());
+        reducers.register(&count, |count, msg: &CounterMsg| match msg {
+            CounterMsg::Add(n) => *count += n,
+            CounterMsg::Reset => *count = 0,
+        });
+        reducers.register(&log, |log: &mut Vec<String>, msg: &CounterMsg| log.push(format!("{msg:?}")));
+
+        assert_eq!(reducers.reduce(&Message::new(CounterMsg::Add(3))), 2);
+        assert_eq!(reducers.reduce(&Message::new(CounterMsg::Add(4))), 2);
+        assert_eq!(count.read(|c| *c).unwrap(), 7);
+        reducers.reduce(&Message::new(CounterMsg::Reset));
+        assert_eq!(count.read(|c| *c).unwrap(), 0);
+        assert_eq!(log.read(|l| l.len()).unwrap(), 3);
+        assert_eq!(reducers.reduce(&Message::new("unhandled")), 0);
+    }
+
+    struct CounterView {
+        count: Entity<i32>,
+    }
+
+    impl crate::Component for CounterView {
+        fn render(&mut self, frame: &mut ratatui::Frame, _cx: &mut crate::Context<Self>) {
+            let count = self.count.read(|c| *c).unwrap_or_default();
+            frame.buffer_mut().set_string(0, 0, format!("Count: {count}"), ratatui::style::Style::default());
+        }
+
+        fn handle_event(&mut self, event: crate::Event, cx: &mut crate::EventContext<Self>) -> Option<crate::Action> {
+            if let crate::Event::Key(key) = event {
+                match key.code {
+                    crossterm::event::KeyCode::Char('+') => cx.dispatch(CounterMsg::Add(1)),
+                    crossterm::event::KeyCode::Char('0') => cx.dispatch(CounterMsg::Reset),
+                    _ => {}
+                }
+            }
+            None
+        }
+    }
+
+    #[test]
+    fn test_components_dispatch_to_reducers() {
+        let count = Entity::new(0);
+        let mut harness = crate::testing::Harness::with_size(CounterView { count: count.clone() }, 20, 2);
+        harness.app().reducer(&count, |count, msg: &CounterMsg| match msg {
+            CounterMsg::Add(n) => *count += n,
+            CounterMsg::Reset => *count = 0,
+        });
+
+        harness.type_str("+++");
+        harness.expect_text("Count: 3");
+        harness.type_str("0+");
+        harness.expect_text("Count: 1");
+    }
+}
@@ -0,0 +1,149 @@
+//! Drag-and-drop between components.
+//!
+//! A component starts a drag with `cx.start_drag(payload)`, usually on
+//! `MouseEventKind::Drag(MouseButton::Left)` over the item being moved. Until the button
+//! is released the event loop follows the pointer with a ghost label and keeps the mouse
+//! events from the tree; on release the root receives `Event::Drop` at the pointer.
+//! `ChildView` routes it by area like a click, so it reaches the component under the
+//! cursor. `Esc` cancels the drag.
+
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+
+use crossterm::event::{KeyCode, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::component::traits::{DropEvent, Event};
+
+/// Ghost label for drags started without one.
+const DEFAULT_LABEL: &str = "⠿";
+
+struct ActiveDrag {
+    payload: Arc<dyn Any + Send + Sync>,
+    type_name: &'static str,
+    label: String,
+    position: Option<Position>,
+}
+
+/// The drag in progress, owned by the `AppContext`.
+#[derive(Clone, Default)]
+pub(crate) struct Drags {
+    active: Arc<Mutex<Option<ActiveDrag>>>,
+}
+
+impl Drags {
+    pub(crate) fn start<T: Any + Send + Sync>(&self, payload: T, label: Option<String>) {
+        if let Ok(mut active) = self.active.lock() {
+            *active = Some(ActiveDrag {
+                payload: Arc::new(payload),
+                type_name: std::any::type_name::<T>(),
+                label: label.unwrap_or_else(|| DEFAULT_LABEL.to_string()),
+                position: None,
+            });
+        }
+    }
+
+    pub(crate) fn cancel(&self) {
+        if let Ok(mut active) = self.active.lock() {
+            *active = None;
+        }
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.active.lock().map(|active| active.is_some()).unwrap_or(false)
+    }
+
+    pub(crate) fn position(&self) -> Option<Position> {
+        self.active.lock().ok()?.as_ref()?.position
+    }
+
+    /// Follow an event while a drag is in progress. Returns `None` if it was consumed,
+    /// the `Event::Drop` ending the drag on release, or the event unchanged.
+    pub(crate) fn intercept(&self, event: Event) -> Option<Event> {
+        let Ok(mut active) = self.active.lock() else {
+            return Some(event);
+        };
+        let Some(drag) = active.as_mut() else {
+            return Some(event);
+        };
+        match &event {
+            Event::Mouse(mouse) => {
+                let position = Position::new(mouse.column, mouse.row);
+                match mouse.kind {
+                    MouseEventKind::Drag(_) | MouseEventKind::Moved => {
+                        drag.position = Some(position);
+                        None
+                    }
+                    MouseEventKind::Up(_) => {
+                        let drag = active.take().expect("drag is active");
+                        Some(Event::Drop(DropEvent::new(drag.payload, drag.type_name, position)))
+                    }
+                    _ => None,
+                }
+            }
+            Event::Key(key) if key.code == KeyCode::Esc => {
+                *active = None;
+                None
+            }
+            Event::Key(_) | Event::Paste(_) => None,
+            _ => Some(event),
+        }
+    }
+
+    /// Draw the ghost next to the pointer, if a drag is in progress.
+    pub(crate) fn render(&self, buf: &mut Buffer, bounds: Rect) {
+        let Ok(active) = self.active.lock() else { return };
+        let Some((drag, position)) = active.as_ref().and_then(|drag| Some((drag, drag.position?))) else {
+            return;
+        };
+        let label = format!(" {} ", drag.label);
+        let width = (label.chars().count() as u16).min(bounds.width);
+        let x = (position.x + 1).min(bounds.right().saturating_sub(width));
+        if !bounds.contains(Position::new(x, position.y)) {
+            return;
+        }
+        let style = Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD);
+        buf.set_stringn(x, position.y, &label, width as usize, style);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEvent, KeyModifiers, MouseButton, MouseEvent};
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE })
+    }
+
+    #[test]
+    fn test_release_turns_into_drop() {
+        let drags = Drags::default();
+        assert!(drags.intercept(mouse(MouseEventKind::Moved, 1, 1)).is_some());
+
+        drags.start(42u32, Some("Card".into()));
+        assert!(drags.intercept(mouse(MouseEventKind::Drag(MouseButton::Left), 7, 3)).is_none());
+        assert_eq!(drags.position(), Some(Position::new(7, 3)));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 5));
+        let area = buf.area;
+        drags.render(&mut buf, area);
+        assert_eq!(buf[(9, 3)].symbol(), "C");
+
+        let Some(Event::Drop(dropped)) = drags.intercept(mouse(MouseEventKind::Up(MouseButton::Left), 8, 4)) else {
+            panic!("release should drop");
+        };
+        assert_eq!((dropped.downcast_ref::<u32>(), dropped.position()), (Some(&42), Position::new(8, 4)));
+        assert!(!drags.is_active());
+    }
+
+    #[test]
+    fn test_escape_cancels() {
+        let drags = Drags::default();
+        drags.start("row 3", None);
+        assert!(drags.intercept(Event::Key(KeyEvent::from(KeyCode::Esc))).is_none());
+        assert!(!drags.is_active());
+        assert!(drags.intercept(mouse(MouseEventKind::Up(MouseButton::Left), 0, 0)).is_some_and(|e| matches!(e, Event::Mouse(_))));
+    }
+}
@@ -14,6 +14,49 @@ pub enum Error {
 
     #[snafu(display("Task execution error"))]
     TaskError,
+
+    #[snafu(display("Entity {entity_id} was modified while an async update was pending"))]
+    UpdateConflict { entity_id: u64 },
+
+    #[snafu(display("Re-entrant lock on entity {entity_id} ({type_name}): already held by this thread"))]
+    ReentrantLock { entity_id: u64, type_name: &'static str },
+
+    #[snafu(display("Cannot construct {target}: missing registrations for {missing}"))]
+    MissingDependencies { target: &'static str, missing: String },
+
+    #[snafu(display("Invalid key sequence {keys:?}: unknown key {key:?}"))]
+    InvalidKeySequence { keys: String, key: String },
+
+    #[snafu(display("Unknown route {route:?} (available: {})", available.join(", ")))]
+    RouteNotFound { route: String, available: Vec<String> },
+
+    #[snafu(display("No {type_name} in the application state"))]
+    StateMissing { type_name: &'static str },
+
+    #[snafu(display("The stored value is not a {expected}"))]
+    DowncastFailed { expected: &'static str },
+
+    #[snafu(display("Entity {entity_id} was dropped"))]
+    EntityDropped { entity_id: u64 },
+
+    #[snafu(display("emit_app_event called with {found} but the application declares {expected}"))]
+    AppEventMismatch { expected: &'static str, found: &'static str },
+
+    #[snafu(display("{type_name} has no component to call back into: its context was cast from a different component's"))]
+    UnboundContext { type_name: &'static str },
+
+    #[snafu(display("{operation} must be called within a tokio runtime"))]
+    NoRuntime { operation: &'static str },
+
+    #[snafu(display("{source}"))]
+    Other { source: Box<dyn std::error::Error + Send + Sync> },
+}
+
+impl Error {
+    /// Wrap any error, or a message, e.g. `.map_err(Error::other)?` in a `TryComponent`.
+    pub fn other(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Error::Other { source: error.into() }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,133 @@
+//! Pluggable input for the event loop.
+//!
+//! The main loop reads terminal events from an `EventSource` on a dedicated blocking
+//! thread. `CrosstermEvents` (the default) reads the local terminal; `ChannelEvents`
+//! accepts events pushed from anywhere else — a web bridge, an SSH session, a gamepad
+//! backend or a test script — so every frontend goes through the same key
+//! normalization, metrics and dispatch.
+//!
+//! # Example
+//! ```ignore
+//! let (tx, source) = ChannelEvents::channel();
+//! std::thread::spawn(move || {
+//!     for key in gamepad.buttons() {
+//!         let _ = tx.send(Event::Key(map_button(key)));
+//!     }
+//! });
+//! Application::new().event_source(source).run(|cx| cx.set_root(Root::new(cx)))?;
+//! ```
+
+use crossterm::event::{self, Event};
+use std::io;
+use std::sync::mpsc::{self as std_mpsc, RecvTimeoutError};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// How long the reader thread waits for input before checking whether the loop is
+/// still running (~60fps).
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Result of waiting for input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Polled {
+    /// An event arrived.
+    Event(Event),
+    /// Nothing arrived within the timeout.
+    Timeout,
+    /// The source has no more events; the application exits.
+    Closed,
+}
+
+/// A source of terminal input events, read from a dedicated blocking thread.
+pub trait EventSource: Send + 'static {
+    /// Wait up to `timeout` for the next event.
+    ///
+    /// An error closes the source, like `Polled::Closed`.
+    fn poll_event(&mut self, timeout: Duration) -> io::Result<Polled>;
+}
+
+/// Events from the local terminal via crossterm.
+#[derive(Debug, Default)]
+pub struct CrosstermEvents;
+
+impl EventSource for CrosstermEvents {
+    fn poll_event(&mut self, timeout: Duration) -> io::Result<Polled> {
+        if event::poll(timeout)? {
+            event::read().map(Polled::Event)
+        } else {
+            Ok(Polled::Timeout)
+        }
+    }
+}
+
+/// Events pushed through a channel from another thread.
+///
+/// The source closes once every sender is dropped.
+#[derive(Debug)]
+pub struct ChannelEvents {
+    rx: std_mpsc::Receiver<Event>,
+}
+
+impl ChannelEvents {
+    /// Create a source and the sender that feeds it.
+    pub fn channel() -> (std_mpsc::Sender<Event>, Self) {
+        let (tx, rx) = std_mpsc::channel();
+        (tx, Self { rx })
+    }
+
+    /// Wrap an existing receiver.
+    pub fn new(rx: std_mpsc::Receiver<Event>) -> Self {
+        Self { rx }
+    }
+}
+
+impl EventSource for ChannelEvents {
+    fn poll_event(&mut self, timeout: Duration) -> io::Result<Polled> {
+        Ok(match self.rx.recv_timeout(timeout) {
+            Ok(event) => Polled::Event(event),
+            Err(RecvTimeoutError::Timeout) => Polled::Timeout,
+            Err(RecvTimeoutError::Disconnected) => Polled::Closed,
+        })
+    }
+}
+
+/// Pump `source` into `tx` until the source closes or the receiving loop is gone.
+/// Blocks; run it on a blocking thread.
+pub(crate) fn forward(mut source: Box<dyn EventSource>, tx: mpsc::UnboundedSender<Event>) {
+    while !tx.is_closed() {
+        match source.poll_event(POLL_INTERVAL) {
+            Ok(Polled::Event(event)) => {
+                if tx.send(event).is_err() {
+                    break;
+                }
+            }
+            Ok(Polled::Timeout) => {}
+            Ok(Polled::Closed) | Err(_) => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    #[test]
+    fn test_channel_source_forwards_until_closed() {
+        let (tx, mut source) = ChannelEvents::channel();
+        assert_eq!(source.poll_event(Duration::from_millis(1)).unwrap(), Polled::Timeout);
+
+        let key = Event::Key(KeyEvent::from(KeyCode::Char('x')));
+        tx.send(key.clone()).unwrap();
+        tx.send(Event::Resize(80, 24)).unwrap();
+        drop(tx);
+
+        let (loop_tx, mut loop_rx) = mpsc::unbounded_channel();
+        forward(Box::new(source), loop_tx);
+        assert_eq!(loop_rx.try_recv().unwrap(), key);
+        assert_eq!(loop_rx.try_recv().unwrap(), Event::Resize(80, 24));
+        // The forwarder returned and dropped its sender, which ends the loop
+        assert!(loop_rx.try_recv().is_err());
+        assert!(loop_rx.is_closed());
+    }
+}
@@ -0,0 +1,564 @@
+//! Forms: labeled fields bound to an `Entity` model, with validation and focus traversal.
+//!
+//! A `Form<T>` owns a list of `Field<T>`s. Each field reads its initial value from the
+//! model and writes back on every change that passes its validators, so other
+//! components subscribed to the model see edits live. Tab/Shift+Tab (or ↑/↓) move the
+//! focus, Enter validates every field and calls `on_submit`. Errors are shown next to
+//! their field once it has been changed or a submit was attempted.
+
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::component::traits::{Action, Component, Event};
+use crate::widgets::TextInput;
+use crate::{Context, Entity, EventContext};
+
+type Getter<T> = Box<dyn Fn(&T) -> String + Send + Sync>;
+type Setter<T> = Box<dyn Fn(&mut T, &str) -> Result<(), String> + Send + Sync>;
+type Validator = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+type SubmitFn<T> = Box<dyn FnMut(&T) -> Option<Action> + Send + Sync>;
+
+/// Common field validators.
+pub mod validators {
+    /// The value must not be blank.
+    pub fn required() -> impl Fn(&str) -> Result<(), String> + Send + Sync + 'static {
+        |value| if value.trim().is_empty() { Err("required".to_string()) } else { Ok(()) }
+    }
+
+    /// The value must have at least `min` characters.
+    pub fn min_len(min: usize) -> impl Fn(&str) -> Result<(), String> + Send + Sync + 'static {
+        move |value| {
+            if value.chars().count() < min {
+                Err(format!("at least {min} characters"))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// The value must have at most `max` characters.
+    pub fn max_len(max: usize) -> impl Fn(&str) -> Result<(), String> + Send + Sync + 'static {
+        move |value| {
+            if value.chars().count() > max {
+                Err(format!("at most {max} characters"))
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+enum Widget {
+    Text(TextInput),
+    Select { options: Vec<String>, index: usize },
+    Checkbox(bool),
+}
+
+impl Widget {
+    fn value(&self) -> String {
+        match self {
+            Widget::Text(input) => input.value().to_string(),
+            Widget::Select { options, index } => options.get(*index).cloned().unwrap_or_default(),
+            Widget::Checkbox(checked) => checked.to_string(),
+        }
+    }
+
+    fn load(&mut self, value: &str) {
+        match self {
+            Widget::Text(input) => input.set_value(value),
+            Widget::Select { options, index } => *index = options.iter().position(|o| o == value).unwrap_or(0),
+            Widget::Checkbox(checked) => *checked = value == "true",
+        }
+    }
+}
+
+/// A labeled form field bound to part of the model `T`.
+pub struct Field<T> {
+    label: String,
+    widget: Widget,
+    get: Getter<T>,
+    set: Setter<T>,
+    validators: Vec<Validator>,
+}
+
+impl<T> Field<T> {
+    /// A text field. `set` may reject the value (e.g. a failed parse) with an error message.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Field::text("Port", |s: &Settings| s.port.to_string(), |s, v| {
+    ///     s.port = v.parse().map_err(|_| "not a port number".to_string())?;
+    ///     Ok(())
+    /// })
+    /// ```
+    pub fn text<G, S>(label: impl Into<String>, get: G, set: S) -> Self
+    where
+        G: Fn(&T) -> String + Send + Sync + 'static,
+        S: Fn(&mut T, &str) -> Result<(), String> + Send + Sync + 'static,
+    {
+        let mut input = TextInput::new();
+        input.set_focused(false);
+        Self::with_widget(label, Widget::Text(input), Box::new(get), Box::new(set))
+    }
+
+    /// A choice between fixed options, cycled with `←/→` or Space.
+    pub fn select<G, S>(label: impl Into<String>, options: Vec<String>, get: G, set: S) -> Self
+    where
+        G: Fn(&T) -> String + Send + Sync + 'static,
+        S: Fn(&mut T, &str) + Send + Sync + 'static,
+    {
+        let set = move |model: &mut T, value: &str| {
+            set(model, value);
+            Ok(())
+        };
+        Self::with_widget(label, Widget::Select { options, index: 0 }, Box::new(get), Box::new(set))
+    }
+
+    /// A checkbox, toggled with Space or a click. Validators see `"true"` or `"false"`.
+    pub fn checkbox<G, S>(label: impl Into<String>, get: G, set: S) -> Self
+    where
+        G: Fn(&T) -> bool + Send + Sync + 'static,
+        S: Fn(&mut T, bool) + Send + Sync + 'static,
+    {
+        let get = move |model: &T| get(model).to_string();
+        let set = move |model: &mut T, value: &str| {
+            set(model, value == "true");
+            Ok(())
+        };
+        Self::with_widget(label, Widget::Checkbox(false), Box::new(get), Box::new(set))
+    }
+
+    fn with_widget(label: impl Into<String>, widget: Widget, get: Getter<T>, set: Setter<T>) -> Self {
+        Self { label: label.into(), widget, get, set, validators: Vec::new() }
+    }
+
+    /// Add a validator, run on every change and on submit.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Field::text("Name", |u: &User| u.name.clone(), |u, v| { u.name = v.into(); Ok(()) })
+    ///     .validate(validators::required())
+    ///     .validate(validators::max_len(40))
+    /// ```
+    pub fn validate<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// Placeholder shown in an empty text field.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        if let Widget::Text(input) = self.widget {
+            self.widget = Widget::Text(input.placeholder(placeholder));
+        }
+        self
+    }
+
+    /// The field label.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn check(&self, value: &str) -> Result<(), String> {
+        self.validators.iter().try_for_each(|validate| validate(value))
+    }
+}
+
+/// A form over the model in `Entity<T>`.
+///
+/// # Example
+/// ```ignore
+/// let form = Form::new(settings.clone())
+///     .field(Field::text("Name", |s: &Settings| s.name.clone(), |s, v| { s.name = v.into(); Ok(()) })
+///         .validate(validators::required()))
+///     .field(Field::select("Theme", themes, |s: &Settings| s.theme.clone(), |s, v| s.theme = v.into()))
+///     .field(Field::checkbox("Autosave", |s: &Settings| s.autosave, |s, v| s.autosave = v))
+///     .on_submit(|_| Some(Action::Back));
+///
+/// // as a child component
+/// self.form = ChildView::new(form);
+/// cx.mount_child(&mut self.form, frame, area);
+/// ```
+pub struct Form<T: Send + Sync + 'static> {
+    model: Entity<T>,
+    fields: Vec<Field<T>>,
+    focus: usize,
+    errors: Vec<Option<String>>,
+    touched: Vec<bool>,
+    label_width: Option<u16>,
+    field_width: u16,
+    on_submit: Option<SubmitFn<T>>,
+    rows: Vec<Rect>,
+}
+
+impl<T: Send + Sync + 'static> Form<T> {
+    /// Create an empty form bound to `model`.
+    pub fn new(model: Entity<T>) -> Self {
+        Self {
+            model,
+            fields: Vec::new(),
+            focus: 0,
+            errors: Vec::new(),
+            touched: Vec::new(),
+            label_width: None,
+            field_width: 30,
+            on_submit: None,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Append a field, loading its value from the model. Fields are focused in the order added.
+    pub fn field(mut self, mut field: Field<T>) -> Self {
+        if let Ok(value) = self.model.read(|m| (field.get)(m)) {
+            field.widget.load(&value);
+        }
+        self.fields.push(field);
+        self.errors.push(None);
+        self.touched.push(false);
+        self.sync_focus();
+        self
+    }
+
+    /// Fix the label column width instead of fitting the longest label.
+    pub fn label_width(mut self, width: u16) -> Self {
+        self.label_width = Some(width);
+        self
+    }
+
+    /// Width of the input column (default 30).
+    pub fn field_width(mut self, width: u16) -> Self {
+        self.field_width = width;
+        self
+    }
+
+    /// Call `f(model)` when the form is submitted with every field valid.
+    pub fn on_submit<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&T) -> Option<Action> + Send + Sync + 'static,
+    {
+        self.on_submit = Some(Box::new(f));
+        self
+    }
+
+    /// The bound model.
+    pub fn model(&self) -> &Entity<T> {
+        &self.model
+    }
+
+    /// Index of the focused field.
+    pub fn focused(&self) -> usize {
+        self.focus
+    }
+
+    /// Focus field `index` (clamped).
+    pub fn focus(&mut self, index: usize) {
+        self.focus = index.min(self.fields.len().saturating_sub(1));
+        self.sync_focus();
+    }
+
+    /// The current error of field `index`, if it has been shown.
+    pub fn error(&self, index: usize) -> Option<&str> {
+        self.errors.get(index)?.as_deref().filter(|_| self.touched[index])
+    }
+
+    /// Whether every field passes its validators.
+    pub fn is_valid(&self) -> bool {
+        self.fields.iter().all(|f| f.check(&f.widget.value()).is_ok())
+    }
+
+    /// Re-read every field from the model, e.g. after it was changed elsewhere.
+    pub fn reload(&mut self) {
+        for field in &mut self.fields {
+            if let Ok(value) = self.model.read(|m| (field.get)(m)) {
+                field.widget.load(&value);
+            }
+        }
+        self.errors.iter_mut().for_each(|e| *e = None);
+        self.touched.iter_mut().for_each(|t| *t = false);
+    }
+
+    /// Validate every field; if all pass, run `on_submit`. Otherwise focus the first
+    /// invalid field.
+    pub fn submit(&mut self) -> Option<Action> {
+        for index in 0..self.fields.len() {
+            self.touched[index] = true;
+            self.apply(index);
+        }
+        if let Some(first) = self.errors.iter().position(Option::is_some) {
+            self.focus(first);
+            return None;
+        }
+        let on_submit = self.on_submit.as_mut()?;
+        self.model.read(|m| on_submit(m)).ok().flatten()
+    }
+
+    /// Handle a key. Returns true if the form consumed it; `Enter` is left to `submit`.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Tab | KeyCode::Down => self.move_focus(1),
+            KeyCode::BackTab | KeyCode::Up => self.move_focus(-1),
+            _ => {
+                let Some(field) = self.fields.get_mut(self.focus) else {
+                    return false;
+                };
+                let changed = match &mut field.widget {
+                    Widget::Text(input) => {
+                        let before = input.value().to_string();
+                        if !input.handle_key(key) {
+                            return false;
+                        }
+                        input.value() != before
+                    }
+                    Widget::Select { options, index } => {
+                        let len = options.len().max(1);
+                        match key.code {
+                            KeyCode::Left => *index = (*index + len - 1) % len,
+                            KeyCode::Right | KeyCode::Char(' ') => *index = (*index + 1) % len,
+                            _ => return false,
+                        }
+                        true
+                    }
+                    Widget::Checkbox(checked) => match key.code {
+                        KeyCode::Char(' ') => {
+                            *checked = !*checked;
+                            true
+                        }
+                        _ => return false,
+                    },
+                };
+                if changed {
+                    self.touched[self.focus] = true;
+                    self.apply(self.focus);
+                }
+            }
+        }
+        true
+    }
+
+    /// Insert pasted text into the focused text field.
+    pub fn handle_paste(&mut self, text: &str) {
+        if let Some(Field { widget: Widget::Text(input), .. }) = self.fields.get_mut(self.focus) {
+            input.insert_str(text);
+            self.touched[self.focus] = true;
+            self.apply(self.focus);
+        }
+    }
+
+    /// Focus the clicked field; clicks toggle checkboxes and cycle selects.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> bool {
+        let position = Position::new(mouse.column, mouse.row);
+        let Some(index) = self.rows.iter().position(|r| r.contains(position)) else {
+            return false;
+        };
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return true;
+        }
+        self.focus(index);
+        let field = &mut self.fields[index];
+        match &mut field.widget {
+            Widget::Text(input) => {
+                input.handle_mouse(mouse);
+                return true;
+            }
+            Widget::Select { options, index } => *index = (*index + 1) % options.len().max(1),
+            Widget::Checkbox(checked) => *checked = !*checked,
+        }
+        self.touched[index] = true;
+        self.apply(index);
+        true
+    }
+
+    /// Draw the fields, one per row: label, input, then the error if any.
+    pub fn draw(&mut self, area: Rect, buf: &mut Buffer) {
+        let label_width = self
+            .label_width
+            .unwrap_or_else(|| self.fields.iter().map(|f| f.label.chars().count() as u16).max().unwrap_or(0) + 1);
+        self.rows.clear();
+        for (index, field) in self.fields.iter_mut().enumerate() {
+            let y = area.y + index as u16;
+            if y >= area.bottom() {
+                break;
+            }
+            let row = Rect::new(area.x, y, area.width, 1);
+            self.rows.push(row);
+            let focused = index == self.focus;
+
+            let label_style = if focused {
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let label = format!("{:>width$}", field.label, width = label_width.saturating_sub(1) as usize);
+            buf.set_stringn(row.x, y, label, label_width as usize, label_style);
+
+            let input_x = row.x + label_width + 1;
+            let input_width = self.field_width.min(row.right().saturating_sub(input_x));
+            let input = Rect::new(input_x, y, input_width, 1);
+            let input_style = Style::default().bg(if focused { Color::DarkGray } else { Color::Black });
+            match &mut field.widget {
+                Widget::Text(text) => {
+                    text.set_style(input_style);
+                    text.draw(input, buf);
+                }
+                Widget::Select { options, index } => {
+                    let value = options.get(*index).map_or("", String::as_str);
+                    buf.set_style(input, input_style);
+                    buf.set_stringn(input.x, y, format!("‹ {value} ›"), input.width as usize, input_style);
+                }
+                Widget::Checkbox(checked) => {
+                    let mark = if *checked { "[x]" } else { "[ ]" };
+                    buf.set_stringn(input.x, y, mark, input.width as usize, input_style);
+                }
+            }
+
+            if let Some(error) = self.errors[index].as_deref().filter(|_| self.touched[index]) {
+                let x = input.right() + 1;
+                if x < row.right() {
+                    let style = Style::default().fg(Color::Red);
+                    buf.set_stringn(x, y, format!("✗ {error}"), (row.right() - x) as usize, style);
+                }
+            }
+        }
+    }
+
+    fn move_focus(&mut self, delta: isize) {
+        let len = self.fields.len() as isize;
+        if len > 0 {
+            self.focus = (self.focus as isize + delta).rem_euclid(len) as usize;
+            self.sync_focus();
+        }
+    }
+
+    fn sync_focus(&mut self) {
+        for (index, field) in self.fields.iter_mut().enumerate() {
+            if let Widget::Text(input) = &mut field.widget {
+                input.set_focused(index == self.focus);
+            }
+        }
+    }
+
+    /// Validate field `index` and, if it passes, write it to the model.
+    fn apply(&mut self, index: usize) {
+        let field = &self.fields[index];
+        let value = field.widget.value();
+        let result = field.check(&value).and_then(|()| {
+            self.model
+                .update(|m| (field.set)(m, &value))
+                .unwrap_or_else(|e| Err(e.to_string()))
+        });
+        self.errors[index] = result.err();
+    }
+}
+
+impl<T: Send + Sync + 'static> Component for Form<T> {
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let area = cx.area(frame);
+        self.draw(area, frame.buffer_mut());
+    }
+
+    fn refresh_on_event(&self) -> bool {
+        true
+    }
+
+    fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+        match event {
+            Event::Key(key) if key.code == KeyCode::Enter => return self.submit(),
+            Event::Key(key) => {
+                self.handle_key(key);
+            }
+            Event::Paste(text) => self.handle_paste(&text),
+            Event::Mouse(mouse) => {
+                self.handle_mouse(mouse);
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Signup {
+        name: String,
+        age: u32,
+        plan: String,
+        newsletter: bool,
+    }
+
+    fn form(model: Entity<Signup>) -> Form<Signup> {
+        Form::new(model)
+            .field(
+                Field::text("Name", |s: &Signup| s.name.clone(), |s, v| {
+                    s.name = v.to_string();
+                    Ok(())
+                })
+                .validate(validators::required()),
+            )
+            .field(Field::text("Age", |s: &Signup| s.age.to_string(), |s, v| {
+                s.age = v.parse().map_err(|_| "not a number".to_string())?;
+                Ok(())
+            }))
+            .field(Field::select(
+                "Plan",
+                vec!["free".into(), "pro".into()],
+                |s: &Signup| s.plan.clone(),
+                |s, v| s.plan = v.to_string(),
+            ))
+            .field(Field::checkbox("News", |s: &Signup| s.newsletter, |s, v| s.newsletter = v))
+            .on_submit(|s| Some(Action::Navigate(format!("welcome/{}", s.name))))
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::from(code)
+    }
+
+    #[test]
+    fn test_binding_focus_and_validation() {
+        let model = Entity::new(Signup::default());
+        let mut form = form(model.clone());
+
+        assert_eq!(form.submit(), None);
+        assert_eq!(form.error(0), Some("required"));
+        assert_eq!(form.focused(), 0);
+
+        form.handle_paste("Ada");
+        form.handle_key(key(KeyCode::Tab));
+        form.handle_key(key(KeyCode::Backspace));
+        form.handle_key(key(KeyCode::Char('x')));
+        assert_eq!(form.error(1), Some("not a number"));
+        form.handle_key(key(KeyCode::Backspace));
+        form.handle_key(key(KeyCode::Char('3')));
+        form.handle_key(key(KeyCode::Char('6')));
+        form.handle_key(key(KeyCode::Tab));
+        form.handle_key(key(KeyCode::Right));
+        form.handle_key(key(KeyCode::Tab));
+        form.handle_key(key(KeyCode::Char(' ')));
+
+        let (name, age, plan, news) = model.read(|s| (s.name.clone(), s.age, s.plan.clone(), s.newsletter)).unwrap();
+        assert_eq!((name.as_str(), age, plan.as_str(), news), ("Ada", 36, "pro", true));
+        assert_eq!(form.submit(), Some(Action::Navigate("welcome/Ada".into())));
+
+        form.handle_key(key(KeyCode::Tab));
+        assert_eq!(form.focused(), 0);
+        form.handle_key(key(KeyCode::BackTab));
+        assert_eq!(form.focused(), 3);
+    }
+
+    #[test]
+    fn test_errors_render_next_to_fields() {
+        let mut form = form(Entity::new(Signup::default())).field_width(6);
+        form.submit();
+        let area = Rect::new(0, 0, 30, 4);
+        let mut buf = Buffer::empty(area);
+        form.draw(area, &mut buf);
+        let row: String = (0..30).map(|x| buf[(x, 0)].symbol().to_string()).collect();
+        assert_eq!(row.trim_end(), "Name         ✗ required");
+    }
+}
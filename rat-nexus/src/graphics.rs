@@ -0,0 +1,538 @@
+//! Inline images through terminal graphics protocols.
+//!
+//! The protocol is detected once at startup and exposed as `Capabilities::graphics`.
+//! Pixel data cannot travel through ratatui's cell buffer, so components queue encoded
+//! images with `AppContext::place_graphic` while rendering; the cells they cover are
+//! marked to be skipped, and once the frame is flushed the queued images are written
+//! at their positions. An image is only re-sent when it is new, moved, re-encoded or
+//! was covered by a popup, and Kitty placements that are no longer drawn are deleted.
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+use ratatui::backend::{Backend, CrosstermBackend, TestBackend};
+use ratatui::layout::Rect;
+use ratatui::Frame;
+
+/// Environment variable that forces a protocol: `kitty`, `iterm2`, `sixel` or `halfblocks`.
+pub const GRAPHICS_ENV: &str = "RAT_NEXUS_GRAPHICS";
+
+/// Cell size in pixels assumed when the terminal does not report its pixel size.
+const FALLBACK_CELL: (u16, u16) = (8, 16);
+/// Base64 bytes per Kitty escape sequence, the maximum the protocol allows.
+const KITTY_CHUNK: usize = 4096;
+
+/// How images are drawn in the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    /// The Kitty graphics protocol (kitty, Ghostty).
+    Kitty,
+    /// iTerm2 inline images (iTerm2, WezTerm, mintty).
+    Iterm2,
+    /// DEC sixel graphics (foot, mlterm, xterm with sixel enabled).
+    Sixel,
+    /// Unicode half blocks with true-color cells; works everywhere at two pixels per cell.
+    HalfBlocks,
+}
+
+impl GraphicsProtocol {
+    /// Guess the protocol from the environment; `RAT_NEXUS_GRAPHICS` overrides the guess.
+    pub fn detect() -> Self {
+        Self::from_env(|name| std::env::var(name).ok())
+    }
+
+    fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        if let Some(forced) = var(GRAPHICS_ENV).and_then(|name| Self::from_name(&name)) {
+            return forced;
+        }
+        // Multiplexers swallow graphics sequences unless configured for passthrough
+        if var("TMUX").is_some() || var("ZELLIJ").is_some() {
+            return Self::HalfBlocks;
+        }
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+        if var("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty" || program == "ghostty" {
+            Self::Kitty
+        } else if matches!(program.as_str(), "iTerm.app" | "WezTerm" | "mintty")
+            || var("LC_TERMINAL").as_deref() == Some("iTerm2")
+        {
+            Self::Iterm2
+        } else if term.contains("sixel") || term.starts_with("foot") || term.starts_with("mlterm") {
+            Self::Sixel
+        } else {
+            Self::HalfBlocks
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "kitty" => Some(Self::Kitty),
+            "iterm2" | "iterm" => Some(Self::Iterm2),
+            "sixel" => Some(Self::Sixel),
+            "halfblocks" | "none" => Some(Self::HalfBlocks),
+            _ => None,
+        }
+    }
+
+    /// Whether images are sent as pixels rather than drawn with cells.
+    pub fn is_pixel(&self) -> bool {
+        !matches!(self, Self::HalfBlocks)
+    }
+}
+
+/// The size of a terminal cell in pixels, as reported by the terminal, or 8x16 if unknown.
+pub fn cell_size() -> (u16, u16) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => {
+            ((size.width / size.columns).max(1), (size.height / size.rows).max(1))
+        }
+        _ => FALLBACK_CELL,
+    }
+}
+
+/// An RGBA image, 8 bits per channel, rows top to bottom.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Pixels {
+    width: u32,
+    height: u32,
+    data: Arc<[u8]>,
+}
+
+impl Pixels {
+    /// Wrap `data` of `width * height` RGBA pixels; `None` if the length does not match.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // decoded with the `image` crate
+    /// let decoded = image::open("logo.png")?.to_rgba8();
+    /// let pixels = Pixels::from_rgba(decoded.width(), decoded.height(), decoded.into_raw()).unwrap();
+    /// ```
+    pub fn from_rgba(width: u32, height: u32, data: impl Into<Arc<[u8]>>) -> Option<Self> {
+        let data = data.into();
+        (data.len() as u64 == u64::from(width) * u64::from(height) * 4).then_some(Self { width, height, data })
+    }
+
+    /// Width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The raw RGBA bytes.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The pixel at (`x`, `y`).
+    pub fn get(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        [self.data[i], self.data[i + 1], self.data[i + 2], self.data[i + 3]]
+    }
+
+    /// Scale to `width`x`height` with nearest-neighbor sampling.
+    pub fn resize(&self, width: u32, height: u32) -> Self {
+        if (width, height) == (self.width, self.height) {
+            return self.clone();
+        }
+        let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+        for y in 0..height {
+            let sy = (u64::from(y) * u64::from(self.height) / u64::from(height.max(1))) as u32;
+            for x in 0..width {
+                let sx = (u64::from(x) * u64::from(self.width) / u64::from(width.max(1))) as u32;
+                data.extend_from_slice(&self.get(sx, sy));
+            }
+        }
+        Self { width, height, data: data.into() }
+    }
+
+    /// The largest size with this image's aspect ratio that fits `width`x`height`.
+    pub fn fit(&self, width: u32, height: u32) -> (u32, u32) {
+        if self.width == 0 || self.height == 0 {
+            return (0, 0);
+        }
+        let (w, h) = (u64::from(self.width), u64::from(self.height));
+        if u64::from(width) * h <= u64::from(height) * w {
+            (width, (u64::from(width) * h / w).max(1) as u32)
+        } else {
+            ((u64::from(height) * w / h).max(1) as u32, height)
+        }
+    }
+}
+
+impl std::fmt::Debug for Pixels {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Pixels({}x{})", self.width, self.height)
+    }
+}
+
+/// An image encoded for a pixel protocol, ready to be placed.
+#[derive(Debug, Clone)]
+pub(crate) struct Encoded {
+    id: u32,
+    protocol: GraphicsProtocol,
+    sequence: Arc<str>,
+}
+
+impl Encoded {
+    /// Encode `pixels`, already scaled to their on-screen size, to cover `cols`x`rows` cells.
+    pub(crate) fn new(protocol: GraphicsProtocol, pixels: &Pixels, cols: u16, rows: u16) -> Self {
+        static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let sequence = match protocol {
+            GraphicsProtocol::Kitty => kitty(pixels, cols, rows, id),
+            GraphicsProtocol::Iterm2 => iterm2(pixels, cols, rows),
+            GraphicsProtocol::Sixel => sixel(pixels),
+            GraphicsProtocol::HalfBlocks => String::new(),
+        };
+        Self { id, protocol, sequence: sequence.into() }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Placement {
+    area: Rect,
+    image: Encoded,
+}
+
+impl Placement {
+    fn same(&self, other: &Placement) -> bool {
+        self.area == other.area && self.image.id == other.image.id
+    }
+}
+
+#[derive(Default)]
+struct State {
+    queued: Vec<Placement>,
+    shown: Vec<Placement>,
+    screen: Rect,
+    pending: String,
+}
+
+/// Images queued for the current frame and those on screen, owned by the `AppContext`.
+#[derive(Clone, Default)]
+pub(crate) struct Graphics {
+    state: Arc<Mutex<State>>,
+}
+
+impl Graphics {
+    pub(crate) fn place(&self, area: Rect, image: Encoded) {
+        if let Ok(mut state) = self.state.lock() {
+            state.queued.push(Placement { area, image });
+        }
+    }
+
+    /// Settle what to write once the frame is complete: queued images whose cells were
+    /// drawn over afterwards are dropped, and images no longer shown are cleaned up.
+    pub(crate) fn prepare(&self, frame: &mut Frame) {
+        let Ok(mut state) = self.state.lock() else { return };
+        let screen = frame.area();
+        let buffer = frame.buffer_mut();
+        let queued = std::mem::take(&mut state.queued);
+        let visible: Vec<Placement> = queued
+            .into_iter()
+            .filter(|p| {
+                screen.contains(p.area.as_position())
+                    && p.area.right() <= screen.right()
+                    && p.area.bottom() <= screen.bottom()
+                    && p.area.positions().all(|pos| buffer[pos].skip)
+            })
+            .collect();
+
+        // A resize clears the screen, so everything is sent again
+        let resized = std::mem::replace(&mut state.screen, screen) != screen;
+        let mut out = String::new();
+        for old in &state.shown {
+            if old.image.protocol == GraphicsProtocol::Kitty && (resized || !visible.iter().any(|p| p.same(old))) {
+                out.push_str(&format!("\x1b_Ga=d,d=I,i={},q=2\x1b\\", old.image.id));
+            }
+        }
+        for placement in &visible {
+            if resized || !state.shown.iter().any(|old| old.same(placement)) {
+                out.push_str(&format!("\x1b[{};{}H", placement.area.y + 1, placement.area.x + 1));
+                out.push_str(&placement.image.sequence);
+            }
+        }
+        state.shown = visible;
+        state.pending.push_str(&out);
+    }
+
+    /// Take what `prepare` settled on, to be written to the terminal.
+    pub(crate) fn take_pending(&self) -> String {
+        self.state.lock().map(|mut state| std::mem::take(&mut state.pending)).unwrap_or_default()
+    }
+
+    /// Write what `prepare` settled on to the terminal.
+    pub(crate) fn flush<B: GraphicsBackend>(&self, backend: &mut B) -> io::Result<()> {
+        let pending = self.take_pending();
+        if pending.is_empty() {
+            return Ok(());
+        }
+        backend.write_raw(pending.as_bytes())
+    }
+}
+
+/// A backend that raw graphics sequences can be written to.
+pub(crate) trait GraphicsBackend: Backend {
+    fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()>;
+}
+
+impl<W: Write> GraphicsBackend for CrosstermBackend<W> {
+    fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.write_all(bytes)?;
+        Write::flush(self)
+    }
+}
+
+impl GraphicsBackend for TestBackend {
+    fn write_raw(&mut self, _bytes: &[u8]) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Kitty: transmit raw RGBA and place it over `cols`x`rows` cells without moving the cursor.
+fn kitty(pixels: &Pixels, cols: u16, rows: u16, id: u32) -> String {
+    let payload = base64(pixels.data());
+    let chunks: Vec<&str> = payload
+        .as_bytes()
+        .chunks(KITTY_CHUNK)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect();
+    let mut out = String::with_capacity(payload.len() + chunks.len() * 16 + 64);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            out.push_str(&format!(
+                "\x1b_Ga=T,f=32,s={},v={},c={cols},r={rows},i={id},C=1,q=2,m={more};{chunk}\x1b\\",
+                pixels.width(),
+                pixels.height()
+            ));
+        } else {
+            out.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+        }
+    }
+    out
+}
+
+/// iTerm2: an inline PNG stretched over `cols`x`rows` cells.
+fn iterm2(pixels: &Pixels, cols: u16, rows: u16) -> String {
+    let png = png(pixels);
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={cols};height={rows};preserveAspectRatio=0;doNotMoveCursor=1:{}\x07",
+        png.len(),
+        base64(&png)
+    )
+}
+
+/// Sixel with a 6x6x6 color cube; transparent pixels keep the background.
+fn sixel(pixels: &Pixels) -> String {
+    let (width, height) = (pixels.width(), pixels.height());
+    let index = |x: u32, y: u32| -> Option<usize> {
+        let [r, g, b, a] = pixels.get(x, y);
+        let level = |c: u8| (usize::from(c) * 5 + 127) / 255;
+        (a >= 128).then(|| level(r) * 36 + level(g) * 6 + level(b))
+    };
+
+    let mut out = format!("\x1bP0;1q\"1;1;{width};{height}");
+    let mut used = [false; 216];
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(i) = index(x, y) {
+                used[i] = true;
+            }
+        }
+    }
+    for (i, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+        let percent = |level: usize| level * 100 / 5;
+        out.push_str(&format!("#{i};2;{};{};{}", percent(i / 36), percent(i / 6 % 6), percent(i % 6)));
+    }
+
+    for band in (0..height).step_by(6) {
+        let rows = (height - band).min(6);
+        let mut colors: Vec<usize> =
+            (0..rows).flat_map(|dy| (0..width).filter_map(move |x| index(x, band + dy))).collect();
+        colors.sort_unstable();
+        colors.dedup();
+        for color in colors {
+            out.push_str(&format!("#{color}"));
+            let mut run: Option<(u8, usize)> = None;
+            for x in 0..width {
+                let bits = (0..rows).filter(|&dy| index(x, band + dy) == Some(color)).fold(0u8, |bits, dy| bits | 1 << dy);
+                run = match run {
+                    Some((sixel, n)) if sixel == bits => Some((sixel, n + 1)),
+                    Some((sixel, n)) => {
+                        push_run(&mut out, sixel, n);
+                        Some((bits, 1))
+                    }
+                    None => Some((bits, 1)),
+                };
+            }
+            if let Some((sixel, n)) = run {
+                push_run(&mut out, sixel, n);
+            }
+            out.push('$');
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_run(out: &mut String, bits: u8, n: usize) {
+    let c = char::from(63 + bits);
+    if n > 3 {
+        out.push_str(&format!("!{n}{c}"));
+    } else {
+        out.extend(std::iter::repeat_n(c, n));
+    }
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(ALPHABET[(n >> (18 - 6 * i) & 63) as usize]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// An uncompressed RGBA PNG; terminals decode it, so size matters less than simplicity.
+fn png(pixels: &Pixels) -> Vec<u8> {
+    let row_len = pixels.width() as usize * 4;
+    let mut raw = Vec::with_capacity((row_len + 1) * pixels.height() as usize);
+    for row in pixels.data().chunks(row_len.max(1)).take(pixels.height() as usize) {
+        raw.push(0); // filter: none
+        raw.extend_from_slice(row);
+    }
+
+    // zlib stream of stored deflate blocks
+    let mut zlib = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = if raw.is_empty() { vec![&[]] } else { raw.chunks(0xffff).collect() };
+    for (i, block) in blocks.iter().enumerate() {
+        let len = block.len() as u16;
+        zlib.push(u8::from(i + 1 == blocks.len()));
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&pixels.width().to_be_bytes());
+    ihdr.extend_from_slice(&pixels.height().to_be_bytes());
+    ihdr.extend_from_slice(&[8, 6, 0, 0, 0]); // 8-bit RGBA, no interlace
+
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+    for (kind, data) in [(b"IHDR", ihdr.as_slice()), (b"IDAT", zlib.as_slice()), (b"IEND", &[])] {
+        out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        let start = out.len();
+        out.extend_from_slice(kind);
+        out.extend_from_slice(data);
+        let crc = crc32(&out[start..]);
+        out.extend_from_slice(&crc.to_be_bytes());
+    }
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &b| {
+        (0..8).fold(crc ^ u32::from(b), |crc, _| if crc & 1 == 1 { crc >> 1 ^ 0xedb8_8320 } else { crc >> 1 })
+    })
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % 65521;
+        (a, (b + a) % 65521)
+    });
+    b << 16 | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn solid(width: u32, height: u32, rgba: [u8; 4]) -> Pixels {
+        Pixels::from_rgba(width, height, rgba.repeat((width * height) as usize)).unwrap()
+    }
+
+    #[test]
+    fn test_detect_from_env() {
+        let detect = |vars: &[(&str, &str)]| {
+            GraphicsProtocol::from_env(|name| vars.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string()))
+        };
+        assert_eq!(detect(&[("TERM", "xterm-kitty")]), GraphicsProtocol::Kitty);
+        assert_eq!(detect(&[("TERM_PROGRAM", "iTerm.app")]), GraphicsProtocol::Iterm2);
+        assert_eq!(detect(&[("TERM", "foot")]), GraphicsProtocol::Sixel);
+        assert_eq!(detect(&[("TERM", "xterm-kitty"), ("TMUX", "1")]), GraphicsProtocol::HalfBlocks);
+        assert_eq!(detect(&[("TERM", "xterm-256color"), (GRAPHICS_ENV, "Sixel")]), GraphicsProtocol::Sixel);
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), GraphicsProtocol::HalfBlocks);
+    }
+
+    #[test]
+    fn test_pixels_fit_and_resize() {
+        let pixels = solid(40, 20, [1, 2, 3, 255]);
+        assert_eq!(pixels.fit(10, 10), (10, 5));
+        assert_eq!(pixels.fit(100, 10), (20, 10));
+        assert_eq!(pixels.resize(4, 2).data(), [1, 2, 3, 255].repeat(8).as_slice());
+        assert!(Pixels::from_rgba(2, 2, vec![0; 15]).is_none());
+    }
+
+    #[test]
+    fn test_encoders() {
+        assert_eq!(base64(b"Ma"), "TWE=");
+        assert_eq!(base64(b"Man!"), "TWFuIQ==");
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+
+        let red = solid(3, 7, [255, 0, 0, 255]);
+        assert!(png(&red).starts_with(b"\x89PNG"));
+        // One color, two bands: all six rows set, then the last row alone
+        assert_eq!(sixel(&red), "\x1bP0;1q\"1;1;3;7#180;2;100;0;0#180~~~$-#180@@@$-\x1b\\");
+        let kitty = kitty(&solid(64, 64, [0; 4]), 4, 2, 7);
+        assert!(kitty.starts_with("\x1b_Ga=T,f=32,s=64,v=64,c=4,r=2,i=7,C=1,q=2,m=1;"));
+        // 16 KiB of pixels in six chunks, the last one closing the transfer
+        assert_eq!(kitty.matches("\x1b_G").count(), 6);
+        assert_eq!(kitty.matches("m=0;").count(), 1);
+        assert!(kitty.rsplit("\x1b_G").next().unwrap().starts_with("m=0;"));
+    }
+
+    #[test]
+    fn test_graphics_sent_once_and_deleted_when_gone() {
+        let graphics = Graphics::default();
+        let image = Encoded::new(GraphicsProtocol::Kitty, &solid(2, 2, [0; 4]), 2, 1);
+        let mut terminal = Terminal::new(TestBackend::new(4, 2)).unwrap();
+        let mut frame = |graphics: &Graphics, image: Option<&Encoded>| {
+            terminal
+                .draw(|frame| {
+                    if let Some(image) = image {
+                        let area = Rect::new(1, 1, 2, 1);
+                        for pos in area.positions() {
+                            frame.buffer_mut()[pos].set_skip(true);
+                        }
+                        graphics.place(area, image.clone());
+                    }
+                    graphics.prepare(frame);
+                })
+                .unwrap();
+            std::mem::take(&mut graphics.state.lock().unwrap().pending)
+        };
+
+        assert!(frame(&graphics, Some(&image)).starts_with("\x1b[2;2H\x1b_Ga=T"));
+        assert_eq!(frame(&graphics, Some(&image)), "");
+        assert_eq!(frame(&graphics, None), format!("\x1b_Ga=d,d=I,i={},q=2\x1b\\", image.id));
+    }
+}
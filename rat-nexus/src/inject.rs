@@ -0,0 +1,126 @@
+//! Dependency-injection style construction from the `AppContext`.
+//!
+//! A component declares the shared state it needs as a tuple of `Deps`; `cx.construct()`
+//! resolves every dependency from the application state (the `cx.set`/`cx.get` TypeMap)
+//! and reports all missing registrations at once instead of failing on the first.
+//! In `define_app!`, a page declared `= construct` is built this way by `Root::try_new`.
+//!
+//! # Example
+//! ```ignore
+//! impl FromContext for MonitorPage {
+//!     type Deps = (Entity<AppState>, Entity<MonitorState>);
+//!
+//!     fn from_context((app_state, state): Self::Deps, _cx: &AppContext) -> Self {
+//!         Self { app_state, state, tasks: TaskTracker::default() }
+//!     }
+//! }
+//!
+//! cx.set(cx.new_entity(AppState::default()));
+//! cx.set(cx.new_entity(MonitorState::default()));
+//! let page: MonitorPage = cx.construct()?;
+//! ```
+
+use crate::application::AppContext;
+
+/// A type that can be built from dependencies registered in the `AppContext`.
+pub trait FromContext: Sized {
+    /// The dependencies to resolve, as a tuple of stored types (e.g. `(Entity<AppState>,)`).
+    type Deps: Resolve;
+
+    /// Build the value from its resolved dependencies.
+    fn from_context(deps: Self::Deps, cx: &AppContext) -> Self;
+}
+
+/// A set of dependencies resolvable from the application state.
+/// Implemented for tuples (up to 8 elements) of `Clone + Send + Sync + 'static` types.
+pub trait Resolve: Sized {
+    /// Resolve all dependencies, pushing the type name of each missing one to `missing`.
+    fn resolve(cx: &AppContext, missing: &mut Vec<&'static str>) -> Option<Self>;
+}
+
+impl Resolve for () {
+    fn resolve(_cx: &AppContext, _missing: &mut Vec<&'static str>) -> Option<Self> {
+        Some(())
+    }
+}
+
+macro_rules! impl_resolve_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> Resolve for ($($name,)+)
+        where
+            $($name: Clone + Send + Sync + 'static),+
+        {
+            #[allow(non_snake_case)]
+            fn resolve(cx: &AppContext, missing: &mut Vec<&'static str>) -> Option<Self> {
+                $(
+                    let $name = cx.get::<$name>().ok();
+                    if $name.is_none() {
+                        missing.push(std::any::type_name::<$name>());
+                    }
+                )+
+                Some(($($name?,)+))
+            }
+        }
+    };
+}
+
+impl_resolve_for_tuple!(A);
+impl_resolve_for_tuple!(A, B);
+impl_resolve_for_tuple!(A, B, C);
+impl_resolve_for_tuple!(A, B, C, D);
+impl_resolve_for_tuple!(A, B, C, D, E);
+impl_resolve_for_tuple!(A, B, C, D, E, F);
+impl_resolve_for_tuple!(A, B, C, D, E, F, G);
+impl_resolve_for_tuple!(A, B, C, D, E, F, G, H);
+
+impl AppContext {
+    /// Construct a value by resolving its declared dependencies from the application state.
+    /// Fails with `Error::MissingDependencies` listing every unregistered type.
+    pub fn construct<T: FromContext>(&self) -> crate::Result<T> {
+        let mut missing = Vec::new();
+        match T::Deps::resolve(self, &mut missing) {
+            Some(deps) => Ok(T::from_context(deps, self)),
+            None => Err(crate::Error::MissingDependencies {
+                target: std::any::type_name::<T>(),
+                missing: missing.join(", "),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Page {
+        name: String,
+        count: u32,
+    }
+
+    impl FromContext for Page {
+        type Deps = (String, u32);
+
+        fn from_context((name, count): Self::Deps, _cx: &AppContext) -> Self {
+            Self { name, count }
+        }
+    }
+
+    #[test]
+    fn test_construct_resolves_dependencies() {
+        let (cx, _, _) = AppContext::new();
+        cx.set(String::from("monitor"));
+        cx.set(3u32);
+
+        let page: Page = cx.construct().unwrap();
+        assert_eq!(page.name, "monitor");
+        assert_eq!(page.count, 3);
+    }
+
+    #[test]
+    fn test_construct_lists_all_missing() {
+        let (cx, _, _) = AppContext::new();
+        let err = cx.construct::<Page>().err().unwrap().to_string();
+        assert!(err.contains("String"), "{}", err);
+        assert!(err.contains("u32"), "{}", err);
+    }
+}
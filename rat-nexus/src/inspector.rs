@@ -0,0 +1,192 @@
+//! Developer overlay showing what the running app is doing.
+//!
+//! Toggled with `F9` (see `AppContext::set_inspector_key`), the inspector lists the
+//! frame rate, a histogram of recent frame times, input latency, the current route, the
+//! number of alive tokio tasks, timers and subscriptions, and the alive entities with
+//! their ids and types. While open it redraws twice a second so the numbers stay live.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Widget};
+
+use crate::component::traits::Event;
+use crate::metrics::FRAME_BUCKETS;
+use crate::timer::TimerHandle;
+use crate::AppContext;
+
+/// How often the open inspector refreshes.
+const REFRESH: Duration = Duration::from_millis(500);
+
+/// Width of the inspector box, in cells.
+const WIDTH: u16 = 44;
+
+/// Histogram bars, from empty to full.
+const BARS: [&str; 9] = [" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+struct InspectorInner {
+    key: Option<KeyEvent>,
+    refresh: Option<TimerHandle>,
+}
+
+/// The inspector overlay, owned by the `AppContext`.
+#[derive(Clone)]
+pub(crate) struct Inspector {
+    inner: Arc<Mutex<InspectorInner>>,
+}
+
+impl Default for Inspector {
+    fn default() -> Self {
+        Self { inner: Arc::new(Mutex::new(InspectorInner { key: Some(KeyEvent::from(KeyCode::F(9))), refresh: None })) }
+    }
+}
+
+impl Inspector {
+    pub(crate) fn set_key(&self, key: Option<KeyEvent>) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.key = key;
+        }
+    }
+
+    pub(crate) fn is_open(&self) -> bool {
+        self.inner.lock().map(|inner| inner.refresh.is_some()).unwrap_or(false)
+    }
+
+    pub(crate) fn set_open(&self, open: bool, app: &AppContext) {
+        let Ok(mut inner) = self.inner.lock() else { return };
+        match (open, inner.refresh.take()) {
+            (true, Some(refresh)) => inner.refresh = Some(refresh),
+            (true, None) => inner.refresh = Some(app.set_interval(REFRESH, |_| {})),
+            (false, Some(refresh)) => refresh.cancel(),
+            (false, None) => {}
+        }
+    }
+
+    /// Toggle on the key; returns true if the event was consumed.
+    pub(crate) fn intercept(&self, event: &Event, app: &AppContext) -> bool {
+        let key = self.inner.lock().ok().and_then(|inner| inner.key);
+        if key.is_none() || !matches!(event, Event::Key(pressed) if Some(*pressed) == key) {
+            return false;
+        }
+        self.set_open(!self.is_open(), app);
+        true
+    }
+
+    /// Draw the open inspector in the top-right corner of `bounds`.
+    pub(crate) fn render(&self, app: &AppContext, buf: &mut Buffer, bounds: Rect) {
+        if !self.is_open() {
+            return;
+        }
+        let lines = report(app);
+        let width = WIDTH.min(bounds.width);
+        let height = (lines.len() as u16 + 2).min(bounds.height);
+        let area = Rect::new(bounds.right() - width, bounds.y, width, height);
+        if area.height < 3 {
+            return;
+        }
+        Clear.render(area, buf);
+        let block = Block::default()
+            .title(" Inspector ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Magenta));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        for (row, (line, style)) in lines.iter().enumerate().take(inner.height as usize) {
+            buf.set_stringn(inner.x, inner.y + row as u16, line, inner.width as usize, *style);
+        }
+    }
+}
+
+/// The inspector's lines, with their styles.
+fn report(app: &AppContext) -> Vec<(String, Style)> {
+    let plain = Style::default();
+    let dim = Style::default().fg(Color::DarkGray);
+    let metrics = app.metrics();
+    let tasks = tokio::runtime::Handle::try_current()
+        .map(|runtime| runtime.metrics().num_alive_tasks().to_string())
+        .unwrap_or_else(|_| "-".into());
+    let mut entities = app.live_entities();
+    entities.sort_by_key(|(id, _)| *id);
+
+    let mut lines = vec![
+        (format!("FPS      {:.0}", metrics.fps), plain),
+        (format!("Frame    {:.1?}", metrics.last_frame), plain),
+        (format!("Frames   {}", histogram(&app.frame_histogram())), Style::default().fg(Color::Cyan)),
+        ("         <1 <2 <4 <8 16 33 ≥33 ms".into(), dim),
+        (format!("Input    p50 {:.1?}  p99 {:.1?}", metrics.p50, metrics.p99), plain),
+        (format!("Route    {}", app.active_route().unwrap_or_else(|| "-".into())), plain),
+        (format!("Tasks    {tasks}  timers {}  subs {}", app.timer_count(), app.subscription_count()), plain),
+        (format!("Entities {}", entities.len()), plain),
+    ];
+    lines.extend(entities.iter().map(|(id, name)| (format!("  #{id:<5} {}", short_type_name(name)), dim)));
+    lines
+}
+
+/// One bar per bucket, scaled to the fullest bucket, spaced to match the axis labels.
+fn histogram(counts: &[usize; FRAME_BUCKETS.len() + 1]) -> String {
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+    counts
+        .iter()
+        .map(|&count| {
+            let level = if count == 0 { 0 } else { (count * (BARS.len() - 1)).div_ceil(max) };
+            format!("{}{} ", BARS[level], BARS[level])
+        })
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+/// `alloc::vec::Vec<my_app::Item>` as `Vec<Item>`.
+fn short_type_name(name: &str) -> String {
+    let mut short = String::with_capacity(name.len());
+    let mut segment = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+        } else {
+            short.push_str(segment.rsplit("::").next().unwrap_or(""));
+            segment.clear();
+            short.push(c);
+        }
+    }
+    short.push_str(segment.rsplit("::").next().unwrap_or(""));
+    short
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_type_name_and_histogram() {
+        assert_eq!(short_type_name("alloc::vec::Vec<my_app::Item>"), "Vec<Item>");
+        assert_eq!(short_type_name("core::option::Option<(u8, a::B)>"), "Option<(u8, B)>");
+        assert_eq!(histogram(&[4, 0, 0, 0, 0, 0, 1]), format!("██{}▂▂", " ".repeat(16)));
+    }
+
+    #[tokio::test]
+    async fn test_inspector_toggles_and_lists_entities() {
+        let (app, _refresh, _inject) = AppContext::new();
+        let _entity = app.new_entity(42u32);
+        let f9 = Event::Key(KeyEvent::from(KeyCode::F(9)));
+        assert!(app.inspector.intercept(&f9, &app));
+        assert!(app.inspector_open());
+        assert_eq!(app.timer_count(), 1);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 60, 40));
+        let bounds = buf.area;
+        app.inspector.render(&app, &mut buf, bounds);
+        let text: String = buf.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Inspector"));
+        assert!(text.contains(" u32"));
+
+        assert!(app.inspector.intercept(&f9, &app));
+        assert!(!app.inspector_open());
+        assert_eq!(app.timer_count(), 0);
+    }
+}
@@ -0,0 +1,160 @@
+//! Mounting existing plain-ratatui screens as components.
+//!
+//! Implement `RatatuiScreen` for a screen that already has a `render(frame, area)` and
+//! a key handler, then mount it through `Bridge` wherever a `Component` is expected
+//! (a `define_app!` page, a `ChildView`, the root). The rest of the app can then move
+//! to components one screen at a time.
+//!
+//! # Example
+//! ```ignore
+//! impl RatatuiScreen for LegacyEditor {
+//!     fn render(&mut self, frame: &mut Frame, area: Rect) {
+//!         ui::draw_editor(frame, area, self);
+//!     }
+//!
+//!     fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+//!         self.on_key(key);
+//!         None
+//!     }
+//!
+//!     fn should_quit(&self) -> bool {
+//!         self.exit
+//!     }
+//! }
+//!
+//! let editor = ChildView::new(Bridge::new(LegacyEditor::load(path)?));
+//! ```
+
+use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::layout::Rect;
+use ratatui::Frame;
+
+use crate::component::traits::{Action, Component, Event};
+use crate::{Context, EventContext};
+
+/// A screen written against plain ratatui.
+pub trait RatatuiScreen: Send + Sync + 'static {
+    /// Draw into `area`.
+    fn render(&mut self, frame: &mut Frame, area: Rect);
+
+    /// Handle a key press, optionally returning a framework action.
+    fn handle_key(&mut self, key: KeyEvent) -> Option<Action>;
+
+    /// Handle a mouse event inside the screen's area. Ignored by default.
+    fn handle_mouse(&mut self, _mouse: MouseEvent) -> Option<Action> {
+        None
+    }
+
+    /// Handle pasted text. Ignored by default.
+    fn handle_paste(&mut self, _text: &str) -> Option<Action> {
+        None
+    }
+
+    /// Whether the screen asked to exit; checked after every event and turned into
+    /// `Action::Quit`.
+    fn should_quit(&self) -> bool {
+        false
+    }
+}
+
+/// A `RatatuiScreen` mounted as a `Component`.
+pub struct Bridge<S: RatatuiScreen> {
+    screen: S,
+    area: Option<Rect>,
+}
+
+impl<S: RatatuiScreen> Bridge<S> {
+    /// Wrap `screen`.
+    pub fn new(screen: S) -> Self {
+        Self { screen, area: None }
+    }
+
+    /// The wrapped screen.
+    pub fn screen(&self) -> &S {
+        &self.screen
+    }
+
+    /// Mutable access to the wrapped screen.
+    pub fn screen_mut(&mut self) -> &mut S {
+        &mut self.screen
+    }
+
+    /// Unwrap the screen.
+    pub fn into_inner(self) -> S {
+        self.screen
+    }
+}
+
+impl<S: RatatuiScreen + Default> Default for Bridge<S> {
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S: RatatuiScreen> Component for Bridge<S> {
+    fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+        let area = cx.area(frame);
+        self.area = Some(area);
+        self.screen.render(frame, area);
+    }
+
+    fn refresh_on_event(&self) -> bool {
+        true
+    }
+
+    fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+        let action = match event {
+            Event::Key(key) => self.screen.handle_key(key),
+            Event::Mouse(mouse) if self.area.is_some_and(|area| area.contains((mouse.column, mouse.row).into())) => {
+                self.screen.handle_mouse(mouse)
+            }
+            Event::Paste(text) => self.screen.handle_paste(&text),
+            _ => None,
+        };
+        action.or_else(|| self.screen.should_quit().then_some(Action::Quit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+    use crossterm::event::KeyCode;
+    use ratatui::widgets::Paragraph;
+
+    #[derive(Default)]
+    struct Counter {
+        count: u32,
+        exit: bool,
+    }
+
+    impl RatatuiScreen for Counter {
+        fn render(&mut self, frame: &mut Frame, area: Rect) {
+            frame.render_widget(Paragraph::new(format!("count {}", self.count)), area);
+        }
+
+        fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+            match key.code {
+                KeyCode::Char('+') => self.count += 1,
+                KeyCode::Char('q') => self.exit = true,
+                KeyCode::Esc => return Some(Action::Back),
+                _ => {}
+            }
+            None
+        }
+
+        fn should_quit(&self) -> bool {
+            self.exit
+        }
+    }
+
+    #[test]
+    fn test_bridge_renders_and_forwards_keys() {
+        let mut harness = Harness::with_size(Bridge::<Counter>::default(), 12, 1);
+        harness.expect_text("count 0");
+        assert_eq!(harness.type_str("++<Esc>"), vec![Action::Back]);
+        harness.expect_text("count 2");
+        assert_eq!(harness.type_str("q"), vec![Action::Quit]);
+        assert_eq!(harness.read(|bridge| bridge.screen().count), 2);
+    }
+}
@@ -0,0 +1,259 @@
+//! Multi-key sequences ("chords") such as `<Space>ff` or `gd`.
+//!
+//! Sequences registered with `AppContext::register_chord` are recognized before the
+//! keys reach components, which receive a single `Event::Chord(name)` instead. While a
+//! sequence is in progress its keys are held back (see `AppContext::pending_keys` for a
+//! status bar indicator). If the next key cannot continue any sequence, or none arrives
+//! within the timeout, the held keys are delivered as ordinary key events, so single
+//! keys that start a sequence still work on their own. `Esc` abandons a sequence.
+//!
+//! Sequences registered with `AppContext::register_chord_in` only apply in one
+//! `InputMode`, so a vim-style app can bind `dd` in normal mode and leave the keys to
+//! the text in insert mode.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::InputMode;
+use crate::component::traits::{CustomEvent, Event};
+use crate::AppContext;
+
+/// Default time to wait for the next key of a sequence.
+pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Name of the internal event that ends a sequence when no key arrives in time.
+const TIMEOUT_EVENT: &str = "rat_nexus::chord_timeout";
+
+/// Payload of a timeout event: the generation of the sequence it was scheduled for.
+struct ChordTimeout(u64);
+
+struct Chord {
+    keys: Vec<KeyEvent>,
+    name: String,
+    /// The only mode the sequence applies in, or `None` for all modes.
+    mode: Option<InputMode>,
+}
+
+struct ChordState {
+    chords: Vec<Chord>,
+    mode: InputMode,
+    pending: Vec<KeyEvent>,
+    timeout: Duration,
+    /// Bumped on every held key, so only the latest timeout ends the sequence.
+    generation: u64,
+}
+
+impl ChordState {
+    /// Follow a key press. Returns the events to deliver and whether the key is held.
+    fn press(&mut self, key: KeyEvent) -> (Vec<Event>, bool) {
+        if key.code == KeyCode::Esc && !self.pending.is_empty() {
+            self.pending.clear();
+            return (Vec::new(), false);
+        }
+        self.pending.push(key);
+        let exact = self.active().find(|chord| chord.keys == self.pending).map(|chord| chord.name.clone());
+        let longer = self.active().any(|chord| chord.keys.len() > self.pending.len() && chord.keys.starts_with(&self.pending));
+        match (exact, longer) {
+            (_, true) => (Vec::new(), true),
+            (Some(name), false) => {
+                self.pending.clear();
+                (vec![Event::Chord(name)], false)
+            }
+            (None, false) => {
+                let mut keys = std::mem::take(&mut self.pending).into_iter();
+                let mut events = vec![Event::Key(keys.next().expect("key was just pushed"))];
+                // The rest may start another sequence
+                let mut held = false;
+                for key in keys {
+                    let (more, holds) = self.press(key);
+                    events.extend(more);
+                    held = holds;
+                }
+                (events, held)
+            }
+        }
+    }
+
+    /// End the sequence in progress: run it if complete, otherwise release its keys.
+    fn expire(&mut self) -> Vec<Event> {
+        let keys = std::mem::take(&mut self.pending);
+        match self.active().find(|chord| chord.keys == keys) {
+            Some(chord) => vec![Event::Chord(chord.name.clone())],
+            None => keys.into_iter().map(Event::Key).collect(),
+        }
+    }
+
+    /// The sequences that apply in the current mode.
+    fn active(&self) -> impl Iterator<Item = &Chord> {
+        self.chords.iter().filter(|chord| chord.mode.as_ref().is_none_or(|mode| *mode == self.mode))
+    }
+}
+
+/// Registered sequences and the one in progress, owned by the `AppContext`.
+#[derive(Clone)]
+pub(crate) struct Chords {
+    state: Arc<Mutex<ChordState>>,
+}
+
+impl Default for Chords {
+    fn default() -> Self {
+        let state = ChordState {
+            chords: Vec::new(),
+            mode: InputMode::default(),
+            pending: Vec::new(),
+            timeout: DEFAULT_TIMEOUT,
+            generation: 0,
+        };
+        Self { state: Arc::new(Mutex::new(state)) }
+    }
+}
+
+impl Chords {
+    /// Deliver `name` when `keys` are pressed in order in `mode` (any mode if `None`),
+    /// replacing an earlier sequence with the same keys and mode.
+    pub(crate) fn register(&self, keys: Vec<KeyEvent>, name: String, mode: Option<InputMode>) {
+        if let Ok(mut state) = self.state.lock() {
+            state.chords.retain(|chord| chord.keys != keys || chord.mode != mode);
+            state.chords.push(Chord { keys, name, mode });
+        }
+    }
+
+    /// Switch modes, abandoning the sequence in progress. Returns false if `mode` is
+    /// already current.
+    pub(crate) fn set_mode(&self, mode: InputMode) -> bool {
+        let Ok(mut state) = self.state.lock() else {
+            return false;
+        };
+        if state.mode == mode {
+            return false;
+        }
+        state.mode = mode;
+        state.pending.clear();
+        true
+    }
+
+    pub(crate) fn mode(&self) -> InputMode {
+        self.state.lock().map(|state| state.mode.clone()).unwrap_or_default()
+    }
+
+    pub(crate) fn set_timeout(&self, timeout: Duration) {
+        if let Ok(mut state) = self.state.lock() {
+            state.timeout = timeout;
+        }
+    }
+
+    pub(crate) fn pending(&self) -> Vec<KeyEvent> {
+        self.state.lock().map(|state| state.pending.clone()).unwrap_or_default()
+    }
+
+    /// Follow an event. Returns the events to deliver in its place: none while a
+    /// sequence is in progress, `Event::Chord` when one completes, held keys when it
+    /// fails, and any other event unchanged.
+    pub(crate) fn feed(&self, event: Event, app: &AppContext) -> Vec<Event> {
+        let Ok(mut state) = self.state.lock() else {
+            return vec![event];
+        };
+        match &event {
+            Event::Key(key) if !state.chords.is_empty() => {
+                let (events, held) = state.press(*key);
+                if held {
+                    state.generation += 1;
+                    let (generation, timeout) = (state.generation, state.timeout);
+                    drop(state);
+                    app.set_timeout(timeout, move |app| {
+                        app.inject_event(Event::Custom(CustomEvent::new(TIMEOUT_EVENT, ChordTimeout(generation))));
+                    });
+                }
+                events
+            }
+            Event::Custom(custom) if custom.name() == TIMEOUT_EVENT => match custom.downcast_ref::<ChordTimeout>() {
+                Some(ChordTimeout(generation)) if *generation == state.generation => state.expire(),
+                _ => Vec::new(),
+            },
+            _ => vec![event],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn chords() -> (Chords, AppContext) {
+        let chords = Chords::default();
+        let space = KeyEvent::from(KeyCode::Char(' '));
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        chords.register(vec![space, key('f'), key('f')], "find-file".into(), None);
+        chords.register(vec![space, key('f')], "files".into(), None);
+        chords.register(vec![key('g'), key('d')], "goto-definition".into(), None);
+        (chords, AppContext::new().0)
+    }
+
+    fn feed(chords: &Chords, app: &AppContext, keys: &str) -> Vec<String> {
+        let mut delivered = Vec::new();
+        for c in keys.chars() {
+            for event in chords.feed(Event::Key(KeyEvent::from(KeyCode::Char(c))), app) {
+                delivered.push(match event {
+                    Event::Chord(name) => name,
+                    Event::Key(key) => key.code.to_string(),
+                    other => format!("{other:?}"),
+                });
+            }
+        }
+        delivered
+    }
+
+    #[test]
+    fn test_sequences_become_chords() {
+        let (chords, app) = chords();
+        assert_eq!(feed(&chords, &app, "gd"), ["goto-definition"]);
+        assert_eq!(feed(&chords, &app, " ff"), ["find-file"]);
+        assert_eq!(feed(&chords, &app, "x"), ["x"]);
+        // `g` cannot continue with `x`; `g` starts a new sequence
+        assert_eq!(feed(&chords, &app, "gxgg"), ["g", "x", "g"]);
+        assert_eq!(chords.pending().len(), 1);
+        assert_eq!(feed(&chords, &app, "d"), ["goto-definition"]);
+        assert!(feed(&chords, &app, " f").is_empty());
+        assert_eq!(chords.pending().len(), 2);
+    }
+
+    #[test]
+    fn test_timeout_completes_or_releases_keys() {
+        let (chords, app) = chords();
+        let timeout = |generation| Event::Custom(CustomEvent::new(TIMEOUT_EVENT, ChordTimeout(generation)));
+
+        feed(&chords, &app, " f");
+        assert!(chords.feed(timeout(1), &app).is_empty(), "stale timeouts are ignored");
+        assert!(matches!(&chords.feed(timeout(2), &app)[..], [Event::Chord(name)] if name == "files"));
+
+        feed(&chords, &app, "g");
+        assert!(matches!(&chords.feed(timeout(3), &app)[..], [Event::Key(key)] if key.code == KeyCode::Char('g')));
+        feed(&chords, &app, "g");
+        assert!(chords.feed(Event::Key(KeyEvent::from(KeyCode::Esc)), &app).is_empty());
+        assert!(chords.pending().is_empty());
+    }
+
+    #[test]
+    fn test_mode_scoped_sequences() {
+        let (chords, app) = chords();
+        let key = |c| KeyEvent::from(KeyCode::Char(c));
+        chords.register(vec![key('d'), key('d')], "delete-line".into(), Some(InputMode::Normal));
+        chords.register(vec![key('i')], "insert-mode".into(), Some(InputMode::Normal));
+        chords.register(vec![KeyEvent::from(KeyCode::Esc)], "normal-mode".into(), Some(InputMode::Insert));
+
+        assert_eq!(feed(&chords, &app, "ddi"), ["delete-line", "insert-mode"]);
+        feed(&chords, &app, "d");
+        assert!(chords.set_mode(InputMode::Insert));
+        assert!(chords.pending().is_empty());
+        assert_eq!(feed(&chords, &app, "ddi"), ["d", "d", "i"]);
+        // Sequences for all modes still apply
+        assert_eq!(feed(&chords, &app, "gd"), ["goto-definition"]);
+        let esc = chords.feed(Event::Key(KeyEvent::from(KeyCode::Esc)), &app);
+        assert!(matches!(&esc[..], [Event::Chord(name)] if name == "normal-mode"));
+        assert_eq!(chords.mode(), InputMode::Insert);
+        assert!(!chords.set_mode(InputMode::Insert));
+    }
+}
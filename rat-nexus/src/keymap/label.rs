@@ -0,0 +1,51 @@
+//! Human-readable key labels.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Format a key event the way it is written in help texts, e.g. `Ctrl+S`, `Alt+←`, `Shift+Tab`.
+/// Shifted characters are shown as the character itself (`A`, not `Shift+A`).
+pub fn key_label(key: &KeyEvent) -> String {
+    let mut label = String::new();
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        label.push_str("Ctrl+");
+    }
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        label.push_str("Alt+");
+    }
+    if key.modifiers.contains(KeyModifiers::SUPER) {
+        label.push_str("Super+");
+    }
+    let shift_shown = matches!(key.code, KeyCode::Char(_) | KeyCode::BackTab);
+    if key.modifiers.contains(KeyModifiers::SHIFT) && !shift_shown {
+        label.push_str("Shift+");
+    }
+    match key.code {
+        KeyCode::Char(' ') => label.push_str("Space"),
+        KeyCode::Char(c) => label.push(c),
+        KeyCode::BackTab => label.push_str("Shift+Tab"),
+        KeyCode::Left => label.push('←'),
+        KeyCode::Right => label.push('→'),
+        KeyCode::Up => label.push('↑'),
+        KeyCode::Down => label.push('↓'),
+        KeyCode::F(n) => label.push_str(&format!("F{n}")),
+        KeyCode::Esc => label.push_str("Esc"),
+        KeyCode::PageUp => label.push_str("PgUp"),
+        KeyCode::PageDown => label.push_str("PgDn"),
+        other => label.push_str(&format!("{other:?}")),
+    }
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_labels() {
+        assert_eq!(key_label(&KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)), "Ctrl+s");
+        assert_eq!(key_label(&KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT)), "A");
+        assert_eq!(key_label(&KeyEvent::new(KeyCode::Left, KeyModifiers::ALT)), "Alt+←");
+        assert_eq!(key_label(&KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT)), "Shift+Tab");
+        assert_eq!(key_label(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)), "Enter");
+    }
+}
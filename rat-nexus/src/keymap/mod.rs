@@ -0,0 +1,15 @@
+//! Keyboard handling utilities.
+//!
+//! Provides `KeyNormalizer`, which maps the inconsistent encodings terminals use for
+//! modifier combinations onto canonical `KeyEvent`s before components see them,
+//! `key_label` for displaying keys, recognition of multi-key sequences (`chord`) and
+//! the input modes they can be scoped to (`mode`).
+
+pub mod chord;
+pub mod label;
+pub mod mode;
+pub mod normalize;
+
+pub use label::key_label;
+pub use mode::InputMode;
+pub use normalize::{KeyNormalizer, TerminalProfile};
@@ -0,0 +1,55 @@
+//! Input modes for modal (vim-style) apps.
+//!
+//! The current mode is app-wide: `AppContext::set_mode` switches it and
+//! `AppContext::mode` reads it, e.g. for a mode indicator in the status bar. Key
+//! sequences registered with `AppContext::register_chord_in` only apply in their mode.
+//!
+//! # Example
+//! ```ignore
+//! app.register_chord_in(InputMode::Normal, "i", "insert")?;
+//! app.register_chord_in(InputMode::Normal, "dd", "delete-line")?;
+//! app.register_chord_in(InputMode::Insert, "<Esc>", "normal")?;
+//!
+//! // In the editor:
+//! match event {
+//!     Event::Chord(name) if name == "insert" => cx.set_mode(InputMode::Insert),
+//!     Event::Chord(name) if name == "normal" => cx.set_mode(InputMode::Normal),
+//!     Event::Key(key) if cx.mode() == InputMode::Insert => self.buffer.insert_key(key),
+//!     _ => {}
+//! }
+//! ```
+
+use std::fmt;
+
+/// An input mode. Apps that are not modal stay in `Normal`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum InputMode {
+    /// Keys are commands.
+    #[default]
+    Normal,
+    /// Keys are text.
+    Insert,
+    /// Keys extend a selection.
+    Visual,
+    /// An app-defined mode, e.g. `"command"` or `"replace"`.
+    Custom(String),
+}
+
+impl InputMode {
+    /// An app-defined mode.
+    pub fn custom(name: impl Into<String>) -> Self {
+        Self::Custom(name.into())
+    }
+}
+
+/// Upper-case names for mode indicators: `NORMAL`, `INSERT`, `VISUAL`, `COMMAND`.
+impl fmt::Display for InputMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputMode::Normal => f.write_str("NORMAL"),
+            InputMode::Insert => f.write_str("INSERT"),
+            InputMode::Visual => f.write_str("VISUAL"),
+            InputMode::Custom(name) => f.write_str(&name.to_uppercase()),
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Key event normalization across terminal emulators.
+//!
+//! Terminals disagree on how modifier combinations are encoded: Shift+Tab may arrive
+//! as `BackTab` with or without `SHIFT`, Ctrl+Space as `Null`, uppercase letters with
+//! or without `SHIFT`, and some emulators send Alt+arrows as Alt+b / Alt+f word motions.
+//! `KeyNormalizer` rewrites these into one canonical form so keymaps behave identically
+//! everywhere:
+//!
+//! - Shift+Tab is `BackTab` + `SHIFT`
+//! - Ctrl+Space is `Char(' ')` + `CONTROL`
+//! - Uppercase letters always carry `SHIFT`; lowercase letters never do
+//! - `Char('\t')` is `Tab`, `Char('\r')` / `Char('\n')` without modifiers are `Enter`
+//!
+//! On top of these, apps can register their own remaps, and opt into a per-terminal
+//! compatibility table (see `TerminalProfile::remaps`). That table is off by default
+//! because it is lossy: under tmux it turns every Ctrl+J into Ctrl+Enter, so apps
+//! that bind the literal keys would never see them.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Terminal emulator family, detected from the environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalProfile {
+    /// No specific quirks known.
+    Generic,
+    /// macOS Terminal.app: Alt+Left/Right are sent as Alt+b / Alt+f.
+    AppleTerminal,
+    /// tmux: Ctrl+Enter is indistinguishable and arrives as Ctrl+j (line feed).
+    Tmux,
+    /// Windows console: Ctrl+Enter arrives as Ctrl+j, Ctrl+Backspace as Ctrl+h.
+    WindowsConsole,
+    /// Terminals implementing the kitty keyboard protocol report keys faithfully.
+    Kitty,
+}
+
+impl TerminalProfile {
+    /// Detect the terminal from environment variables.
+    pub fn detect() -> Self {
+        let env = |name: &str| std::env::var(name).unwrap_or_default();
+        if cfg!(windows) && std::env::var_os("WT_SESSION").is_none() {
+            return Self::WindowsConsole;
+        }
+        if !env("TMUX").is_empty() {
+            return Self::Tmux;
+        }
+        match env("TERM_PROGRAM").as_str() {
+            "Apple_Terminal" => return Self::AppleTerminal,
+            "WezTerm" | "ghostty" => return Self::Kitty,
+            _ => {}
+        }
+        if env("TERM") == "xterm-kitty" {
+            return Self::Kitty;
+        }
+        Self::Generic
+    }
+
+    /// The compatibility remaps for this terminal, as `(received, canonical)` pairs.
+    /// Each one makes the received key unreachable, so they only apply after
+    /// `KeyNormalizer::enable_profile_remaps`.
+    pub fn remaps(&self) -> Vec<((KeyCode, KeyModifiers), (KeyCode, KeyModifiers))> {
+        match self {
+            Self::AppleTerminal => vec![
+                ((KeyCode::Char('b'), KeyModifiers::ALT), (KeyCode::Left, KeyModifiers::ALT)),
+                ((KeyCode::Char('f'), KeyModifiers::ALT), (KeyCode::Right, KeyModifiers::ALT)),
+            ],
+            Self::Tmux => vec![
+                ((KeyCode::Char('j'), KeyModifiers::CONTROL), (KeyCode::Enter, KeyModifiers::CONTROL)),
+            ],
+            Self::WindowsConsole => vec![
+                ((KeyCode::Char('j'), KeyModifiers::CONTROL), (KeyCode::Enter, KeyModifiers::CONTROL)),
+                ((KeyCode::Char('h'), KeyModifiers::CONTROL), (KeyCode::Backspace, KeyModifiers::CONTROL)),
+            ],
+            Self::Generic | Self::Kitty => Vec::new(),
+        }
+    }
+}
+
+/// Rewrites raw key events into their canonical form.
+#[derive(Debug, Clone)]
+pub struct KeyNormalizer {
+    profile: TerminalProfile,
+    remaps: Vec<((KeyCode, KeyModifiers), (KeyCode, KeyModifiers))>,
+}
+
+impl Default for KeyNormalizer {
+    fn default() -> Self {
+        Self::new(TerminalProfile::Generic)
+    }
+}
+
+impl KeyNormalizer {
+    /// Create a normalizer for the given terminal. Only the canonical forms apply; see
+    /// `enable_profile_remaps` for the terminal's compatibility table.
+    pub fn new(profile: TerminalProfile) -> Self {
+        Self { profile, remaps: Vec::new() }
+    }
+
+    /// Create a normalizer for the terminal detected from the environment.
+    pub fn detect() -> Self {
+        Self::new(TerminalProfile::detect())
+    }
+
+    /// The terminal profile in use.
+    pub fn profile(&self) -> TerminalProfile {
+        self.profile
+    }
+
+    /// Apply the terminal's compatibility remaps (see `TerminalProfile::remaps`), e.g.
+    /// Ctrl+J as Ctrl+Enter under tmux. Custom remaps of the same keys take precedence.
+    pub fn enable_profile_remaps(&mut self) {
+        for (from, to) in self.profile.remaps() {
+            if !self.remaps.iter().any(|(existing, _)| *existing == from) {
+                self.remaps.push((from, to));
+            }
+        }
+    }
+
+    /// Add a custom remap applied after the built-in canonicalization.
+    pub fn remap(&mut self, from: (KeyCode, KeyModifiers), to: (KeyCode, KeyModifiers)) {
+        self.remaps.retain(|(existing, _)| *existing != from);
+        self.remaps.push((from, to));
+    }
+
+    /// Normalize a key event.
+    pub fn normalize(&self, mut key: KeyEvent) -> KeyEvent {
+        match key.code {
+            KeyCode::BackTab => key.modifiers |= KeyModifiers::SHIFT,
+            KeyCode::Tab if key.modifiers.contains(KeyModifiers::SHIFT) => key.code = KeyCode::BackTab,
+            KeyCode::Null => {
+                key.code = KeyCode::Char(' ');
+                key.modifiers |= KeyModifiers::CONTROL;
+            }
+            KeyCode::Char('\t') => key.code = KeyCode::Tab,
+            KeyCode::Char('\r') | KeyCode::Char('\n') if key.modifiers.is_empty() => key.code = KeyCode::Enter,
+            KeyCode::Char(c) if c.is_uppercase() => key.modifiers |= KeyModifiers::SHIFT,
+            KeyCode::Char(c) if c.is_lowercase() && key.modifiers.contains(KeyModifiers::SHIFT) => {
+                // Letters whose uppercase is several chars ('ß' -> "SS") keep their case
+                if let Some(upper) = single_char(c.to_uppercase()) {
+                    key.code = KeyCode::Char(upper);
+                }
+            }
+            _ => {}
+        }
+
+        if let Some((_, (code, modifiers))) = self.remaps.iter().find(|(from, _)| *from == (key.code, key.modifiers)) {
+            key.code = *code;
+            key.modifiers = *modifiers;
+        }
+        key
+    }
+}
+
+/// The only char of `chars`, if it has exactly one.
+fn single_char(mut chars: impl Iterator<Item = char>) -> Option<char> {
+    let first = chars.next()?;
+    chars.next().is_none().then_some(first)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_canonical_forms() {
+        let n = KeyNormalizer::default();
+        let canonical = |k: KeyEvent| {
+            let k = n.normalize(k);
+            (k.code, k.modifiers)
+        };
+        assert_eq!(canonical(key(KeyCode::BackTab, KeyModifiers::NONE)), (KeyCode::BackTab, KeyModifiers::SHIFT));
+        assert_eq!(canonical(key(KeyCode::Tab, KeyModifiers::SHIFT)), (KeyCode::BackTab, KeyModifiers::SHIFT));
+        assert_eq!(canonical(key(KeyCode::Null, KeyModifiers::NONE)), (KeyCode::Char(' '), KeyModifiers::CONTROL));
+        assert_eq!(canonical(key(KeyCode::Char('A'), KeyModifiers::NONE)), (KeyCode::Char('A'), KeyModifiers::SHIFT));
+        assert_eq!(canonical(key(KeyCode::Char('a'), KeyModifiers::SHIFT)), (KeyCode::Char('A'), KeyModifiers::SHIFT));
+        assert_eq!(canonical(key(KeyCode::Char('\r'), KeyModifiers::NONE)), (KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(canonical(key(KeyCode::Char('É'), KeyModifiers::NONE)), (KeyCode::Char('É'), KeyModifiers::SHIFT));
+        assert_eq!(canonical(key(KeyCode::Char('ж'), KeyModifiers::SHIFT)), (KeyCode::Char('Ж'), KeyModifiers::SHIFT));
+        assert_eq!(canonical(key(KeyCode::Char('ß'), KeyModifiers::SHIFT)), (KeyCode::Char('ß'), KeyModifiers::SHIFT));
+    }
+
+    #[test]
+    fn test_profile_remaps() {
+        let mut n = KeyNormalizer::new(TerminalProfile::AppleTerminal);
+        let k = n.normalize(key(KeyCode::Char('b'), KeyModifiers::ALT));
+        assert_eq!((k.code, k.modifiers), (KeyCode::Char('b'), KeyModifiers::ALT), "remaps are opt-in");
+        n.enable_profile_remaps();
+        let k = n.normalize(key(KeyCode::Char('b'), KeyModifiers::ALT));
+        assert_eq!((k.code, k.modifiers), (KeyCode::Left, KeyModifiers::ALT));
+
+        let mut n = KeyNormalizer::new(TerminalProfile::Tmux);
+        n.enable_profile_remaps();
+        n.remap((KeyCode::F(13), KeyModifiers::NONE), (KeyCode::F(1), KeyModifiers::SHIFT));
+        let k = n.normalize(key(KeyCode::Char('j'), KeyModifiers::CONTROL));
+        assert_eq!((k.code, k.modifiers), (KeyCode::Enter, KeyModifiers::CONTROL));
+        let k = n.normalize(key(KeyCode::F(13), KeyModifiers::NONE));
+        assert_eq!((k.code, k.modifiers), (KeyCode::F(1), KeyModifiers::SHIFT));
+    }
+}
@@ -0,0 +1,436 @@
+//! Width-aware text and layout helpers.
+//!
+//! Terminal cells are not characters: CJK ideographs and most emoji take two cells,
+//! combining marks and zero-width joiners take none, and an emoji such as 👩‍💻 is several
+//! chars forming one grapheme. Counting `chars()` (or bytes) misaligns columns and cuts
+//! graphemes in half. The text helpers here measure display width and only split between
+//! graphemes:
+//!
+//! - `display_width` measures a string
+//! - `truncate` and `truncate_with_ellipsis` cut it to a width
+//! - `wrap` breaks it into lines of at most a width
+//! - `pad` fills it up to a width, aligned left, right or centered
+//!
+//! The layout helpers cover the rest of the usual popup math: `centered` and
+//! `centered_percent` place a block in a `Rect`, and `percent` sizes a constraint as a
+//! share of the available space within bounds.
+//!
+//! For splitting a screen into panels, `row!` and `column!` describe the split next to
+//! what goes in each part, like CSS flexbox:
+//!
+//! ```ignore
+//! fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+//!     row![
+//!         fixed(30, &mut self.sidebar),
+//!         flex(1, column![flex(1, &mut self.editor), fixed(1, &mut self.status)]),
+//!     ]
+//!     .render(frame, frame.area(), cx);
+//! }
+//! ```
+//!
+//! Children are `ChildView`s (or nested rows and columns), mounted as with
+//! `cx.mount_child`, so they record their areas and receive only the mouse events that hit
+//! them. `()` leaves a part empty; `Flex::areas` only computes the rects.
+
+use std::borrow::Cow;
+
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::Frame;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::component::traits::AnyComponent;
+use crate::component::{ChildView, Component};
+use crate::Context;
+
+/// The number of cells `text` takes on screen.
+pub fn display_width(text: &str) -> usize {
+    text.graphemes(true).map(grapheme_width).sum()
+}
+
+/// `text` cut to at most `width` cells, at a grapheme boundary. A wide grapheme that
+/// would straddle the limit is dropped, so the result may be one cell short.
+pub fn truncate(text: &str, width: usize) -> &str {
+    &text[..fit(text, width)]
+}
+
+/// `text` cut to at most `width` cells with a trailing `…` when anything was cut.
+pub fn truncate_with_ellipsis(text: &str, width: usize) -> Cow<'_, str> {
+    if display_width(text) <= width {
+        return Cow::Borrowed(text);
+    }
+    if width == 0 {
+        return Cow::Borrowed("");
+    }
+    let end = fit(text, width - 1);
+    Cow::Owned(format!("{}…", text[..end].trim_end()))
+}
+
+/// Break `text` into lines of at most `width` cells. Lines break at whitespace where
+/// possible and between graphemes otherwise (words longer than a line, and scripts
+/// written without spaces such as Chinese). Existing line breaks are kept.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut line_width = 0;
+        for word in paragraph.split_word_bounds() {
+            let word_width = display_width(word);
+            if word.trim().is_empty() {
+                // Whitespace: kept inside a line, dropped at a break
+                if line_width + word_width <= width {
+                    line.push_str(word);
+                    line_width += word_width;
+                } else {
+                    lines.push(std::mem::take(&mut line).trim_end().to_string());
+                    line_width = 0;
+                }
+                continue;
+            }
+            // Move a word to the next line if it fits there; split it otherwise
+            if line_width + word_width > width && line_width > 0 && word_width <= width {
+                lines.push(std::mem::take(&mut line).trim_end().to_string());
+                line_width = 0;
+            }
+            for grapheme in word.graphemes(true) {
+                let grapheme_width = grapheme_width(grapheme);
+                if line_width + grapheme_width > width && line_width > 0 {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0;
+                }
+                line.push_str(grapheme);
+                line_width += grapheme_width;
+            }
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines
+}
+
+/// `text` truncated to `width` cells and padded with spaces to exactly `width`.
+pub fn pad(text: &str, width: usize, alignment: Alignment) -> String {
+    let text = truncate(text, width);
+    let gap = width - display_width(text);
+    let (left, right) = match alignment {
+        Alignment::Left => (0, gap),
+        Alignment::Right => (gap, 0),
+        Alignment::Center => (gap / 2, gap - gap / 2),
+    };
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
+
+/// Center a `width` x `height` rect inside `area`, shrinking it if needed.
+pub fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// Center a rect taking `width_percent` and `height_percent` of `area`.
+pub fn centered_percent(area: Rect, width_percent: u16, height_percent: u16) -> Rect {
+    let share = |total: u16, percent: u16| (u32::from(total) * u32::from(percent.min(100)) / 100) as u16;
+    centered(area, share(area.width, width_percent), share(area.height, height_percent))
+}
+
+/// A share of the available space, kept within bounds (see `percent`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Percent {
+    percent: u16,
+    min: u16,
+    max: u16,
+}
+
+/// `percent` of the available space, e.g. `percent(30).at_least(20).at_most(60)` for a
+/// sidebar that takes 30% but stays readable on narrow terminals and does not sprawl on
+/// wide ones.
+pub fn percent(percent: u16) -> Percent {
+    Percent { percent: percent.min(100), min: 0, max: u16::MAX }
+}
+
+impl Percent {
+    /// Never fewer than `cells`, unless the space itself is smaller.
+    pub fn at_least(mut self, cells: u16) -> Self {
+        self.min = cells;
+        self
+    }
+
+    /// Never more than `cells`.
+    pub fn at_most(mut self, cells: u16) -> Self {
+        self.max = cells;
+        self
+    }
+
+    /// The size in cells out of `total`.
+    pub fn resolve(&self, total: u16) -> u16 {
+        let share = (u32::from(total) * u32::from(self.percent) / 100) as u16;
+        share.min(self.max).max(self.min).min(total)
+    }
+
+    /// A fixed-length constraint for splitting `total` cells.
+    pub fn constraint(&self, total: u16) -> Constraint {
+        Constraint::Length(self.resolve(total))
+    }
+}
+
+/// How much space an item of a `row!` or `column!` takes along the split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Size {
+    /// Exactly this many cells.
+    Fixed(u16),
+    /// A share of the space the other items leave, weighted against other flex items.
+    Flex(u16),
+    /// A bounded share of the whole space (see `percent`).
+    Percent(Percent),
+}
+
+impl Size {
+    fn constraint(&self, total: u16) -> Constraint {
+        match self {
+            Size::Fixed(cells) => Constraint::Length(*cells),
+            Size::Flex(weight) => Constraint::Fill(*weight),
+            Size::Percent(percent) => percent.constraint(total),
+        }
+    }
+}
+
+/// Something rendered into the area of a `row!` or `column!` item.
+pub trait Mount {
+    fn mount(&mut self, frame: &mut Frame, area: Rect, cx: &Context<dyn AnyComponent>);
+}
+
+impl<T: Component> Mount for ChildView<T> {
+    fn mount(&mut self, frame: &mut Frame, area: Rect, cx: &Context<dyn AnyComponent>) {
+        cx.mount_child(self, frame, area);
+    }
+}
+
+impl Mount for Flex<'_> {
+    fn mount(&mut self, frame: &mut Frame, area: Rect, cx: &Context<dyn AnyComponent>) {
+        self.render(frame, area, cx);
+    }
+}
+
+impl<M: Mount + ?Sized> Mount for &mut M {
+    fn mount(&mut self, frame: &mut Frame, area: Rect, cx: &Context<dyn AnyComponent>) {
+        (**self).mount(frame, area, cx);
+    }
+}
+
+/// An empty part.
+impl Mount for () {
+    fn mount(&mut self, _frame: &mut Frame, _area: Rect, _cx: &Context<dyn AnyComponent>) {}
+}
+
+/// One part of a `row!` or `column!`: its size and what goes in it.
+pub struct Item<'a> {
+    size: Size,
+    content: Box<dyn Mount + 'a>,
+    name: Option<String>,
+}
+
+impl<'a> Item<'a> {
+    pub fn new(size: Size, content: impl Mount + 'a) -> Self {
+        Self { size, content: Box::new(content), name: None }
+    }
+
+    /// Also record the area under `name` when rendering (see `Context::name_area`).
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+}
+
+/// An item taking a `weight` share of the space left by fixed and percentage items.
+pub fn flex<'a>(weight: u16, content: impl Mount + 'a) -> Item<'a> {
+    Item::new(Size::Flex(weight), content)
+}
+
+/// An item exactly `cells` wide in a row, or high in a column.
+pub fn fixed<'a>(cells: u16, content: impl Mount + 'a) -> Item<'a> {
+    Item::new(Size::Fixed(cells), content)
+}
+
+/// An item taking a bounded share of the space, e.g. `share(percent(30).at_least(20), tree)`.
+pub fn share<'a>(percent: Percent, content: impl Mount + 'a) -> Item<'a> {
+    Item::new(Size::Percent(percent), content)
+}
+
+/// Items laid out side by side or stacked, usually built with `row!` or `column!`.
+pub struct Flex<'a> {
+    direction: Direction,
+    items: Vec<Item<'a>>,
+    spacing: u16,
+}
+
+impl<'a> Flex<'a> {
+    /// Items side by side, left to right.
+    pub fn row(items: Vec<Item<'a>>) -> Self {
+        Self { direction: Direction::Horizontal, items, spacing: 0 }
+    }
+
+    /// Items stacked, top to bottom.
+    pub fn column(items: Vec<Item<'a>>) -> Self {
+        Self { direction: Direction::Vertical, items, spacing: 0 }
+    }
+
+    /// Leave `cells` empty between neighbouring items.
+    pub fn spacing(mut self, cells: u16) -> Self {
+        self.spacing = cells;
+        self
+    }
+
+    /// The area of each item within `area`, in order.
+    pub fn areas(&self, area: Rect) -> Vec<Rect> {
+        let total = match self.direction {
+            Direction::Horizontal => area.width,
+            Direction::Vertical => area.height,
+        };
+        Layout::default()
+            .direction(self.direction)
+            .spacing(self.spacing)
+            .constraints(self.items.iter().map(|item| item.size.constraint(total)))
+            .split(area)
+            .to_vec()
+    }
+
+    /// Render each item into its area and return the areas, in order.
+    pub fn render<P: ?Sized + Send + Sync + 'static>(&mut self, frame: &mut Frame, area: Rect, cx: &Context<P>) -> Vec<Rect> {
+        let areas = self.areas(area);
+        let cx = cx.cast::<dyn AnyComponent>();
+        for (item, area) in self.items.iter_mut().zip(&areas) {
+            if let Some(name) = &item.name {
+                cx.name_area(name.clone(), *area);
+            }
+            item.content.mount(frame, *area, &cx);
+        }
+        areas
+    }
+}
+
+/// Lay out items side by side: `row![fixed(30, &mut self.sidebar), flex(1, &mut self.main)]`.
+#[macro_export]
+macro_rules! row {
+    ($($item:expr),* $(,)?) => {
+        $crate::layout::Flex::row(vec![$($item),*])
+    };
+}
+
+/// Stack items top to bottom: `column![flex(1, &mut self.list), fixed(1, &mut self.status)]`.
+#[macro_export]
+macro_rules! column {
+    ($($item:expr),* $(,)?) => {
+        $crate::layout::Flex::column(vec![$($item),*])
+    };
+}
+
+/// Display width of one grapheme. Emoji sequences are as wide as their first char.
+fn grapheme_width(grapheme: &str) -> usize {
+    match grapheme.chars().next() {
+        Some(c) if grapheme.len() > c.len_utf8() && grapheme.contains('\u{200d}') => {
+            unicode_width::UnicodeWidthChar::width(c).unwrap_or(0)
+        }
+        _ => grapheme.width(),
+    }
+}
+
+/// Byte length of the longest prefix of `text` that fits in `width` cells.
+fn fit(text: &str, width: usize) -> usize {
+    let mut used = 0;
+    for (start, grapheme) in text.grapheme_indices(true) {
+        used += grapheme_width(grapheme);
+        if used > width {
+            return start;
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_width_and_truncation() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(display_width("👩‍💻"), 2);
+        assert_eq!(truncate("日本語", 5), "日本");
+        assert_eq!(truncate("👩‍💻x", 1), "");
+        assert_eq!(truncate_with_ellipsis("hello world", 8), "hello w…");
+        assert_eq!(truncate_with_ellipsis("hello world", 7), "hello…");
+        assert_eq!(truncate_with_ellipsis("日本語テキスト", 7), "日本語…");
+        assert_eq!(truncate_with_ellipsis("short", 5), "short");
+        assert_eq!(pad("日本", 7, Alignment::Center), " 日本  ");
+        assert_eq!(pad("abc", 2, Alignment::Right), "ab");
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("the quick brown fox", 10), ["the quick", "brown fox"]);
+        assert_eq!(wrap("日本語のテキスト", 6), ["日本語", "のテキ", "スト"]);
+        assert_eq!(wrap("abcdefgh ij", 4), ["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("one\n\ntwo", 10), ["one", "", "two"]);
+    }
+
+    #[test]
+    fn test_centering_and_percentages() {
+        let area = Rect::new(10, 5, 80, 20);
+        assert_eq!(centered(area, 40, 10), Rect::new(30, 10, 40, 10));
+        assert_eq!(centered_percent(area, 50, 50), Rect::new(30, 10, 40, 10));
+        let sidebar = percent(30).at_least(20).at_most(40);
+        assert_eq!(sidebar.resolve(200), 40);
+        assert_eq!(sidebar.resolve(100), 30);
+        assert_eq!(sidebar.resolve(50), 20);
+        assert_eq!(sidebar.resolve(10), 10);
+        assert_eq!(sidebar.constraint(100), Constraint::Length(30));
+    }
+
+    #[derive(Default)]
+    struct Panel;
+
+    impl Component for Panel {
+        fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+            let area = cx.area(frame);
+            frame.buffer_mut()[(area.x, area.y)].set_symbol("#");
+        }
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let areas = crate::row![fixed(30, ()), flex(1, ()), flex(2, ())].areas(Rect::new(0, 0, 120, 10));
+        assert_eq!(areas.iter().map(|area| (area.x, area.width)).collect::<Vec<_>>(), [(0, 30), (30, 30), (60, 60)]);
+        let areas = crate::column![share(percent(50).at_most(4), ()), flex(1, ())].spacing(1).areas(Rect::new(0, 0, 10, 20));
+        assert_eq!(areas, [Rect::new(0, 0, 10, 4), Rect::new(0, 5, 10, 15)]);
+
+        let (app, _refresh, _inject) = crate::AppContext::new();
+        let cx = Context::<Panel>::new(app.clone(), crate::Entity::new(Panel).downgrade());
+        let (mut sidebar, mut editor, mut status) = (ChildView::new(Panel), ChildView::new(Panel), ChildView::new(Panel));
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(40, 10)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                crate::row![
+                    fixed(10, &mut sidebar).named("sidebar"),
+                    flex(1, crate::column![flex(1, &mut editor), fixed(1, &mut status)]),
+                ]
+                .render(frame, area, &cx);
+            })
+            .unwrap();
+        assert_eq!(sidebar.area(), Some(Rect::new(0, 0, 10, 10)));
+        assert_eq!(editor.area(), Some(Rect::new(10, 0, 30, 9)));
+        assert!(status.contains(12, 9));
+        assert_eq!(app.region("sidebar"), Some(Rect::new(0, 0, 10, 10)));
+        assert_eq!(terminal.backend().buffer()[(10, 9)].symbol(), "#");
+    }
+}
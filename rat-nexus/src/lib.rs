@@ -3,16 +3,96 @@ pub mod component;
 pub mod state;
 pub mod router;
 pub mod task;
+pub mod timer;
+pub mod worker;
 pub mod error;
+pub mod metrics;
+#[cfg(feature = "persist")]
+pub mod persist;
+#[cfg(feature = "config")]
+pub mod config;
+pub mod cli;
+pub mod inject;
+pub mod soak;
+pub mod backend;
+pub mod capabilities;
+pub mod graphics;
+pub mod keymap;
+pub mod logs;
+pub mod storm;
+pub mod budget;
+pub mod density;
+pub mod theme;
+pub mod presentation;
+pub mod contrast;
+pub mod palette;
+pub mod context_menu;
+pub mod widgets;
+pub mod layout;
+pub mod form;
+pub mod event_source;
+pub mod recording;
+pub mod interop;
+pub mod session;
+#[cfg(feature = "ssh")]
+pub mod ssh;
+#[cfg(feature = "web")]
+pub mod web;
+pub mod dispatch;
+pub mod shell;
+pub mod testing;
+mod subscription;
+mod overlay;
+mod switcher;
+mod splash;
+mod inspector;
+mod damage;
+mod drag;
+mod click;
 
 pub use error::{Error, Result};
 
 // Re-export common types for convenience
-pub use application::{Application, AppContext, Context, EventContext};
-pub use component::{Component, traits::{Event, Action, AnyComponent}};
-pub use state::{Entity, WeakEntity, EntityId};
-pub use router::{Route, Router};
-pub use task::{TaskHandle, TaskTracker};
+pub use application::{AppLoop, Application, AppContext, Context, EventContext, Step};
+pub use component::{Component, ChildView, ComponentError, MountTask, Styled, TryComponent, traits::{Event, AppEvent, CustomEvent, DropEvent, Action, CustomAction, AnyComponent}};
+pub use state::{push_capped, Capped, Derived, Entity, Observable, WeakEntity, EntityId, History, Loadable, SizeOf};
+pub use router::{NavigationEvent, NavigationTrigger, PageStatus, Route, RouteInfo, Router, Slot, StatusView, ViewState, ViewStateStore};
+pub use task::{CancelToken, Progress, ProgressState, TaskHandle, TaskTracker};
+pub use timer::TimerHandle;
+pub use worker::{Responder, Worker, WorkerHandle};
+pub use metrics::{Metrics, MetricsSnapshot};
+#[cfg(feature = "config")]
+pub use config::{Config, ConfigLoader, ConfigMap};
+pub use cli::CliArgs;
+pub use inject::FromContext;
+pub use backend::{BackendKind, TerminalBackend};
+pub use capabilities::{Capabilities, ColorDepth, Platform};
+pub use graphics::{GraphicsProtocol, Pixels};
+pub use keymap::{key_label, InputMode, KeyNormalizer, TerminalProfile};
+pub use logs::{CapturedLogs, LogPanel, LogRecord};
+pub use storm::{StormDetector, StormReport};
+pub use budget::{BudgetWarning, FrameBudget};
+pub use contrast::{ContrastAudit, ContrastIssue};
+pub use palette::{CommandPalette, PaletteItem};
+pub use context_menu::{ContextMenu, MenuItem, MenuPick};
+pub use layout::{Flex, Mount};
+pub use density::Density;
+pub use theme::Theme;
+pub use form::{Field, Form};
+pub use event_source::{ChannelEvents, CrosstermEvents, EventSource, Polled};
+pub use recording::{Recorder, Replay};
+pub use interop::{Bridge, RatatuiScreen};
+pub use session::{Session, SessionHandle};
+#[cfg(feature = "ssh")]
+pub use ssh::{Credentials, SshClient, SshServer};
+#[cfg(feature = "web")]
+pub use web::{WebClient, WebServer};
+pub use shell::AppShell;
 
 // Re-export paste for macro usage
 pub use paste;
+
+// Re-export ratatui so derive-generated code can name its types
+pub use ratatui;
+
+pub use rat_nexus_macros::{SizeOf, Theme, ViewState};
@@ -0,0 +1,468 @@
+//! Captured log output and the panel showing it.
+//!
+//! Writing to stderr while the app owns the alternate screen garbles the display.
+//! `Application::capture_logs` keeps records in a bounded, entity-backed buffer of the
+//! application's own instead (`AppContext::captured_logs`), and enables an overlay
+//! listing them, toggled with `F12` (see `AppContext::set_log_panel_key`). The
+//! `LogPanel` component shows the same records inside a page or chrome slot.
+//!
+//! `log` takes one logger per process: the one `logs::capture` installs hands every
+//! record to all live capture buffers, so applications running side by side each see
+//! the whole process's records, filtered at their own level. With the `tracing`
+//! feature, `logs::layer` is a `tracing-subscriber` layer feeding events to the same
+//! buffers, so they show in the overlay next to `log` records. Plain `eprintln!`
+//! output is not captured.
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::{Level, LevelFilter};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget};
+
+use crate::component::traits::{Component, Event};
+use crate::{Context, Entity, WeakEntity};
+
+/// Records kept by `Application::capture_logs`.
+pub const DEFAULT_CAPACITY: usize = 1_000;
+
+/// One captured log record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    /// Severity.
+    pub level: Level,
+    /// Module or target the record came from.
+    pub target: String,
+    /// The formatted message.
+    pub message: String,
+    /// When it was logged.
+    pub at: SystemTime,
+}
+
+/// Bounded buffer of captured records; the oldest are dropped once full.
+#[derive(Debug, Clone)]
+pub struct CapturedLogs {
+    records: VecDeque<LogRecord>,
+    capacity: usize,
+    dropped: u64,
+}
+
+impl Default for CapturedLogs {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl CapturedLogs {
+    /// Create a buffer keeping at most `capacity` records.
+    pub fn new(capacity: usize) -> Self {
+        Self { records: VecDeque::new(), capacity: capacity.max(1), dropped: 0 }
+    }
+
+    /// Append a record, dropping the oldest one if the buffer is full.
+    pub fn push(&mut self, record: LogRecord) {
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+            self.dropped += 1;
+        }
+        self.records.push_back(record);
+    }
+
+    /// Iterate over the records, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &LogRecord> {
+        self.records.iter()
+    }
+
+    /// Number of buffered records.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Whether no record is buffered.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Records dropped to stay within capacity.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Remove all records.
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+}
+
+/// A capture buffer and the least severe level it keeps.
+struct Sink {
+    logs: WeakEntity<CapturedLogs>,
+    level: LevelFilter,
+}
+
+/// The buffers records are stored in; those of ended applications are dropped.
+static SINKS: Mutex<Vec<Sink>> = Mutex::new(Vec::new());
+static LOGGER: OnceLock<()> = OnceLock::new();
+
+thread_local! {
+    /// Set while this thread stores a record.
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Store `record` in every live buffer keeping its level. Records logged while storing
+/// one (e.g. a re-entrant lock report) are dropped.
+fn store(record: LogRecord) {
+    if CAPTURING.with(Cell::get) {
+        return;
+    }
+    CAPTURING.with(|capturing| capturing.set(true));
+    let logs: Vec<Entity<CapturedLogs>> = match SINKS.lock() {
+        Ok(mut sinks) => {
+            sinks.retain(|sink| sink.logs.upgrade().is_some());
+            sinks.iter().filter(|sink| record.level <= sink.level).filter_map(|sink| sink.logs.upgrade()).collect()
+        }
+        Err(_) => Vec::new(),
+    };
+    for logs in logs {
+        let _ = logs.update(|logs| logs.push(record.clone()));
+    }
+    CAPTURING.with(|capturing| capturing.set(false));
+}
+
+struct Capture;
+
+impl log::Log for Capture {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        store(LogRecord {
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+            at: SystemTime::now(),
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+/// Install the capturing logger, and return a new buffer keeping at most `capacity`
+/// records at `level` and above for as long as it is alive. Fails if another logger
+/// was installed first. `Application::capture_logs` calls it for the application.
+pub fn capture(level: LevelFilter, capacity: usize) -> Result<Entity<CapturedLogs>, log::SetLoggerError> {
+    let mut installed = Ok(());
+    LOGGER.get_or_init(|| installed = log::set_logger(&Capture));
+    installed?;
+    Ok(buffer(level, capacity))
+}
+
+/// A new buffer receiving the records at `level` and above.
+fn buffer(level: LevelFilter, capacity: usize) -> Entity<CapturedLogs> {
+    let logs = Entity::new(CapturedLogs::new(capacity));
+    if let Ok(mut sinks) = SINKS.lock() {
+        sinks.retain(|sink| sink.logs.upgrade().is_some());
+        sinks.push(Sink { logs: logs.downgrade(), level });
+        log::set_max_level(sinks.iter().map(|sink| sink.level).max().unwrap_or(LevelFilter::Off));
+    }
+    logs
+}
+
+/// A `tracing-subscriber` layer storing events in the buffers of the applications
+/// capturing logs, as `capture` does with `log` records. Filter it with
+/// `Layer::with_filter` to capture less.
+///
+/// # Example
+/// ```ignore
+/// use tracing_subscriber::prelude::*;
+///
+/// tracing_subscriber::registry().with(rat_nexus::logs::layer()).init();
+/// Application::new().capture_logs(LevelFilter::Debug).run(setup)?;
+/// ```
+#[cfg(feature = "tracing")]
+pub fn layer() -> CaptureLayer {
+    CaptureLayer
+}
+
+/// Captures `tracing` events; see `layer`.
+#[cfg(feature = "tracing")]
+pub struct CaptureLayer;
+
+#[cfg(feature = "tracing")]
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _cx: tracing_subscriber::layer::Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut message = FieldWriter::default();
+        event.record(&mut message);
+        let level = match *metadata.level() {
+            tracing::Level::ERROR => Level::Error,
+            tracing::Level::WARN => Level::Warn,
+            tracing::Level::INFO => Level::Info,
+            tracing::Level::DEBUG => Level::Debug,
+            tracing::Level::TRACE => Level::Trace,
+        };
+        store(LogRecord { level, target: metadata.target().to_string(), message: message.0, at: SystemTime::now() });
+    }
+}
+
+/// Formats an event's message followed by its other fields as `name=value`.
+#[cfg(feature = "tracing")]
+#[derive(Default)]
+struct FieldWriter(String);
+
+#[cfg(feature = "tracing")]
+impl tracing::field::Visit for FieldWriter {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.record_debug(field, &format_args!("{value}"));
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        use std::fmt::Write;
+
+        let separator = if self.0.is_empty() { "" } else { " " };
+        let _ = match field.name() {
+            "message" => write!(self.0, "{separator}{value:?}"),
+            name => write!(self.0, "{separator}{name}={value:?}"),
+        };
+    }
+}
+
+fn level_style(level: Level) -> Style {
+    match level {
+        Level::Error => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        Level::Warn => Style::default().fg(Color::Yellow),
+        Level::Info => Style::default().fg(Color::Green),
+        Level::Debug => Style::default().fg(Color::Blue),
+        Level::Trace => Style::default().fg(Color::DarkGray),
+    }
+}
+
+/// Draw the newest records that fit into `area`, oldest at the top.
+fn draw_records(logs: &CapturedLogs, min_level: Level, area: Rect, buf: &mut Buffer) {
+    let lines: Vec<Line> = logs
+        .iter()
+        .rev()
+        .filter(|record| record.level <= min_level)
+        .take(area.height as usize)
+        .map(|record| {
+            Line::from(vec![
+                Span::styled(format!("{:<5} ", record.level), level_style(record.level)),
+                Span::styled(format!("{} ", record.target), Style::default().fg(Color::DarkGray)),
+                Span::raw(record.message.as_str()),
+            ])
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    Paragraph::new(lines).render(area, buf);
+}
+
+/// Shows captured log records, newest at the bottom.
+///
+/// # Example
+/// ```ignore
+/// // with Application::new().capture_logs(LevelFilter::Debug)
+/// logs: ChildView<LogPanel>,
+/// self.logs = ChildView::new(LogPanel::new(cx.app.captured_logs().unwrap_or_default()));
+/// ```
+pub struct LogPanel {
+    logs: Entity<CapturedLogs>,
+    min_level: Level,
+}
+
+impl LogPanel {
+    /// Show the records in `logs`.
+    pub fn new(logs: Entity<CapturedLogs>) -> Self {
+        Self { logs, min_level: Level::Trace }
+    }
+
+    /// Hide records less severe than `level`.
+    pub fn min_level(mut self, level: Level) -> Self {
+        self.min_level = level;
+        self
+    }
+}
+
+impl Component for LogPanel {
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let area = cx.area(frame);
+        cx.subscribe(&self.logs);
+        let min_level = self.min_level;
+        let _ = self.logs.read(|logs| draw_records(logs, min_level, area, frame.buffer_mut()));
+    }
+}
+
+struct OverlayInner {
+    key: Option<KeyEvent>,
+    open: bool,
+    /// The application's captured records, once it captures logs.
+    logs: Option<Entity<CapturedLogs>>,
+}
+
+/// The log overlay toggled over the whole app, owned by the `AppContext`.
+#[derive(Clone)]
+pub(crate) struct LogOverlay {
+    inner: Arc<Mutex<OverlayInner>>,
+}
+
+impl Default for LogOverlay {
+    fn default() -> Self {
+        Self { inner: Arc::new(Mutex::new(OverlayInner { key: Some(KeyEvent::from(KeyCode::F(12))), open: false, logs: None })) }
+    }
+}
+
+impl LogOverlay {
+    pub(crate) fn set_key(&self, key: Option<KeyEvent>) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.key = key;
+        }
+    }
+
+    pub(crate) fn logs(&self) -> Option<Entity<CapturedLogs>> {
+        self.inner.lock().ok().and_then(|inner| inner.logs.clone())
+    }
+
+    pub(crate) fn set_logs(&self, logs: Entity<CapturedLogs>) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.logs = Some(logs);
+        }
+    }
+
+    pub(crate) fn is_open(&self) -> bool {
+        self.inner.lock().map(|inner| inner.open).unwrap_or(false)
+    }
+
+    pub(crate) fn set_open(&self, open: bool) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.open = open && inner.logs.is_some();
+        }
+    }
+
+    /// Toggle on the key, close on `Esc`; returns true if the event was consumed.
+    /// Does nothing until logs are captured.
+    pub(crate) fn intercept(&self, event: &Event) -> bool {
+        let Ok(mut inner) = self.inner.lock() else { return false };
+        if inner.logs.is_none() {
+            return false;
+        }
+        match event {
+            Event::Key(key) if inner.key == Some(*key) => {
+                inner.open = !inner.open;
+                true
+            }
+            Event::Key(key) if inner.open && key.code == KeyCode::Esc => {
+                inner.open = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Draw the open overlay over the bottom part of `bounds`.
+    pub(crate) fn render(&self, buf: &mut Buffer, bounds: Rect) {
+        let Some(logs) = self.logs().filter(|_| self.is_open()) else { return };
+        let height = (bounds.height * 2 / 5).max(3).min(bounds.height);
+        let area = Rect { y: bounds.bottom() - height, height, ..bounds };
+        Clear.render(area, buf);
+        let _ = logs.read(|logs| {
+            let title = match logs.dropped() {
+                0 => format!(" Logs ({}) ", logs.len()),
+                dropped => format!(" Logs ({}, {dropped} dropped) ", logs.len()),
+            };
+            let block = Block::default()
+                .title(title)
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Cyan));
+            let inner = block.inner(area);
+            block.render(area, buf);
+            draw_records(logs, Level::Trace, inner, buf);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+
+    fn record(level: Level, message: &str) -> LogRecord {
+        LogRecord { level, target: "app".into(), message: message.into(), at: SystemTime::now() }
+    }
+
+    #[test]
+    fn test_buffer_is_bounded() {
+        let mut logs = CapturedLogs::new(2);
+        for message in ["a", "b", "c"] {
+            logs.push(record(Level::Info, message));
+        }
+        assert_eq!(logs.iter().map(|r| r.message.as_str()).collect::<Vec<_>>(), ["b", "c"]);
+        assert_eq!(logs.dropped(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_panel_shows_newest_records_at_level() {
+        let logs = Entity::new(CapturedLogs::default());
+        let mut harness = Harness::with_size(LogPanel::new(Entity::clone(&logs)).min_level(Level::Info), 24, 2);
+        let _ = logs.update(|logs| {
+            logs.push(record(Level::Error, "first"));
+            logs.push(record(Level::Debug, "noise"));
+            logs.push(record(Level::Warn, "second"));
+            logs.push(record(Level::Info, "third"));
+        });
+        harness.render();
+        assert_eq!(harness.text(), "WARN  app second\nINFO  app third");
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_layer_captures_tracing_events() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let logs = buffer(LevelFilter::Info, 100);
+        let subscriber = tracing_subscriber::registry().with(layer());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(target: "layer_test", user = "ann", retries = 3, "login failed");
+        });
+        let mine: Vec<LogRecord> = logs
+            .read(|logs| logs.iter().filter(|r| r.target == "layer_test").cloned().collect())
+            .unwrap();
+        assert_eq!(mine.len(), 1);
+        assert_eq!(mine[0].level, Level::Warn);
+        assert_eq!(mine[0].message, "login failed user=ann retries=3");
+    }
+
+    #[test]
+    fn test_capture_installs_logger_and_overlay_toggles() {
+        let logs = capture(LevelFilter::Info, 100).unwrap();
+        let verbose = capture(LevelFilter::Debug, 100).unwrap();
+        log::info!(target: "capture_test", "captured {}", 42);
+        log::debug!(target: "capture_test", "filtered");
+        let mine = |logs: &Entity<CapturedLogs>| -> Vec<String> {
+            logs.read(|logs| logs.iter().filter(|r| r.target == "capture_test").map(|r| r.message.clone()).collect())
+                .unwrap()
+        };
+        assert_eq!(mine(&logs), ["captured 42"]);
+        assert_eq!(mine(&verbose), ["captured 42", "filtered"]);
+
+        let overlay = LogOverlay::default();
+        assert!(!overlay.intercept(&Event::Key(KeyEvent::from(KeyCode::F(12)))));
+        overlay.set_logs(logs);
+        assert!(overlay.intercept(&Event::Key(KeyEvent::from(KeyCode::F(12)))));
+        assert!(overlay.is_open());
+        assert!(overlay.intercept(&Event::Key(KeyEvent::from(KeyCode::Esc))));
+        assert!(!overlay.is_open());
+        assert!(!overlay.intercept(&Event::Key(KeyEvent::from(KeyCode::Esc))));
+    }
+}
@@ -0,0 +1,261 @@
+//! Input latency metrics.
+//!
+//! The event loop records when each event is received, handled and finally flushed
+//! to the terminal, for terminal input and events injected through the context alike.
+//! `cx.metrics()` exposes input-to-frame latency percentiles so apps can quantify
+//! responsiveness regressions.
+//!
+//! The last frames' start times and durations are kept as well, for the frame rate
+//! and the frame time histogram shown by the inspector overlay, and for the frame rate
+//! badge drawn in the top-right corner when `AppContext::set_fps_overlay` is on.
+//!
+//! Each stage is also reported at trace level under the `rat_nexus::pipeline` target,
+//! through the `log` facade by default. With the `tracing` feature it is emitted as
+//! spans instead: `event` (with `source = "input"` or `"injected"`) and `frame`, which
+//! holds `render` and `flush`.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+/// Number of latency samples kept for percentile computation.
+const SAMPLE_CAPACITY: usize = 512;
+
+/// Number of recent frames kept for the frame rate and histogram.
+const FRAME_CAPACITY: usize = 120;
+
+/// Upper bounds of the frame time histogram buckets; the last bucket is open.
+pub const FRAME_BUCKETS: [Duration; 6] = [
+    Duration::from_millis(1),
+    Duration::from_millis(2),
+    Duration::from_millis(4),
+    Duration::from_millis(8),
+    Duration::from_millis(16),
+    Duration::from_millis(33),
+];
+
+/// Log target used for pipeline stage records.
+pub const PIPELINE_TARGET: &str = "rat_nexus::pipeline";
+
+/// Report a pipeline record through `tracing` or `log`, depending on the features.
+macro_rules! pipeline_trace {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        tracing::trace!(target: PIPELINE_TARGET, $($arg)*);
+        #[cfg(not(feature = "tracing"))]
+        log::trace!(target: PIPELINE_TARGET, $($arg)*);
+    }};
+}
+
+/// Enter a span for one pipeline stage. Without the `tracing` feature this is a no-op
+/// guard.
+macro_rules! pipeline_span {
+    ($name:literal $(, $($field:tt)*)?) => {{
+        #[cfg(feature = "tracing")]
+        let span = tracing::trace_span!(target: $crate::metrics::PIPELINE_TARGET, $name $(, $($field)*)?).entered();
+        #[cfg(not(feature = "tracing"))]
+        let span = $crate::metrics::NoSpan;
+        span
+    }};
+}
+pub(crate) use pipeline_span;
+
+/// Stand-in for an entered span when the `tracing` feature is off.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct NoSpan;
+
+/// A point-in-time summary of pipeline latency.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    /// Median input-to-frame latency.
+    pub p50: Duration,
+    /// 99th percentile input-to-frame latency.
+    pub p99: Duration,
+    /// Worst input-to-frame latency in the sample window.
+    pub max: Duration,
+    /// Number of samples in the window.
+    pub samples: usize,
+    /// Time spent in the most recent event handler.
+    pub last_handle: Duration,
+    /// Time spent rendering and flushing the most recent frame.
+    pub last_frame: Duration,
+    /// Frames per second over the last second, while frames are being drawn.
+    pub fps: f64,
+}
+
+#[derive(Default)]
+struct MetricsInner {
+    /// Receive time of the oldest input not yet reflected in a frame.
+    pending_input: Option<Instant>,
+    samples: VecDeque<Duration>,
+    last_handle: Duration,
+    last_frame: Duration,
+    /// Start and duration of the most recent frames.
+    frames: VecDeque<(Instant, Duration)>,
+    /// Draw the frame rate badge.
+    overlay: bool,
+}
+
+/// Shared latency recorder, owned by the `AppContext`.
+#[derive(Clone, Default)]
+pub struct Metrics {
+    inner: Arc<Mutex<MetricsInner>>,
+}
+
+impl Metrics {
+    /// Mark an event as received from `source` (`"input"` for terminal events,
+    /// `"injected"` for events emitted through the context). Returns the timestamp to
+    /// pass to `event_handled`.
+    pub(crate) fn event_received(&self, source: &str) -> Instant {
+        pipeline_trace!("{} event received", source);
+        Instant::now()
+    }
+
+    /// Mark an event as handled by the component tree. Only events that requested
+    /// a `redraw` count towards input-to-frame latency.
+    pub(crate) fn event_handled(&self, received: Instant, redraw: bool) {
+        let elapsed = received.elapsed();
+        pipeline_trace!("event handled in {:?}", elapsed);
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.last_handle = elapsed;
+            if redraw {
+                inner.pending_input.get_or_insert(received);
+            }
+        }
+    }
+
+    /// Mark a frame as rendered and flushed, started at `frame_start`.
+    pub(crate) fn frame_flushed(&self, frame_start: Instant) {
+        let now = Instant::now();
+        let frame = now - frame_start;
+        pipeline_trace!("frame rendered and flushed in {:?}", frame);
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.last_frame = frame;
+            if inner.frames.len() == FRAME_CAPACITY {
+                inner.frames.pop_front();
+            }
+            inner.frames.push_back((frame_start, frame));
+            if let Some(received) = inner.pending_input.take() {
+                let latency = now - received;
+                pipeline_trace!("input-to-frame latency {:?}", latency);
+                if inner.samples.len() == SAMPLE_CAPACITY {
+                    inner.samples.pop_front();
+                }
+                inner.samples.push_back(latency);
+            }
+        }
+    }
+
+    /// Compute a snapshot of the current latency statistics.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let Ok(inner) = self.inner.lock() else {
+            return MetricsSnapshot::default();
+        };
+        let mut sorted: Vec<Duration> = inner.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        MetricsSnapshot {
+            p50: percentile(&sorted, 50),
+            p99: percentile(&sorted, 99),
+            max: sorted.last().copied().unwrap_or_default(),
+            samples: sorted.len(),
+            last_handle: inner.last_handle,
+            last_frame: inner.last_frame,
+            fps: fps(&inner.frames, Instant::now()),
+        }
+    }
+
+    /// Counts of recent frames per `FRAME_BUCKETS` bucket, plus one for slower frames.
+    pub fn frame_histogram(&self) -> [usize; FRAME_BUCKETS.len() + 1] {
+        let mut counts = [0; FRAME_BUCKETS.len() + 1];
+        if let Ok(inner) = self.inner.lock() {
+            for (_, frame) in &inner.frames {
+                let bucket = FRAME_BUCKETS.iter().position(|bound| frame < bound).unwrap_or(FRAME_BUCKETS.len());
+                counts[bucket] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Clear all recorded samples.
+    pub fn reset(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            *inner = MetricsInner { overlay: inner.overlay, ..MetricsInner::default() };
+        }
+    }
+
+    /// Draw the frame rate in the top-right corner of every frame.
+    pub fn set_overlay(&self, enabled: bool) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.overlay = enabled;
+        }
+    }
+
+    /// Draw the frame rate badge if enabled.
+    pub(crate) fn render_overlay(&self, frame: &mut Frame) {
+        let Some(fps) = self.inner.lock().ok().filter(|inner| inner.overlay).map(|inner| fps(&inner.frames, Instant::now())) else {
+            return;
+        };
+        let text = format!(" {fps:.0} fps ");
+        let area = frame.area();
+        let width = (text.len() as u16).min(area.width);
+        let badge = Rect::new(area.right() - width, area.y, width, area.height.min(1));
+        frame.render_widget(Paragraph::new(text).style(Style::default().fg(Color::Black).bg(Color::Green)), badge);
+    }
+}
+
+/// Frames started within the second before `now`.
+fn fps(frames: &VecDeque<(Instant, Duration)>, now: Instant) -> f64 {
+    frames.iter().filter(|(start, _)| now.duration_since(*start) < Duration::from_secs(1)).count() as f64
+}
+
+/// Nearest-rank percentile of a sorted slice.
+fn percentile(sorted: &[Duration], pct: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_rate_and_histogram() {
+        let metrics = Metrics::default();
+        for _ in 0..3 {
+            metrics.frame_flushed(Instant::now());
+        }
+        metrics.frame_flushed(Instant::now() - Duration::from_millis(40));
+        assert_eq!(metrics.snapshot().fps, 4.0);
+        assert_eq!(metrics.frame_histogram(), [3, 0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_percentile() {
+        let samples: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&samples, 50), Duration::from_millis(50));
+        assert_eq!(percentile(&samples, 99), Duration::from_millis(99));
+        assert_eq!(percentile(&[], 50), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_input_sampled_once_per_frame() {
+        let metrics = Metrics::default();
+        let first = metrics.event_received("input");
+        metrics.event_handled(first, true);
+        let second = metrics.event_received("input");
+        metrics.event_handled(second, true);
+        metrics.frame_flushed(Instant::now());
+        // A frame without new input records no sample
+        metrics.frame_flushed(Instant::now());
+
+        assert_eq!(metrics.snapshot().samples, 1);
+    }
+}
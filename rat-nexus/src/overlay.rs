@@ -0,0 +1,53 @@
+//! Deferred drawing on top of the component tree.
+//!
+//! Popups (dropdowns, palettes) must not be painted over by siblings rendered after
+//! them. Components queue a draw with `AppContext::overlay` during `render`; the queue
+//! is drained in order once the root has finished rendering the frame.
+
+use std::sync::{Arc, Mutex};
+
+use ratatui::Frame;
+
+type OverlayFn = Box<dyn FnOnce(&mut Frame) + Send>;
+
+/// Draws queued for the current frame, owned by the `AppContext`.
+#[derive(Clone, Default)]
+pub(crate) struct Overlays {
+    queue: Arc<Mutex<Vec<OverlayFn>>>,
+}
+
+impl Overlays {
+    pub(crate) fn push(&self, draw: OverlayFn) {
+        if let Ok(mut queue) = self.queue.lock() {
+            queue.push(draw);
+        }
+    }
+
+    /// Run and clear the queued draws, in the order they were queued.
+    pub(crate) fn render(&self, frame: &mut Frame) {
+        let queued = self.queue.lock().map(|mut queue| std::mem::take(&mut *queue)).unwrap_or_default();
+        for draw in queued {
+            draw(frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn test_overlays_draw_in_order_once() {
+        let overlays = Overlays::default();
+        overlays.push(Box::new(|f| f.buffer_mut().set_string(0, 0, "ab", ratatui::style::Style::default())));
+        overlays.push(Box::new(|f| f.buffer_mut().set_string(1, 0, "X", ratatui::style::Style::default())));
+
+        let mut terminal = Terminal::new(TestBackend::new(4, 1)).unwrap();
+        let frame = terminal.draw(|f| overlays.render(f)).unwrap();
+        assert_eq!(frame.buffer[(0, 0)].symbol(), "a");
+        assert_eq!(frame.buffer[(1, 0)].symbol(), "X");
+        assert!(overlays.queue.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,453 @@
+//! Command palette: a fuzzy-searchable list drawn as an overlay.
+//!
+//! `AppContext::open_palette(items, on_pick)` shows arbitrary items. The palette key
+//! (`Ctrl+P` by default, see `AppContext::set_palette_key`) opens the command palette,
+//! listing the routes registered by `define_app!` ("Go to …") and the keybindings
+//! registered with `AppContext::register_keybinding`, so every app gets page jumping
+//! and a searchable key reference for free. While open, the palette receives key, paste
+//! and mouse events before the root component.
+
+use std::sync::{Arc, Mutex};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Widget};
+
+use crate::component::traits::Event;
+use crate::widgets::select::fuzzy_match;
+use crate::AppContext;
+
+/// Maximum number of rows in the list.
+const MAX_ROWS: u16 = 12;
+
+type PickFn = Box<dyn FnOnce(usize, &AppContext) + Send>;
+
+/// An entry in the palette: a label to match against and an optional right-aligned hint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteItem {
+    /// Text shown and matched against the query.
+    pub label: String,
+    /// Dimmed text on the right, not matched.
+    pub hint: String,
+}
+
+impl PaletteItem {
+    /// An item with a hint, e.g. the key that runs the same command.
+    pub fn new(label: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self { label: label.into(), hint: hint.into() }
+    }
+}
+
+impl From<&str> for PaletteItem {
+    fn from(label: &str) -> Self {
+        Self::new(label, "")
+    }
+}
+
+impl From<String> for PaletteItem {
+    fn from(label: String) -> Self {
+        Self::new(label, "")
+    }
+}
+
+/// Result of a key press in a `CommandPalette`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteOutcome {
+    /// Still open.
+    Pending,
+    /// The item at this index was picked.
+    Picked(usize),
+    /// Dismissed with `Esc`.
+    Closed,
+}
+
+/// A fuzzy-searchable list with a query line.
+///
+/// Usually opened through `AppContext::open_palette`; can also be owned and drawn by a
+/// component directly.
+#[derive(Debug, Clone)]
+pub struct CommandPalette {
+    title: String,
+    items: Vec<PaletteItem>,
+    query: String,
+    matches: Vec<(usize, Vec<usize>)>,
+    cursor: usize,
+    offset: usize,
+    list_area: Option<Rect>,
+    area: Option<Rect>,
+}
+
+impl CommandPalette {
+    /// Create a palette over `items`.
+    pub fn new(title: impl Into<String>, items: Vec<PaletteItem>) -> Self {
+        let mut palette = Self {
+            title: format!(" {} ", title.into()),
+            items,
+            query: String::new(),
+            matches: Vec::new(),
+            cursor: 0,
+            offset: 0,
+            list_area: None,
+            area: None,
+        };
+        palette.refilter();
+        palette
+    }
+
+    /// The items.
+    pub fn items(&self) -> &[PaletteItem] {
+        &self.items
+    }
+
+    /// The current query.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Indices of the items matching the query, best match first.
+    pub fn matches(&self) -> impl Iterator<Item = usize> + '_ {
+        self.matches.iter().map(|(i, _)| *i)
+    }
+
+    /// Append text to the query.
+    pub fn insert_str(&mut self, text: &str) {
+        self.query.extend(text.chars().filter(|c| !c.is_control()));
+        self.refilter();
+    }
+
+    /// Handle a key: typing filters, `↑/↓` move, `Enter` picks, `Esc` closes.
+    pub fn handle_key(&mut self, key: KeyEvent) -> PaletteOutcome {
+        let last = self.matches.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc => return PaletteOutcome::Closed,
+            KeyCode::Enter => {
+                if let Some((index, _)) = self.matches.get(self.cursor) {
+                    return PaletteOutcome::Picked(*index);
+                }
+            }
+            KeyCode::Up => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Down => self.cursor = (self.cursor + 1).min(last),
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => self.cursor = (self.cursor + 1).min(last),
+            KeyCode::PageUp => self.cursor = self.cursor.saturating_sub(MAX_ROWS as usize),
+            KeyCode::PageDown => self.cursor = (self.cursor + MAX_ROWS as usize).min(last),
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.refilter();
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.query.clear();
+                self.refilter();
+            }
+            KeyCode::Char(c) if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                self.insert_str(&c.to_string());
+            }
+            _ => {}
+        }
+        PaletteOutcome::Pending
+    }
+
+    /// Handle a mouse event: clicking a row picks it, clicking outside closes.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> PaletteOutcome {
+        let position = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(list) = self.list_area.filter(|r| r.contains(position)) {
+                    let row = self.offset + (mouse.row - list.y) as usize;
+                    if let Some((index, _)) = self.matches.get(row) {
+                        return PaletteOutcome::Picked(*index);
+                    }
+                } else if !self.area.is_some_and(|r| r.contains(position)) {
+                    return PaletteOutcome::Closed;
+                }
+            }
+            MouseEventKind::ScrollUp => self.cursor = self.cursor.saturating_sub(1),
+            MouseEventKind::ScrollDown => self.cursor = (self.cursor + 1).min(self.matches.len().saturating_sub(1)),
+            _ => {}
+        }
+        PaletteOutcome::Pending
+    }
+
+    /// Draw the palette centered horizontally near the top of `bounds`.
+    pub fn draw(&mut self, bounds: Rect, buf: &mut Buffer) {
+        let width = (bounds.width * 3 / 5).clamp(30.min(bounds.width), 80.min(bounds.width));
+        let rows = (self.matches.len().max(1) as u16).min(MAX_ROWS);
+        let height = (rows + 4).min(bounds.height.saturating_sub(bounds.height / 8));
+        let area = Rect::new(bounds.x + (bounds.width - width) / 2, bounds.y + bounds.height / 8, width, height);
+        self.area = Some(area);
+        if area.height < 4 {
+            return;
+        }
+        Clear.render(area, buf);
+        let block = Block::default()
+            .title(self.title.as_str())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let prompt = format!("› {}", self.query);
+        buf.set_stringn(inner.x, inner.y, &prompt, inner.width as usize, Style::default().add_modifier(Modifier::BOLD));
+        let cursor_x = inner.x + prompt.chars().count() as u16;
+        if cursor_x < inner.right() {
+            buf[(cursor_x, inner.y)].set_style(Style::default().add_modifier(Modifier::REVERSED));
+        }
+        let rule = "─".repeat(inner.width as usize);
+        buf.set_string(inner.x, inner.y + 1, rule, Style::default().fg(Color::DarkGray));
+
+        let list = Rect::new(inner.x, inner.y + 2, inner.width, inner.height.saturating_sub(2));
+        self.list_area = Some(list);
+        self.draw_list(list, buf);
+    }
+
+    fn draw_list(&mut self, area: Rect, buf: &mut Buffer) {
+        let height = area.height as usize;
+        if height == 0 {
+            return;
+        }
+        if self.matches.is_empty() {
+            buf.set_stringn(area.x, area.y, "no matches", area.width as usize, Style::default().fg(Color::DarkGray));
+            return;
+        }
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + height {
+            self.offset = self.cursor + 1 - height;
+        }
+        for (row, (index, positions)) in self.matches.iter().enumerate().skip(self.offset).take(height) {
+            let y = area.y + (row - self.offset) as u16;
+            let item = &self.items[*index];
+            let base = if row == self.cursor { Style::default().fg(Color::Black).bg(Color::Cyan) } else { Style::default() };
+            buf.set_style(Rect::new(area.x, y, area.width, 1), base);
+            let spans: Vec<Span> = item
+                .label
+                .chars()
+                .enumerate()
+                .map(|(i, c)| {
+                    let style = if positions.contains(&i) { base.add_modifier(Modifier::BOLD | Modifier::UNDERLINED) } else { base };
+                    Span::styled(c.to_string(), style)
+                })
+                .collect();
+            buf.set_line(area.x, y, &Line::from(spans), area.width);
+            let hint_width = item.hint.chars().count() as u16;
+            if !item.hint.is_empty() && hint_width + 2 < area.width {
+                let style = if row == self.cursor { base } else { base.fg(Color::DarkGray) };
+                buf.set_string(area.right() - hint_width - 1, y, &item.hint, style);
+            }
+        }
+    }
+
+    fn refilter(&mut self) {
+        self.matches = if self.query.is_empty() {
+            (0..self.items.len()).map(|i| (i, Vec::new())).collect()
+        } else {
+            let mut scored: Vec<(i64, usize, Vec<usize>)> = self
+                .items
+                .iter()
+                .enumerate()
+                .filter_map(|(i, item)| fuzzy_match(&self.query, &item.label).map(|(score, pos)| (score, i, pos)))
+                .collect();
+            scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+            scored.into_iter().map(|(_, i, pos)| (i, pos)).collect()
+        };
+        self.cursor = 0;
+        self.offset = 0;
+    }
+}
+
+/// What a command palette entry does.
+#[derive(Debug, Clone)]
+enum Command {
+    Navigate(String),
+    Key(KeyEvent),
+}
+
+struct PaletteInner {
+    open: Option<(CommandPalette, PickFn)>,
+    key: Option<KeyEvent>,
+    routes: Vec<String>,
+    bindings: Vec<(KeyEvent, String)>,
+}
+
+/// Shared palette state and command registry, owned by the `AppContext`.
+#[derive(Clone)]
+pub(crate) struct Palettes {
+    inner: Arc<Mutex<PaletteInner>>,
+}
+
+impl Default for Palettes {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(PaletteInner {
+                open: None,
+                key: Some(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
+                routes: Vec::new(),
+                bindings: Vec::new(),
+            })),
+        }
+    }
+}
+
+impl Palettes {
+    pub(crate) fn open(&self, palette: CommandPalette, on_pick: PickFn) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.open = Some((palette, on_pick));
+        }
+    }
+
+    pub(crate) fn close(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.open = None;
+        }
+    }
+
+    pub(crate) fn is_open(&self) -> bool {
+        self.inner.lock().map(|inner| inner.open.is_some()).unwrap_or(false)
+    }
+
+    pub(crate) fn set_key(&self, key: Option<KeyEvent>) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.key = key;
+        }
+    }
+
+    pub(crate) fn register_routes(&self, routes: Vec<String>) {
+        if let Ok(mut inner) = self.inner.lock() {
+            for route in routes {
+                if !inner.routes.contains(&route) {
+                    inner.routes.push(route);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn register_keybinding(&self, key: KeyEvent, description: String) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.bindings.retain(|(k, _)| *k != key);
+            inner.bindings.push((key, description));
+        }
+    }
+
+    /// The command palette: registered routes, then registered keybindings.
+    pub(crate) fn commands(&self) -> (CommandPalette, PickFn) {
+        let (routes, bindings) = self
+            .inner
+            .lock()
+            .map(|inner| (inner.routes.clone(), inner.bindings.clone()))
+            .unwrap_or_default();
+        let mut items = Vec::new();
+        let mut commands = Vec::new();
+        for route in routes {
+            items.push(PaletteItem::new(format!("Go to {route}"), "page"));
+            commands.push(Command::Navigate(route));
+        }
+        for (key, description) in bindings {
+            items.push(PaletteItem::new(description, crate::keymap::key_label(&key)));
+            commands.push(Command::Key(key));
+        }
+        let on_pick: PickFn = Box::new(move |index, app| match commands.get(index) {
+            Some(Command::Navigate(route)) => app.navigate(route.clone()),
+            Some(Command::Key(key)) => app.inject_event(Event::Key(*key)),
+            None => {}
+        });
+        (CommandPalette::new("Commands", items), on_pick)
+    }
+
+    /// Offer an event to the palette. Returns true if it was consumed.
+    pub(crate) fn intercept(&self, event: &Event, app: &AppContext) -> bool {
+        let Ok(mut inner) = self.inner.lock() else {
+            return false;
+        };
+        let Some((palette, _)) = inner.open.as_mut() else {
+            let opens = matches!(event, Event::Key(key) if Some(*key) == inner.key);
+            drop(inner);
+            if opens {
+                let (palette, on_pick) = self.commands();
+                self.open(palette, on_pick);
+            }
+            return opens;
+        };
+        let outcome = match event {
+            Event::Key(key) => palette.handle_key(*key),
+            Event::Mouse(mouse) => palette.handle_mouse(*mouse),
+            Event::Paste(text) => {
+                palette.insert_str(text);
+                PaletteOutcome::Pending
+            }
+            _ => return false,
+        };
+        match outcome {
+            PaletteOutcome::Pending => {}
+            PaletteOutcome::Closed => inner.open = None,
+            PaletteOutcome::Picked(index) => {
+                let (_, on_pick) = inner.open.take().expect("palette is open");
+                // Release the lock first: the callback may open another palette
+                drop(inner);
+                on_pick(index, app);
+            }
+        }
+        true
+    }
+
+    /// Draw the open palette, if any.
+    pub(crate) fn render(&self, buf: &mut Buffer, bounds: Rect) {
+        if let Ok(mut inner) = self.inner.lock() {
+            if let Some((palette, _)) = inner.open.as_mut() {
+                palette.draw(bounds, buf);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn test_palette_filters_and_picks() {
+        let mut palette = CommandPalette::new("Files", vec!["src/main.rs".into(), "Cargo.toml".into(), "src/lib.rs".into()]);
+        palette.insert_str("lib");
+        assert_eq!(palette.matches().collect::<Vec<_>>(), vec![2]);
+        palette.handle_key(KeyEvent::from(KeyCode::Backspace));
+        palette.handle_key(KeyEvent::from(KeyCode::Backspace));
+        palette.handle_key(KeyEvent::from(KeyCode::Backspace));
+        palette.insert_str("rs");
+        assert_eq!(palette.matches().collect::<Vec<_>>(), vec![2, 0]);
+        palette.handle_key(KeyEvent::from(KeyCode::Down));
+        assert_eq!(palette.handle_key(KeyEvent::from(KeyCode::Enter)), PaletteOutcome::Picked(0));
+    }
+
+    #[test]
+    fn test_command_palette_lists_routes_and_bindings() {
+        let (app, _refresh, mut injected) = AppContext::new();
+        app.register_routes(["Menu", "Monitor"]);
+        app.register_keybinding(KeyEvent::from(KeyCode::Char('t')), "Switch theme");
+
+        let ctrl_p = Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        assert!(app.palettes.intercept(&ctrl_p, &app));
+        assert!(app.palette_open());
+        for c in "mon".chars() {
+            app.palettes.intercept(&key(KeyCode::Char(c)), &app);
+        }
+        assert!(!app.palettes.intercept(&Event::Resize(10, 10), &app));
+        assert!(app.palettes.intercept(&key(KeyCode::Enter), &app));
+        assert!(!app.palette_open());
+        let navigate = injected.try_recv().unwrap();
+        assert_eq!(navigate.custom::<String>().map(String::as_str), Some("Monitor"));
+
+        app.palettes.intercept(&ctrl_p, &app);
+        for c in "theme".chars() {
+            app.palettes.intercept(&key(KeyCode::Char(c)), &app);
+        }
+        app.palettes.intercept(&key(KeyCode::Enter), &app);
+        assert!(matches!(injected.try_recv().unwrap(), Event::Key(k) if k.code == KeyCode::Char('t')));
+    }
+}
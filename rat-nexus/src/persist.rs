@@ -0,0 +1,235 @@
+//! Entity persistence to disk (`persist` feature).
+//!
+//! `cx.persisted(path)` loads an entity's value from a JSON file on creation and
+//! writes it back once updates have been quiet for `SAVE_DEBOUNCE`. `AppContext::save_all()` flushes
+//! every live entity persisted through that application; the application calls it
+//! automatically on shutdown.
+//!
+//! Values are encoded with serde, so any `Serialize + DeserializeOwned` type with a
+//! `Default` can be persisted. A missing file starts from `T::default()`; a file that
+//! does not deserialize (corrupt, or written by an older schema) is moved aside to
+//! `<path>.bak` first, so the defaults never overwrite it.
+//!
+//! # Example
+//! ```ignore
+//! #[derive(Default, Serialize, Deserialize)]
+//! struct AppState {
+//!     counter: i32,
+//! }
+//!
+//! let state = cx.persisted::<AppState>("app_state.json")?;
+//! ```
+
+use crate::error::IoSnafu;
+use crate::state::Entity;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use snafu::ResultExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+/// Quiet period after the last update before the write to disk; every update restarts it.
+pub const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Something that can write itself to disk.
+trait Saver: Send + Sync {
+    fn save(&self) -> crate::Result<()>;
+}
+
+struct EntitySaver<T: Serialize + Send + Sync + 'static> {
+    entity: crate::state::WeakEntity<T>,
+    path: PathBuf,
+}
+
+impl<T: Serialize + Send + Sync + 'static> Saver for EntitySaver<T> {
+    fn save(&self) -> crate::Result<()> {
+        match self.entity.upgrade() {
+            Some(entity) => save_entity(&entity, &self.path),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Live persisted entities of one application, owned by its `AppContext`.
+#[derive(Clone, Default)]
+pub(crate) struct Persisted {
+    savers: Arc<Mutex<Vec<Weak<dyn Saver>>>>,
+}
+
+impl Persisted {
+    /// Create an entity backed by the file at `path` (see `AppContext::persisted`).
+    pub(crate) fn entity<T>(&self, path: PathBuf) -> crate::Result<Entity<T>>
+    where
+        T: Serialize + DeserializeOwned + Default + Send + Sync + 'static,
+    {
+        let runtime = tokio::runtime::Handle::try_current()
+            .map_err(|_| crate::Error::NoRuntime { operation: "AppContext::persisted" })?;
+        let entity = Entity::new(load::<T>(&path)?);
+
+        let saver: Arc<dyn Saver> = Arc::new(EntitySaver { entity: entity.downgrade(), path });
+        self.savers.lock().map_err(|_| crate::Error::LockPoisoned)?.push(Arc::downgrade(&saver));
+
+        let mut rx = entity.subscribe();
+        let weak = entity.downgrade();
+        runtime.spawn(async move {
+            while rx.changed().await.is_ok() {
+                // Restart the quiet period on every further change
+                loop {
+                    tokio::select! {
+                        changed = rx.changed() => {
+                            if changed.is_err() {
+                                break;
+                            }
+                        }
+                        _ = tokio::time::sleep(SAVE_DEBOUNCE) => break,
+                    }
+                }
+                if weak.upgrade().is_none() {
+                    break;
+                }
+                if let Err(error) = saver.save() {
+                    log::warn!("failed to save persisted entity: {error}");
+                }
+            }
+        });
+
+        Ok(entity)
+    }
+
+    /// Flush every live persisted entity to disk. Returns the first error encountered.
+    pub(crate) fn save_all(&self) -> crate::Result<()> {
+        let savers: Vec<Arc<dyn Saver>> = {
+            let mut savers = self.savers.lock().map_err(|_| crate::Error::LockPoisoned)?;
+            savers.retain(|s| s.strong_count() > 0);
+            savers.iter().filter_map(Weak::upgrade).collect()
+        };
+        let mut result = Ok(());
+        for saver in savers {
+            if let Err(e) = saver.save() {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Read the value stored at `path`, or `T::default()` if there is none. A file that
+/// does not deserialize is renamed to `<path>.bak` so saving the defaults cannot
+/// destroy it; any other read error is returned.
+fn load<T: DeserializeOwned + Default>(path: &Path) -> crate::Result<T> {
+    let data = match std::fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(T::default()),
+        Err(e) => return Err(e).context(IoSnafu),
+    };
+    match serde_json::from_str(&data) {
+        Ok(value) => Ok(value),
+        Err(error) => {
+            let backup = sibling(path, ".bak");
+            std::fs::rename(path, &backup).context(IoSnafu)?;
+            log::warn!("{} does not load ({error}); moved it to {}", path.display(), backup.display());
+            Ok(T::default())
+        }
+    }
+}
+
+/// `path` with `suffix` appended to its full file name.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Write the entity's current value to `path`, atomically via `<path>.tmp`.
+fn save_entity<T: Serialize + Send + Sync>(entity: &Entity<T>, path: &Path) -> crate::Result<()> {
+    let data = entity.read(serde_json::to_string_pretty)?.map_err(crate::Error::other)?;
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).context(IoSnafu)?;
+    }
+    let tmp = sibling(path, ".tmp");
+    std::fs::write(&tmp, data).context(IoSnafu)?;
+    std::fs::rename(&tmp, path).context(IoSnafu)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct Settings {
+        volume: u8,
+        name: String,
+    }
+
+    #[tokio::test]
+    async fn test_persisted_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("rat-nexus-persist-{}", std::process::id()));
+        let path = dir.join("settings.json");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("settings.tmp"), "unrelated").unwrap();
+
+        let persisted = Persisted::default();
+        let entity = persisted.entity::<Settings>(path.clone()).unwrap();
+        assert_eq!(entity.read(|s| s.volume).unwrap(), 0);
+        entity.update(|s| {
+            s.volume = 7;
+            s.name = "demo".into();
+        }).unwrap();
+        persisted.save_all().unwrap();
+        // The temporary file keeps the full name, so a sibling `settings.tmp` survives
+        assert_eq!(std::fs::read_to_string(dir.join("settings.tmp")).unwrap(), "unrelated");
+        assert!(!dir.join("settings.json.tmp").exists());
+
+        let reloaded = Persisted::default().entity::<Settings>(path).unwrap();
+        assert_eq!(reloaded.read(|s| s.volume).unwrap(), 7);
+        assert_eq!(reloaded.read(|s| s.name.clone()).unwrap(), "demo");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_file_is_moved_aside() {
+        let dir = std::env::temp_dir().join(format!("rat-nexus-persist-bak-{}", std::process::id()));
+        let path = dir.join("settings.json");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&path, r#"{"volume": "loud"}"#).unwrap();
+
+        let persisted = Persisted::default();
+        let entity = persisted.entity::<Settings>(path.clone()).unwrap();
+        assert_eq!(entity.read(|s| s.volume).unwrap(), 0);
+        persisted.save_all().unwrap();
+        assert_eq!(std::fs::read_to_string(dir.join("settings.json.bak")).unwrap(), r#"{"volume": "loud"}"#);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_save_waits_for_updates_to_settle() {
+        let dir = std::env::temp_dir().join(format!("rat-nexus-persist-debounce-{}", std::process::id()));
+        let path = dir.join("settings.json");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let entity = Persisted::default().entity::<Settings>(path.clone()).unwrap();
+        for volume in 1..=4 {
+            entity.update(|s| s.volume = volume).unwrap();
+            tokio::time::sleep(SAVE_DEBOUNCE / 2).await;
+        }
+        assert!(!path.exists(), "saved while updates kept arriving");
+        tokio::time::sleep(SAVE_DEBOUNCE * 2).await;
+        let saved: Settings = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved.volume, 4);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_persisted_requires_a_runtime() {
+        let Err(error) = Persisted::default().entity::<Settings>(PathBuf::from("unused.json")) else {
+            panic!("persisted outside a runtime");
+        };
+        assert!(matches!(error, crate::Error::NoRuntime { .. }), "{error}");
+    }
+}
@@ -0,0 +1,167 @@
+//! Presentation mode for demos and screencasts.
+//!
+//! When enabled via `AppContext::set_presentation_mode`:
+//! - recently pressed keys are shown in a bubble in the bottom-right corner,
+//! - `AppContext::animation_interval` stretches animation ticks by the configured slowdown,
+//! - debug chrome (such as the render storm banner) is hidden,
+//! - the UI switches to `Density::Spacious`, since terminal font size cannot be changed
+//!   from the application; the previous density is restored when turned off.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::Density;
+
+/// How long a key stays in the bubble.
+pub(crate) const KEY_DISPLAY: Duration = Duration::from_millis(2000);
+
+/// Maximum number of keys shown at once.
+const MAX_KEYS: usize = 6;
+
+struct PresentationInner {
+    enabled: bool,
+    slowdown: f64,
+    keys: VecDeque<(String, Instant)>,
+    restore_density: Option<Density>,
+}
+
+/// Shared presentation mode state, owned by the `AppContext`.
+#[derive(Clone)]
+pub(crate) struct Presentation {
+    inner: Arc<Mutex<PresentationInner>>,
+}
+
+impl Default for Presentation {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(PresentationInner {
+                enabled: false,
+                slowdown: 1.0,
+                keys: VecDeque::new(),
+                restore_density: None,
+            })),
+        }
+    }
+}
+
+impl Presentation {
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.inner.lock().map(|inner| inner.enabled).unwrap_or(false)
+    }
+
+    /// Toggle the mode. Returns the density to switch to, if it should change.
+    pub(crate) fn set_enabled(&self, enabled: bool, current: Density) -> Option<Density> {
+        let mut inner = self.inner.lock().ok()?;
+        if inner.enabled == enabled {
+            return None;
+        }
+        inner.enabled = enabled;
+        inner.keys.clear();
+        if enabled {
+            inner.restore_density = Some(current);
+            Some(Density::Spacious)
+        } else {
+            inner.restore_density.take()
+        }
+    }
+
+    pub(crate) fn set_slowdown(&self, factor: f64) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.slowdown = if factor.is_finite() { factor.max(1.0) } else { 1.0 };
+        }
+    }
+
+    pub(crate) fn scale(&self, interval: Duration) -> Duration {
+        match self.inner.lock() {
+            Ok(inner) if inner.enabled => interval.mul_f64(inner.slowdown),
+            _ => interval,
+        }
+    }
+
+    /// Remember a key press for the bubble. Returns true if it was recorded.
+    pub(crate) fn record_key(&self, key: &KeyEvent) -> bool {
+        let Ok(mut inner) = self.inner.lock() else {
+            return false;
+        };
+        if !inner.enabled {
+            return false;
+        }
+        inner.keys.push_back((crate::keymap::key_label(key), Instant::now()));
+        while inner.keys.len() > MAX_KEYS {
+            inner.keys.pop_front();
+        }
+        true
+    }
+
+    fn recent_keys(&self, now: Instant) -> Vec<String> {
+        let Ok(mut inner) = self.inner.lock() else {
+            return Vec::new();
+        };
+        inner.keys.retain(|(_, at)| now.saturating_duration_since(*at) < KEY_DISPLAY);
+        inner.keys.iter().map(|(label, _)| label.clone()).collect()
+    }
+
+    /// Draw the key bubble in the bottom-right corner.
+    pub(crate) fn render_overlay(&self, frame: &mut Frame) {
+        if !self.is_enabled() {
+            return;
+        }
+        let keys = self.recent_keys(Instant::now());
+        if keys.is_empty() {
+            return;
+        }
+        let text = format!(" {} ", keys.join("  "));
+        let area = frame.area();
+        let width = (text.chars().count() as u16 + 2).min(area.width);
+        let height = 3.min(area.height);
+        let bubble = Rect::new(
+            area.right().saturating_sub(width + 1).max(area.x),
+            area.bottom().saturating_sub(height + 1).max(area.y),
+            width,
+            height,
+        );
+        let style = Style::default().fg(Color::White).add_modifier(Modifier::BOLD);
+        frame.render_widget(Clear, bubble);
+        frame.render_widget(
+            Paragraph::new(text).style(style).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Yellow)),
+            ),
+            bubble,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    #[test]
+    fn test_presentation_toggle_and_keys() {
+        let presentation = Presentation::default();
+        let key = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert!(!presentation.record_key(&key));
+
+        assert_eq!(presentation.set_enabled(true, Density::Compact), Some(Density::Spacious));
+        presentation.set_slowdown(2.0);
+        assert_eq!(presentation.scale(Duration::from_millis(30)), Duration::from_millis(60));
+        for _ in 0..10 {
+            presentation.record_key(&key);
+        }
+        assert_eq!(presentation.recent_keys(Instant::now()).len(), MAX_KEYS);
+        assert!(presentation.recent_keys(Instant::now() + KEY_DISPLAY).is_empty());
+
+        assert_eq!(presentation.set_enabled(false, Density::Spacious), Some(Density::Compact));
+        assert_eq!(presentation.scale(Duration::from_millis(30)), Duration::from_millis(30));
+    }
+}
@@ -0,0 +1,391 @@
+//! Recording input sessions and replaying them.
+//!
+//! `Application::record_events(path)` writes every terminal event the app receives
+//! (keys, mouse, resizes, focus, paste) to a text file, stamped with the time since
+//! the start of the run. `Application::replay(path)` runs the app headlessly against
+//! such a file, delivering the same events with the same timing on a backend of the
+//! recorded terminal size, so a bug report that comes with a recording reproduces
+//! without a terminal. The app exits when the recording ends, or earlier on
+//! `Action::Quit`.
+//!
+//! A recording is one event per line, readable and editable by hand:
+//!
+//! ```text
+//! # rat-nexus events v1
+//! size 120 40
+//! 0 resize 120 40
+//! 812 key char:j 0 press
+//! 1030 mouse down-left 14 6 0
+//! 1500 paste hello\nworld
+//! ```
+//!
+//! `Replay` is also an `EventSource`, to replay a session on a real terminal with
+//! `Application::event_source`.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{
+    Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use tokio::sync::mpsc;
+
+use crate::application::{AppContext, Application};
+use crate::component::traits::{AnyComponent, Event};
+use crate::event_source::{EventSource, Polled};
+use crate::state::Entity;
+
+/// First line of every recording.
+const HEADER: &str = "# rat-nexus events v1";
+
+/// Terminal size used to replay a recording that does not state one.
+pub const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+/// Wraps an `EventSource`, writing each event it yields to a recording.
+pub struct Recorder {
+    source: Box<dyn EventSource>,
+    out: BufWriter<File>,
+    start: Instant,
+}
+
+impl Recorder {
+    /// Record the events of `source` to a new file at `path`, starting with the
+    /// current terminal size where it is known.
+    pub fn create(path: impl AsRef<Path>, source: impl EventSource) -> io::Result<Self> {
+        Self::create_boxed(path.as_ref(), Box::new(source))
+    }
+
+    pub(crate) fn create_boxed(path: &Path, source: Box<dyn EventSource>) -> io::Result<Self> {
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(out, "{HEADER}")?;
+        if let Ok((width, height)) = crossterm::terminal::size() {
+            writeln!(out, "size {width} {height}")?;
+        }
+        out.flush()?;
+        Ok(Self { source, out, start: Instant::now() })
+    }
+}
+
+impl EventSource for Recorder {
+    fn poll_event(&mut self, timeout: Duration) -> io::Result<Polled> {
+        let polled = self.source.poll_event(timeout)?;
+        if let Polled::Event(event) = &polled {
+            if let Some(line) = encode(event) {
+                // Flushed per event, so a session that ends in a crash is still complete
+                writeln!(self.out, "{} {line}", self.start.elapsed().as_millis())?;
+                self.out.flush()?;
+            }
+        }
+        Ok(polled)
+    }
+}
+
+/// Events read from a recording, delivered with their recorded timing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    size: Option<(u16, u16)>,
+    events: Vec<(Duration, CrosstermEvent)>,
+    next: usize,
+    speed: f64,
+    start: Option<Instant>,
+}
+
+impl Replay {
+    /// Read the recording at `path`. Fails on unreadable files and malformed lines.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parse a recording. Blank lines and lines starting with `#` are skipped.
+    pub fn parse(data: &str) -> io::Result<Self> {
+        let mut replay = Self { size: None, events: Vec::new(), next: 0, speed: 1.0, start: None };
+        for (number, line) in data.lines().enumerate() {
+            let line = line.trim_start();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("line {}: invalid event `{line}`", number + 1));
+            if let Some(size) = line.strip_prefix("size ") {
+                let (width, height) = size.split_once(' ').ok_or_else(invalid)?;
+                replay.size = Some((width.trim().parse().map_err(|_| invalid())?, height.trim().parse().map_err(|_| invalid())?));
+                continue;
+            }
+            let (millis, event) = line.split_once(' ').ok_or_else(invalid)?;
+            let at = Duration::from_millis(millis.parse().map_err(|_| invalid())?);
+            replay.events.push((at, decode(event).ok_or_else(invalid)?));
+        }
+        replay.events.sort_by_key(|(at, _)| *at);
+        Ok(replay)
+    }
+
+    /// Play back `factor` times faster (e.g. `10.0`), or slower below `1.0`.
+    pub fn speed(mut self, factor: f64) -> Self {
+        if factor > 0.0 {
+            self.speed = factor;
+        }
+        self
+    }
+
+    /// The terminal size at the start of the recording, if it was known.
+    pub fn size(&self) -> Option<(u16, u16)> {
+        self.size
+    }
+
+    /// The recorded events, with their time since the start of the recording.
+    pub fn events(&self) -> &[(Duration, CrosstermEvent)] {
+        &self.events
+    }
+}
+
+impl EventSource for Replay {
+    fn poll_event(&mut self, timeout: Duration) -> io::Result<Polled> {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        let Some((at, event)) = self.events.get(self.next) else {
+            return Ok(Polled::Closed);
+        };
+        let due = start + at.div_f64(self.speed);
+        let now = Instant::now();
+        if due > now {
+            std::thread::sleep((due - now).min(timeout));
+            if due > Instant::now() {
+                return Ok(Polled::Timeout);
+            }
+        }
+        let event = event.clone();
+        self.next += 1;
+        Ok(Polled::Event(event))
+    }
+}
+
+/// Run the application headlessly on a backend of `size`, reading `source` (the
+/// replay, possibly wrapped in a `Recorder`) until it ends or the app quits.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run<E: Send + Sync + 'static>(
+    application: &Application<E>,
+    size: Option<(u16, u16)>,
+    source: Box<dyn EventSource>,
+    app: AppContext,
+    root: Entity<dyn AnyComponent>,
+    re_render_rx: mpsc::UnboundedReceiver<()>,
+    inject_rx: mpsc::UnboundedReceiver<Event>,
+    startup: crate::splash::Startup,
+) -> anyhow::Result<()> {
+    let (width, height) = size.unwrap_or(DEFAULT_SIZE);
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    Application::<E>::mount_root(&app, &root)?;
+
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    tokio::task::spawn_blocking(move || crate::event_source::forward(source, event_tx));
+    application.run_app_loop(app, &mut terminal, root, re_render_rx, inject_rx, event_rx, startup, false).await
+}
+
+const KEY_NAMES: [(KeyCode, &str); 16] = [
+    (KeyCode::Backspace, "backspace"),
+    (KeyCode::Enter, "enter"),
+    (KeyCode::Left, "left"),
+    (KeyCode::Right, "right"),
+    (KeyCode::Up, "up"),
+    (KeyCode::Down, "down"),
+    (KeyCode::Home, "home"),
+    (KeyCode::End, "end"),
+    (KeyCode::PageUp, "pageup"),
+    (KeyCode::PageDown, "pagedown"),
+    (KeyCode::Tab, "tab"),
+    (KeyCode::BackTab, "backtab"),
+    (KeyCode::Delete, "delete"),
+    (KeyCode::Insert, "insert"),
+    (KeyCode::Esc, "esc"),
+    (KeyCode::Null, "null"),
+];
+
+const MOUSE_BUTTONS: [(MouseButton, &str); 3] =
+    [(MouseButton::Left, "left"), (MouseButton::Right, "right"), (MouseButton::Middle, "middle")];
+
+/// One event as a line of a recording; `None` for events that are not recorded
+/// (media and modifier keys).
+fn encode(event: &CrosstermEvent) -> Option<String> {
+    Some(match event {
+        CrosstermEvent::Key(key) => {
+            let code = match key.code {
+                KeyCode::Char(c) => format!("char:{c}"),
+                KeyCode::F(n) => format!("f{n}"),
+                code => KEY_NAMES.iter().find(|(known, _)| *known == code)?.1.to_string(),
+            };
+            let kind = match key.kind {
+                KeyEventKind::Press => "press",
+                KeyEventKind::Repeat => "repeat",
+                KeyEventKind::Release => "release",
+            };
+            format!("key {code} {} {kind}", key.modifiers.bits())
+        }
+        CrosstermEvent::Mouse(mouse) => {
+            let button = |button| MOUSE_BUTTONS.iter().find(|(known, _)| *known == button).map(|(_, name)| *name);
+            let kind = match mouse.kind {
+                MouseEventKind::Down(b) => format!("down-{}", button(b)?),
+                MouseEventKind::Up(b) => format!("up-{}", button(b)?),
+                MouseEventKind::Drag(b) => format!("drag-{}", button(b)?),
+                MouseEventKind::Moved => "moved".into(),
+                MouseEventKind::ScrollDown => "scroll-down".into(),
+                MouseEventKind::ScrollUp => "scroll-up".into(),
+                MouseEventKind::ScrollLeft => "scroll-left".into(),
+                MouseEventKind::ScrollRight => "scroll-right".into(),
+            };
+            format!("mouse {kind} {} {} {}", mouse.column, mouse.row, mouse.modifiers.bits())
+        }
+        CrosstermEvent::Resize(width, height) => format!("resize {width} {height}"),
+        CrosstermEvent::FocusGained => "focus-gained".into(),
+        CrosstermEvent::FocusLost => "focus-lost".into(),
+        CrosstermEvent::Paste(text) => {
+            let escaped = text.replace('\\', "\\\\").replace('\n', "\\n").replace('\r', "\\r");
+            format!("paste {escaped}")
+        }
+    })
+}
+
+/// Parse the event part of a recorded line.
+fn decode(line: &str) -> Option<CrosstermEvent> {
+    let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let mut fields = rest.split(' ');
+    let mut number = || fields.next()?.parse::<u16>().ok();
+    Some(match kind {
+        "key" => {
+            // The code may itself be a space (`char: `), so split it off by its prefix
+            let (code, rest) = match rest.strip_prefix("char:") {
+                Some(rest) => {
+                    let c = rest.chars().next()?;
+                    (KeyCode::Char(c), rest[c.len_utf8()..].trim_start())
+                }
+                None => {
+                    let (name, rest) = rest.split_once(' ')?;
+                    let code = match name.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                        Some(n) => KeyCode::F(n),
+                        None => KEY_NAMES.iter().find(|(_, known)| *known == name)?.0,
+                    };
+                    (code, rest)
+                }
+            };
+            let (modifiers, kind) = rest.split_once(' ')?;
+            let kind = match kind {
+                "press" => KeyEventKind::Press,
+                "repeat" => KeyEventKind::Repeat,
+                "release" => KeyEventKind::Release,
+                _ => return None,
+            };
+            CrosstermEvent::Key(KeyEvent::new_with_kind(code, KeyModifiers::from_bits_truncate(modifiers.parse().ok()?), kind))
+        }
+        "mouse" => {
+            let (action, rest) = rest.split_once(' ')?;
+            let button = |name: &str| MOUSE_BUTTONS.iter().find(|(_, known)| *known == name).map(|(button, _)| *button);
+            let kind = match action.split_once('-') {
+                Some(("down", b)) => MouseEventKind::Down(button(b)?),
+                Some(("up", b)) => MouseEventKind::Up(button(b)?),
+                Some(("drag", b)) => MouseEventKind::Drag(button(b)?),
+                Some(("scroll", "down")) => MouseEventKind::ScrollDown,
+                Some(("scroll", "up")) => MouseEventKind::ScrollUp,
+                Some(("scroll", "left")) => MouseEventKind::ScrollLeft,
+                Some(("scroll", "right")) => MouseEventKind::ScrollRight,
+                None if action == "moved" => MouseEventKind::Moved,
+                _ => return None,
+            };
+            let mut fields = rest.split(' ');
+            let mut number = || fields.next()?.parse::<u16>().ok();
+            let (column, row) = (number()?, number()?);
+            let modifiers = KeyModifiers::from_bits_truncate(number()? as u8);
+            CrosstermEvent::Mouse(MouseEvent { kind, column, row, modifiers })
+        }
+        "resize" => CrosstermEvent::Resize(number()?, number()?),
+        "focus-gained" => CrosstermEvent::FocusGained,
+        "focus-lost" => CrosstermEvent::FocusLost,
+        "paste" => {
+            let mut text = String::with_capacity(rest.len());
+            let mut chars = rest.chars();
+            while let Some(c) = chars.next() {
+                text.push(match c {
+                    '\\' => match chars.next()? {
+                        'n' => '\n',
+                        'r' => '\r',
+                        '\\' => '\\',
+                        _ => return None,
+                    },
+                    c => c,
+                });
+            }
+            CrosstermEvent::Paste(text)
+        }
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_source::ChannelEvents;
+
+    fn events() -> Vec<CrosstermEvent> {
+        vec![
+            CrosstermEvent::Key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::CONTROL)),
+            CrosstermEvent::Key(KeyEvent::new_with_kind(KeyCode::F(5), KeyModifiers::NONE, KeyEventKind::Release)),
+            CrosstermEvent::Key(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT)),
+            CrosstermEvent::Mouse(MouseEvent {
+                kind: MouseEventKind::Drag(MouseButton::Right),
+                column: 3,
+                row: 9,
+                modifiers: KeyModifiers::ALT,
+            }),
+            CrosstermEvent::Mouse(MouseEvent { kind: MouseEventKind::Moved, column: 0, row: 1, modifiers: KeyModifiers::NONE }),
+            CrosstermEvent::Resize(100, 30),
+            CrosstermEvent::FocusLost,
+            CrosstermEvent::Paste("a\\b\nc d".into()),
+        ]
+    }
+
+    #[test]
+    fn test_events_round_trip() {
+        for event in events() {
+            let line = encode(&event).unwrap();
+            assert!(!line.contains('\n'), "{line}");
+            assert_eq!(decode(&line), Some(event), "{line}");
+        }
+        assert_eq!(encode(&CrosstermEvent::Key(KeyEvent::from(KeyCode::CapsLock))), None);
+        assert!(Replay::parse("12 key nope 0 press").unwrap_err().to_string().contains("line 1"));
+    }
+
+    #[test]
+    fn test_recorder_writes_what_replay_reads() {
+        let path = std::env::temp_dir().join(format!("rat_nexus_recording_{}.txt", std::process::id()));
+        let (tx, source) = ChannelEvents::channel();
+        let mut recorder = Recorder::create(&path, source).unwrap();
+        for event in events() {
+            tx.send(event).unwrap();
+            assert!(matches!(recorder.poll_event(Duration::from_millis(1)).unwrap(), Polled::Event(_)));
+        }
+        drop(recorder);
+
+        let mut replay = Replay::open(&path).unwrap().speed(1000.0);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(replay.events().iter().map(|(_, e)| e.clone()).collect::<Vec<_>>(), events());
+        let mut replayed = Vec::new();
+        loop {
+            match replay.poll_event(Duration::from_millis(10)).unwrap() {
+                Polled::Event(event) => replayed.push(event),
+                Polled::Timeout => {}
+                Polled::Closed => break,
+            }
+        }
+        assert_eq!(replayed, events());
+    }
+
+    #[test]
+    fn test_replay_waits_for_recorded_time() {
+        let mut replay = Replay::parse("size 40 10\n0 key char:a 0 press\n60 key enter 0 press").unwrap();
+        assert_eq!(replay.size(), Some((40, 10)));
+        assert!(matches!(replay.poll_event(Duration::ZERO).unwrap(), Polled::Event(_)));
+        assert_eq!(replay.poll_event(Duration::from_millis(1)).unwrap(), Polled::Timeout);
+        assert!(matches!(replay.poll_event(Duration::from_millis(200)).unwrap(), Polled::Event(_)));
+        assert_eq!(replay.poll_event(Duration::ZERO).unwrap(), Polled::Closed);
+    }
+}
@@ -0,0 +1,254 @@
+//! Persistent chrome (header, sidebar) around the routed content of `define_app!`.
+//!
+//! `ChromeLayout` describes which chrome slots exist and how large they are; `split`
+//! turns the frame into `ChromeAreas`. The Root generated by `define_app!` renders each
+//! slot into its area, delivers keys to the focused `Slot` and mouse events to the slot
+//! under the pointer. `F6` cycles focus between slots.
+
+use crossterm::event::{KeyCode, KeyEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+
+use crate::component::traits::Event;
+
+/// A region of the application chrome.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Slot {
+    Header,
+    Sidebar,
+    /// The routed page.
+    #[default]
+    Content,
+}
+
+/// Where the Root generated by `define_app!` delivers an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventTarget {
+    /// Handled by the layout itself (focus cycling) or outside every slot.
+    Consumed,
+    /// Only the given slot.
+    Slot(Slot),
+    /// Every slot, then the page.
+    All,
+}
+
+/// Sizes of the chrome slots; a slot without a size is absent.
+///
+/// # Example
+/// ```ignore
+/// let areas = ChromeLayout::new().header(3).sidebar(24).split(frame.area());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChromeLayout {
+    header: Option<u16>,
+    sidebar: Option<u16>,
+}
+
+impl ChromeLayout {
+    /// A layout without chrome: the content fills the frame.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a header of `height` rows across the top.
+    pub fn header(mut self, height: u16) -> Self {
+        self.header = Some(height);
+        self
+    }
+
+    /// Add a sidebar of `width` columns on the left, below the header.
+    pub fn sidebar(mut self, width: u16) -> Self {
+        self.sidebar = Some(width);
+        self
+    }
+
+    /// Whether no chrome slot is configured.
+    pub fn is_empty(&self) -> bool {
+        self.header.is_none() && self.sidebar.is_none()
+    }
+
+    /// The configured slots in focus order, ending with `Slot::Content`.
+    pub fn slots(&self) -> Vec<Slot> {
+        let mut slots = Vec::with_capacity(3);
+        if self.header.is_some() {
+            slots.push(Slot::Header);
+        }
+        if self.sidebar.is_some() {
+            slots.push(Slot::Sidebar);
+        }
+        slots.push(Slot::Content);
+        slots
+    }
+
+    /// The slot after `focus` in focus order, wrapping around.
+    pub fn next_focus(&self, focus: Slot) -> Slot {
+        let slots = self.slots();
+        let index = slots.iter().position(|slot| *slot == focus).unwrap_or(0);
+        slots[(index + 1) % slots.len()]
+    }
+
+    /// Whether `key` cycles focus between slots. Only set when chrome is configured.
+    pub fn is_focus_key(&self, key: &KeyEvent) -> bool {
+        !self.is_empty() && key.code == KeyCode::F(6)
+    }
+
+    /// Decide where `event` goes: keys to `focus`, mouse, click and drop events to the
+    /// slot under the pointer, everything else to all slots. Cycles `focus` on `F6` and moves it to
+    /// the slot that is clicked.
+    pub fn target(&self, event: &Event, areas: &ChromeAreas, focus: &mut Slot) -> EventTarget {
+        match event {
+            Event::Key(key) if self.is_focus_key(key) => {
+                *focus = self.next_focus(*focus);
+                EventTarget::Consumed
+            }
+            Event::Key(_) => EventTarget::Slot(*focus),
+            _ => match event.position() {
+                Some(_) if self.is_empty() => EventTarget::Slot(Slot::Content),
+                Some(position) => match areas.slot_at(position.x, position.y) {
+                    Some(slot) => {
+                        if matches!(event, Event::Mouse(mouse) if matches!(mouse.kind, MouseEventKind::Down(_))) {
+                            *focus = slot;
+                        }
+                        EventTarget::Slot(slot)
+                    }
+                    None => EventTarget::Consumed,
+                },
+                None => EventTarget::All,
+            },
+        }
+    }
+
+    /// Split `area` into the slot areas. Absent slots get an empty area.
+    pub fn split(&self, area: Rect) -> ChromeAreas {
+        let [header, body] = Layout::vertical([Constraint::Length(self.header.unwrap_or(0)), Constraint::Min(0)]).areas(area);
+        let [sidebar, content] = Layout::horizontal([Constraint::Length(self.sidebar.unwrap_or(0)), Constraint::Min(0)]).areas(body);
+        ChromeAreas { header, sidebar, content }
+    }
+}
+
+/// The areas of the chrome slots for one frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChromeAreas {
+    pub header: Rect,
+    pub sidebar: Rect,
+    pub content: Rect,
+}
+
+impl ChromeAreas {
+    /// The area of `slot`.
+    pub fn get(&self, slot: Slot) -> Rect {
+        match slot {
+            Slot::Header => self.header,
+            Slot::Sidebar => self.sidebar,
+            Slot::Content => self.content,
+        }
+    }
+
+    /// The slot containing the cell at (`column`, `row`), if any.
+    pub fn slot_at(&self, column: u16, row: u16) -> Option<Slot> {
+        let position = Position::new(column, row);
+        [Slot::Header, Slot::Sidebar, Slot::Content]
+            .into_iter()
+            .find(|slot| self.get(*slot).contains(position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_and_focus_order() {
+        let layout = ChromeLayout::new().header(2).sidebar(10);
+        let areas = layout.split(Rect::new(0, 0, 40, 12));
+        assert_eq!(areas.header, Rect::new(0, 0, 40, 2));
+        assert_eq!(areas.sidebar, Rect::new(0, 2, 10, 10));
+        assert_eq!(areas.content, Rect::new(10, 2, 30, 10));
+        assert_eq!(areas.slot_at(5, 5), Some(Slot::Sidebar));
+        assert_eq!(areas.slot_at(39, 1), Some(Slot::Header));
+
+        assert_eq!(layout.next_focus(Slot::Content), Slot::Header);
+        let sidebar_only = ChromeLayout::new().sidebar(4);
+        assert_eq!(sidebar_only.next_focus(Slot::Sidebar), Slot::Content);
+        assert_eq!(sidebar_only.next_focus(Slot::Content), Slot::Sidebar);
+        assert_eq!(ChromeLayout::new().split(Rect::new(0, 0, 8, 3)).content, Rect::new(0, 0, 8, 3));
+    }
+
+    #[allow(dead_code)]
+    mod app {
+        use ratatui::Frame;
+
+        use crate::{Action, Context, Event};
+
+        fn write(frame: &mut Frame, cx: &Context<impl Send + Sync + ?Sized>, text: &str) {
+            let area = cx.area(frame);
+            frame.buffer_mut().set_string(area.x, area.y, text, ratatui::style::Style::default());
+        }
+
+        #[derive(Default)]
+        pub struct Header;
+
+        impl crate::Component for Header {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                write(frame, cx, "HEADER");
+            }
+        }
+
+        #[derive(Default)]
+        pub struct Side;
+
+        impl crate::Component for Side {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                write(frame, cx, "nav");
+            }
+
+            fn handle_event(&mut self, event: Event, _cx: &mut Context<Self>) -> Option<Action> {
+                matches!(event, Event::Key(_)).then(|| Action::Navigate("Second".into()))
+            }
+        }
+
+        #[derive(Default)]
+        pub struct First;
+
+        impl crate::Component for First {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                write(frame, cx, "first");
+            }
+        }
+
+        #[derive(Default)]
+        pub struct Second;
+
+        impl crate::Component for Second {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                write(frame, cx, "second");
+            }
+        }
+
+        crate::define_app! {
+            #[Root(default=First)]
+            #[Chrome(header: Header = 1, sidebar: Side = 4)]
+            pub struct Root {
+                First => first: First,
+                Second => second: Second,
+            }
+        }
+    }
+
+    #[test]
+    fn test_define_app_chrome_slots() {
+        use crate::testing::Harness;
+
+        let mut harness = Harness::build(20, 4, app::Root::new);
+        assert_eq!(harness.text(), "HEADER\nnav first\n\n");
+        assert_eq!(harness.region("content"), Rect::new(4, 1, 16, 3));
+
+        harness.type_str("x");
+        harness.expect_text("first");
+        harness.type_str("<F6>");
+        assert_eq!(harness.read(|root| root.focused_slot()), Slot::Header);
+        harness.click("side", 0, 2);
+        assert_eq!(harness.read(|root| root.focused_slot()), Slot::Sidebar);
+        harness.type_str("x");
+        harness.expect_text("nav second");
+    }
+}
@@ -1,7 +1,24 @@
 //! Router module.
 //!
-//! Provides navigation management with `Router` struct and `define_routes!` macro.
+//! Provides navigation management with `Router` struct and `define_routes!` macro,
+//! plus `ViewState` for restoring per-page UI state across navigation,
+//! `ChromeLayout` for persistent header/sidebar slots around the routed content,
+//! `PageStatus` for loading and error states of routed pages and `NavigationEvent`
+//! notifications about route changes.
 
+pub mod layout;
+pub mod navigation;
+pub mod registry;
+pub mod status;
 pub mod traits;
+pub mod view_state;
 
+pub use layout::{ChromeAreas, ChromeLayout, EventTarget, Slot};
+pub use navigation::{NavigationEvent, NavigationTrigger, NAVIGATED};
+pub use registry::RouteInfo;
+pub use status::{PageStatus, StatusView};
 pub use traits::{Route, Router};
+
+/// Name of the custom event emitted by `AppContext::navigate`, carrying the route name as a `String`.
+pub const NAVIGATE: &str = "rat_nexus::navigate";
+pub use view_state::{ViewState, ViewStateStore};
@@ -0,0 +1,167 @@
+//! Notifications about route changes.
+//!
+//! After every route change `define_app!` and `AppShell` emit a `NavigationEvent` as
+//! the custom event `NAVIGATED`. It is delivered to the whole tree on the next loop
+//! iteration (chrome slots included), so breadcrumbs, status bars, tours and telemetry
+//! can follow navigation without hooking into the dispatcher.
+//!
+//! # Example
+//! ```ignore
+//! fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+//!     if let Some(nav) = event.custom::<NavigationEvent>() {
+//!         self.breadcrumb.push(nav.to.clone());
+//!     }
+//!     None
+//! }
+//! ```
+
+use std::time::SystemTime;
+
+/// Name of the custom event carrying a `NavigationEvent`.
+pub const NAVIGATED: &str = "rat_nexus::navigated";
+
+/// What caused a route change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NavigationTrigger {
+    /// A component returned `Action::Navigate`.
+    Action,
+    /// Code asked for it: `AppContext::navigate`, or `AppShell::navigate` called directly.
+    Request,
+    /// `Action::Back` returned to the previous route.
+    Back,
+}
+
+/// A route change that happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavigationEvent {
+    /// The route left.
+    pub from: String,
+    /// The route entered.
+    pub to: String,
+    /// What caused the change.
+    pub trigger: NavigationTrigger,
+    /// When the change happened.
+    pub at: SystemTime,
+}
+
+impl NavigationEvent {
+    /// A change from `from` to `to`, happening now.
+    pub fn new(from: impl Into<String>, to: impl Into<String>, trigger: NavigationTrigger) -> Self {
+        Self { from: from.into(), to: to.into(), trigger, at: SystemTime::now() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(dead_code)]
+    mod app {
+        use ratatui::Frame;
+
+        use super::{NavigationEvent, NavigationTrigger};
+        use crate::{Action, Context, Event};
+
+        fn write(frame: &mut Frame, cx: &Context<impl Send + Sync + ?Sized>, text: &str) {
+            let area = cx.area(frame);
+            frame.buffer_mut().set_string(area.x, area.y, text, ratatui::style::Style::default());
+        }
+
+        /// Shows the last navigation, as a breadcrumb would.
+        #[derive(Default)]
+        pub struct Crumb(String);
+
+        impl crate::Component for Crumb {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                write(frame, cx, &self.0);
+            }
+
+            fn handle_event(&mut self, event: Event, _cx: &mut Context<Self>) -> Option<Action> {
+                if let Some(nav) = event.custom::<NavigationEvent>() {
+                    let trigger = match nav.trigger {
+                        NavigationTrigger::Action => "action",
+                        NavigationTrigger::Request => "request",
+                        NavigationTrigger::Back => "back",
+                    };
+                    self.0 = format!("{}>{} {trigger}", nav.from, nav.to);
+                }
+                None
+            }
+        }
+
+        #[derive(Default)]
+        pub struct Menu;
+
+        impl crate::Component for Menu {
+            fn render(&mut self, _frame: &mut Frame, _cx: &mut Context<Self>) {}
+
+            fn handle_event(&mut self, event: Event, cx: &mut Context<Self>) -> Option<Action> {
+                match event {
+                    Event::Key(key) if key.code == crossterm::event::KeyCode::Char('a') => {
+                        Some(Action::Navigate("Detail".into()))
+                    }
+                    Event::Key(key) if key.code == crossterm::event::KeyCode::Char('r') => {
+                        cx.navigate("Detail");
+                        None
+                    }
+                    Event::Key(key) if key.code == crossterm::event::KeyCode::Char('z') => {
+                        Some(Action::Navigate("Nowhere".into()))
+                    }
+                    _ => None,
+                }
+            }
+        }
+
+        #[derive(Default)]
+        pub struct Detail;
+
+        impl crate::Component for Detail {
+            fn render(&mut self, _frame: &mut Frame, _cx: &mut Context<Self>) {}
+
+            fn handle_event(&mut self, event: Event, _cx: &mut Context<Self>) -> Option<Action> {
+                matches!(event, Event::Key(_)).then_some(Action::Back)
+            }
+        }
+
+        crate::define_app! {
+            #[Chrome(header: Crumb = 1)]
+            Menu => menu: Menu,
+            Detail => detail: Detail,
+        }
+    }
+
+    #[test]
+    fn test_define_app_announces_route_changes() {
+        use crate::testing::Harness;
+
+        let mut harness = Harness::build(20, 2, app::Root::new);
+        harness.type_str("a");
+        assert_eq!(harness.region_text("crumb"), "Menu>Detail action");
+        harness.type_str("x");
+        assert_eq!(harness.region_text("crumb"), "Detail>Menu back");
+        harness.type_str("r");
+        assert_eq!(harness.region_text("crumb"), "Menu>Detail request");
+    }
+
+    #[test]
+    fn test_unknown_route_reaches_the_error_handler() {
+        use crate::testing::Harness;
+        use std::sync::{Arc, Mutex};
+
+        let mut harness = Harness::build(20, 2, app::Root::new);
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&errors);
+        harness.app().on_error(move |error, _| seen.lock().unwrap().push((error.hook(), error.into_error())));
+        harness.type_str("z");
+        let errors = errors.lock().unwrap();
+        assert!(matches!(errors[..], [("navigate", crate::Error::RouteNotFound { ref route, .. })] if route == "Nowhere"));
+    }
+
+    #[test]
+    fn test_navigation_event_is_timestamped() {
+        let before = SystemTime::now();
+        let event = NavigationEvent::new("A", "B", NavigationTrigger::Request);
+        assert!(event.at >= before);
+        assert_eq!((event.from.as_str(), event.to.as_str()), ("A", "B"));
+    }
+}
@@ -0,0 +1,106 @@
+//! Application-wide list of routes with display metadata, and the active route.
+//!
+//! `define_app!` and `AppShell` register their routes on mount and report the route
+//! they show, so navigation UIs (the command palette, `widgets::Sidebar`) stay in sync
+//! with the router without being wired to it.
+
+use std::sync::{Arc, RwLock};
+
+/// A route and how navigation UIs present it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteInfo {
+    /// Name passed to `Action::Navigate`.
+    pub name: String,
+    /// Display label; the name unless set.
+    pub label: String,
+    /// Short icon shown before the label, e.g. an emoji or a Nerd Font glyph.
+    pub icon: Option<String>,
+}
+
+impl RouteInfo {
+    /// A route labeled with its name and without an icon.
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self { label: name.clone(), name, icon: None }
+    }
+
+    /// Set the display label.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Set the icon.
+    pub fn icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    routes: Vec<RouteInfo>,
+    active: Option<String>,
+}
+
+/// Shared route registry held by `AppContext`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RouteRegistry {
+    inner: Arc<RwLock<Inner>>,
+}
+
+impl RouteRegistry {
+    /// Add routes by name, keeping the metadata of routes already known.
+    pub(crate) fn register_names(&self, names: &[String]) {
+        if let Ok(mut inner) = self.inner.write() {
+            for name in names {
+                if !inner.routes.iter().any(|route| route.name.eq_ignore_ascii_case(name)) {
+                    inner.routes.push(RouteInfo::new(name.as_str()));
+                }
+            }
+        }
+    }
+
+    /// Add a route, or replace the metadata of a known one in place.
+    pub(crate) fn describe(&self, info: RouteInfo) {
+        if let Ok(mut inner) = self.inner.write() {
+            match inner.routes.iter_mut().find(|route| route.name.eq_ignore_ascii_case(&info.name)) {
+                Some(route) => *route = info,
+                None => inner.routes.push(info),
+            }
+        }
+    }
+
+    pub(crate) fn routes(&self) -> Vec<RouteInfo> {
+        self.inner.read().map(|inner| inner.routes.clone()).unwrap_or_default()
+    }
+
+    pub(crate) fn set_active(&self, name: &str) {
+        if let Ok(mut inner) = self.inner.write() {
+            inner.active = Some(name.to_string());
+        }
+    }
+
+    pub(crate) fn active(&self) -> Option<String> {
+        self.inner.read().ok().and_then(|inner| inner.active.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_survives_registration() {
+        let registry = RouteRegistry::default();
+        registry.describe(RouteInfo::new("Monitor").icon("📈").label("System monitor"));
+        registry.register_names(&["Menu".to_string(), "monitor".to_string()]);
+        let routes = registry.routes();
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].label, "System monitor");
+        assert_eq!(routes[1], RouteInfo::new("Menu"));
+
+        registry.set_active("Menu");
+        assert_eq!(registry.active().as_deref(), Some("Menu"));
+    }
+}
@@ -0,0 +1,185 @@
+//! Loading and error states of routed pages.
+//!
+//! A page reports its state through `Component::status`: `Loading` while an async
+//! preload started in `on_enter` is running, `Failed` when a fallible step of its
+//! lifecycle errored. For routes that declare them, the Root generated by `define_app!`
+//! then shows a loading skeleton or an error page in place of the page.
+//!
+//! # Example
+//! ```ignore
+//! define_app! {
+//!     Menu => menu: Menu,
+//!     #[loading(Skeleton)]
+//!     #[error(ErrorPage)]
+//!     Report => report: ReportPage,
+//! }
+//!
+//! impl Component for ReportPage {
+//!     fn on_enter(&mut self, cx: &mut Context<Self>) {
+//!         self.state.set(PageStatus::Loading);
+//!         let state = self.state.clone();
+//!         cx.spawn(move |_| async move {
+//!             match fetch_report().await {
+//!                 Ok(report) => state.set_ready(report),
+//!                 Err(e) => state.set(PageStatus::Failed(e.to_string())),
+//!             }
+//!         });
+//!     }
+//!
+//!     fn status(&self) -> PageStatus {
+//!         self.state.status()
+//!     }
+//!     // ...
+//! }
+//! ```
+
+/// Whether a page can be shown.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum PageStatus {
+    /// The page renders itself.
+    #[default]
+    Ready,
+    /// Data the page needs is still loading.
+    Loading,
+    /// Loading or another lifecycle step failed, with a message for the user.
+    Failed(String),
+}
+
+impl PageStatus {
+    /// Whether the page renders itself.
+    pub fn is_ready(&self) -> bool {
+        matches!(self, PageStatus::Ready)
+    }
+}
+
+/// A component shown in place of a page that is loading or failed.
+///
+/// `show` is called before each render with the route name and status, e.g. so an
+/// error page can display the message. Loading and error components receive events
+/// before the page; the page still gets those they ignore (e.g. a retry key).
+pub trait StatusView: crate::Component + Default {
+    /// Update the view for `route` in `status`.
+    fn show(&mut self, route: &str, status: &PageStatus) {
+        let _ = (route, status);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(dead_code)]
+    mod app {
+        use ratatui::Frame;
+
+        use super::{PageStatus, StatusView};
+        use crate::{Action, Context, Event};
+
+        fn write(frame: &mut Frame, cx: &Context<impl Send + Sync + ?Sized>, text: &str) {
+            let area = cx.area(frame);
+            frame.buffer_mut().set_string(area.x, area.y, text, ratatui::style::Style::default());
+        }
+
+        #[derive(Default)]
+        pub struct Menu;
+
+        impl crate::Component for Menu {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                write(frame, cx, "menu");
+            }
+
+            fn handle_event(&mut self, event: Event, _cx: &mut Context<Self>) -> Option<Action> {
+                matches!(event, Event::Key(_)).then(|| Action::Navigate("Report".into()))
+            }
+        }
+
+        #[derive(Default)]
+        pub struct Report {
+            status: PageStatus,
+        }
+
+        impl crate::Component for Report {
+            fn on_enter(&mut self, _cx: &mut Context<Self>) {
+                self.status = PageStatus::Loading;
+            }
+
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                write(frame, cx, "report");
+            }
+
+            fn handle_event(&mut self, event: Event, _cx: &mut Context<Self>) -> Option<Action> {
+                if let Event::Key(key) = event {
+                    match key.code {
+                        crossterm::event::KeyCode::Char('f') => self.status = PageStatus::Failed("boom".into()),
+                        crossterm::event::KeyCode::Char('r') => self.status = PageStatus::Ready,
+                        _ => {}
+                    }
+                }
+                None
+            }
+
+            fn status(&self) -> PageStatus {
+                self.status.clone()
+            }
+        }
+
+        #[derive(Default)]
+        pub struct Skeleton;
+
+        impl crate::Component for Skeleton {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                write(frame, cx, "loading...");
+            }
+        }
+
+        impl StatusView for Skeleton {}
+
+        #[derive(Default)]
+        pub struct ErrorPage(String);
+
+        impl crate::Component for ErrorPage {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                write(frame, cx, &self.0);
+            }
+
+            fn handle_event(&mut self, event: Event, _cx: &mut Context<Self>) -> Option<Action> {
+                matches!(event, Event::Key(key) if key.code == crossterm::event::KeyCode::Esc).then_some(Action::Back)
+            }
+        }
+
+        impl StatusView for ErrorPage {
+            fn show(&mut self, route: &str, status: &PageStatus) {
+                if let PageStatus::Failed(message) = status {
+                    self.0 = format!("{route}: {message}");
+                }
+            }
+        }
+
+        crate::define_app! {
+            Menu => menu: Menu,
+            #[loading(Skeleton)]
+            #[error(ErrorPage)]
+            Report => report: Report,
+        }
+    }
+
+    #[test]
+    fn test_loading_and_error_views_replace_page() {
+        use crate::testing::Harness;
+
+        assert!(PageStatus::default().is_ready());
+        let mut harness = Harness::build(20, 1, app::Root::new);
+        assert_eq!(harness.text(), "menu");
+
+        harness.type_str("x");
+        assert_eq!(harness.text(), "loading...");
+        harness.type_str("f");
+        assert_eq!(harness.text(), "Report: boom");
+        harness.type_str("r");
+        assert_eq!(harness.text(), "report");
+
+        // The error view's actions take precedence over the page
+        harness.type_str("f<Esc>");
+        assert_eq!(harness.text(), "menu");
+    }
+}
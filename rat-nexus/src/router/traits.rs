@@ -124,12 +124,16 @@ macro_rules! define_routes {
 /// This macro generates a Root component that automatically handles:
 /// - RootRoute enum definition
 /// - Root struct with router and all page fields
-/// - Root::new(cx) with automatic page construction via Default trait
+/// - Root::new(cx) building each page from its constructor expression or Default
+///   (Root::try_new(cx) when a page is built with `= construct`)
 /// - Complete Component implementation with routing and lifecycle dispatch
 /// - Navigation action handling
+/// - Capture/restore of page view state for pages implementing `ViewState`
+/// - Optional persistent chrome (header, sidebar) around the routed page
+/// - Optional per-route loading and error views for pages that are not ready
 ///
-/// All components are created with Default::default() and can be customized
-/// in their on_mount() lifecycle method.
+/// Pages without a constructor expression, chrome and loading/error views are created
+/// with Default::default() and can be customized in their on_mount() lifecycle method.
 ///
 /// Minimal syntax - just list the routes and page types!
 ///
@@ -147,37 +151,123 @@ macro_rules! define_routes {
 /// // Automatically creates:
 /// // - `enum RootRoute { Menu, Monitor, Timer }`
 /// // - `pub struct Root { router, menu, monitor, timer }`
-/// // - `impl Root { fn new() -> Self }`
+/// // - `impl Root { fn new(cx: &AppContext) -> Self }`
 /// // - `impl Component for Root` with full routing
 ///
 /// // In main.rs:
-/// // let root = Root::new();
+/// // let root = Root::new(cx);
 /// ```
+///
+/// # Constructors
+/// `= expr` after a page type builds the page with `expr` instead of `Default`, so a
+/// page can own its entities from the start rather than hold `Option`s filled in
+/// `on_mount`. The expression is a call (`Page::new(..)`) or a struct literal
+/// (`Page { .. }`) and may use `cx`, the `&AppContext` passed to `Root::new`.
+///
+/// ```ignore
+/// define_app! {
+///     Menu => menu: Menu,
+///     Monitor => monitor: MonitorPage = MonitorPage::new(cx),
+///     Timer => timer: TimerPage = TimerPage { laps: cx.new_entity(Vec::new()) },
+/// }
+/// ```
+///
+/// `= construct` builds the page with `cx.construct()` from the dependencies it declares
+/// with `FromContext` (see `inject`). Resolving them can fail, so an app with such pages
+/// gets `Root::try_new(cx)`, which reports every missing registration, instead of
+/// `Root::new`. Register the dependencies before building the root:
+///
+/// ```ignore
+/// define_app! {
+///     Menu => menu: Menu,
+///     Monitor => monitor: MonitorPage = construct,
+/// }
+///
+/// Application::new().run(|cx| {
+///     cx.set(cx.new_entity(MonitorState::default()));
+///     cx.set_root(Root::try_new(cx)?)?;
+///     Ok(())
+/// })
+/// ```
+///
+/// # Chrome
+/// `#[Chrome(...)]` declares components that stay on screen across routes: a `header`
+/// of the given height and/or a `sidebar` of the given width (a literal, a constant or
+/// a `{ block }`). The page renders into the remaining area, which it gets from
+/// `cx.area(frame)`. Keys go to the focused slot (initially the page; `F6` cycles
+/// focus, clicking a slot focuses it), mouse events to the slot under the pointer and
+/// everything else to all slots. When focus moves, the slot losing it gets `on_blur`
+/// and the one gaining it `on_focus`. Actions returned by chrome, such as
+/// `Action::Navigate`, are handled like the page's.
+///
+/// ```ignore
+/// define_app! {
+///     #[Chrome(header: HeaderBar = 1, sidebar: SideNav = 24)]
+///     Menu => menu: Menu,
+///     Timer => timer: TimerPage,
+/// }
+/// ```
+///
+/// # Loading and error views
+/// `#[loading(Ty)]` and `#[error(Ty)]` before a route declare `StatusView` components
+/// shown in place of the page while its `Component::status` is `PageStatus::Loading`
+/// or `PageStatus::Failed`, e.g. while data preloaded in `on_enter` arrives. They get
+/// events before the page; see `router::status`.
+///
+/// ```ignore
+/// define_app! {
+///     Menu => menu: Menu,
+///     #[loading(Skeleton)]
+///     #[error(ErrorPage)]
+///     Report => report: ReportPage,
+/// }
+/// ```
+///
+/// # Navigation events
+/// Every route change is announced with a `NavigationEvent` (see `router::navigation`),
+/// delivered to the chrome and the page on the next loop iteration.
 #[macro_export]
 macro_rules! define_app {
     // Syntax 1: Simple - just routes, first route is default
     (
+        $(#[Chrome($(header: $header:ty = $header_height:tt $(,)?)? $(sidebar: $sidebar:ty = $sidebar_width:tt $(,)?)?)])?
         $(
-            $route:ident => $field:ident : $page:ty
+            $(#[loading($loading:ty)])?
+            $(#[error($error:ty)])?
+            $route:ident => $field:ident : $page:ty $(= $ctor:ident $(:: $ctor_path:ident)* $(($($ctor_call:tt)*))? $({$($ctor_fields:tt)*})?)?
         ),* $(,)?
     ) => {
-        define_app!(@impl (Menu) $($route => $field : $page),*);
+        $crate::define_app!(@impl (Menu)
+            [$($(header: $header = $header_height)?)?]
+            [$($(sidebar: $sidebar = $sidebar_width)?)?]
+            $([$($loading)?] [$($error)?] $route => $field : $page [$($ctor $(:: $ctor_path)* $(($($ctor_call)*))? $({$($ctor_fields)*})?)?]),*);
     };
 
     // Syntax 2: Full - with #[Root(default=...)] attribute
     (
         #[Root(default=$default_route:ident)]
+        $(#[Chrome($(header: $header:ty = $header_height:tt $(,)?)? $(sidebar: $sidebar:ty = $sidebar_width:tt $(,)?)?)])?
         pub struct Root {
             $(
-                $route:ident => $field:ident : $page:ty
+                $(#[loading($loading:ty)])?
+                $(#[error($error:ty)])?
+                $route:ident => $field:ident : $page:ty $(= $ctor:ident $(:: $ctor_path:ident)* $(($($ctor_call:tt)*))? $({$($ctor_fields:tt)*})?)?
             ),* $(,)?
         }
     ) => {
-        define_app!(@impl ($default_route) $($route => $field : $page),*);
+        $crate::define_app!(@impl ($default_route)
+            [$($(header: $header = $header_height)?)?]
+            [$($(sidebar: $sidebar = $sidebar_width)?)?]
+            $([$($loading)?] [$($error)?] $route => $field : $page [$($ctor $(:: $ctor_path)* $(($($ctor_call)*))? $({$($ctor_fields)*})?)?]),*);
     };
 
-    // Internal: actual implementation - takes default route and routes
-    (@impl ($default_route:ident) $($route:ident => $field:ident : $page:ty),*) => {
+    // Internal: actual implementation - takes default route, chrome slots and routes
+    // with their loading and error views
+    (@impl ($default_route:ident)
+        [$(header: $header:ty = $header_height:tt)?]
+        [$(sidebar: $sidebar:ty = $sidebar_width:tt)?]
+        $([$($loading:ty)?] [$($error:ty)?] $route:ident => $field:ident : $page:ty [$($ctor:tt)*]),*
+    ) => {
         $crate::paste::paste! {
             use $crate::Component;
             // Generate RootRoute enum
@@ -201,9 +291,9 @@ macro_rules! define_app {
             }
 
             /// Type-safe route parsing from strings.
-            /// Returns error with available routes on mismatch.
+            /// Returns `Error::RouteNotFound` with the available routes on mismatch.
             impl std::str::FromStr for RootRoute {
-                type Err = String;
+                type Err = $crate::Error;
 
                 fn from_str(s: &str) -> Result<Self, Self::Err> {
                     let lower = s.to_lowercase();
@@ -212,30 +302,41 @@ macro_rules! define_app {
                             return Ok(RootRoute::$route);
                         }
                     )*
-                    Err(format!(
-                        "Unknown route: '{}'. Available routes: {}",
-                        s,
-                        vec![$(stringify!($route)),*].join(", ")
-                    ))
+                    Err($crate::Error::RouteNotFound {
+                        route: s.to_string(),
+                        available: vec![$(stringify!($route).to_string()),*],
+                    })
                 }
             }
 
             // Generate Root struct
             pub struct Root {
                 router: $crate::Router<RootRoute>,
+                view_states: $crate::ViewStateStore<RootRoute>,
+                slot_focus: $crate::router::Slot,
+                chrome_areas: $crate::router::ChromeAreas,
+                $(header: $crate::ChildView<$header>,)?
+                $(sidebar: $crate::ChildView<$sidebar>,)?
+                $($( [<$field _loading>]: $loading, )? $( [<$field _error>]: $error, )?)*
+                $([<$field _owner>]: $crate::component::binding::PageOwner,)*
                 $($field: $page),*
             }
 
             impl Root {
-                /// Create a new Root instance.
-                /// All pages are constructed using Default::default().
-                /// Customize components in their on_mount() lifecycle method.
-                pub fn new() -> Self {
+                $crate::define_app!(@constructors cx [$([$($ctor)*])*] {
+                    let _ = cx;
                     Self {
                         router: $crate::Router::new(RootRoute::default()),
-                        $($field: <$page>::default()),*
+                        view_states: $crate::ViewStateStore::new(),
+                        slot_focus: $crate::router::Slot::Content,
+                        chrome_areas: $crate::router::ChromeAreas::default(),
+                        $(header: $crate::ChildView::new(<$header>::default()),)?
+                        $(sidebar: $crate::ChildView::new(<$sidebar>::default()),)?
+                        $($( [<$field _loading>]: <$loading>::default(), )? $( [<$field _error>]: <$error>::default(), )?)*
+                        $([<$field _owner>]: Default::default(),)*
+                        $($field: $crate::define_app!(@page cx $page [$($ctor)*])),*
                     }
-                }
+                });
 
                 /// Get the current route
                 pub fn current_route(&self) -> &RootRoute {
@@ -252,54 +353,265 @@ macro_rules! define_app {
                     self.router.go_back()
                 }
 
-                /// Helper: Call on_enter for the given route
+                /// The chrome slot receiving key events
+                pub fn focused_slot(&self) -> $crate::router::Slot {
+                    self.slot_focus
+                }
+
+                /// Move key focus to a slot, calling `on_blur` and `on_focus`; ignored if the slot is not declared
+                pub fn focus_slot(&mut self, slot: $crate::router::Slot, cx: &mut $crate::Context<Self>) {
+                    if Self::chrome_layout().slots().contains(&slot) {
+                        let from = std::mem::replace(&mut self.slot_focus, slot);
+                        self.focus_moved(from, cx);
+                    }
+                }
+
+                /// The chrome slots declared with `#[Chrome(...)]`
+                fn chrome_layout() -> $crate::router::ChromeLayout {
+                    $crate::router::ChromeLayout::new()
+                        $(.header({ let _: Option<$header> = None; $header_height }))?
+                        $(.sidebar({ let _: Option<$sidebar> = None; $sidebar_width }))?
+                }
+
+                /// Helper: The content area when chrome is declared
+                fn content_area(&self) -> Option<ratatui::layout::Rect> {
+                    (!Self::chrome_layout().is_empty()).then_some(self.chrome_areas.content)
+                }
+
+                /// Helper: Context for the current page's loading or error view, bound to the content area when chrome is declared
+                fn page_cx<V: ?Sized + Send + Sync + 'static>(&self, cx: &$crate::Context<Self>) -> $crate::Context<V> {
+                    match self.content_area() {
+                        Some(area) => cx.cast_within(area),
+                        None => cx.cast(),
+                    }
+                }
+
+                $(
+                    /// Helper: Context for the page, bound to it so its observers, timers and tasks reach it
+                    fn [<$field _context>](&self, cx: &$crate::Context<Self>) -> $crate::Context<$page> {
+                        cx.project(Some(&self.[<$field _owner>]), self.content_area(), |root| &mut root.$field)
+                    }
+                )*
+
+                /// Helper: Restore view state (for `ViewState` pages) and call on_enter for the given route
                 fn call_on_enter(&mut self, route: RootRoute, cx: &mut $crate::Context<Self>) {
+                    #[allow(unused_imports)]
+                    use $crate::router::view_state::__private::{Probe, ViaFallback as _, ViaViewState as _};
+                    cx.set_active_route(&route.to_string());
                     match route {
-                        $(RootRoute::$route => self.$field.on_enter(&mut cx.cast())),*
+                        $(RootRoute::$route => {
+                            if let Some(hooks) = (&Probe::<$page>::new()).hooks() {
+                                (hooks.restore)(&mut self.view_states, &route, &mut self.$field);
+                            }
+                            self.$field.on_enter(&mut self.[<$field _context>](cx))
+                        }),*
                     }
                 }
 
-                /// Helper: Call on_exit for the given route
+                /// Helper: Call on_exit and capture view state (for `ViewState` pages) for the given route
                 fn call_on_exit(&mut self, route: RootRoute, cx: &mut $crate::Context<Self>) {
+                    #[allow(unused_imports)]
+                    use $crate::router::view_state::__private::{Probe, ViaFallback as _, ViaViewState as _};
                     match route {
-                        $(RootRoute::$route => self.$field.on_exit(&mut cx.cast())),*
+                        $(RootRoute::$route => {
+                            let mut page_cx = self.[<$field _context>](cx);
+                            self.$field.on_exit(&mut page_cx);
+                            page_cx.unsubscribe_all();
+                            if let Some(hooks) = (&Probe::<$page>::new()).hooks() {
+                                (hooks.save)(&mut self.view_states, route, &self.$field);
+                            }
+                        }),*
+                    }
+                }
+
+                /// Helper: The loading or error view declared for the current page, if the page is not ready
+                #[allow(unreachable_code, unused_variables)]
+                fn status_view(&mut self) -> Option<&mut dyn $crate::AnyComponent> {
+                    #[allow(unused_imports)]
+                    use $crate::router::StatusView as _;
+                    match *self.router.current() {
+                        $(RootRoute::$route => {
+                            let status = self.$field.status();
+                            match status {
+                                $crate::router::PageStatus::Ready => None,
+                                $crate::router::PageStatus::Loading => {
+                                    $(
+                                        let view: &mut $loading = &mut self.[<$field _loading>];
+                                        view.show(stringify!($route), &status);
+                                        return Some(view);
+                                    )?
+                                    None
+                                }
+                                $crate::router::PageStatus::Failed(_) => {
+                                    $(
+                                        let view: &mut $error = &mut self.[<$field _error>];
+                                        view.show(stringify!($route), &status);
+                                        return Some(view);
+                                    )?
+                                    None
+                                }
+                            }
+                        }),*
+                    }
+                }
+
+                /// Helper: Deliver an event to the current page, or first to its loading or error view
+                fn page_event(&mut self, event: $crate::Event, cx: &mut $crate::Context<Self>) -> Option<$crate::Action> {
+                    let mut status_cx = self.page_cx::<dyn $crate::AnyComponent>(cx);
+                    if let Some(view) = self.status_view() {
+                        if let Some(action) = view.handle_event_any(event.clone(), &mut status_cx) {
+                            return Some(action);
+                        }
+                    }
+                    match *self.router.current() {
+                        $(RootRoute::$route => {
+                            let action = self.$field.handle_event(event, &mut self.[<$field _context>](cx));
+                            if $crate::Component::refresh_on_event(&self.$field) {
+                                cx.refresh();
+                            }
+                            action
+                        }),*
+                    }
+                }
+
+                /// Helper: Call on_focus (or on_blur) on the component in a slot
+                #[allow(unused_variables)]
+                fn slot_focus_hook(&mut self, slot: $crate::router::Slot, focused: bool, cx: &mut $crate::Context<Self>) {
+                    match slot {
+                        $crate::router::Slot::Header => {
+                            $(
+                                let header: &mut $crate::ChildView<$header> = &mut self.header;
+                                if focused { header.on_focus(cx) } else { header.on_blur(cx) }
+                            )?
+                        }
+                        $crate::router::Slot::Sidebar => {
+                            $(
+                                let sidebar: &mut $crate::ChildView<$sidebar> = &mut self.sidebar;
+                                if focused { sidebar.on_focus(cx) } else { sidebar.on_blur(cx) }
+                            )?
+                        }
+                        $crate::router::Slot::Content => match *self.router.current() {
+                            $(RootRoute::$route => {
+                                let mut page_cx = self.[<$field _context>](cx);
+                                if focused { $crate::Component::on_focus(&mut self.$field, &mut page_cx) } else { $crate::Component::on_blur(&mut self.$field, &mut page_cx) }
+                            }),*
+                        },
+                    }
+                }
+
+                /// Helper: Tell the slots focus moved from `from` to the focused slot
+                fn focus_moved(&mut self, from: $crate::router::Slot, cx: &mut $crate::Context<Self>) {
+                    if from != self.slot_focus {
+                        self.slot_focus_hook(from, false, cx);
+                        self.slot_focus_hook(self.slot_focus, true, cx);
+                    }
+                }
+
+                /// Helper: Deliver an event to the chrome slot(s) and page it is meant for
+                fn dispatch(&mut self, event: $crate::Event, cx: &mut $crate::Context<Self>) -> Option<$crate::Action> {
+                    let from = self.slot_focus;
+                    let target = Self::chrome_layout().target(&event, &self.chrome_areas, &mut self.slot_focus);
+                    self.focus_moved(from, cx);
+                    match target {
+                        $crate::router::EventTarget::Consumed => None,
+                        $crate::router::EventTarget::Slot($crate::router::Slot::Header) => None
+                            $(.or_else(|| $crate::ChildView::<$header>::handle_event(&mut self.header, event, cx)))?,
+                        $crate::router::EventTarget::Slot($crate::router::Slot::Sidebar) => None
+                            $(.or_else(|| $crate::ChildView::<$sidebar>::handle_event(&mut self.sidebar, event, cx)))?,
+                        $crate::router::EventTarget::Slot($crate::router::Slot::Content) => self.page_event(event, cx),
+                        $crate::router::EventTarget::All => {
+                            let chrome: Option<$crate::Action> = None
+                                $(.or($crate::ChildView::<$header>::handle_event(&mut self.header, event.clone(), cx)))?
+                                $(.or($crate::ChildView::<$sidebar>::handle_event(&mut self.sidebar, event.clone(), cx)))?;
+                            self.page_event(event, cx).or(chrome)
+                        }
                     }
                 }
             }
 
             impl $crate::Component for Root {
                 fn on_mount(&mut self, cx: &mut $crate::Context<Self>) {
-                    $(self.$field.on_mount(&mut cx.cast());)*
+                    cx.register_routes([$(stringify!($route)),*]);
+                    $($crate::ChildView::<$header>::on_mount(&mut self.header, cx);)?
+                    $($crate::ChildView::<$sidebar>::on_mount(&mut self.sidebar, cx);)?
+                    $(self.$field.on_mount(&mut self.[<$field _context>](cx));)*
+                    $($( { let mut view_cx = self.page_cx(cx); <$loading as $crate::Component>::on_mount(&mut self.[<$field _loading>], &mut view_cx); } )? $( { let mut view_cx = self.page_cx(cx); <$error as $crate::Component>::on_mount(&mut self.[<$field _error>], &mut view_cx); } )?)*
                 }
 
                 fn on_enter(&mut self, cx: &mut $crate::Context<Self>) {
+                    $($crate::ChildView::<$header>::on_enter(&mut self.header, cx);)?
+                    $($crate::ChildView::<$sidebar>::on_enter(&mut self.sidebar, cx);)?
                     self.call_on_enter(*self.router.current(), cx);
                 }
 
                 fn on_exit(&mut self, cx: &mut $crate::Context<Self>) {
                     self.call_on_exit(*self.router.current(), cx);
+                    $($crate::ChildView::<$header>::on_exit(&mut self.header, cx);)?
+                    $($crate::ChildView::<$sidebar>::on_exit(&mut self.sidebar, cx);)?
                 }
 
                 fn on_shutdown(&mut self, cx: &mut $crate::Context<Self>) {
-                    $(self.$field.on_shutdown(&mut cx.cast());)*
+                    $($crate::ChildView::<$header>::on_shutdown(&mut self.header, cx);)?
+                    $($crate::ChildView::<$sidebar>::on_shutdown(&mut self.sidebar, cx);)?
+                    $(self.$field.on_shutdown(&mut self.[<$field _context>](cx));)*
+                    $($( { let mut view_cx = self.page_cx(cx); <$loading as $crate::Component>::on_shutdown(&mut self.[<$field _loading>], &mut view_cx); } )? $( { let mut view_cx = self.page_cx(cx); <$error as $crate::Component>::on_shutdown(&mut self.[<$field _error>], &mut view_cx); } )?)*
+                }
+
+                fn on_focus(&mut self, cx: &mut $crate::Context<Self>) {
+                    self.slot_focus_hook(self.slot_focus, true, cx);
+                }
+
+                fn on_blur(&mut self, cx: &mut $crate::Context<Self>) {
+                    self.slot_focus_hook(self.slot_focus, false, cx);
+                }
+
+                fn on_resize(&mut self, width: u16, height: u16, cx: &mut $crate::Context<Self>) {
+                    $($crate::ChildView::<$header>::on_resize(&mut self.header, width, height, cx);)?
+                    $($crate::ChildView::<$sidebar>::on_resize(&mut self.sidebar, width, height, cx);)?
+                    $(self.$field.on_resize(width, height, &mut self.[<$field _context>](cx));)*
+                    $($( { let mut view_cx = self.page_cx(cx); <$loading as $crate::Component>::on_resize(&mut self.[<$field _loading>], width, height, &mut view_cx); } )? $( { let mut view_cx = self.page_cx(cx); <$error as $crate::Component>::on_resize(&mut self.[<$field _error>], width, height, &mut view_cx); } )?)*
                 }
 
                 fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut $crate::Context<Self>) {
+                    self.chrome_areas = Self::chrome_layout().split(cx.area(frame));
+                    $(cx.mount_child::<$header>(&mut self.header, frame, self.chrome_areas.header);)?
+                    $(cx.mount_child::<$sidebar>(&mut self.sidebar, frame, self.chrome_areas.sidebar);)?
+                    if !Self::chrome_layout().is_empty() {
+                        cx.name_area("content", self.chrome_areas.content);
+                    }
+                    let mut status_cx = self.page_cx::<dyn $crate::AnyComponent>(cx);
+                    if let Some(view) = self.status_view() {
+                        view.render_any(frame, &mut status_cx);
+                        return;
+                    }
                     match self.router.current() {
-                        $(RootRoute::$route => self.$field.render(frame, &mut cx.cast())),*
+                        $(RootRoute::$route => {
+                            let mut page_cx = self.[<$field _context>](cx);
+                            cx.frame_budget().measure::<$page, _>(|| self.$field.render(frame, &mut page_cx))
+                        }),*
                     }
                 }
 
                 fn handle_event(&mut self, event: $crate::Event, cx: &mut $crate::EventContext<Self>) -> Option<$crate::Action> {
                     let current = *self.router.current();
-                    let action = match current {
-                        $(RootRoute::$route => self.$field.handle_event(event, &mut cx.cast())),*
+                    let navigate = match &event {
+                        $crate::Event::Custom(custom) if custom.name() == $crate::router::NAVIGATE => custom.downcast_ref::<String>().cloned(),
+                        _ => None,
+                    };
+                    let trigger = match navigate {
+                        Some(_) => $crate::NavigationTrigger::Request,
+                        None => $crate::NavigationTrigger::Action,
+                    };
+                    let action = match navigate {
+                        Some(route) => Some($crate::Action::Navigate(route)),
+                        None => self.dispatch(event, cx),
                     };
 
                     // Handle navigation actions with type-safe routing
                     if let Some(action) = action {
                         match &action {
                             $crate::Action::Navigate(route_str) => {
+                                cx.refresh();
                                 // Type-safe route parsing with clear error messages
                                 match route_str.parse::<RootRoute>() {
                                     Ok(target_route) => {
@@ -307,25 +619,30 @@ macro_rules! define_app {
                                         self.call_on_exit(current, cx);
                                         self.router.navigate(target_route);
                                         self.call_on_enter(target_route, cx);
+                                        if target_route != current {
+                                            cx.emit_navigation($crate::NavigationEvent::new(current.to_string(), target_route.to_string(), trigger));
+                                        }
                                     }
-                                    Err(e) => {
-                                        eprintln!("Navigation error: {}", e);
-                                    }
+                                    Err(e) => cx.report_navigation_error::<Self>(e),
                                 }
                                 None
                             }
                             $crate::Action::Back => {
+                                cx.refresh();
                                 // Exit current
                                 self.call_on_exit(current, cx);
 
                                 if self.router.go_back() {
                                     // Enter previous
-                                    self.call_on_enter(*self.router.current(), cx);
+                                    let previous = *self.router.current();
+                                    self.call_on_enter(previous, cx);
+                                    cx.emit_navigation($crate::NavigationEvent::new(current.to_string(), previous.to_string(), $crate::NavigationTrigger::Back));
                                 }
                                 None
                             }
                             $crate::Action::Quit => Some($crate::Action::Quit),
                             $crate::Action::Noop => None,
+                            $crate::Action::Dispatch(dispatched) => Some($crate::Action::Dispatch(dispatched.clone())),
                         }
                     } else {
                         None
@@ -334,6 +651,67 @@ macro_rules! define_app {
             }
         }
     };
+
+    // Internal: `Root::new` and `Root::try_new` around the body building Root. `new` is
+    // left out when a page is built with `= construct`, which can fail
+    (@constructors $cx:ident [] $body:block) => {
+        /// Create a new Root instance.
+        /// Pages are built by their constructor expression, or with Default::default().
+        /// Customize components in their on_mount() lifecycle method.
+        pub fn new($cx: &$crate::AppContext) -> Self $body
+
+        /// Same as `new`, for symmetry with apps that have `= construct` pages.
+        pub fn try_new($cx: &$crate::AppContext) -> $crate::Result<Self> {
+            Ok(Self::new($cx))
+        }
+    };
+    (@constructors $cx:ident [[construct] $($rest:tt)*] $body:block) => {
+        /// Create a new Root instance, resolving the dependencies of `= construct` pages
+        /// from the application state. Fails with `Error::MissingDependencies` listing
+        /// every unregistered type.
+        pub fn try_new($cx: &$crate::AppContext) -> $crate::Result<Self> {
+            Ok($body)
+        }
+    };
+    (@constructors $cx:ident [$other:tt $($rest:tt)*] $body:block) => {
+        $crate::define_app!(@constructors $cx [$($rest)*] $body);
+    };
+
+    // Internal: a page's constructor expression, `FromContext` construction, or its Default
+    (@page $app:ident $page:ty []) => {
+        <$page>::default()
+    };
+    (@page $app:ident $page:ty [construct]) => {
+        $app.construct::<$page>()?
+    };
+    (@page $app:ident $page:ty [$($ctor:tt)+]) => {
+        $crate::define_app!(@construct $app [$($ctor)+] [$($ctor)+])
+    };
+
+    // Internal: find the caller's `cx` in a constructor expression and bind it to the
+    // context passed to Root::new, which the caller's tokens cannot name directly
+    (@construct $app:ident [$($ctor:tt)+] []) => {
+        $($ctor)+
+    };
+    (@construct $app:ident $ctor:tt [($($inner:tt)*) $($rest:tt)*]) => {
+        $crate::define_app!(@construct $app $ctor [$($inner)* $($rest)*])
+    };
+    (@construct $app:ident $ctor:tt [[$($inner:tt)*] $($rest:tt)*]) => {
+        $crate::define_app!(@construct $app $ctor [$($inner)* $($rest)*])
+    };
+    (@construct $app:ident $ctor:tt [{$($inner:tt)*} $($rest:tt)*]) => {
+        $crate::define_app!(@construct $app $ctor [$($inner)* $($rest)*])
+    };
+    (@construct $app:ident $ctor:tt [$first:tt $($rest:tt)*]) => {
+        $crate::define_app!(@bind $app $ctor $first $first [$($rest)*])
+    };
+    (@bind $app:ident [$($ctor:tt)+] cx $cx:ident [$($rest:tt)*]) => {{
+        let $cx = $app;
+        $($ctor)+
+    }};
+    (@bind $app:ident $ctor:tt $other:tt $same:tt [$($rest:tt)*]) => {
+        $crate::define_app!(@construct $app $ctor [$($rest)*])
+    };
 }
 
 #[cfg(test)]
@@ -378,4 +756,182 @@ mod tests {
         router.navigate(TestRoute::Home); // Same route
         assert_eq!(router.history_len(), 0); // No history added
     }
+
+    #[allow(dead_code)]
+    mod app {
+        use crate::{Action, AppContext, Context, Entity, Event};
+        use ratatui::widgets::Paragraph;
+        use ratatui::Frame;
+
+        pub struct Counter {
+            count: Entity<u32>,
+        }
+
+        impl Counter {
+            pub fn new(cx: &AppContext, start: u32) -> Self {
+                Self { count: cx.new_entity(start) }
+            }
+        }
+
+        impl crate::Component for Counter {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                let count = self.count.read(|count| *count).unwrap_or_default();
+                frame.render_widget(Paragraph::new(format!("count {count}")), cx.area(frame));
+            }
+
+            fn handle_event(&mut self, event: Event, _cx: &mut Context<Self>) -> Option<Action> {
+                matches!(event, Event::Key(_)).then(|| Action::Navigate("Second".into()))
+            }
+        }
+
+        pub struct Label {
+            pub text: &'static str,
+        }
+
+        impl crate::Component for Label {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                frame.render_widget(Paragraph::new(self.text), cx.area(frame));
+            }
+        }
+
+        crate::define_app! {
+            Menu => menu: Counter = Counter::new(cx, 3),
+            Second => second: Label = Label { text: "second" },
+        }
+    }
+
+    #[test]
+    fn test_define_app_page_constructors() {
+        use crate::testing::Harness;
+
+        let mut harness = Harness::build(10, 1, app::Root::new);
+        assert_eq!(harness.text(), "count 3");
+        harness.type_str("x");
+        assert_eq!(harness.text(), "second");
+    }
+
+    #[allow(dead_code)]
+    mod injected {
+        use crate::{AppContext, Context, FromContext};
+        use ratatui::widgets::Paragraph;
+        use ratatui::Frame;
+
+        pub struct Greeting {
+            name: String,
+        }
+
+        impl FromContext for Greeting {
+            type Deps = (String,);
+
+            fn from_context((name,): Self::Deps, _cx: &AppContext) -> Self {
+                Self { name }
+            }
+        }
+
+        impl crate::Component for Greeting {
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                frame.render_widget(Paragraph::new(format!("hi {}", self.name)), cx.area(frame));
+            }
+        }
+
+        crate::define_app! {
+            Menu => hello: Greeting = construct,
+        }
+    }
+
+    #[test]
+    fn test_define_app_construct_pages() {
+        use crate::testing::Harness;
+
+        let harness = Harness::build(10, 1, |cx| {
+            cx.set(String::from("ada"));
+            injected::Root::try_new(cx).unwrap()
+        });
+        assert_eq!(harness.text(), "hi ada");
+
+        let (cx, _, _) = crate::AppContext::new();
+        let Err(error) = injected::Root::try_new(&cx) else {
+            panic!("built without its dependencies");
+        };
+        assert!(matches!(error, crate::Error::MissingDependencies { .. }), "{error}");
+    }
+
+    #[allow(dead_code)]
+    mod observing {
+        use crate::{AppContext, ChildView, Context, Entity};
+        use ratatui::layout::Rect;
+        use ratatui::widgets::Paragraph;
+        use ratatui::Frame;
+
+        pub struct Panel {
+            source: Entity<u32>,
+            seen: u32,
+        }
+
+        impl crate::Component for Panel {
+            fn on_mount(&mut self, cx: &mut Context<Self>) {
+                let source = Entity::clone(&self.source);
+                cx.observe(&source, |this, _cx| this.seen = this.source.read(|v| *v).unwrap_or_default()).unwrap();
+            }
+
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                frame.render_widget(Paragraph::new(format!("panel {}", self.seen)), cx.area(frame));
+            }
+        }
+
+        pub struct Watcher {
+            source: Entity<u32>,
+            seen: u32,
+            panel: ChildView<Panel>,
+        }
+
+        impl Watcher {
+            pub fn new(cx: &AppContext) -> Self {
+                let source = cx.get::<Entity<u32>>().unwrap();
+                Self { panel: ChildView::new(Panel { source: Entity::clone(&source), seen: 0 }), source, seen: 0 }
+            }
+        }
+
+        impl crate::Component for Watcher {
+            fn on_mount(&mut self, cx: &mut Context<Self>) {
+                let source = Entity::clone(&self.source);
+                cx.observe(&source, |this, _cx| this.seen = this.source.read(|v| *v).unwrap_or_default()).unwrap();
+                self.panel.on_mount(cx);
+            }
+
+            fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+                frame.render_widget(Paragraph::new(format!("page {}", self.seen)), Rect::new(0, 0, 10, 1));
+                cx.mount_child(&mut self.panel, frame, Rect::new(0, 1, 10, 1));
+            }
+        }
+
+        crate::define_app! {
+            Menu => watcher: Watcher = Watcher::new(cx),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pages_and_their_children_observe_entities() {
+        let source = crate::Entity::new(0u32);
+        let (_cx, mut app) = crate::Application::new()
+            .into_parts(|cx| {
+                cx.set(crate::Entity::clone(&source));
+                cx.set_root(observing::Root::new(cx)).map_err(Into::into)
+            })
+            .unwrap();
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(10, 2)).unwrap();
+        let mut screen = |app: &mut crate::AppLoop| {
+            app.draw(&mut terminal).unwrap();
+            let buffer = terminal.backend().buffer();
+            (0..2)
+                .map(|y| (0..10).map(|x| buffer[(x, y)].symbol()).collect::<String>().trim_end().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(screen(&mut app), ["page 0", "panel 0"]);
+
+        source.update(|v| *v = 7).unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(app.needs_redraw());
+        assert_eq!(screen(&mut app), ["page 7", "panel 7"]);
+    }
 }
@@ -0,0 +1,163 @@
+//! Per-route view state (selection, scroll offset, cursor) capture and restore.
+//!
+//! Pages implement `ViewState`, usually via `#[derive(ViewState)]`. The Root generated by
+//! `define_app!` keeps a `ViewStateStore` and captures the page's state after `on_exit`,
+//! then restores it before the next `on_enter`, so pages don't have to.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// UI state of a page that should survive navigating away and back.
+///
+/// # Example
+/// ```ignore
+/// #[derive(Default, rat_nexus::ViewState)]
+/// pub struct LogPage {
+///     #[view_state]
+///     scroll_offset: usize,
+///     lines: Vec<String>,
+/// }
+/// ```
+pub trait ViewState {
+    /// Captured state, typically a tuple of the marked fields.
+    type Snapshot: Send + Sync + 'static;
+
+    /// Capture the current view state.
+    fn capture(&self) -> Self::Snapshot;
+
+    /// Restore previously captured view state.
+    fn restore(&mut self, snapshot: Self::Snapshot);
+}
+
+/// Snapshots of view state keyed by route.
+pub struct ViewStateStore<R> {
+    snapshots: HashMap<R, Box<dyn Any + Send + Sync>>,
+}
+
+impl<R> Default for ViewStateStore<R> {
+    fn default() -> Self {
+        Self { snapshots: HashMap::new() }
+    }
+}
+
+impl<R: Eq + Hash> ViewStateStore<R> {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Capture `view`'s state for `route`, replacing any earlier snapshot.
+    pub fn save<V: ViewState>(&mut self, route: R, view: &V) {
+        self.snapshots.insert(route, Box::new(view.capture()));
+    }
+
+    /// Restore `view` from the snapshot for `route`. Returns true if one existed.
+    pub fn restore<V: ViewState>(&mut self, route: &R, view: &mut V) -> bool {
+        match self.snapshots.remove(route).map(|s| s.downcast::<V::Snapshot>()) {
+            Some(Ok(snapshot)) => {
+                view.restore(*snapshot);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Drop the snapshot for `route`, so the page starts fresh next time.
+    pub fn forget(&mut self, route: &R) {
+        self.snapshots.remove(route);
+    }
+
+    /// Drop all snapshots.
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+}
+
+/// Support for `define_app!`, which must save and restore pages that may or may
+/// not implement `ViewState`. Resolved at compile time via autoref specialization.
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+
+    pub struct Probe<V>(PhantomData<fn() -> V>);
+
+    impl<V> Probe<V> {
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Self(PhantomData)
+        }
+    }
+
+    pub struct Hooks<R, V> {
+        pub save: fn(&mut ViewStateStore<R>, R, &V),
+        pub restore: fn(&mut ViewStateStore<R>, &R, &mut V) -> bool,
+    }
+
+    pub trait ViaViewState<R, V> {
+        fn hooks(&self) -> Option<Hooks<R, V>>;
+    }
+
+    impl<R: Eq + Hash, V: ViewState> ViaViewState<R, V> for Probe<V> {
+        fn hooks(&self) -> Option<Hooks<R, V>> {
+            Some(Hooks { save: ViewStateStore::save::<V>, restore: ViewStateStore::restore::<V> })
+        }
+    }
+
+    pub trait ViaFallback<R, V> {
+        fn hooks(&self) -> Option<Hooks<R, V>>;
+    }
+
+    impl<R, V> ViaFallback<R, V> for &Probe<V> {
+        fn hooks(&self) -> Option<Hooks<R, V>> {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::__private::{Probe, ViaFallback as _, ViaViewState as _};
+    use super::*;
+
+    #[derive(Default)]
+    struct Page {
+        selected: usize,
+        items: Vec<&'static str>,
+    }
+
+    impl ViewState for Page {
+        type Snapshot = (usize,);
+        fn capture(&self) -> Self::Snapshot {
+            (self.selected,)
+        }
+        fn restore(&mut self, snapshot: Self::Snapshot) {
+            self.selected = snapshot.0;
+        }
+    }
+
+    struct Plain;
+
+    #[test]
+    fn test_save_restore() {
+        let mut store = ViewStateStore::new();
+        let mut page = Page { selected: 3, items: vec!["a"] };
+        store.save("menu", &page);
+
+        page.selected = 0;
+        page.items.clear();
+        assert!(store.restore(&"menu", &mut page));
+        assert_eq!(page.selected, 3);
+        assert!(!store.restore(&"menu", &mut page));
+    }
+
+    #[test]
+    #[allow(clippy::needless_borrow)] // the explicit borrow drives the resolution
+    fn test_probe_resolution() {
+        let hooks: Option<super::__private::Hooks<&str, Page>> = (&Probe::<Page>::new()).hooks();
+        assert!(hooks.is_some());
+        let hooks: Option<super::__private::Hooks<&str, Plain>> = (&Probe::<Plain>::new()).hooks();
+        assert!(hooks.is_none());
+    }
+}
@@ -0,0 +1,492 @@
+//! Serving an application to remote terminals.
+//!
+//! A `Session` is one client's terminal reached over a byte stream rather than the
+//! local tty: an SSH channel, a telnet socket, a pty pair. Output goes to a writer and
+//! input arrives as raw bytes, which are decoded into the same `Event`s a local
+//! terminal produces. Hand it to `Application::session` and start the application with
+//! `Application::spawn`, one per connection; every run gets its own `AppContext`.
+//!
+//! Nothing is shared between sessions unless the application shares it: pass a clone
+//! of the same `Entity` to each with `Application::share` to give them common state.
+//!
+//! # Example
+//! ```ignore
+//! // In an SSH server's handler, once the client has a pty and a shell:
+//! let (session, handle) = Session::new(channel_writer, cols, rows);
+//! Application::new()
+//!     .session(session)
+//!     .share(board.clone()) // the same board for every client
+//!     .spawn(|cx| cx.set_root(Game::new(cx.get::<Entity<Board>>().unwrap())).map_err(Into::into));
+//! // Then on channel data: handle.input(&data); on window change: handle.resize(cols, rows).
+//! // Dropping the handle when the client disconnects ends its application.
+//! ```
+
+use std::io::{self, Write};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crossterm::event::{
+    DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::{Backend, ClearType, CrosstermBackend, WindowSize};
+use ratatui::buffer::Cell;
+use ratatui::layout::{Position, Size};
+
+use crate::backend::TerminalBackend;
+use crate::capabilities::Capabilities;
+use crate::event_source::{ChannelEvents, EventSource, Polled};
+use crate::graphics::GraphicsBackend;
+
+/// What the connection side sends to a session.
+enum Input {
+    Bytes(Vec<u8>),
+    Resize(u16, u16),
+}
+
+/// A remote client's terminal, to run an application on (see `Application::session`).
+pub struct Session {
+    output: Box<dyn Write + Send>,
+    size: Arc<Mutex<Size>>,
+    input: mpsc::Receiver<Input>,
+}
+
+/// The connection side of a `Session`: forwards the client's input and window size.
+/// Dropping it ends the session's application.
+#[derive(Clone)]
+pub struct SessionHandle {
+    tx: mpsc::Sender<Input>,
+    size: Arc<Mutex<Size>>,
+}
+
+impl Session {
+    /// A session writing to `output` for a client terminal of `width`x`height` cells.
+    pub fn new(output: impl Write + Send + 'static, width: u16, height: u16) -> (Self, SessionHandle) {
+        let (tx, input) = mpsc::channel();
+        let size = Arc::new(Mutex::new(Size::new(width, height)));
+        let session = Self { output: Box::new(output), size: Arc::clone(&size), input };
+        (session, SessionHandle { tx, size })
+    }
+
+    /// Take over the client's terminal.
+    pub(crate) fn open(self, capabilities: &Capabilities) -> io::Result<SessionBackend> {
+        let mut output = self.output;
+        crossterm::execute!(output, EnterAlternateScreen)?;
+        if capabilities.mouse {
+            crossterm::execute!(output, EnableMouseCapture)?;
+        }
+        let events = SessionEvents { rx: self.input, decoder: Decoder::default() };
+        Ok(SessionBackend {
+            inner: CrosstermBackend::new(output),
+            size: self.size,
+            cursor: Position::ORIGIN,
+            events: Mutex::new(Some(events)),
+        })
+    }
+}
+
+impl SessionHandle {
+    /// Pass on bytes the client typed. Returns `false` once the application has ended.
+    pub fn input(&self, bytes: &[u8]) -> bool {
+        self.tx.send(Input::Bytes(bytes.to_vec())).is_ok()
+    }
+
+    /// Pass on a change of the client's window size.
+    pub fn resize(&self, width: u16, height: u16) -> bool {
+        if let Ok(mut size) = self.size.lock() {
+            *size = Size::new(width, height);
+        }
+        self.tx.send(Input::Resize(width, height)).is_ok()
+    }
+}
+
+/// The backend an application draws a `Session` with.
+pub struct SessionBackend {
+    inner: CrosstermBackend<Box<dyn Write + Send>>,
+    size: Arc<Mutex<Size>>,
+    cursor: Position,
+    events: Mutex<Option<SessionEvents>>,
+}
+
+impl Backend for SessionBackend {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        self.inner.draw(content)
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.inner.append_lines(n)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+    /// The client cannot be asked where its cursor is, so this is where it was put.
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        Ok(self.cursor)
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.cursor = position.into();
+        self.inner.set_cursor_position(self.cursor)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.inner.clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.inner.clear_region(clear_type)
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.size.lock().map(|size| *size).map_err(|_| io::Error::other("session size lock poisoned"))
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        Ok(WindowSize { columns_rows: self.size()?, pixels: Size::default() })
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Backend::flush(&mut self.inner)
+    }
+}
+
+impl TerminalBackend for SessionBackend {
+    /// Session backends are opened from their `Session` by `Application::session`.
+    fn enter(_capabilities: &Capabilities) -> io::Result<Self> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "session backends are opened through Application::session"))
+    }
+
+    fn leave(&mut self, capabilities: &Capabilities) -> io::Result<()> {
+        if capabilities.mouse {
+            crossterm::execute!(self.inner, DisableMouseCapture)?;
+        }
+        crossterm::execute!(self.inner, LeaveAlternateScreen)?;
+        self.show_cursor()
+    }
+
+    fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()> {
+        GraphicsBackend::write_raw(self, bytes)
+    }
+
+    fn events(&self) -> Box<dyn EventSource> {
+        match self.events.lock().ok().and_then(|mut events| events.take()) {
+            Some(events) => Box::new(events),
+            // Already handed out; a source with no sender closes at once
+            None => Box::new(ChannelEvents::channel().1),
+        }
+    }
+}
+
+impl GraphicsBackend for SessionBackend {
+    fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()> {
+        GraphicsBackend::write_raw(&mut self.inner, bytes)
+    }
+}
+
+/// Input from a `SessionHandle`, decoded into terminal events.
+struct SessionEvents {
+    rx: mpsc::Receiver<Input>,
+    decoder: Decoder,
+}
+
+impl EventSource for SessionEvents {
+    fn poll_event(&mut self, timeout: Duration) -> io::Result<Polled> {
+        loop {
+            if let Some(event) = self.decoder.next() {
+                return Ok(Polled::Event(event));
+            }
+            match self.rx.recv_timeout(timeout) {
+                Ok(Input::Bytes(bytes)) => self.decoder.feed(&bytes),
+                Ok(Input::Resize(width, height)) => return Ok(Polled::Event(Event::Resize(width, height))),
+                Err(RecvTimeoutError::Timeout) => return Ok(Polled::Timeout),
+                Err(RecvTimeoutError::Disconnected) => return Ok(Polled::Closed),
+            }
+        }
+    }
+}
+
+/// Decodes the bytes a terminal sends for keys and mouse reports (xterm/SGR
+/// encoding). Sequences split across reads are completed by the next `feed`.
+#[derive(Default)]
+pub(crate) struct Decoder {
+    pending: Vec<u8>,
+    events: std::collections::VecDeque<Event>,
+}
+
+/// One step of decoding.
+enum Parsed {
+    Event(Event, usize),
+    Skip(usize),
+    Incomplete,
+}
+
+impl Decoder {
+    pub(crate) fn feed(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+        let mut start = 0;
+        while start < self.pending.len() {
+            match parse(&self.pending[start..]) {
+                Parsed::Event(event, len) => {
+                    self.events.push_back(event);
+                    start += len;
+                }
+                Parsed::Skip(len) => start += len,
+                Parsed::Incomplete => break,
+            }
+        }
+        self.pending.drain(..start);
+    }
+
+    pub(crate) fn next(&mut self) -> Option<Event> {
+        self.events.pop_front()
+    }
+}
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> Parsed {
+    Parsed::Event(Event::Key(KeyEvent::new(code, modifiers)), 0)
+}
+
+fn consumed(parsed: Parsed, len: usize) -> Parsed {
+    match parsed {
+        Parsed::Event(event, _) => Parsed::Event(event, len),
+        other => other,
+    }
+}
+
+fn parse(bytes: &[u8]) -> Parsed {
+    let none = KeyModifiers::NONE;
+    match bytes[0] {
+        0x1b => match bytes.get(1) {
+            // A lone escape at the end of a read is the Escape key
+            None => consumed(key(KeyCode::Esc, none), 1),
+            Some(b'[') => parse_csi(bytes),
+            Some(b'O') => match bytes.get(2) {
+                None => Parsed::Incomplete,
+                Some(&b) => match cursor_key(b) {
+                    Some(code) => consumed(key(code, none), 3),
+                    None => Parsed::Skip(3),
+                },
+            },
+            Some(0x1b) => consumed(key(KeyCode::Esc, none), 1),
+            Some(_) => match parse(&bytes[1..]) {
+                Parsed::Event(Event::Key(mut key), len) => {
+                    key.modifiers |= KeyModifiers::ALT;
+                    Parsed::Event(Event::Key(key), len + 1)
+                }
+                other => other,
+            },
+        },
+        b'\r' | b'\n' => consumed(key(KeyCode::Enter, none), 1),
+        b'\t' => consumed(key(KeyCode::Tab, none), 1),
+        0x7f | 0x08 => consumed(key(KeyCode::Backspace, none), 1),
+        0x00 => consumed(key(KeyCode::Char(' '), KeyModifiers::CONTROL), 1),
+        b @ 0x01..=0x1a => consumed(key(KeyCode::Char((b + 0x60) as char), KeyModifiers::CONTROL), 1),
+        b @ 0x1c..=0x1f => consumed(key(KeyCode::Char((b - 0x1c + b'4') as char), KeyModifiers::CONTROL), 1),
+        b => {
+            let len = match b {
+                0x00..=0x7f => 1,
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => return Parsed::Skip(1),
+            };
+            if bytes.len() < len {
+                return Parsed::Incomplete;
+            }
+            match std::str::from_utf8(&bytes[..len]).ok().and_then(|s| s.chars().next()) {
+                Some(c) if c.is_uppercase() => consumed(key(KeyCode::Char(c), KeyModifiers::SHIFT), len),
+                Some(c) => consumed(key(KeyCode::Char(c), none), len),
+                None => Parsed::Skip(1),
+            }
+        }
+    }
+}
+
+fn cursor_key(b: u8) -> Option<KeyCode> {
+    Some(match b {
+        b'A' => KeyCode::Up,
+        b'B' => KeyCode::Down,
+        b'C' => KeyCode::Right,
+        b'D' => KeyCode::Left,
+        b'H' => KeyCode::Home,
+        b'F' => KeyCode::End,
+        b'P' => KeyCode::F(1),
+        b'Q' => KeyCode::F(2),
+        b'R' => KeyCode::F(3),
+        b'S' => KeyCode::F(4),
+        _ => return None,
+    })
+}
+
+/// Modifiers encoded as xterm's `1 + bits` parameter.
+fn xterm_modifiers(param: u16) -> KeyModifiers {
+    let bits = param.saturating_sub(1);
+    let mut modifiers = KeyModifiers::NONE;
+    for (bit, modifier) in [(1, KeyModifiers::SHIFT), (2, KeyModifiers::ALT), (4, KeyModifiers::CONTROL)] {
+        if bits & bit != 0 {
+            modifiers |= modifier;
+        }
+    }
+    modifiers
+}
+
+fn parse_csi(bytes: &[u8]) -> Parsed {
+    let Some(end) = bytes[2..].iter().position(|b| (0x40..=0x7e).contains(b)).map(|i| i + 2) else {
+        return Parsed::Incomplete;
+    };
+    let len = end + 1;
+    let body = &bytes[2..end];
+    let sgr_mouse = body.first() == Some(&b'<');
+    let params: Vec<u16> = std::str::from_utf8(if sgr_mouse { &body[1..] } else { body })
+        .unwrap_or_default()
+        .split(';')
+        .map(|param| param.parse().unwrap_or(0))
+        .collect();
+    let param = |i: usize| params.get(i).copied().unwrap_or(0);
+    if sgr_mouse {
+        return match mouse_event(param(0), param(1), param(2), bytes[end] == b'm') {
+            Some(mouse) => Parsed::Event(Event::Mouse(mouse), len),
+            None => Parsed::Skip(len),
+        };
+    }
+    let modifiers = xterm_modifiers(param(1));
+    let code = match bytes[end] {
+        b'Z' => return consumed(key(KeyCode::BackTab, KeyModifiers::SHIFT), len),
+        b'I' => return Parsed::Event(Event::FocusGained, len),
+        b'O' => return Parsed::Event(Event::FocusLost, len),
+        b'~' => match param(0) {
+            1 | 7 => KeyCode::Home,
+            2 => KeyCode::Insert,
+            3 => KeyCode::Delete,
+            4 | 8 => KeyCode::End,
+            5 => KeyCode::PageUp,
+            6 => KeyCode::PageDown,
+            n @ 11..=15 => KeyCode::F((n - 10) as u8),
+            n @ 17..=21 => KeyCode::F((n - 11) as u8),
+            n @ 23..=24 => KeyCode::F((n - 12) as u8),
+            _ => return Parsed::Skip(len),
+        },
+        b => match cursor_key(b) {
+            Some(code) => code,
+            None => return Parsed::Skip(len),
+        },
+    };
+    consumed(key(code, modifiers), len)
+}
+
+/// An SGR mouse report: button code `cb`, 1-based cell (`x`, `y`).
+fn mouse_event(cb: u16, x: u16, y: u16, release: bool) -> Option<MouseEvent> {
+    let button = match cb & 0b11 {
+        0 => MouseButton::Left,
+        1 => MouseButton::Middle,
+        2 => MouseButton::Right,
+        _ => MouseButton::Left,
+    };
+    let kind = if cb & 64 != 0 {
+        match cb & 0b11 {
+            0 => MouseEventKind::ScrollUp,
+            1 => MouseEventKind::ScrollDown,
+            2 => MouseEventKind::ScrollLeft,
+            _ => MouseEventKind::ScrollRight,
+        }
+    } else if cb & 32 != 0 {
+        match cb & 0b11 {
+            3 => MouseEventKind::Moved,
+            _ => MouseEventKind::Drag(button),
+        }
+    } else if release {
+        MouseEventKind::Up(button)
+    } else if cb & 0b11 == 3 {
+        return None;
+    } else {
+        MouseEventKind::Down(button)
+    };
+    let mut modifiers = KeyModifiers::NONE;
+    for (bit, modifier) in [(4, KeyModifiers::SHIFT), (8, KeyModifiers::ALT), (16, KeyModifiers::CONTROL)] {
+        if cb & bit != 0 {
+            modifiers |= modifier;
+        }
+    }
+    Some(MouseEvent { kind, column: x.saturating_sub(1), row: y.saturating_sub(1), modifiers })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(chunks: &[&[u8]]) -> Vec<Event> {
+        let mut decoder = Decoder::default();
+        let mut events = Vec::new();
+        for chunk in chunks {
+            decoder.feed(chunk);
+            events.extend(std::iter::from_fn(|| decoder.next()));
+        }
+        events
+    }
+
+    fn keys(events: &[Event]) -> Vec<KeyEvent> {
+        events.iter().filter_map(|event| if let Event::Key(key) = event { Some(*key) } else { None }).collect()
+    }
+
+    #[test]
+    fn test_decode_keys() {
+        let events = decode(&[b"aZ\r\x03\x1bx\x1b[A\x1b[1;5C\x1b[3~\x1b[15~\x1bOP\xc3\xa9\x1b[Z"]);
+        assert_eq!(
+            keys(&events),
+            [
+                KeyEvent::from(KeyCode::Char('a')),
+                KeyEvent::new(KeyCode::Char('Z'), KeyModifiers::SHIFT),
+                KeyEvent::from(KeyCode::Enter),
+                KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+                KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT),
+                KeyEvent::from(KeyCode::Up),
+                KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL),
+                KeyEvent::from(KeyCode::Delete),
+                KeyEvent::from(KeyCode::F(5)),
+                KeyEvent::from(KeyCode::F(1)),
+                KeyEvent::from(KeyCode::Char('é')),
+                KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT),
+            ]
+        );
+        assert_eq!(keys(&decode(&[b"\x1b"])), [KeyEvent::from(KeyCode::Esc)]);
+    }
+
+    #[test]
+    fn test_decode_split_sequences_and_mouse() {
+        let events = decode(&[b"\x1b[<0;5", b";3M\xc3", b"\xa9\x1b[<0;5;3m\x1b[<65;1;1M"]);
+        let Event::Mouse(down) = events[0] else { panic!("expected a mouse event") };
+        assert_eq!((down.kind, down.column, down.row), (MouseEventKind::Down(MouseButton::Left), 4, 2));
+        assert_eq!(events[1], Event::Key(KeyEvent::from(KeyCode::Char('é'))));
+        assert!(matches!(events[2], Event::Mouse(MouseEvent { kind: MouseEventKind::Up(MouseButton::Left), .. })));
+        assert!(matches!(events[3], Event::Mouse(MouseEvent { kind: MouseEventKind::ScrollDown, .. })));
+    }
+
+    #[test]
+    fn test_session_backend_size_and_input() {
+        let (session, handle) = Session::new(io::sink(), 40, 10);
+        let backend = session.open(&Capabilities::default()).unwrap();
+        let mut events = backend.events();
+        assert!(handle.input(b"q"));
+        assert!(handle.resize(50, 12));
+        assert_eq!(backend.size().unwrap(), Size::new(50, 12));
+        let next = |events: &mut Box<dyn EventSource>| match events.poll_event(Duration::from_millis(10)).unwrap() {
+            Polled::Event(event) => Some(event),
+            _ => None,
+        };
+        assert_eq!(next(&mut events), Some(Event::Key(KeyEvent::from(KeyCode::Char('q')))));
+        assert_eq!(next(&mut events), Some(Event::Resize(50, 12)));
+        drop(handle);
+        assert!(matches!(events.poll_event(Duration::from_millis(10)).unwrap(), Polled::Closed));
+    }
+}
@@ -0,0 +1,442 @@
+//! Ready-made root component with header, sidebar, body and status bar slots.
+//!
+//! `AppShell` is the skeleton most apps start with: fill the slots with components,
+//! add the body pages as named routes and set it as the root. Routing, lifecycle
+//! hooks, focus and the command palette's route list are wired up, the same way the
+//! Root generated by `define_app!` does it.
+//!
+//! # Example
+//! ```ignore
+//! Application::new().run(|cx| {
+//!     cx.set_root(
+//!         AppShell::new()
+//!             .header(1, TitleBar::default())
+//!             .sidebar(20, NavMenu::default())
+//!             .status_bar(1, StatusBar::default())
+//!             .route("Home", HomePage::default())
+//!             .route("Settings", SettingsPage::default()),
+//!     )
+//! })?;
+//! ```
+
+use ratatui::layout::{Constraint, Layout, Rect};
+
+use crate::component::binding::Held;
+use crate::component::traits::{Action, Component, Event};
+use crate::router::{ChromeAreas, ChromeLayout, EventTarget, NavigationEvent, NavigationTrigger, Router, Slot};
+use crate::{Context, EventContext};
+
+/// Root component with header, sidebar, body and status bar slots.
+///
+/// The body shows one route at a time; any slot can switch it by returning
+/// `Action::Navigate(name)` (matched case-insensitively) or `Action::Back`, and
+/// `AppContext::navigate` works from anywhere. Keys go to the focused slot (the body
+/// initially; `F6` cycles, a click focuses), mouse events to the slot under the
+/// pointer and other events to every slot. The status bar never takes focus. When
+/// focus moves, the slot losing it gets `on_blur` and the one gaining it `on_focus`.
+pub struct AppShell {
+    header: Option<Held>,
+    sidebar: Option<Held>,
+    status_bar: Option<Held>,
+    status_height: u16,
+    routes: Vec<(String, Held)>,
+    router: Router<usize>,
+    layout: ChromeLayout,
+    focus: Slot,
+    areas: ChromeAreas,
+    status_area: Rect,
+}
+
+impl Default for AppShell {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AppShell {
+    /// An empty shell.
+    pub fn new() -> Self {
+        Self {
+            header: None,
+            sidebar: None,
+            status_bar: None,
+            status_height: 0,
+            routes: Vec::new(),
+            router: Router::new(0),
+            layout: ChromeLayout::new(),
+            focus: Slot::Content,
+            areas: ChromeAreas::default(),
+            status_area: Rect::default(),
+        }
+    }
+
+    /// Show `component` in a header of `height` rows.
+    pub fn header(mut self, height: u16, component: impl Component) -> Self {
+        self.header = Some(Held::new(component));
+        self.layout = self.layout.header(height);
+        self
+    }
+
+    /// Show `component` in a sidebar of `width` columns.
+    pub fn sidebar(mut self, width: u16, component: impl Component) -> Self {
+        self.sidebar = Some(Held::new(component));
+        self.layout = self.layout.sidebar(width);
+        self
+    }
+
+    /// Show `component` in a status bar of `height` rows along the bottom.
+    pub fn status_bar(mut self, height: u16, component: impl Component) -> Self {
+        self.status_bar = Some(Held::new(component));
+        self.status_height = height;
+        self
+    }
+
+    /// Add a body page reachable as route `name`. The first route is shown initially.
+    pub fn route(mut self, name: impl Into<String>, component: impl Component) -> Self {
+        self.routes.push((name.into(), Held::new(component)));
+        self
+    }
+
+    /// Use `component` as the only body page, for apps without routing.
+    pub fn body(self, component: impl Component) -> Self {
+        self.route("Main", component)
+    }
+
+    /// Name of the route shown in the body.
+    pub fn current_route(&self) -> Option<&str> {
+        self.routes.get(*self.router.current()).map(|(name, _)| name.as_str())
+    }
+
+    /// The slot receiving key events.
+    pub fn focused_slot(&self) -> Slot {
+        self.focus
+    }
+
+    /// Move key focus to `slot`, calling `on_blur` and `on_focus`; ignored if the slot
+    /// is empty.
+    pub fn focus_slot<P: ?Sized + Send + Sync>(&mut self, slot: Slot, cx: &Context<P>) {
+        if self.layout.slots().contains(&slot) {
+            let from = std::mem::replace(&mut self.focus, slot);
+            self.focus_moved(from, cx);
+        }
+    }
+
+    /// Show route `name` in the body, running the lifecycle hooks. Returns
+    /// `Error::RouteNotFound` if there is no such route.
+    pub fn navigate<P: ?Sized + Send + Sync>(&mut self, name: &str, cx: &Context<P>) -> crate::Result<()> {
+        self.go_to(name, NavigationTrigger::Request, cx)
+    }
+
+    /// Return to the previous route. Returns whether there was one.
+    pub fn go_back<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) -> bool {
+        if !self.router.can_go_back() {
+            return false;
+        }
+        let from = self.current_route().map(str::to_string);
+        self.exit_page(cx);
+        self.router.go_back();
+        self.enter_page(cx);
+        self.announce(from, NavigationTrigger::Back, cx);
+        true
+    }
+
+    fn go_to<P: ?Sized + Send + Sync>(&mut self, name: &str, trigger: NavigationTrigger, cx: &Context<P>) -> crate::Result<()> {
+        let Some(index) = self.routes.iter().position(|(route, _)| route.eq_ignore_ascii_case(name)) else {
+            let available = self.routes.iter().map(|(route, _)| route.clone()).collect();
+            return Err(crate::Error::RouteNotFound { route: name.to_string(), available });
+        };
+        if index != *self.router.current() {
+            let from = self.current_route().map(str::to_string);
+            self.exit_page(cx);
+            self.router.navigate(index);
+            self.enter_page(cx);
+            self.announce(from, trigger, cx);
+        }
+        Ok(())
+    }
+
+    fn announce<P: ?Sized + Send + Sync>(&self, from: Option<String>, trigger: NavigationTrigger, cx: &Context<P>) {
+        if let (Some(from), Some(to)) = (from, self.current_route()) {
+            cx.emit_navigation(NavigationEvent::new(from, to, trigger));
+        }
+    }
+
+    fn page(&mut self) -> Option<&mut Held> {
+        self.routes.get_mut(*self.router.current()).map(|(_, page)| page)
+    }
+
+    fn enter_page<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        if let Some(route) = self.current_route() {
+            cx.set_active_route(route);
+        }
+        let area = self.areas.content;
+        if let Some(page) = self.page() {
+            page.with(cx, Some(area), |view, cx| view.on_enter_any(cx));
+        }
+    }
+
+    fn exit_page<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        let area = self.areas.content;
+        if let Some(page) = self.page() {
+            page.with(cx, Some(area), |view, cx| view.on_exit_any(cx));
+        }
+    }
+
+    /// The component in `slot` and its area.
+    fn slot(&mut self, slot: Slot) -> Option<(Rect, &mut Held)> {
+        match slot {
+            Slot::Header => Some((self.areas.header, self.header.as_mut()?)),
+            Slot::Sidebar => Some((self.areas.sidebar, self.sidebar.as_mut()?)),
+            Slot::Content => {
+                let area = self.areas.content;
+                Some((area, self.page()?))
+            }
+        }
+    }
+
+    /// Tell the slots focus moved from `from` to the focused slot.
+    fn focus_moved<P: ?Sized + Send + Sync>(&mut self, from: Slot, cx: &Context<P>) {
+        if from == self.focus {
+            return;
+        }
+        if let Some((area, view)) = self.slot(from) {
+            view.with(cx, Some(area), |view, cx| view.on_blur_any(cx));
+        }
+        if let Some((area, view)) = self.slot(self.focus) {
+            view.with(cx, Some(area), |view, cx| view.on_focus_any(cx));
+        }
+    }
+
+    /// Every occupied slot with its area, the body page last.
+    fn slots(&mut self) -> Vec<(Rect, &mut Held)> {
+        let page = self.routes.get_mut(*self.router.current()).map(|(_, page)| page);
+        let parts = [
+            (self.areas.header, self.header.as_mut()),
+            (self.areas.sidebar, self.sidebar.as_mut()),
+            (self.status_area, self.status_bar.as_mut()),
+            (self.areas.content, page),
+        ];
+        parts.into_iter().filter_map(|(area, view)| Some((area, view?))).collect()
+    }
+
+    fn deliver(&mut self, event: Event, cx: &Context<Self>) -> Option<Action> {
+        let in_status = event.position().is_some_and(|position| self.status_area.contains(position));
+        if in_status {
+            let status = self.status_bar.as_mut()?;
+            return status.with(cx, Some(self.status_area), |view, cx| view.handle_event_any(event, cx));
+        }
+        let (header, sidebar, content) = (self.areas.header, self.areas.sidebar, self.areas.content);
+        let from = self.focus;
+        let target = self.layout.target(&event, &self.areas, &mut self.focus);
+        self.focus_moved(from, cx);
+        match target {
+            EventTarget::Consumed => None,
+            EventTarget::Slot(Slot::Header) => self.header.as_mut()?.with(cx, Some(header), |view, cx| view.handle_event_any(event, cx)),
+            EventTarget::Slot(Slot::Sidebar) => self.sidebar.as_mut()?.with(cx, Some(sidebar), |view, cx| view.handle_event_any(event, cx)),
+            EventTarget::Slot(Slot::Content) => self.page()?.with(cx, Some(content), |view, cx| view.handle_event_any(event, cx)),
+            EventTarget::All => {
+                let mut action = None;
+                for (area, view) in self.slots() {
+                    action = view.with(cx, Some(area), |view, cx| view.handle_event_any(event.clone(), cx)).or(action);
+                }
+                action
+            }
+        }
+    }
+}
+
+impl Component for AppShell {
+    fn on_mount(&mut self, cx: &mut Context<Self>) {
+        cx.register_routes(self.routes.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>());
+        let chrome = [self.header.as_mut(), self.sidebar.as_mut(), self.status_bar.as_mut()];
+        for view in chrome.into_iter().flatten() {
+            view.with(cx, None, |view, cx| view.on_mount_any(cx));
+        }
+        for (_, page) in &mut self.routes {
+            page.with(cx, None, |view, cx| view.on_mount_any(cx));
+        }
+    }
+
+    fn on_enter(&mut self, cx: &mut Context<Self>) {
+        if let Some(route) = self.current_route() {
+            cx.set_active_route(route);
+        }
+        for (area, view) in self.slots() {
+            view.with(cx, Some(area), |view, cx| view.on_enter_any(cx));
+        }
+    }
+
+    fn on_exit(&mut self, cx: &mut Context<Self>) {
+        for (area, view) in self.slots() {
+            view.with(cx, Some(area), |view, cx| view.on_exit_any(cx));
+        }
+    }
+
+    fn on_shutdown(&mut self, cx: &mut Context<Self>) {
+        let chrome = [self.header.as_mut(), self.sidebar.as_mut(), self.status_bar.as_mut()];
+        for view in chrome.into_iter().flatten() {
+            view.with(cx, None, |view, cx| view.on_shutdown_any(cx));
+        }
+        for (_, page) in &mut self.routes {
+            page.with(cx, None, |view, cx| view.on_shutdown_any(cx));
+        }
+    }
+
+    fn on_resize(&mut self, width: u16, height: u16, cx: &mut Context<Self>) {
+        let chrome = [self.header.as_mut(), self.sidebar.as_mut(), self.status_bar.as_mut()];
+        for view in chrome.into_iter().flatten() {
+            view.with(cx, None, |view, cx| view.on_resize_any(width, height, cx));
+        }
+        for (_, page) in &mut self.routes {
+            page.with(cx, None, |view, cx| view.on_resize_any(width, height, cx));
+        }
+    }
+
+    fn on_focus(&mut self, cx: &mut Context<Self>) {
+        if let Some((area, view)) = self.slot(self.focus) {
+            view.with(cx, Some(area), |view, cx| view.on_focus_any(cx));
+        }
+    }
+
+    fn on_blur(&mut self, cx: &mut Context<Self>) {
+        if let Some((area, view)) = self.slot(self.focus) {
+            view.with(cx, Some(area), |view, cx| view.on_blur_any(cx));
+        }
+    }
+
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(self.status_height)]).areas(cx.area(frame));
+        self.areas = self.layout.split(main);
+        self.status_area = status;
+        cx.name_area("body", self.areas.content);
+        for (area, view) in self.slots() {
+            view.with(cx, Some(area), |view, cx| view.render_any(frame, cx));
+        }
+    }
+
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+        let requested = matches!(&event, Event::Custom(custom) if custom.name() == crate::router::NAVIGATE);
+        let action = match event.custom::<String>() {
+            Some(route) if requested => Some(Action::Navigate(route.clone())),
+            _ => self.deliver(event, cx),
+        };
+        match action? {
+            Action::Navigate(route) => {
+                cx.refresh();
+                let trigger = if requested { NavigationTrigger::Request } else { NavigationTrigger::Action };
+                if let Err(error) = self.go_to(&route, trigger, cx) {
+                    log::warn!("AppShell: {error}");
+                }
+                None
+            }
+            Action::Back => {
+                cx.refresh();
+                self.go_back(cx);
+                None
+            }
+            Action::Quit => Some(Action::Quit),
+            Action::Noop => None,
+            Action::Dispatch(action) => Some(Action::Dispatch(action)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+    use ratatui::style::Style;
+
+    struct Label(&'static str);
+
+    impl Component for Label {
+        fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+            let area = cx.area(frame);
+            frame.buffer_mut().set_string(area.x, area.y, self.0, Style::default());
+        }
+
+        fn handle_event(&mut self, event: Event, _cx: &mut Context<Self>) -> Option<Action> {
+            match event {
+                Event::Key(key) if self.0 == "nav" => Some(match key.code {
+                    crossterm::event::KeyCode::Esc => Action::Back,
+                    _ => Action::Navigate("settings".into()),
+                }),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_shell_slots_and_navigation() {
+        let shell = AppShell::new()
+            .header(1, Label("title"))
+            .sidebar(4, Label("nav"))
+            .status_bar(1, Label("ready"))
+            .route("Home", Label("home"))
+            .route("Settings", Label("settings"));
+        let mut harness = Harness::with_size(shell, 16, 4);
+        assert_eq!(harness.text(), "title\nnav home\n\nready");
+
+        // Keys go to the body until the sidebar is focused
+        harness.type_str("x");
+        assert_eq!(harness.read(|shell| shell.current_route().map(String::from)), Some("Home".into()));
+        harness.type_str("<F6><F6>x");
+        assert_eq!(harness.read(|shell| shell.focused_slot()), Slot::Sidebar);
+        harness.expect_text("nav settings");
+        harness.type_str("<Esc>");
+        harness.expect_text("nav home");
+    }
+
+    #[test]
+    fn test_unknown_route_lists_available() {
+        let (app, _refresh, _inject) = crate::AppContext::new();
+        let cx = Context::<AppShell>::new(app, crate::Entity::new(AppShell::new()).downgrade());
+        let mut shell = AppShell::new().route("Home", Label("home")).route("Settings", Label("settings"));
+        let error = shell.navigate("about", &cx).unwrap_err();
+        assert_eq!(error.to_string(), r#"Unknown route "about" (available: Home, Settings)"#);
+        shell.navigate("settings", &cx).unwrap();
+        assert_eq!(shell.current_route(), Some("Settings"));
+    }
+
+    struct Focusable(&'static str, crate::Entity<Vec<String>>);
+
+    impl Component for Focusable {
+        fn on_focus(&mut self, _cx: &mut Context<Self>) {
+            let _ = self.1.update(|log| log.push(format!("+{}", self.0)));
+        }
+
+        fn on_blur(&mut self, _cx: &mut Context<Self>) {
+            let _ = self.1.update(|log| log.push(format!("-{}", self.0)));
+        }
+
+        fn render(&mut self, _frame: &mut ratatui::Frame, _cx: &mut Context<Self>) {}
+    }
+
+    #[test]
+    fn test_focus_moves_call_blur_and_focus() {
+        use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+        let log = crate::Entity::new(Vec::new());
+        let shell = AppShell::new()
+            .header(1, Focusable("header", log.clone()))
+            .sidebar(4, Focusable("sidebar", log.clone()))
+            .body(Focusable("body", log.clone()));
+        let mut harness = Harness::with_size(shell, 16, 4);
+        harness.type_str("<F6>");
+        harness.send(Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 8,
+            row: 2,
+            modifiers: KeyModifiers::NONE,
+        }));
+        // A click in the focused slot changes nothing
+        harness.send(Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 9,
+            row: 2,
+            modifiers: KeyModifiers::NONE,
+        }));
+        assert_eq!(log.read(Vec::clone).unwrap(), ["-body", "+header", "-header", "+body"]);
+    }
+}
@@ -0,0 +1,364 @@
+//! Headless soak-test mode for leak detection.
+//!
+//! Setting `RAT_NEXUS_SOAK=<minutes>` makes `Application::run` skip the real terminal
+//! and drive the app headlessly with a storm of synthetic key, mouse and resize events.
+//! Every few seconds it samples the number of alive tokio tasks, alive entities, the
+//! approximate size of entities registered with `Entity::track_size`, the heap and the
+//! resident memory of the process, and prints a growth report to stderr at the end. The
+//! report names the largest tracked entities, so an unbounded `Vec` points at its owner.
+//!
+//! The heap size is jemalloc's count of allocated bytes. It needs the `soak` feature and
+//! `tikv_jemallocator::Jemalloc` (0.6) installed as the binary's `#[global_allocator]`,
+//! and is not available on MSVC targets. The resident memory is read from procfs and is
+//! only available on Linux. Either shows as `n/a` in the report when unavailable.
+//!
+//! Optional knobs:
+//! - `RAT_NEXUS_SOAK_RATE`: synthetic events per second (default 200)
+//! - `RAT_NEXUS_SOAK_SAMPLE_SECS`: seconds between samples (default 5)
+//! - `RAT_NEXUS_SOAK_SEED`: seed of the event storm (default from the clock); the report
+//!   prints the seed used, so a run that found a leak can be repeated
+//!
+//! `Action::Quit` is ignored during a soak so the storm can reach every page.
+//!
+//! Alive entities are only counted where entities are tracked (see `AppContext::live_entities`):
+//! in debug builds, or in release builds with the `soak` feature. Elsewhere soak mode
+//! is unavailable and `RAT_NEXUS_SOAK` is ignored with a warning.
+
+use crate::application::{AppContext, Application};
+use crate::component::traits::{AnyComponent, Event};
+use crate::state::{self, Entity};
+use crossterm::event::{
+    Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Environment variable enabling soak mode, in minutes.
+pub const SOAK_ENV: &str = "RAT_NEXUS_SOAK";
+/// Environment variable for the synthetic event rate, per second.
+pub const SOAK_RATE_ENV: &str = "RAT_NEXUS_SOAK_RATE";
+/// Environment variable for the sampling interval, in seconds.
+pub const SOAK_SAMPLE_ENV: &str = "RAT_NEXUS_SOAK_SAMPLE_SECS";
+/// Environment variable for the event storm seed.
+pub const SOAK_SEED_ENV: &str = "RAT_NEXUS_SOAK_SEED";
+
+/// Soak run configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoakConfig {
+    /// Total run time.
+    pub duration: Duration,
+    /// Synthetic events per second.
+    pub events_per_sec: u32,
+    /// Interval between resource samples.
+    pub sample_interval: Duration,
+    /// Seed of the synthetic event storm.
+    pub seed: u64,
+}
+
+impl SoakConfig {
+    /// Read the configuration from the environment. Returns `None` if soak mode is off,
+    /// or unavailable in this build.
+    pub fn from_env() -> Option<Self> {
+        let minutes: f64 = std::env::var(SOAK_ENV).ok()?.parse().ok()?;
+        if !state::TRACK_LIVE {
+            log::warn!("{SOAK_ENV} ignored: release builds need the `soak` feature to count entities");
+            return None;
+        }
+        let events_per_sec = std::env::var(SOAK_RATE_ENV).ok().and_then(|v| v.parse().ok()).unwrap_or(200);
+        let sample_secs: u64 = std::env::var(SOAK_SAMPLE_ENV).ok().and_then(|v| v.parse().ok()).unwrap_or(5);
+        let seed = std::env::var(SOAK_SEED_ENV).ok().and_then(|v| v.parse().ok()).unwrap_or_else(clock_seed);
+        Some(Self {
+            duration: Duration::from_secs_f64(minutes.max(0.0) * 60.0),
+            events_per_sec: events_per_sec.max(1),
+            sample_interval: Duration::from_secs(sample_secs.max(1)),
+            seed,
+        })
+    }
+}
+
+/// One resource sample taken during a soak run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoakSample {
+    /// Time since the start of the run.
+    pub elapsed: Duration,
+    /// Alive tokio tasks.
+    pub tasks: usize,
+    /// Alive entities.
+    pub entities: usize,
+    /// Approximate size of the tracked entities, in bytes.
+    pub tracked_bytes: usize,
+    /// Bytes allocated through jemalloc, when it is the global allocator.
+    pub heap_bytes: Option<u64>,
+    /// Resident set size in bytes, where the platform exposes it.
+    pub rss_bytes: Option<u64>,
+}
+
+/// Run the application headlessly under a synthetic event storm and report resource growth.
+pub(crate) async fn run<E: Send + Sync + 'static>(
+    application: &Application<E>,
+    config: SoakConfig,
+    app: AppContext,
+    root: Entity<dyn AnyComponent>,
+    re_render_rx: mpsc::UnboundedReceiver<()>,
+    inject_rx: mpsc::UnboundedReceiver<Event>,
+    startup: crate::splash::Startup,
+) -> anyhow::Result<()> {
+    let mut terminal = Terminal::new(TestBackend::new(120, 40))?;
+    Application::<E>::mount_root(&app, &root)?;
+
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let storm = tokio::spawn(storm(event_tx, config.clone()));
+    let sampler = tokio::spawn(sample(AppContext::clone(&app), config.clone()));
+    let entities = AppContext::clone(&app);
+
+    application.run_app_loop(app, &mut terminal, root, re_render_rx, inject_rx, event_rx, startup, true).await?;
+    let _ = storm.await;
+    let samples = sampler.await.unwrap_or_default();
+    eprintln!("{}", report(&samples));
+    eprintln!("  seed {} (repeat with {SOAK_SEED_ENV}={})", config.seed, config.seed);
+    for (id, name, size) in entities.entity_sizes().into_iter().take(5) {
+        eprintln!("  entity {id} ({name}): {:.1} KiB", size as f64 / 1024.0);
+    }
+    Ok(())
+}
+
+/// Feed synthetic events until the configured duration elapses, then close the channel.
+async fn storm(tx: mpsc::UnboundedSender<CrosstermEvent>, config: SoakConfig) {
+    let start = Instant::now();
+    let mut rng = XorShift::new(config.seed);
+    let mut ticker = tokio::time::interval(Duration::from_secs(1) / config.events_per_sec);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    while start.elapsed() < config.duration {
+        ticker.tick().await;
+        if tx.send(synthetic_event(&mut rng)).is_err() {
+            break;
+        }
+    }
+}
+
+/// Sample resource usage at the configured interval for the duration of the run.
+async fn sample(app: AppContext, config: SoakConfig) -> Vec<SoakSample> {
+    let start = Instant::now();
+    let handle = tokio::runtime::Handle::current();
+    let mut samples = Vec::new();
+    loop {
+        samples.push(SoakSample {
+            elapsed: start.elapsed(),
+            tasks: handle.metrics().num_alive_tasks(),
+            entities: app.live_entities().len(),
+            tracked_bytes: app.entity_sizes().iter().map(|(_, _, size)| size).sum(),
+            heap_bytes: allocated_memory(),
+            rss_bytes: resident_memory(),
+        });
+        if start.elapsed() >= config.duration {
+            break;
+        }
+        tokio::time::sleep(config.sample_interval.min(config.duration.saturating_sub(start.elapsed()))).await;
+    }
+    samples
+}
+
+/// Generate a random key, mouse or resize event. Quit keys are excluded.
+fn synthetic_event(rng: &mut XorShift) -> CrosstermEvent {
+    const KEYS: &[KeyCode] = &[
+        KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right, KeyCode::Enter,
+        KeyCode::Tab, KeyCode::BackTab, KeyCode::Esc, KeyCode::Backspace,
+        KeyCode::PageUp, KeyCode::PageDown, KeyCode::Home, KeyCode::End, KeyCode::Char(' '),
+    ];
+    match rng.next() % 10 {
+        0..=5 => {
+            let code = if rng.next().is_multiple_of(2) {
+                KEYS[(rng.next() % KEYS.len() as u64) as usize]
+            } else {
+                let c = (b'a' + (rng.next() % 26) as u8) as char;
+                KeyCode::Char(if c == 'q' { 'w' } else { c })
+            };
+            CrosstermEvent::Key(KeyEvent::new(code, KeyModifiers::NONE))
+        }
+        6..=8 => {
+            let kinds = [
+                MouseEventKind::Moved,
+                MouseEventKind::Down(MouseButton::Left),
+                MouseEventKind::Up(MouseButton::Left),
+                MouseEventKind::Down(MouseButton::Right),
+                MouseEventKind::ScrollUp,
+                MouseEventKind::ScrollDown,
+            ];
+            CrosstermEvent::Mouse(MouseEvent {
+                kind: kinds[(rng.next() % kinds.len() as u64) as usize],
+                column: (rng.next() % 120) as u16,
+                row: (rng.next() % 40) as u16,
+                modifiers: KeyModifiers::NONE,
+            })
+        }
+        _ => CrosstermEvent::Resize(60 + (rng.next() % 60) as u16, 20 + (rng.next() % 20) as u16),
+    }
+}
+
+/// Bytes the application has allocated through jemalloc, when it is the global allocator.
+#[cfg(all(feature = "soak", not(target_env = "msvc")))]
+fn allocated_memory() -> Option<u64> {
+    use tikv_jemalloc_ctl::{epoch, stats};
+    // Statistics are a snapshot refreshed by advancing the epoch
+    let allocated = || -> Option<usize> {
+        epoch::advance().ok()?;
+        stats::allocated::read().ok()
+    };
+    // jemalloc counts its own bookkeeping even when it is only linked, so check once that
+    // a large allocation shows up in its statistics
+    static GLOBAL: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    let global = *GLOBAL.get_or_init(|| {
+        const PROBE: usize = 1 << 20;
+        let Some(before) = allocated() else { return false };
+        let probe = std::hint::black_box(vec![0u8; PROBE]);
+        let after = allocated();
+        drop(probe);
+        after.is_some_and(|after| after >= before + PROBE)
+    });
+    if !global {
+        return None;
+    }
+    allocated().and_then(|bytes| u64::try_from(bytes).ok())
+}
+
+#[cfg(not(all(feature = "soak", not(target_env = "msvc"))))]
+fn allocated_memory() -> Option<u64> {
+    None
+}
+
+/// Resident set size of the current process, read from procfs on Linux.
+fn resident_memory() -> Option<u64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some(pages * page_size()?)
+}
+
+/// Size of a memory page, which `statm` counts in.
+#[cfg(unix)]
+fn page_size() -> Option<u64> {
+    // SAFETY: sysconf has no preconditions and only reads a system constant.
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    u64::try_from(size).ok().filter(|&size| size > 0)
+}
+
+#[cfg(not(unix))]
+fn page_size() -> Option<u64> {
+    None
+}
+
+/// A storm seed that differs between runs.
+fn clock_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|since| since.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+/// Least-squares slope of `values` over `minutes`, in units per minute.
+fn slope_per_minute(minutes: &[f64], values: &[f64]) -> f64 {
+    let n = minutes.len() as f64;
+    if n < 2.0 {
+        return 0.0;
+    }
+    let mean_x = minutes.iter().sum::<f64>() / n;
+    let mean_y = values.iter().sum::<f64>() / n;
+    let cov: f64 = minutes.iter().zip(values).map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let var: f64 = minutes.iter().map(|x| (x - mean_x).powi(2)).sum();
+    if var == 0.0 { 0.0 } else { cov / var }
+}
+
+/// Format a growth report over the collected samples.
+pub fn report(samples: &[SoakSample]) -> String {
+    let Some(last) = samples.last() else {
+        return "soak: no samples collected".to_string();
+    };
+    let minutes: Vec<f64> = samples.iter().map(|s| s.elapsed.as_secs_f64() / 60.0).collect();
+    let series = |f: fn(&SoakSample) -> Option<f64>| -> Option<Vec<f64>> {
+        samples.iter().map(f).collect()
+    };
+
+    let mut out = format!("soak: {} samples over {:.1} min\n", samples.len(), last.elapsed.as_secs_f64() / 60.0);
+    let mut line = |name: &str, values: Option<Vec<f64>>, scale: f64, unit: &str| {
+        let Some(values) = values else {
+            out.push_str(&format!("  {:<9} n/a\n", name));
+            return;
+        };
+        let slope = slope_per_minute(&minutes, &values) / scale;
+        let start = values[0] / scale;
+        let end = values[values.len() - 1] / scale;
+        let verdict = if slope > 0.0 && end > start * 1.2 + 1.0 { "possible leak" } else { "stable" };
+        out.push_str(&format!(
+            "  {:<9} {:>10.1} -> {:>10.1} {:<3} ({:+.2}{}/min) {}\n",
+            name, start, end, unit, slope, unit, verdict
+        ));
+    };
+    line("tasks", series(|s| Some(s.tasks as f64)), 1.0, "");
+    line("entities", series(|s| Some(s.entities as f64)), 1.0, "");
+    line("state", series(|s| Some(s.tracked_bytes as f64)), 1024.0, "KiB");
+    line("heap", series(|s| s.heap_bytes.map(|b| b as f64)), 1024.0 * 1024.0, "MiB");
+    line("memory", series(|s| s.rss_bytes.map(|b| b as f64)), 1024.0 * 1024.0, "MiB");
+    out
+}
+
+/// Small xorshift PRNG so soak runs don't need an external RNG crate.
+struct XorShift(u64);
+
+impl XorShift {
+    /// Seed the generator; xorshift is stuck at zero, so the seed is mixed and kept odd.
+    fn new(seed: u64) -> Self {
+        Self(0x9E37_79B9_7F4A_7C15 ^ seed | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(minute: u64, tasks: usize) -> SoakSample {
+        SoakSample { elapsed: Duration::from_secs(minute * 60), tasks, entities: 10, tracked_bytes: 0, heap_bytes: None, rss_bytes: None }
+    }
+
+    #[test]
+    fn test_report_flags_growth() {
+        let samples: Vec<SoakSample> = (0..5).map(|m| sample(m, 10 + m as usize * 50)).collect();
+        let report = report(&samples);
+        assert!(report.lines().any(|l| l.contains("tasks") && l.contains("possible leak")));
+        assert!(report.lines().any(|l| l.contains("entities") && l.contains("stable")));
+        assert!(report.lines().any(|l| l.contains("heap") && l.contains("n/a")));
+        assert!(report.lines().any(|l| l.contains("memory") && l.contains("n/a")));
+    }
+
+    #[test]
+    fn test_heap_needs_jemalloc_as_global_allocator() {
+        // Tests run on the system allocator, so jemalloc has nothing to report
+        assert_eq!(allocated_memory(), None);
+    }
+
+    #[test]
+    fn test_storm_is_reproducible_from_its_seed() {
+        let storm = |seed| {
+            let mut rng = XorShift::new(seed);
+            (0..32).map(|_| synthetic_event(&mut rng)).collect::<Vec<_>>()
+        };
+        assert_eq!(storm(7), storm(7));
+        assert_ne!(storm(7), storm(8));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_resident_memory_uses_the_page_size() {
+        let page = page_size().unwrap();
+        assert!(page.is_power_of_two());
+        assert_eq!(resident_memory().unwrap() % page, 0);
+    }
+}
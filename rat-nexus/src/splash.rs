@@ -0,0 +1,197 @@
+//! Splash screen shown during startup, and the first-frame readiness hook.
+//!
+//! `Application::with_splash` puts a component on screen before `setup` runs and keeps
+//! it there until the root is ready: setup has returned, the root no longer reports
+//! `PageStatus::Loading` (e.g. while data started in `on_mount` is preloading), its
+//! `on_mount_async` task has completed, and the splash has been up for its minimum
+//! duration. The root is mounted behind the splash, so injected events still reach it;
+//! terminal input goes to the splash. Once the root takes over, `Application::on_ready`
+//! runs right after its first frame.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use ratatui::Terminal;
+
+use crate::component::traits::{Action, AnyComponent, Component, Event};
+use crate::graphics::GraphicsBackend;
+use crate::router::PageStatus;
+use crate::state::{Entity, RwLock};
+use crate::{AppContext, Context, EventContext};
+
+/// Called once after the first frame showing the root.
+pub(crate) type ReadyHook = Box<dyn FnOnce(&AppContext) + Send>;
+
+/// How often readiness is re-checked, and the splash redrawn, while it is up.
+pub(crate) const SPLASH_TICK: Duration = Duration::from_millis(50);
+
+/// A component to show while the application starts.
+pub(crate) struct Splash {
+    view: Entity<dyn AnyComponent>,
+    min_duration: Duration,
+}
+
+impl Splash {
+    pub(crate) fn new(component: impl Component, min_duration: Duration) -> Self {
+        let view = Entity::from_arc(Arc::new(RwLock::new(component)) as Arc<RwLock<dyn AnyComponent>>);
+        Self { view, min_duration }
+    }
+}
+
+/// Startup progress of a running application.
+pub(crate) struct Startup {
+    splash: Option<Splash>,
+    shown_since: Option<Instant>,
+    on_ready: Option<ReadyHook>,
+}
+
+impl Startup {
+    pub(crate) fn new(splash: Option<Splash>, on_ready: Option<ReadyHook>) -> Self {
+        Self { splash, shown_since: None, on_ready }
+    }
+
+    /// Whether the splash has not been taken down yet.
+    pub(crate) fn waiting(&self) -> bool {
+        self.splash.is_some()
+    }
+
+    fn context(view: &Entity<dyn AnyComponent>, app: &AppContext) -> Context<dyn AnyComponent> {
+        Context::new(AppContext::clone(app), view.downgrade())
+    }
+
+    fn show(&mut self, app: &AppContext) {
+        let Some(splash) = &self.splash else { return };
+        if self.shown_since.is_none() {
+            self.shown_since = Some(Instant::now());
+            let mut cx = Self::context(&splash.view, app);
+            let _ = splash.view.update(|comp| {
+                comp.on_mount_any(&mut cx);
+                comp.on_enter_any(&mut cx);
+            });
+        }
+    }
+
+    /// Decide what the next frame shows: true for the splash. Takes the splash down
+    /// once the root is ready and the minimum duration has passed.
+    pub(crate) fn splash_active(&mut self, app: &AppContext, root: &Entity<dyn AnyComponent>) -> bool {
+        self.show(app);
+        let Some(splash) = &self.splash else { return false };
+        let early = self.shown_since.is_some_and(|since| since.elapsed() < splash.min_duration);
+        let loading = app.mounts.root_pending() || root.read(|comp| comp.status_any() == PageStatus::Loading).unwrap_or(false);
+        if early || loading {
+            return true;
+        }
+        let mut cx = Self::context(&splash.view, app);
+        let _ = splash.view.update(|comp| comp.on_exit_any(&mut cx));
+        self.splash = None;
+        false
+    }
+
+    pub(crate) fn render_splash(&self, frame: &mut ratatui::Frame, app: &AppContext) {
+        let Some(splash) = &self.splash else { return };
+        let mut cx = Self::context(&splash.view, app);
+        let _ = splash.view.update(|comp| comp.render_any(frame, &mut cx));
+    }
+
+    /// Draw the splash on its own, before the event loop is running.
+    pub(crate) fn draw_splash<B: GraphicsBackend>(&mut self, app: &AppContext, terminal: &mut Terminal<B>) -> anyhow::Result<()> {
+        self.show(app);
+        terminal.draw(|frame| {
+            app.clear_regions();
+            self.render_splash(frame, app);
+            app.capabilities().downgrade(frame.buffer_mut());
+            app.prepare_graphics(frame);
+        })?;
+        app.graphics.flush(terminal.backend_mut())?;
+        Ok(())
+    }
+
+    /// Deliver terminal input to the splash. Returns `true` when it requested `Action::Quit`.
+    pub(crate) fn splash_event(&self, app: &AppContext, event: Event) -> anyhow::Result<bool> {
+        let Some(splash) = &self.splash else { return Ok(false) };
+        let mut cx = EventContext::<dyn AnyComponent>::new(AppContext::clone(app), splash.view.downgrade());
+        let action = splash.view.update(|comp| comp.handle_event_any(event, &mut cx))
+            .map_err(|_| anyhow::anyhow!("Splash mutex poisoned during event"))?;
+        app.refresh();
+        Ok(matches!(action, Some(Action::Quit)))
+    }
+
+    /// A frame was drawn; `splash` says whether it showed the splash.
+    pub(crate) fn frame_drawn(&mut self, app: &AppContext, splash: bool) {
+        if !splash {
+            if let Some(on_ready) = self.on_ready.take() {
+                on_ready(app);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use ratatui::backend::TestBackend;
+    use ratatui::Frame;
+
+    /// Renders its text; loading while the flag is set.
+    struct Text(&'static str, Arc<AtomicBool>);
+
+    impl Component for Text {
+        fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+            let area = cx.area(frame);
+            frame.buffer_mut().set_string(area.x, area.y, self.0, ratatui::style::Style::default());
+        }
+
+        fn status(&self) -> PageStatus {
+            if self.1.load(Ordering::SeqCst) { PageStatus::Loading } else { PageStatus::Ready }
+        }
+    }
+
+    fn draw(startup: &mut Startup, app: &AppContext, root: &Entity<dyn AnyComponent>) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(8, 1)).unwrap();
+        let splash = startup.splash_active(app, root);
+        terminal.draw(|frame| {
+            if splash {
+                startup.render_splash(frame, app);
+            } else {
+                let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), root.downgrade());
+                root.update(|comp| comp.render_any(frame, &mut cx)).unwrap();
+            }
+        }).unwrap();
+        startup.frame_drawn(app, splash);
+        terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect::<String>().trim_end().to_string()
+    }
+
+    #[test]
+    fn test_splash_waits_for_root_then_fires_on_ready() {
+        let (app, _, _) = AppContext::new();
+        let ready = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&ready);
+        let loading = Arc::new(AtomicBool::new(true));
+        let mut startup = Startup::new(
+            Some(Splash::new(Text("splash", Arc::default()), Duration::ZERO)),
+            Some(Box::new(move |_: &AppContext| flag.store(true, Ordering::SeqCst))),
+        );
+        let root: Entity<dyn AnyComponent> =
+            Entity::from_arc(Arc::new(RwLock::new(Text("root", Arc::clone(&loading)))) as Arc<RwLock<dyn AnyComponent>>);
+
+        assert_eq!(draw(&mut startup, &app, &root), "splash");
+        assert!(startup.waiting());
+        assert!(!ready.load(Ordering::SeqCst));
+
+        loading.store(false, Ordering::SeqCst);
+        assert_eq!(draw(&mut startup, &app, &root), "root");
+        assert!(!startup.waiting());
+        assert!(ready.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_splash_stays_for_min_duration() {
+        let (app, _, _) = AppContext::new();
+        let mut startup = Startup::new(Some(Splash::new(Text("splash", Arc::default()), Duration::from_secs(60))), None);
+        let root: Entity<dyn AnyComponent> =
+            Entity::from_arc(Arc::new(RwLock::new(Text("root", Arc::default()))) as Arc<RwLock<dyn AnyComponent>>);
+        assert_eq!(draw(&mut startup, &app, &root), "splash");
+        assert_eq!(draw(&mut startup, &app, &root), "splash");
+    }
+}
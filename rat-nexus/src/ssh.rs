@@ -0,0 +1,436 @@
+//! Serving an application over SSH (`ssh` feature).
+//!
+//! `SshServer` accepts SSH connections with russh and runs an `Application` for each
+//! client on the terminal it requested, every one with its own `AppContext` (see
+//! `session`). Clients must ask for a pty (`ssh -t`, the default for interactive
+//! logins); window changes resize the application and closing the channel ends it.
+//! Nothing is shared between clients unless `application` shares it; the connecting
+//! `SshClient` is always available as `cx.get::<SshClient>()`.
+//!
+//! # Example
+//! ```ignore
+//! let board = Entity::new(Board::default());
+//! SshServer::new(|cx| {
+//!         let board = cx.get::<Entity<Board>>()?;
+//!         cx.set_root(Game::new(board))?;
+//!         Ok(())
+//!     })
+//!     .host_key(russh::keys::load_secret_key("host_ed25519", None)?)
+//!     .authenticate(|user, credentials| matches!(credentials, Credentials::Password(p) if check(user, p)))
+//!     .application(move |_client| Application::new().share(board.clone()))
+//!     .listen("0.0.0.0:2222")
+//!     .await?;
+//! ```
+
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use russh::keys::{PrivateKey, PublicKey};
+use russh::server::{Auth, Handle, Msg, Server as _, Session};
+use russh::{Channel, ChannelId, CryptoVec, Pty};
+use tokio::net::{TcpListener, ToSocketAddrs};
+use tokio::sync::mpsc;
+
+use crate::session::SessionHandle;
+use crate::{AppContext, Application};
+
+/// What a client offers to authenticate with, checked by `SshServer::authenticate`.
+#[derive(Debug)]
+pub enum Credentials<'a> {
+    /// Nothing: the client asked to log in without authenticating.
+    None,
+    /// A password.
+    Password(&'a str),
+    /// A public key the client proved it holds the private key of.
+    PublicKey(&'a PublicKey),
+}
+
+/// The client an application serves, shared into its context.
+#[derive(Debug, Clone)]
+pub struct SshClient {
+    /// The user name the client authenticated as.
+    pub user: String,
+    /// The client's address.
+    pub peer: Option<SocketAddr>,
+    /// The client's terminal type (`TERM`).
+    pub term: String,
+}
+
+type AuthFn = dyn Fn(&str, &Credentials) -> bool + Send + Sync;
+type AppFn = dyn Fn(&SshClient) -> Application + Send + Sync;
+type SetupFn = dyn Fn(&AppContext) -> anyhow::Result<()> + Send + Sync;
+
+/// What every connection of a server uses.
+struct Shared {
+    authenticate: Box<AuthFn>,
+    application: Box<AppFn>,
+    setup: Box<SetupFn>,
+}
+
+/// Serves an application to SSH clients, one `Application` per connection.
+pub struct SshServer {
+    config: russh::server::Config,
+    shared: Shared,
+}
+
+impl SshServer {
+    /// A server running `setup` for every client, as `Application::run` would.
+    /// Clients are rejected until `authenticate` or `allow_anyone` says otherwise.
+    pub fn new<F>(setup: F) -> Self
+    where
+        F: Fn(&AppContext) -> anyhow::Result<()> + Send + Sync + 'static,
+    {
+        Self {
+            config: russh::server::Config::default(),
+            shared: Shared {
+                authenticate: Box::new(|_, _| false),
+                application: Box::new(|_| Application::new()),
+                setup: Box::new(setup),
+            },
+        }
+    }
+
+    /// Identify the server with `key`. At least one host key is required.
+    pub fn host_key(mut self, key: PrivateKey) -> Self {
+        self.config.keys.push(key);
+        self
+    }
+
+    /// Accept the clients for which `f(user, credentials)` returns true.
+    pub fn authenticate<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str, &Credentials) -> bool + Send + Sync + 'static,
+    {
+        self.shared.authenticate = Box::new(f);
+        self
+    }
+
+    /// Accept every client, with or without credentials.
+    pub fn allow_anyone(self) -> Self {
+        self.authenticate(|_, _| true)
+    }
+
+    /// Build each client's application with `f` (`Application::new()` by default),
+    /// e.g. to `share` state between clients. The session is attached afterwards.
+    pub fn application<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&SshClient) -> Application + Send + Sync + 'static,
+    {
+        self.shared.application = Box::new(f);
+        self
+    }
+
+    /// Change russh's server settings (timeouts, algorithms...). Host keys are kept.
+    pub fn configure(mut self, f: impl FnOnce(&mut russh::server::Config)) -> Self {
+        f(&mut self.config);
+        self
+    }
+
+    /// Accept connections on `addrs` until the listener fails.
+    pub async fn listen(self, addrs: impl ToSocketAddrs + Send) -> io::Result<()> {
+        self.listen_on(TcpListener::bind(addrs).await?).await
+    }
+
+    /// Accept connections on `listener` until it fails.
+    pub async fn listen_on(self, listener: TcpListener) -> io::Result<()> {
+        if self.config.keys.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "SshServer needs a host key"));
+        }
+        let mut acceptor = Acceptor { shared: Arc::new(self.shared) };
+        acceptor.run_on_socket(Arc::new(self.config), &listener).await
+    }
+}
+
+struct Acceptor {
+    shared: Arc<Shared>,
+}
+
+impl russh::server::Server for Acceptor {
+    type Handler = Connection;
+
+    fn new_client(&mut self, peer: Option<SocketAddr>) -> Connection {
+        Connection { shared: Arc::clone(&self.shared), peer, user: String::new(), pty: None, input: None }
+    }
+
+    fn handle_session_error(&mut self, error: russh::Error) {
+        log::debug!("ssh connection failed: {error}");
+    }
+}
+
+/// One client connection.
+struct Connection {
+    shared: Arc<Shared>,
+    peer: Option<SocketAddr>,
+    user: String,
+    /// The terminal type and size of the requested pty.
+    pty: Option<(String, u16, u16)>,
+    /// Input of the running application.
+    input: Option<SessionHandle>,
+}
+
+impl Connection {
+    fn check(&mut self, user: &str, credentials: Credentials) -> Result<Auth, russh::Error> {
+        if !(self.shared.authenticate)(user, &credentials) {
+            return Ok(Auth::reject());
+        }
+        self.user = user.to_string();
+        Ok(Auth::Accept)
+    }
+
+    /// Run the application on `channel`, closing it once the application ends.
+    fn start(&mut self, channel: ChannelId, handle: Handle, term: String, width: u16, height: u16) {
+        let (tx, mut output) = mpsc::unbounded_channel();
+        let (session, input) = crate::session::Session::new(ChannelWriter { tx, buffer: Vec::new() }, width, height);
+        let client = SshClient { user: self.user.clone(), peer: self.peer, term };
+        let shared = Arc::clone(&self.shared);
+        let run = (shared.application)(&client).share(client).session(session).spawn(move |cx| (shared.setup)(cx));
+        self.input = Some(input);
+
+        tokio::spawn(async move {
+            let mut joined = tokio::task::spawn_blocking(move || run.join());
+            let result = loop {
+                tokio::select! {
+                    bytes = output.recv() => match bytes {
+                        Some(bytes) => {
+                            let _ = handle.data(channel, CryptoVec::from(bytes)).await;
+                        }
+                        None => break (&mut joined).await,
+                    },
+                    result = &mut joined => {
+                        while let Ok(bytes) = output.try_recv() {
+                            let _ = handle.data(channel, CryptoVec::from(bytes)).await;
+                        }
+                        break result;
+                    }
+                }
+            };
+            let status = match result {
+                Ok(Ok(Ok(()))) => 0,
+                Ok(Ok(Err(error))) => {
+                    log::warn!("ssh application failed: {error}");
+                    1
+                }
+                _ => {
+                    log::warn!("ssh application panicked");
+                    1
+                }
+            };
+            let _ = handle.exit_status_request(channel, status).await;
+            let _ = handle.eof(channel).await;
+            let _ = handle.close(channel).await;
+        });
+    }
+}
+
+impl russh::server::Handler for Connection {
+    type Error = russh::Error;
+
+    async fn auth_none(&mut self, user: &str) -> Result<Auth, Self::Error> {
+        self.check(user, Credentials::None)
+    }
+
+    async fn auth_password(&mut self, user: &str, password: &str) -> Result<Auth, Self::Error> {
+        self.check(user, Credentials::Password(password))
+    }
+
+    async fn auth_publickey(&mut self, user: &str, key: &PublicKey) -> Result<Auth, Self::Error> {
+        self.check(user, Credentials::PublicKey(key))
+    }
+
+    async fn channel_open_session(&mut self, _channel: Channel<Msg>, _session: &mut Session) -> Result<bool, Self::Error> {
+        // One application per connection
+        Ok(self.input.is_none())
+    }
+
+    async fn pty_request(
+        &mut self,
+        channel: ChannelId,
+        term: &str,
+        width: u32,
+        height: u32,
+        _pix_width: u32,
+        _pix_height: u32,
+        _modes: &[(Pty, u32)],
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        self.pty = Some((term.to_string(), cells(width), cells(height)));
+        session.channel_success(channel)
+    }
+
+    async fn shell_request(&mut self, channel: ChannelId, session: &mut Session) -> Result<(), Self::Error> {
+        let Some((term, width, height)) = self.pty.clone().filter(|_| self.input.is_none()) else {
+            session.channel_failure(channel)?;
+            session.data(channel, CryptoVec::from_slice(b"A terminal is required; connect with `ssh -t`.\r\n"))?;
+            return session.close(channel);
+        };
+        session.channel_success(channel)?;
+        self.start(channel, session.handle(), term, width, height);
+        Ok(())
+    }
+
+    async fn data(&mut self, _channel: ChannelId, data: &[u8], _session: &mut Session) -> Result<(), Self::Error> {
+        if let Some(input) = &self.input {
+            input.input(data);
+        }
+        Ok(())
+    }
+
+    async fn window_change_request(
+        &mut self,
+        _channel: ChannelId,
+        width: u32,
+        height: u32,
+        _pix_width: u32,
+        _pix_height: u32,
+        _session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        if let Some(input) = &self.input {
+            input.resize(cells(width), cells(height));
+        }
+        Ok(())
+    }
+
+    async fn channel_eof(&mut self, _channel: ChannelId, _session: &mut Session) -> Result<(), Self::Error> {
+        // Dropping the input ends the application
+        self.input = None;
+        Ok(())
+    }
+
+    async fn channel_close(&mut self, _channel: ChannelId, _session: &mut Session) -> Result<(), Self::Error> {
+        self.input = None;
+        Ok(())
+    }
+}
+
+fn cells(n: u32) -> u16 {
+    u16::try_from(n).unwrap_or(u16::MAX)
+}
+
+/// The application's output, sent to the client on each flush.
+struct ChannelWriter {
+    tx: mpsc::UnboundedSender<Vec<u8>>,
+    buffer: Vec<u8>,
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        self.tx.send(std::mem::take(&mut self.buffer)).map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))
+    }
+}
+
+impl Drop for ChannelWriter {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::application::{Context, EventContext};
+    use crate::{Action, Component, Entity, Event};
+    use crossterm::event::KeyCode;
+    use ratatui::Frame;
+    use russh::keys::ssh_key::rand_core::OsRng;
+    use russh::keys::Algorithm;
+    use russh::ChannelMsg;
+
+    struct Client;
+
+    impl russh::client::Handler for Client {
+        type Error = russh::Error;
+
+        async fn check_server_key(&mut self, _key: &PublicKey) -> Result<bool, Self::Error> {
+            Ok(true)
+        }
+    }
+
+    struct Sizes {
+        log: Entity<Vec<String>>,
+    }
+
+    impl Component for Sizes {
+        fn render(&mut self, frame: &mut Frame, _cx: &mut Context<Self>) {
+            frame.render_widget("hello", frame.area());
+        }
+
+        fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+            match event {
+                Event::Resize(width, height) => {
+                    let _ = self.log.update(|log| log.push(format!("{width}x{height}")));
+                    None
+                }
+                Event::Key(key) if key.code == KeyCode::Char('q') => Some(Action::Quit),
+                _ => None,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_serves_one_application_per_client() {
+        let key = PrivateKey::random(&mut OsRng, Algorithm::Ed25519).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let log = Entity::new(Vec::<String>::new());
+        let shared = log.clone();
+        let server = SshServer::new(|cx| {
+                let log = cx.get::<Entity<Vec<String>>>()?;
+                let client = cx.get::<SshClient>()?;
+                let _ = log.update(|log| log.push(format!("{} on {}", client.user, client.term)));
+                cx.set_root(Sizes { log })?;
+                Ok(())
+            })
+            .host_key(key)
+            .authenticate(|user, credentials| user == "ann" && matches!(credentials, Credentials::Password("secret")))
+            .application(move |_client| Application::new().share(shared.clone()));
+        tokio::spawn(server.listen_on(listener));
+
+        let config = Arc::new(russh::client::Config::default());
+        let mut session = russh::client::connect(Arc::clone(&config), addr, Client).await.unwrap();
+        assert!(!session.authenticate_password("ann", "wrong").await.unwrap().success());
+        assert!(session.authenticate_password("ann", "secret").await.unwrap().success());
+        let mut channel = session.channel_open_session().await.unwrap();
+        channel.request_pty(true, "xterm", 40, 10, 0, 0, &[]).await.unwrap();
+        channel.request_shell(true).await.unwrap();
+        channel.window_change(60, 20, 0, 0).await.unwrap();
+
+        let mut output = Vec::new();
+        let mut status = None;
+        let mut quit = false;
+        while let Some(msg) = tokio::time::timeout(Duration::from_secs(10), channel.wait()).await.unwrap() {
+            match msg {
+                ChannelMsg::Data { data } => {
+                    output.extend_from_slice(&data);
+                    if !quit && String::from_utf8_lossy(&output).contains("hello") {
+                        // Let the resize through before quitting
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        channel.data(&b"q"[..]).await.unwrap();
+                        quit = true;
+                    }
+                }
+                ChannelMsg::ExitStatus { exit_status } => status = Some(exit_status),
+                ChannelMsg::Close => break,
+                _ => {}
+            }
+        }
+        assert_eq!(status, Some(0));
+        assert_eq!(log.read(Vec::clone).unwrap(), ["ann on xterm", "60x20"]);
+    }
+
+    #[tokio::test]
+    async fn test_requires_a_host_key() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let error = SshServer::new(|_| Ok(())).allow_anyone().listen_on(listener).await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
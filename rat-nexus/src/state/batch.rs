@@ -0,0 +1,140 @@
+//! Notification batching.
+//!
+//! While a batch is active on the current thread, entity change notifications
+//! and refresh requests are collected instead of being sent immediately. When the
+//! outermost batch ends, each changed entity is notified once and each application
+//! that asked for a refresh gets a single one.
+
+use super::EntityId;
+use std::cell::RefCell;
+use tokio::sync::{mpsc, watch};
+
+#[derive(Default)]
+struct BatchState {
+    depth: usize,
+    notifications: Vec<(EntityId, watch::Sender<()>)>,
+    refresh: Vec<mpsc::UnboundedSender<()>>,
+}
+
+thread_local! {
+    static BATCH: RefCell<BatchState> = RefCell::new(BatchState::default());
+}
+
+/// Guard for an active batch. Flushes pending notifications when the outermost guard drops.
+pub(crate) struct BatchGuard {
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+
+/// Begin a batch on the current thread. Batches nest; only the outermost one flushes.
+pub(crate) fn begin() -> BatchGuard {
+    BATCH.with(|b| b.borrow_mut().depth += 1);
+    BatchGuard { _not_send: std::marker::PhantomData }
+}
+
+impl Drop for BatchGuard {
+    fn drop(&mut self) {
+        let pending = BATCH.with(|b| {
+            let mut b = b.borrow_mut();
+            b.depth -= 1;
+            if b.depth == 0 {
+                Some((std::mem::take(&mut b.notifications), std::mem::take(&mut b.refresh)))
+            } else {
+                None
+            }
+        });
+
+        if let Some((notifications, refresh)) = pending {
+            for (_, tx) in notifications {
+                let _ = tx.send(());
+            }
+            for tx in refresh {
+                let _ = tx.send(());
+            }
+        }
+    }
+}
+
+/// Defer an entity notification if a batch is active. Returns `false` if the
+/// caller should notify immediately.
+pub(crate) fn defer_notify(id: EntityId, tx: &watch::Sender<()>) -> bool {
+    BATCH.with(|b| {
+        let mut b = b.borrow_mut();
+        if b.depth == 0 {
+            return false;
+        }
+        if !b.notifications.iter().any(|(pending, _)| *pending == id) {
+            b.notifications.push((id, watch::Sender::clone(tx)));
+        }
+        true
+    })
+}
+
+/// Whether an entity other than `id` changed during the active batch.
+pub(crate) fn notified_besides(id: EntityId) -> bool {
+    BATCH.with(|b| b.borrow().notifications.iter().any(|(pending, _)| *pending != id))
+}
+
+/// Whether the application behind `tx` requested a refresh during the active batch.
+pub(crate) fn refresh_pending(tx: &mpsc::UnboundedSender<()>) -> bool {
+    BATCH.with(|b| b.borrow().refresh.iter().any(|pending| pending.same_channel(tx)))
+}
+
+/// Defer a refresh request if a batch is active. Returns `false` if the
+/// caller should refresh immediately.
+pub(crate) fn defer_refresh(tx: &mpsc::UnboundedSender<()>) -> bool {
+    BATCH.with(|b| {
+        let mut b = b.borrow_mut();
+        if b.depth == 0 {
+            return false;
+        }
+        if !b.refresh.iter().any(|pending| pending.same_channel(tx)) {
+            b.refresh.push(mpsc::UnboundedSender::clone(tx));
+        }
+        true
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::AppContext;
+
+    #[tokio::test]
+    async fn test_batch_sends_one_refresh() {
+        let (app, mut refresh, _inject) = AppContext::new();
+        let (a, b) = (app.new_entity(0), app.new_entity(0));
+        let (a_rx, b_rx) = (a.subscribe(), b.subscribe());
+
+        app.batch(|app| {
+            for _ in 0..3 {
+                a.update(|v| *v += 1).unwrap();
+                b.update(|v| *v += 2).unwrap();
+                app.refresh();
+            }
+            app.batch(|app| app.refresh());
+            assert!(refresh.try_recv().is_err());
+            assert!(!a_rx.has_changed().unwrap());
+        });
+
+        assert!(refresh.try_recv().is_ok());
+        assert!(refresh.try_recv().is_err());
+        assert!(a_rx.has_changed().unwrap() && b_rx.has_changed().unwrap());
+        assert_eq!((a.read(|v| *v).unwrap(), b.read(|v| *v).unwrap()), (3, 6));
+    }
+
+    #[tokio::test]
+    async fn test_batch_refreshes_every_app() {
+        let (first, mut first_refresh, _first_inject) = AppContext::new();
+        let (second, mut second_refresh, _second_inject) = AppContext::new();
+
+        first.batch(|first| {
+            first.refresh();
+            second.refresh();
+            second.refresh();
+            assert!(second_refresh.try_recv().is_err());
+        });
+
+        assert!(first_refresh.try_recv().is_ok());
+        assert!(second_refresh.try_recv().is_ok());
+        assert!(second_refresh.try_recv().is_err());
+    }
+}
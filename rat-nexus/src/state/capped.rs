@@ -0,0 +1,173 @@
+//! Bounded buffers for history and log state.
+//!
+//! State that grows with time (samples, laps, log lines) must drop old entries or a
+//! long-running session slowly eats memory. `push_capped` bounds an existing `Vec`;
+//! `Capped` is a ring buffer whose bound is part of its type, so it can sit in a
+//! `#[derive(Default)]` state struct.
+
+use std::collections::VecDeque;
+
+use super::SizeOf;
+
+/// Push `item` onto `vec`, then drop the oldest items beyond `cap`.
+///
+/// # Example
+/// ```ignore
+/// state.update(|s| push_capped(&mut s.cpu_history, sample, 60));
+/// ```
+pub fn push_capped<T>(vec: &mut Vec<T>, item: T, cap: usize) {
+    vec.push(item);
+    if vec.len() > cap {
+        vec.drain(..vec.len() - cap);
+    }
+}
+
+/// A buffer keeping the last `N` pushed items, oldest first.
+///
+/// Reads go through `Deref` to the underlying `VecDeque`; writes go through `push`
+/// (and `extend`, `clear`), so the bound cannot be bypassed. With the `persist`
+/// feature it serializes as a sequence, and deserializing keeps the newest `N` items.
+///
+/// # Example
+/// ```ignore
+/// #[derive(Clone, Default)]
+/// pub struct TimerState {
+///     pub laps: Capped<u64, 100>,
+/// }
+///
+/// state.update(|s| s.laps.push(s.elapsed_ms));
+/// let latest = state.read(|s| s.laps.back().copied());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Capped<T, const N: usize> {
+    items: VecDeque<T>,
+}
+
+impl<T, const N: usize> Default for Capped<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Capped<T, N> {
+    /// An empty buffer.
+    pub fn new() -> Self {
+        Self { items: VecDeque::new() }
+    }
+
+    /// The most items kept.
+    pub const fn cap(&self) -> usize {
+        N
+    }
+
+    /// Append `item`, returning the oldest item if it had to make room.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        if N == 0 {
+            return Some(item);
+        }
+        let evicted = if self.items.len() == N { self.items.pop_front() } else { None };
+        self.items.push_back(item);
+        evicted
+    }
+
+    /// Remove all items.
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// The items as one slice, oldest first, e.g. for a `Sparkline`.
+    pub fn as_slice(&mut self) -> &[T] {
+        self.items.make_contiguous()
+    }
+
+    /// Unwrap the items, oldest first.
+    pub fn into_inner(self) -> VecDeque<T> {
+        self.items
+    }
+}
+
+impl<T, const N: usize> std::ops::Deref for Capped<T, N> {
+    type Target = VecDeque<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.items
+    }
+}
+
+impl<T, const N: usize> Extend<T> for Capped<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for Capped<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut capped = Self::new();
+        capped.extend(iter);
+        capped
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a Capped<T, N> {
+    type Item = &'a T;
+    type IntoIter = std::collections::vec_deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl<T: SizeOf, const N: usize> SizeOf for Capped<T, N> {
+    fn heap_size(&self) -> usize {
+        self.items.heap_size()
+    }
+}
+
+#[cfg(feature = "persist")]
+impl<T: serde::Serialize, const N: usize> serde::Serialize for Capped<T, N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.items.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "persist")]
+impl<'de, T: serde::Deserialize<'de>, const N: usize> serde::Deserialize<'de> for Capped<T, N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        VecDeque::<T>::deserialize(deserializer).map(|items| items.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_capped_drops_oldest() {
+        let mut history = vec![1, 2, 3];
+        push_capped(&mut history, 4, 3);
+        assert_eq!(history, vec![2, 3, 4]);
+        push_capped(&mut history, 5, 2);
+        assert_eq!(history, vec![4, 5]);
+    }
+
+    #[test]
+    fn test_capped_bounds() {
+        let mut laps: Capped<u64, 3> = (1..=4).collect();
+        assert_eq!(laps.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(laps.push(5), Some(2));
+        assert_eq!(laps.as_slice(), &[3, 4, 5]);
+    }
+
+    #[cfg(feature = "persist")]
+    #[test]
+    fn test_capped_serde_keeps_newest() {
+        let laps: Capped<u64, 3> = (3..=5).collect();
+        let encoded = serde_json::to_string(&laps).unwrap();
+        assert_eq!(encoded, "[3,4,5]");
+        let decoded: Capped<u64, 2> = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(decoded.into_inner(), VecDeque::from([4, 5]));
+        assert!(serde_json::from_str::<Capped<u64, 3>>("[1,\"x\"]").is_err());
+    }
+}
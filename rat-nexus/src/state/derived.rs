@@ -0,0 +1,231 @@
+//! Derived (computed) entities.
+//!
+//! A derived value is recomputed from one or more source entities and only
+//! notifies its own subscribers when the computed value actually changes.
+
+use super::{Entity, EntityId, Observable, WeakEntity};
+use tokio::sync::{mpsc, watch};
+
+/// A read-only value computed from one or more entities (see `Entity::map`).
+///
+/// Cloning shares the value. The background recompute holds only weak handles to the
+/// sources and stops once the last clone is dropped, so a derived value never keeps
+/// its sources alive.
+pub struct Derived<U: Send + Sync> {
+    entity: Entity<U>,
+}
+
+impl<U: Send + Sync> Derived<U> {
+    /// Get the unique ID of the underlying entity.
+    pub fn entity_id(&self) -> EntityId {
+        self.entity.entity_id()
+    }
+
+    /// Read the current value.
+    pub fn read<F, R>(&self, f: F) -> crate::Result<R>
+    where
+        F: FnOnce(&U) -> R,
+    {
+        self.entity.read(f)
+    }
+
+    /// Incremented every time the computed value changes.
+    pub fn version(&self) -> u64 {
+        self.entity.version()
+    }
+
+    /// Subscribe to changes of the computed value.
+    pub fn subscribe(&self) -> watch::Receiver<()> {
+        self.entity.subscribe()
+    }
+}
+
+impl<U: Send + Sync> Clone for Derived<U> {
+    fn clone(&self) -> Self {
+        Self { entity: Entity::clone(&self.entity) }
+    }
+}
+
+impl<U: Send + Sync + 'static> super::sealed::Sealed for Derived<U> {
+    type Value = U;
+
+    fn entity(&self) -> &Entity<U> {
+        &self.entity
+    }
+}
+
+impl<U: Send + Sync + 'static> Observable for Derived<U> {}
+
+impl<T: ?Sized + Send + Sync + 'static> Entity<T> {
+    /// Create a read-only value derived from this entity.
+    /// The derived value is recomputed whenever this entity changes, but subscribers
+    /// of the derived value are only notified when the output differs from the previous one.
+    /// Fails if this entity's lock is poisoned.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Only redraw when the counter changes, not on unrelated AppState edits.
+    /// let counter = app_state.map(|s| s.counter)?;
+    /// cx.subscribe(&counter);
+    /// ```
+    pub fn map<U, F>(&self, f: F) -> crate::Result<Derived<U>>
+    where
+        U: PartialEq + Send + Sync + 'static,
+        F: Fn(&T) -> U + Send + Sync + 'static,
+    {
+        let source = self.downgrade();
+        derive_from(vec![self.subscribe()], move || read_weak(&source, &f))
+    }
+
+    /// Create a read-only value derived from this entity and another one.
+    /// Recomputed when either source changes; notifies only when the output changes.
+    /// Fails if either source's lock is poisoned.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let label = settings.combine(&stats, |s, st| format!("{}: {}", s.name, st.total))?;
+    /// ```
+    pub fn combine<O, U, F>(&self, other: &Entity<O>, f: F) -> crate::Result<Derived<U>>
+    where
+        O: ?Sized + Send + Sync + 'static,
+        U: PartialEq + Send + Sync + 'static,
+        F: Fn(&T, &O) -> U + Send + Sync + 'static,
+    {
+        let a = self.downgrade();
+        let b = other.downgrade();
+        derive_from(vec![self.subscribe(), other.subscribe()], move || {
+            read_weak(&a, |a| read_weak(&b, |b| f(a, b)))?
+        })
+    }
+}
+
+/// Read a source through its weak handle; `Error::EntityDropped` once it is gone.
+fn read_weak<T, R>(source: &WeakEntity<T>, f: impl FnOnce(&T) -> R) -> crate::Result<R>
+where
+    T: ?Sized + Send + Sync,
+{
+    let entity = source.upgrade().ok_or(crate::Error::EntityDropped { entity_id: source.entity_id().as_u64() })?;
+    entity.read(f)
+}
+
+/// Build a derived value driven by the given change receivers.
+/// The background tasks end when the derived value is dropped, a source is dropped, or
+/// a source's lock is poisoned.
+fn derive_from<U, F>(sources: Vec<watch::Receiver<()>>, compute: F) -> crate::Result<Derived<U>>
+where
+    U: PartialEq + Send + Sync + 'static,
+    F: Fn() -> crate::Result<U> + Send + Sync + 'static,
+{
+    let derived = Entity::new(compute()?);
+    let weak = derived.downgrade();
+    let mut alive = derived.subscribe();
+
+    // Fan all source notifications into a single channel.
+    let (tx, mut rx) = mpsc::unbounded_channel::<()>();
+    for mut source in sources {
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    changed = source.changed() => {
+                        if changed.is_err() || tx.send(()).is_err() {
+                            break;
+                        }
+                    }
+                    _ = tx.closed() => break,
+                }
+            }
+        });
+    }
+    drop(tx);
+
+    // Dropping `rx` when this task ends closes the channel, which stops the forwarders.
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                received = rx.recv() => {
+                    if received.is_none() {
+                        break;
+                    }
+                }
+                _ = crate::subscription::closed(Some(&mut alive)) => break,
+            }
+            // Coalesce bursts of source updates into a single recompute.
+            while rx.try_recv().is_ok() {}
+
+            let Some(target) = weak.upgrade() else { break };
+            let Ok(value) = compute() else { break };
+            let unchanged = target.read(|current| *current == value).unwrap_or(false);
+            if !unchanged {
+                let _ = target.update(|current| *current = value);
+            }
+        }
+    });
+
+    Ok(Derived { entity: derived })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[derive(Clone, Default)]
+    struct AppState {
+        counter: i32,
+        label: String,
+    }
+
+    #[tokio::test]
+    async fn test_map_notifies_only_on_output_change() {
+        let state = Entity::new(AppState::default());
+        let counter = state.map(|s| s.counter).unwrap();
+        let mut rx = counter.subscribe();
+
+        // Unrelated change: recompute yields the same value, no notification.
+        state.update(|s| s.label.push('x')).unwrap();
+        assert!(tokio::time::timeout(Duration::from_millis(50), rx.changed()).await.is_err());
+
+        state.update(|s| s.counter = 7).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), rx.changed()).await.unwrap().unwrap();
+        assert_eq!(counter.read(|c| *c).unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_combine_recomputes_from_both_sources() {
+        let a = Entity::new(1);
+        let b = Entity::new(2);
+        let sum = a.combine(&b, |a, b| a + b).unwrap();
+        let mut rx = sum.subscribe();
+        assert_eq!(sum.read(|v| *v).unwrap(), 3);
+
+        b.update(|v| *v = 10).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), rx.changed()).await.unwrap().unwrap();
+        assert_eq!(sum.read(|v| *v).unwrap(), 11);
+    }
+
+    #[cfg(not(feature = "parking_lot"))]
+    #[tokio::test]
+    async fn test_poisoned_source_is_an_error() {
+        let state = Entity::new(AppState::default());
+        let poisoner = Entity::clone(&state);
+        let _ = std::thread::spawn(move || poisoner.update(|_| panic!("poison the lock"))).join();
+        assert!(state.map(|s| s.counter).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dropped_derived_releases_its_sources() {
+        let a = Entity::new(1);
+        let b = Entity::new(2);
+        let sum = a.combine(&b, |a, b| a + b).unwrap();
+        let (weak_a, weak_b) = (a.downgrade(), b.downgrade());
+        let mut sum_rx = sum.subscribe();
+        drop(sum);
+        // The recompute task notices the drop without waiting for a source change
+        tokio::time::timeout(Duration::from_secs(1), crate::subscription::closed(Some(&mut sum_rx))).await.unwrap();
+
+        drop((a, b));
+        assert!(weak_a.upgrade().is_none());
+        assert!(weak_b.upgrade().is_none());
+    }
+}
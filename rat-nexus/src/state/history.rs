@@ -0,0 +1,166 @@
+//! Undo/redo history for entities.
+
+use super::Entity;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+struct Stacks<T> {
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+}
+
+/// An entity wrapper that records a snapshot before each update and supports undo/redo.
+///
+/// Clones share the same entity and history. Only updates made through the `History`
+/// are recorded; writes through the underlying entity bypass the history.
+///
+/// # Example
+/// ```ignore
+/// let board = cx.new_entity(Board::default()).with_history(100);
+/// board.update(|b| b.place(3, 4));
+/// board.undo(); // notifies subscribers
+/// board.redo();
+/// ```
+pub struct History<T: Clone + Send + Sync + 'static> {
+    entity: Entity<T>,
+    stacks: Arc<Mutex<Stacks<T>>>,
+    capacity: usize,
+}
+
+impl<T: Clone + Send + Sync + 'static> Entity<T> {
+    /// Wrap this entity in an undo/redo history keeping at most `capacity` undo steps.
+    pub fn with_history(&self, capacity: usize) -> History<T> {
+        History::new(Entity::clone(self), capacity)
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> History<T> {
+    /// Create a history for the given entity keeping at most `capacity` undo steps.
+    pub fn new(entity: Entity<T>, capacity: usize) -> Self {
+        Self {
+            entity,
+            stacks: Arc::new(Mutex::new(Stacks { undo: VecDeque::new(), redo: Vec::new() })),
+            capacity,
+        }
+    }
+
+    /// The underlying entity, e.g. for `cx.subscribe` or reads.
+    pub fn entity(&self) -> &Entity<T> {
+        &self.entity
+    }
+
+    /// Update the value, recording the previous state as an undo step.
+    /// Any redo steps are discarded.
+    pub fn update<F, R>(&self, f: F) -> crate::Result<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let mut stacks = self.stacks.lock().map_err(|_| crate::Error::LockPoisoned)?;
+        self.entity.update(|value| {
+            if self.capacity > 0 {
+                if stacks.undo.len() == self.capacity {
+                    stacks.undo.pop_front();
+                }
+                stacks.undo.push_back(value.clone());
+            }
+            stacks.redo.clear();
+            f(value)
+        })
+    }
+
+    /// Read the current value.
+    pub fn read<F, R>(&self, f: F) -> crate::Result<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.entity.read(f)
+    }
+
+    /// Restore the previous state. Returns `false` if there is nothing to undo.
+    pub fn undo(&self) -> bool {
+        let Ok(mut stacks) = self.stacks.lock() else { return false };
+        let Some(previous) = stacks.undo.pop_back() else { return false };
+        match self.entity.update(|value| std::mem::replace(value, previous)) {
+            Ok(current) => {
+                stacks.redo.push(current);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Re-apply the most recently undone state. Returns `false` if there is nothing to redo.
+    pub fn redo(&self) -> bool {
+        let Ok(mut stacks) = self.stacks.lock() else { return false };
+        let Some(next) = stacks.redo.pop() else { return false };
+        match self.entity.update(|value| std::mem::replace(value, next)) {
+            Ok(current) => {
+                stacks.undo.push_back(current);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Check if there is a step to undo.
+    pub fn can_undo(&self) -> bool {
+        self.stacks.lock().map(|s| !s.undo.is_empty()).unwrap_or(false)
+    }
+
+    /// Check if there is a step to redo.
+    pub fn can_redo(&self) -> bool {
+        self.stacks.lock().map(|s| !s.redo.is_empty()).unwrap_or(false)
+    }
+
+    /// Drop all recorded undo and redo steps.
+    pub fn clear(&self) {
+        if let Ok(mut stacks) = self.stacks.lock() {
+            stacks.undo.clear();
+            stacks.redo.clear();
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Clone for History<T> {
+    fn clone(&self) -> Self {
+        Self {
+            entity: Entity::clone(&self.entity),
+            stacks: Arc::clone(&self.stacks),
+            capacity: self.capacity,
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync + Default + 'static> Default for History<T> {
+    /// A history of 100 undo steps over a default entity.
+    fn default() -> Self {
+        History::new(Entity::default(), 100)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_redo() {
+        let history = Entity::new(0).with_history(2);
+        history.update(|v| *v = 1).unwrap();
+        history.update(|v| *v = 2).unwrap();
+        history.update(|v| *v = 3).unwrap();
+
+        assert!(history.undo());
+        assert_eq!(history.read(|v| *v).unwrap(), 2);
+        assert!(history.undo());
+        assert_eq!(history.read(|v| *v).unwrap(), 1);
+        // Capacity of 2 dropped the oldest step
+        assert!(!history.undo());
+
+        assert!(history.redo());
+        assert_eq!(history.read(|v| *v).unwrap(), 2);
+
+        // A new update discards the redo stack
+        history.update(|v| *v = 10).unwrap();
+        assert!(!history.can_redo());
+    }
+}
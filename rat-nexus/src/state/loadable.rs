@@ -0,0 +1,207 @@
+//! Async data loading state.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Paragraph, Wrap};
+
+use super::Entity;
+use crate::router::PageStatus;
+
+/// Spinner frames shown while loading.
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// How often the spinner advances; `AppContext::load` redraws at this rate while pending.
+pub(crate) const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Data that is fetched asynchronously, and the state of fetching it.
+///
+/// Keep it in an `Entity` and fill it with `AppContext::load`, which moves it through
+/// `Loading` to `Loaded` or `Error` and redraws on each step.
+///
+/// # Example
+/// ```ignore
+/// // in on_enter
+/// let client = self.client.clone();
+/// cx.load(&self.users, async move { client.fetch_users().await });
+///
+/// // in render
+/// cx.subscribe(&self.users);
+/// let users = self.users.read(Loadable::clone).unwrap_or_default();
+/// if let Some(users) = users.render_state(frame, area) {
+///     frame.render_widget(users_table(users), area);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Loadable<T> {
+    /// Nothing was requested yet, or the request was aborted.
+    #[default]
+    NotLoaded,
+    /// A request is running.
+    Loading,
+    /// The data arrived.
+    Loaded(T),
+    /// The request failed, with a message for the user.
+    Error(String),
+}
+
+impl<T> Loadable<T> {
+    /// Whether a request is running.
+    pub fn is_loading(&self) -> bool {
+        matches!(self, Loadable::Loading)
+    }
+
+    /// Whether the data arrived.
+    pub fn is_loaded(&self) -> bool {
+        matches!(self, Loadable::Loaded(_))
+    }
+
+    /// The data, once loaded.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Loadable::Loaded(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The error message, if the request failed.
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Loadable::Error(message) => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Take the data out, once loaded.
+    pub fn into_value(self) -> Option<T> {
+        match self {
+            Loadable::Loaded(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Transform the loaded data, keeping the other states.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Loadable<U> {
+        match self {
+            Loadable::NotLoaded => Loadable::NotLoaded,
+            Loadable::Loading => Loadable::Loading,
+            Loadable::Loaded(value) => Loadable::Loaded(f(value)),
+            Loadable::Error(message) => Loadable::Error(message),
+        }
+    }
+
+    /// The matching page status, e.g. to return from `Component::status` so
+    /// `define_app!` shows the route's loading or error view.
+    pub fn status(&self) -> PageStatus {
+        match self {
+            Loadable::Loading => PageStatus::Loading,
+            Loadable::Error(message) => PageStatus::Failed(message.clone()),
+            Loadable::NotLoaded | Loadable::Loaded(_) => PageStatus::Ready,
+        }
+    }
+
+    /// Draw the state into `area` unless the data is loaded: a spinner while loading,
+    /// the message in red on error, nothing before the first request. Returns the data
+    /// when it is loaded, for the caller to draw.
+    pub fn render_state(&self, frame: &mut ratatui::Frame, area: Rect) -> Option<&T> {
+        match self {
+            Loadable::Loaded(value) => return Some(value),
+            Loadable::Loading => {
+                let line = format!("{} Loading…", spinner_frame());
+                frame.render_widget(Paragraph::new(line).style(Style::default().fg(Color::DarkGray)), area);
+            }
+            Loadable::Error(message) => {
+                let error = Paragraph::new(format!("✗ {message}"))
+                    .style(Style::default().fg(Color::Red))
+                    .wrap(Wrap { trim: true });
+                frame.render_widget(error, area);
+            }
+            Loadable::NotLoaded => {}
+        }
+        None
+    }
+}
+
+/// The spinner frame for the current time.
+pub fn spinner_frame() -> &'static str {
+    let millis = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+    SPINNER[(millis / SPINNER_INTERVAL.as_millis()) as usize % SPINNER.len()]
+}
+
+/// Sets an entity back to `NotLoaded` if its load is dropped before finishing.
+pub(crate) struct LoadGuard<T: Send + Sync + 'static>(pub(crate) Option<Entity<Loadable<T>>>);
+
+impl<T: Send + Sync + 'static> LoadGuard<T> {
+    /// Store the outcome and disarm the guard.
+    pub(crate) fn finish(mut self, outcome: Loadable<T>) {
+        if let Some(entity) = self.0.take() {
+            let _ = entity.update(|state| *state = outcome);
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> Drop for LoadGuard<T> {
+    fn drop(&mut self) {
+        if let Some(entity) = self.0.take() {
+            let _ = entity.update_when(|state| {
+                let loading = state.is_loading();
+                if loading {
+                    *state = Loadable::NotLoaded;
+                }
+                loading
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn draw(state: &Loadable<u32>) -> (String, Option<u32>) {
+        let mut terminal = Terminal::new(TestBackend::new(12, 1)).unwrap();
+        let mut value = None;
+        terminal.draw(|frame| value = state.render_state(frame, frame.area()).copied()).unwrap();
+        let text = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect::<String>();
+        (text.trim_end().to_string(), value)
+    }
+
+    #[test]
+    fn test_render_state() {
+        assert_eq!(draw(&Loadable::NotLoaded), (String::new(), None));
+        assert!(draw(&Loadable::Loading).0.ends_with(" Loading…"));
+        assert_eq!(draw(&Loadable::Error("offline".into())), ("✗ offline".to_string(), None));
+        assert_eq!(draw(&Loadable::Loaded(7)), (String::new(), Some(7)));
+        assert_eq!(Loadable::<u32>::Error("x".into()).status(), PageStatus::Failed("x".into()));
+        assert_eq!(Loadable::Loaded(2).map(|v| v * 2).value(), Some(&4));
+    }
+
+    #[tokio::test]
+    async fn test_load_moves_through_states() {
+        let (app, _refresh, _inject) = crate::AppContext::new();
+        let users: Entity<Loadable<Vec<&str>>> = Entity::default();
+        let (go_tx, go_rx) = tokio::sync::oneshot::channel::<()>();
+        app.load(&users, async move {
+            let _ = go_rx.await;
+            Ok::<_, String>(vec!["ada"])
+        });
+        assert!(users.read(Loadable::is_loading).unwrap());
+
+        let _ = go_tx.send(());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(users.read(Loadable::clone).unwrap(), Loadable::Loaded(vec!["ada"]));
+
+        let handle = app.load(&users, async { Err::<Vec<&str>, _>("down") });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(handle.is_finished());
+        assert_eq!(users.read(|u| u.error().map(String::from)).unwrap().as_deref(), Some("down"));
+
+        let handle = app.load(&users, std::future::pending::<Result<Vec<&str>, String>>());
+        handle.abort();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(users.read(Loadable::clone).unwrap(), Loadable::NotLoaded);
+    }
+}
@@ -0,0 +1,137 @@
+//! Lock backend for entity state.
+//!
+//! Entities use `std::sync::RwLock` by default. Enabling the `parking_lot` feature
+//! switches to `parking_lot::RwLock`, which never poisons and is faster under contention.
+//!
+//! In debug builds every acquisition is tracked per thread. Taking an entity's lock while
+//! the same thread already holds it exclusively (e.g. calling `update` from inside a `read`
+//! closure during render) would deadlock; instead it is logged with the entity id and
+//! returns `Error::ReentrantLock`.
+
+use std::cell::RefCell;
+use std::ops::{Deref, DerefMut};
+
+use super::EntityId;
+
+/// The lock wrapping entity state.
+#[cfg(not(feature = "parking_lot"))]
+pub type RwLock<T> = std::sync::RwLock<T>;
+/// The lock wrapping entity state.
+#[cfg(feature = "parking_lot")]
+pub type RwLock<T> = parking_lot::RwLock<T>;
+
+#[cfg(not(feature = "parking_lot"))]
+type ReadGuard<'a, T> = std::sync::RwLockReadGuard<'a, T>;
+#[cfg(not(feature = "parking_lot"))]
+type WriteGuard<'a, T> = std::sync::RwLockWriteGuard<'a, T>;
+#[cfg(feature = "parking_lot")]
+type ReadGuard<'a, T> = parking_lot::RwLockReadGuard<'a, T>;
+#[cfg(feature = "parking_lot")]
+type WriteGuard<'a, T> = parking_lot::RwLockWriteGuard<'a, T>;
+
+const DIAGNOSTICS: bool = cfg!(debug_assertions);
+
+thread_local! {
+    /// Entity locks held by this thread, with whether they are held exclusively.
+    static HELD: RefCell<Vec<(EntityId, bool)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Marks an entity lock as held by the current thread until dropped.
+struct Held(Option<EntityId>);
+
+impl Held {
+    fn acquire(id: EntityId, exclusive: bool) -> crate::Result<Self> {
+        if !DIAGNOSTICS {
+            return Ok(Self(None));
+        }
+        let conflict = HELD.with(|held| {
+            let mut held = held.borrow_mut();
+            let conflict = held
+                .iter()
+                .any(|(held_id, held_exclusive)| *held_id == id && (exclusive || *held_exclusive));
+            if !conflict {
+                held.push((id, exclusive));
+            }
+            conflict
+        });
+        if conflict {
+            // Logged outside the borrow: a logger may take entity locks itself
+            let type_name = super::entity_type_name(id);
+            log::error!(
+                "re-entrant {} lock on entity {id} ({type_name}): already held by this thread",
+                if exclusive { "write" } else { "read" },
+            );
+            return Err(crate::Error::ReentrantLock { entity_id: id.as_u64(), type_name });
+        }
+        Ok(Self(Some(id)))
+    }
+}
+
+impl Drop for Held {
+    fn drop(&mut self) {
+        if let Some(id) = self.0 {
+            HELD.with(|held| {
+                let mut held = held.borrow_mut();
+                if let Some(pos) = held.iter().rposition(|(held_id, _)| *held_id == id) {
+                    held.remove(pos);
+                }
+            });
+        }
+    }
+}
+
+/// A lock guard tracked by the re-entrancy diagnostics.
+pub(crate) struct Guard<G> {
+    guard: G,
+    _held: Held,
+}
+
+impl<G: Deref> Deref for Guard<G> {
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<G: DerefMut> DerefMut for Guard<G> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+/// Acquire a shared lock on an entity's state.
+pub(crate) fn read<T: ?Sized>(lock: &RwLock<T>, id: EntityId) -> crate::Result<Guard<ReadGuard<'_, T>>> {
+    let held = Held::acquire(id, false)?;
+    #[cfg(not(feature = "parking_lot"))]
+    let guard = lock.read().map_err(|_| crate::Error::LockPoisoned)?;
+    #[cfg(feature = "parking_lot")]
+    let guard = lock.read();
+    Ok(Guard { guard, _held: held })
+}
+
+/// Acquire an exclusive lock on an entity's state.
+pub(crate) fn write<T: ?Sized>(lock: &RwLock<T>, id: EntityId) -> crate::Result<Guard<WriteGuard<'_, T>>> {
+    let held = Held::acquire(id, true)?;
+    #[cfg(not(feature = "parking_lot"))]
+    let guard = lock.write().map_err(|_| crate::Error::LockPoisoned)?;
+    #[cfg(feature = "parking_lot")]
+    let guard = lock.write();
+    Ok(Guard { guard, _held: held })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Entity;
+
+    #[test]
+    fn test_reentrant_write_is_reported() {
+        let entity = Entity::new(1);
+        let nested = entity.read(|_| entity.update(|v| *v += 1)).unwrap();
+        assert!(matches!(nested, Err(crate::Error::ReentrantLock { .. })));
+
+        let nested_read = entity.read(|_| entity.read(|v| *v)).unwrap();
+        assert_eq!(nested_read.unwrap(), 1);
+        assert!(entity.update(|v| *v += 1).is_ok());
+    }
+}
@@ -1,8 +1,25 @@
 use std::num::NonZeroU64;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, RwLock, Weak};
+use std::sync::{Arc, Weak};
 use tokio::sync::watch;
 
+pub(crate) mod batch;
+mod capped;
+pub mod lock;
+mod derived;
+mod history;
+pub mod loadable;
+mod registry;
+pub mod size;
+
+pub use capped::{push_capped, Capped};
+pub use derived::Derived;
+pub use history::History;
+pub use loadable::Loadable;
+pub use lock::RwLock;
+pub(crate) use registry::EntityRegistry;
+pub use size::SizeOf;
+
 /// Global counter for generating unique entity IDs.
 static NEXT_ENTITY_ID: AtomicU64 = AtomicU64::new(1);
 
@@ -17,7 +34,7 @@ impl EntityId {
     /// # Panics
     /// Panics if more than 2^64-1 entities are created (theoretically impossible in practice).
     /// Consider implementing overflow recovery in production systems handling extreme scale.
-    fn next() -> Self {
+    pub(crate) fn next() -> Self {
         let id = NEXT_ENTITY_ID.fetch_add(1, Ordering::Relaxed);
         // SAFETY: We start at 1 and only increment, so it's never zero.
         Self(NonZeroU64::new(id).unwrap_or_else(|| {
@@ -43,6 +60,65 @@ impl std::fmt::Display for EntityId {
     }
 }
 
+/// Whether live entities are listed in their application's `EntityRegistry`: in debug
+/// builds, and in release builds with the `soak` feature. Listing takes a lock on every
+/// entity creation and drop, so it stays off the hot path of release builds by default.
+pub(crate) const TRACK_LIVE: bool = cfg!(any(debug_assertions, feature = "soak"));
+
+/// Metadata shared by all strong handles of an entity.
+/// Lists the entity in its application's registry for as long as a strong handle exists.
+struct EntityMeta {
+    id: EntityId,
+    /// The registry of the application that created the entity, if any.
+    registry: Option<EntityRegistry>,
+    /// Incremented on every mutation.
+    version: AtomicU64,
+    /// Serializes `update_async` calls.
+    async_lock: tokio::sync::Mutex<()>,
+    /// Change notifications. Dropped with the last strong handle, which ends subscriptions.
+    tx: watch::Sender<()>,
+}
+
+impl EntityMeta {
+    fn register<T: ?Sized>(id: EntityId, registry: Option<EntityRegistry>) -> Arc<Self> {
+        if let Some(registry) = &registry {
+            registry.insert(id, std::any::type_name::<T>());
+        }
+        Arc::new(Self {
+            id,
+            registry,
+            version: AtomicU64::new(0),
+            async_lock: tokio::sync::Mutex::new(()),
+            tx: watch::channel(()).0,
+        })
+    }
+}
+
+impl Drop for EntityMeta {
+    fn drop(&mut self) {
+        if let Some(registry) = &self.registry {
+            registry.remove(self.id);
+        }
+    }
+}
+
+/// Type name of a live entity of the application running on this thread, for
+/// diagnostics.
+pub(crate) fn entity_type_name(id: EntityId) -> &'static str {
+    EntityRegistry::current().and_then(|registry| registry.type_name(id)).unwrap_or("<unknown>")
+}
+
+/// Reads an entity's version without holding its value or keeping it alive.
+#[derive(Debug, Clone)]
+pub(crate) struct VersionProbe(Weak<EntityMeta>);
+
+impl VersionProbe {
+    /// The current version, or `None` once the entity is dropped.
+    pub(crate) fn version(&self) -> Option<u64> {
+        self.0.upgrade().map(|meta| meta.version.load(Ordering::Acquire))
+    }
+}
+
 /// Shared state wrapper with RwLock for efficient concurrent access.
 /// - Use read() for read-heavy workloads (no contention)
 /// - Use write() for mutations (exclusive access)
@@ -54,14 +130,38 @@ pub type SharedState<T> = Arc<RwLock<T>>;
 pub struct Entity<T: ?Sized + Send + Sync> {
     id: EntityId,
     pub(crate) inner: SharedState<T>,
-    tx: watch::Sender<()>,
+    meta: Arc<EntityMeta>,
+}
+
+/// A source of change notifications that components can `subscribe` to or `observe`:
+/// an `Entity`, or a read-only `Derived` value.
+pub trait Observable: sealed::Sealed {}
+
+pub(crate) mod sealed {
+    /// Gives the framework the entity behind an `Observable` without exposing it, so a
+    /// `Derived` stays read-only outside the crate.
+    pub trait Sealed {
+        type Value: ?Sized + Send + Sync + 'static;
+
+        fn entity(&self) -> &super::Entity<Self::Value>;
+    }
 }
 
+impl<T: ?Sized + Send + Sync + 'static> sealed::Sealed for Entity<T> {
+    type Value = T;
+
+    fn entity(&self) -> &Entity<T> {
+        self
+    }
+}
+
+impl<T: ?Sized + Send + Sync + 'static> Observable for Entity<T> {}
+
 /// A weak handle to an entity.
 pub struct WeakEntity<T: ?Sized + Send + Sync> {
     id: EntityId,
     pub(crate) inner: Weak<RwLock<T>>,
-    tx: watch::Sender<()>,
+    meta: Weak<EntityMeta>,
 }
 
 impl<T: ?Sized + Send + Sync> Entity<T> {
@@ -75,13 +175,53 @@ impl<T: ?Sized + Send + Sync> Entity<T> {
     where
         F: FnOnce(&mut T) -> R,
     {
-        let mut guard = self.inner.write().map_err(|_| crate::Error::LockPoisoned)?;
+        let mut guard = lock::write(&self.inner, self.id)?;
         let res = f(&mut *guard);
+        self.bump_version();
         drop(guard);
-        let _ = self.tx.send(());
+        self.notify();
         Ok(res)
     }
 
+    /// Update the inner value, notifying subscribers only if the closure returns `true`.
+    /// Returns the closure's verdict. Use it for frequent updates that are usually no-ops
+    /// (mouse moves, polling) so they do not schedule frames.
+    ///
+    /// # Example
+    /// ```ignore
+    /// state.update_when(|s| {
+    ///     let changed = s.hover != Some(cell);
+    ///     s.hover = Some(cell);
+    ///     changed
+    /// })?;
+    /// ```
+    pub fn update_when<F>(&self, f: F) -> crate::Result<bool>
+    where
+        F: FnOnce(&mut T) -> bool,
+    {
+        let mut guard = lock::write(&self.inner, self.id)?;
+        let changed = f(&mut *guard);
+        if changed {
+            self.bump_version();
+        }
+        drop(guard);
+        if changed {
+            self.notify();
+        }
+        Ok(changed)
+    }
+
+    /// Update the inner value without notifying subscribers.
+    /// For render-time bookkeeping (e.g. a widget adjusting its scroll offset) that must
+    /// not schedule another frame.
+    pub(crate) fn update_quietly<F, R>(&self, f: F) -> crate::Result<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let mut guard = lock::write(&self.inner, self.id)?;
+        Ok(f(&mut *guard))
+    }
+
     /// Update the inner value with a Context bound to this entity.
     /// This is the GPUI-style update that provides a properly bound Context for async operations.
     ///
@@ -101,19 +241,45 @@ impl<T: ?Sized + Send + Sync> Entity<T> {
     {
         let weak = self.downgrade();
         let mut cx = crate::Context::new(app.clone(), weak);
-        let mut guard = self.inner.write().map_err(|_| crate::Error::LockPoisoned)?;
+        let mut guard = lock::write(&self.inner, self.id)?;
         let res = f(&mut *guard, &mut cx);
+        self.bump_version();
         drop(guard);
-        let _ = self.tx.send(());
+        self.notify();
         Ok(res)
     }
 
+    /// Get the mutation counter of this entity.
+    /// Incremented on every update; compare versions to tell whether the value changed.
+    pub fn version(&self) -> u64 {
+        self.meta.version.load(Ordering::Acquire)
+    }
+
+    /// A handle reading this entity's version without keeping it alive.
+    pub(crate) fn version_probe(&self) -> VersionProbe {
+        VersionProbe(Arc::downgrade(&self.meta))
+    }
+
+    /// Record a mutation. Called while the write guard is still held, so `update_async`
+    /// cannot commit between a synchronous write and its version bump and overwrite it.
+    fn bump_version(&self) {
+        self.meta.version.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Notify subscribers that this entity changed.
+    /// Deferred until the end of the batch if called inside `AppContext::batch`.
+    pub(crate) fn notify(&self) {
+        if !batch::defer_notify(self.id, &self.meta.tx) {
+            let _ = self.meta.tx.send(());
+        }
+    }
+
     /// Read the inner value using a closure (non-blocking for concurrent readers).
     pub fn read<F, R>(&self, f: F) -> crate::Result<R>
     where
         F: FnOnce(&T) -> R,
     {
-        let guard = self.inner.read().map_err(|_| crate::Error::LockPoisoned)?;
+        let guard = lock::read(&self.inner, self.id)?;
         Ok(f(&*guard))
     }
 
@@ -122,13 +288,13 @@ impl<T: ?Sized + Send + Sync> Entity<T> {
         WeakEntity {
             id: self.id,
             inner: Arc::downgrade(&self.inner),
-            tx: watch::Sender::clone(&self.tx),
+            meta: Arc::downgrade(&self.meta),
         }
     }
 
     /// Subscribe to changes of this entity.
     pub fn subscribe(&self) -> watch::Receiver<()> {
-        self.tx.subscribe()
+        self.meta.tx.subscribe()
     }
 }
 
@@ -140,19 +306,28 @@ impl<T: ?Sized + Send + Sync> WeakEntity<T> {
 
     /// Upgrade this weak handle to a strong handle, if the entity is still alive.
     pub fn upgrade(&self) -> Option<Entity<T>> {
-        self.inner.upgrade().map(|inner| Entity {
+        let inner = self.inner.upgrade()?;
+        let meta = self.meta.upgrade()?;
+        Some(Entity {
             id: self.id,
             inner,
-            tx: watch::Sender::clone(&self.tx),
+            meta,
         })
     }
 
-    /// Update the entity if it is still alive.
-    pub fn update<F, R>(&self, f: F) -> Option<crate::Result<R>>
+    /// Subscribe to changes of the entity, if it is still alive.
+    /// The receiver reports an error once the last strong handle is dropped.
+    pub fn subscribe(&self) -> Option<watch::Receiver<()>> {
+        self.meta.upgrade().map(|meta| meta.tx.subscribe())
+    }
+
+    /// Update the entity if it is still alive; `Error::EntityDropped` otherwise.
+    pub fn update<F, R>(&self, f: F) -> crate::Result<R>
     where
         F: FnOnce(&mut T) -> R,
     {
-        self.upgrade().map(|entity| entity.update(f))
+        let entity = self.upgrade().ok_or(crate::Error::EntityDropped { entity_id: self.id.as_u64() })?;
+        entity.update(f)
     }
 }
 
@@ -161,7 +336,7 @@ impl<T: ?Sized + Send + Sync> Clone for Entity<T> {
         Self {
             id: self.id,
             inner: Arc::clone(&self.inner),
-            tx: watch::Sender::clone(&self.tx),
+            meta: Arc::clone(&self.meta),
         }
     }
 }
@@ -171,7 +346,7 @@ impl<T: ?Sized + Send + Sync> Clone for WeakEntity<T> {
         Self {
             id: self.id,
             inner: Weak::clone(&self.inner),
-            tx: watch::Sender::clone(&self.tx),
+            meta: Weak::clone(&self.meta),
         }
     }
 }
@@ -179,15 +354,193 @@ impl<T: ?Sized + Send + Sync> Clone for WeakEntity<T> {
 impl<T: Send + Sync> Entity<T> {
     /// Create a new entity with the given initial value.
     pub fn new(value: T) -> Self {
-        let (tx, _) = watch::channel(());
+        Self::new_in(value, EntityRegistry::current())
+    }
+
+    /// Create an entity listed in `registry` rather than the current thread's.
+    pub(crate) fn new_in(value: T, registry: Option<EntityRegistry>) -> Self {
+        let id = EntityId::next();
         Self {
-            id: EntityId::next(),
+            id,
             inner: Arc::new(RwLock::new(value)),
-            tx,
+            meta: EntityMeta::register::<T>(id, registry),
         }
     }
 }
 
+impl<T: SizeOf + Send + Sync + 'static> Entity<T> {
+    /// Approximate size of the value in bytes; see `SizeOf`.
+    pub fn approx_size(&self) -> usize {
+        self.read(|value| value.approx_size()).unwrap_or(0)
+    }
+
+    /// Include this entity in `AppContext::entity_sizes` (and soak reports) while it is
+    /// alive. Entities created outside an application are not tracked.
+    pub fn track_size(self) -> Self {
+        if let Some(registry) = &self.meta.registry {
+            registry.track(self.id, size::probe(self.id, Arc::downgrade(&self.inner)));
+        }
+        self
+    }
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> Entity<T> {
+    /// Update the inner value, notifying subscribers only if it is no longer equal to
+    /// its value before the update. Returns whether it changed. See `update_when` for
+    /// values that are expensive to clone or compare.
+    pub fn update_if_changed<F>(&self, f: F) -> crate::Result<bool>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.update_when(|value| {
+            let before = value.clone();
+            f(value);
+            *value != before
+        })
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Entity<T> {
+    /// Apply a fallible update atomically.
+    /// The closure runs against the live value under the write lock; on `Ok` the changes
+    /// are committed and subscribers notified, on `Err` the previous value is restored
+    /// and no notification is sent. The outer `Result` reports lock failures.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let outcome = form.transaction(|f| {
+    ///     f.name = input.trim().to_string();
+    ///     f.age = age_input.parse().map_err(|_| "age must be a number")?;
+    ///     Ok::<_, &str>(())
+    /// })?;
+    /// if let Err(msg) = outcome {
+    ///     // form is unchanged
+    /// }
+    /// ```
+    pub fn transaction<F, R, E>(&self, f: F) -> crate::Result<std::result::Result<R, E>>
+    where
+        F: FnOnce(&mut T) -> std::result::Result<R, E>,
+    {
+        let mut guard = lock::write(&self.inner, self.id)?;
+        let snapshot = guard.clone();
+        let res = f(&mut *guard);
+        if res.is_err() {
+            *guard = snapshot;
+            return Ok(res);
+        }
+        self.bump_version();
+        drop(guard);
+        self.notify();
+        Ok(res)
+    }
+
+    /// Update the value with an async closure, without holding a lock across await points.
+    /// The closure mutates a working copy which is committed (and subscribers notified) when
+    /// it completes. Concurrent `update_async` calls on the same entity run one at a time.
+    /// If the entity is modified synchronously while the closure is pending, the working
+    /// copy is discarded and `Error::UpdateConflict` is returned.
+    ///
+    /// # Example
+    /// ```ignore
+    /// state.update_async(async |s| {
+    ///     s.status = Status::Loading;
+    ///     s.items = fetch_items().await;
+    ///     s.status = Status::Ready;
+    /// }).await?;
+    /// ```
+    pub async fn update_async<F, R>(&self, f: F) -> crate::Result<R>
+    where
+        F: AsyncFnOnce(&mut T) -> R,
+    {
+        let _serial = self.meta.async_lock.lock().await;
+        let start_version = self.version();
+        let mut working = self.read(T::clone)?;
+
+        let res = f(&mut working).await;
+
+        let mut guard = lock::write(&self.inner, self.id)?;
+        if self.version() != start_version {
+            return Err(crate::Error::UpdateConflict { entity_id: self.id.as_u64() });
+        }
+        *guard = working;
+        self.bump_version();
+        drop(guard);
+        self.notify();
+        Ok(res)
+    }
+
+    /// Subscribe to changes of this entity, receiving the updated value.
+    /// Unlike `subscribe`, the receiver carries a snapshot of the new state,
+    /// so consumers can react to *what* changed rather than just *that* it changed.
+    /// The forwarding task stops once all receivers are dropped or the entity is gone.
+    /// Fails if the entity's lock is poisoned.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut rx = counter.subscribe_value()?;
+    /// while rx.changed().await.is_ok() {
+    ///     let value = rx.borrow_and_update().clone();
+    /// }
+    /// ```
+    pub fn subscribe_value(&self) -> crate::Result<watch::Receiver<T>> {
+        let initial = lock::read(&self.inner, self.id)?.clone();
+        let (value_tx, value_rx) = watch::channel(initial);
+        let mut rx = self.subscribe();
+        let weak = Arc::downgrade(&self.inner);
+        let id = self.id;
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    changed = rx.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                    }
+                    _ = value_tx.closed() => break,
+                }
+                let Some(inner) = weak.upgrade() else { break };
+                let value = match lock::read(&inner, id) {
+                    Ok(guard) => guard.clone(),
+                    Err(_) => break,
+                };
+                if value_tx.send(value).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(value_rx)
+    }
+
+    /// Observe changes of this entity with both the previous and the new value.
+    /// The callback runs on a background task after each (possibly coalesced) update.
+    /// Abort the returned handle to stop observing. Fails if the entity's lock is poisoned.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let handle = state.observe(|old, new| {
+    ///     if old.theme != new.theme {
+    ///         // react to theme switches only
+    ///     }
+    /// })?;
+    /// tasks.track(handle);
+    /// ```
+    pub fn observe<F>(&self, mut f: F) -> crate::Result<crate::task::TaskHandle>
+    where
+        F: FnMut(&T, &T) + Send + 'static,
+    {
+        let mut rx = self.subscribe_value()?;
+        let join_handle = tokio::spawn(async move {
+            let mut old = rx.borrow_and_update().clone();
+            while rx.changed().await.is_ok() {
+                let new = rx.borrow_and_update().clone();
+                f(&old, &new);
+                old = new;
+            }
+        });
+        Ok(crate::task::TaskHandle::from_join_handle(join_handle))
+    }
+}
+
 impl<T: Send + Sync + Default> Default for Entity<T> {
     /// Create a default entity with the default value of T.
     /// Useful for component initialization - replace with real entity in on_mount().
@@ -200,11 +553,155 @@ impl<T: ?Sized + Send + Sync> Entity<T> {
     /// Create an entity from an existing Arc<RwLock<T>>.
     /// This is useful for creating Entity<dyn Trait> from coerced Arc types.
     pub fn from_arc(inner: Arc<RwLock<T>>) -> Self {
-        let (tx, _) = watch::channel(());
+        let id = EntityId::next();
         Self {
-            id: EntityId::next(),
+            id,
             inner,
-            tx,
+            meta: EntityMeta::register::<T>(id, EntityRegistry::current()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_subscribe_value_receives_updates() {
+        let entity = Entity::new(1);
+        let mut rx = entity.subscribe_value().unwrap();
+        assert_eq!(*rx.borrow(), 1);
+
+        entity.update(|v| *v = 42).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), rx.changed()).await.unwrap().unwrap();
+        assert_eq!(*rx.borrow_and_update(), 42);
+    }
+
+    #[test]
+    fn test_live_entity_registry() {
+        let registry = EntityRegistry::default();
+        let _entered = registry.enter();
+        let entity = Entity::new(5u8);
+        let id = entity.entity_id();
+        let weak = entity.downgrade();
+        assert_eq!(registry.live_entities().iter().any(|(live, name)| *live == id && *name == "u8"), TRACK_LIVE);
+
+        drop(entity);
+        assert!(weak.upgrade().is_none());
+        assert!(matches!(weak.update(|v| *v += 1), Err(crate::Error::EntityDropped { entity_id }) if entity_id == id.as_u64()));
+        assert!(!registry.live_entities().iter().any(|(live, _)| *live == id));
+    }
+
+    #[test]
+    fn test_batch_coalesces_notifications() {
+        let entity = Entity::new(0);
+        let mut rx = entity.subscribe();
+        rx.mark_unchanged();
+        {
+            let _guard = batch::begin();
+            entity.update(|v| *v += 1).unwrap();
+            entity.update(|v| *v += 1).unwrap();
+            assert!(!rx.has_changed().unwrap());
         }
+        assert!(rx.has_changed().unwrap());
+        assert_eq!(entity.read(|v| *v).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_transaction_rolls_back_on_err() {
+        let entity = Entity::new((1, 2));
+        let mut rx = entity.subscribe();
+        rx.mark_unchanged();
+
+        let res = entity.transaction(|v| {
+            v.0 = 10;
+            Err::<(), _>("invalid")
+        }).unwrap();
+        assert_eq!(res, Err("invalid"));
+        assert_eq!(entity.read(|v| *v).unwrap(), (1, 2));
+        assert!(!rx.has_changed().unwrap());
+
+        let res = entity.transaction(|v| {
+            v.0 = 10;
+            v.1 = 20;
+            Ok::<_, &str>(v.0 + v.1)
+        }).unwrap();
+        assert_eq!(res, Ok(30));
+        assert_eq!(entity.read(|v| *v).unwrap(), (10, 20));
+        assert!(rx.has_changed().unwrap());
+    }
+
+    #[test]
+    fn test_update_if_changed_skips_no_ops() {
+        let entity = Entity::new((0u16, 0u16));
+        let mut rx = entity.subscribe();
+        rx.mark_unchanged();
+
+        assert!(!entity.update_if_changed(|v| v.0 = 0).unwrap());
+        assert!(!entity.update_when(|v| std::mem::replace(&mut v.1, 0) != 0).unwrap());
+        assert!(!rx.has_changed().unwrap());
+        assert_eq!(entity.version(), 0);
+
+        assert!(entity.update_if_changed(|v| v.0 = 3).unwrap());
+        assert!(rx.has_changed().unwrap());
+        assert_eq!(entity.version(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_update_async_commits_working_copy() {
+        let entity = Entity::new(vec![1]);
+        let version = entity.version();
+        let len = entity.update_async(async |v| {
+            tokio::task::yield_now().await;
+            v.push(2);
+            v.len()
+        }).await.unwrap();
+        assert_eq!(len, 2);
+        assert_eq!(entity.read(|v| v.clone()).unwrap(), vec![1, 2]);
+        assert!(entity.version() > version);
+
+        // The update future can be moved onto another task
+        let spawned = Entity::clone(&entity);
+        tokio::spawn(async move {
+            spawned.update_async(async |v| v.push(3)).await.unwrap();
+        }).await.unwrap();
+        assert_eq!(entity.read(|v| v.len()).unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_update_async_detects_conflict() {
+        let entity = Entity::new(0);
+        let other = Entity::clone(&entity);
+        let res = entity.update_async(async |v| {
+            other.update(|o| *o = 5).unwrap();
+            *v = 1;
+        }).await;
+        assert!(matches!(res, Err(crate::Error::UpdateConflict { .. })));
+        assert_eq!(entity.read(|v| *v).unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_observe_old_and_new() {
+        let entity = Entity::new(String::from("a"));
+        let (tx, mut seen) = tokio::sync::mpsc::unbounded_channel();
+        let _handle = entity.observe(move |old, new| {
+            let _ = tx.send((old.clone(), new.clone()));
+        }).unwrap();
+        tokio::task::yield_now().await;
+
+        entity.update(|v| v.push('b')).unwrap();
+        let pair = tokio::time::timeout(Duration::from_secs(1), seen.recv()).await.unwrap().unwrap();
+        assert_eq!(pair, (String::from("a"), String::from("ab")));
+    }
+
+    #[cfg(not(feature = "parking_lot"))]
+    #[tokio::test]
+    async fn test_subscribe_value_on_poisoned_entity_is_an_error() {
+        let entity = Entity::new(0u8);
+        let poisoner = Entity::clone(&entity);
+        let _ = std::thread::spawn(move || poisoner.update(|_| panic!("poison the lock"))).join();
+        assert!(entity.subscribe_value().is_err());
+        assert!(entity.observe(|_, _| {}).is_err());
     }
 }
@@ -0,0 +1,148 @@
+//! The entities of one application, for diagnostics.
+//!
+//! Every `AppContext` owns an `EntityRegistry` listing its live entities and the size
+//! probes of those registered with `Entity::track_size`. An entity joins the registry
+//! of the application running on the thread that creates it: the thread running the
+//! event loop and the worker threads of the runtime `Application::run` starts enter it.
+//! `AppContext::new_entity` joins its own application's registry from any thread, and
+//! entities created outside an application are not listed anywhere.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use super::size::SizeProbe;
+use super::{EntityId, TRACK_LIVE};
+
+thread_local! {
+    /// The registry of the application running on this thread.
+    static CURRENT: RefCell<Option<EntityRegistry>> = const { RefCell::new(None) };
+}
+
+#[derive(Default)]
+struct Inner {
+    /// Live entities and their value type names, when `TRACK_LIVE`.
+    live: Mutex<BTreeMap<EntityId, &'static str>>,
+    /// Size probes of entities registered with `Entity::track_size`.
+    sizes: Mutex<BTreeMap<EntityId, SizeProbe>>,
+}
+
+/// Live entities and tracked sizes of one application.
+#[derive(Clone, Default)]
+pub(crate) struct EntityRegistry {
+    inner: Arc<Inner>,
+}
+
+/// Restores the previous registry of the thread when dropped; see `EntityRegistry::enter`.
+pub(crate) struct Entered {
+    previous: Option<EntityRegistry>,
+}
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+impl EntityRegistry {
+    /// The registry of the application running on this thread, if any.
+    pub(crate) fn current() -> Option<Self> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// Register the entities created on this thread here until the guard is dropped.
+    pub(crate) fn enter(&self) -> Entered {
+        let previous = CURRENT.with(|current| current.borrow_mut().replace(self.clone()));
+        Entered { previous }
+    }
+
+    /// Register the entities created on this thread here for the rest of its life, for
+    /// runtime worker threads.
+    pub(crate) fn install(&self) {
+        CURRENT.with(|current| *current.borrow_mut() = Some(self.clone()));
+    }
+
+    pub(super) fn insert(&self, id: EntityId, type_name: &'static str) {
+        if TRACK_LIVE {
+            if let Ok(mut live) = self.inner.live.lock() {
+                live.insert(id, type_name);
+            }
+        }
+    }
+
+    pub(super) fn remove(&self, id: EntityId) {
+        if TRACK_LIVE {
+            if let Ok(mut live) = self.inner.live.lock() {
+                live.remove(&id);
+            }
+        }
+        if let Ok(mut sizes) = self.inner.sizes.lock() {
+            sizes.remove(&id);
+        }
+    }
+
+    pub(super) fn track(&self, id: EntityId, probe: SizeProbe) {
+        if let Ok(mut sizes) = self.inner.sizes.lock() {
+            sizes.insert(id, probe);
+        }
+    }
+
+    /// IDs and value type names of the live entities, oldest first.
+    /// Always empty in release builds without the `soak` feature.
+    pub(crate) fn live_entities(&self) -> Vec<(EntityId, &'static str)> {
+        self.inner
+            .live
+            .lock()
+            .map(|live| live.iter().map(|(id, name)| (*id, *name)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Type name of a live entity.
+    pub(crate) fn type_name(&self, id: EntityId) -> Option<&'static str> {
+        self.inner.live.lock().ok().and_then(|live| live.get(&id).copied())
+    }
+
+    /// Tracked entities with their value type names and approximate sizes in bytes,
+    /// largest first.
+    pub(crate) fn entity_sizes(&self) -> Vec<(EntityId, &'static str, usize)> {
+        let sizes: Vec<(EntityId, usize)> = match self.inner.sizes.lock() {
+            Ok(sizes) => sizes.iter().filter_map(|(id, probe)| Some((*id, probe()?))).collect(),
+            Err(_) => return Vec::new(),
+        };
+        let mut sizes: Vec<_> = sizes
+            .into_iter()
+            .map(|(id, size)| (id, self.type_name(id).unwrap_or("<unknown>"), size))
+            .collect();
+        sizes.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(&b.0)));
+        sizes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Entity;
+
+    #[test]
+    fn test_entities_join_the_registry_entered_on_their_thread() {
+        let (first, second) = (EntityRegistry::default(), EntityRegistry::default());
+        let outside = Entity::new(0u8);
+        let entered = first.enter();
+        let mine = Entity::new(1u16);
+        let theirs = {
+            let _entered = second.enter();
+            Entity::new(2u32)
+        };
+        let also_mine = Entity::new(3u64);
+        drop(entered);
+
+        let ids = |registry: &EntityRegistry| registry.live_entities().into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+        let expected = |ids: Vec<EntityId>| if TRACK_LIVE { ids } else { Vec::new() };
+        assert_eq!(ids(&first), expected(vec![mine.entity_id(), also_mine.entity_id()]));
+        assert_eq!(ids(&second), expected(vec![theirs.entity_id()]));
+        assert!(EntityRegistry::current().is_none());
+        drop((outside, mine));
+        assert_eq!(ids(&first), expected(vec![also_mine.entity_id()]));
+    }
+}
@@ -0,0 +1,172 @@
+//! Approximate memory accounting for entity values.
+//!
+//! `SizeOf` estimates how many bytes a value holds, counting heap allocations by
+//! capacity. Entities whose value implements it can be registered with
+//! `Entity::track_size`; `AppContext::entity_sizes` then lists them largest first, and soak runs
+//! report their total, so unbounded growth (logs, laps, particles) shows up in long
+//! sessions before it shows up as a slowdown.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::mem::size_of;
+use std::sync::Weak;
+
+use super::{EntityId, RwLock};
+
+/// Approximate size of a value in memory.
+///
+/// Implement `heap_size` (bytes owned behind pointers); `approx_size` adds the inline
+/// size. Structs can `#[derive(SizeOf)]`, which sums the fields' heap sizes; mark
+/// fields without an implementation `#[size_of(skip)]`.
+///
+/// # Example
+/// ```ignore
+/// #[derive(Clone, Default, SizeOf)]
+/// pub struct TimerState {
+///     pub elapsed_ms: u64,
+///     pub laps: Vec<u64>,
+/// }
+///
+/// let state = cx.new_entity(TimerState::default()).track_size();
+/// state.approx_size(); // 40 + 8 per lap of capacity
+/// ```
+pub trait SizeOf {
+    /// Bytes owned outside the value itself, e.g. a `Vec`'s buffer.
+    fn heap_size(&self) -> usize {
+        0
+    }
+
+    /// Inline size plus heap size.
+    fn approx_size(&self) -> usize {
+        std::mem::size_of_val(self) + self.heap_size()
+    }
+}
+
+macro_rules! inline_only {
+    ($($ty:ty),*) => {
+        $(impl SizeOf for $ty {})*
+    };
+}
+
+inline_only!(
+    (), bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64,
+    &'static str, std::time::Duration, std::time::Instant, std::time::SystemTime,
+    ratatui::style::Color, ratatui::style::Style, ratatui::layout::Rect
+);
+
+impl SizeOf for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: SizeOf> SizeOf for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(SizeOf::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: SizeOf> SizeOf for VecDeque<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(SizeOf::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: SizeOf> SizeOf for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, SizeOf::heap_size)
+    }
+}
+
+impl<T: SizeOf> SizeOf for Box<T> {
+    fn heap_size(&self) -> usize {
+        (**self).approx_size()
+    }
+}
+
+impl<T: SizeOf, const N: usize> SizeOf for [T; N] {
+    fn heap_size(&self) -> usize {
+        self.iter().map(SizeOf::heap_size).sum()
+    }
+}
+
+impl<K: SizeOf, V: SizeOf, S> SizeOf for HashMap<K, V, S> {
+    fn heap_size(&self) -> usize {
+        // One control byte per bucket on top of the entries
+        self.capacity() * (size_of::<(K, V)>() + 1)
+            + self.iter().map(|(k, v)| k.heap_size() + v.heap_size()).sum::<usize>()
+    }
+}
+
+impl<T: SizeOf, S> SizeOf for HashSet<T, S> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * (size_of::<T>() + 1) + self.iter().map(SizeOf::heap_size).sum::<usize>()
+    }
+}
+
+impl<K: SizeOf, V: SizeOf> SizeOf for BTreeMap<K, V> {
+    fn heap_size(&self) -> usize {
+        // Nodes hold up to 11 entries; assume they are about two-thirds full
+        self.len() * size_of::<(K, V)>() * 3 / 2
+            + self.iter().map(|(k, v)| k.heap_size() + v.heap_size()).sum::<usize>()
+    }
+}
+
+macro_rules! tuple {
+    ($($name:ident),+) => {
+        impl<$($name: SizeOf),+> SizeOf for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn heap_size(&self) -> usize {
+                let ($($name,)+) = self;
+                0 $(+ $name.heap_size())+
+            }
+        }
+    };
+}
+
+tuple!(A);
+tuple!(A, B);
+tuple!(A, B, C);
+tuple!(A, B, C, D);
+
+pub(super) type SizeProbe = Box<dyn Fn() -> Option<usize> + Send>;
+
+/// Reads the approximate size of the entity's value while it is alive.
+pub(super) fn probe<T: SizeOf + Send + Sync + 'static>(id: EntityId, value: Weak<RwLock<T>>) -> SizeProbe {
+    Box::new(move || {
+        let lock = value.upgrade()?;
+        let size = super::lock::read(&lock, id).ok().map(|value| value.approx_size());
+        size
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::EntityRegistry;
+    use crate::Entity;
+
+    #[test]
+    fn test_sizes_count_capacity_and_nested_heap() {
+        assert_eq!(42u32.approx_size(), 4);
+        let strings = vec![String::with_capacity(10), String::with_capacity(6)];
+        assert_eq!(strings.heap_size(), strings.capacity() * size_of::<String>() + 16);
+        assert_eq!((1u8, Some(String::with_capacity(3))).heap_size(), 3);
+    }
+
+    #[test]
+    fn test_tracked_entities_report_growth() {
+        let registry = EntityRegistry::default();
+        let _entered = registry.enter();
+        let laps = Entity::new(Vec::<u64>::new()).track_size();
+        let id = laps.entity_id();
+        let size = |id| registry.entity_sizes().into_iter().find(|entry| entry.0 == id).map(|entry| entry.2);
+        assert_eq!(size(id), Some(size_of::<Vec<u64>>()));
+
+        laps.update(|laps| laps.extend(0..100)).unwrap();
+        assert!(size(id).unwrap() >= size_of::<Vec<u64>>() + 800);
+        assert_eq!(laps.approx_size(), size(id).unwrap());
+
+        drop(laps);
+        assert_eq!(size(id), None);
+    }
+}
@@ -0,0 +1,200 @@
+//! Render storm detection.
+//!
+//! Every refresh request is recorded together with the entity whose change triggered it
+//! (if any). When requests arrive faster than the configured threshold, a `StormReport`
+//! naming the busiest entities is logged at warn level and, optionally, drawn as a
+//! one-line banner on top of the frame. This makes runaway subscribe/update loops (a
+//! component re-subscribing on every render, an observer updating what it observes)
+//! visible instead of silently burning CPU.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::state::EntityId;
+
+/// Default threshold, in refresh requests per second.
+pub const DEFAULT_THRESHOLD: u32 = 1000;
+
+/// Length of the measurement window.
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Number of entities named in a report.
+const TOP_SOURCES: usize = 5;
+
+/// A detected render storm.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StormReport {
+    /// Refresh requests per second over the last window.
+    pub rate: u32,
+    /// Entities that triggered refreshes, busiest first, with their type name and count.
+    pub sources: Vec<(EntityId, &'static str, u32)>,
+    /// Requests not attributed to an entity (explicit `refresh`/`notify` calls).
+    pub untracked: u32,
+}
+
+impl std::fmt::Display for StormReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "render storm: {} refresh/s", self.rate)?;
+        if self.sources.is_empty() {
+            return write!(f, " (no entity sources, {} explicit refreshes)", self.untracked);
+        }
+        let sources: Vec<String> = self
+            .sources
+            .iter()
+            .map(|(id, name, count)| format!("entity {id} ({name}) x{count}"))
+            .collect();
+        write!(f, " from {}", sources.join(", "))
+    }
+}
+
+struct StormInner {
+    threshold: u32,
+    overlay: bool,
+    window_start: Instant,
+    total: u32,
+    sources: HashMap<EntityId, u32>,
+    report: Option<StormReport>,
+}
+
+/// Shared refresh-rate monitor, owned by the `AppContext`.
+#[derive(Clone)]
+pub struct StormDetector {
+    inner: Arc<Mutex<StormInner>>,
+}
+
+impl Default for StormDetector {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(StormInner {
+                threshold: DEFAULT_THRESHOLD,
+                overlay: false,
+                window_start: Instant::now(),
+                total: 0,
+                sources: HashMap::new(),
+                report: None,
+            })),
+        }
+    }
+}
+
+impl StormDetector {
+    /// Set the refresh rate (requests per second) above which a storm is reported.
+    pub fn set_threshold(&self, per_second: u32) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.threshold = per_second.max(1);
+        }
+    }
+
+    /// Draw a warning banner on top of the frame while a storm is ongoing.
+    pub fn set_overlay(&self, enabled: bool) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.overlay = enabled;
+        }
+    }
+
+    /// The ongoing storm, if any.
+    pub fn report(&self) -> Option<StormReport> {
+        self.report_at(Instant::now())
+    }
+
+    /// Record a refresh request, attributed to `source` if an entity change triggered it.
+    pub(crate) fn record(&self, source: Option<EntityId>) {
+        self.record_at(source, Instant::now());
+    }
+
+    /// Draw the warning banner if enabled and a storm is ongoing.
+    pub(crate) fn render_overlay(&self, frame: &mut Frame) {
+        let overlay = self.inner.lock().map(|inner| inner.overlay).unwrap_or(false);
+        let Some(report) = overlay.then(|| self.report()).flatten() else {
+            return;
+        };
+        let area = frame.area();
+        let banner = Rect::new(area.x, area.y, area.width, area.height.min(1));
+        let style = Style::default().fg(Color::White).bg(Color::Red).add_modifier(Modifier::BOLD);
+        frame.render_widget(Paragraph::new(format!(" ⚠ {report}")).style(style), banner);
+    }
+
+    fn record_at(&self, source: Option<EntityId>, now: Instant) {
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        let elapsed = now.saturating_duration_since(inner.window_start);
+        if elapsed >= WINDOW {
+            let rate = (f64::from(inner.total) / elapsed.as_secs_f64()).round() as u32;
+            inner.report = (rate > inner.threshold).then(|| {
+                let mut sources: Vec<(EntityId, &'static str, u32)> = inner
+                    .sources
+                    .iter()
+                    .map(|(id, count)| (*id, crate::state::entity_type_name(*id), *count))
+                    .collect();
+                sources.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(&b.0)));
+                sources.truncate(TOP_SOURCES);
+                let attributed: u32 = inner.sources.values().sum();
+                StormReport { rate, sources, untracked: inner.total - attributed }
+            });
+            if let Some(report) = &inner.report {
+                log::warn!("{report}");
+            }
+            inner.window_start = now;
+            inner.total = 0;
+            inner.sources.clear();
+        }
+
+        inner.total += 1;
+        if let Some(id) = source {
+            *inner.sources.entry(id).or_insert(0) += 1;
+        }
+    }
+
+    fn report_at(&self, now: Instant) -> Option<StormReport> {
+        let inner = self.inner.lock().ok()?;
+        // A storm that stopped leaves no further records to close the window with
+        let stale = now.saturating_duration_since(inner.window_start) >= WINDOW * 2;
+        if stale { None } else { inner.report.clone() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Entity;
+
+    #[test]
+    fn test_storm_names_sources() {
+        let detector = StormDetector::default();
+        detector.set_threshold(100);
+        let noisy = Entity::new(0u8);
+        let start = Instant::now();
+
+        for i in 0..300u32 {
+            let at = start + Duration::from_millis(u64::from(i) * 3);
+            let source = (i % 3 != 0).then(|| noisy.entity_id());
+            detector.record_at(source, at);
+        }
+        detector.record_at(None, start + Duration::from_secs(1));
+
+        let report = detector.report_at(start + Duration::from_secs(1)).expect("storm detected");
+        assert!(report.rate > 100);
+        assert_eq!(report.sources[0].0, noisy.entity_id());
+        assert_eq!(report.sources[0].2, 200);
+        assert_eq!(report.untracked, 100);
+        assert!(detector.report_at(start + Duration::from_secs(3)).is_none());
+    }
+
+    #[test]
+    fn test_calm_rate_is_not_reported() {
+        let detector = StormDetector::default();
+        let start = Instant::now();
+        for i in 0..60u64 {
+            detector.record_at(None, start + Duration::from_millis(i * 16));
+        }
+        detector.record_at(None, start + Duration::from_secs(1));
+        assert!(detector.report_at(start + Duration::from_secs(1)).is_none());
+    }
+}
@@ -0,0 +1,170 @@
+//! Registry of entity subscriptions and observers.
+//!
+//! `Context::subscribe` is typically called from `render`, i.e. every frame. The registry
+//! keys forwarding tasks by (subscribing component, subscribed entity, kind) so each
+//! combination runs exactly one task. A task ends when the subscribed entity or the
+//! subscribing component is dropped, or when the component's subscriptions are removed
+//! (on `on_exit`), and then removes itself from the registry.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::watch;
+use tokio::task::AbortHandle;
+
+use crate::state::EntityId;
+
+/// What a registered task does on each change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Kind {
+    /// Request a re-render (`Context::subscribe`).
+    Refresh,
+    /// Run a component callback (`Context::observe`).
+    Observe,
+}
+
+/// (subscribing component, subscribed entity, kind). Contexts not bound to a component
+/// (e.g. obtained via `cast`) share the `None` owner.
+type Key = (Option<EntityId>, EntityId, Kind);
+
+#[derive(Clone, Default)]
+pub(crate) struct Subscriptions {
+    tasks: Arc<Mutex<HashMap<Key, AbortHandle>>>,
+}
+
+impl Subscriptions {
+    /// Spawn the task built by `make` for `key`, unless one is already running.
+    pub(crate) fn insert_with<F, Fut>(&self, key: Key, make: F)
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let Ok(mut tasks) = self.tasks.lock() else {
+            return;
+        };
+        if tasks.get(&key).is_some_and(|task| !task.is_finished()) {
+            return;
+        }
+        let registry = self.clone();
+        let future = make();
+        let handle = tokio::spawn(async move {
+            future.await;
+            registry.remove_task(key, tokio::task::id());
+        });
+        tasks.insert(key, handle.abort_handle());
+    }
+
+    /// Abort every subscription held by `owner`.
+    pub(crate) fn remove_owner(&self, owner: EntityId) {
+        if let Ok(mut tasks) = self.tasks.lock() {
+            tasks.retain(|(task_owner, _, _), task| {
+                let keep = *task_owner != Some(owner);
+                if !keep {
+                    task.abort();
+                }
+                keep
+            });
+        }
+    }
+
+    /// Number of live subscriptions.
+    pub(crate) fn len(&self) -> usize {
+        self.tasks.lock().map(|tasks| tasks.len()).unwrap_or(0)
+    }
+
+    fn remove_task(&self, key: Key, id: tokio::task::Id) {
+        if let Ok(mut tasks) = self.tasks.lock() {
+            if tasks.get(&key).is_some_and(|task| task.id() == id) {
+                tasks.remove(&key);
+            }
+        }
+    }
+}
+
+/// Resolve once the watched entity is gone; never resolves for `None`.
+pub(crate) async fn closed(rx: Option<&mut watch::Receiver<()>>) {
+    match rx {
+        Some(rx) => while rx.changed().await.is_ok() {},
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AppContext, Context, Entity};
+
+    struct Owner;
+
+    #[derive(Default)]
+    struct Observer {
+        seen: Vec<i32>,
+    }
+
+    async fn settle() {
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_is_idempotent_and_cleaned_up() {
+        let (app, _refresh, _inject) = AppContext::new();
+        let owner = Entity::new(Owner);
+        let mut cx = Context::new(app.clone(), owner.downgrade());
+        let counter = Entity::new(0);
+
+        for _ in 0..100 {
+            cx.subscribe(&counter);
+        }
+        assert_eq!(app.subscription_count(), 1);
+
+        drop(counter);
+        settle().await;
+        assert_eq!(app.subscription_count(), 0);
+
+        let other = Entity::new(0);
+        cx.subscribe(&other);
+        assert_eq!(app.subscription_count(), 1);
+        drop(owner);
+        settle().await;
+        assert_eq!(app.subscription_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_observe_runs_callback_until_unsubscribed() {
+        let (app, _refresh, _inject) = AppContext::new();
+        let observer = Entity::new(Observer::default());
+        let counter = Entity::new(0);
+        let mut cx = Context::new(app.clone(), observer.downgrade());
+
+        let source = counter.clone();
+        cx.observe(&counter, move |this: &mut Observer, _cx| {
+            this.seen.push(source.read(|v| *v).unwrap_or_default());
+        }).unwrap();
+        let _ = counter.update(|v| *v = 1);
+        settle().await;
+        let _ = counter.update(|v| *v = 2);
+        settle().await;
+        assert_eq!(observer.read(|o| o.seen.clone()).unwrap(), vec![1, 2]);
+
+        cx.unsubscribe_all();
+        settle().await;
+        let _ = counter.update(|v| *v = 3);
+        settle().await;
+        assert_eq!(observer.read(|o| o.seen.len()).unwrap(), 2);
+        assert_eq!(app.subscription_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_observe_needs_a_bound_context() {
+        let (app, _refresh, _inject) = AppContext::new();
+        let observer = Entity::new(Observer::default());
+        let counter = Entity::new(0);
+        let mut unbound = Context::new(app.clone(), observer.downgrade()).cast::<Observer>();
+
+        let observed = unbound.observe(&counter, |_: &mut Observer, _cx| {});
+        assert!(matches!(observed, Err(crate::Error::UnboundContext { .. })), "{observed:?}");
+        assert_eq!(app.subscription_count(), 0);
+    }
+}
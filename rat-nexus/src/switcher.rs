@@ -0,0 +1,277 @@
+//! Quick switcher: an Alt-Tab-style overlay over recently visited routes.
+//!
+//! Enabled with `Application::quick_switcher(true)`. The switcher follows the
+//! `NavigationEvent`s emitted by `define_app!` and `AppShell` and keeps routes in
+//! most-recently-visited order. The switcher key (`Ctrl+E` by default, see
+//! `AppContext::set_switcher_key`) opens it with the previous route selected, so the
+//! key and `Enter` flip between two pages; pressing the key again moves further back.
+//! `↑/↓` move, `1`–`9` pick directly, `Esc` closes. Terminals do not report modifier
+//! releases, so the choice is confirmed with `Enter` rather than by letting go.
+
+use std::sync::{Arc, Mutex};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Widget};
+
+use crate::component::traits::Event;
+use crate::router::NavigationEvent;
+use crate::AppContext;
+
+/// Routes remembered, and listed, at most.
+const MAX_RECENT: usize = 9;
+
+/// The open overlay: routes with their display labels, and the selected row.
+struct Open {
+    items: Vec<(String, String)>,
+    cursor: usize,
+    area: Option<Rect>,
+}
+
+struct SwitcherInner {
+    enabled: bool,
+    key: Option<KeyEvent>,
+    recent: Vec<String>,
+    open: Option<Open>,
+}
+
+/// Recent routes and the open switcher, owned by the `AppContext`.
+#[derive(Clone)]
+pub(crate) struct Switcher {
+    inner: Arc<Mutex<SwitcherInner>>,
+}
+
+impl Default for Switcher {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(SwitcherInner {
+                enabled: false,
+                key: Some(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL)),
+                recent: Vec::new(),
+                open: None,
+            })),
+        }
+    }
+}
+
+impl Switcher {
+    pub(crate) fn set_enabled(&self, enabled: bool) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.enabled = enabled;
+            if !enabled {
+                inner.open = None;
+            }
+        }
+    }
+
+    pub(crate) fn set_key(&self, key: Option<KeyEvent>) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.key = key;
+        }
+    }
+
+    pub(crate) fn is_open(&self) -> bool {
+        self.inner.lock().map(|inner| inner.open.is_some()).unwrap_or(false)
+    }
+
+    /// Visited routes, most recent (the current one) first.
+    pub(crate) fn recent(&self) -> Vec<String> {
+        self.inner.lock().map(|inner| inner.recent.clone()).unwrap_or_default()
+    }
+
+    fn record(inner: &mut SwitcherInner, navigation: &NavigationEvent) {
+        inner.recent.retain(|route| *route != navigation.to);
+        inner.recent.insert(0, navigation.to.clone());
+        if !inner.recent.contains(&navigation.from) {
+            inner.recent.insert(1, navigation.from.clone());
+        }
+        inner.recent.truncate(MAX_RECENT);
+    }
+
+    /// Offer an event to the switcher. Navigation events are recorded and passed on;
+    /// returns true if the event was consumed.
+    pub(crate) fn intercept(&self, event: &Event, app: &AppContext) -> bool {
+        let Ok(mut guard) = self.inner.lock() else {
+            return false;
+        };
+        let inner = &mut *guard;
+        if !inner.enabled {
+            return false;
+        }
+        if let Some(navigation) = event.custom::<NavigationEvent>() {
+            Self::record(inner, navigation);
+            return false;
+        }
+        let switch_key = inner.key;
+
+        let Some(open) = inner.open.as_mut() else {
+            if !matches!(event, Event::Key(key) if Some(*key) == switch_key) {
+                return false;
+            }
+            if inner.recent.is_empty() {
+                inner.recent.extend(app.active_route());
+            }
+            let routes = app.routes();
+            let items: Vec<(String, String)> = inner
+                .recent
+                .iter()
+                .map(|name| {
+                    let label = match routes.iter().find(|info| info.name.eq_ignore_ascii_case(name)) {
+                        Some(info) => match &info.icon {
+                            Some(icon) => format!("{icon} {}", info.label),
+                            None => info.label.clone(),
+                        },
+                        None => name.clone(),
+                    };
+                    (name.clone(), label)
+                })
+                .collect();
+            let cursor = usize::from(items.len() > 1);
+            inner.open = Some(Open { items, cursor, area: None });
+            return true;
+        };
+
+        let len = open.items.len().max(1);
+        let mut pick = None;
+        match event {
+            Event::Key(key) if Some(*key) == switch_key => open.cursor = (open.cursor + 1) % len,
+            Event::Key(key) => match key.code {
+                KeyCode::Esc => {
+                    inner.open = None;
+                    return true;
+                }
+                KeyCode::Enter | KeyCode::Char(' ') => pick = Some(open.cursor),
+                KeyCode::Down | KeyCode::Tab => open.cursor = (open.cursor + 1) % len,
+                KeyCode::Up | KeyCode::BackTab => open.cursor = (open.cursor + len - 1) % len,
+                KeyCode::Char(c @ '1'..='9') => pick = Some(c as usize - '1' as usize),
+                _ => {}
+            },
+            Event::Mouse(mouse) if matches!(mouse.kind, MouseEventKind::Down(MouseButton::Left)) => {
+                let position = Position::new(mouse.column, mouse.row);
+                match open.area.filter(|area| area.contains(position)) {
+                    Some(area) => pick = Some(usize::from(mouse.row.saturating_sub(area.y + 1))),
+                    None => {
+                        inner.open = None;
+                        return true;
+                    }
+                }
+            }
+            Event::Mouse(_) => {}
+            _ => return false,
+        }
+
+        if let Some(index) = pick {
+            let Some((route, _)) = open.items.get(index).cloned() else {
+                return true;
+            };
+            inner.open = None;
+            // Release the lock first: navigating records into the switcher
+            drop(guard);
+            if app.active_route().is_none_or(|active| !active.eq_ignore_ascii_case(&route)) {
+                app.navigate(route);
+            }
+        }
+        true
+    }
+
+    /// Draw the open switcher centered in `bounds`, if any.
+    pub(crate) fn render(&self, buf: &mut Buffer, bounds: Rect) {
+        let Ok(mut inner) = self.inner.lock() else { return };
+        let Some(open) = inner.open.as_mut() else { return };
+
+        let widest = open.items.iter().map(|(_, label)| label.chars().count()).max().unwrap_or(0) as u16;
+        let width = (widest + 16).clamp(24, 60).min(bounds.width);
+        let height = (open.items.len() as u16 + 2).min(bounds.height);
+        let area = Rect::new(
+            bounds.x + (bounds.width - width) / 2,
+            bounds.y + (bounds.height - height) / 2,
+            width,
+            height,
+        );
+        open.area = Some(area);
+        if area.height < 3 {
+            return;
+        }
+        Clear.render(area, buf);
+        let block = Block::default()
+            .title(" Recent ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        for (row, (_, label)) in open.items.iter().enumerate().take(inner_area.height as usize) {
+            let y = inner_area.y + row as u16;
+            let style = if row == open.cursor { Style::default().fg(Color::Black).bg(Color::Cyan) } else { Style::default() };
+            buf.set_style(Rect::new(inner_area.x, y, inner_area.width, 1), style);
+            buf.set_stringn(inner_area.x, y, format!(" {}  {label}", row + 1), inner_area.width as usize, style);
+            if row == 0 && inner_area.width > 12 {
+                let hint = if row == open.cursor { style } else { style.fg(Color::DarkGray) };
+                buf.set_string(inner_area.right() - 8, y, "current", hint);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::NavigationTrigger;
+
+    fn navigated(from: &str, to: &str) -> Event {
+        Event::Custom(crate::CustomEvent::new(
+            crate::router::NAVIGATED,
+            NavigationEvent::new(from, to, NavigationTrigger::Action),
+        ))
+    }
+
+    #[test]
+    fn test_switcher_orders_recent_routes_and_switches() {
+        let (app, _refresh, mut injected) = AppContext::new();
+        let ctrl_e = Event::Key(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL));
+        assert!(!app.switcher.intercept(&ctrl_e, &app), "disabled by default");
+
+        app.switcher.set_enabled(true);
+        for (from, to) in [("Menu", "Monitor"), ("Monitor", "Timer"), ("Timer", "Menu")] {
+            assert!(!app.switcher.intercept(&navigated(from, to), &app));
+        }
+        assert_eq!(app.recent_routes(), vec!["Menu", "Timer", "Monitor"]);
+
+        // Key, Enter: back to the previous route
+        assert!(app.switcher.intercept(&ctrl_e, &app));
+        assert!(app.switcher_open());
+        assert!(app.switcher.intercept(&Event::Key(KeyEvent::from(KeyCode::Enter)), &app));
+        assert!(!app.switcher_open());
+        assert_eq!(injected.try_recv().unwrap().custom::<String>().map(String::as_str), Some("Timer"));
+
+        // Key twice: two routes back
+        app.switcher.intercept(&ctrl_e, &app);
+        app.switcher.intercept(&ctrl_e, &app);
+        app.switcher.intercept(&Event::Key(KeyEvent::from(KeyCode::Enter)), &app);
+        assert_eq!(injected.try_recv().unwrap().custom::<String>().map(String::as_str), Some("Monitor"));
+
+        app.switcher.intercept(&ctrl_e, &app);
+        assert!(!app.switcher.intercept(&Event::Resize(10, 10), &app));
+        assert!(app.switcher.intercept(&Event::Key(KeyEvent::from(KeyCode::Esc)), &app));
+        assert!(injected.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_switcher_draws_labels() {
+        let (app, _, _) = AppContext::new();
+        app.switcher.set_enabled(true);
+        app.describe_route(crate::RouteInfo::new("Monitor").label("System monitor"));
+        app.switcher.intercept(&navigated("Menu", "Monitor"), &app);
+        app.switcher.intercept(&Event::Key(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL)), &app);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 6));
+        let bounds = buf.area;
+        app.switcher.render(&mut buf, bounds);
+        let row = |y: u16| (0..40).map(|x| buf[(x, y)].symbol()).collect::<String>();
+        assert!(row(2).contains("1  System monitor"));
+        assert!(row(3).contains("2  Menu"));
+    }
+}
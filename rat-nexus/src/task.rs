@@ -2,19 +2,178 @@
 //!
 //! Provides `TaskHandle` for cancellable async tasks and `TaskTracker` for
 //! managing multiple tasks that should be cancelled together (e.g., on component exit).
+//! A scoped tracker (`TaskTracker::scoped`) signals a `CancelToken` and awaits its
+//! tasks instead of aborting them, so their cleanup runs.
+//! Tasks started with `spawn_with_progress` report through a `Progress`, which the
+//! `TaskProgress` widget renders as a gauge.
+//! When the application exits it signals `AppContext::shutdown_token` and waits for
+//! the tasks started with `AppContext::spawn_critical`, up to a grace period.
 
-use tokio::task::AbortHandle;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-/// A handle to a spawned task that can be aborted.
+use tokio::sync::watch;
+use tokio::task::{AbortHandle, JoinHandle};
+
+use crate::Entity;
+
+/// What a task reported about its progress.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProgressState {
+    /// Share of the work done, 0.0–1.0.
+    pub fraction: f64,
+    /// What the task is currently doing, if it said.
+    pub message: Option<String>,
+    /// The task completed or was aborted.
+    pub finished: bool,
+}
+
+/// Progress reported by a running task.
+///
+/// Cloning shares the same state: the task keeps one clone to report through, the
+/// `TaskHandle` and any `TaskProgress` widget read the others. Reports that change
+/// nothing do not trigger a redraw.
+#[derive(Clone, Default)]
+pub struct Progress {
+    state: Entity<ProgressState>,
+}
+
+impl Progress {
+    /// Create progress at 0%.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report the share of the work done, clamped to 0.0–1.0.
+    pub fn report(&self, fraction: f64) {
+        let fraction = if fraction.is_nan() { 0.0 } else { fraction.clamp(0.0, 1.0) };
+        let _ = self.state.update_if_changed(|s| s.fraction = fraction);
+    }
+
+    /// Describe the current step, e.g. the file being copied.
+    pub fn message(&self, message: impl Into<String>) {
+        let message = message.into();
+        let _ = self.state.update_if_changed(|s| s.message = Some(message));
+    }
+
+    /// The latest report.
+    pub fn get(&self) -> ProgressState {
+        self.state.read(ProgressState::clone).unwrap_or_default()
+    }
+
+    /// The underlying entity, e.g. to `cx.subscribe` to it.
+    pub fn entity(&self) -> &Entity<ProgressState> {
+        &self.state
+    }
+
+    /// A guard marking the progress finished when dropped, whether the task completes
+    /// or is aborted.
+    pub(crate) fn finish_guard(&self) -> FinishGuard {
+        FinishGuard(self.clone())
+    }
+}
+
+impl std::fmt::Debug for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Progress").field(&self.get()).finish()
+    }
+}
+
+/// Marks a `Progress` finished when the task future is dropped.
+pub(crate) struct FinishGuard(Progress);
+
+impl Drop for FinishGuard {
+    fn drop(&mut self) {
+        let _ = self.0.state.update_if_changed(|s| s.finished = true);
+    }
+}
+
+/// Cooperative cancellation signal shared with the tasks of a `TaskTracker`.
+///
+/// Cloning shares the signal. Tasks that need to clean up select on `cancelled()`
+/// instead of being aborted mid-await.
+#[derive(Debug, Clone)]
+pub struct CancelToken {
+    tx: Arc<watch::Sender<bool>>,
+}
+
+impl Default for CancelToken {
+    fn default() -> Self {
+        Self { tx: Arc::new(watch::Sender::new(false)) }
+    }
+}
+
+impl CancelToken {
+    /// Create a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Signal cancellation to every clone.
+    pub fn cancel(&self) {
+        self.tx.send_replace(true);
+    }
+
+    /// Whether cancellation was signalled.
+    pub fn is_cancelled(&self) -> bool {
+        *self.tx.borrow()
+    }
+
+    /// Wait until cancellation is signalled.
+    pub async fn cancelled(&self) {
+        let mut rx = self.tx.subscribe();
+        let _ = rx.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+/// A handle to a spawned task that can be aborted or awaited.
 #[derive(Debug)]
 pub struct TaskHandle {
     abort_handle: AbortHandle,
+    join_handle: Option<JoinHandle<()>>,
+    name: Option<String>,
+    progress: Option<Progress>,
 }
 
 impl TaskHandle {
     /// Create a new TaskHandle from an AbortHandle.
+    /// Such a handle can only be awaited by polling; prefer `from_join_handle`.
     pub fn new(abort_handle: AbortHandle) -> Self {
-        Self { abort_handle }
+        Self { abort_handle, join_handle: None, name: None, progress: None }
+    }
+
+    /// Create a TaskHandle that can be awaited with `join`.
+    pub fn from_join_handle(join_handle: JoinHandle<()>) -> Self {
+        Self { abort_handle: join_handle.abort_handle(), join_handle: Some(join_handle), name: None, progress: None }
+    }
+
+    /// Name the task, e.g. for `TaskTracker::active_names`.
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// The task's name, if it was given one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Attach the progress the task reports through.
+    pub fn with_progress(mut self, progress: Progress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// The task's progress, if it was spawned with `spawn_with_progress`.
+    pub fn progress(&self) -> Option<Progress> {
+        self.progress.clone()
+    }
+
+    /// Report progress on the task's behalf; a no-op without attached progress.
+    pub fn report(&self, fraction: f64) {
+        if let Some(progress) = &self.progress {
+            progress.report(fraction);
+        }
     }
 
     /// Abort the task. The task will be cancelled at the next await point.
@@ -26,12 +185,38 @@ impl TaskHandle {
     pub fn is_finished(&self) -> bool {
         self.abort_handle.is_finished()
     }
+
+    /// Wait for the task to finish, whether it completes, panics or is aborted.
+    pub async fn join(self) {
+        match self.join_handle {
+            Some(join_handle) => {
+                if let Err(err) = join_handle.await {
+                    if err.is_panic() {
+                        log::warn!("task {} panicked", self.name.as_deref().unwrap_or("<unnamed>"));
+                    }
+                }
+            }
+            None => {
+                while !self.abort_handle.is_finished() {
+                    tokio::time::sleep(JOIN_POLL).await;
+                }
+            }
+        }
+    }
 }
 
-/// A collection of task handles that can be cancelled together.
+/// How often `join` checks a handle created from a bare `AbortHandle`.
+const JOIN_POLL: Duration = Duration::from_millis(5);
+
+/// How long a scoped tracker waits for cancelled tasks before aborting them.
+pub const DEFAULT_GRACE: Duration = Duration::from_secs(1);
+
+/// A collection of task handles that can be cancelled, or awaited, together.
 ///
 /// Useful for components that spawn multiple background tasks that should
-/// all be cancelled when the component exits.
+/// all be cancelled when the component exits. A plain tracker aborts its tasks; a
+/// `scoped` one signals its `CancelToken` and waits up to a grace period for them to
+/// return, so cleanup code inside the tasks runs, aborting only the stragglers.
 ///
 /// # Example
 /// ```ignore
@@ -48,7 +233,7 @@ impl TaskHandle {
 ///                 tokio::time::sleep(Duration::from_secs(1)).await;
 ///             }
 ///         });
-///         self.tasks.track(handle);
+///         self.tasks.track(handle.named("poller"));
 ///     }
 ///
 ///     fn on_exit(&mut self, _cx: &mut Context<Self>) {
@@ -57,15 +242,52 @@ impl TaskHandle {
 ///     }
 /// }
 /// ```
+///
+/// With `TaskTracker::scoped()`, tasks watch the tracker's token to clean up:
+/// ```ignore
+/// let token = self.tasks.token();
+/// self.tasks.track(cx.spawn_task(|_, _| async move {
+///     let mut conn = connect().await;
+///     tokio::select! {
+///         _ = conn.stream() => {}
+///         _ = token.cancelled() => {}
+///     }
+///     conn.close().await; // runs on exit too
+/// }));
+/// ```
 #[derive(Debug, Default)]
 pub struct TaskTracker {
     handles: Vec<TaskHandle>,
+    token: CancelToken,
+    grace: Option<Duration>,
 }
 
 impl TaskTracker {
     /// Create a new empty TaskTracker.
     pub fn new() -> Self {
-        Self { handles: Vec::new() }
+        Self::default()
+    }
+
+    /// Create a tracker whose `abort_all` (and drop) cancels its token and waits up to
+    /// `DEFAULT_GRACE` for the tasks to return before aborting them.
+    pub fn scoped() -> Self {
+        Self::new().with_grace(DEFAULT_GRACE)
+    }
+
+    /// Make the tracker scoped, waiting up to `grace` for cancelled tasks.
+    pub fn with_grace(mut self, grace: Duration) -> Self {
+        self.grace = Some(grace);
+        self
+    }
+
+    /// Whether tasks are waited for instead of aborted outright.
+    pub fn is_scoped(&self) -> bool {
+        self.grace.is_some()
+    }
+
+    /// The token cancelled by `abort_all` and `cancel`, for tasks to watch.
+    pub fn token(&self) -> CancelToken {
+        self.token.clone()
     }
 
     /// Track a task handle. The task will be aborted when `abort_all` is called.
@@ -75,13 +297,38 @@ impl TaskTracker {
         self.handles.push(handle);
     }
 
-    /// Abort all tracked tasks.
+    /// Signal the tracker's token without aborting or forgetting any task, e.g. before
+    /// `join_all`. Tasks tracked afterwards get a fresh token.
+    pub fn cancel(&mut self) {
+        std::mem::take(&mut self.token).cancel();
+    }
+
+    /// Abort all tracked tasks. A scoped tracker cancels its token and gives the tasks
+    /// its grace period to return in the background before aborting the rest.
     pub fn abort_all(&mut self) {
-        for handle in self.handles.drain(..) {
-            handle.abort();
+        self.cancel();
+        let handles: Vec<TaskHandle> = self.handles.drain(..).filter(|h| !h.is_finished()).collect();
+        let runtime = tokio::runtime::Handle::try_current();
+        match (self.grace, runtime) {
+            (Some(grace), Ok(runtime)) if !handles.is_empty() => {
+                let aborts: Vec<AbortHandle> = handles.iter().map(|h| h.abort_handle.clone()).collect();
+                runtime.spawn(async move {
+                    let joined = join_each(handles);
+                    if tokio::time::timeout(grace, joined).await.is_err() {
+                        aborts.iter().for_each(AbortHandle::abort);
+                    }
+                });
+            }
+            _ => handles.iter().for_each(TaskHandle::abort),
         }
     }
 
+    /// Wait for every tracked task to finish. Tasks are not cancelled; call `cancel`
+    /// first to ask them to stop.
+    pub async fn join_all(&mut self) {
+        join_each(std::mem::take(&mut self.handles)).await;
+    }
+
     /// Get the number of active (non-finished) tracked tasks.
     pub fn active_count(&self) -> usize {
         self.handles.iter().filter(|h| !h.is_finished()).count()
@@ -91,6 +338,54 @@ impl TaskTracker {
     pub fn has_active_tasks(&self) -> bool {
         self.handles.iter().any(|h| !h.is_finished())
     }
+
+    /// Names of the active tasks that were given one.
+    pub fn active_names(&self) -> Vec<String> {
+        self.handles.iter().filter(|h| !h.is_finished()).filter_map(|h| h.name.clone()).collect()
+    }
+}
+
+/// The application's shutdown: a token signalled on exit, and the critical tasks
+/// waited for before the runtime goes away.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Shutdown {
+    token: CancelToken,
+    critical: Arc<Mutex<Vec<TaskHandle>>>,
+}
+
+impl Shutdown {
+    pub(crate) fn token(&self) -> CancelToken {
+        self.token.clone()
+    }
+
+    pub(crate) fn register(&self, handle: TaskHandle) {
+        if let Ok(mut critical) = self.critical.lock() {
+            critical.retain(|h| !h.is_finished());
+            critical.push(handle);
+        }
+    }
+
+    /// Signal the token and wait up to `grace` for the critical tasks, aborting those
+    /// still running after it. Returns whether they all finished in time.
+    pub(crate) async fn run(&self, grace: Duration) -> bool {
+        self.token.cancel();
+        let handles = self.critical.lock().map(|mut critical| std::mem::take(&mut *critical)).unwrap_or_default();
+        let aborts: Vec<AbortHandle> = handles.iter().map(|h| h.abort_handle.clone()).collect();
+        let names: Vec<String> = handles.iter().filter_map(|h| h.name.clone()).collect();
+        if tokio::time::timeout(grace, join_each(handles)).await.is_ok() {
+            return true;
+        }
+        log::warn!("critical tasks still running after {grace:?}, aborting: {}", names.join(", "));
+        aborts.iter().for_each(AbortHandle::abort);
+        false
+    }
+}
+
+/// Await each handle in turn; the slowest one bounds the total.
+async fn join_each(handles: Vec<TaskHandle>) {
+    for handle in handles {
+        handle.join().await;
+    }
 }
 
 impl Drop for TaskTracker {
@@ -104,6 +399,26 @@ impl Drop for TaskTracker {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_shutdown_waits_for_critical_tasks() {
+        let shutdown = Shutdown::default();
+        let token = shutdown.token();
+        let saved = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let done = Arc::clone(&saved);
+        shutdown.register(TaskHandle::from_join_handle(tokio::spawn(async move {
+            token.cancelled().await;
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            done.store(true, std::sync::atomic::Ordering::SeqCst);
+        })));
+        assert!(shutdown.run(Duration::from_secs(1)).await);
+        assert!(saved.load(std::sync::atomic::Ordering::SeqCst));
+
+        // A task ignoring the token is aborted once the grace period is over
+        let stuck = tokio::spawn(std::future::pending::<()>());
+        shutdown.register(TaskHandle::from_join_handle(stuck).named("stuck"));
+        assert!(!shutdown.run(Duration::from_millis(20)).await);
+    }
+
     #[tokio::test]
     async fn test_task_handle_abort() {
         let handle = tokio::spawn(async {
@@ -136,4 +451,72 @@ mod tests {
 
         assert_eq!(tracker.active_count(), 0);
     }
+
+    #[tokio::test]
+    async fn test_join_all_waits_for_named_tasks() {
+        let mut tracker = TaskTracker::new();
+        let (go_tx, go_rx) = tokio::sync::oneshot::channel::<()>();
+        tracker.track(TaskHandle::from_join_handle(tokio::spawn(async { let _ = go_rx.await; })).named("loader"));
+        tracker.track(TaskHandle::from_join_handle(tokio::spawn(async {})));
+        tokio::task::yield_now().await;
+        assert_eq!(tracker.active_names(), vec!["loader".to_string()]);
+
+        let _ = go_tx.send(());
+        tracker.join_all().await;
+        assert!(!tracker.has_active_tasks());
+    }
+
+    #[tokio::test]
+    async fn test_scoped_tracker_lets_tasks_clean_up() {
+        for scoped in [true, false] {
+            let mut tracker = if scoped { TaskTracker::scoped() } else { TaskTracker::new() };
+            let token = tracker.token();
+            let (cleaned_tx, cleaned_rx) = tokio::sync::oneshot::channel();
+            tracker.track(TaskHandle::from_join_handle(tokio::spawn(async move {
+                token.cancelled().await;
+                tokio::task::yield_now().await;
+                let _ = cleaned_tx.send(());
+            })));
+            tokio::task::yield_now().await;
+
+            tracker.abort_all();
+            assert!(!tracker.token().is_cancelled(), "fresh token after cancelling");
+            let cleaned = tokio::time::timeout(tokio::time::Duration::from_millis(100), cleaned_rx).await;
+            assert_eq!(matches!(cleaned, Ok(Ok(()))), scoped);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scoped_tracker_aborts_after_grace() {
+        let mut tracker = TaskTracker::new().with_grace(tokio::time::Duration::from_millis(10));
+        let handle = tokio::spawn(async { loop { tokio::time::sleep(tokio::time::Duration::from_secs(1)).await; } });
+        let abort = handle.abort_handle();
+        tracker.track(TaskHandle::from_join_handle(handle));
+        tracker.abort_all();
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        assert!(abort.is_finished());
+    }
+
+    #[tokio::test]
+    async fn test_spawn_with_progress_reports_and_finishes() {
+        let (cx, _, _) = crate::AppContext::new();
+        let (step_tx, mut step_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (go_tx, go_rx) = tokio::sync::oneshot::channel::<()>();
+        let handle = cx.spawn_with_progress(|_, progress| async move {
+            progress.message("copying");
+            progress.report(1.7);
+            let _ = step_tx.send(());
+            let _ = go_rx.await;
+        });
+        step_rx.recv().await;
+
+        let progress = handle.progress().unwrap();
+        assert_eq!(progress.get(), ProgressState { fraction: 1.0, message: Some("copying".into()), finished: false });
+        handle.report(0.5);
+        assert_eq!(progress.get().fraction, 0.5);
+
+        let _ = go_tx.send(());
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        assert!(progress.get().finished);
+    }
 }
@@ -0,0 +1,348 @@
+//! Headless test harness that drives a component like a user would.
+//!
+//! `Harness` mounts a component on an in-memory terminal, renders it after every
+//! simulated interaction and exposes the screen as text, so behavior tests read as a
+//! script of keystrokes, clicks and expectations instead of hand-built `Event`s.
+//!
+//! Components that spawn tasks need a tokio runtime; use `#[tokio::test]` for those.
+//! Events injected while handling an interaction (`navigate`, `emit_custom`,
+//! `emit_app_event`, `dispatch`) are delivered before the next render, as the event
+//! loop does.
+//!
+//! # Example
+//! ```ignore
+//! let mut harness = Harness::new(Game::new());
+//! harness.type_str("hello<Enter>");
+//! harness.click("board", 3, 4);
+//! harness.expect_text("Score: 1");
+//! ```
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+use ratatui::Terminal;
+use tokio::sync::mpsc;
+
+use crate::component::traits::{Action, Component, Event};
+use crate::{AppContext, Context, Entity};
+
+/// A component mounted on a headless terminal.
+pub struct Harness<C: Component> {
+    app: AppContext,
+    root: Entity<C>,
+    terminal: Terminal<TestBackend>,
+    actions: Vec<Action>,
+    injected: mpsc::UnboundedReceiver<Event>,
+}
+
+/// Rounds of injected events delivered per interaction, so components that emit an
+/// event for every event they handle cannot hang a test.
+const MAX_INJECTED_ROUNDS: usize = 16;
+
+impl<C: Component> Harness<C> {
+    /// Mount `component` on an 80x24 terminal and render it once.
+    pub fn new(component: C) -> Self {
+        Self::with_size(component, 80, 24)
+    }
+
+    /// Mount `component` on a `width`x`height` terminal and render it once.
+    pub fn with_size(component: C, width: u16, height: u16) -> Self {
+        Self::build(width, height, |_| component)
+    }
+
+    /// Mount the component built by `build` from the harness's application context,
+    /// e.g. `Root::new` of a `define_app!` root, on a `width`x`height` terminal.
+    pub fn build(width: u16, height: u16, build: impl FnOnce(&AppContext) -> C) -> Self {
+        let (app, _, injected) = AppContext::new();
+        let root = Entity::new(build(&app));
+        let terminal = Terminal::new(TestBackend::new(width, height)).expect("test backend never fails");
+        let mut harness = Self { app, root, terminal, actions: Vec::new(), injected };
+        let mut cx = harness.context();
+        harness.root.update(|c| {
+            c.on_mount(&mut cx);
+            c.on_enter(&mut cx);
+        }).expect("component lock poisoned");
+        harness.render();
+        harness
+    }
+
+    /// The application context the component runs in.
+    pub fn app(&self) -> &AppContext {
+        &self.app
+    }
+
+    /// Read the component's state.
+    pub fn read<R>(&self, f: impl FnOnce(&C) -> R) -> R {
+        self.root.read(f).expect("component lock poisoned")
+    }
+
+    /// Every action the component returned so far, in order.
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    /// Resize the terminal, call `on_resize` and deliver `Event::Resize`.
+    pub fn resize(&mut self, width: u16, height: u16) -> Option<Action> {
+        self.terminal.backend_mut().resize(width, height);
+        let mut cx = self.context();
+        self.root.update(|c| c.on_resize(width, height, &mut cx)).expect("component lock poisoned");
+        self.send(Event::Resize(width, height))
+    }
+
+    /// Deliver an event and any events injected meanwhile, then re-render. Returns the
+    /// component's action for `event`, if any.
+    pub fn send(&mut self, event: Event) -> Option<Action> {
+        let action = self.handle(event);
+        for _ in 0..MAX_INJECTED_ROUNDS {
+            let injected: Vec<Event> = std::iter::from_fn(|| self.injected.try_recv().ok()).collect();
+            if injected.is_empty() {
+                break;
+            }
+            for event in injected {
+                if self.app.reduce_message(&event) {
+                    continue;
+                }
+                self.handle(event);
+            }
+        }
+        self.render();
+        action
+    }
+
+    fn handle(&mut self, event: Event) -> Option<Action> {
+        let mut cx = self.context();
+        let action = self.root.update(|c| c.handle_event(event, &mut cx)).expect("component lock poisoned");
+        self.actions.extend(action.clone());
+        action
+    }
+
+    /// Press a single key.
+    pub fn press(&mut self, key: KeyEvent) -> Option<Action> {
+        self.send(Event::Key(key))
+    }
+
+    /// Type a key sequence. Plain characters are typed as-is; special keys are written in
+    /// angle brackets, e.g. `"ab<BS>c<Enter>"`, `"<C-s>"`, `"<A-x>"`, `"<S-Tab>"`, `"<lt>"`.
+    /// Returns the actions produced along the way.
+    ///
+    /// # Panics
+    /// On an unknown `<key>` name.
+    pub fn type_str(&mut self, keys: &str) -> Vec<Action> {
+        let keys = parse_keys(keys).unwrap_or_else(|name| panic!("unknown key <{name}> in {keys:?}"));
+        keys.into_iter().filter_map(|key| self.press(key)).collect()
+    }
+
+    /// Left-click at (`column`, `row`) relative to the named region; see `AppContext::region`.
+    ///
+    /// # Panics
+    /// If no region of that name was rendered in the last frame.
+    pub fn click(&mut self, region: &str, column: u16, row: u16) -> Option<Action> {
+        let area = self.region(region);
+        self.send(Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: area.x + column,
+            row: area.y + row,
+            modifiers: KeyModifiers::NONE,
+        }))
+    }
+
+    /// Paste text, delivered as `Event::Paste`.
+    pub fn paste(&mut self, text: &str) -> Option<Action> {
+        self.send(Event::Paste(text.to_string()))
+    }
+
+    /// The area of a region rendered in the last frame.
+    ///
+    /// # Panics
+    /// If no region of that name exists; the message lists the known ones.
+    pub fn region(&self, name: &str) -> Rect {
+        self.app.region(name).unwrap_or_else(|| {
+            let mut known = self.app.region_names();
+            known.sort();
+            panic!("no region named {name:?} in the last frame (known: {known:?})")
+        })
+    }
+
+    /// Render a frame, as the event loop does after each event.
+    pub fn render(&mut self) {
+        let mut cx = self.context();
+        let (root, app) = (&self.root, &self.app);
+        self.terminal
+            .draw(|frame| {
+                app.clear_regions();
+                app.frame_budget().begin_frame();
+                root.update(|c| c.render(frame, &mut cx)).expect("component lock poisoned");
+                app.render_overlays(frame);
+                app.prepare_graphics(frame);
+            })
+            .expect("test backend never fails");
+    }
+
+    /// The screen as text, one line per row with trailing spaces removed.
+    pub fn text(&self) -> String {
+        self.text_in(self.terminal.backend().buffer().area)
+    }
+
+    /// The text of the named region.
+    pub fn region_text(&self, name: &str) -> String {
+        self.text_in(self.region(name))
+    }
+
+    /// Assert that `needle` appears on screen.
+    ///
+    /// # Panics
+    /// If it does not; the message includes the screen.
+    #[track_caller]
+    pub fn expect_text(&self, needle: &str) {
+        let screen = self.text();
+        assert!(screen.contains(needle), "expected {needle:?} on screen:\n{screen}");
+    }
+
+    /// Assert that `needle` does not appear on screen.
+    #[track_caller]
+    pub fn expect_no_text(&self, needle: &str) {
+        let screen = self.text();
+        assert!(!screen.contains(needle), "did not expect {needle:?} on screen:\n{screen}");
+    }
+
+    fn text_in(&self, area: Rect) -> String {
+        let buffer = self.terminal.backend().buffer();
+        (area.top()..area.bottom())
+            .map(|y| {
+                let line: String = (area.left()..area.right()).map(|x| buffer[(x, y)].symbol()).collect();
+                line.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn context(&self) -> Context<C> {
+        Context::new(self.app.clone(), self.root.downgrade())
+    }
+}
+
+/// Parse a key sequence for `Harness::type_str` and `AppContext::register_chord`.
+/// Errors with the unknown key name.
+pub(crate) fn parse_keys(keys: &str) -> Result<Vec<KeyEvent>, String> {
+    let mut events = Vec::new();
+    let mut rest = keys;
+    while let Some(c) = rest.chars().next() {
+        let special = if c == '<' { rest.find('>').map(|end| &rest[1..end]) } else { None };
+        match special {
+            Some(name) => {
+                events.push(parse_special(name).ok_or_else(|| name.to_string())?);
+                rest = &rest[name.len() + 2..];
+            }
+            None => {
+                events.push(char_key(c, KeyModifiers::NONE));
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    Ok(events)
+}
+
+fn char_key(c: char, modifiers: KeyModifiers) -> KeyEvent {
+    let shift = if c.is_uppercase() { KeyModifiers::SHIFT } else { KeyModifiers::NONE };
+    KeyEvent::new(KeyCode::Char(c), modifiers | shift)
+}
+
+fn parse_special(name: &str) -> Option<KeyEvent> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut key = name;
+    while let Some((prefix, tail)) = key.split_once('-').filter(|(_, tail)| !tail.is_empty()) {
+        modifiers |= match prefix {
+            "C" => KeyModifiers::CONTROL,
+            "A" | "M" => KeyModifiers::ALT,
+            "S" => KeyModifiers::SHIFT,
+            _ => return None,
+        };
+        key = tail;
+    }
+    let code = match key.to_ascii_lowercase().as_str() {
+        "enter" | "cr" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        "tab" => KeyCode::Tab,
+        "backtab" => return Some(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT)),
+        "bs" | "backspace" => KeyCode::Backspace,
+        "del" | "delete" => KeyCode::Delete,
+        "space" => KeyCode::Char(' '),
+        "lt" => KeyCode::Char('<'),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pgup" | "pageup" => KeyCode::PageUp,
+        "pgdn" | "pagedown" => KeyCode::PageDown,
+        "insert" => KeyCode::Insert,
+        f if f.len() > 1 && f.starts_with('f') => KeyCode::F(f[1..].parse().ok()?),
+        _ => {
+            let mut chars = key.chars();
+            let c = chars.next()?;
+            return chars.next().is_none().then(|| char_key(c, modifiers));
+        }
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::TextInput;
+    use crate::ChildView;
+
+    #[derive(Default)]
+    struct Counter {
+        score: u32,
+        name: ChildView<TextInput>,
+    }
+
+    impl Component for Counter {
+        fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+            let area = frame.area();
+            frame.buffer_mut().set_string(0, 0, format!("Score: {}", self.score), ratatui::style::Style::default());
+            cx.name_area("board", Rect::new(0, 2, 10, 5));
+            cx.mount_child(&mut self.name, frame, Rect::new(0, 1, area.width, 1));
+        }
+
+        fn handle_event(&mut self, event: Event, cx: &mut Context<Self>) -> Option<Action> {
+            match event {
+                Event::Mouse(mouse) if mouse.row >= 2 => {
+                    self.score += u32::from(mouse.column) + u32::from(mouse.row - 2);
+                    None
+                }
+                Event::Key(key) if key.code == KeyCode::Enter => Some(Action::Navigate(self.name.value().to_string())),
+                event => self.name.handle_event(event, cx),
+            }
+        }
+    }
+
+    #[test]
+    fn test_type_click_and_expect() {
+        let mut harness = Harness::with_size(Counter::default(), 20, 8);
+        harness.expect_text("Score: 0");
+
+        let actions = harness.type_str("helo<BS>lo<lt><Enter>");
+        assert_eq!(actions, vec![Action::Navigate("hello<".into())]);
+        assert_eq!(harness.region_text("text_input"), "hello<");
+
+        harness.click("board", 3, 4);
+        harness.expect_text("Score: 7");
+        assert_eq!(harness.actions().len(), 1);
+    }
+
+    #[test]
+    fn test_parse_keys() {
+        let keys = parse_keys("aB<C-s><S-Tab><F5><A-x><Space>").unwrap();
+        assert_eq!(keys[1], KeyEvent::new(KeyCode::Char('B'), KeyModifiers::SHIFT));
+        assert_eq!(keys[2], KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+        assert_eq!(keys[3], KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT));
+        assert_eq!(keys[4].code, KeyCode::F(5));
+        assert_eq!(keys[5], KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT));
+        assert_eq!(keys[6].code, KeyCode::Char(' '));
+        assert_eq!(parse_keys("<Nope>"), Err("Nope".to_string()));
+    }
+}
@@ -0,0 +1,220 @@
+//! Theme files (`theme-files` feature); see the `theme` module docs for the format.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use super::Theme;
+use crate::task::TaskHandle;
+use crate::AppContext;
+
+/// How often a watched theme file is checked for changes.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Read the themes defined in the file at `path`: JSON if it ends in `.json`, TOML
+/// otherwise.
+pub fn load_themes(path: impl AsRef<Path>) -> io::Result<Vec<Theme>> {
+    let path = path.as_ref();
+    let data = std::fs::read_to_string(path)?;
+    if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+        themes_from_json(&data)
+    } else {
+        themes_from_toml(&data)
+    }
+}
+
+/// Parse themes from TOML: top-level keys for one theme, `[[theme]]` tables for several.
+pub fn themes_from_toml(data: &str) -> io::Result<Vec<Theme>> {
+    build(toml::from_str(data).map_err(invalid)?)
+}
+
+/// Parse themes from JSON: one object, an array of objects or `{ "themes": [...] }`.
+pub fn themes_from_json(data: &str) -> io::Result<Vec<Theme>> {
+    build(serde_json::from_str(data).map_err(invalid)?)
+}
+
+/// The layouts a theme file may use.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ThemeFile {
+    Several {
+        #[serde(alias = "themes")]
+        theme: Vec<ThemeSpec>,
+    },
+    Nested {
+        theme: ThemeSpec,
+    },
+    One(ThemeSpec),
+    List(Vec<ThemeSpec>),
+}
+
+/// One theme as written in a file; roles are checked against `Theme::role_mut`.
+#[derive(Deserialize)]
+struct ThemeSpec {
+    name: String,
+    base: Option<String>,
+    #[serde(flatten)]
+    roles: BTreeMap<String, ColorSpec>,
+}
+
+/// A color name, `#rrggbb`, or a 256-color index.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ColorSpec {
+    Index(u8),
+    Text(String),
+}
+
+/// Turn parsed themes into `Theme`s, resolving `base` against the built-in themes and
+/// those defined earlier.
+fn build(file: ThemeFile) -> io::Result<Vec<Theme>> {
+    let specs = match file {
+        ThemeFile::Several { theme } | ThemeFile::List(theme) => theme,
+        ThemeFile::Nested { theme } | ThemeFile::One(theme) => vec![theme],
+    };
+    let mut themes: Vec<Theme> = Vec::new();
+    for spec in specs {
+        let name = spec.name;
+        let mut theme = match spec.base {
+            Some(base) => themes
+                .iter()
+                .rev()
+                .cloned()
+                .chain(Theme::builtin())
+                .find(|known| known.name.eq_ignore_ascii_case(&base))
+                .ok_or_else(|| invalid(format!("{name}: unknown base theme `{base}`")))?,
+            None => Theme::dark(),
+        };
+        for (key, value) in spec.roles {
+            let role = theme.role_mut(&key).ok_or_else(|| invalid(format!("{name}: unknown role `{key}`")))?;
+            *role = match value {
+                ColorSpec::Index(index) => Color::Indexed(index),
+                ColorSpec::Text(text) => {
+                    Color::from_str(&text).map_err(|_| invalid(format!("{name}: invalid color `{text}` for `{key}`")))?
+                }
+            };
+        }
+        theme.name = name;
+        themes.push(theme);
+    }
+    if themes.is_empty() {
+        return Err(invalid("no themes defined"));
+    }
+    Ok(themes)
+}
+
+fn invalid(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Load the themes in `path` into `app`, making the first one current.
+pub(crate) fn apply_file(app: &AppContext, path: &Path) -> io::Result<()> {
+    let themes = load_themes(path)?;
+    let first = themes[0].clone();
+    for theme in themes {
+        app.register_theme(theme);
+    }
+    app.set_theme(first);
+    Ok(())
+}
+
+/// Reload `path` into `app` whenever its modification time changes. A file that fails
+/// to parse is logged and the current themes are kept.
+pub(crate) fn watch_file(app: &AppContext, path: PathBuf) -> TaskHandle {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|meta| meta.modified()).ok();
+    let mut last: Option<SystemTime> = modified(&path);
+    app.spawn_task(move |app| async move {
+        let mut ticker = tokio::time::interval(WATCH_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let current = modified(&path);
+            if current.is_none() || current == last {
+                continue;
+            }
+            last = current;
+            match apply_file(&app, &path) {
+                Ok(()) => log::info!("reloaded themes from {}", path.display()),
+                Err(err) => log::warn!("keeping current theme; {} is invalid: {err}", path.display()),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toml_themes() {
+        let themes = themes_from_toml(
+            r##"
+            # Two themes; the second builds on the first.
+            [[theme]]
+            name = "Ocean"   # trailing comment
+            base = "nord"
+            primary = "#0096c8"
+            text_muted = 'light gray'
+
+            [[theme]]
+            name = "Deep Ocean"
+            base = "Ocean"
+            background = 17
+            "##,
+        )
+        .unwrap();
+        assert_eq!(themes.len(), 2);
+        assert_eq!(themes[0].primary, Color::Rgb(0, 150, 200));
+        assert_eq!(themes[0].text_muted, Color::White);
+        assert_eq!(themes[0].error, Theme::nord().error);
+        assert_eq!(themes[1].primary, Color::Rgb(0, 150, 200));
+        assert_eq!(themes[1].background, Color::Indexed(17));
+
+        let single = themes_from_toml("name = \"Plain\"\nprimary = \"red\"").unwrap();
+        assert_eq!(single[0].primary, Color::Red);
+        assert_eq!(single[0].text, Theme::dark().text);
+        assert_eq!(themes_from_toml("[theme]\nname = \"Nested\"").unwrap()[0].name, "Nested");
+
+        assert!(themes_from_toml("name = \"X\"\nprimry = \"red\"").is_err());
+        assert!(themes_from_toml("name = \"X\"\nprimary = \"#12\"").is_err());
+        assert!(themes_from_toml("primary = \"red\"").is_err());
+        assert!(themes_from_toml("# nothing").is_err());
+    }
+
+    #[test]
+    fn test_json_themes() {
+        let object = themes_from_json(r##"{ "name": "Ocean", "primary": "#0096c8", "border": 8 }"##).unwrap();
+        assert_eq!(object[0].primary, Color::Rgb(0, 150, 200));
+        assert_eq!(object[0].border, Color::Indexed(8));
+
+        let wrapped = themes_from_json(r#"{ "themes": [ { "name": "A" }, { "name": "B", "base": "A", "error": "magenta" } ] }"#).unwrap();
+        assert_eq!(wrapped.iter().map(|theme| theme.name.as_str()).collect::<Vec<_>>(), ["A", "B"]);
+        assert_eq!(wrapped[1].error, Color::Magenta);
+        assert_eq!(themes_from_json(r#"[{ "name": "A" }]"#).unwrap().len(), 1);
+
+        assert!(themes_from_json(r#"{ "name": "A", "primary": true }"#).is_err());
+        assert!(themes_from_json(r#"{ "name": "A" } extra"#).is_err());
+        assert!(themes_from_json(r#"{ "name": "A""#).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_theme_file_loads_and_reapplies_edits() {
+        let path = std::env::temp_dir().join(format!("rat-nexus-theme-{}.toml", std::process::id()));
+        std::fs::write(&path, "name = \"Mine\"\nprimary = \"green\"\n").unwrap();
+        let (app, _refresh, _inject) = AppContext::new();
+        app.load_theme_file(&path).unwrap();
+        assert_eq!(app.theme().name, "Mine");
+        assert_eq!(app.theme().primary, Color::Green);
+
+        std::fs::write(&path, "name = \"Mine\"\nprimary = \"blue\"\n").unwrap();
+        app.load_theme_file(&path).unwrap();
+        assert_eq!(app.theme().primary, Color::Blue);
+        assert_eq!(app.themes().iter().filter(|theme| theme.name == "Mine").count(), 1);
+        let _ = std::fs::remove_file(&path);
+    }
+}
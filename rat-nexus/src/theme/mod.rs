@@ -0,0 +1,335 @@
+//! Application-wide color theme.
+//!
+//! A `Theme` names colors by role (primary, surface, error, muted text…) rather than by
+//! hue, so components ask for `cx.theme().primary` and follow whatever palette is
+//! active. The current theme is stored on the `AppContext` along with the themes the
+//! user can cycle through: the built-in palettes plus any registered with
+//! `AppContext::register_theme`. Switching at runtime redraws everything and emits a
+//! `THEME_CHANGED` custom event.
+//!
+//! With the `theme-files` feature, themes can also live in a file, loaded with
+//! `Application::theme_file` and reloaded whenever it changes. TOML files hold one theme at the top level or several
+//! `[[theme]]` tables; JSON files hold one object, an array of them or
+//! `{ "themes": [...] }`. Each theme has a `name`, an optional `base` (a built-in theme,
+//! or one earlier in the file, that unspecified roles are taken from; `Dark` by
+//! default) and any of the roles, as `#rrggbb`, a color name or a 256-color index:
+//!
+//! ```toml
+//! [[theme]]
+//! name = "Ocean"
+//! base = "Nord"
+//! primary = "#0096c8"
+//! text_muted = "gray"
+//! ```
+
+use std::sync::{Arc, RwLock};
+
+use ratatui::style::{Color, Modifier, Style};
+
+#[cfg(feature = "theme-files")]
+mod file;
+
+#[cfg(feature = "theme-files")]
+pub use file::{load_themes, themes_from_json, themes_from_toml};
+#[cfg(feature = "theme-files")]
+pub(crate) use file::{apply_file, watch_file};
+
+/// Name of the custom event emitted when the theme changes, carrying the new `Theme`.
+pub const THEME_CHANGED: &str = "rat_nexus::theme_changed";
+
+/// Colors by semantic role.
+///
+/// Build a custom theme from a built-in one:
+///
+/// ```ignore
+/// let ocean = Theme { name: "Ocean".into(), primary: Color::Rgb(0, 150, 200), ..Theme::dark() };
+/// cx.register_theme(ocean);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// Display name; themes are registered and selected by it.
+    pub name: String,
+    /// Main accent: titles, focused borders, the selected item.
+    pub primary: Color,
+    /// Secondary accent for less prominent highlights.
+    pub secondary: Color,
+    /// Window background.
+    pub background: Color,
+    /// Background of panels, bars and popups drawn over the background.
+    pub surface: Color,
+    /// Body text.
+    pub text: Color,
+    /// Hints, labels and disabled text.
+    pub text_muted: Color,
+    /// Text drawn on a `primary` background.
+    pub on_primary: Color,
+    /// Unfocused borders and separators.
+    pub border: Color,
+    /// Background of selected rows.
+    pub selection: Color,
+    /// Positive states.
+    pub success: Color,
+    /// Warnings.
+    pub warning: Color,
+    /// Errors and destructive actions.
+    pub error: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// The default theme, using the terminal's own colors and the 16 named colors.
+    pub fn dark() -> Self {
+        Self {
+            name: "Dark".into(),
+            primary: Color::Cyan,
+            secondary: Color::Blue,
+            background: Color::Reset,
+            surface: Color::Reset,
+            text: Color::White,
+            text_muted: Color::DarkGray,
+            on_primary: Color::Black,
+            border: Color::DarkGray,
+            selection: Color::DarkGray,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+        }
+    }
+
+    /// Dark text on a light background.
+    pub fn light() -> Self {
+        Self {
+            name: "Light".into(),
+            primary: Color::Rgb(0, 95, 175),
+            secondary: Color::Rgb(135, 0, 175),
+            background: Color::Rgb(250, 250, 250),
+            surface: Color::Rgb(235, 235, 235),
+            text: Color::Rgb(30, 30, 30),
+            text_muted: Color::Rgb(110, 110, 110),
+            on_primary: Color::Rgb(255, 255, 255),
+            border: Color::Rgb(170, 170, 170),
+            selection: Color::Rgb(210, 225, 245),
+            success: Color::Rgb(0, 128, 0),
+            warning: Color::Rgb(160, 100, 0),
+            error: Color::Rgb(190, 0, 0),
+        }
+    }
+
+    /// Pure black and white with bright accents, for low vision and projectors.
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "High Contrast".into(),
+            primary: Color::LightYellow,
+            secondary: Color::LightCyan,
+            background: Color::Black,
+            surface: Color::Black,
+            text: Color::White,
+            text_muted: Color::Gray,
+            on_primary: Color::Black,
+            border: Color::White,
+            selection: Color::Blue,
+            success: Color::LightGreen,
+            warning: Color::LightYellow,
+            error: Color::LightRed,
+        }
+    }
+
+    /// The Nord palette.
+    pub fn nord() -> Self {
+        Self {
+            name: "Nord".into(),
+            primary: Color::Rgb(136, 192, 208),
+            secondary: Color::Rgb(129, 161, 193),
+            background: Color::Rgb(46, 52, 64),
+            surface: Color::Rgb(59, 66, 82),
+            text: Color::Rgb(236, 239, 244),
+            text_muted: Color::Rgb(127, 138, 158),
+            on_primary: Color::Rgb(46, 52, 64),
+            border: Color::Rgb(76, 86, 106),
+            selection: Color::Rgb(67, 76, 94),
+            success: Color::Rgb(163, 190, 140),
+            warning: Color::Rgb(235, 203, 139),
+            error: Color::Rgb(191, 97, 106),
+        }
+    }
+
+    /// The Gruvbox dark palette.
+    pub fn gruvbox() -> Self {
+        Self {
+            name: "Gruvbox".into(),
+            primary: Color::Rgb(250, 189, 47),
+            secondary: Color::Rgb(131, 165, 152),
+            background: Color::Rgb(40, 40, 40),
+            surface: Color::Rgb(60, 56, 54),
+            text: Color::Rgb(235, 219, 178),
+            text_muted: Color::Rgb(146, 131, 116),
+            on_primary: Color::Rgb(40, 40, 40),
+            border: Color::Rgb(102, 92, 84),
+            selection: Color::Rgb(80, 73, 69),
+            success: Color::Rgb(184, 187, 38),
+            warning: Color::Rgb(254, 128, 25),
+            error: Color::Rgb(251, 73, 52),
+        }
+    }
+
+    /// Every built-in theme, `dark` first.
+    pub fn builtin() -> Vec<Theme> {
+        vec![Self::dark(), Self::light(), Self::high_contrast(), Self::nord(), Self::gruvbox()]
+    }
+
+    /// The role named `role` as in theme files, such as `"text_muted"`.
+    pub fn role_mut(&mut self, role: &str) -> Option<&mut Color> {
+        Some(match role {
+            "primary" => &mut self.primary,
+            "secondary" => &mut self.secondary,
+            "background" => &mut self.background,
+            "surface" => &mut self.surface,
+            "text" => &mut self.text,
+            "text_muted" => &mut self.text_muted,
+            "on_primary" => &mut self.on_primary,
+            "border" => &mut self.border,
+            "selection" => &mut self.selection,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            _ => return None,
+        })
+    }
+
+    /// Body text on the background.
+    pub fn base(&self) -> Style {
+        Style::default().fg(self.text).bg(self.background)
+    }
+
+    /// Hint and label text.
+    pub fn muted(&self) -> Style {
+        Style::default().fg(self.text_muted)
+    }
+
+    /// Bold text in the primary color, for titles.
+    pub fn title(&self) -> Style {
+        Style::default().fg(self.primary).add_modifier(Modifier::BOLD)
+    }
+
+    /// Borders, in the primary color when focused.
+    pub fn border(&self, focused: bool) -> Style {
+        Style::default().fg(if focused { self.primary } else { self.border })
+    }
+
+    /// A selected row.
+    pub fn selected(&self) -> Style {
+        Style::default().fg(self.text).bg(self.selection)
+    }
+
+    /// A bar or badge filled with the primary color.
+    pub fn bar(&self) -> Style {
+        Style::default().fg(self.on_primary).bg(self.primary)
+    }
+}
+
+impl std::fmt::Display for Theme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+struct ThemesInner {
+    available: Vec<Theme>,
+    current: Theme,
+}
+
+/// The active theme and those available to switch to, owned by the `AppContext`.
+#[derive(Clone)]
+pub(crate) struct Themes {
+    inner: Arc<RwLock<ThemesInner>>,
+}
+
+impl Default for Themes {
+    fn default() -> Self {
+        Self { inner: Arc::new(RwLock::new(ThemesInner { available: Theme::builtin(), current: Theme::default() })) }
+    }
+}
+
+impl Themes {
+    pub(crate) fn current(&self) -> Theme {
+        self.inner.read().map(|inner| inner.current.clone()).unwrap_or_default()
+    }
+
+    pub(crate) fn available(&self) -> Vec<Theme> {
+        self.inner.read().map(|inner| inner.available.clone()).unwrap_or_default()
+    }
+
+    /// Add `theme`, replacing an available theme of the same name; returns the new
+    /// current theme if the replaced one was active.
+    pub(crate) fn register(&self, theme: Theme) -> Option<Theme> {
+        let mut inner = self.inner.write().ok()?;
+        match inner.available.iter_mut().find(|known| known.name == theme.name) {
+            Some(known) => *known = theme.clone(),
+            None => inner.available.push(theme.clone()),
+        }
+        (inner.current.name == theme.name && inner.current != theme).then(|| {
+            inner.current = theme.clone();
+            theme
+        })
+    }
+
+    /// Make `theme` current; returns false if it already was.
+    pub(crate) fn set(&self, theme: Theme) -> bool {
+        let Ok(mut inner) = self.inner.write() else { return false };
+        if inner.current == theme {
+            return false;
+        }
+        inner.current = theme;
+        true
+    }
+
+    pub(crate) fn find(&self, name: &str) -> Option<Theme> {
+        let inner = self.inner.read().ok()?;
+        inner.available.iter().find(|theme| theme.name == name).cloned()
+    }
+
+    /// The available theme after (or, with `step` -1, before) the current one.
+    pub(crate) fn cycle(&self, step: isize) -> Option<Theme> {
+        let inner = self.inner.read().ok()?;
+        let count = inner.available.len() as isize;
+        if count == 0 {
+            return None;
+        }
+        let index = inner.available.iter().position(|theme| theme.name == inner.current.name);
+        let next = match index {
+            Some(index) => (index as isize + step).rem_euclid(count),
+            None => 0,
+        };
+        inner.available.get(next as usize).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_replaces_by_name_and_cycles() {
+        let themes = Themes::default();
+        assert_eq!(themes.current().name, "Dark");
+        assert_eq!(themes.cycle(1).map(|theme| theme.name), Some("Light".into()));
+        assert_eq!(themes.cycle(-1).map(|theme| theme.name), Some("Gruvbox".into()));
+
+        let ocean = Theme { name: "Ocean".into(), primary: Color::Blue, ..Theme::dark() };
+        assert_eq!(themes.register(ocean.clone()), None);
+        assert_eq!(themes.available().len(), Theme::builtin().len() + 1);
+        assert!(themes.set(ocean.clone()));
+        assert!(!themes.set(ocean.clone()));
+        assert_eq!(themes.cycle(1).map(|theme| theme.name), Some("Dark".into()));
+
+        let deeper = Theme { primary: Color::Rgb(0, 0, 120), ..ocean };
+        assert_eq!(themes.register(deeper.clone()), Some(deeper.clone()));
+        assert_eq!(themes.current(), deeper);
+        assert_eq!(themes.available().len(), Theme::builtin().len() + 1);
+    }
+}
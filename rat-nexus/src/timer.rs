@@ -0,0 +1,325 @@
+//! Central timer service behind `set_interval` and `set_timeout`.
+//!
+//! Every timer of an application is driven by one task: a queue of deadlines that
+//! sleeps until the earliest one, runs the callbacks that are due and requests a
+//! single re-render for them. Components use `Context::set_interval` instead of
+//! spawning their own sleep loops; those timers are removed when the component exits
+//! or is dropped. `AppContext::set_interval` timers are not owned by a component and
+//! run until their `TimerHandle` is cancelled.
+//!
+//! Intervals that fall behind (e.g. under a slow frame) skip the missed ticks rather
+//! than firing them in a burst.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+use crate::state::EntityId;
+use crate::AppContext;
+
+/// Runs on each tick; returns false to stop the timer.
+pub(crate) type TimerFn = Box<dyn FnMut(&AppContext) -> bool + Send>;
+
+struct Timer {
+    owner: Option<EntityId>,
+    period: Option<Duration>,
+    deadline: Instant,
+    callback: Arc<Mutex<TimerFn>>,
+}
+
+#[derive(Default)]
+struct TimersInner {
+    next_id: u64,
+    timers: HashMap<u64, Timer>,
+    /// Deadlines by timer id; entries of removed or rescheduled timers are skipped.
+    queue: BinaryHeap<Reverse<(Instant, u64)>>,
+    driving: bool,
+}
+
+/// All pending timers of an application, owned by the `AppContext`.
+#[derive(Clone, Default)]
+pub(crate) struct Timers {
+    inner: Arc<Mutex<TimersInner>>,
+    wake: Arc<Notify>,
+}
+
+impl Timers {
+    /// Schedule `callback` after `delay`, then every `period` if given.
+    pub(crate) fn insert(
+        &self,
+        app: &AppContext,
+        owner: Option<EntityId>,
+        delay: Duration,
+        period: Option<Duration>,
+        callback: TimerFn,
+    ) -> TimerHandle {
+        let Ok(mut inner) = self.inner.lock() else {
+            return TimerHandle::inert(self);
+        };
+        inner.next_id += 1;
+        let id = inner.next_id;
+        let deadline = Instant::now() + delay;
+        let callback = Arc::new(Mutex::new(callback));
+        inner.timers.insert(id, Timer { owner, period, deadline, callback });
+        inner.queue.push(Reverse((deadline, id)));
+        if !inner.driving {
+            if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+                inner.driving = true;
+                runtime.spawn(Self::drive(self.clone(), AppContext::clone(app)));
+            }
+        }
+        drop(inner);
+        self.wake.notify_one();
+        TimerHandle { id, timers: self.clone() }
+    }
+
+    /// Cancel every timer owned by `owner`.
+    pub(crate) fn remove_owner(&self, owner: EntityId) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.timers.retain(|_, timer| timer.owner != Some(owner));
+        }
+    }
+
+    /// Number of pending timers.
+    pub(crate) fn len(&self) -> usize {
+        self.inner.lock().map(|inner| inner.timers.len()).unwrap_or(0)
+    }
+
+    fn remove(&self, id: u64) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.timers.remove(&id);
+        }
+    }
+
+    fn contains(&self, id: u64) -> bool {
+        self.inner.lock().map(|inner| inner.timers.contains_key(&id)).unwrap_or(false)
+    }
+
+    /// The earliest live deadline, dropping stale queue entries on the way.
+    fn next_deadline(&self) -> Option<Instant> {
+        let mut inner = self.inner.lock().ok()?;
+        while let Some(Reverse((deadline, id))) = inner.queue.peek().copied() {
+            if inner.timers.get(&id).is_some_and(|timer| timer.deadline == deadline) {
+                return Some(deadline);
+            }
+            inner.queue.pop();
+        }
+        None
+    }
+
+    /// Timers due at `now`, removed from the queue.
+    fn take_due(&self, now: Instant) -> Vec<(u64, Arc<Mutex<TimerFn>>)> {
+        let Ok(mut inner) = self.inner.lock() else { return Vec::new() };
+        let mut due = Vec::new();
+        while let Some(Reverse((deadline, id))) = inner.queue.peek().copied() {
+            if deadline > now {
+                break;
+            }
+            inner.queue.pop();
+            if let Some(timer) = inner.timers.get(&id).filter(|timer| timer.deadline == deadline) {
+                due.push((id, Arc::clone(&timer.callback)));
+            }
+        }
+        due
+    }
+
+    /// Requeue an interval after it ran, or remove a finished timer.
+    fn reschedule(&self, id: u64, keep: bool, now: Instant) {
+        let Ok(mut inner) = self.inner.lock() else { return };
+        let Some(timer) = inner.timers.get_mut(&id) else { return };
+        match timer.period.filter(|_| keep) {
+            Some(period) => {
+                let mut deadline = timer.deadline + period;
+                if deadline <= now {
+                    deadline = now + period;
+                }
+                timer.deadline = deadline;
+                inner.queue.push(Reverse((deadline, id)));
+            }
+            None => {
+                inner.timers.remove(&id);
+            }
+        }
+    }
+
+    async fn drive(self, app: AppContext) {
+        loop {
+            match self.next_deadline() {
+                Some(deadline) => {
+                    tokio::select! {
+                        _ = tokio::time::sleep_until(deadline) => {}
+                        _ = self.wake.notified() => continue,
+                    }
+                }
+                None => {
+                    self.wake.notified().await;
+                    continue;
+                }
+            }
+
+            let now = Instant::now();
+            let due = self.take_due(now);
+            for (id, callback) in &due {
+                // Callbacks may set or cancel timers, so no lock on the queue is held
+                let keep = callback.lock().map(|mut callback| callback(&app)).unwrap_or(false);
+                self.reschedule(*id, keep, now);
+            }
+            if !due.is_empty() {
+                app.refresh();
+            }
+        }
+    }
+}
+
+/// A handle to a timer set with `set_interval` or `set_timeout`.
+///
+/// Dropping the handle does not cancel the timer; call `cancel`.
+#[derive(Clone)]
+pub struct TimerHandle {
+    id: u64,
+    timers: Timers,
+}
+
+impl TimerHandle {
+    /// A handle to no timer.
+    pub(crate) fn inert(timers: &Timers) -> Self {
+        Self { id: 0, timers: timers.clone() }
+    }
+
+    /// Stop the timer. It does not fire again, even if it is due.
+    pub fn cancel(&self) {
+        self.timers.remove(self.id);
+    }
+
+    /// Whether the timer will still fire: not cancelled, not removed with its
+    /// component, and, for a timeout, not yet fired.
+    pub fn is_active(&self) -> bool {
+        self.timers.contains(self.id)
+    }
+}
+
+impl std::fmt::Debug for TimerHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimerHandle").field("id", &self.id).field("active", &self.is_active()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::{Component, Context, Entity};
+
+    #[derive(Default)]
+    struct Clock {
+        ticks: u32,
+    }
+
+    impl Component for Clock {
+        fn render(&mut self, _frame: &mut ratatui::Frame, _cx: &mut Context<Self>) {}
+    }
+
+    #[tokio::test]
+    async fn test_interval_ticks_until_component_exits() {
+        let (app, _refresh, _inject) = AppContext::new();
+        let clock = Entity::new(Clock::default());
+        let cx = Context::new(AppContext::clone(&app), clock.downgrade());
+        let handle = cx.set_interval(Duration::from_millis(10), |this: &mut Clock, _cx| this.ticks += 1).unwrap();
+
+        tokio::time::sleep(Duration::from_millis(55)).await;
+        let ticks = clock.read(|c| c.ticks).unwrap();
+        assert!(ticks >= 3, "ticked {ticks} times");
+
+        cx.unsubscribe_all();
+        assert!(!handle.is_active());
+        assert_eq!(app.timer_count(), 0);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(clock.read(|c| c.ticks).unwrap(), ticks);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_fires_once_and_cancel_stops_it() {
+        let (app, _refresh, _inject) = AppContext::new();
+        let fired = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&fired);
+        let once = app.set_timeout(Duration::from_millis(50), move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let counter = Arc::clone(&fired);
+        let cancelled = app.set_timeout(Duration::from_millis(60), move |_| {
+            counter.fetch_add(10, Ordering::SeqCst);
+        });
+        cancelled.cancel();
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(fired.load(Ordering::SeqCst), 1);
+        assert!(!once.is_active());
+    }
+
+    #[tokio::test]
+    async fn test_interval_stops_when_component_is_dropped() {
+        let (app, _refresh, _inject) = AppContext::new();
+        let clock = Entity::new(Clock::default());
+        let cx = Context::new(AppContext::clone(&app), clock.downgrade());
+        cx.set_interval(Duration::from_millis(10), |this: &mut Clock, _cx| this.ticks += 1).unwrap();
+        drop(clock);
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(app.timer_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_root_and_children_set_timers() {
+        use crate::{Application, ChildView};
+        use ratatui::layout::Rect;
+
+        #[derive(Default)]
+        struct Alarm {
+            rang: bool,
+        }
+
+        impl Component for Alarm {
+            fn on_mount(&mut self, cx: &mut Context<Self>) {
+                cx.set_timeout(Duration::from_millis(10), |this, _cx| this.rang = true).unwrap();
+            }
+
+            fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+                frame.render_widget(ratatui::widgets::Paragraph::new(self.rang.to_string()), cx.area(frame));
+            }
+        }
+
+        struct Desk {
+            clock: Clock,
+            alarm: ChildView<Alarm>,
+        }
+
+        impl Component for Desk {
+            fn on_mount(&mut self, cx: &mut Context<Self>) {
+                cx.set_interval(Duration::from_millis(10), |this, _cx| this.clock.ticks += 1).unwrap();
+                self.alarm.on_mount(cx);
+            }
+
+            fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+                let ticked = (self.clock.ticks > 0).to_string();
+                frame.render_widget(ratatui::widgets::Paragraph::new(ticked), Rect::new(0, 0, 5, 1));
+                cx.mount_child(&mut self.alarm, frame, Rect::new(0, 1, 5, 1));
+            }
+        }
+
+        let (app, mut app_loop) = Application::new()
+            .into_parts(|cx| cx.set_root(Desk { clock: Clock::default(), alarm: ChildView::new(Alarm::default()) }).map_err(Into::into))
+            .unwrap();
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(5, 2)).unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        app_loop.draw(&mut terminal).unwrap();
+        let buffer = terminal.backend().buffer();
+        let lines: Vec<String> = (0..2).map(|y| (0..5).map(|x| buffer[(x, y)].symbol()).collect::<String>().trim_end().to_string()).collect();
+        assert_eq!(lines, ["true", "true"]);
+        assert_eq!(app.timer_count(), 1);
+    }
+}
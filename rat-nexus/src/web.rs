@@ -0,0 +1,403 @@
+//! Serving an application to web browsers (`web` feature).
+//!
+//! `WebServer` answers plain HTTP requests with a page that runs xterm.js, and the
+//! page's WebSocket connection with an `Application` drawn into that browser terminal,
+//! every one with its own `AppContext` (see `session`). Keys, mouse and window size
+//! travel over the socket and arrive as the usual `Event`s, so the same components
+//! serve a terminal, an SSH client and a browser tab. The connecting `WebClient` is
+//! available as `cx.get::<WebClient>()`.
+//!
+//! The application itself runs natively on the server; only xterm.js runs in the
+//! browser. The page loads xterm.js from a CDN unless replaced with `WebServer::page`.
+//!
+//! There is no WebAssembly build: rat-nexus does not compile for `wasm32-unknown-unknown`
+//! (it needs tokio's multi-threaded runtime, crossterm's event reader and worker
+//! threads), so applications cannot run inside the page with a renderer such as
+//! ratzilla. Serving them from a server is the browser backend until the core is ported.
+//!
+//! The socket protocol is small enough to drive from a custom page: binary messages
+//! carry terminal bytes both ways (typed input to the server, ANSI output to the
+//! browser), and a text message `resize <cols> <rows>` reports the terminal size. The
+//! application starts on the first `resize` and ends when the socket closes.
+//!
+//! # Example
+//! ```ignore
+//! WebServer::new(|cx| {
+//!         cx.set_root(Demo::default())?;
+//!         Ok(())
+//!     })
+//!     .listen("127.0.0.1:8080")
+//!     .await?;
+//! ```
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::session::SessionHandle;
+use crate::{AppContext, Application};
+
+/// The page served to browsers by default: a full-window xterm.js terminal connected
+/// to the server's WebSocket.
+pub const DEFAULT_PAGE: &str = r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>rat-nexus</title>
+<link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/@xterm/xterm@5.5.0/css/xterm.css">
+<script src="https://cdn.jsdelivr.net/npm/@xterm/xterm@5.5.0/lib/xterm.js"></script>
+<script src="https://cdn.jsdelivr.net/npm/@xterm/addon-fit@0.10.0/lib/addon-fit.js"></script>
+<style>html, body, #terminal { margin: 0; height: 100%; background: #000; }</style>
+</head>
+<body>
+<div id="terminal"></div>
+<script>
+const term = new Terminal();
+const fit = new FitAddon.FitAddon();
+term.loadAddon(fit);
+term.open(document.getElementById("terminal"));
+const ws = new WebSocket((location.protocol === "https:" ? "wss://" : "ws://") + location.host + location.pathname);
+ws.binaryType = "arraybuffer";
+const encoder = new TextEncoder();
+const resize = () => ws.send(`resize ${term.cols} ${term.rows}`);
+ws.onopen = () => { fit.fit(); resize(); term.focus(); };
+ws.onmessage = (e) => term.write(new Uint8Array(e.data));
+ws.onclose = () => term.write("\r\n[connection closed]\r\n");
+term.onData((data) => ws.readyState === WebSocket.OPEN && ws.send(encoder.encode(data)));
+term.onResize(() => ws.readyState === WebSocket.OPEN && resize());
+window.addEventListener("resize", () => fit.fit());
+</script>
+</body>
+</html>
+"#;
+
+/// Longest HTTP request head read before giving up on a connection.
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+/// The browser an application serves, shared into its context.
+#[derive(Debug, Clone)]
+pub struct WebClient {
+    /// The browser's address.
+    pub peer: Option<SocketAddr>,
+    /// The path the page's WebSocket connected to.
+    pub path: String,
+}
+
+type AppFn = dyn Fn(&WebClient) -> Application + Send + Sync;
+type SetupFn = dyn Fn(&AppContext) -> anyhow::Result<()> + Send + Sync;
+
+/// What every connection of a server uses.
+struct Shared {
+    application: Box<AppFn>,
+    setup: Box<SetupFn>,
+    page: Cow<'static, str>,
+}
+
+/// Serves an application to browsers, one `Application` per WebSocket connection.
+pub struct WebServer {
+    shared: Shared,
+}
+
+impl WebServer {
+    /// A server running `setup` for every browser, as `Application::run` would.
+    pub fn new<F>(setup: F) -> Self
+    where
+        F: Fn(&AppContext) -> anyhow::Result<()> + Send + Sync + 'static,
+    {
+        Self {
+            shared: Shared {
+                application: Box::new(|_| Application::new()),
+                setup: Box::new(setup),
+                page: Cow::Borrowed(DEFAULT_PAGE),
+            },
+        }
+    }
+
+    /// Build each browser's application with `f` (`Application::new()` by default),
+    /// e.g. to `share` state between browsers. The session is attached afterwards.
+    pub fn application<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&WebClient) -> Application + Send + Sync + 'static,
+    {
+        self.shared.application = Box::new(f);
+        self
+    }
+
+    /// Serve `html` instead of `DEFAULT_PAGE`, e.g. to bundle xterm.js rather than load
+    /// it from a CDN. The page must speak the protocol described in the module docs.
+    pub fn page(mut self, html: impl Into<Cow<'static, str>>) -> Self {
+        self.shared.page = html.into();
+        self
+    }
+
+    /// Accept connections on `addrs` until the listener fails.
+    pub async fn listen(self, addrs: impl ToSocketAddrs) -> io::Result<()> {
+        self.listen_on(TcpListener::bind(addrs).await?).await
+    }
+
+    /// Accept connections on `listener` until it fails.
+    pub async fn listen_on(self, listener: TcpListener) -> io::Result<()> {
+        let shared = Arc::new(self.shared);
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let shared = Arc::clone(&shared);
+            tokio::spawn(async move {
+                if let Err(error) = serve(stream, peer, shared).await {
+                    log::debug!("web connection failed: {error}");
+                }
+            });
+        }
+    }
+}
+
+/// Answer one connection: a WebSocket upgrade runs the application, anything else gets
+/// the page.
+async fn serve(mut stream: TcpStream, peer: SocketAddr, shared: Arc<Shared>) -> io::Result<()> {
+    let head = peek_head(&stream).await?;
+    let Some(path) = websocket_path(&head) else {
+        let body = shared.page.as_bytes();
+        let header = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len(),
+        );
+        stream.write_all(header.as_bytes()).await?;
+        stream.write_all(body).await?;
+        return stream.shutdown().await;
+    };
+    let socket = tokio_tungstenite::accept_async(stream).await.map_err(io::Error::other)?;
+    run(socket, WebClient { peer: Some(peer), path }, shared).await;
+    Ok(())
+}
+
+/// The request head, read without consuming it so the WebSocket handshake can.
+async fn peek_head(stream: &TcpStream) -> io::Result<String> {
+    let mut buffer = vec![0; MAX_REQUEST_HEAD];
+    loop {
+        let n = stream.peek(&mut buffer).await?;
+        if n == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        if let Some(end) = buffer[..n].windows(4).position(|w| w == b"\r\n\r\n") {
+            return Ok(String::from_utf8_lossy(&buffer[..end]).into_owned());
+        }
+        if n == buffer.len() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "request head too long"));
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+}
+
+/// The requested path, if `head` asks for a WebSocket upgrade.
+fn websocket_path(head: &str) -> Option<String> {
+    let mut lines = head.lines();
+    let path = lines.next()?.split_whitespace().nth(1)?.to_string();
+    let upgrade = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("upgrade") && value.trim().eq_ignore_ascii_case("websocket")
+        })
+    });
+    upgrade.then_some(path)
+}
+
+/// Run the application over `socket` once the browser reports its size, closing the
+/// socket when the application ends.
+async fn run<S>(socket: tokio_tungstenite::WebSocketStream<S>, client: WebClient, shared: Arc<Shared>)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let (mut sink, mut stream) = socket.split();
+    let (tx, mut output) = mpsc::unbounded_channel();
+    let mut writer = Some(SocketWriter { tx, buffer: Vec::new() });
+    let mut input: Option<SessionHandle> = None;
+    let mut joined = None;
+
+    loop {
+        tokio::select! {
+            message = stream.next() => match message {
+                Some(Ok(Message::Binary(bytes))) => {
+                    if let Some(input) = &input {
+                        input.input(&bytes);
+                    }
+                }
+                Some(Ok(Message::Text(text))) => {
+                    let Some((width, height)) = parse_resize(&text) else { continue };
+                    match (&input, writer.take()) {
+                        (Some(input), _) => {
+                            input.resize(width, height);
+                        }
+                        (None, Some(writer)) => {
+                            let (session, handle) = crate::session::Session::new(writer, width, height);
+                            let shared = Arc::clone(&shared);
+                            let run = (shared.application)(&client)
+                                .share(client.clone())
+                                .session(session)
+                                .spawn(move |cx| (shared.setup)(cx));
+                            input = Some(handle);
+                            joined = Some(tokio::task::spawn_blocking(move || run.join()));
+                        }
+                        (None, None) => {}
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            bytes = output.recv() => match bytes {
+                Some(bytes) => {
+                    if sink.send(Message::binary(bytes)).await.is_err() {
+                        break;
+                    }
+                }
+                // Every writer is gone: the application has ended
+                None => break,
+            },
+        }
+    }
+
+    // Dropping the input ends the application
+    drop(input);
+    if let Some(joined) = joined {
+        match joined.await {
+            Ok(Ok(Ok(()))) => {}
+            Ok(Ok(Err(error))) => log::warn!("web application failed: {error}"),
+            _ => log::warn!("web application panicked"),
+        }
+    }
+    while let Ok(bytes) = output.try_recv() {
+        let _ = sink.send(Message::binary(bytes)).await;
+    }
+    let _ = sink.close().await;
+}
+
+/// The size in a `resize <cols> <rows>` message.
+fn parse_resize(text: &str) -> Option<(u16, u16)> {
+    let mut words = text.split_whitespace();
+    if words.next()? != "resize" {
+        return None;
+    }
+    let width = words.next()?.parse().ok()?;
+    let height = words.next()?.parse().ok()?;
+    Some((width, height))
+}
+
+/// The application's output, sent to the browser on each flush.
+struct SocketWriter {
+    tx: mpsc::UnboundedSender<Vec<u8>>,
+    buffer: Vec<u8>,
+}
+
+impl Write for SocketWriter {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        self.tx.send(std::mem::take(&mut self.buffer)).map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))
+    }
+}
+
+impl Drop for SocketWriter {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::application::{Context, EventContext};
+    use crate::{Action, Component, Entity, Event};
+    use crossterm::event::KeyCode;
+    use ratatui::Frame;
+    use tokio::io::AsyncReadExt;
+
+    struct Sizes {
+        log: Entity<Vec<String>>,
+    }
+
+    impl Component for Sizes {
+        fn render(&mut self, frame: &mut Frame, _cx: &mut Context<Self>) {
+            frame.render_widget("hello", frame.area());
+        }
+
+        fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+            match event {
+                Event::Resize(width, height) => {
+                    let _ = self.log.update(|log| log.push(format!("{width}x{height}")));
+                    None
+                }
+                Event::Key(key) if key.code == KeyCode::Char('q') => Some(Action::Quit),
+                _ => None,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_serves_the_page_and_one_application_per_socket() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let log = Entity::new(Vec::<String>::new());
+        let shared = log.clone();
+        let server = WebServer::new(|cx| {
+                let log = cx.get::<Entity<Vec<String>>>()?;
+                let client = cx.get::<WebClient>()?;
+                let _ = log.update(|log| log.push(format!("connected to {}", client.path)));
+                cx.set_root(Sizes { log })?;
+                Ok(())
+            })
+            .application(move |_client| Application::new().share(shared.clone()));
+        tokio::spawn(server.listen_on(listener));
+
+        let mut http = TcpStream::connect(addr).await.unwrap();
+        http.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+        let mut page = String::new();
+        http.read_to_string(&mut page).await.unwrap();
+        assert!(page.starts_with("HTTP/1.1 200 OK"));
+        assert!(page.contains("new Terminal()"));
+
+        let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/demo")).await.unwrap();
+        socket.send(Message::text("resize 40 10")).await.unwrap();
+        socket.send(Message::text("resize 60 20")).await.unwrap();
+
+        let mut output = Vec::new();
+        let mut quit = false;
+        while let Some(message) = tokio::time::timeout(Duration::from_secs(10), socket.next()).await.unwrap() {
+            match message.unwrap() {
+                Message::Binary(bytes) => {
+                    output.extend_from_slice(&bytes);
+                    if !quit && String::from_utf8_lossy(&output).contains("hello") {
+                        // Let the resize through before quitting
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        socket.send(Message::binary(&b"q"[..])).await.unwrap();
+                        quit = true;
+                    }
+                }
+                Message::Close(_) => break,
+                _ => {}
+            }
+        }
+        assert!(quit);
+        assert_eq!(log.read(Vec::clone).unwrap(), ["connected to /demo", "60x20"]);
+    }
+
+    #[test]
+    fn test_protocol_parsing() {
+        assert_eq!(parse_resize("resize 80 24"), Some((80, 24)));
+        assert_eq!(parse_resize("resize 80"), None);
+        assert_eq!(parse_resize("hello"), None);
+        let head = "GET /ws HTTP/1.1\r\nHost: x\r\nUpgrade: WebSocket\r\nConnection: Upgrade";
+        assert_eq!(websocket_path(head).as_deref(), Some("/ws"));
+        assert_eq!(websocket_path("GET / HTTP/1.1\r\nHost: x"), None);
+    }
+}
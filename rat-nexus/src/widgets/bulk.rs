@@ -0,0 +1,178 @@
+//! Key-bound bulk actions over a `Selection`, with confirmation.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+
+use super::{ConfirmDialog, Selection};
+
+/// A typed action to apply to several items at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkAction<A> {
+    /// The application-defined action, e.g. `Op::Delete`.
+    pub action: A,
+    /// Indices of the affected items, ascending.
+    pub indices: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Binding<A> {
+    key: KeyCode,
+    modifiers: KeyModifiers,
+    label: String,
+    action: A,
+    confirm: bool,
+}
+
+/// Key bindings for bulk actions and the confirmation currently pending, if any.
+///
+/// Pressing a bound key targets the marked items (or the cursor item if none are
+/// marked). Bindings added with `bind` ask "<label> N items?" first; those added with
+/// `bind_immediate` dispatch right away.
+#[derive(Debug, Clone)]
+pub struct BulkActions<A> {
+    bindings: Vec<Binding<A>>,
+    pending: Option<(BulkAction<A>, ConfirmDialog)>,
+}
+
+impl<A> Default for BulkActions<A> {
+    fn default() -> Self {
+        Self { bindings: Vec::new(), pending: None }
+    }
+}
+
+impl<A: Clone> BulkActions<A> {
+    /// Bind `key` to `action`, asking for confirmation with the item count first.
+    pub fn bind(&mut self, key: KeyCode, label: impl Into<String>, action: A) {
+        self.push(key, label.into(), action, true);
+    }
+
+    /// Bind `key` to `action` without confirmation.
+    pub fn bind_immediate(&mut self, key: KeyCode, label: impl Into<String>, action: A) {
+        self.push(key, label.into(), action, false);
+    }
+
+    /// Whether a confirmation dialog is open.
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// The open confirmation dialog, for rendering.
+    pub fn dialog(&self) -> Option<&ConfirmDialog> {
+        self.pending.as_ref().map(|(_, dialog)| dialog)
+    }
+
+    /// Key hints, e.g. `[("d", "Delete"), ("a", "Archive")]`.
+    pub fn hints(&self) -> Vec<(String, &str)> {
+        self.bindings
+            .iter()
+            .map(|b| (crate::keymap::key_label(&KeyEvent::new(b.key, b.modifiers)), b.label.as_str()))
+            .collect()
+    }
+
+    /// Handle a key. Returns `(consumed, dispatched action)`.
+    pub fn handle_key(&mut self, key: KeyEvent, selection: &Selection) -> (bool, Option<BulkAction<A>>) {
+        if let Some((_, dialog)) = &mut self.pending {
+            return match dialog.handle_key(key) {
+                Some(true) => (true, self.pending.take().map(|(bulk, _)| bulk)),
+                Some(false) => {
+                    self.pending = None;
+                    (true, None)
+                }
+                None => (true, None),
+            };
+        }
+
+        let modifiers = key.modifiers | implied_modifiers(key.code);
+        let Some(binding) = self.bindings.iter().find(|b| b.key == key.code && b.modifiers == modifiers) else {
+            return (false, None);
+        };
+        let indices = selection.targets();
+        if indices.is_empty() {
+            return (true, None);
+        }
+        let bulk = BulkAction { action: binding.action.clone(), indices };
+        if !binding.confirm {
+            return (true, Some(bulk));
+        }
+        let count = bulk.indices.len();
+        let message = format!("{} {} item{}?", binding.label, count, if count == 1 { "" } else { "s" });
+        let dialog = ConfirmDialog::new(message).confirm_label(binding.label.clone()).cancel_label("Cancel");
+        self.pending = Some((bulk, dialog));
+        (true, None)
+    }
+
+    /// Draw the confirmation dialog, if open, centered in `area`.
+    pub fn render_dialog(&self, area: Rect, buf: &mut Buffer) {
+        if let Some(dialog) = self.dialog() {
+            dialog.render(area, buf);
+        }
+    }
+
+    fn push(&mut self, key: KeyCode, label: String, action: A, confirm: bool) {
+        self.bindings.retain(|b| b.key != key);
+        self.bindings.push(Binding { key, modifiers: implied_modifiers(key), label, action, confirm });
+    }
+}
+
+/// Modifiers implied by the key itself: Shift for uppercase letters, which arrive with
+/// it once normalized (see `KeyNormalizer`).
+fn implied_modifiers(key: KeyCode) -> KeyModifiers {
+    match key {
+        KeyCode::Char(c) if c.is_uppercase() => KeyModifiers::SHIFT,
+        _ => KeyModifiers::NONE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Op {
+        Delete,
+        Archive,
+    }
+
+    #[test]
+    fn test_bulk_with_confirmation() {
+        let mut bulk = BulkActions::default();
+        bulk.bind(KeyCode::Char('d'), "Delete", Op::Delete);
+        bulk.bind_immediate(KeyCode::Char('a'), "Archive", Op::Archive);
+        let mut selection = Selection::new(10);
+        selection.toggle(2);
+        selection.toggle(7);
+
+        let (consumed, action) = bulk.handle_key(KeyEvent::from(KeyCode::Char('d')), &selection);
+        assert!(consumed && action.is_none());
+        assert_eq!(bulk.dialog().map(ConfirmDialog::message), Some("Delete 2 items?"));
+        let (_, action) = bulk.handle_key(KeyEvent::from(KeyCode::Char('y')), &selection);
+        assert_eq!(action, Some(BulkAction { action: Op::Delete, indices: vec![2, 7] }));
+        assert!(!bulk.is_pending());
+
+        let (_, action) = bulk.handle_key(KeyEvent::from(KeyCode::Char('a')), &selection);
+        assert_eq!(action.map(|a| a.action), Some(Op::Archive));
+    }
+
+    #[test]
+    fn test_bulk_bindings_need_exact_modifiers() {
+        let mut bulk = BulkActions::default();
+        bulk.bind_immediate(KeyCode::Char('a'), "Archive", Op::Archive);
+        bulk.bind_immediate(KeyCode::Char('D'), "Delete", Op::Delete);
+        let mut selection = Selection::new(3);
+        selection.toggle(1);
+
+        // Ctrl+A is select-all in lists, not a chorded Archive
+        let ctrl_a = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL);
+        assert_eq!(bulk.handle_key(ctrl_a, &selection), (false, None));
+
+        // Uppercase bindings match with or without the Shift a normalized key carries
+        for modifiers in [KeyModifiers::NONE, KeyModifiers::SHIFT] {
+            let (_, action) = bulk.handle_key(KeyEvent::new(KeyCode::Char('D'), modifiers), &selection);
+            assert_eq!(action.map(|a| a.action), Some(Op::Delete));
+        }
+        let alt_d = KeyEvent::new(KeyCode::Char('D'), KeyModifiers::SHIFT | KeyModifiers::ALT);
+        assert_eq!(bulk.handle_key(alt_d, &selection), (false, None));
+    }
+}
@@ -0,0 +1,514 @@
+//! Read-only source code viewer with syntax highlighting and line numbers.
+//!
+//! Highlighting goes through the `Highlighter` trait. The built-in `Keywords`
+//! highlighter colors keywords, strings, numbers and line comments of C-like and
+//! scripting languages without extra dependencies. With the `syntect` feature,
+//! `Syntect` highlights with Sublime Text grammars and themes for grammar-accurate
+//! output, including strings and comments spanning lines.
+
+use crossterm::event::{KeyCode, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use unicode_width::UnicodeWidthStr;
+
+use crate::component::traits::{Action, Component, Event};
+use crate::{Context, EventContext};
+
+/// Rows scrolled per mouse wheel notch.
+const WHEEL_STEP: usize = 3;
+/// Columns scrolled per `←`/`→`.
+const H_STEP: usize = 4;
+
+/// Turns source text into styled lines.
+pub trait Highlighter: Send + Sync {
+    /// One line of styled spans per line of `source`.
+    fn highlight(&self, source: &str) -> Vec<Line<'static>>;
+}
+
+/// Token-based highlighter for keywords, strings, numbers and line comments.
+///
+/// Highlighting is per line: strings and comments spanning lines are not recognized.
+#[derive(Debug, Clone)]
+pub struct Keywords {
+    keywords: Vec<&'static str>,
+    line_comment: &'static str,
+    keyword_style: Style,
+    string_style: Style,
+    number_style: Style,
+    comment_style: Style,
+}
+
+impl Keywords {
+    /// Highlight `keywords`, and comments starting with `line_comment`.
+    pub fn new(keywords: &[&'static str], line_comment: &'static str) -> Self {
+        Self {
+            keywords: keywords.to_vec(),
+            line_comment,
+            keyword_style: Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+            string_style: Style::default().fg(Color::Green),
+            number_style: Style::default().fg(Color::Yellow),
+            comment_style: Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+        }
+    }
+
+    /// Rust keywords.
+    pub fn rust() -> Self {
+        Self::new(
+            &[
+                "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+                "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+                "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+                "use", "where", "while",
+            ],
+            "//",
+        )
+    }
+
+    /// Python keywords.
+    pub fn python() -> Self {
+        Self::new(
+            &[
+                "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif",
+                "else", "except", "False", "finally", "for", "from", "global", "if", "import", "in", "is",
+                "lambda", "None", "nonlocal", "not", "or", "pass", "raise", "return", "True", "try", "while",
+                "with", "yield",
+            ],
+            "#",
+        )
+    }
+
+    /// The highlighter for a file extension, e.g. `"rs"`; `None` if unknown.
+    pub fn for_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "rs" => Some(Self::rust()),
+            "py" => Some(Self::python()),
+            _ => None,
+        }
+    }
+
+    /// Set the styles of keywords, strings, numbers and comments.
+    pub fn styles(mut self, keyword: Style, string: Style, number: Style, comment: Style) -> Self {
+        self.keyword_style = keyword;
+        self.string_style = string;
+        self.number_style = number;
+        self.comment_style = comment;
+        self
+    }
+
+    fn line(&self, text: &str) -> Line<'static> {
+        let mut spans = Vec::new();
+        let mut plain = String::new();
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            let (len, style) = if !self.line_comment.is_empty() && rest.starts_with(self.line_comment) {
+                (rest.len(), self.comment_style)
+            } else if c == '"' || c == '\'' && !is_lifetime(rest) {
+                (string_len(rest, c), self.string_style)
+            } else if c.is_ascii_digit() {
+                (word_len(rest), self.number_style)
+            } else if c.is_alphanumeric() || c == '_' {
+                let len = word_len(rest);
+                if self.keywords.contains(&&rest[..len]) {
+                    (len, self.keyword_style)
+                } else {
+                    plain.push_str(&rest[..len]);
+                    rest = &rest[len..];
+                    continue;
+                }
+            } else {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+                continue;
+            };
+            if !plain.is_empty() {
+                spans.push(Span::raw(std::mem::take(&mut plain)));
+            }
+            spans.push(Span::styled(rest[..len].to_string(), style));
+            rest = &rest[len..];
+        }
+        if !plain.is_empty() {
+            spans.push(Span::raw(plain));
+        }
+        Line::from(spans)
+    }
+}
+
+impl Highlighter for Keywords {
+    fn highlight(&self, source: &str) -> Vec<Line<'static>> {
+        source.lines().map(|line| self.line(line)).collect()
+    }
+}
+
+/// Highlighter using syntect's bundled Sublime Text grammars and themes (`syntect`
+/// feature).
+///
+/// The bundled grammars and themes are loaded once, on first use. Only foreground
+/// colors and font styles are applied; the background comes from `CodeView::style`.
+///
+/// # Example
+/// ```ignore
+/// let view = CodeView::new(&source).highlighter(Syntect::for_extension("rs").unwrap());
+/// ```
+#[cfg(feature = "syntect")]
+#[derive(Debug, Clone)]
+pub struct Syntect {
+    syntax: String,
+    theme: syntect::highlighting::Theme,
+}
+
+#[cfg(feature = "syntect")]
+impl Syntect {
+    /// Theme used unless `theme` or `theme_named` picks another.
+    pub const DEFAULT_THEME: &'static str = "base16-ocean.dark";
+
+    /// The bundled grammar for a file extension, e.g. `"rs"`; `None` if unknown.
+    pub fn for_extension(extension: &str) -> Option<Self> {
+        let syntax = syntect_syntaxes().find_syntax_by_extension(extension)?;
+        Some(Self::with_syntax(&syntax.name))
+    }
+
+    /// The bundled grammar named `name`, e.g. `"Rust"`; `None` if unknown.
+    pub fn for_name(name: &str) -> Option<Self> {
+        let syntax = syntect_syntaxes().find_syntax_by_name(name)?;
+        Some(Self::with_syntax(&syntax.name))
+    }
+
+    fn with_syntax(name: &str) -> Self {
+        let theme = syntect_themes().themes.get(Self::DEFAULT_THEME).cloned().unwrap_or_default();
+        Self { syntax: name.to_string(), theme }
+    }
+
+    /// Color with `theme`.
+    pub fn theme(mut self, theme: syntect::highlighting::Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Color with the bundled theme named `name` (see `syntect::highlighting::ThemeSet`);
+    /// unknown names keep the current theme.
+    pub fn theme_named(mut self, name: &str) -> Self {
+        if let Some(theme) = syntect_themes().themes.get(name) {
+            self.theme = theme.clone();
+        }
+        self
+    }
+}
+
+#[cfg(feature = "syntect")]
+impl Highlighter for Syntect {
+    fn highlight(&self, source: &str) -> Vec<Line<'static>> {
+        use syntect::easy::HighlightLines;
+        use syntect::util::LinesWithEndings;
+
+        let syntaxes = syntect_syntaxes();
+        let syntax = syntaxes.find_syntax_by_name(&self.syntax).unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+        let mut lines = HighlightLines::new(syntax, &self.theme);
+        let trim = |text: &str| text.trim_end_matches(['\n', '\r']).to_string();
+        LinesWithEndings::from(source)
+            .map(|line| match lines.highlight_line(line, syntaxes) {
+                Ok(ranges) => ranges
+                    .into_iter()
+                    .map(|(style, text)| Span::styled(trim(text), syntect_style(style)))
+                    .filter(|span| !span.content.is_empty())
+                    .collect::<Vec<_>>()
+                    .into(),
+                // A grammar failing on one line leaves it plain
+                Err(_) => Line::raw(trim(line)),
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "syntect")]
+fn syntect_syntaxes() -> &'static syntect::parsing::SyntaxSet {
+    static SYNTAXES: std::sync::OnceLock<syntect::parsing::SyntaxSet> = std::sync::OnceLock::new();
+    SYNTAXES.get_or_init(syntect::parsing::SyntaxSet::load_defaults_newlines)
+}
+
+#[cfg(feature = "syntect")]
+fn syntect_themes() -> &'static syntect::highlighting::ThemeSet {
+    static THEMES: std::sync::OnceLock<syntect::highlighting::ThemeSet> = std::sync::OnceLock::new();
+    THEMES.get_or_init(syntect::highlighting::ThemeSet::load_defaults)
+}
+
+/// The ratatui style of a syntect style's foreground and font style.
+#[cfg(feature = "syntect")]
+fn syntect_style(style: syntect::highlighting::Style) -> Style {
+    use syntect::highlighting::FontStyle;
+
+    let fg = style.foreground;
+    let mut out = Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b));
+    if style.font_style.contains(FontStyle::BOLD) {
+        out = out.add_modifier(Modifier::BOLD);
+    }
+    if style.font_style.contains(FontStyle::ITALIC) {
+        out = out.add_modifier(Modifier::ITALIC);
+    }
+    if style.font_style.contains(FontStyle::UNDERLINE) {
+        out = out.add_modifier(Modifier::UNDERLINED);
+    }
+    out
+}
+
+/// Length of the identifier or number at the start of `text`.
+fn word_len(text: &str) -> usize {
+    text.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.' && text.starts_with(|d: char| d.is_ascii_digit())))
+        .unwrap_or(text.len())
+}
+
+/// Length of the string literal opened by `quote` at the start of `text`, up to the
+/// end of the line if it is not closed.
+fn string_len(text: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            '\\' if !escaped => escaped = true,
+            c if c == quote && !escaped => return i + c.len_utf8(),
+            _ => escaped = false,
+        }
+    }
+    text.len()
+}
+
+/// Whether the `'` at the start of `text` begins a Rust lifetime rather than a char.
+fn is_lifetime(text: &str) -> bool {
+    let mut chars = text.chars().skip(1);
+    matches!((chars.next(), chars.next()), (Some(c), Some(next)) if (c.is_alphabetic() || c == '_') && next != '\'')
+}
+
+/// Scrollable, syntax-highlighted view of a source file.
+///
+/// `↑/↓`/`j`/`k`, `PageUp/PageDown`, `Home/End` (`g`/`G`) and the mouse wheel scroll
+/// vertically, `←/→`/`h`/`l` horizontally. Source is highlighted once when set, and
+/// only the visible lines are drawn.
+///
+/// # Example
+/// ```ignore
+/// // in the page struct
+/// preview: ChildView<CodeView>,
+///
+/// // when a file is selected
+/// let view = CodeView::new(&source).highlighter(Keywords::for_extension("rs").unwrap());
+/// self.preview = ChildView::new(view);
+///
+/// // in render
+/// cx.mount_child(&mut self.preview, frame, area);
+/// ```
+pub struct CodeView {
+    source: String,
+    lines: Vec<Line<'static>>,
+    highlighter: Option<Box<dyn Highlighter>>,
+    line_numbers: bool,
+    offset: usize,
+    h_offset: usize,
+    height: usize,
+    area: Option<Rect>,
+    style: Style,
+    gutter_style: Style,
+}
+
+impl CodeView {
+    /// View `source` without highlighting and with line numbers.
+    pub fn new(source: impl Into<String>) -> Self {
+        let mut view = Self {
+            source: String::new(),
+            lines: Vec::new(),
+            highlighter: None,
+            line_numbers: true,
+            offset: 0,
+            h_offset: 0,
+            height: 0,
+            area: None,
+            style: Style::default(),
+            gutter_style: Style::default().fg(Color::DarkGray),
+        };
+        view.set_source(source);
+        view
+    }
+
+    /// Highlight with `highlighter`.
+    pub fn highlighter(mut self, highlighter: impl Highlighter + 'static) -> Self {
+        self.highlighter = Some(Box::new(highlighter));
+        self.rehighlight();
+        self
+    }
+
+    /// Show or hide the line number gutter.
+    pub fn line_numbers(mut self, show: bool) -> Self {
+        self.line_numbers = show;
+        self
+    }
+
+    /// Base style of the code.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Style of the line numbers.
+    pub fn gutter_style(mut self, style: Style) -> Self {
+        self.gutter_style = style;
+        self
+    }
+
+    /// Replace the source, keeping the scroll position where possible.
+    pub fn set_source(&mut self, source: impl Into<String>) {
+        self.source = source.into();
+        self.rehighlight();
+    }
+
+    /// The source shown.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Number of lines.
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Index of the first visible line.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Scroll so that line `index` (0-based) is visible.
+    pub fn scroll_to_line(&mut self, index: usize) {
+        if index < self.offset {
+            self.offset = index;
+        } else if self.height > 0 && index >= self.offset + self.height {
+            self.offset = index + 1 - self.height;
+        }
+        self.clamp();
+    }
+
+    fn rehighlight(&mut self) {
+        self.lines = match &self.highlighter {
+            Some(highlighter) => highlighter.highlight(&self.source),
+            None => self.source.lines().map(|line| Line::raw(line.to_string())).collect(),
+        };
+        self.clamp();
+    }
+
+    fn clamp(&mut self) {
+        self.offset = self.offset.min(self.lines.len().saturating_sub(self.height.max(1)));
+    }
+
+    fn scroll_by(&mut self, delta: isize) {
+        self.offset = self.offset.saturating_add_signed(delta);
+        self.clamp();
+    }
+
+    fn gutter_width(&self) -> u16 {
+        if self.line_numbers { self.lines.len().max(1).to_string().len() as u16 + 1 } else { 0 }
+    }
+}
+
+impl Component for CodeView {
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let area = cx.area(frame);
+        self.area = Some(area);
+        self.height = usize::from(area.height);
+        self.clamp();
+
+        let gutter = self.gutter_width().min(area.width);
+        let code = Rect { x: area.x + gutter, width: area.width - gutter, ..area };
+        let buffer = frame.buffer_mut();
+        buffer.set_style(area, self.style);
+        for (row, (index, line)) in self.lines.iter().enumerate().skip(self.offset).take(self.height).enumerate() {
+            let y = area.y + row as u16;
+            if gutter > 0 {
+                let number = format!("{:>width$} ", index + 1, width = usize::from(gutter - 1));
+                buffer.set_stringn(area.x, y, number, usize::from(gutter), self.gutter_style);
+            }
+            // Skip `h_offset` columns, then draw the remaining spans
+            let mut skip = self.h_offset;
+            let mut x = code.x;
+            for span in &line.spans {
+                if x >= code.right() {
+                    break;
+                }
+                let mut text = span.content.as_ref();
+                while skip > 0 && !text.is_empty() {
+                    let c = text.chars().next().unwrap_or_default();
+                    skip = skip.saturating_sub(UnicodeWidthStr::width(&text[..c.len_utf8()]));
+                    text = &text[c.len_utf8()..];
+                }
+                let style = self.style.patch(span.style);
+                let (end, _) = buffer.set_stringn(x, y, text, usize::from(code.right() - x), style);
+                x = end;
+            }
+        }
+    }
+
+    fn refresh_on_event(&self) -> bool {
+        true
+    }
+
+    fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+        match event {
+            Event::Key(key) => match key.code {
+                KeyCode::Up | KeyCode::Char('k') => self.scroll_by(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.scroll_by(1),
+                KeyCode::PageUp => self.scroll_by(-(self.height.max(2) as isize - 1)),
+                KeyCode::PageDown => self.scroll_by(self.height.max(2) as isize - 1),
+                KeyCode::Home | KeyCode::Char('g') => self.offset = 0,
+                KeyCode::End | KeyCode::Char('G') => self.scroll_to_line(self.lines.len().saturating_sub(1)),
+                KeyCode::Left | KeyCode::Char('h') => self.h_offset = self.h_offset.saturating_sub(H_STEP),
+                KeyCode::Right | KeyCode::Char('l') => self.h_offset += H_STEP,
+                _ => {}
+            },
+            Event::Mouse(mouse) if self.area.is_some_and(|area| area.contains(Position::new(mouse.column, mouse.row))) => {
+                match mouse.kind {
+                    MouseEventKind::ScrollUp => self.scroll_by(-(WHEEL_STEP as isize)),
+                    MouseEventKind::ScrollDown => self.scroll_by(WHEEL_STEP as isize),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+
+    #[test]
+    fn test_keywords_highlight_tokens() {
+        let line = Keywords::rust().line(r#"let s = "a\"b"; // 42 fn"#);
+        let styled: Vec<(&str, bool)> =
+            line.spans.iter().map(|span| (span.content.as_ref(), span.style != Style::default())).collect();
+        assert_eq!(styled, vec![("let", true), (" s = ", false), (r#""a\"b""#, true), ("; ", false), ("// 42 fn", true)]);
+        assert_eq!(Keywords::rust().line("fn f<'a>(x: &'a u8) -> char { 'x' }").spans.len(), 4);
+    }
+
+    #[cfg(feature = "syntect")]
+    #[test]
+    fn test_syntect_highlights_multiline_comments() {
+        let lines = Syntect::for_extension("rs").unwrap().highlight("/* open\nstill comment */ fn f() {}");
+        assert_eq!(lines.len(), 2);
+        let text: String = lines[1].spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(text, "still comment */ fn f() {}");
+        // The comment carried over from the first line is styled apart from the `fn` keyword
+        let style_of = |needle: &str| lines[1].spans.iter().find(|span| span.content.contains(needle)).unwrap().style;
+        assert_ne!(style_of("still"), style_of("fn"));
+        assert!(Syntect::for_extension("no-such-extension").is_none());
+    }
+
+    #[test]
+    fn test_code_view_scrolls() {
+        let source: String = (1..=20).map(|i| format!("let x{i} = {i};\n")).collect();
+        let view = CodeView::new(source).highlighter(Keywords::rust());
+        let mut harness = Harness::with_size(view, 16, 3);
+        assert_eq!(harness.text(), " 1 let x1 = 1;\n 2 let x2 = 2;\n 3 let x3 = 3;");
+
+        harness.type_str("<End>");
+        assert_eq!(harness.text(), "18 let x18 = 18;\n19 let x19 = 19;\n20 let x20 = 20;");
+        harness.type_str("kl");
+        assert_eq!(harness.text(), "17 x17 = 17;\n18 x18 = 18;\n19 x19 = 19;");
+    }
+}
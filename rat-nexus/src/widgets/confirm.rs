@@ -0,0 +1,146 @@
+//! Yes/no confirmation dialog.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget, Wrap};
+
+/// A modal yes/no question, rendered centered over the given area.
+///
+/// `y`/`n`, `Enter` (on the focused button), `Esc` (cancel) and `←`/`→`/`Tab`
+/// (switch buttons) are handled by `handle_key`.
+///
+/// # Example
+/// ```ignore
+/// self.confirm = Some(ConfirmDialog::new("Delete 3 items?").confirm_label("Delete"));
+///
+/// // in handle_event
+/// if let Some(dialog) = &mut self.confirm {
+///     if let Some(yes) = dialog.handle_key(key) {
+///         self.confirm = None;
+///         if yes { self.delete_marked(); }
+///     }
+///     return None;
+/// }
+///
+/// // in render, after the content
+/// if let Some(dialog) = &self.confirm {
+///     frame.render_widget(dialog, area);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmDialog {
+    title: String,
+    message: String,
+    confirm_label: String,
+    cancel_label: String,
+    confirm_focused: bool,
+}
+
+impl ConfirmDialog {
+    /// Create a dialog asking `message`. The cancel button is focused initially.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            title: " Confirm ".to_string(),
+            message: message.into(),
+            confirm_label: "Yes".to_string(),
+            cancel_label: "No".to_string(),
+            confirm_focused: false,
+        }
+    }
+
+    /// Set the dialog title.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = format!(" {} ", title.into());
+        self
+    }
+
+    /// Set the label of the confirm button.
+    pub fn confirm_label(mut self, label: impl Into<String>) -> Self {
+        self.confirm_label = label.into();
+        self
+    }
+
+    /// Set the label of the cancel button.
+    pub fn cancel_label(mut self, label: impl Into<String>) -> Self {
+        self.cancel_label = label.into();
+        self
+    }
+
+    /// The question shown.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Handle a key. Returns `Some(true)` when confirmed, `Some(false)` when cancelled,
+    /// `None` while the dialog stays open.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<bool> {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => Some(true),
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => Some(false),
+            KeyCode::Enter => Some(self.confirm_focused),
+            KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::BackTab => {
+                self.confirm_focused = !self.confirm_focused;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn button(label: &str, focused: bool) -> Span<'_> {
+        let style = if focused {
+            Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        Span::styled(format!(" {label} "), style)
+    }
+}
+
+pub use crate::layout::centered;
+
+impl Widget for &ConfirmDialog {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let buttons = self.confirm_label.len() + self.cancel_label.len() + 8;
+        let width = (self.message.chars().count().max(buttons) + 6).min(60) as u16;
+        let popup = centered(area, width, 6);
+        Clear.render(popup, buf);
+
+        let lines = vec![
+            Line::from(self.message.as_str()),
+            Line::from(""),
+            Line::from(vec![
+                ConfirmDialog::button(&self.confirm_label, self.confirm_focused),
+                Span::raw("  "),
+                ConfirmDialog::button(&self.cancel_label, !self.confirm_focused),
+            ]),
+        ];
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .title(self.title.as_str())
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Yellow)),
+            )
+            .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirm_keys() {
+        let mut dialog = ConfirmDialog::new("Delete?");
+        assert_eq!(dialog.handle_key(KeyEvent::from(KeyCode::Enter)), Some(false));
+        assert_eq!(dialog.handle_key(KeyEvent::from(KeyCode::Left)), None);
+        assert_eq!(dialog.handle_key(KeyEvent::from(KeyCode::Enter)), Some(true));
+        assert_eq!(dialog.handle_key(KeyEvent::from(KeyCode::Char('n'))), Some(false));
+    }
+}
@@ -0,0 +1,162 @@
+//! Single-cell field editors used for inline editing.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+
+use super::TextInput;
+
+/// What kind of value a field holds, which decides how it is edited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Free text.
+    Text,
+    /// A number; only numeric characters are accepted and the value must parse as `f64`.
+    Number,
+    /// One of a fixed set of options, cycled with `←/→` or `↑/↓`.
+    Select(Vec<String>),
+}
+
+/// Result of feeding a key to a `CellEditor`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOutcome {
+    /// Still editing.
+    Pending,
+    /// Enter was pressed; validate and apply `value()`.
+    Commit,
+    /// Esc was pressed; discard the edit.
+    Cancel,
+}
+
+/// Editing state of one field: a `TextInput` (or the chosen option for selects)
+/// and the last validation error.
+#[derive(Debug)]
+pub struct CellEditor {
+    kind: FieldKind,
+    input: TextInput,
+    choice: usize,
+    error: Option<String>,
+}
+
+impl CellEditor {
+    /// Start editing a field currently displaying `initial`.
+    pub fn new(kind: FieldKind, initial: &str) -> Self {
+        let choice = match &kind {
+            FieldKind::Select(options) => options.iter().position(|o| o == initial).unwrap_or(0),
+            _ => 0,
+        };
+        let mut input = TextInput::new().with_value(initial);
+        if kind == FieldKind::Number {
+            input = input.filter(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'));
+        }
+        // Start with the old value selected so typing replaces it
+        input.select_all();
+        Self { kind, input, choice, error: None }
+    }
+
+    /// The field kind.
+    pub fn kind(&self) -> &FieldKind {
+        &self.kind
+    }
+
+    /// The value as currently edited.
+    pub fn value(&self) -> String {
+        match &self.kind {
+            FieldKind::Select(options) => options.get(self.choice).cloned().unwrap_or_default(),
+            _ => self.input.value().to_string(),
+        }
+    }
+
+    /// The last validation error, shown until the value changes.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Report a validation error for the current value.
+    pub fn set_error(&mut self, error: impl Into<String>) {
+        self.error = Some(error.into());
+    }
+
+    /// Check the value against the field kind.
+    pub fn validate(&self) -> Result<(), String> {
+        match &self.kind {
+            FieldKind::Number if self.input.value().trim().parse::<f64>().is_err() => {
+                Err(format!("'{}' is not a number", self.input.value().trim()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Insert pasted text at the cursor. Newlines are dropped.
+    pub fn insert_str(&mut self, text: &str) {
+        self.input.insert_str(text);
+        self.error = None;
+    }
+
+    /// Handle a key while editing.
+    pub fn handle_key(&mut self, key: KeyEvent) -> EditOutcome {
+        match key.code {
+            KeyCode::Enter => return EditOutcome::Commit,
+            KeyCode::Esc => return EditOutcome::Cancel,
+            _ => {}
+        }
+        if let FieldKind::Select(options) = &self.kind {
+            let len = options.len().max(1);
+            match key.code {
+                KeyCode::Left | KeyCode::Up => self.choice = (self.choice + len - 1) % len,
+                KeyCode::Right | KeyCode::Down | KeyCode::Char(' ') => self.choice = (self.choice + 1) % len,
+                _ => {}
+            }
+            return EditOutcome::Pending;
+        }
+        if self.input.handle_key(key) {
+            self.error = None;
+        }
+        EditOutcome::Pending
+    }
+
+    /// Draw the value into a one-row `area`, with the cursor shown reversed.
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, style: Style) {
+        if area.is_empty() {
+            return;
+        }
+        buf.set_style(area, style);
+        if let FieldKind::Select(_) = self.kind {
+            buf.set_stringn(area.x, area.y, format!("‹ {} ›", self.value()), area.width as usize, style);
+            return;
+        }
+        self.input.set_style(style);
+        self.input.draw(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(editor: &mut CellEditor, code: KeyCode) -> EditOutcome {
+        editor.handle_key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn test_number_and_select_editing() {
+        let mut number = CellEditor::new(FieldKind::Number, "42");
+        press(&mut number, KeyCode::End);
+        press(&mut number, KeyCode::Char('x'));
+        press(&mut number, KeyCode::Home);
+        press(&mut number, KeyCode::Char('-'));
+        assert_eq!(number.value(), "-42");
+        assert!(number.validate().is_ok());
+        press(&mut number, KeyCode::End);
+        press(&mut number, KeyCode::Char('-'));
+        assert!(number.validate().is_err());
+        assert_eq!(press(&mut number, KeyCode::Enter), EditOutcome::Commit);
+
+        let options = vec!["low".to_string(), "mid".to_string(), "high".to_string()];
+        let mut select = CellEditor::new(FieldKind::Select(options), "high");
+        press(&mut select, KeyCode::Right);
+        assert_eq!(select.value(), "low");
+        assert_eq!(press(&mut select, KeyCode::Esc), EditOutcome::Cancel);
+    }
+}
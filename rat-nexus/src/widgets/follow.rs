@@ -0,0 +1,183 @@
+//! Follow-tail scrolling for growing lists.
+//!
+//! `FollowState` sticks the viewport to the newest item until the user scrolls up,
+//! keeps the viewport on the same items while paused (even when old items are evicted
+//! from the front), and resumes following on `End` or when the user scrolls back down
+//! to the bottom.
+
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
+
+/// Scroll state with follow-tail behaviour.
+///
+/// Call `sync` once per render with the current item count, the number of items
+/// evicted from the front so far, and the viewport height, then draw
+/// `visible_range()`.
+///
+/// # Example
+/// ```ignore
+/// // in render
+/// self.scroll.sync(self.items.len(), 0, area.height as usize);
+/// let mut list_state = ListState::default().with_offset(self.scroll.offset());
+/// frame.render_stateful_widget(list, area, &mut list_state);
+///
+/// // in handle_event
+/// if let Event::Key(key) = event {
+///     if self.scroll.handle_key(key) { return None; }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FollowState {
+    offset: usize,
+    following: bool,
+    len: usize,
+    evicted: u64,
+    height: usize,
+}
+
+impl Default for FollowState {
+    fn default() -> Self {
+        Self { offset: 0, following: true, len: 0, evicted: 0, height: 0 }
+    }
+}
+
+impl FollowState {
+    /// Create a state that starts out following the tail.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update with the current content.
+    ///
+    /// `evicted` is the total number of items ever dropped from the front (pass 0 for
+    /// lists that only grow); it keeps a paused viewport anchored on the same items.
+    pub fn sync(&mut self, len: usize, evicted: u64, height: usize) {
+        let dropped = usize::try_from(evicted.saturating_sub(self.evicted)).unwrap_or(usize::MAX);
+        self.evicted = evicted;
+        self.len = len;
+        self.height = height;
+        if self.following {
+            self.offset = self.max_offset();
+        } else {
+            self.offset = self.offset.saturating_sub(dropped).min(self.max_offset());
+        }
+    }
+
+    /// First visible item.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Whether the viewport is stuck to the newest item.
+    pub fn is_following(&self) -> bool {
+        self.following
+    }
+
+    /// Range of item indices visible in the viewport.
+    pub fn visible_range(&self) -> std::ops::Range<usize> {
+        self.offset..(self.offset + self.height).min(self.len)
+    }
+
+    /// Number of items below the viewport, e.g. for a "N new" indicator while paused.
+    pub fn unseen(&self) -> usize {
+        self.len.saturating_sub(self.offset + self.height)
+    }
+
+    /// Scroll towards older items, pausing follow mode.
+    pub fn scroll_up(&mut self, lines: usize) {
+        if lines == 0 || self.offset == 0 {
+            return;
+        }
+        self.offset = self.offset.saturating_sub(lines);
+        self.following = false;
+    }
+
+    /// Scroll towards newer items. Reaching the bottom resumes follow mode.
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.offset = (self.offset + lines).min(self.max_offset());
+        if self.offset >= self.max_offset() {
+            self.following = true;
+        }
+    }
+
+    /// Jump to the oldest item, pausing follow mode.
+    pub fn scroll_to_top(&mut self) {
+        self.offset = 0;
+        self.following = self.max_offset() == 0;
+    }
+
+    /// Jump to the newest item and resume following.
+    pub fn scroll_to_bottom(&mut self) {
+        self.offset = self.max_offset();
+        self.following = true;
+    }
+
+    /// Handle navigation keys: arrows, PageUp/PageDown, Home and End.
+    /// Returns true if the key was consumed.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let page = self.height.saturating_sub(1).max(1);
+        match key.code {
+            KeyCode::Up => self.scroll_up(1),
+            KeyCode::Down => self.scroll_down(1),
+            KeyCode::PageUp => self.scroll_up(page),
+            KeyCode::PageDown => self.scroll_down(page),
+            KeyCode::Home => self.scroll_to_top(),
+            KeyCode::End => self.scroll_to_bottom(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Handle mouse wheel scrolling. Returns true if the event was consumed.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> bool {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.scroll_up(3),
+            MouseEventKind::ScrollDown => self.scroll_down(3),
+            _ => return false,
+        }
+        true
+    }
+
+    fn max_offset(&self) -> usize {
+        self.len.saturating_sub(self.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follows_until_scrolled_up() {
+        let mut state = FollowState::new();
+        state.sync(100, 0, 10);
+        assert_eq!(state.offset(), 90);
+
+        state.scroll_up(5);
+        assert!(!state.is_following());
+        state.sync(120, 0, 10);
+        assert_eq!(state.offset(), 85);
+        assert_eq!(state.unseen(), 25);
+
+        state.scroll_down(100);
+        assert!(state.is_following());
+        state.sync(130, 0, 10);
+        assert_eq!(state.offset(), 120);
+    }
+
+    #[test]
+    fn test_paused_view_stays_anchored_on_eviction() {
+        let mut state = FollowState::new();
+        state.sync(50, 0, 10);
+        state.scroll_up(20);
+        assert_eq!(state.offset(), 20);
+
+        // Capacity-bound buffer: 5 new lines pushed, 5 old lines evicted
+        state.sync(50, 5, 10);
+        assert_eq!(state.offset(), 15);
+        assert!(!state.is_following());
+
+        state.handle_key(KeyEvent::from(KeyCode::End));
+        assert!(state.is_following());
+        assert_eq!(state.offset(), 40);
+    }
+}
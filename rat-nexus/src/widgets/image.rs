@@ -0,0 +1,145 @@
+//! Images drawn inside a component area.
+//!
+//! `Image` uses the protocol in `Capabilities::graphics`: Kitty, iTerm2 and sixel
+//! terminals get real pixels, everything else unicode half blocks. The image keeps its
+//! aspect ratio and is anchored at the top-left of the area. The encoding is cached and
+//! redone when the area, the terminal's cell size or the pixels change, so resizing the
+//! terminal re-encodes at the new size.
+
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+
+use crate::component::traits::Component;
+use crate::graphics::{self, Encoded, GraphicsProtocol, Pixels};
+use crate::Context;
+
+/// Displays an RGBA image.
+///
+/// # Example
+/// ```ignore
+/// // in the page struct
+/// logo: ChildView<Image>,
+///
+/// // once decoded, e.g. with the `image` crate
+/// let decoded = image::open("logo.png")?.to_rgba8();
+/// let pixels = Pixels::from_rgba(decoded.width(), decoded.height(), decoded.into_raw()).unwrap();
+/// self.logo = ChildView::new(Image::new(pixels));
+///
+/// // in render
+/// cx.mount_child(&mut self.logo, frame, area);
+/// ```
+pub struct Image {
+    pixels: Pixels,
+    protocol: Option<GraphicsProtocol>,
+    cache: Option<Cache>,
+}
+
+/// An encoding of the image for one area size and protocol.
+struct Cache {
+    key: (GraphicsProtocol, u16, u16, (u16, u16)),
+    cols: u16,
+    rows: u16,
+    scaled: Pixels,
+    encoded: Option<Encoded>,
+}
+
+impl Image {
+    /// Show `pixels` with the terminal's protocol.
+    pub fn new(pixels: Pixels) -> Self {
+        Self { pixels, protocol: None, cache: None }
+    }
+
+    /// Draw with `protocol` instead of the detected one.
+    pub fn protocol(mut self, protocol: GraphicsProtocol) -> Self {
+        self.protocol = Some(protocol);
+        self
+    }
+
+    /// Replace the image.
+    pub fn set_pixels(&mut self, pixels: Pixels) {
+        if pixels != self.pixels {
+            self.pixels = pixels;
+            self.cache = None;
+        }
+    }
+
+    /// The image shown.
+    pub fn pixels(&self) -> &Pixels {
+        &self.pixels
+    }
+
+    /// Scale and encode for `area`, reusing the last encoding if nothing changed.
+    fn encode(&mut self, protocol: GraphicsProtocol, area: Rect) {
+        // Half blocks draw two square-ish pixels per cell
+        let cell = if protocol.is_pixel() { graphics::cell_size() } else { (1, 2) };
+        let key = (protocol, area.width, area.height, cell);
+        if self.cache.as_ref().is_none_or(|cache| cache.key != key) {
+            let (cw, ch) = (u32::from(cell.0), u32::from(cell.1));
+            let (width, height) = self.pixels.fit(u32::from(area.width) * cw, u32::from(area.height) * ch);
+            let cols = width.div_ceil(cw).min(u32::from(area.width)) as u16;
+            let rows = height.div_ceil(ch).min(u32::from(area.height)) as u16;
+            let scaled = self.pixels.resize(width, height);
+            let encoded = (protocol.is_pixel() && cols > 0 && rows > 0)
+                .then(|| Encoded::new(protocol, &scaled, cols, rows));
+            self.cache = Some(Cache { key, cols, rows, scaled, encoded });
+        }
+    }
+}
+
+impl Component for Image {
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let area = cx.area(frame);
+        let protocol = self.protocol.unwrap_or(cx.app().capabilities().graphics);
+        self.encode(protocol, area);
+        let Some(cache) = &self.cache else { return };
+        let target = Rect { width: cache.cols, height: cache.rows, ..area };
+        let buffer = frame.buffer_mut();
+
+        if let Some(encoded) = &cache.encoded {
+            // Keep ratatui from drawing over the pixels
+            for pos in target.positions() {
+                buffer[pos].reset();
+                buffer[pos].set_skip(true);
+            }
+            cx.app().place_graphic(target, encoded.clone());
+            return;
+        }
+
+        let pixel = |x: u32, y: u32| {
+            let [r, g, b, a] = cache.scaled.get(x, y);
+            (a >= 128).then_some(Color::Rgb(r, g, b))
+        };
+        for row in 0..target.height {
+            for col in 0..target.width {
+                let (x, y) = (u32::from(col), u32::from(row) * 2);
+                let top = pixel(x, y);
+                let bottom = if y + 1 < cache.scaled.height() { pixel(x, y + 1) } else { None };
+                let cell = &mut buffer[(target.x + col, target.y + row)];
+                match (top, bottom) {
+                    (Some(top), Some(bottom)) => cell.set_symbol("▀").set_fg(top).set_bg(bottom),
+                    (Some(top), None) => cell.set_symbol("▀").set_fg(top),
+                    (None, Some(bottom)) => cell.set_symbol("▄").set_fg(bottom),
+                    (None, None) => continue,
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+
+    #[test]
+    fn test_half_blocks_keep_aspect_and_follow_resize() {
+        let red = Pixels::from_rgba(4, 4, [255, 0, 0, 255].repeat(16)).unwrap();
+        let image = Image::new(red).protocol(GraphicsProtocol::HalfBlocks);
+        let mut harness = Harness::with_size(image, 10, 2);
+        assert_eq!(harness.text(), "▀▀▀▀\n▀▀▀▀");
+
+        harness.resize(3, 3);
+        assert_eq!(harness.text(), "▀▀▀\n▀▀▀\n");
+        assert_eq!(harness.read(|image| image.cache.as_ref().map(|cache| (cache.cols, cache.rows))), Some((3, 2)));
+    }
+}
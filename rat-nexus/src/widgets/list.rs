@@ -0,0 +1,512 @@
+//! Selectable list with multi-select and bulk actions.
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Widget};
+
+use super::{BulkAction, BulkActions, Selection};
+use crate::Event;
+
+/// Outcome of `ListView::handle_event` / `DataTable::handle_event`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ListEvent<A> {
+    /// Enter was pressed (or the item double-activated) on the item at this index.
+    Activated(usize),
+    /// The cursor moved to the item at this index.
+    Selected(usize),
+    /// A bulk action was dispatched (and confirmed, if required).
+    Bulk(BulkAction<A>),
+    /// The item at `from` was moved to `to` by keyboard or drag.
+    Reordered { from: usize, to: usize },
+    /// A table cell was edited in place and the new value applied.
+    Edited { row: usize, col: usize, value: String },
+}
+
+/// How long a moved row stays highlighted after a reorder.
+pub const REORDER_ANIMATION: Duration = Duration::from_millis(300);
+
+type ReorderFn = Box<dyn FnMut(usize, usize) + Send + Sync>;
+
+/// Navigation, marking, mouse and bulk-action handling shared by list-like widgets.
+#[derive(Debug, Clone)]
+pub(crate) struct ListCore<A> {
+    pub(crate) selection: Selection,
+    pub(crate) bulk: BulkActions<A>,
+    pub(crate) offset: usize,
+    /// Area of the item rows at the last render, for mouse hit-testing.
+    pub(crate) rows_area: Rect,
+}
+
+impl<A> Default for ListCore<A> {
+    fn default() -> Self {
+        Self { selection: Selection::default(), bulk: BulkActions::default(), offset: 0, rows_area: Rect::default() }
+    }
+}
+
+impl<A: Clone> ListCore<A> {
+    /// Handle an event, reporting a cursor move as `ListEvent::Selected` when nothing
+    /// else happened.
+    pub(crate) fn handle_event(&mut self, event: &Event, len: usize) -> Option<ListEvent<A>> {
+        self.selection.set_len(len);
+        let before = self.selection.cursor();
+        let outcome = self.handle_input(event);
+        match self.selection.cursor() {
+            cursor if outcome.is_none() && cursor != before => cursor.map(ListEvent::Selected),
+            _ => outcome,
+        }
+    }
+
+    fn handle_input(&mut self, event: &Event) -> Option<ListEvent<A>> {
+        match event {
+            Event::Key(key) => {
+                let (consumed, bulk) = self.bulk.handle_key(*key, &self.selection);
+                if consumed {
+                    return bulk.map(ListEvent::Bulk);
+                }
+                let page = (self.rows_area.height as isize - 1).max(1);
+                match key.code {
+                    KeyCode::Enter => return self.selection.cursor().map(ListEvent::Activated),
+                    KeyCode::PageUp => self.selection.move_by(-page),
+                    KeyCode::PageDown => self.selection.move_by(page),
+                    _ => {
+                        self.selection.handle_key(*key);
+                    }
+                }
+                None
+            }
+            Event::Mouse(mouse) if !self.bulk.is_pending() => {
+                match mouse.kind {
+                    MouseEventKind::Down(MouseButton::Left) => {
+                        let index = self.row_at(mouse.column, mouse.row)?;
+                        if mouse.modifiers.contains(KeyModifiers::CONTROL) {
+                            self.selection.toggle(index);
+                        } else if mouse.modifiers.contains(KeyModifiers::SHIFT) {
+                            self.selection.extend_to(index);
+                        } else {
+                            self.selection.select(index);
+                        }
+                    }
+                    MouseEventKind::ScrollUp => self.selection.move_by(-3),
+                    MouseEventKind::ScrollDown => self.selection.move_by(3),
+                    _ => {}
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Adjust the scroll offset so the cursor is visible in `height` rows.
+    pub(crate) fn scroll_into_view(&mut self, len: usize, height: usize) {
+        self.selection.set_len(len);
+        let cursor = self.selection.cursor().unwrap_or(0);
+        if cursor < self.offset {
+            self.offset = cursor;
+        } else if height > 0 && cursor >= self.offset + height {
+            self.offset = cursor + 1 - height;
+        }
+        self.offset = self.offset.min(len.saturating_sub(height));
+    }
+
+    pub(crate) fn row_at(&self, column: u16, row: u16) -> Option<usize> {
+        if !self.rows_area.contains(Position::new(column, row)) {
+            return None;
+        }
+        let index = self.offset + usize::from(row - self.rows_area.y);
+        (index < self.selection.len()).then_some(index)
+    }
+
+    /// Index of the visible row nearest to terminal row `row`, for drags that leave the area.
+    pub(crate) fn nearest_row(&self, row: u16) -> Option<usize> {
+        if self.rows_area.is_empty() || self.selection.is_empty() {
+            return None;
+        }
+        let row = row.clamp(self.rows_area.y, self.rows_area.bottom() - 1);
+        let index = self.offset + usize::from(row - self.rows_area.y);
+        Some(index.min(self.selection.len() - 1))
+    }
+}
+
+/// Styles shared by list-like widgets.
+pub(crate) fn row_style(base: Style, highlight: Style, is_cursor: bool, is_marked: bool) -> Style {
+    let style = if is_marked { base.fg(Color::Yellow) } else { base };
+    if is_cursor { style.patch(highlight) } else { style }
+}
+
+/// A scrollable list with a cursor, multi-item marking and key-bound bulk actions.
+///
+/// Items are owned by the view. `Space` marks items, `Shift+↑/↓` marks ranges,
+/// `Ctrl+A` marks all, `Ctrl+click` toggles; see `Selection` for the full key set.
+///
+/// Only the visible rows are drawn, so lists of tens of thousands of items stay
+/// cheap. Cursor moves are reported as `ListEvent::Selected`, `Enter` as
+/// `ListEvent::Activated`.
+///
+/// Lists made `reorderable` also move the cursor item with `Alt+↑/↓` (or `K`/`J`)
+/// and by dragging it with the mouse; the moved row is briefly highlighted.
+///
+/// # Example
+/// ```ignore
+/// #[derive(Clone)]
+/// enum MailOp { Delete, Archive }
+///
+/// let mut inbox = ListView::new(messages)
+///     .bind(KeyCode::Char('d'), "Delete", MailOp::Delete)
+///     .bind_immediate(KeyCode::Char('a'), "Archive", MailOp::Archive);
+///
+/// // in handle_event
+/// match inbox.handle_event(&event) {
+///     Some(ListEvent::Bulk(BulkAction { action: MailOp::Delete, indices })) => {
+///         inbox.remove(&indices);
+///     }
+///     Some(ListEvent::Activated(i)) => return Some(Action::Navigate(format!("mail/{i}"))),
+///     _ => {}
+/// }
+///
+/// // in render
+/// inbox.render(area, frame.buffer_mut(), |msg| Line::from(msg.subject.as_str()));
+/// ```
+pub struct ListView<T, A = ()> {
+    items: Vec<T>,
+    core: ListCore<A>,
+    block: Option<Block<'static>>,
+    style: Style,
+    highlight_style: Style,
+    reorderable: bool,
+    on_reorder: Option<ReorderFn>,
+    drag: Option<usize>,
+    moved: Option<(usize, usize, Instant)>,
+}
+
+impl<T, A: Clone> ListView<T, A> {
+    /// Create a list over `items`.
+    pub fn new(items: Vec<T>) -> Self {
+        let mut core = ListCore::default();
+        core.selection.set_len(items.len());
+        Self {
+            items,
+            core,
+            block: None,
+            style: Style::default(),
+            highlight_style: Style::default().add_modifier(Modifier::REVERSED),
+            reorderable: false,
+            on_reorder: None,
+            drag: None,
+            moved: None,
+        }
+    }
+
+    /// Allow moving items with `Alt+↑/↓`, `K`/`J` and mouse drag.
+    pub fn reorderable(mut self) -> Self {
+        self.reorderable = true;
+        self
+    }
+
+    /// Call `f(from, to)` after every reorder. Implies `reorderable`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let queue = self.queue.clone();
+    /// ListView::new(tracks).on_reorder(move |from, to| {
+    ///     let _ = queue.update(|q| q.move_track(from, to));
+    /// })
+    /// ```
+    pub fn on_reorder<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(usize, usize) + Send + Sync + 'static,
+    {
+        self.reorderable = true;
+        self.on_reorder = Some(Box::new(f));
+        self
+    }
+
+    /// Wrap the list in a block.
+    pub fn block(mut self, block: Block<'static>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Set the base item style.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style patched onto the cursor row.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Bind a bulk action that asks for confirmation with the item count.
+    pub fn bind(mut self, key: KeyCode, label: impl Into<String>, action: A) -> Self {
+        self.core.bulk.bind(key, label, action);
+        self
+    }
+
+    /// Bind a bulk action that dispatches without confirmation.
+    pub fn bind_immediate(mut self, key: KeyCode, label: impl Into<String>, action: A) -> Self {
+        self.core.bulk.bind_immediate(key, label, action);
+        self
+    }
+
+    /// The items.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Mutable access to the items. The selection is re-clamped on the next event or render.
+    pub fn items_mut(&mut self) -> &mut Vec<T> {
+        &mut self.items
+    }
+
+    /// Replace the items, clearing marks.
+    pub fn set_items(&mut self, items: Vec<T>) {
+        self.items = items;
+        self.core.selection.clear_marks();
+        self.core.selection.set_len(self.items.len());
+    }
+
+    /// Remove the items at `indices` (e.g. from a `BulkAction`), returning them.
+    pub fn remove(&mut self, indices: &[usize]) -> Vec<T> {
+        self.core.selection.set_len(self.items.len());
+        let mut sorted: Vec<usize> = indices.iter().copied().filter(|&i| i < self.items.len()).collect();
+        sorted.sort_unstable();
+        sorted.dedup();
+        let removed = sorted.iter().rev().map(|&i| self.items.remove(i)).collect::<Vec<_>>();
+        self.core.selection.remove_indices(&sorted);
+        removed.into_iter().rev().collect()
+    }
+
+    /// The selection model.
+    pub fn selection(&self) -> &Selection {
+        &self.core.selection
+    }
+
+    /// Mutable access to the selection model.
+    pub fn selection_mut(&mut self) -> &mut Selection {
+        &mut self.core.selection
+    }
+
+    /// The item under the cursor.
+    pub fn selected_item(&self) -> Option<&T> {
+        self.core.selection.cursor().and_then(|i| self.items.get(i))
+    }
+
+    /// Bulk action key hints, e.g. for a footer.
+    pub fn hints(&self) -> Vec<(String, &str)> {
+        self.core.bulk.hints()
+    }
+
+    /// Whether a confirmation dialog is open (the list then captures all keys).
+    pub fn is_confirming(&self) -> bool {
+        self.core.bulk.is_pending()
+    }
+
+    /// Move the item at `from` to `to`, keeping marks and the cursor on their items.
+    ///
+    /// Returns `None` if either index is out of range or they are equal.
+    pub fn move_item(&mut self, from: usize, to: usize) -> Option<ListEvent<A>> {
+        let len = self.items.len();
+        if from >= len || to >= len || from == to {
+            return None;
+        }
+        let item = self.items.remove(from);
+        self.items.insert(to, item);
+        self.core.selection.set_len(len);
+        self.core.selection.move_item(from, to);
+        self.moved = Some((from, to, Instant::now()));
+        if let Some(on_reorder) = &mut self.on_reorder {
+            on_reorder(from, to);
+        }
+        Some(ListEvent::Reordered { from, to })
+    }
+
+    /// Whether a reorder highlight is still fading; keep refreshing while true.
+    pub fn is_animating(&self) -> bool {
+        self.moved.is_some_and(|(_, _, at)| at.elapsed() < REORDER_ANIMATION)
+    }
+
+    /// Handle a key or mouse event.
+    pub fn handle_event(&mut self, event: &Event) -> Option<ListEvent<A>> {
+        if self.reorderable && !self.core.bulk.is_pending() {
+            if let Some(handled) = self.handle_reorder(event) {
+                return handled;
+            }
+        }
+        self.core.handle_event(event, self.items.len())
+    }
+
+    /// Reorder keys and drags. `Some(_)` means the event was consumed.
+    fn handle_reorder(&mut self, event: &Event) -> Option<Option<ListEvent<A>>> {
+        let cursor = self.core.selection.cursor()?;
+        match event {
+            Event::Key(key) => {
+                let delta = reorder_delta(key)?;
+                let to = cursor.checked_add_signed(delta).filter(|&to| to < self.items.len());
+                Some(to.and_then(|to| self.move_item(cursor, to)))
+            }
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) if mouse.modifiers.is_empty() => {
+                    self.drag = self.core.row_at(mouse.column, mouse.row);
+                    None
+                }
+                MouseEventKind::Drag(MouseButton::Left) => {
+                    let from = self.drag?;
+                    let to = self.core.nearest_row(mouse.row)?;
+                    self.drag = Some(to);
+                    Some(self.move_item(from, to))
+                }
+                MouseEventKind::Up(MouseButton::Left) => {
+                    self.drag = None;
+                    None
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Render the list into `area`, drawing each item with `item`.
+    pub fn render<F>(&mut self, area: Rect, buf: &mut Buffer, item: F)
+    where
+        F: for<'a> Fn(&'a T) -> Line<'a>,
+    {
+        let inner = match &self.block {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.clone().render(area, buf);
+                inner
+            }
+            None => area,
+        };
+        self.core.rows_area = inner;
+        self.core.scroll_into_view(self.items.len(), inner.height as usize);
+
+        let cursor = self.core.selection.cursor();
+        let moved = self.moved.filter(|&(_, _, at)| at.elapsed() < REORDER_ANIMATION);
+        for (row, index) in (self.core.offset..self.items.len()).take(inner.height as usize).enumerate() {
+            let y = inner.y + row as u16;
+            let marked = self.core.selection.is_marked(index);
+            let mut style = row_style(self.style, self.highlight_style, cursor == Some(index), marked);
+            let mut gutter = if marked { "● " } else { "  " };
+            if let Some((from, to, _)) = moved.filter(|&(_, to, _)| to == index) {
+                style = style.add_modifier(Modifier::BOLD).fg(Color::Cyan);
+                gutter = if to < from { "↑ " } else { "↓ " };
+            } else if self.drag == Some(index) {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            let row_area = Rect::new(inner.x, y, inner.width, 1);
+            buf.set_style(row_area, style);
+            let (x, _) = buf.set_stringn(inner.x, y, gutter, inner.width as usize, style);
+            let remaining = inner.right().saturating_sub(x);
+            buf.set_line(x, y, &item(&self.items[index]).patch_style(style), remaining);
+        }
+
+        self.core.bulk.render_dialog(area, buf);
+    }
+}
+
+/// Direction of a reorder key, if `key` is one.
+fn reorder_delta(key: &KeyEvent) -> Option<isize> {
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    match key.code {
+        KeyCode::Up if alt => Some(-1),
+        KeyCode::Down if alt => Some(1),
+        KeyCode::Char('K') => Some(-1),
+        KeyCode::Char('J') => Some(1),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyEvent;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn test_list_bulk_delete() {
+        let mut list = ListView::new(vec!["a", "b", "c", "d"]).bind(KeyCode::Char('d'), "Delete", "delete");
+        list.handle_event(&key(KeyCode::Char(' ')));
+        list.handle_event(&key(KeyCode::Down));
+        list.handle_event(&key(KeyCode::Char(' ')));
+        assert_eq!(list.selection().marked(), vec![0, 2]);
+
+        assert_eq!(list.handle_event(&key(KeyCode::Char('d'))), None);
+        assert!(list.is_confirming());
+        match list.handle_event(&key(KeyCode::Char('y'))) {
+            Some(ListEvent::Bulk(bulk)) => {
+                assert_eq!(list.remove(&bulk.indices), vec!["a", "c"]);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert_eq!(list.items(), &["b", "d"]);
+        assert_eq!(list.handle_event(&key(KeyCode::Enter)), Some(ListEvent::Activated(1)));
+    }
+
+    #[test]
+    fn test_list_reorder_keys_and_drag() {
+        use crossterm::event::MouseEvent;
+        use std::sync::{Arc, Mutex};
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let sink = log.clone();
+        let mut list: ListView<&str> = ListView::new(vec!["a", "b", "c", "d"])
+            .on_reorder(move |from, to| sink.lock().unwrap().push((from, to)));
+
+        let alt_down = Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::ALT));
+        assert_eq!(list.handle_event(&alt_down), Some(ListEvent::Reordered { from: 0, to: 1 }));
+        assert_eq!(list.items(), &["b", "a", "c", "d"]);
+        assert_eq!(list.selection().cursor(), Some(1));
+        assert!(list.is_animating());
+
+        let area = Rect::new(0, 0, 10, 4);
+        list.render(area, &mut Buffer::empty(area), |s| Line::from(*s));
+        let mouse = |kind, row| Event::Mouse(MouseEvent { kind, column: 3, row, modifiers: KeyModifiers::NONE });
+        list.handle_event(&mouse(MouseEventKind::Down(MouseButton::Left), 3));
+        list.handle_event(&mouse(MouseEventKind::Drag(MouseButton::Left), 2));
+        list.handle_event(&mouse(MouseEventKind::Drag(MouseButton::Left), 0));
+        list.handle_event(&mouse(MouseEventKind::Up(MouseButton::Left), 0));
+        assert_eq!(list.items(), &["d", "b", "a", "c"]);
+        assert_eq!(*log.lock().unwrap(), vec![(0, 1), (3, 2), (2, 0)]);
+    }
+
+    #[test]
+    fn test_list_render_scrolls_to_cursor() {
+        let mut list: ListView<String> = ListView::new((0..20).map(|i| format!("item {i}")).collect());
+        list.selection_mut().select(15);
+        let area = Rect::new(0, 0, 12, 5);
+        let mut buf = Buffer::empty(area);
+        list.render(area, &mut buf, |s| Line::from(s.as_str()));
+        let last: String = (2..9).map(|x| buf[(x, 4)].symbol().to_string()).collect();
+        assert_eq!(last, "item 15");
+    }
+
+    #[test]
+    fn test_list_virtualizes_and_reports_selection() {
+        let mut list: ListView<usize> = ListView::new((0..10_000).collect());
+        let area = Rect::new(0, 0, 12, 5);
+        let drawn = std::sync::atomic::AtomicUsize::new(0);
+        list.render(area, &mut Buffer::empty(area), |i| {
+            drawn.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Line::from(i.to_string())
+        });
+        assert_eq!(drawn.into_inner(), 5);
+
+        assert_eq!(list.handle_event(&key(KeyCode::Char('j'))), Some(ListEvent::Selected(1)));
+        assert_eq!(list.handle_event(&key(KeyCode::Char(' '))), Some(ListEvent::Selected(2)));
+        assert_eq!(list.handle_event(&key(KeyCode::End)), Some(ListEvent::Selected(9_999)));
+        assert_eq!(list.handle_event(&key(KeyCode::End)), None);
+        assert_eq!(list.selection().marked(), vec![1]);
+    }
+}
@@ -0,0 +1,186 @@
+//! Scrolling log view with follow-tail mode.
+
+use std::collections::VecDeque;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, StatefulWidget, Widget};
+
+use super::FollowState;
+use crate::Density;
+
+/// Bounded line buffer for `LogTail`. The oldest lines are evicted once full.
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    lines: VecDeque<String>,
+    capacity: usize,
+    evicted: u64,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(10_000)
+    }
+}
+
+impl LogBuffer {
+    /// Create a buffer keeping at most `capacity` lines.
+    pub fn new(capacity: usize) -> Self {
+        Self { lines: VecDeque::new(), capacity: capacity.max(1), evicted: 0 }
+    }
+
+    /// Append a line, evicting the oldest one if the buffer is full.
+    pub fn push(&mut self, line: impl Into<String>) {
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+            self.evicted += 1;
+        }
+        self.lines.push_back(line.into());
+    }
+
+    /// Number of buffered lines.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Whether the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Total number of lines evicted so far.
+    pub fn evicted(&self) -> u64 {
+        self.evicted
+    }
+
+    /// Iterate over the buffered lines, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().map(String::as_str)
+    }
+
+    /// Remove all lines.
+    pub fn clear(&mut self) {
+        self.evicted += self.lines.len() as u64;
+        self.lines.clear();
+    }
+}
+
+/// A log view that sticks to the newest line unless the user scrolls up.
+/// While paused, a "N new" indicator is shown in the bottom-right corner.
+///
+/// # Example
+/// ```ignore
+/// let tail = LogTail::new(&self.log).block(Block::bordered().title("Log"));
+/// frame.render_stateful_widget(tail, area, &mut self.scroll);
+/// ```
+pub struct LogTail<'a> {
+    buffer: &'a LogBuffer,
+    block: Option<Block<'a>>,
+    style: Style,
+    indicator_style: Style,
+    density: Density,
+}
+
+impl<'a> LogTail<'a> {
+    /// Create a view over `buffer`.
+    pub fn new(buffer: &'a LogBuffer) -> Self {
+        Self {
+            buffer,
+            block: None,
+            style: Style::default(),
+            indicator_style: Style::default().fg(Color::Black).bg(Color::Yellow),
+            density: Density::default(),
+        }
+    }
+
+    /// Wrap the view in a block.
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Set the text style.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the density, which controls padding inside the block.
+    pub fn density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Set the style of the "N new" indicator.
+    pub fn indicator_style(mut self, style: Style) -> Self {
+        self.indicator_style = style;
+        self
+    }
+}
+
+impl StatefulWidget for LogTail<'_> {
+    type State = FollowState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let inner = match &self.block {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.clone().render(area, buf);
+                Block::default().padding(self.density.padding()).inner(inner)
+            }
+            None => area,
+        };
+        if inner.is_empty() {
+            return;
+        }
+
+        state.sync(self.buffer.len(), self.buffer.evicted(), inner.height as usize);
+        let range = state.visible_range();
+        let lines: Vec<Line> = self
+            .buffer
+            .iter()
+            .skip(range.start)
+            .take(range.len())
+            .map(Line::raw)
+            .collect();
+        Paragraph::new(lines).style(self.style).render(inner, buf);
+
+        let unseen = state.unseen();
+        if !state.is_following() && unseen > 0 {
+            let label = format!(" ↓ {unseen} new ");
+            let width = (label.chars().count() as u16).min(inner.width);
+            let x = inner.right() - width;
+            let y = inner.bottom() - 1;
+            buf.set_stringn(x, y, &label, width as usize, self.indicator_style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_follows_and_pauses() {
+        let mut log = LogBuffer::new(100);
+        for i in 0..20 {
+            log.push(format!("line {i}"));
+        }
+        let area = Rect::new(0, 0, 20, 5);
+        let mut state = FollowState::new();
+        let mut buf = Buffer::empty(area);
+        LogTail::new(&log).render(area, &mut buf, &mut state);
+        let last_row: String = (0..7).map(|x| buf[(x, 4)].symbol().to_string()).collect();
+        assert_eq!(last_row, "line 19");
+
+        state.scroll_up(10);
+        log.push("line 20");
+        let mut buf = Buffer::empty(area);
+        LogTail::new(&log).render(area, &mut buf, &mut state);
+        let first_row: String = (0..6).map(|x| buf[(x, 0)].symbol().to_string()).collect();
+        assert_eq!(first_row, "line 5");
+        assert_eq!(state.unseen(), 11);
+    }
+}
@@ -0,0 +1,49 @@
+//! Reusable widgets and the state types that drive them.
+//!
+//! Widgets here are plain ratatui `Widget`/`StatefulWidget`s; their state lives in the
+//! owning component (or an `Entity`) and is updated from `handle_event`.
+
+pub mod bulk;
+pub mod code_view;
+pub mod confirm;
+pub mod edit;
+pub mod follow;
+pub mod image;
+pub mod list;
+pub mod log_tail;
+pub mod scroll_view;
+pub mod select;
+pub mod selection;
+pub mod sidebar;
+pub mod split_pane;
+pub mod stateful;
+pub mod status_line;
+pub mod table;
+mod table_job;
+pub mod tabs;
+pub mod task_progress;
+pub mod text_area;
+pub mod text_input;
+
+pub use bulk::{BulkAction, BulkActions};
+pub use code_view::{CodeView, Highlighter, Keywords};
+#[cfg(feature = "syntect")]
+pub use code_view::Syntect;
+pub use confirm::ConfirmDialog;
+pub use edit::{CellEditor, EditOutcome, FieldKind};
+pub use follow::FollowState;
+pub use image::Image;
+pub use list::{ListEvent, ListView};
+pub use log_tail::{LogBuffer, LogTail};
+pub use scroll_view::{ScrollState, ScrollView};
+pub use select::Select;
+pub use selection::Selection;
+pub use sidebar::Sidebar;
+pub use split_pane::{Pane, SplitPane};
+pub use stateful::{Nav, Navigate, StatefulWidgetComponent};
+pub use status_line::{Segment, StatusLine, StatusLineState};
+pub use table::{Column, DataTable, TableView};
+pub use tabs::Tabs;
+pub use task_progress::TaskProgress;
+pub use text_area::{KeyMode, TextArea, VimMode};
+pub use text_input::TextInput;
@@ -0,0 +1,490 @@
+//! Scrollable container for any component.
+//!
+//! `ScrollView` renders its child into an offscreen buffer of the full content height
+//! and copies the visible window into the frame, so the child draws as if it had all
+//! the room it needs. A memoized child (see `Component::memoize`, or
+//! `ScrollView::cached`) is drawn offscreen only when it may look different, so
+//! scrolling a long, unchanged document just copies another window. The scroll
+//! position lives in an `Entity<ScrollState>` that other components can read,
+//! subscribe to or drive.
+
+use crossterm::event::{KeyCode, MouseEventKind};
+use ratatui::backend::TestBackend;
+use ratatui::layout::{Position, Rect};
+use ratatui::widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget};
+use ratatui::Terminal;
+
+use crate::component::traits::{Action, Component, Event};
+use crate::damage::{DependencyTracker, RenderStamp};
+use crate::{Context, Entity};
+
+/// Rows scrolled per mouse wheel notch.
+const WHEEL_STEP: isize = 3;
+
+/// Viewport position of a `ScrollView`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScrollState {
+    offset: u16,
+    content_height: u16,
+    viewport_height: u16,
+}
+
+impl ScrollState {
+    /// First visible content row.
+    pub fn offset(&self) -> u16 {
+        self.offset
+    }
+
+    /// Total content height in rows.
+    pub fn content_height(&self) -> u16 {
+        self.content_height
+    }
+
+    /// Visible rows at the last render.
+    pub fn viewport_height(&self) -> u16 {
+        self.viewport_height
+    }
+
+    /// Largest valid offset.
+    pub fn max_offset(&self) -> u16 {
+        self.content_height.saturating_sub(self.viewport_height)
+    }
+
+    /// Whether the last content row is visible.
+    pub fn is_at_bottom(&self) -> bool {
+        self.offset >= self.max_offset()
+    }
+
+    /// Scroll to `offset` (clamped).
+    pub fn scroll_to(&mut self, offset: u16) {
+        self.offset = offset.min(self.max_offset());
+    }
+
+    /// Scroll by `delta` rows (clamped).
+    pub fn scroll_by(&mut self, delta: isize) {
+        let offset = (self.offset as isize + delta).clamp(0, u16::MAX as isize) as u16;
+        self.scroll_to(offset);
+    }
+
+    /// Scroll up by one page, keeping one row of context.
+    pub fn page_up(&mut self) {
+        self.scroll_by(-self.page());
+    }
+
+    /// Scroll down by one page, keeping one row of context.
+    pub fn page_down(&mut self) {
+        self.scroll_by(self.page());
+    }
+
+    /// Scroll the minimum amount needed to show content row `row`.
+    pub fn ensure_visible(&mut self, row: u16) {
+        if row < self.offset {
+            self.offset = row;
+        } else if self.viewport_height > 0 && row >= self.offset + self.viewport_height {
+            self.scroll_to(row + 1 - self.viewport_height);
+        }
+    }
+
+    fn page(&self) -> isize {
+        (self.viewport_height as isize - 1).max(1)
+    }
+
+    fn set_size(&mut self, content_height: u16, viewport_height: u16) {
+        self.content_height = content_height;
+        self.viewport_height = viewport_height;
+        self.offset = self.offset.min(self.max_offset());
+    }
+}
+
+/// A child component rendered inside a scrolling viewport with a scrollbar.
+///
+/// The mouse wheel, `PageUp` and `PageDown` scroll the view; every other event goes to
+/// the child. Mouse positions are translated into content coordinates, so the child's
+/// `cx.local_position` reports the content row under the pointer.
+///
+/// # Example
+/// ```ignore
+/// struct LogPage {
+///     body: ScrollView<LogBody>,
+/// }
+///
+/// fn render(&mut self, frame: &mut Frame, cx: &mut Context<Self>) {
+///     self.body.set_content_height(self.body.lines.len() as u16);
+///     self.body.render(frame, frame.area(), cx);
+/// }
+///
+/// fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+///     self.body.handle_event(event, cx)
+/// }
+/// ```
+pub struct ScrollView<T> {
+    view: T,
+    state: Entity<ScrollState>,
+    content_height: u16,
+    scrollbar: bool,
+    area: Option<Rect>,
+    viewport: Rect,
+    offscreen: Option<Terminal<TestBackend>>,
+    cached: bool,
+    /// What the offscreen buffer was last drawn for, while it can be reused.
+    stamp: Option<RenderStamp>,
+}
+
+impl<T> ScrollView<T> {
+    /// Wrap `view`, whose content is `content_height` rows tall.
+    pub fn new(view: T, content_height: u16) -> Self {
+        Self {
+            view,
+            state: Entity::new(ScrollState::default()),
+            content_height,
+            scrollbar: true,
+            area: None,
+            viewport: Rect::default(),
+            offscreen: None,
+            cached: false,
+            stamp: None,
+        }
+    }
+
+    /// Only draw the child offscreen again when it may look different, like
+    /// `ChildView::cached`: after an entity it subscribed to while rendering changed, an
+    /// event reached it, it was borrowed mutably, or its size or inherited style
+    /// changed. Scrolling alone reuses the offscreen buffer.
+    ///
+    /// The child must subscribe to (or `watch`) every entity it reads in `render`.
+    pub fn cached(mut self) -> Self {
+        self.cached = true;
+        self
+    }
+
+    /// Draw the child offscreen again on the next frame.
+    pub fn invalidate(&mut self) {
+        self.stamp = None;
+    }
+
+    /// Share an existing scroll state, e.g. to keep the offset across page rebuilds.
+    pub fn with_state(mut self, state: Entity<ScrollState>) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// Hide the scrollbar; the child then gets the full width.
+    pub fn without_scrollbar(mut self) -> Self {
+        self.scrollbar = false;
+        self
+    }
+
+    /// The scroll state entity.
+    pub fn state(&self) -> &Entity<ScrollState> {
+        &self.state
+    }
+
+    /// Current scroll position.
+    pub fn scroll(&self) -> ScrollState {
+        self.state.read(|s| *s).unwrap_or_default()
+    }
+
+    /// Update the content height when the child's content grows or shrinks.
+    pub fn set_content_height(&mut self, height: u16) {
+        self.content_height = height;
+    }
+
+    /// Scroll so that content row `row` is visible.
+    pub fn ensure_visible(&self, row: u16) {
+        let _ = self.state.update(|s| s.ensure_visible(row));
+    }
+
+    /// The area the view was last rendered into.
+    pub fn area(&self) -> Option<Rect> {
+        self.area
+    }
+
+    /// Unwrap the child component.
+    pub fn into_inner(self) -> T {
+        self.view
+    }
+
+    fn content_area(&self) -> Rect {
+        Rect::new(0, 0, self.viewport.width, self.content_height)
+    }
+}
+
+impl<T: Component> ScrollView<T> {
+    /// Render the child into `area`, scrolled to the current offset.
+    pub fn render<P: ?Sized + Send + Sync>(&mut self, frame: &mut ratatui::Frame, area: Rect, cx: &Context<P>) {
+        self.area = Some(area);
+        let overflow = self.content_height > area.height;
+        let bar = u16::from(self.scrollbar && overflow && area.width > 1);
+        self.viewport = Rect::new(area.x, area.y, area.width - bar, area.height);
+
+        let size = (self.content_height, area.height);
+        if self.state.read(|s| (s.content_height, s.viewport_height)).ok() != Some(size) {
+            let _ = self.state.update(|s| s.set_size(size.0, size.1));
+        }
+        let offset = self.scroll().offset();
+
+        let content = self.content_area();
+        if content.is_empty() {
+            return;
+        }
+        let style = cx.style();
+        let generation = cx.damage.generation();
+        let cached = self.cached || self.view.memoize();
+        let offscreen = match &mut self.offscreen {
+            Some(terminal) if terminal.backend().buffer().area == content => terminal,
+            slot => match Terminal::new(TestBackend::new(content.width, content.height)) {
+                Ok(terminal) => slot.insert(terminal),
+                Err(e) => {
+                    log::warn!("ScrollView: failed to create offscreen buffer: {e}");
+                    return;
+                }
+            },
+        };
+
+        let valid = self.stamp.as_ref().filter(|stamp| stamp.is_valid(content, style, generation));
+        if let Some(stamp) = valid.filter(|_| cached) {
+            stamp.report(cx.dependency_trackers());
+        } else {
+            let view = &mut self.view;
+            let mut child_cx = cx.child::<T>(Some(content));
+            let tracker = DependencyTracker::default();
+            if cached {
+                child_cx.track_dependencies(&tracker);
+            }
+            if let Err(e) = offscreen.draw(|f| view.render(f, &mut child_cx)) {
+                log::warn!("ScrollView: offscreen render failed: {e}");
+                return;
+            }
+            self.stamp = cached.then(|| RenderStamp::new(content, style, generation, &tracker));
+        }
+        // The backend holds every cell of the last draw
+        let drawn = offscreen.backend().buffer();
+
+        let buf = frame.buffer_mut();
+        let rows = self.viewport.height.min(content.height - offset);
+        for y in 0..rows {
+            for x in 0..self.viewport.width {
+                buf[(self.viewport.x + x, self.viewport.y + y)] = drawn[(x, offset + y)].clone();
+            }
+        }
+
+        if bar == 1 {
+            let mut bar_state = ScrollbarState::new(self.content_height.saturating_sub(area.height) as usize)
+                .position(offset as usize)
+                .viewport_content_length(area.height as usize);
+            Scrollbar::new(ScrollbarOrientation::VerticalRight).render(area, buf, &mut bar_state);
+        }
+    }
+
+    /// Handle scrolling input and forward everything else to the child.
+    ///
+    /// Mouse events outside the viewport are dropped.
+    pub fn handle_event<P: ?Sized + Send + Sync>(&mut self, event: Event, cx: &Context<P>) -> Option<Action> {
+        match &event {
+            Event::Key(key) if key.code == KeyCode::PageUp => {
+                let _ = self.state.update(|s| s.page_up());
+                None
+            }
+            Event::Key(key) if key.code == KeyCode::PageDown => {
+                let _ = self.state.update(|s| s.page_down());
+                None
+            }
+            Event::Mouse(mouse) => {
+                let area = self.area?;
+                if !area.contains(Position::new(mouse.column, mouse.row)) {
+                    return None;
+                }
+                match mouse.kind {
+                    MouseEventKind::ScrollUp => {
+                        let _ = self.state.update(|s| s.scroll_by(-WHEEL_STEP));
+                        None
+                    }
+                    MouseEventKind::ScrollDown => {
+                        let _ = self.state.update(|s| s.scroll_by(WHEEL_STEP));
+                        None
+                    }
+                    _ if self.viewport.contains(Position::new(mouse.column, mouse.row)) => {
+                        let mut local = *mouse;
+                        local.column = mouse.column - self.viewport.x;
+                        local.row = mouse.row - self.viewport.y + self.scroll().offset();
+                        self.forward(Event::Mouse(local), cx)
+                    }
+                    _ => None,
+                }
+            }
+            _ => self.forward(event, cx),
+        }
+    }
+
+    /// Deliver an event to the child, redrawing afterwards if it asks for that.
+    fn forward<P: ?Sized + Send + Sync>(&mut self, event: Event, cx: &Context<P>) -> Option<Action> {
+        self.invalidate();
+        let mut cx = cx.child::<T>(Some(self.content_area()));
+        let action = self.view.handle_event(event, &mut cx);
+        if self.view.refresh_on_event() {
+            cx.refresh();
+        }
+        action
+    }
+
+    /// Forward `on_mount` to the child.
+    pub fn on_mount<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        self.invalidate();
+        self.view.on_mount(&mut cx.child(Some(self.content_area())));
+    }
+
+    /// Forward `on_enter` to the child.
+    pub fn on_enter<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        self.invalidate();
+        self.view.on_enter(&mut cx.child(Some(self.content_area())));
+    }
+
+    /// Forward `on_exit` to the child.
+    pub fn on_exit<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        self.invalidate();
+        self.view.on_exit(&mut cx.child(Some(self.content_area())));
+    }
+
+    /// Forward `on_shutdown` to the child.
+    pub fn on_shutdown<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        self.invalidate();
+        self.view.on_shutdown(&mut cx.child(Some(self.content_area())));
+    }
+
+    /// Forward `on_resize` to the child.
+    pub fn on_resize<P: ?Sized + Send + Sync>(&mut self, width: u16, height: u16, cx: &Context<P>) {
+        self.invalidate();
+        self.view.on_resize(width, height, &mut cx.child(Some(self.content_area())));
+    }
+
+    /// Forward `on_focus` to the child.
+    pub fn on_focus<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        self.invalidate();
+        self.view.on_focus(&mut cx.child(Some(self.content_area())));
+    }
+
+    /// Forward `on_blur` to the child.
+    pub fn on_blur<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        self.invalidate();
+        self.view.on_blur(&mut cx.child(Some(self.content_area())));
+    }
+}
+
+impl<T> std::ops::Deref for ScrollView<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.view
+    }
+}
+
+impl<T> std::ops::DerefMut for ScrollView<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // The caller may change what the child draws
+        self.invalidate();
+        &mut self.view
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEvent, KeyModifiers, MouseButton, MouseEvent};
+    use ratatui::widgets::Paragraph;
+
+    #[derive(Default)]
+    struct Lines {
+        clicked: Option<(u16, u16)>,
+    }
+
+    impl Component for Lines {
+        fn render(&mut self, frame: &mut ratatui::Frame, _cx: &mut Context<Self>) {
+            let text: Vec<_> = (0..20).map(|i| ratatui::text::Line::from(format!("line {i}"))).collect();
+            frame.render_widget(Paragraph::new(text), frame.area());
+        }
+
+        fn handle_event(&mut self, event: Event, cx: &mut Context<Self>) -> Option<Action> {
+            if let Event::Mouse(mouse) = event {
+                self.clicked = cx.local_position(mouse.column, mouse.row);
+            }
+            None
+        }
+    }
+
+    #[test]
+    fn test_scroll_view_window_and_mouse() {
+        let (app, _refresh, _inject) = crate::AppContext::new();
+        let cx = Context::<Lines>::new(app, Entity::new(Lines::default()).downgrade());
+        let mut view = ScrollView::new(Lines::default(), 20);
+        let mut terminal = Terminal::new(TestBackend::new(10, 5)).unwrap();
+        let mut draw = |view: &mut ScrollView<Lines>| {
+            let frame = terminal.draw(|f| view.render(f, f.area(), &cx)).unwrap();
+            (0..9).map(|x| frame.buffer[(x, 0)].symbol().to_string()).collect::<String>()
+        };
+
+        assert_eq!(draw(&mut view).trim_end(), "line 0");
+        view.handle_event(Event::Key(KeyEvent::from(KeyCode::PageDown)), &cx);
+        assert_eq!(view.scroll().offset(), 4);
+        view.handle_event(
+            Event::Mouse(MouseEvent { kind: MouseEventKind::ScrollDown, column: 1, row: 1, modifiers: KeyModifiers::NONE }),
+            &cx,
+        );
+        assert_eq!(draw(&mut view).trim_end(), "line 7");
+
+        view.handle_event(
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column: 2,
+                row: 1,
+                modifiers: KeyModifiers::NONE,
+            }),
+            &cx,
+        );
+        assert_eq!(view.clicked, Some((2, 8)));
+
+        view.handle_event(Event::Key(KeyEvent::from(KeyCode::PageDown)), &cx);
+        view.handle_event(Event::Key(KeyEvent::from(KeyCode::PageDown)), &cx);
+        assert!(view.scroll().is_at_bottom());
+        assert_eq!(view.scroll().offset(), 15);
+    }
+
+    struct Document {
+        title: Entity<&'static str>,
+        renders: usize,
+    }
+
+    impl Component for Document {
+        fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+            self.renders += 1;
+            let title = cx.watch(&self.title, |t| *t).unwrap_or_default();
+            let text: Vec<_> = (0..20).map(|i| ratatui::text::Line::from(format!("{title} {i}"))).collect();
+            frame.render_widget(Paragraph::new(text), frame.area());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_scroll_view_draws_offscreen_only_on_change() {
+        let (app, _refresh, _inject) = crate::AppContext::new();
+        let cx = Context::<Lines>::new(app, Entity::new(Lines::default()).downgrade());
+        let title = Entity::new("doc");
+        let mut view = ScrollView::new(Document { title: title.clone(), renders: 0 }, 20).cached();
+        let mut terminal = Terminal::new(TestBackend::new(10, 5)).unwrap();
+        let mut draw = |view: &mut ScrollView<Document>| {
+            let frame = terminal.draw(|f| view.render(f, f.area(), &cx)).unwrap();
+            (0..9).map(|x| frame.buffer[(x, 0)].symbol().to_string()).collect::<String>()
+        };
+
+        assert_eq!(draw(&mut view).trim_end(), "doc 0");
+        let _ = view.state().update(|s| s.scroll_to(6));
+        assert_eq!(draw(&mut view).trim_end(), "doc 6");
+        assert_eq!(view.renders, 1);
+
+        title.update(|t| *t = "log").unwrap();
+        assert_eq!(draw(&mut view).trim_end(), "log 6");
+        assert_eq!(view.renders, 2);
+
+        view.set_content_height(30);
+        draw(&mut view);
+        assert_eq!(view.renders, 3);
+    }
+}
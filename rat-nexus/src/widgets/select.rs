@@ -0,0 +1,547 @@
+//! Select / dropdown with fuzzy filtering.
+
+use std::sync::Arc;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Widget};
+
+use crate::component::traits::{Action, Component, Event};
+use crate::{Context, EventContext};
+
+type LabelFn<T> = Arc<dyn Fn(&T) -> String + Send + Sync>;
+type SelectFn<T> = Box<dyn FnMut(T) -> Option<Action> + Send + Sync>;
+
+/// Match `pattern` as a case-insensitive subsequence of `text`.
+///
+/// Returns a score (higher is better: consecutive runs and word starts count extra)
+/// and the char indices of the matched characters. Every occurrence of the first
+/// pattern character is tried as a starting point and the best match kept.
+pub(crate) fn fuzzy_match(pattern: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+    let text: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = text.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect();
+    let word_start = |i: usize| {
+        i == 0 || !text[i - 1].is_alphanumeric() || (text[i].is_uppercase() && text[i - 1].is_lowercase())
+    };
+    let Some(&first) = pattern.first() else {
+        return Some((0, Vec::new()));
+    };
+
+    let mut best: Option<(i64, Vec<usize>)> = None;
+    for start in (0..text.len()).filter(|&i| lower[i] == first) {
+        let mut positions = vec![start];
+        let mut next = start + 1;
+        for &p in &pattern[1..] {
+            match (next..text.len()).find(|&i| lower[i] == p) {
+                Some(i) => {
+                    positions.push(i);
+                    next = i + 1;
+                }
+                None => break,
+            }
+        }
+        if positions.len() < pattern.len() {
+            break; // later starts cannot match more
+        }
+        let score = positions
+            .iter()
+            .enumerate()
+            .map(|(n, &i)| {
+                let run = n > 0 && positions[n - 1] + 1 == i;
+                1 + if word_start(i) { 8 } else { 0 } + if run { 4 } else { 0 }
+            })
+            .sum::<i64>();
+        if best.as_ref().is_none_or(|(b, _)| score > *b) {
+            best = Some((score, positions));
+        }
+    }
+    // Prefer shorter candidates among equal matches
+    best.map(|(score, positions)| (score * 64 - text.len().min(63) as i64, positions))
+}
+
+/// A dropdown over `items`, filtered fuzzily as the user types.
+///
+/// Closed, it shows the selected item with a `▾`. `Enter`, `Space`, `↓` or a click
+/// opens it; typing filters, `↑/↓` move, `Enter` or a click picks and calls
+/// `on_select`, `Esc` closes. As a `Component` the popup is drawn with
+/// `AppContext::overlay`, above later siblings; when drawing manually, call
+/// `draw_popup` after everything else.
+///
+/// With `inline(height)` the filter line and list are drawn in place instead.
+///
+/// # Example
+/// ```ignore
+/// let theme = ChildView::new(
+///     Select::new(themes, |t: &Theme| t.name.clone())
+///         .placeholder("Theme…")
+///         .on_select(|t| Some(Action::Navigate(format!("preview/{}", t.name)))),
+/// );
+/// ```
+pub struct Select<T> {
+    items: Vec<T>,
+    label: LabelFn<T>,
+    selected: Option<usize>,
+    open: bool,
+    inline: Option<u16>,
+    query: String,
+    matches: Vec<(usize, Vec<usize>)>,
+    cursor: usize,
+    offset: usize,
+    max_height: u16,
+    placeholder: String,
+    style: Style,
+    highlight_style: Style,
+    on_select: Option<SelectFn<T>>,
+    area: Option<Rect>,
+    list_area: Option<Rect>,
+}
+
+impl<T: Clone> Select<T> {
+    /// Create a select over `items`, displayed with `label`.
+    pub fn new<F>(items: Vec<T>, label: F) -> Self
+    where
+        F: Fn(&T) -> String + Send + Sync + 'static,
+    {
+        let mut select = Self {
+            items,
+            label: Arc::new(label),
+            selected: None,
+            open: false,
+            inline: None,
+            query: String::new(),
+            matches: Vec::new(),
+            cursor: 0,
+            offset: 0,
+            max_height: 8,
+            placeholder: String::new(),
+            style: Style::default(),
+            highlight_style: Style::default().fg(Color::Black).bg(Color::Cyan),
+            on_select: None,
+            area: None,
+            list_area: None,
+        };
+        select.refilter();
+        select
+    }
+
+    /// Draw the filter line and list in place, using `height` rows, instead of a popup.
+    pub fn inline(mut self, height: u16) -> Self {
+        self.inline = Some(height.max(2));
+        self.open = true;
+        self
+    }
+
+    /// Maximum number of rows in the popup list (default 8).
+    pub fn max_height(mut self, rows: u16) -> Self {
+        self.max_height = rows.max(1);
+        self
+    }
+
+    /// Text shown while nothing is selected.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Base style of the field and list.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Style of the highlighted list row.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Call `f(item)` when an item is picked; its action is returned from `handle_event`.
+    pub fn on_select<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(T) -> Option<Action> + Send + Sync + 'static,
+    {
+        self.on_select = Some(Box::new(f));
+        self
+    }
+
+    /// Start with `items[index]` selected.
+    pub fn with_selected(mut self, index: usize) -> Self {
+        self.selected = (index < self.items.len()).then_some(index);
+        self
+    }
+
+    /// All items.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Replace the items, clearing the selection.
+    pub fn set_items(&mut self, items: Vec<T>) {
+        self.items = items;
+        self.selected = None;
+        self.refilter();
+    }
+
+    /// The selected item.
+    pub fn selected(&self) -> Option<&T> {
+        self.items.get(self.selected?)
+    }
+
+    /// Index of the selected item.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// The current filter text.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Indices of the items matching the filter, best match first.
+    pub fn matches(&self) -> impl Iterator<Item = usize> + '_ {
+        self.matches.iter().map(|(i, _)| *i)
+    }
+
+    /// Whether the list is showing.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Show the list, highlighting the selected item.
+    pub fn open(&mut self) {
+        self.open = true;
+        self.cursor = self.matches.iter().position(|(i, _)| Some(*i) == self.selected).unwrap_or(0);
+    }
+
+    /// Hide the list and clear the filter. Inline selects only clear the filter.
+    pub fn close(&mut self) {
+        self.open = self.inline.is_some();
+        self.query.clear();
+        self.refilter();
+    }
+
+    /// Append text to the filter, opening the list.
+    pub fn insert_str(&mut self, text: &str) {
+        self.query.extend(text.chars().filter(|c| !c.is_control()));
+        self.open = true;
+        self.refilter();
+    }
+
+    /// `Enter`: open the list, or pick the highlighted match.
+    pub fn confirm(&mut self) -> Option<Action> {
+        if !self.open {
+            self.open();
+            return None;
+        }
+        let index = self.matches.get(self.cursor)?.0;
+        self.pick(index)
+    }
+
+    /// Select `items[index]`, close the list and call `on_select`.
+    pub fn pick(&mut self, index: usize) -> Option<Action> {
+        let item = self.items.get(index)?.clone();
+        self.selected = Some(index);
+        self.close();
+        self.on_select.as_mut().and_then(|f| f(item))
+    }
+
+    /// Handle a key. Returns true if the select consumed it.
+    /// `Enter` is not handled here; see `confirm`.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if !self.open {
+            return match key.code {
+                KeyCode::Down | KeyCode::Char(' ') => {
+                    self.open();
+                    true
+                }
+                KeyCode::Char(c) if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                    self.insert_str(&c.to_string());
+                    true
+                }
+                _ => false,
+            };
+        }
+        let page = self.list_rows().max(1);
+        match key.code {
+            KeyCode::Esc if self.query.is_empty() && self.inline.is_some() => return false,
+            KeyCode::Esc => self.close(),
+            KeyCode::Up => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Down => self.cursor = (self.cursor + 1).min(self.matches.len().saturating_sub(1)),
+            KeyCode::PageUp => self.cursor = self.cursor.saturating_sub(page),
+            KeyCode::PageDown => self.cursor = (self.cursor + page).min(self.matches.len().saturating_sub(1)),
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.refilter();
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.query.clear();
+                self.refilter();
+            }
+            KeyCode::Char(c) if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                self.insert_str(&c.to_string());
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Handle a mouse event: clicking the field toggles the list, clicking a row picks it,
+    /// the wheel scrolls. Returns the `on_select` action of a pick.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> Option<Action> {
+        let position = Position::new(mouse.column, mouse.row);
+        if let Some(list) = self.list_area.filter(|r| self.open && r.contains(position)) {
+            match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    let index = self.offset + (mouse.row - list.y) as usize;
+                    let item = self.matches.get(index)?.0;
+                    return self.pick(item);
+                }
+                MouseEventKind::ScrollUp => self.cursor = self.cursor.saturating_sub(1),
+                MouseEventKind::ScrollDown => self.cursor = (self.cursor + 1).min(self.matches.len().saturating_sub(1)),
+                _ => {}
+            }
+            return None;
+        }
+        if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+            if self.area.is_some_and(|r| r.contains(position)) {
+                if self.open {
+                    self.close();
+                } else {
+                    self.open();
+                }
+            } else if self.open && self.inline.is_none() {
+                self.close();
+            }
+        }
+        None
+    }
+
+    /// Draw the field (or, inline, the filter line and list) into `area`.
+    pub fn draw(&mut self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        let field = Rect { height: 1, ..area };
+        self.area = Some(field);
+        buf.set_style(field, self.style);
+        if self.open && (self.inline.is_some() || !self.query.is_empty()) {
+            let text = format!("› {}", self.query);
+            buf.set_stringn(field.x, field.y, &text, field.width as usize, self.style);
+            let cursor_x = field.x + text.chars().count() as u16;
+            if cursor_x < field.right() {
+                buf[(cursor_x, field.y)].set_style(self.style.add_modifier(Modifier::REVERSED));
+            }
+        } else {
+            let (text, style) = match self.selected() {
+                Some(item) => ((self.label)(item), self.style),
+                None => (self.placeholder.clone(), self.style.fg(Color::DarkGray)),
+            };
+            let width = field.width.saturating_sub(2) as usize;
+            buf.set_stringn(field.x, field.y, text, width, style);
+            buf.set_stringn(field.right().saturating_sub(1), field.y, "▾", 1, self.style);
+        }
+
+        if let Some(height) = self.inline {
+            let list = Rect::new(area.x, area.y + 1, area.width, height.min(area.height).saturating_sub(1));
+            self.draw_list(list, buf);
+        }
+    }
+
+    /// Draw the popup list below the field (above it if there is no room), clipped to
+    /// `bounds`. Does nothing while closed or inline.
+    pub fn draw_popup(&mut self, bounds: Rect, buf: &mut Buffer) {
+        let Some(popup) = self.popup_area(bounds) else {
+            return;
+        };
+        Clear.render(popup, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(self.style.fg(Color::Cyan));
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+        self.draw_list(inner, buf);
+    }
+
+    fn popup_area(&self, bounds: Rect) -> Option<Rect> {
+        let field = self.area.filter(|_| self.open && self.inline.is_none())?;
+        let rows = (self.matches.len().max(1) as u16).min(self.max_height) + 2;
+        let below = bounds.bottom().saturating_sub(field.bottom());
+        let above = field.y.saturating_sub(bounds.y);
+        let (y, height) = if below >= rows || below >= above {
+            (field.bottom(), rows.min(below))
+        } else {
+            (field.y - rows.min(above), rows.min(above))
+        };
+        let popup = Rect::new(field.x, y, field.width.max(12), height).intersection(bounds);
+        (!popup.is_empty()).then_some(popup)
+    }
+
+    fn list_rows(&self) -> usize {
+        self.list_area.map_or(self.max_height as usize, |r| r.height as usize)
+    }
+
+    fn draw_list(&mut self, area: Rect, buf: &mut Buffer) {
+        self.list_area = Some(area);
+        let height = area.height as usize;
+        if height == 0 {
+            return;
+        }
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + height {
+            self.offset = self.cursor + 1 - height;
+        }
+        if self.matches.is_empty() {
+            let style = self.style.fg(Color::DarkGray);
+            buf.set_stringn(area.x, area.y, "no matches", area.width as usize, style);
+            return;
+        }
+        for (row, (index, positions)) in self.matches.iter().enumerate().skip(self.offset).take(height) {
+            let y = area.y + (row - self.offset) as u16;
+            let base = if row == self.cursor { self.style.patch(self.highlight_style) } else { self.style };
+            let marker = if Some(*index) == self.selected { "✓ " } else { "  " };
+            let label = (self.label)(&self.items[*index]);
+            let mut spans = vec![Span::styled(marker, base)];
+            spans.extend(label.chars().enumerate().map(|(i, c)| {
+                let style = if positions.contains(&i) { base.add_modifier(Modifier::BOLD | Modifier::UNDERLINED) } else { base };
+                Span::styled(c.to_string(), style)
+            }));
+            buf.set_style(Rect::new(area.x, y, area.width, 1), base);
+            buf.set_line(area.x, y, &Line::from(spans), area.width);
+        }
+    }
+
+    fn refilter(&mut self) {
+        self.matches = if self.query.is_empty() {
+            (0..self.items.len()).map(|i| (i, Vec::new())).collect()
+        } else {
+            let mut scored: Vec<(i64, usize, Vec<usize>)> = self
+                .items
+                .iter()
+                .enumerate()
+                .filter_map(|(i, item)| fuzzy_match(&self.query, &(self.label)(item)).map(|(score, pos)| (score, i, pos)))
+                .collect();
+            scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+            scored.into_iter().map(|(_, i, pos)| (i, pos)).collect()
+        };
+        self.cursor = 0;
+        self.offset = 0;
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Component for Select<T> {
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let area = cx.area(frame);
+        self.draw(area, frame.buffer_mut());
+        let bounds = frame.area();
+        let Some(popup) = self.popup_area(bounds) else {
+            return;
+        };
+        // Lay out the list now so mouse hits match what the overlay draws
+        let mut scratch = Buffer::empty(popup);
+        self.draw_popup(bounds, &mut scratch);
+        cx.overlay(move |frame| {
+            let buf = frame.buffer_mut();
+            for y in popup.top()..popup.bottom() {
+                for x in popup.left()..popup.right() {
+                    buf[(x, y)] = scratch[(x, y)].clone();
+                }
+            }
+        });
+    }
+
+    fn refresh_on_event(&self) -> bool {
+        true
+    }
+
+    fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+        match event {
+            Event::Key(key) if key.code == KeyCode::Enter => self.confirm(),
+            Event::Key(key) => {
+                self.handle_key(key);
+                None
+            }
+            Event::Mouse(mouse) => self.handle_mouse(mouse),
+            Event::Paste(text) => {
+                self.insert_str(&text);
+                None
+            }
+            Event::FocusLost if self.inline.is_none() => {
+                self.close();
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fruits() -> Select<&'static str> {
+        Select::new(vec!["apple", "banana", "blueberry", "cherry", "grape"], |s: &&str| s.to_string())
+            .on_select(|s| Some(Action::Navigate(s.to_string())))
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::from(code)
+    }
+
+    #[test]
+    fn test_fuzzy_ranks_word_starts_and_runs() {
+        assert_eq!(fuzzy_match("bb", "blueberry").map(|m| m.1), Some(vec![0, 4]));
+        assert!(fuzzy_match("xyz", "blueberry").is_none());
+        let prefix = fuzzy_match("ch", "cherry").unwrap().0;
+        let scattered = fuzzy_match("ch", "crunchy").unwrap().0;
+        assert!(prefix > scattered);
+
+        let mut select = fruits();
+        select.insert_str("b");
+        assert_eq!(select.matches().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_keys_filter_and_pick() {
+        let mut select = fruits();
+        assert!(select.handle_key(key(KeyCode::Char('r'))));
+        assert!(select.is_open());
+        assert_eq!(select.matches().count(), 3);
+        select.handle_key(key(KeyCode::Down));
+        assert_eq!(select.confirm(), Some(Action::Navigate("cherry".into())));
+        assert_eq!(select.selected(), Some(&"cherry"));
+        assert!(!select.is_open());
+        assert_eq!(select.query(), "");
+
+        select.handle_key(key(KeyCode::Down));
+        select.handle_key(key(KeyCode::Esc));
+        assert!(!select.is_open());
+        assert_eq!(select.selected_index(), Some(3));
+    }
+
+    #[test]
+    fn test_popup_draws_below_field_and_picks_on_click() {
+        let mut select = fruits();
+        let bounds = Rect::new(0, 0, 20, 10);
+        let mut buf = Buffer::empty(bounds);
+        select.open();
+        select.draw(Rect::new(0, 0, 14, 1), &mut buf);
+        select.draw_popup(bounds, &mut buf);
+        let row = |y: u16| (0..14).map(|x| buf[(x, y)].symbol().to_string()).collect::<String>();
+        assert_eq!(row(2), "│  apple     │");
+        assert_eq!(row(6), "│  grape     │");
+
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 4,
+            row: 3,
+            modifiers: KeyModifiers::NONE,
+        };
+        assert_eq!(select.handle_mouse(click), Some(Action::Navigate("banana".into())));
+    }
+}
@@ -0,0 +1,213 @@
+//! Cursor and multi-item marking shared by list-like widgets.
+
+use std::collections::BTreeSet;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Selection state of a list: a cursor plus a set of marked items.
+///
+/// Keys handled by `handle_key`:
+/// - `↑`/`↓` (or `k`/`j`), `Home`, `End`: move the cursor
+/// - `Shift+↑`/`Shift+↓`: extend the marked range from the anchor
+/// - `Space`: toggle the mark on the cursor item and move down
+/// - `Ctrl+A`: mark all items
+/// - `Esc`: clear marks (only consumed while items are marked)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Selection {
+    cursor: usize,
+    len: usize,
+    marked: BTreeSet<usize>,
+    anchor: Option<usize>,
+}
+
+impl Selection {
+    /// Create a selection over `len` items.
+    pub fn new(len: usize) -> Self {
+        Self { len, ..Self::default() }
+    }
+
+    /// Update the item count, clamping the cursor and dropping out-of-range marks.
+    pub fn set_len(&mut self, len: usize) {
+        self.len = len;
+        self.cursor = self.cursor.min(len.saturating_sub(1));
+        self.marked.retain(|&i| i < len);
+        if self.anchor.is_some_and(|a| a >= len) {
+            self.anchor = None;
+        }
+    }
+
+    /// Number of items.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Index of the cursor item, if there are any items.
+    pub fn cursor(&self) -> Option<usize> {
+        (self.len > 0).then_some(self.cursor)
+    }
+
+    /// Move the cursor to `index` (clamped).
+    pub fn select(&mut self, index: usize) {
+        self.cursor = index.min(self.len.saturating_sub(1));
+        self.anchor = None;
+    }
+
+    /// Move the cursor by `delta` items (clamped).
+    pub fn move_by(&mut self, delta: isize) {
+        self.select(self.cursor.saturating_add_signed(delta));
+    }
+
+    /// Toggle the mark on `index`.
+    pub fn toggle(&mut self, index: usize) {
+        if index < self.len && !self.marked.remove(&index) {
+            self.marked.insert(index);
+        }
+    }
+
+    /// Mark every item.
+    pub fn mark_all(&mut self) {
+        self.marked = (0..self.len).collect();
+    }
+
+    /// Remove all marks.
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+        self.anchor = None;
+    }
+
+    /// Whether `index` is marked.
+    pub fn is_marked(&self, index: usize) -> bool {
+        self.marked.contains(&index)
+    }
+
+    /// Marked indices in ascending order.
+    pub fn marked(&self) -> Vec<usize> {
+        self.marked.iter().copied().collect()
+    }
+
+    /// Number of marked items.
+    pub fn marked_count(&self) -> usize {
+        self.marked.len()
+    }
+
+    /// Items an action applies to: the marked items, or the cursor item if none are marked.
+    pub fn targets(&self) -> Vec<usize> {
+        if self.marked.is_empty() {
+            self.cursor().into_iter().collect()
+        } else {
+            self.marked()
+        }
+    }
+
+    /// Move the cursor to `index`, marking every item between the anchor and it.
+    pub fn extend_to(&mut self, index: usize) {
+        if self.len == 0 {
+            return;
+        }
+        let anchor = *self.anchor.get_or_insert(self.cursor);
+        self.cursor = index.min(self.len - 1);
+        let (start, end) = if anchor <= self.cursor { (anchor, self.cursor) } else { (self.cursor, anchor) };
+        self.marked.extend(start..=end);
+    }
+
+    /// Account for items removed at `indices`: drops their marks, shifts the remaining
+    /// ones and keeps the cursor on the nearest surviving item.
+    pub fn remove_indices(&mut self, indices: &[usize]) {
+        let removed: BTreeSet<usize> = indices.iter().copied().filter(|&i| i < self.len).collect();
+        let shift = |i: usize| i - removed.range(..i).count();
+        self.marked = self.marked.iter().filter(|i| !removed.contains(i)).map(|&i| shift(i)).collect();
+        self.cursor = shift(self.cursor);
+        self.anchor = None;
+        self.set_len(self.len - removed.len());
+    }
+
+    /// Account for the item at `from` being moved to `to`, shifting the items in
+    /// between. Marks and the cursor follow the items they were on.
+    pub fn move_item(&mut self, from: usize, to: usize) {
+        if from >= self.len || to >= self.len || from == to {
+            return;
+        }
+        let remap = |i: usize| match i {
+            i if i == from => to,
+            i if from < to && i > from && i <= to => i - 1,
+            i if to < from && i >= to && i < from => i + 1,
+            i => i,
+        };
+        self.marked = self.marked.iter().map(|&i| remap(i)).collect();
+        self.cursor = remap(self.cursor);
+        self.anchor = None;
+    }
+
+    /// Handle selection keys. Returns true if the key was consumed.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Up if shift => self.extend_to(self.cursor.saturating_sub(1)),
+            KeyCode::Down if shift => self.extend_to(self.cursor + 1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_by(1),
+            KeyCode::Home => self.select(0),
+            KeyCode::End => self.select(self.len.saturating_sub(1)),
+            KeyCode::Char(' ') => {
+                self.toggle(self.cursor);
+                self.move_by(1);
+            }
+            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => self.mark_all(),
+            KeyCode::Esc if !self.marked.is_empty() => self.clear_marks(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_targets_and_marks() {
+        let mut sel = Selection::new(5);
+        assert_eq!(sel.targets(), vec![0]);
+        sel.handle_key(KeyEvent::from(KeyCode::Char(' ')));
+        sel.handle_key(KeyEvent::from(KeyCode::Char(' ')));
+        assert_eq!(sel.targets(), vec![0, 1]);
+        assert_eq!(sel.cursor(), Some(2));
+
+        sel.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT));
+        assert_eq!(sel.marked(), vec![0, 1, 2, 3]);
+        assert!(sel.handle_key(KeyEvent::from(KeyCode::Esc)));
+        assert_eq!(sel.marked_count(), 0);
+        assert!(!sel.handle_key(KeyEvent::from(KeyCode::Esc)));
+    }
+
+    #[test]
+    fn test_move_item_remaps_marks() {
+        let mut sel = Selection::new(5);
+        sel.toggle(0);
+        sel.toggle(3);
+        sel.select(0);
+        sel.move_item(0, 3);
+        assert_eq!(sel.marked(), vec![2, 3]);
+        assert_eq!(sel.cursor(), Some(3));
+        sel.move_item(4, 1);
+        assert_eq!(sel.marked(), vec![3, 4]);
+    }
+
+    #[test]
+    fn test_remove_indices_shifts() {
+        let mut sel = Selection::new(6);
+        sel.toggle(1);
+        sel.toggle(4);
+        sel.toggle(5);
+        sel.select(5);
+        sel.remove_indices(&[1, 4]);
+        assert_eq!(sel.len(), 4);
+        assert_eq!(sel.marked(), vec![3]);
+        assert_eq!(sel.cursor(), Some(3));
+    }
+}
@@ -0,0 +1,253 @@
+//! Route navigation sidebar that follows the router.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use unicode_width::UnicodeWidthStr;
+
+use crate::component::traits::{Action, Component, Event};
+use crate::router::RouteInfo;
+use crate::{Context, EventContext};
+
+/// Lists the registered routes (see `AppContext::describe_route`) with their icons and
+/// highlights the active one.
+///
+/// Routes come from the app's route registry, which `define_app!` and `AppShell` fill
+/// and update on every navigation, so the sidebar needs no wiring: mount it (e.g. as
+/// `define_app!` chrome) and it follows the router. `↑/↓`/`j`/`k` move the cursor,
+/// `Enter` or a click returns `Action::Navigate` for the route, and `Ctrl+B` collapses
+/// it to icons only.
+///
+/// # Example
+/// ```ignore
+/// define_app! {
+///     #[Chrome(sidebar: Sidebar = 18)]
+///     Menu => menu: Menu,
+///     Monitor => monitor: MonitorPage,
+/// }
+///
+/// // during setup
+/// cx.describe_route(RouteInfo::new("Menu").icon("☰"));
+/// cx.describe_route(RouteInfo::new("Monitor").icon("📈").label("System monitor"));
+/// ```
+pub struct Sidebar {
+    cursor: usize,
+    offset: usize,
+    collapsed: bool,
+    toggle_key: Option<KeyEvent>,
+    style: Style,
+    active_style: Style,
+    cursor_style: Style,
+    synced: Option<String>,
+    area: Option<Rect>,
+}
+
+impl Default for Sidebar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sidebar {
+    /// An expanded sidebar.
+    pub fn new() -> Self {
+        Self {
+            cursor: 0,
+            offset: 0,
+            collapsed: false,
+            toggle_key: Some(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::CONTROL)),
+            style: Style::default(),
+            active_style: Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            cursor_style: Style::default().add_modifier(Modifier::REVERSED),
+            synced: None,
+            area: None,
+        }
+    }
+
+    /// Start collapsed to icons.
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+
+    /// Key toggling collapse (`Ctrl+B` by default), or `None` to disable it.
+    pub fn toggle_key(mut self, key: Option<KeyEvent>) -> Self {
+        self.toggle_key = key;
+        self
+    }
+
+    /// Base style of the entries.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Style patched onto the active route.
+    pub fn active_style(mut self, style: Style) -> Self {
+        self.active_style = style;
+        self
+    }
+
+    /// Style patched onto the cursor row.
+    pub fn cursor_style(mut self, style: Style) -> Self {
+        self.cursor_style = style;
+        self
+    }
+
+    /// Whether only icons are shown.
+    pub fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    /// Collapse to icons or expand.
+    pub fn set_collapsed(&mut self, collapsed: bool) {
+        self.collapsed = collapsed;
+    }
+
+    /// Index of the cursor in the route list.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Columns needed to show every entry in the current mode.
+    pub fn preferred_width(&self, routes: &[RouteInfo]) -> u16 {
+        routes.iter().map(|route| entry(route, self.collapsed).width() as u16).max().unwrap_or(0) + 1
+    }
+
+    /// Move the cursor to the active route when the route changes.
+    fn sync(&mut self, routes: &[RouteInfo], active: Option<String>) {
+        if active != self.synced {
+            if let Some(index) = active.as_deref().and_then(|name| position(routes, name)) {
+                self.cursor = index;
+            }
+            self.synced = active;
+        }
+        self.cursor = self.cursor.min(routes.len().saturating_sub(1));
+    }
+
+    fn move_by(&mut self, delta: isize, len: usize) {
+        self.cursor = self.cursor.saturating_add_signed(delta).min(len.saturating_sub(1));
+    }
+
+    fn navigate(routes: &[RouteInfo], index: usize) -> Option<Action> {
+        routes.get(index).map(|route| Action::Navigate(route.name.clone()))
+    }
+}
+
+fn position(routes: &[RouteInfo], name: &str) -> Option<usize> {
+    routes.iter().position(|route| route.name.eq_ignore_ascii_case(name))
+}
+
+/// The text of an entry: icon and label, or just the icon (the label's first letter
+/// without one) when collapsed.
+fn entry(route: &RouteInfo, collapsed: bool) -> String {
+    match (&route.icon, collapsed) {
+        (Some(icon), true) => format!(" {icon}"),
+        (Some(icon), false) => format!(" {icon} {}", route.label),
+        (None, true) => format!(" {}", route.label.chars().next().unwrap_or(' ')),
+        (None, false) => format!(" {}", route.label),
+    }
+}
+
+impl Component for Sidebar {
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let area = cx.area(frame);
+        self.area = Some(area);
+        let routes = cx.routes();
+        let active = cx.active_route();
+        self.sync(&routes, active.clone());
+
+        let height = usize::from(area.height);
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if height > 0 && self.cursor >= self.offset + height {
+            self.offset = self.cursor + 1 - height;
+        }
+
+        let buffer = frame.buffer_mut();
+        buffer.set_style(area, self.style);
+        for (row, (index, route)) in routes.iter().enumerate().skip(self.offset).take(height).enumerate() {
+            let y = area.y + row as u16;
+            let mut style = self.style;
+            if active.as_deref().is_some_and(|name| name.eq_ignore_ascii_case(&route.name)) {
+                style = style.patch(self.active_style);
+            }
+            if index == self.cursor {
+                style = style.patch(self.cursor_style);
+            }
+            buffer.set_style(Rect::new(area.x, y, area.width, 1), style);
+            buffer.set_stringn(area.x, y, entry(route, self.collapsed), usize::from(area.width), style);
+        }
+    }
+
+    fn refresh_on_event(&self) -> bool {
+        true
+    }
+
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+        let routes = cx.routes();
+        self.sync(&routes, cx.active_route());
+        match event {
+            Event::Key(key) if Some(key) == self.toggle_key => {
+                self.collapsed = !self.collapsed;
+                None
+            }
+            Event::Key(key) => {
+                match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => self.move_by(-1, routes.len()),
+                    KeyCode::Down | KeyCode::Char('j') => self.move_by(1, routes.len()),
+                    KeyCode::Home => self.cursor = 0,
+                    KeyCode::End => self.cursor = routes.len().saturating_sub(1),
+                    KeyCode::Enter | KeyCode::Char(' ') => return Self::navigate(&routes, self.cursor),
+                    _ => {}
+                }
+                None
+            }
+            Event::Mouse(mouse) => {
+                let area = self.area.filter(|area| area.contains(Position::new(mouse.column, mouse.row)))?;
+                let index = self.offset + usize::from(mouse.row - area.y);
+                match mouse.kind {
+                    MouseEventKind::Down(MouseButton::Left) if index < routes.len() => {
+                        self.cursor = index;
+                        Self::navigate(&routes, index)
+                    }
+                    MouseEventKind::ScrollUp => {
+                        self.move_by(-1, routes.len());
+                        None
+                    }
+                    MouseEventKind::ScrollDown => {
+                        self.move_by(1, routes.len());
+                        None
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+    use crossterm::event::MouseEvent;
+
+    #[test]
+    fn test_sidebar_follows_routes() {
+        let mut harness = Harness::with_size(Sidebar::new(), 14, 3);
+        harness.app().register_routes(["Menu", "Monitor", "Timer"]);
+        harness.app().describe_route(RouteInfo::new("Monitor").icon("#").label("Stats"));
+        harness.app().set_active_route("Timer");
+        harness.render();
+        assert_eq!(harness.text(), " Menu\n # Stats\n Timer");
+        assert_eq!(harness.read(|sidebar| sidebar.cursor()), 2);
+
+        assert_eq!(harness.type_str("k<Enter>"), vec![Action::Navigate("Monitor".into())]);
+        let click = MouseEvent { kind: MouseEventKind::Down(MouseButton::Left), column: 3, row: 0, modifiers: KeyModifiers::NONE };
+        assert_eq!(harness.send(Event::Mouse(click)), Some(Action::Navigate("Menu".into())));
+
+        harness.type_str("<C-b>");
+        assert_eq!(harness.text(), " M\n #\n T");
+    }
+}
@@ -0,0 +1,353 @@
+//! Two components side by side (or stacked) with a movable divider.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::layout::{Direction, Position, Rect};
+use ratatui::style::{Modifier, Style};
+
+use crate::component::traits::{Action, Component, Event};
+use crate::{ChildView, Context, Entity, EventContext};
+
+/// Which pane of a `SplitPane`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Pane {
+    #[default]
+    First,
+    Second,
+}
+
+/// Two child components split by a one-cell divider.
+///
+/// The share of the first pane is kept in an `Entity<f32>` (0.0–1.0), so sharing one
+/// entity between rebuilt panes, or persisting it, keeps the layout across navigation.
+/// Drag the divider with the mouse, or press `Ctrl+←/→` (`Ctrl+↑/↓` when stacked) to
+/// move it; see `resize_keys`. Keys go to the focused pane, which is the one last
+/// clicked.
+///
+/// # Example
+/// ```ignore
+/// // in the page struct
+/// split: ChildView<SplitPane<FileTree, Editor>>,
+///
+/// // in on_mount, sharing the ratio through app state
+/// let ratio = cx.get_keyed::<Entity<f32>>("editor.split").unwrap_or_else(|_| {
+///     let ratio = Entity::new(0.3);
+///     cx.set_keyed("editor.split", ratio.clone());
+///     ratio
+/// });
+/// self.split = ChildView::new(SplitPane::horizontal(FileTree::default(), Editor::default()).ratio_entity(ratio));
+///
+/// // in render
+/// cx.mount_child(&mut self.split, frame, area);
+/// ```
+pub struct SplitPane<A, B> {
+    first: ChildView<A>,
+    second: ChildView<B>,
+    direction: Direction,
+    ratio: Entity<f32>,
+    min_size: u16,
+    step: f32,
+    shrink_key: KeyEvent,
+    grow_key: KeyEvent,
+    focus: Pane,
+    divider: Option<Rect>,
+    area: Option<Rect>,
+    dragging: bool,
+    divider_style: Style,
+}
+
+impl<A: Component, B: Component> SplitPane<A, B> {
+    /// Split `direction`-wise: `Direction::Horizontal` puts `first` left of `second`,
+    /// `Direction::Vertical` puts it above. Starts at an even split.
+    pub fn new(direction: Direction, first: A, second: B) -> Self {
+        let (shrink, grow) = match direction {
+            Direction::Horizontal => (KeyCode::Left, KeyCode::Right),
+            Direction::Vertical => (KeyCode::Up, KeyCode::Down),
+        };
+        Self {
+            first: ChildView::new(first),
+            second: ChildView::new(second),
+            direction,
+            ratio: Entity::new(0.5),
+            min_size: 1,
+            step: 0.05,
+            shrink_key: KeyEvent::new(shrink, KeyModifiers::CONTROL),
+            grow_key: KeyEvent::new(grow, KeyModifiers::CONTROL),
+            focus: Pane::First,
+            divider: None,
+            area: None,
+            dragging: false,
+            divider_style: Style::default().add_modifier(Modifier::DIM),
+        }
+    }
+
+    /// `first` on the left, `second` on the right.
+    pub fn horizontal(first: A, second: B) -> Self {
+        Self::new(Direction::Horizontal, first, second)
+    }
+
+    /// `first` on top, `second` below.
+    pub fn vertical(first: A, second: B) -> Self {
+        Self::new(Direction::Vertical, first, second)
+    }
+
+    /// Keep the first pane's share in `ratio`, e.g. an entity shared with the page
+    /// that outlives this pane.
+    pub fn ratio_entity(mut self, ratio: Entity<f32>) -> Self {
+        self.ratio = ratio;
+        self
+    }
+
+    /// Smallest size of either pane, in cells (default 1).
+    pub fn min_size(mut self, cells: u16) -> Self {
+        self.min_size = cells;
+        self
+    }
+
+    /// How far one key press moves the divider, as a share of the total (default 0.05).
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Keys moving the divider towards the first and the second pane.
+    pub fn resize_keys(mut self, shrink: KeyEvent, grow: KeyEvent) -> Self {
+        self.shrink_key = shrink;
+        self.grow_key = grow;
+        self
+    }
+
+    /// Style of the divider; it is reversed while being dragged.
+    pub fn divider_style(mut self, style: Style) -> Self {
+        self.divider_style = style;
+        self
+    }
+
+    /// The first pane.
+    pub fn first(&self) -> &ChildView<A> {
+        &self.first
+    }
+
+    /// The first pane, mutably.
+    pub fn first_mut(&mut self) -> &mut ChildView<A> {
+        &mut self.first
+    }
+
+    /// The second pane.
+    pub fn second(&self) -> &ChildView<B> {
+        &self.second
+    }
+
+    /// The second pane, mutably.
+    pub fn second_mut(&mut self) -> &mut ChildView<B> {
+        &mut self.second
+    }
+
+    /// The entity holding the first pane's share.
+    pub fn ratio(&self) -> &Entity<f32> {
+        &self.ratio
+    }
+
+    /// The pane receiving keys.
+    pub fn focused(&self) -> Pane {
+        self.focus
+    }
+
+    /// Send keys to `pane`.
+    pub fn focus(&mut self, pane: Pane) {
+        self.focus = pane;
+    }
+
+    fn length(&self, area: Rect) -> u16 {
+        match self.direction {
+            Direction::Horizontal => area.width,
+            Direction::Vertical => area.height,
+        }
+    }
+
+    /// Size of the first pane for `total` cells of panes (divider excluded).
+    fn first_size(&self, total: u16) -> u16 {
+        let ratio = self.ratio.read(|ratio| *ratio).unwrap_or(0.5).clamp(0.0, 1.0);
+        let size = (f32::from(total) * ratio).round() as u16;
+        let min = self.min_size.min(total / 2);
+        size.clamp(min, total - min)
+    }
+
+    fn set_ratio(&self, ratio: f32) {
+        let _ = self.ratio.update(|r| *r = ratio.clamp(0.0, 1.0));
+    }
+
+    /// Move the divider to the cell at `column`/`row`.
+    fn drag_to(&self, column: u16, row: u16) {
+        let Some(area) = self.area else { return };
+        let total = self.length(area).saturating_sub(1);
+        if total == 0 {
+            return;
+        }
+        let offset = match self.direction {
+            Direction::Horizontal => column.saturating_sub(area.x),
+            Direction::Vertical => row.saturating_sub(area.y),
+        };
+        self.set_ratio(f32::from(offset.min(total)) / f32::from(total));
+    }
+
+    /// Split `area` into the first pane, the divider and the second pane.
+    fn split(&self, area: Rect) -> (Rect, Rect, Rect) {
+        let total = self.length(area).saturating_sub(1);
+        let first = self.first_size(total);
+        match self.direction {
+            Direction::Horizontal => (
+                Rect { width: first, ..area },
+                Rect { x: area.x + first, width: 1.min(area.width), ..area },
+                Rect { x: area.x + first + 1, width: total - first, ..area },
+            ),
+            Direction::Vertical => (
+                Rect { height: first, ..area },
+                Rect { y: area.y + first, height: 1.min(area.height), ..area },
+                Rect { y: area.y + first + 1, height: total - first, ..area },
+            ),
+        }
+    }
+}
+
+impl<A: Component, B: Component> Component for SplitPane<A, B> {
+    fn on_mount(&mut self, cx: &mut Context<Self>) {
+        self.first.on_mount(cx);
+        self.second.on_mount(cx);
+    }
+
+    fn on_enter(&mut self, cx: &mut Context<Self>) {
+        self.first.on_enter(cx);
+        self.second.on_enter(cx);
+    }
+
+    fn on_exit(&mut self, cx: &mut Context<Self>) {
+        self.dragging = false;
+        self.first.on_exit(cx);
+        self.second.on_exit(cx);
+    }
+
+    fn on_shutdown(&mut self, cx: &mut Context<Self>) {
+        self.first.on_shutdown(cx);
+        self.second.on_shutdown(cx);
+    }
+
+    fn on_resize(&mut self, width: u16, height: u16, cx: &mut Context<Self>) {
+        self.first.on_resize(width, height, cx);
+        self.second.on_resize(width, height, cx);
+    }
+
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let area = cx.area(frame);
+        self.area = Some(area);
+        cx.subscribe(&self.ratio);
+        let (first, divider, second) = self.split(area);
+        self.divider = Some(divider);
+        cx.mount_child(&mut self.first, frame, first);
+        cx.mount_child(&mut self.second, frame, second);
+
+        let symbol = match self.direction {
+            Direction::Horizontal => "│",
+            Direction::Vertical => "─",
+        };
+        let style = if self.dragging { self.divider_style.add_modifier(Modifier::REVERSED) } else { self.divider_style };
+        let buffer = frame.buffer_mut();
+        for position in divider.positions() {
+            buffer[position].set_symbol(symbol).set_style(style);
+        }
+    }
+
+    fn refresh_on_event(&self) -> bool {
+        true
+    }
+
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+        match &event {
+            Event::Key(key) if *key == self.shrink_key || *key == self.grow_key => {
+                let total = self.area.map_or(0, |area| self.length(area).saturating_sub(1));
+                let current = self.first_size(total);
+                let ratio = if total == 0 { 0.5 } else { f32::from(current) / f32::from(total) };
+                let delta = if *key == self.grow_key { self.step } else { -self.step };
+                self.set_ratio(ratio + delta);
+                return None;
+            }
+            Event::Key(_) => {
+                return match self.focus {
+                    Pane::First => self.first.handle_event(event, cx),
+                    Pane::Second => self.second.handle_event(event, cx),
+                };
+            }
+            Event::Mouse(mouse) => {
+                let position = Position::new(mouse.column, mouse.row);
+                match mouse.kind {
+                    MouseEventKind::Down(MouseButton::Left) if self.divider.is_some_and(|d| d.contains(position)) => {
+                        self.dragging = true;
+                        return None;
+                    }
+                    MouseEventKind::Drag(MouseButton::Left) if self.dragging => {
+                        self.drag_to(mouse.column, mouse.row);
+                        return None;
+                    }
+                    MouseEventKind::Up(_) if self.dragging => {
+                        self.dragging = false;
+                        return None;
+                    }
+                    MouseEventKind::Down(_) => {
+                        if self.first.contains(mouse.column, mouse.row) {
+                            self.focus = Pane::First;
+                        } else if self.second.contains(mouse.column, mouse.row) {
+                            self.focus = Pane::Second;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        let first = self.first.handle_event(event.clone(), cx);
+        self.second.handle_event(event, cx).or(first)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+    use crossterm::event::MouseEvent;
+
+    #[derive(Default)]
+    struct Fill(char);
+
+    impl Component for Fill {
+        fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+            let area = cx.area(frame);
+            for position in area.positions() {
+                frame.buffer_mut()[position].set_char(self.0);
+            }
+        }
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16) -> Event {
+        Event::Mouse(MouseEvent { kind, column, row: 0, modifiers: KeyModifiers::NONE })
+    }
+
+    #[tokio::test]
+    async fn test_divider_moves_with_keys_and_drag() {
+        let ratio = Entity::new(0.5);
+        let split = SplitPane::horizontal(Fill('a'), Fill('b')).ratio_entity(ratio.clone()).step(0.25);
+        let mut harness = Harness::with_size(split, 9, 1);
+        assert_eq!(harness.text(), "aaaa│bbbb");
+
+        harness.type_str("<C-Right>");
+        assert_eq!(harness.text(), "aaaaaa│bb");
+        assert_eq!(ratio.read(|r| *r).unwrap(), 0.75);
+
+        harness.send(mouse(MouseEventKind::Down(MouseButton::Left), 6));
+        harness.send(mouse(MouseEventKind::Drag(MouseButton::Left), 2));
+        harness.send(mouse(MouseEventKind::Up(MouseButton::Left), 2));
+        assert_eq!(harness.text(), "aa│bbbbbb");
+
+        // A new pane sharing the entity keeps the layout
+        let rebuilt = Harness::with_size(SplitPane::horizontal(Fill('a'), Fill('b')).ratio_entity(ratio), 9, 1);
+        assert_eq!(rebuilt.text(), "aa│bbbbbb");
+    }
+}
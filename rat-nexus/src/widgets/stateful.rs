@@ -0,0 +1,243 @@
+//! Adapter turning any ratatui `StatefulWidget` into a `Component`.
+
+use crossterm::event::{KeyCode, KeyEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use ratatui::widgets::{ListState, ScrollbarState, StatefulWidget, TableState};
+
+use crate::component::traits::{Action, Component, Event};
+use crate::{Context, Entity, EventContext};
+
+type NavigateFn<S> = Box<dyn Fn(&mut S, Nav) + Send + Sync>;
+type KeyFn<S> = Box<dyn FnMut(&mut S, KeyEvent) -> Option<Action> + Send + Sync>;
+
+/// A standard navigation step, produced from keys and the mouse wheel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nav {
+    Next,
+    Previous,
+    First,
+    Last,
+    /// Forward by a page of the given number of rows.
+    PageDown(u16),
+    /// Back by a page of the given number of rows.
+    PageUp(u16),
+}
+
+/// Widget states with built-in selection/scroll navigation.
+pub trait Navigate {
+    /// Apply a navigation step.
+    fn navigate(&mut self, nav: Nav);
+}
+
+impl Navigate for ListState {
+    fn navigate(&mut self, nav: Nav) {
+        match nav {
+            Nav::Next => self.select_next(),
+            Nav::Previous => self.select_previous(),
+            Nav::First => self.select_first(),
+            Nav::Last => self.select_last(),
+            Nav::PageDown(rows) => self.scroll_down_by(rows),
+            Nav::PageUp(rows) => self.scroll_up_by(rows),
+        }
+    }
+}
+
+impl Navigate for TableState {
+    fn navigate(&mut self, nav: Nav) {
+        match nav {
+            Nav::Next => self.select_next(),
+            Nav::Previous => self.select_previous(),
+            Nav::First => self.select_first(),
+            Nav::Last => self.select_last(),
+            Nav::PageDown(rows) => self.scroll_down_by(rows),
+            Nav::PageUp(rows) => self.scroll_up_by(rows),
+        }
+    }
+}
+
+impl Navigate for ScrollbarState {
+    fn navigate(&mut self, nav: Nav) {
+        match nav {
+            Nav::Next => self.next(),
+            Nav::Previous => self.prev(),
+            Nav::First => self.first(),
+            Nav::Last => self.last(),
+            Nav::PageDown(rows) => (0..rows).for_each(|_| self.next()),
+            Nav::PageUp(rows) => (0..rows).for_each(|_| self.prev()),
+        }
+    }
+}
+
+/// A ratatui `StatefulWidget` and its state, wrapped as a `Component`.
+///
+/// The state lives in an `Entity`, so other components can read or drive it (and
+/// subscribe to it). Bookkeeping the widget does while rendering (scroll offsets) does
+/// not notify subscribers. Navigation keys are `↓/j`, `↑/k`, `Home/g`, `End/G`,
+/// `PgDn/PgUp` and the mouse wheel; they apply to `ListState`, `TableState` and
+/// `ScrollbarState` via `navigable()`, or to any state via `navigate_with`.
+///
+/// # Example
+/// ```ignore
+/// let tree = StatefulWidgetComponent::new(Tree::new(&items)?, cx.new_entity(TreeState::default()))
+///     .navigate_with(|state, nav| match nav {
+///         Nav::Next => { state.key_down(); }
+///         Nav::Previous => { state.key_up(); }
+///         _ => {}
+///     })
+///     .on_key(|state, key| {
+///         if key.code == KeyCode::Enter { state.toggle_selected(); }
+///         None
+///     });
+///
+/// let list = StatefulWidgetComponent::new(List::new(names), cx.new_entity(ListState::default())).navigable();
+/// ```
+pub struct StatefulWidgetComponent<W>
+where
+    W: StatefulWidget,
+    W::State: Send + Sync,
+{
+    widget: W,
+    state: Entity<W::State>,
+    navigate: Option<NavigateFn<W::State>>,
+    on_key: Option<KeyFn<W::State>>,
+    area: Option<Rect>,
+}
+
+impl<W> StatefulWidgetComponent<W>
+where
+    W: StatefulWidget + Clone,
+    W::State: Send + Sync + 'static,
+{
+    /// Wrap `widget`, rendered with the state in `state`. No keys are handled until
+    /// `navigable`, `navigate_with` or `on_key` is set.
+    pub fn new(widget: W, state: Entity<W::State>) -> Self {
+        Self { widget, state, navigate: None, on_key: None, area: None }
+    }
+
+    /// Handle navigation keys with the state's built-in `Navigate` implementation.
+    pub fn navigable(self) -> Self
+    where
+        W::State: Navigate,
+    {
+        self.navigate_with(|state, nav| state.navigate(nav))
+    }
+
+    /// Handle navigation keys with `f`.
+    pub fn navigate_with<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut W::State, Nav) + Send + Sync + 'static,
+    {
+        self.navigate = Some(Box::new(f));
+        self
+    }
+
+    /// Handle other keys with `f`, which runs before navigation and may return an action.
+    pub fn on_key<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&mut W::State, KeyEvent) -> Option<Action> + Send + Sync + 'static,
+    {
+        self.on_key = Some(Box::new(f));
+        self
+    }
+
+    /// The widget.
+    pub fn widget(&self) -> &W {
+        &self.widget
+    }
+
+    /// Mutable access to the widget, e.g. to replace its items.
+    pub fn widget_mut(&mut self) -> &mut W {
+        &mut self.widget
+    }
+
+    /// The state entity.
+    pub fn state(&self) -> &Entity<W::State> {
+        &self.state
+    }
+
+    /// Apply a navigation step, if navigation is configured.
+    pub fn apply(&mut self, nav: Nav) -> bool {
+        let Some(navigate) = &self.navigate else {
+            return false;
+        };
+        self.state.update(|state| navigate(state, nav)).is_ok()
+    }
+
+    fn page(&self) -> u16 {
+        self.area.map_or(1, |area| area.height.max(1))
+    }
+
+    fn nav_for(&self, key: KeyEvent) -> Option<Nav> {
+        Some(match key.code {
+            KeyCode::Down | KeyCode::Char('j') => Nav::Next,
+            KeyCode::Up | KeyCode::Char('k') => Nav::Previous,
+            KeyCode::Home | KeyCode::Char('g') => Nav::First,
+            KeyCode::End | KeyCode::Char('G') => Nav::Last,
+            KeyCode::PageDown => Nav::PageDown(self.page()),
+            KeyCode::PageUp => Nav::PageUp(self.page()),
+            _ => return None,
+        })
+    }
+}
+
+impl<W> Component for StatefulWidgetComponent<W>
+where
+    W: StatefulWidget + Clone + Send + Sync + 'static,
+    W::State: Send + Sync + 'static,
+{
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let area = cx.area(frame);
+        self.area = Some(area);
+        let widget = self.widget.clone();
+        let _ = self.state.update_quietly(|state| frame.render_stateful_widget(widget, area, state));
+    }
+
+    fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+        match event {
+            Event::Key(key) => {
+                if let Some(on_key) = &mut self.on_key {
+                    if let Ok(Some(action)) = self.state.update(|state| on_key(state, key)) {
+                        return Some(action);
+                    }
+                }
+                if let Some(nav) = self.nav_for(key) {
+                    self.apply(nav);
+                }
+                None
+            }
+            Event::Mouse(mouse) if self.area.is_some_and(|a| a.contains(Position::new(mouse.column, mouse.row))) => {
+                match mouse.kind {
+                    MouseEventKind::ScrollDown => self.apply(Nav::Next),
+                    MouseEventKind::ScrollUp => self.apply(Nav::Previous),
+                    _ => false,
+                };
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+    use ratatui::widgets::List;
+
+    #[test]
+    fn test_list_adapter_navigates_entity_state() {
+        let state = Entity::new(ListState::default());
+        let list = List::new(["alpha", "beta", "gamma"]).highlight_symbol("> ");
+        let mut harness = Harness::with_size(StatefulWidgetComponent::new(list, state.clone()).navigable(), 12, 3);
+
+        let before = state.version();
+        harness.render();
+        assert_eq!(state.version(), before);
+
+        harness.type_str("jj");
+        assert_eq!(state.read(|s| s.selected()).unwrap(), Some(1));
+        harness.expect_text("> beta");
+        harness.type_str("G");
+        harness.expect_text("> gamma");
+    }
+}
@@ -0,0 +1,290 @@
+//! Segmented status line.
+//!
+//! Segments are grouped left, center and right. When the terminal is too narrow to show
+//! them all, the lowest-priority segments are hidden first. Segments can carry an id and
+//! an `Action` so clicks can be resolved through `StatusLineState` after rendering.
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::Span;
+use ratatui::widgets::{StatefulWidget, Widget};
+
+use crate::{Action, Density};
+
+/// One status line segment.
+#[derive(Debug, Clone)]
+pub struct Segment {
+    text: String,
+    style: Style,
+    priority: u8,
+    id: Option<String>,
+    action: Option<Action>,
+}
+
+impl Segment {
+    /// Create a segment. It is padded on each side according to the status line's density.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into(), style: Style::default(), priority: 0, id: None, action: None }
+    }
+
+    /// Set the segment style.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the priority. When space runs out, lower priorities are hidden first.
+    pub fn priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Name the segment so clicks on it can be identified.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Action returned by `StatusLineState::handle_mouse` when the segment is clicked.
+    pub fn on_click(mut self, action: Action) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    fn width(&self, padding: u16) -> u16 {
+        u16::try_from(Span::raw(&self.text).width())
+            .unwrap_or(u16::MAX)
+            .saturating_add(padding * 2)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Group {
+    Left,
+    Center,
+    Right,
+}
+
+/// A status line built from left, center and right segment groups.
+///
+/// # Example
+/// ```ignore
+/// let status = StatusLine::new()
+///     .style(Style::default().bg(Color::DarkGray))
+///     .left(Segment::new("NORMAL").style(mode_style).priority(10))
+///     .left(Segment::new(path).priority(5))
+///     .center(Segment::new("● modified").priority(1))
+///     .right(Segment::new("Help").id("help").on_click(Action::Navigate("help".into())))
+///     .right(Segment::new(format!("{}:{}", line, col)).priority(8));
+/// frame.render_stateful_widget(status, footer_area, &mut self.status_state);
+///
+/// // in handle_event
+/// if let Event::Mouse(mouse) = event {
+///     if let Some(action) = self.status_state.handle_mouse(mouse) {
+///         return Some(action);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StatusLine {
+    segments: Vec<(Group, Segment)>,
+    style: Style,
+    density: Density,
+}
+
+impl StatusLine {
+    /// Create an empty status line.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the background style of the whole line.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the density, which controls segment padding.
+    pub fn density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Append a segment to the left group.
+    pub fn left(mut self, segment: Segment) -> Self {
+        self.segments.push((Group::Left, segment));
+        self
+    }
+
+    /// Append a segment to the center group.
+    pub fn center(mut self, segment: Segment) -> Self {
+        self.segments.push((Group::Center, segment));
+        self
+    }
+
+    /// Append a segment to the right group.
+    pub fn right(mut self, segment: Segment) -> Self {
+        self.segments.push((Group::Right, segment));
+        self
+    }
+
+    /// Indices of the segments that fit in `width`, dropping low priorities first
+    /// (later segments go first among equal priorities).
+    fn fit(&self, width: u16) -> Vec<usize> {
+        let padding = self.density.inline_padding();
+        let mut visible: Vec<usize> = (0..self.segments.len()).collect();
+        let total = |visible: &[usize]| -> u32 {
+            visible.iter().map(|&i| u32::from(self.segments[i].1.width(padding))).sum()
+        };
+        while total(&visible) > u32::from(width) {
+            let Some(pos) = visible
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, &i)| (self.segments[i].1.priority, std::cmp::Reverse(i)))
+                .map(|(pos, _)| pos)
+            else {
+                break;
+            };
+            visible.remove(pos);
+        }
+        visible
+    }
+}
+
+/// Click targets of the last rendered status line.
+#[derive(Debug, Clone, Default)]
+pub struct StatusLineState {
+    hits: Vec<Hit>,
+}
+
+#[derive(Debug, Clone)]
+struct Hit {
+    area: Rect,
+    id: Option<String>,
+    action: Option<Action>,
+}
+
+impl StatusLineState {
+    /// Id of the segment at the given cell, if any.
+    pub fn segment_at(&self, column: u16, row: u16) -> Option<&str> {
+        self.hit(column, row).and_then(|hit| hit.id.as_deref())
+    }
+
+    /// Resolve a left click to the clicked segment's action.
+    pub fn handle_mouse(&self, mouse: MouseEvent) -> Option<Action> {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return None;
+        }
+        self.hit(mouse.column, mouse.row).and_then(|hit| hit.action.clone())
+    }
+
+    fn hit(&self, column: u16, row: u16) -> Option<&Hit> {
+        self.hits
+            .iter()
+            .find(|hit| hit.area.contains(ratatui::layout::Position::new(column, row)))
+    }
+}
+
+impl StatefulWidget for StatusLine {
+    type State = StatusLineState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        state.hits.clear();
+        if area.is_empty() {
+            return;
+        }
+        let area = Rect { height: 1, ..area };
+        buf.set_style(area, self.style);
+
+        let padding = self.density.inline_padding();
+        let visible = self.fit(area.width);
+        let group_width = |group: Group| -> u16 {
+            visible
+                .iter()
+                .filter(|&&i| self.segments[i].0 == group)
+                .map(|&i| self.segments[i].1.width(padding))
+                .sum()
+        };
+        let left_width = group_width(Group::Left);
+        let center_width = group_width(Group::Center);
+        let right_width = group_width(Group::Right);
+
+        let centered = area.x + area.width.saturating_sub(center_width) / 2;
+        let center_start = centered
+            .max(area.x + left_width)
+            .min(area.right().saturating_sub(right_width + center_width));
+        let mut cursors = [
+            (Group::Left, area.x),
+            (Group::Center, center_start),
+            (Group::Right, area.right() - right_width),
+        ];
+
+        for &i in &visible {
+            let (group, segment) = &self.segments[i];
+            let Some((_, x)) = cursors.iter_mut().find(|(g, _)| g == group) else {
+                continue;
+            };
+            let width = segment.width(padding);
+            let segment_area = Rect::new(*x, area.y, width, 1);
+            buf.set_style(segment_area, segment.style);
+            buf.set_stringn(*x + padding, area.y, &segment.text, usize::from(width - padding * 2), segment.style);
+            state.hits.push(Hit { area: segment_area, id: segment.id.clone(), action: segment.action.clone() });
+            *x += width;
+        }
+    }
+}
+
+impl Widget for StatusLine {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        StatefulWidget::render(self, area, buf, &mut StatusLineState::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(buf: &Buffer) -> String {
+        (0..buf.area.width).map(|x| buf[(x, 0)].symbol().to_string()).collect()
+    }
+
+    #[test]
+    fn test_groups_are_aligned() {
+        let area = Rect::new(0, 0, 30, 1);
+        let mut buf = Buffer::empty(area);
+        let status = StatusLine::new()
+            .left(Segment::new("L"))
+            .center(Segment::new("C"))
+            .right(Segment::new("R"));
+        Widget::render(status.clone(), area, &mut buf);
+        assert_eq!(row(&buf), " L            C             R ");
+
+        let mut buf = Buffer::empty(area);
+        Widget::render(status.density(Density::Compact), area, &mut buf);
+        assert_eq!(row(&buf), "L             C              R");
+    }
+
+    #[test]
+    fn test_low_priority_dropped_when_narrow() {
+        let area = Rect::new(0, 0, 12, 1);
+        let mut buf = Buffer::empty(area);
+        let mut state = StatusLineState::default();
+        let status = StatusLine::new()
+            .left(Segment::new("MODE").priority(9))
+            .left(Segment::new("file.rs").priority(1))
+            .right(Segment::new("1:1").priority(5).id("pos").on_click(Action::Back));
+        StatefulWidget::render(status, area, &mut buf, &mut state);
+        assert_eq!(row(&buf), " MODE   1:1 ");
+        assert_eq!(state.segment_at(9, 0), Some("pos"));
+
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 10,
+            row: 0,
+            modifiers: crossterm::event::KeyModifiers::NONE,
+        };
+        assert_eq!(state.handle_mouse(click), Some(Action::Back));
+    }
+}
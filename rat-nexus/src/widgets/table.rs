@@ -0,0 +1,1077 @@
+//! Data table with column definitions, multi-select, bulk actions and inline editing.
+//!
+//! Unlike ratatui's `Table`, `DataTable` lays out only the columns that fit the
+//! viewport: the header and footer rows stay put while rows scroll, and pinned left
+//! columns stay put while the remaining columns scroll horizontally.
+//!
+//! Sorting and filtering produce a display order (`view`) over the rows. For large
+//! tables the work runs on tokio's blocking pool, so typing into a filter box stays
+//! responsive; each new sort or filter cancels the one still running.
+
+use std::cmp::Ordering;
+use std::sync::atomic::{AtomicBool, AtomicUsize};
+use std::sync::Arc;
+
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Widget};
+
+use super::edit::{CellEditor, EditOutcome, FieldKind};
+use super::list::{row_style, ListCore, ListEvent};
+use super::table_job::{compute_view, CellFn, CompareFn, ViewJob, ViewSpec};
+use super::Selection;
+use crate::{AppContext, Event};
+
+/// Row count above which sorting and filtering run off the UI thread, by default.
+pub const DEFAULT_OFFLOAD_THRESHOLD: usize = 10_000;
+type ApplyFn<T> = Box<dyn Fn(&mut T, &str) -> Result<(), String> + Send + Sync>;
+type CommitFn = Box<dyn FnMut(usize, usize, &str) + Send + Sync>;
+type FooterFn<T> = Box<dyn Fn(&[T]) -> String + Send + Sync>;
+
+/// A table column: header, width and how to display a row's value.
+pub struct Column<T> {
+    header: String,
+    width: Constraint,
+    cell: CellFn<T>,
+    compare: Option<CompareFn<T>>,
+    edit: Option<(FieldKind, ApplyFn<T>)>,
+    footer: Option<FooterFn<T>>,
+}
+
+impl<T> Column<T> {
+    /// Create a column.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Column::new("Name", Constraint::Fill(1), |p: &Process| p.name.clone())
+    /// ```
+    pub fn new<F>(header: impl Into<String>, width: Constraint, cell: F) -> Self
+    where
+        F: Fn(&T) -> String + Send + Sync + 'static,
+    {
+        Self { header: header.into(), width, cell: Arc::new(cell), compare: None, edit: None, footer: None }
+    }
+
+    /// Allow sorting by this column, comparing the displayed cell text.
+    pub fn sortable(mut self) -> Self
+    where
+        T: 'static,
+    {
+        let cell = self.cell.clone();
+        self.compare = Some(Arc::new(move |a, b| cell(a).cmp(&cell(b))));
+        self
+    }
+
+    /// Allow sorting by this column with a custom comparison, e.g. numerically.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Column::new("CPU", Constraint::Length(6), |p: &Process| format!("{:.1}", p.cpu))
+    ///     .sort_by(|a, b| a.cpu.total_cmp(&b.cpu))
+    /// ```
+    pub fn sort_by<F>(mut self, compare: F) -> Self
+    where
+        F: Fn(&T, &T) -> Ordering + Send + Sync + 'static,
+    {
+        self.compare = Some(Arc::new(compare));
+        self
+    }
+
+    /// Whether the table can be sorted by this column.
+    pub fn is_sortable(&self) -> bool {
+        self.compare.is_some()
+    }
+
+    /// Show a summary of all rows in a footer row that stays visible while scrolling.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Column::new("Size", Constraint::Length(8), |f: &File| f.size.to_string())
+    ///     .footer(|files| files.iter().map(|f| f.size).sum::<u64>().to_string())
+    /// ```
+    pub fn footer<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&[T]) -> String + Send + Sync + 'static,
+    {
+        self.footer = Some(Box::new(f));
+        self
+    }
+
+    /// Make the column editable in place. `apply` validates the edited value and writes
+    /// it into the row; an `Err` is shown under the cell and keeps the editor open.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Column::new("Qty", Constraint::Length(5), |i: &Item| i.qty.to_string())
+    ///     .editable(FieldKind::Number, |i: &mut Item, v| {
+    ///         i.qty = v.parse().map_err(|_| "whole numbers only".to_string())?;
+    ///         Ok(())
+    ///     })
+    /// ```
+    pub fn editable<F>(mut self, kind: FieldKind, apply: F) -> Self
+    where
+        F: Fn(&mut T, &str) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.edit = Some((kind, Box::new(apply)));
+        self
+    }
+
+    /// Whether the column can be edited in place.
+    pub fn is_editable(&self) -> bool {
+        self.edit.is_some()
+    }
+
+    /// The column header.
+    pub fn header(&self) -> &str {
+        &self.header
+    }
+
+    /// Display the cell of `row` in this column.
+    pub fn cell(&self, row: &T) -> String {
+        (self.cell)(row)
+    }
+
+    /// Width the column needs before it is worth showing, given the full table width.
+    fn natural_width(&self, total: u16) -> u16 {
+        match self.width {
+            Constraint::Length(n) | Constraint::Min(n) | Constraint::Max(n) => n,
+            Constraint::Percentage(p) => (u32::from(total) * u32::from(p) / 100) as u16,
+            Constraint::Ratio(a, b) => (u64::from(total) * u64::from(a) / u64::from(b.max(1))) as u16,
+            Constraint::Fill(_) => (self.header.chars().count() as u16).max(8),
+        }
+    }
+}
+
+/// A table over typed rows with a header, cursor, multi-item marking and key-bound
+/// bulk actions. Navigation and marking keys are the same as `ListView`.
+///
+/// When some columns are `editable`, `←/→` (or a click) focuses a cell and `Enter`
+/// edits it in place with a field editor matching its `FieldKind`. `Enter` commits
+/// after validation, `Esc` cancels. Committed edits are reported as
+/// `ListEvent::Edited` and to the `on_commit` callback.
+///
+/// The header row (and the footer row, if any column has a `footer`) never scroll.
+/// When the columns do not all fit, the first `pin_columns` columns stay visible and
+/// `←/→` scrolls the others, with `◀`/`▶` marking hidden columns.
+///
+/// Clicking the header of a `sortable` column, or pressing the `sort_key` while it is
+/// focused, toggles sorting by it; `set_filter`
+/// hides rows whose cells do not contain the query. Selection and the cursor work on
+/// the displayed order, while row indices reported in events (`Activated`, `Bulk`,
+/// `Edited`) and taken by `remove`/`begin_edit` index `get_rows()`. With `offload`,
+/// tables above the threshold sort and filter in the background and show progress in
+/// the header until the new order is ready.
+///
+/// Only the rows in the viewport are drawn, and their cells are formatted as they
+/// are drawn, so a table over 100k rows renders as cheaply as one that fits the
+/// screen.
+///
+/// # Example
+/// ```ignore
+/// let mut table = DataTable::new(vec![
+///         Column::new("PID", Constraint::Length(6), |p: &Process| p.pid.to_string()),
+///         Column::new("Name", Constraint::Fill(1), |p: &Process| p.name.clone()),
+///     ])
+///     .rows(processes)
+///     .bind(KeyCode::Char('k'), "Kill", ProcOp::Kill);
+///
+/// table.render(area, frame.buffer_mut());
+/// ```
+pub struct DataTable<T, A = ()> {
+    columns: Vec<Column<T>>,
+    rows: Arc<Vec<T>>,
+    view: Vec<usize>,
+    filter: String,
+    sort: Option<(usize, bool)>,
+    dirty: bool,
+    offload: Option<(AppContext, usize)>,
+    job: Option<ViewJob>,
+    core: ListCore<A>,
+    block: Option<Block<'static>>,
+    style: Style,
+    header_style: Style,
+    highlight_style: Style,
+    column: usize,
+    sort_key: Option<KeyCode>,
+    pinned: usize,
+    h_offset: usize,
+    overflow: bool,
+    column_areas: Vec<(usize, Rect)>,
+    editing: Option<(usize, usize, CellEditor)>,
+    on_commit: Option<CommitFn>,
+}
+
+/// `DataTable` under the name matching `ListView`.
+pub type TableView<T, A = ()> = DataTable<T, A>;
+
+impl<T, A> DataTable<T, A>
+where
+    T: Send + Sync + 'static,
+    A: Clone,
+{
+    /// Create an empty table with the given columns.
+    pub fn new(columns: Vec<Column<T>>) -> Self {
+        Self {
+            columns,
+            rows: Arc::new(Vec::new()),
+            view: Vec::new(),
+            filter: String::new(),
+            sort: None,
+            dirty: false,
+            offload: None,
+            job: None,
+            core: ListCore::default(),
+            block: None,
+            style: Style::default(),
+            header_style: Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            highlight_style: Style::default().add_modifier(Modifier::REVERSED),
+            column: 0,
+            sort_key: None,
+            pinned: 0,
+            h_offset: 0,
+            overflow: false,
+            column_areas: Vec::new(),
+            editing: None,
+            on_commit: None,
+        }
+    }
+
+    /// Keep the first `n` columns visible while the others scroll horizontally.
+    pub fn pin_columns(mut self, n: usize) -> Self {
+        self.pinned = n;
+        self
+    }
+
+    /// Toggle sorting by the focused column when `key` is pressed. `←/→` focus columns
+    /// while a sort key is set.
+    pub fn sort_key(mut self, key: KeyCode) -> Self {
+        self.sort_key = Some(key);
+        self
+    }
+
+    /// Sort and filter tables with more than `DEFAULT_OFFLOAD_THRESHOLD` rows on tokio's
+    /// blocking pool, refreshing the app when the new order is ready.
+    ///
+    /// # Example
+    /// ```ignore
+    /// fn on_mount(&mut self, cx: &mut Context<Self>) {
+    ///     self.table = DataTable::new(columns()).offload(cx.app()).rows(load_log());
+    /// }
+    /// ```
+    pub fn offload(mut self, app: &AppContext) -> Self {
+        self.offload = Some((app.clone(), DEFAULT_OFFLOAD_THRESHOLD));
+        self
+    }
+
+    /// Change the row count above which work is offloaded. Requires `offload`.
+    pub fn offload_threshold(mut self, rows: usize) -> Self {
+        if let Some((_, threshold)) = &mut self.offload {
+            *threshold = rows;
+        }
+        self
+    }
+
+    /// Call `f(row, column, value)` after an edit has been validated and applied.
+    pub fn on_commit<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(usize, usize, &str) + Send + Sync + 'static,
+    {
+        self.on_commit = Some(Box::new(f));
+        self
+    }
+
+    /// Set the initial rows.
+    pub fn rows(mut self, rows: Vec<T>) -> Self {
+        self.set_rows(rows);
+        self
+    }
+
+    /// Wrap the table in a block.
+    pub fn block(mut self, block: Block<'static>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Set the base row style.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the header style.
+    pub fn header_style(mut self, style: Style) -> Self {
+        self.header_style = style;
+        self
+    }
+
+    /// Set the style patched onto the cursor row.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Bind a bulk action that asks for confirmation with the row count.
+    pub fn bind(mut self, key: KeyCode, label: impl Into<String>, action: A) -> Self {
+        self.core.bulk.bind(key, label, action);
+        self
+    }
+
+    /// Bind a bulk action that dispatches without confirmation.
+    pub fn bind_immediate(mut self, key: KeyCode, label: impl Into<String>, action: A) -> Self {
+        self.core.bulk.bind_immediate(key, label, action);
+        self
+    }
+
+    /// The columns.
+    pub fn columns(&self) -> &[Column<T>] {
+        &self.columns
+    }
+
+    /// All rows, in insertion order regardless of sorting and filtering.
+    pub fn get_rows(&self) -> &[T] {
+        &self.rows
+    }
+
+    /// Indices into `get_rows()` of the displayed rows, in display order.
+    pub fn view(&self) -> &[usize] {
+        &self.view
+    }
+
+    /// Replace the rows, clearing marks.
+    pub fn set_rows(&mut self, rows: Vec<T>) {
+        self.rows = Arc::new(rows);
+        self.view = (0..self.rows.len()).collect();
+        self.core.selection.clear_marks();
+        self.core.selection.set_len(self.view.len());
+        self.refresh_view();
+    }
+
+    /// Filter to rows with a cell containing `query`, ignoring case. An empty query shows all rows.
+    pub fn set_filter(&mut self, query: &str) {
+        let query = query.to_lowercase();
+        if query != self.filter {
+            self.filter = query;
+            self.refresh_view();
+        }
+    }
+
+    /// The current filter, lowercased.
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Sort by column `col`. Does nothing unless the column is `sortable`.
+    pub fn sort_by_column(&mut self, col: usize, ascending: bool) {
+        if self.columns.get(col).is_some_and(Column::is_sortable) {
+            self.sort = Some((col, ascending));
+            self.refresh_view();
+        }
+    }
+
+    /// Sort by `col` ascending, or flip the direction if already sorted by it.
+    pub fn toggle_sort(&mut self, col: usize) {
+        let ascending = !matches!(self.sort, Some((c, true)) if c == col);
+        self.sort_by_column(col, ascending);
+    }
+
+    /// Return to insertion order.
+    pub fn clear_sort(&mut self) {
+        if self.sort.take().is_some() {
+            self.refresh_view();
+        }
+    }
+
+    /// The sort column and whether it is ascending.
+    pub fn sort(&self) -> Option<(usize, bool)> {
+        self.sort
+    }
+
+    /// Whether a background sort or filter is still running.
+    pub fn is_busy(&self) -> bool {
+        self.job.is_some()
+    }
+
+    /// Recompute the display order, in the background if offloading applies.
+    fn refresh_view(&mut self) {
+        self.dirty = false;
+        let mut spec = ViewSpec {
+            filter: self.filter.clone(),
+            cells: self.columns.iter().map(|c| c.cell.clone()).collect(),
+            sort: self.sort.and_then(|(col, asc)| Some((self.columns.get(col)?.compare.clone()?, asc))),
+        };
+        if let Some((app, threshold)) = &self.offload {
+            if self.rows.len() > *threshold {
+                // Replacing the job drops, and so cancels, the one still running
+                match ViewJob::spawn(self.rows.clone(), spec, app) {
+                    Ok(job) => {
+                        self.job = Some(job);
+                        return;
+                    }
+                    Err(returned) => {
+                        log::warn!("DataTable: no tokio runtime, sorting {} rows inline", self.rows.len());
+                        spec = returned;
+                    }
+                }
+            }
+        }
+        self.job = None;
+        let (cancel, progress) = (AtomicBool::new(false), AtomicUsize::new(0));
+        if let Some(view) = compute_view(&self.rows, &spec, &cancel, &progress) {
+            self.set_view(view);
+        }
+    }
+
+    /// Pick up a finished background job or re-apply sorting after `rows_mut`.
+    fn apply_pending(&mut self) {
+        if self.dirty {
+            self.refresh_view();
+        }
+        if let Some(view) = self.job.as_ref().and_then(ViewJob::try_take) {
+            self.job = None;
+            self.set_view(view);
+        }
+    }
+
+    /// Switch to a new display order, keeping marks and the cursor on the same rows.
+    fn set_view(&mut self, view: Vec<usize>) {
+        let selection = &mut self.core.selection;
+        selection.set_len(self.view.len());
+        let cursor_row = selection.cursor().and_then(|p| self.view.get(p).copied());
+        let mut marked = vec![false; self.rows.len()];
+        for p in selection.marked() {
+            if let Some(&row) = self.view.get(p) {
+                marked[row] = true;
+            }
+        }
+
+        self.view = view;
+        selection.clear_marks();
+        selection.set_len(self.view.len());
+        for (p, &row) in self.view.iter().enumerate() {
+            if marked.get(row) == Some(&true) {
+                selection.toggle(p);
+            }
+        }
+        let cursor = cursor_row.and_then(|row| self.view.iter().position(|&r| r == row));
+        selection.select(cursor.unwrap_or(0));
+    }
+
+    /// The selection model.
+    pub fn selection(&self) -> &Selection {
+        &self.core.selection
+    }
+
+    /// Mutable access to the selection model.
+    pub fn selection_mut(&mut self) -> &mut Selection {
+        &mut self.core.selection
+    }
+
+    /// The row under the cursor.
+    pub fn selected_row(&self) -> Option<&T> {
+        self.core.selection.cursor().and_then(|p| self.rows.get(*self.view.get(p)?))
+    }
+
+    /// Bulk action key hints, e.g. for a footer.
+    pub fn hints(&self) -> Vec<(String, &str)> {
+        self.core.bulk.hints()
+    }
+
+    /// Whether a confirmation dialog is open (the table then captures all keys).
+    pub fn is_confirming(&self) -> bool {
+        self.core.bulk.is_pending()
+    }
+
+    /// Index of the focused column.
+    pub fn focused_column(&self) -> usize {
+        self.column
+    }
+
+    /// The cell being edited as `(row, column)`, if any.
+    pub fn editing(&self) -> Option<(usize, usize)> {
+        self.editing.as_ref().map(|(row, col, _)| (*row, *col))
+    }
+
+    /// Start editing the cell at `row` (into `get_rows()`), `col`. Returns false if it is
+    /// not editable or the row is filtered out.
+    pub fn begin_edit(&mut self, row: usize, col: usize) -> bool {
+        let (Some(data), Some(column)) = (self.rows.get(row), self.columns.get(col)) else {
+            return false;
+        };
+        let Some((kind, _)) = &column.edit else {
+            return false;
+        };
+        let Some(position) = self.view.iter().position(|&r| r == row) else {
+            return false;
+        };
+        let editor = CellEditor::new(kind.clone(), &column.cell(data));
+        self.column = col;
+        self.core.selection.select(position);
+        self.editing = Some((row, col, editor));
+        true
+    }
+
+    /// Abandon the current edit, if any.
+    pub fn cancel_edit(&mut self) {
+        self.editing = None;
+    }
+
+    /// Handle a key or mouse event.
+    pub fn handle_event(&mut self, event: &Event) -> Option<ListEvent<A>> {
+        self.apply_pending();
+        if let Some(handled) = self.handle_edit(event) {
+            return handled;
+        }
+        if let Event::Mouse(mouse) = event {
+            if mouse.kind == MouseEventKind::Down(MouseButton::Left) && !self.core.bulk.is_pending() {
+                let hit = Position::new(mouse.column, mouse.row);
+                if let Some(&(col, _)) = self.column_areas.iter().find(|(_, r)| r.contains(hit)) {
+                    self.toggle_sort(col);
+                    return None;
+                }
+            }
+        }
+        Some(match self.core.handle_event(event, self.view.len())? {
+            ListEvent::Activated(p) => ListEvent::Activated(self.view[p]),
+            ListEvent::Selected(p) => ListEvent::Selected(self.view[p]),
+            ListEvent::Bulk(mut bulk) => {
+                bulk.indices = bulk.indices.iter().map(|&p| self.view[p]).collect();
+                bulk.indices.sort_unstable();
+                ListEvent::Bulk(bulk)
+            }
+            other => other,
+        })
+    }
+
+    /// Editing keys and cell focus. `Some(_)` means the event was consumed.
+    fn handle_edit(&mut self, event: &Event) -> Option<Option<ListEvent<A>>> {
+        if let Some((_, _, editor)) = &mut self.editing {
+            return Some(match event {
+                Event::Key(key) => match editor.handle_key(*key) {
+                    EditOutcome::Pending => None,
+                    EditOutcome::Cancel => {
+                        self.editing = None;
+                        None
+                    }
+                    EditOutcome::Commit => self.commit(),
+                },
+                Event::Paste(text) => {
+                    editor.insert_str(text);
+                    None
+                }
+                _ => None,
+            });
+        }
+        if self.core.bulk.is_pending() || !self.focuses_cells() {
+            return None;
+        }
+        match event {
+            Event::Key(key) => match key.code {
+                KeyCode::Left => {
+                    self.focus_column(-1);
+                    Some(None)
+                }
+                KeyCode::Right => {
+                    self.focus_column(1);
+                    Some(None)
+                }
+                KeyCode::Enter if self.columns.get(self.column).is_some_and(Column::is_editable) => {
+                    let row = *self.view.get(self.core.selection.cursor()?)?;
+                    self.begin_edit(row, self.column);
+                    Some(None)
+                }
+                code if Some(code) == self.sort_key && !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                    self.toggle_sort(self.column);
+                    Some(None)
+                }
+                _ => None,
+            },
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    // Focus the clicked column, then let the row selection happen as usual
+                    let (_, first) = self.column_areas.first()?;
+                    let hit = Position::new(mouse.column, first.y);
+                    if mouse.row == first.y {
+                        return None;
+                    }
+                    if let Some((col, _)) = self.column_areas.iter().find(|(_, r)| r.contains(hit)) {
+                        self.column = *col;
+                    }
+                    None
+                }
+                MouseEventKind::ScrollLeft => {
+                    self.focus_column(-1);
+                    Some(None)
+                }
+                MouseEventKind::ScrollRight => {
+                    self.focus_column(1);
+                    Some(None)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Whether `←/→` move a focused column: it can be edited, scrolled to or sorted by.
+    fn focuses_cells(&self) -> bool {
+        self.overflow || self.sort_key.is_some() || self.columns.iter().any(Column::is_editable)
+    }
+
+    fn focus_column(&mut self, delta: isize) {
+        let last = self.columns.len().saturating_sub(1);
+        self.column = self.column.saturating_add_signed(delta).min(last);
+    }
+
+    /// Pick the columns to show in `width` cells: all of them if they fit, otherwise the
+    /// pinned ones plus as many scrolled ones as fit, scrolled to keep the focus visible.
+    fn visible_columns(&mut self, width: u16) -> Vec<usize> {
+        let count = self.columns.len();
+        let needed = |cols: &[usize]| -> u32 {
+            let widths: u32 = cols.iter().map(|&i| u32::from(self.columns[i].natural_width(width))).sum();
+            widths + cols.len().saturating_sub(1) as u32
+        };
+        let all: Vec<usize> = (0..count).collect();
+        self.overflow = needed(&all) > u32::from(width);
+        if !self.overflow {
+            return all;
+        }
+
+        let pinned = self.pinned.min(count);
+        if self.column >= pinned && self.column < self.h_offset {
+            self.h_offset = self.column;
+        }
+        self.h_offset = self.h_offset.clamp(pinned, count.saturating_sub(1).max(pinned));
+        loop {
+            let mut visible: Vec<usize> = (0..pinned).collect();
+            for i in self.h_offset..count {
+                visible.push(i);
+                if needed(&visible) > u32::from(width) {
+                    // Always show at least one scrolled column, squeezed if need be
+                    if visible.len() > pinned + 1 {
+                        visible.pop();
+                    }
+                    break;
+                }
+            }
+            if self.column < pinned || visible.contains(&self.column) || self.h_offset >= self.column {
+                return visible;
+            }
+            self.h_offset += 1;
+        }
+    }
+
+    fn commit(&mut self) -> Option<ListEvent<A>> {
+        let (row, col, editor) = self.editing.as_mut()?;
+        let (row, col) = (*row, *col);
+        let value = editor.value();
+        let applied = editor.validate().and_then(|()| {
+            let (_, apply) = self.columns[col].edit.as_ref().ok_or("column is not editable")?;
+            // Dropping the job cancels it; its copy of the rows goes once it notices
+            self.job = None;
+            let rows = Arc::get_mut(&mut self.rows).ok_or("rows are still being sorted, try again")?;
+            let data = rows.get_mut(row).ok_or("row no longer exists")?;
+            apply(data, &value)
+        });
+        if let Err(error) = applied {
+            editor.set_error(error);
+            return None;
+        }
+        self.editing = None;
+        if let Some(on_commit) = &mut self.on_commit {
+            on_commit(row, col, &value);
+        }
+        Some(ListEvent::Edited { row, col, value })
+    }
+
+    /// Render the table into `area`.
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        self.apply_pending();
+        let inner = match &self.block {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.clone().render(area, buf);
+                inner
+            }
+            None => area,
+        };
+        if inner.height == 0 || inner.width <= 2 {
+            return;
+        }
+
+        // Two cells for the mark column, then the data columns
+        self.column = self.column.min(self.columns.len().saturating_sub(1));
+        let visible = self.visible_columns(inner.width - 2);
+        let columns_area = Rect::new(inner.x + 2, inner.y, inner.width - 2, 1);
+        let widths = Layout::horizontal(visible.iter().map(|&i| self.columns[i].width))
+            .spacing(1)
+            .split(columns_area);
+        self.column_areas = visible.iter().copied().zip(widths.iter().copied()).collect();
+        let focus_cells = self.focuses_cells();
+
+        buf.set_style(Rect::new(inner.x, inner.y, inner.width, 1), self.header_style);
+        for &(col, cell) in &self.column_areas {
+            let header = match self.sort {
+                Some((sorted, ascending)) if sorted == col => {
+                    format!("{} {}", self.columns[col].header, if ascending { "▲" } else { "▼" })
+                }
+                _ => self.columns[col].header.clone(),
+            };
+            buf.set_stringn(cell.x, inner.y, header, cell.width as usize, self.header_style);
+        }
+        if self.overflow {
+            let pinned = self.pinned.min(self.columns.len());
+            if self.h_offset > pinned {
+                buf.set_string(inner.x, inner.y, "◀", self.header_style);
+            }
+            if visible.last().is_some_and(|&last| last + 1 < self.columns.len()) {
+                buf.set_string(inner.right() - 1, inner.y, "▶", self.header_style);
+            }
+        }
+
+        if let Some(job) = &self.job {
+            let label = format!(" ⟳ {} ", job.label());
+            let width = label.chars().count() as u16;
+            let x = inner.right().saturating_sub(width).max(inner.x);
+            buf.set_stringn(x, inner.y, label, inner.width as usize, self.header_style.add_modifier(Modifier::REVERSED));
+        }
+
+        let has_footer = self.columns.iter().any(|c| c.footer.is_some());
+        let footer_height = u16::from(has_footer && inner.height > 2);
+        let rows_area = Rect::new(inner.x, inner.y + 1, inner.width, inner.height - 1 - footer_height);
+        self.core.rows_area = rows_area;
+        self.core.scroll_into_view(self.view.len(), rows_area.height as usize);
+
+        let cursor = self.core.selection.cursor();
+        for (line, index) in (self.core.offset..self.view.len()).take(rows_area.height as usize).enumerate() {
+            let y = rows_area.y + line as u16;
+            let marked = self.core.selection.is_marked(index);
+            let style = row_style(self.style, self.highlight_style, cursor == Some(index), marked);
+            let row = &self.rows[self.view[index]];
+            buf.set_style(Rect::new(rows_area.x, y, rows_area.width, 1), style);
+            buf.set_stringn(rows_area.x, y, if marked { "● " } else { "  " }, 2, style);
+            for &(col, cell) in &self.column_areas {
+                let cell_style = if focus_cells && cursor == Some(index) && col == self.column {
+                    style.add_modifier(Modifier::UNDERLINED | Modifier::BOLD)
+                } else {
+                    style
+                };
+                buf.set_stringn(cell.x, y, self.columns[col].cell(row), cell.width as usize, cell_style);
+            }
+        }
+
+        if footer_height == 1 {
+            let y = rows_area.bottom();
+            buf.set_style(Rect::new(inner.x, y, inner.width, 1), self.header_style);
+            for &(col, cell) in &self.column_areas {
+                if let Some(footer) = &self.columns[col].footer {
+                    buf.set_stringn(cell.x, y, footer(&self.rows), cell.width as usize, self.header_style);
+                }
+            }
+        }
+
+        if let Some((row, col, mut editor)) = self.editing.take() {
+            self.render_editor(row, col, &mut editor, rows_area, buf);
+            self.editing = Some((row, col, editor));
+        }
+
+        self.core.bulk.render_dialog(area, buf);
+    }
+
+    fn render_editor(&self, row: usize, col: usize, editor: &mut CellEditor, rows: Rect, buf: &mut Buffer) {
+        let Some(position) = self.core.selection.cursor().filter(|&p| self.view.get(p) == Some(&row)) else {
+            return;
+        };
+        let Some(line) = position.checked_sub(self.core.offset).filter(|&l| l < rows.height as usize) else {
+            return;
+        };
+        let Some(&(_, column)) = self.column_areas.iter().find(|(c, _)| *c == col) else {
+            return;
+        };
+        let y = rows.y + line as u16;
+        let cell = Rect::new(column.x, y, column.width, 1);
+        buf.set_style(Rect::new(rows.x, y, rows.width, 1), self.style);
+        let editor_style = Style::default().fg(Color::Black).bg(Color::Yellow);
+        editor.render(cell, buf, editor_style);
+
+        if let Some(error) = editor.error() {
+            // Show the error below the cell, or above it on the last visible row
+            let error_y = if y + 1 < rows.bottom() { y + 1 } else { y.saturating_sub(1) };
+            let error_area = Rect::new(cell.x, error_y, rows.right().saturating_sub(cell.x), 1);
+            buf.set_style(error_area, Style::default().fg(Color::White).bg(Color::Red));
+            buf.set_stringn(error_area.x, error_y, format!(" {error} "), error_area.width as usize, Style::default());
+        }
+    }
+}
+
+/// Row mutators. The rows are shared with a running background sort, so these copy
+/// them if that sort has not let go yet.
+impl<T, A> DataTable<T, A>
+where
+    T: Clone + Send + Sync + 'static,
+    A: Clone,
+{
+    /// Mutable access to the rows. Sorting and filtering are re-applied on the next event
+    /// or render.
+    pub fn rows_mut(&mut self) -> &mut Vec<T> {
+        self.dirty = true;
+        self.job = None;
+        Arc::make_mut(&mut self.rows)
+    }
+
+    /// Remove the rows at `indices` into `get_rows()` (e.g. from a `BulkAction`), returning them.
+    pub fn remove(&mut self, indices: &[usize]) -> Vec<T> {
+        self.apply_pending();
+        let mut sorted: Vec<usize> = indices.iter().copied().filter(|&i| i < self.rows.len()).collect();
+        sorted.sort_unstable();
+        sorted.dedup();
+        self.job = None;
+
+        let rows = Arc::make_mut(&mut self.rows);
+        let removed = sorted.iter().rev().map(|&i| rows.remove(i)).collect::<Vec<_>>();
+        let positions: Vec<usize> = (0..self.view.len()).filter(|&p| sorted.binary_search(&self.view[p]).is_ok()).collect();
+        self.core.selection.set_len(self.view.len());
+        self.core.selection.remove_indices(&positions);
+        self.view.retain(|i| sorted.binary_search(i).is_err());
+        for i in &mut self.view {
+            *i -= sorted.partition_point(|&r| r < *i);
+        }
+        removed.into_iter().rev().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyEvent;
+
+    #[test]
+    fn test_table_inline_edit_validates() {
+        let key = |code| Event::Key(KeyEvent::from(code));
+        let mut table: DataTable<(String, u32)> = DataTable::new(vec![
+            Column::new("Name", Constraint::Fill(1), |r: &(String, u32)| r.0.clone()),
+            Column::new("Qty", Constraint::Length(4), |r: &(String, u32)| r.1.to_string()).editable(
+                FieldKind::Number,
+                |r: &mut (String, u32), v| {
+                    r.1 = v.parse().map_err(|_| "whole numbers only".to_string())?;
+                    Ok(())
+                },
+            ),
+        ])
+        .rows(vec![("apples".into(), 3), ("pears".into(), 5)]);
+
+        table.handle_event(&key(KeyCode::Down));
+        table.handle_event(&key(KeyCode::Right));
+        table.handle_event(&key(KeyCode::Enter));
+        assert_eq!(table.editing(), Some((1, 1)));
+        table.handle_event(&key(KeyCode::Char('.')));
+        table.handle_event(&key(KeyCode::Char('5')));
+        assert_eq!(table.handle_event(&key(KeyCode::Enter)), None);
+        assert_eq!(table.editing(), Some((1, 1)));
+
+        table.handle_event(&key(KeyCode::Backspace));
+        table.handle_event(&key(KeyCode::Backspace));
+        table.handle_event(&Event::Paste("50".into()));
+        assert_eq!(
+            table.handle_event(&key(KeyCode::Enter)),
+            Some(ListEvent::Edited { row: 1, col: 1, value: "50".into() })
+        );
+        assert_eq!(table.get_rows()[1].1, 50);
+
+        table.handle_event(&key(KeyCode::Enter));
+        table.handle_event(&key(KeyCode::Char('9')));
+        table.handle_event(&key(KeyCode::Esc));
+        assert_eq!(table.editing(), None);
+        assert_eq!(table.get_rows()[1].1, 50);
+    }
+
+    #[test]
+    fn test_table_rows_need_not_be_clone() {
+        struct Job {
+            id: u32,
+            owner: std::sync::Mutex<String>,
+        }
+        let job = |id, owner: &str| Job { id, owner: std::sync::Mutex::new(owner.into()) };
+        let owner = |j: &Job| j.owner.lock().unwrap().clone();
+        let mut table: DataTable<Job> = DataTable::new(vec![
+            Column::new("Id", Constraint::Length(3), |j: &Job| j.id.to_string()).sort_by(|a, b| a.id.cmp(&b.id)),
+            Column::new("Owner", Constraint::Fill(1), owner).editable(FieldKind::Text, |j: &mut Job, v| {
+                *j.owner.get_mut().unwrap() = v.to_string();
+                Ok(())
+            }),
+        ])
+        .rows(vec![job(2, "ann"), job(1, "bob")]);
+
+        table.sort_by_column(0, true);
+        table.set_filter("b");
+        assert_eq!(table.view(), &[1]);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 12, 3));
+        table.render(buf.area, &mut buf);
+
+        assert!(table.begin_edit(1, 1));
+        table.handle_event(&Event::Paste("cy".into()));
+        assert!(matches!(table.handle_event(&Event::Key(KeyEvent::from(KeyCode::Enter))), Some(ListEvent::Edited { .. })));
+        assert_eq!(owner(&table.get_rows()[1]), "cy");
+    }
+
+    #[test]
+    fn test_table_pinned_columns_and_footer() {
+        let mut table: DataTable<u32> = DataTable::new(vec![
+            Column::new("Id", Constraint::Length(2), |r: &u32| r.to_string()),
+            Column::new("Aaaa", Constraint::Length(4), |_: &u32| "a".into()),
+            Column::new("Bbbb", Constraint::Length(4), |_: &u32| "b".into()),
+            Column::new("Cccc", Constraint::Length(4), |_: &u32| "c".into())
+                .footer(|rows: &[u32]| format!("n={}", rows.len())),
+        ])
+        .rows((1..=9).collect())
+        .pin_columns(1);
+
+        let area = Rect::new(0, 0, 12, 5);
+        let render = |table: &mut DataTable<u32>| {
+            let mut buf = Buffer::empty(area);
+            table.render(area, &mut buf);
+            (0..5)
+                .map(|y| (0..12).map(|x| buf[(x, y)].symbol().to_string()).collect::<String>())
+                .collect::<Vec<_>>()
+        };
+
+        let lines = render(&mut table);
+        assert_eq!(lines[0], "  Id Aaaa  ▶");
+        assert_eq!(lines[1], "  1  a      ");
+
+        table.handle_event(&Event::Key(KeyEvent::from(KeyCode::End)));
+        for _ in 0..3 {
+            table.handle_event(&Event::Key(KeyEvent::from(KeyCode::Right)));
+        }
+        let lines = render(&mut table);
+        assert_eq!(lines[0], "◀ Id Cccc   ");
+        assert_eq!(lines[3], "  9  c      ");
+        assert_eq!(lines[4], "     n=9    ");
+    }
+
+    #[test]
+    fn test_table_sort_filter_keeps_marks() {
+        let mut table: DataTable<(u32, &str), &str> = DataTable::new(vec![
+            Column::new("N", Constraint::Length(3), |r: &(u32, &str)| r.0.to_string()).sort_by(|a, b| a.0.cmp(&b.0)),
+            Column::new("Name", Constraint::Fill(1), |r: &(u32, &str)| r.1.to_string()).sortable(),
+        ])
+        .rows(vec![(3, "cherry"), (1, "apple"), (2, "banana"), (10, "apricot")])
+        .bind_immediate(KeyCode::Char('x'), "Archive", "archive");
+
+        table.selection_mut().toggle(1);
+        table.sort_by_column(0, false);
+        assert_eq!(table.view(), &[3, 0, 2, 1]);
+        assert_eq!(table.selection().marked(), vec![3]);
+
+        table.set_filter("AP");
+        assert_eq!(table.view(), &[3, 1]);
+        match table.handle_event(&Event::Key(KeyEvent::from(KeyCode::Char('x')))) {
+            Some(ListEvent::Bulk(bulk)) => assert_eq!(bulk.indices, vec![1]),
+            other => panic!("unexpected {other:?}"),
+        }
+        table.remove(&[1]);
+        assert_eq!(table.view(), &[2]);
+        assert_eq!(table.selected_row(), Some(&(10, "apricot")));
+    }
+
+    #[tokio::test]
+    async fn test_table_offloads_and_cancels_stale_work() {
+        let (app, _refresh, _inject) = crate::AppContext::new();
+        let mut table: DataTable<u32> =
+            DataTable::new(vec![Column::new("N", Constraint::Length(6), |r: &u32| r.to_string()).sortable()])
+                .offload(&app)
+                .offload_threshold(100)
+                .rows((0..5000).collect());
+
+        table.set_filter("12");
+        table.set_filter("123");
+        table.toggle_sort(0);
+        assert!(table.is_busy());
+
+        let area = Rect::new(0, 0, 20, 5);
+        let mut buf = Buffer::empty(area);
+        table.render(area, &mut buf);
+        for _ in 0..200 {
+            if !table.is_busy() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            table.render(area, &mut buf);
+        }
+        assert!(!table.is_busy());
+        let shown: Vec<u32> = table.view().iter().map(|&i| table.get_rows()[i]).collect();
+        assert_eq!(shown, vec![1123, 123, 1230, 1231, 1232, 1233, 1234, 1235, 1236, 1237, 1238, 1239, 2123, 3123, 4123]);
+    }
+
+    #[test]
+    fn test_table_renders_and_bulk_marks() {
+        let mut table: DataTable<(u32, &str), &str> = DataTable::new(vec![
+            Column::new("ID", Constraint::Length(3), |r: &(u32, &str)| r.0.to_string()),
+            Column::new("Name", Constraint::Fill(1), |r: &(u32, &str)| r.1.to_string()),
+        ])
+        .rows(vec![(1, "alpha"), (2, "beta"), (3, "gamma")])
+        .bind_immediate(KeyCode::Char('x'), "Archive", "archive");
+
+        let area = Rect::new(0, 0, 20, 4);
+        let mut buf = Buffer::empty(area);
+        table.render(area, &mut buf);
+        let row = |y: u16| -> String { (0..20).map(|x| buf[(x, y)].symbol().to_string()).collect() };
+        assert_eq!(row(0).trim_end(), "  ID  Name");
+        assert_eq!(row(2).trim_end(), "  2   beta");
+
+        table.handle_event(&Event::Key(KeyEvent::new(KeyCode::Char('a'), crossterm::event::KeyModifiers::CONTROL)));
+        match table.handle_event(&Event::Key(KeyEvent::from(KeyCode::Char('x')))) {
+            Some(ListEvent::Bulk(bulk)) => assert_eq!(bulk.indices, vec![0, 1, 2]),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_table_view_virtualizes_and_sorts_on_click() {
+        use crossterm::event::{KeyModifiers, MouseEvent};
+
+        let formatted = Arc::new(AtomicUsize::new(0));
+        let counter = formatted.clone();
+        let mut table: TableView<u32> = TableView::new(vec![Column::new("N", Constraint::Length(6), move |r: &u32| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            r.to_string()
+        })
+        .sortable()])
+        .rows((0..100_000).collect());
+
+        let area = Rect::new(0, 0, 10, 6);
+        let mut buf = Buffer::empty(area);
+        table.render(area, &mut buf);
+        assert_eq!(formatted.load(std::sync::atomic::Ordering::Relaxed), 5);
+
+        let click = MouseEvent { kind: MouseEventKind::Down(MouseButton::Left), column: 3, row: 0, modifiers: KeyModifiers::NONE };
+        table.handle_event(&Event::Mouse(click));
+        table.handle_event(&Event::Mouse(click));
+        assert_eq!(table.sort(), Some((0, false)));
+        table.render(area, &mut buf);
+        assert_eq!(table.get_rows()[table.view()[0]], 99_999);
+    }
+
+    #[test]
+    fn test_table_sort_key_sorts_the_focused_column() {
+        let key = |code| Event::Key(KeyEvent::from(code));
+        let mut table: TableView<(u32, &str)> = TableView::new(vec![
+            Column::new("N", Constraint::Length(3), |r: &(u32, &str)| r.0.to_string()).sort_by(|a, b| a.0.cmp(&b.0)),
+            Column::new("Name", Constraint::Fill(1), |r: &(u32, &str)| r.1.to_string()).sortable(),
+        ])
+        .rows(vec![(2, "banana"), (1, "cherry"), (3, "apple")])
+        .sort_key(KeyCode::Char('s'));
+
+        table.handle_event(&key(KeyCode::Char('s')));
+        assert_eq!(table.view(), &[1, 0, 2]);
+        table.handle_event(&key(KeyCode::Right));
+        assert_eq!(table.focused_column(), 1);
+        table.handle_event(&key(KeyCode::Char('s')));
+        table.handle_event(&key(KeyCode::Char('s')));
+        assert_eq!(table.sort(), Some((1, false)));
+        assert_eq!(table.view(), &[1, 0, 2]);
+        table.handle_event(&Event::Key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)));
+        assert_eq!(table.sort(), Some((1, false)));
+    }
+}
@@ -0,0 +1,108 @@
+//! Sort/filter computation for `DataTable`, run inline or on a blocking thread.
+
+use std::cmp::Ordering;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex};
+
+use crate::AppContext;
+
+pub(crate) type CellFn<T> = Arc<dyn Fn(&T) -> String + Send + Sync>;
+pub(crate) type CompareFn<T> = Arc<dyn Fn(&T, &T) -> Ordering + Send + Sync>;
+
+/// What to compute: rows whose cells contain `filter` (case-insensitive), ordered by `sort`.
+pub(crate) struct ViewSpec<T> {
+    pub(crate) filter: String,
+    pub(crate) cells: Vec<CellFn<T>>,
+    pub(crate) sort: Option<(CompareFn<T>, bool)>,
+}
+
+/// Compute the display order of `rows`. Returns `None` if cancelled part-way.
+pub(crate) fn compute_view<T>(rows: &[T], spec: &ViewSpec<T>, cancel: &AtomicBool, progress: &AtomicUsize) -> Option<Vec<usize>> {
+    let mut view: Vec<usize> = if spec.filter.is_empty() {
+        (0..rows.len()).collect()
+    } else {
+        let mut view = Vec::new();
+        for (i, row) in rows.iter().enumerate() {
+            if i % 1024 == 0 {
+                if cancel.load(AtomicOrdering::Relaxed) {
+                    return None;
+                }
+                progress.store(i, AtomicOrdering::Relaxed);
+            }
+            if spec.cells.iter().any(|cell| cell(row).to_lowercase().contains(&spec.filter)) {
+                view.push(i);
+            }
+        }
+        view
+    };
+    progress.store(rows.len(), AtomicOrdering::Relaxed);
+
+    if let Some((compare, ascending)) = &spec.sort {
+        // A cancelled sort degrades to all-equal comparisons so it finishes quickly
+        view.sort_by(|&a, &b| {
+            if cancel.load(AtomicOrdering::Relaxed) {
+                return Ordering::Equal;
+            }
+            let ord = compare(&rows[a], &rows[b]);
+            if *ascending { ord } else { ord.reverse() }
+        });
+    }
+    (!cancel.load(AtomicOrdering::Relaxed)).then_some(view)
+}
+
+/// A sort/filter running on a blocking thread. Dropping the job cancels it, so starting
+/// a new one for fresh input abandons the stale one.
+pub(crate) struct ViewJob {
+    cancel: Arc<AtomicBool>,
+    progress: Arc<AtomicUsize>,
+    total: usize,
+    result: Arc<Mutex<Option<Vec<usize>>>>,
+}
+
+impl ViewJob {
+    /// Start computing on tokio's blocking pool. Outside a runtime the spec is handed back.
+    pub(crate) fn spawn<T>(rows: Arc<Vec<T>>, spec: ViewSpec<T>, app: &AppContext) -> Result<Self, ViewSpec<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return Err(spec);
+        };
+        let job = Self {
+            cancel: Arc::new(AtomicBool::new(false)),
+            progress: Arc::new(AtomicUsize::new(0)),
+            total: rows.len(),
+            result: Arc::new(Mutex::new(None)),
+        };
+        let (cancel, progress, result) = (job.cancel.clone(), job.progress.clone(), job.result.clone());
+        let app = app.clone();
+        handle.spawn_blocking(move || {
+            if let Some(view) = compute_view(&rows, &spec, &cancel, &progress) {
+                *result.lock().unwrap_or_else(|e| e.into_inner()) = Some(view);
+                app.refresh();
+            }
+        });
+        Ok(job)
+    }
+
+    /// The finished view, if the computation is done.
+    pub(crate) fn try_take(&self) -> Option<Vec<usize>> {
+        self.result.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    /// Progress label for the header: filtering percentage, then sorting.
+    pub(crate) fn label(&self) -> String {
+        let done = self.progress.load(AtomicOrdering::Relaxed);
+        if done >= self.total {
+            "sorting…".to_string()
+        } else {
+            format!("filtering {}%", done * 100 / self.total.max(1))
+        }
+    }
+}
+
+impl Drop for ViewJob {
+    fn drop(&mut self) {
+        self.cancel.store(true, AtomicOrdering::Relaxed);
+    }
+}
@@ -0,0 +1,327 @@
+//! Tab container hosting one component per tab.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::Tabs as TabBar;
+use unicode_width::UnicodeWidthStr;
+
+use crate::component::binding::Held;
+use crate::component::traits::{Action, Component, Event};
+use crate::{Context, EventContext};
+
+struct Tab {
+    title: String,
+    view: Held,
+    mounted: bool,
+}
+
+/// Tabbed container: a tab bar on the top row and the active tab's component below.
+///
+/// Only the active tab is rendered and receives events. A tab's `on_mount` runs the
+/// first time it becomes active; switching tabs calls `on_exit` on the old tab and
+/// `on_enter` on the new one. `Tab`/`BackTab` switch by default (see `next_keys` and
+/// `prev_keys`), and clicking a title selects it.
+///
+/// # Example
+/// ```ignore
+/// let tabs = Tabs::new()
+///     .tab("Logs", LogPage::default())
+///     .tab("Metrics", MetricsPage::default())
+///     .next_keys([KeyEvent::new(KeyCode::Char(']'), KeyModifiers::NONE)])
+///     .prev_keys([KeyEvent::new(KeyCode::Char('['), KeyModifiers::NONE)]);
+/// self.tabs = ChildView::new(tabs);
+/// ```
+pub struct Tabs {
+    tabs: Vec<Tab>,
+    active: usize,
+    next_keys: Vec<KeyEvent>,
+    prev_keys: Vec<KeyEvent>,
+    style: Style,
+    highlight_style: Style,
+    entered: bool,
+    bar: Option<Rect>,
+    content: Option<Rect>,
+}
+
+impl Default for Tabs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tabs {
+    /// An empty tab container.
+    pub fn new() -> Self {
+        Self {
+            tabs: Vec::new(),
+            active: 0,
+            next_keys: vec![KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE)],
+            prev_keys: vec![KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT)],
+            style: Style::default(),
+            highlight_style: Style::default().add_modifier(Modifier::REVERSED),
+            entered: false,
+            bar: None,
+            content: None,
+        }
+    }
+
+    /// Add a tab titled `title` showing `component`.
+    pub fn tab(mut self, title: impl Into<String>, component: impl Component) -> Self {
+        self.tabs.push(Tab { title: title.into(), view: Held::new(component), mounted: false });
+        self
+    }
+
+    /// Keys that switch to the next tab, replacing the default `Tab`.
+    pub fn next_keys(mut self, keys: impl IntoIterator<Item = KeyEvent>) -> Self {
+        self.next_keys = keys.into_iter().collect();
+        self
+    }
+
+    /// Keys that switch to the previous tab, replacing the default `BackTab`.
+    pub fn prev_keys(mut self, keys: impl IntoIterator<Item = KeyEvent>) -> Self {
+        self.prev_keys = keys.into_iter().collect();
+        self
+    }
+
+    /// Style of the tab bar.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Style of the active tab's title.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Index of the active tab.
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Number of tabs.
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Whether there are no tabs.
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    /// Titles of all tabs, in order.
+    pub fn titles(&self) -> impl Iterator<Item = &str> {
+        self.tabs.iter().map(|tab| tab.title.as_str())
+    }
+
+    /// Whether the tab at `index` has been mounted (was active at least once).
+    pub fn is_mounted(&self, index: usize) -> bool {
+        self.tabs.get(index).is_some_and(|tab| tab.mounted)
+    }
+
+    /// Make the tab at `index` active, running the lifecycle hooks. Out-of-range
+    /// indices are ignored.
+    pub fn select<P: ?Sized + Send + Sync>(&mut self, index: usize, cx: &Context<P>) {
+        if index >= self.tabs.len() || index == self.active {
+            return;
+        }
+        if self.entered {
+            self.exit_active(cx);
+        }
+        self.active = index;
+        if self.entered {
+            self.enter_active(cx);
+        }
+    }
+
+    /// Switch to the next tab, wrapping around.
+    pub fn select_next<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        if !self.tabs.is_empty() {
+            self.select((self.active + 1) % self.tabs.len(), cx);
+        }
+    }
+
+    /// Switch to the previous tab, wrapping around.
+    pub fn select_previous<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        if !self.tabs.is_empty() {
+            self.select((self.active + self.tabs.len() - 1) % self.tabs.len(), cx);
+        }
+    }
+
+    fn enter_active<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        let area = self.content;
+        let Some(tab) = self.tabs.get_mut(self.active) else { return };
+        let mount = !std::mem::replace(&mut tab.mounted, true);
+        tab.view.with(cx, area, |view, cx| {
+            if mount {
+                view.on_mount_any(cx);
+            }
+            view.on_enter_any(cx);
+        });
+    }
+
+    fn exit_active<P: ?Sized + Send + Sync>(&mut self, cx: &Context<P>) {
+        let area = self.content;
+        if let Some(tab) = self.tabs.get_mut(self.active).filter(|tab| tab.mounted) {
+            tab.view.with(cx, area, |view, cx| view.on_exit_any(cx));
+        }
+    }
+
+    /// Index of the tab whose title is at `column` in the bar, matching the padding
+    /// and divider of ratatui's `Tabs`.
+    fn tab_at(&self, column: u16) -> Option<usize> {
+        let bar = self.bar?;
+        let mut x = bar.x;
+        for (index, tab) in self.tabs.iter().enumerate() {
+            let end = x + tab.title.width() as u16 + 2;
+            if (x..end).contains(&column) {
+                return Some(index);
+            }
+            x = end + 1;
+        }
+        None
+    }
+}
+
+impl Component for Tabs {
+    fn on_enter(&mut self, cx: &mut Context<Self>) {
+        self.entered = true;
+        self.enter_active(cx);
+    }
+
+    fn on_exit(&mut self, cx: &mut Context<Self>) {
+        self.exit_active(cx);
+        self.entered = false;
+    }
+
+    fn on_shutdown(&mut self, cx: &mut Context<Self>) {
+        for tab in self.tabs.iter_mut().filter(|tab| tab.mounted) {
+            tab.view.with(cx, self.content, |view, cx| view.on_shutdown_any(cx));
+        }
+    }
+
+    fn on_resize(&mut self, width: u16, height: u16, cx: &mut Context<Self>) {
+        for tab in self.tabs.iter_mut().filter(|tab| tab.mounted) {
+            tab.view.with(cx, self.content, |view, cx| view.on_resize_any(width, height, cx));
+        }
+    }
+
+    fn on_focus(&mut self, cx: &mut Context<Self>) {
+        if let Some(tab) = self.tabs.get_mut(self.active).filter(|tab| tab.mounted) {
+            tab.view.with(cx, self.content, |view, cx| view.on_focus_any(cx));
+        }
+    }
+
+    fn on_blur(&mut self, cx: &mut Context<Self>) {
+        if let Some(tab) = self.tabs.get_mut(self.active).filter(|tab| tab.mounted) {
+            tab.view.with(cx, self.content, |view, cx| view.on_blur_any(cx));
+        }
+    }
+
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let [bar, content] = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(cx.area(frame));
+        self.bar = Some(bar);
+        self.content = Some(content);
+        cx.name_area("tab_bar", bar);
+        let titles = self.tabs.iter().map(|tab| tab.title.as_str());
+        frame.render_widget(
+            TabBar::new(titles).select(self.active).style(self.style).highlight_style(self.highlight_style),
+            bar,
+        );
+        if !self.entered {
+            // Rendered without lifecycle forwarding: treat the first frame as entering
+            self.entered = true;
+            self.enter_active(cx);
+        }
+        if let Some(tab) = self.tabs.get_mut(self.active) {
+            tab.view.with(cx, Some(content), |view, cx| view.render_any(frame, cx));
+        }
+    }
+
+    fn refresh_on_event(&self) -> bool {
+        true
+    }
+
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+        match &event {
+            Event::Key(key) if self.next_keys.contains(key) => {
+                self.select_next(cx);
+                return None;
+            }
+            Event::Key(key) if self.prev_keys.contains(key) => {
+                self.select_previous(cx);
+                return None;
+            }
+            Event::Mouse(mouse) if self.bar.is_some_and(|bar| bar.contains(Position::new(mouse.column, mouse.row))) => {
+                if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+                    if let Some(index) = self.tab_at(mouse.column) {
+                        self.select(index, cx);
+                    }
+                }
+                return None;
+            }
+            _ => {}
+        }
+        let area = self.content;
+        let tab = self.tabs.get_mut(self.active).filter(|tab| tab.mounted)?;
+        tab.view.with(cx, area, |view, cx| view.handle_event_any(event, cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+    use std::sync::{Arc, Mutex};
+
+    struct Page {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Component for Page {
+        fn on_mount(&mut self, _cx: &mut Context<Self>) {
+            self.log.lock().unwrap().push(format!("mount {}", self.name));
+        }
+
+        fn on_enter(&mut self, _cx: &mut Context<Self>) {
+            self.log.lock().unwrap().push(format!("enter {}", self.name));
+        }
+
+        fn on_exit(&mut self, _cx: &mut Context<Self>) {
+            self.log.lock().unwrap().push(format!("exit {}", self.name));
+        }
+
+        fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+            let area = cx.area(frame);
+            frame.buffer_mut().set_string(area.x, area.y, format!("page {}", self.name), Style::default());
+        }
+    }
+
+    #[test]
+    fn test_tabs_mount_lazily_and_switch() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let page = |name| Page { name, log: log.clone() };
+        let tabs = Tabs::new().tab("One", page("one")).tab("Two", page("two"));
+        let mut harness = Harness::with_size(tabs, 20, 3);
+
+        harness.expect_text("page one");
+        harness.expect_no_text("page two");
+        assert_eq!(*log.lock().unwrap(), ["mount one", "enter one"]);
+
+        harness.type_str("<Tab>");
+        harness.expect_text("page two");
+        harness.expect_no_text("page one");
+        harness.type_str("<S-Tab>");
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["mount one", "enter one", "exit one", "mount two", "enter two", "exit two", "enter one"]
+        );
+
+        // " One │ Two ": "Two" spans columns 6..11
+        harness.click("tab_bar", 7, 0);
+        assert_eq!(harness.read(|tabs| tabs.active()), 1);
+    }
+}
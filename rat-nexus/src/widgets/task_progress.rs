@@ -0,0 +1,93 @@
+//! Gauge bound to the progress of a background task.
+
+use ratatui::style::{Color, Style};
+use ratatui::widgets::Gauge;
+
+use crate::component::traits::Component;
+use crate::task::Progress;
+use crate::Context;
+
+/// Renders a task's `Progress` as a gauge labelled with its message and percentage.
+///
+/// Redraws whenever the task reports. Once the task ends the label reads "Done", or
+/// "Stopped" if it ended (e.g. was aborted) before reaching 100%.
+///
+/// # Example
+/// ```ignore
+/// // in the page struct
+/// gauge: ChildView<TaskProgress>,
+///
+/// // when starting the job
+/// let handle = cx.spawn_with_progress(|_, progress| async move { /* progress.report(..) */ }, |_, (), _| {})?;
+/// self.gauge = ChildView::new(TaskProgress::new(handle.progress().unwrap()));
+///
+/// // in render
+/// cx.mount_child(&mut self.gauge, frame, area);
+/// ```
+pub struct TaskProgress {
+    progress: Progress,
+    gauge_style: Style,
+}
+
+impl TaskProgress {
+    /// Show `progress`.
+    pub fn new(progress: Progress) -> Self {
+        Self { progress, gauge_style: Style::default().fg(Color::Cyan) }
+    }
+
+    /// Style of the filled part of the gauge.
+    pub fn gauge_style(mut self, style: Style) -> Self {
+        self.gauge_style = style;
+        self
+    }
+
+    /// Show another task's progress, e.g. when the job is restarted.
+    pub fn set_progress(&mut self, progress: Progress) {
+        self.progress = progress;
+    }
+
+    fn label(&self) -> String {
+        let state = self.progress.get();
+        let percent = (state.fraction * 100.0).floor();
+        match (state.finished, state.message) {
+            (true, _) if state.fraction >= 1.0 => "Done".to_string(),
+            (true, _) => format!("Stopped at {percent}%"),
+            (false, Some(message)) => format!("{message} {percent}%"),
+            (false, None) => format!("{percent}%"),
+        }
+    }
+}
+
+impl Component for TaskProgress {
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let area = cx.area(frame);
+        cx.subscribe(self.progress.entity());
+        let gauge = Gauge::default()
+            .gauge_style(self.gauge_style)
+            .ratio(self.progress.get().fraction)
+            .label(self.label());
+        frame.render_widget(gauge, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+
+    #[tokio::test]
+    async fn test_label_follows_reports() {
+        let progress = Progress::new();
+        let mut harness = Harness::with_size(TaskProgress::new(progress.clone()), 20, 1);
+        harness.expect_text("0%");
+
+        progress.message("copying");
+        progress.report(0.426);
+        harness.render();
+        harness.expect_text("copying 42%");
+
+        drop(progress.finish_guard());
+        harness.render();
+        harness.expect_text("Stopped at 42%");
+    }
+}
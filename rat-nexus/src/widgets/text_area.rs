@@ -0,0 +1,790 @@
+//! Multi-line text editor.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Modifier, Style};
+use unicode_width::UnicodeWidthChar;
+
+use crate::component::traits::{Action, Component, Event};
+use crate::{Context, EventContext};
+
+/// Maximum number of undo steps kept.
+const UNDO_LIMIT: usize = 200;
+
+/// Key bindings used by a `TextArea`, chosen at construction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyMode {
+    /// Arrows, `Home`/`End`, `Ctrl+Z`/`Ctrl+Y` for undo/redo.
+    #[default]
+    Standard,
+    /// Emacs bindings: `Ctrl+F/B/N/P/A/E`, `Alt+F/B`, `Ctrl+D`, `Ctrl+K` kill,
+    /// `Ctrl+Y` yank, `Ctrl+/` or `Ctrl+_` undo. Arrows work as well.
+    Emacs,
+    /// Modal vim bindings. Normal mode: `hjkl`, `w`/`b`, `0`/`$`, `gg`/`G`, `x`, `dd`,
+    /// `D`, `p`, `u`/`Ctrl+R`, and `i`/`a`/`I`/`A`/`o`/`O` to insert. `Esc` leaves insert mode.
+    Vim,
+}
+
+/// Current vim mode of a `TextArea` in `KeyMode::Vim`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VimMode {
+    Normal,
+    Insert,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Insert,
+    Delete,
+    Other,
+}
+
+#[derive(Debug, Clone)]
+struct Snapshot {
+    lines: Vec<String>,
+    row: usize,
+    col: usize,
+}
+
+/// A multi-line text editor with soft wrapping, vertical scrolling and undo.
+///
+/// Usable as a `Component` or driven directly with `handle_key`, `handle_mouse` and
+/// `insert_str`, and drawn with `draw`. `↑/↓` move by logical lines.
+///
+/// # Example
+/// ```ignore
+/// let notes = ChildView::new(TextArea::new().key_mode(KeyMode::Vim).with_text(&saved));
+///
+/// // in render, show the vim mode in a status line
+/// if let Some(VimMode::Insert) = self.notes.vim_mode() {
+///     status = "-- INSERT --";
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextArea {
+    lines: Vec<String>,
+    row: usize,
+    col: usize,
+    mode: KeyMode,
+    vim: VimMode,
+    pending: Option<char>,
+    wrap: bool,
+    scroll: usize,
+    h_scroll: usize,
+    style: Style,
+    cursor_style: Style,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    last_edit: Option<EditKind>,
+    register: Option<(String, bool)>,
+    area: Option<Rect>,
+    visual: Vec<(usize, usize)>,
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextArea {
+    /// Create an empty editor with `KeyMode::Standard` and wrapping enabled.
+    pub fn new() -> Self {
+        Self {
+            lines: vec![String::new()],
+            row: 0,
+            col: 0,
+            mode: KeyMode::Standard,
+            vim: VimMode::Normal,
+            pending: None,
+            wrap: true,
+            scroll: 0,
+            h_scroll: 0,
+            style: Style::default(),
+            cursor_style: Style::default().add_modifier(Modifier::REVERSED),
+            undo: Vec::new(),
+            redo: Vec::new(),
+            last_edit: None,
+            register: None,
+            area: None,
+            visual: Vec::new(),
+        }
+    }
+
+    /// Choose the key bindings. Vim mode starts in normal mode.
+    pub fn key_mode(mut self, mode: KeyMode) -> Self {
+        self.mode = mode;
+        self.vim = VimMode::Normal;
+        self
+    }
+
+    /// Set the initial text, with the cursor at the start. Clears undo history.
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Enable or disable soft wrapping. Without wrapping, long lines scroll horizontally.
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Set the text style.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the cursor cell.
+    pub fn cursor_style(mut self, style: Style) -> Self {
+        self.cursor_style = style;
+        self
+    }
+
+    /// The text, with lines joined by `\n`.
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// The lines of text.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Replace the text, moving the cursor to the start and clearing undo history.
+    pub fn set_text(&mut self, text: &str) {
+        self.lines = text.split('\n').map(str::to_string).collect();
+        self.row = 0;
+        self.col = 0;
+        self.scroll = 0;
+        self.undo.clear();
+        self.redo.clear();
+        self.last_edit = None;
+    }
+
+    /// Cursor position as `(line, column)` in characters.
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    /// The key bindings in use.
+    pub fn mode(&self) -> KeyMode {
+        self.mode
+    }
+
+    /// The vim mode, if using `KeyMode::Vim`.
+    pub fn vim_mode(&self) -> Option<VimMode> {
+        (self.mode == KeyMode::Vim).then_some(self.vim)
+    }
+
+    /// Whether there is anything to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Revert the last edit. Consecutive typing or deleting is undone as one step.
+    pub fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo.pop() else {
+            return false;
+        };
+        let current = self.snapshot();
+        self.redo.push(current);
+        self.restore(snapshot);
+        true
+    }
+
+    /// Re-apply the last undone edit.
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo.pop() else {
+            return false;
+        };
+        let current = self.snapshot();
+        self.undo.push(current);
+        self.restore(snapshot);
+        true
+    }
+
+    /// Insert text at the cursor as a single undo step. `\r\n` and `\r` become `\n`.
+    pub fn insert_str(&mut self, text: &str) {
+        self.checkpoint(EditKind::Other);
+        for c in text.replace("\r\n", "\n").replace('\r', "\n").chars() {
+            self.insert_char(c);
+        }
+    }
+
+    /// Handle a key according to the key mode. Returns true if the key was consumed.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match self.mode {
+            KeyMode::Standard => self.standard_key(key),
+            KeyMode::Emacs => self.emacs_key(key) || self.standard_key(key),
+            KeyMode::Vim => match self.vim {
+                VimMode::Normal => self.vim_normal_key(key),
+                VimMode::Insert if key.code == KeyCode::Esc => {
+                    self.vim = VimMode::Normal;
+                    self.col = self.col.saturating_sub(1);
+                    self.last_edit = None;
+                    true
+                }
+                VimMode::Insert => self.standard_key(key),
+            },
+        }
+    }
+
+    /// Place the cursor on click and move it with the wheel, relative to the last drawn area.
+    /// Returns true if the event hit the editor.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> bool {
+        let Some(area) = self.area else {
+            return false;
+        };
+        if !area.contains(Position::new(mouse.column, mouse.row)) {
+            return false;
+        }
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let index = self.scroll + usize::from(mouse.row - area.y);
+                let Some(&(row, start)) = self.visual.get(index).or(self.visual.last()) else {
+                    return true;
+                };
+                let skip = if self.wrap { start } else { self.h_scroll };
+                self.row = row;
+                self.col = skip + index_at(&self.lines[row], skip, mouse.column - area.x);
+                self.clamp_col();
+                self.last_edit = None;
+            }
+            MouseEventKind::ScrollUp => self.move_row(-3),
+            MouseEventKind::ScrollDown => self.move_row(3),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Draw the text into `area`, scrolling to keep the cursor visible.
+    pub fn draw(&mut self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        buf.set_style(area, self.style);
+        if area.is_empty() {
+            return;
+        }
+        let width = area.width as usize;
+        let height = area.height as usize;
+
+        self.visual.clear();
+        for (row, line) in self.lines.iter().enumerate() {
+            if self.wrap {
+                self.visual.extend(wrap_starts(line, width).into_iter().map(|start| (row, start)));
+            } else {
+                self.visual.push((row, 0));
+            }
+        }
+
+        let cursor_visual = self
+            .visual
+            .iter()
+            .rposition(|&(row, start)| row == self.row && start <= self.col)
+            .unwrap_or(0);
+        if cursor_visual < self.scroll {
+            self.scroll = cursor_visual;
+        } else if cursor_visual >= self.scroll + height {
+            self.scroll = cursor_visual + 1 - height;
+        }
+        if !self.wrap {
+            let line = &self.lines[self.row];
+            if self.col < self.h_scroll {
+                self.h_scroll = self.col;
+            }
+            while self.h_scroll < self.col && width_between(line, self.h_scroll, self.col) + 1 > width {
+                self.h_scroll += 1;
+            }
+        }
+
+        for (y, &(row, start)) in self.visual.iter().skip(self.scroll).take(height).enumerate() {
+            let y = area.y + y as u16;
+            let line = &self.lines[row];
+            let (skip, end) = if self.wrap {
+                let end = self.visual.get(self.scroll + (y - area.y) as usize + 1).filter(|(r, _)| *r == row).map(|(_, s)| *s);
+                (start, end.unwrap_or(usize::MAX))
+            } else {
+                (self.h_scroll, usize::MAX)
+            };
+            let mut x = area.x;
+            for (i, c) in line.chars().enumerate().skip(skip).take(end - skip) {
+                let w = c.width().unwrap_or(0) as u16;
+                if x + w > area.right() {
+                    break;
+                }
+                buf[(x, y)].set_char(c);
+                if row == self.row && i == self.col {
+                    buf[(x, y)].set_style(self.cursor_style);
+                }
+                x += w;
+            }
+            // Cursor past the last character of the line
+            let at_end = row == self.row && self.col >= line.chars().count() && (self.scroll + (y - area.y) as usize) == cursor_visual;
+            if at_end && x < area.right() {
+                buf[(x, y)].set_style(self.cursor_style);
+            }
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot { lines: self.lines.clone(), row: self.row, col: self.col }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.lines = snapshot.lines;
+        self.row = snapshot.row;
+        self.col = snapshot.col;
+        self.last_edit = None;
+        self.clamp_col();
+    }
+
+    /// Record an undo step unless this edit continues a run of the same kind.
+    fn checkpoint(&mut self, kind: EditKind) {
+        if kind == EditKind::Other || self.last_edit != Some(kind) {
+            let snapshot = self.snapshot();
+            crate::state::push_capped(&mut self.undo, snapshot, UNDO_LIMIT);
+        }
+        self.redo.clear();
+        self.last_edit = Some(kind);
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    fn clamp_col(&mut self) {
+        let len = self.line_len(self.row);
+        let max = if self.vim_mode() == Some(VimMode::Normal) { len.saturating_sub(1) } else { len };
+        self.col = self.col.min(max);
+    }
+
+    fn move_to(&mut self, row: usize, col: usize) {
+        self.row = row.min(self.lines.len() - 1);
+        self.col = col;
+        self.clamp_col();
+        self.last_edit = None;
+    }
+
+    fn move_row(&mut self, delta: isize) {
+        let row = self.row.saturating_add_signed(delta);
+        self.move_to(row, self.col);
+    }
+
+    fn move_col(&mut self, delta: isize) {
+        if delta < 0 && self.col == 0 && self.row > 0 {
+            let row = self.row - 1;
+            self.move_to(row, self.line_len(row));
+        } else if delta > 0 && self.col >= self.line_len(self.row) && self.row + 1 < self.lines.len() {
+            self.move_to(self.row + 1, 0);
+        } else {
+            self.move_to(self.row, self.col.saturating_add_signed(delta));
+        }
+    }
+
+    fn page(&self) -> isize {
+        self.area.map_or(10, |a| (a.height as isize - 1).max(1))
+    }
+
+    fn insert_char(&mut self, c: char) {
+        let line = &mut self.lines[self.row];
+        let at = byte_index(line, self.col);
+        if c == '\n' {
+            let rest = line.split_off(at);
+            self.lines.insert(self.row + 1, rest);
+            self.row += 1;
+            self.col = 0;
+        } else {
+            line.insert(at, c);
+            self.col += 1;
+        }
+    }
+
+    fn type_char(&mut self, c: char) {
+        self.checkpoint(if c == '\n' { EditKind::Other } else { EditKind::Insert });
+        self.insert_char(c);
+    }
+
+    fn backspace(&mut self) {
+        if self.col > 0 {
+            self.checkpoint(EditKind::Delete);
+            self.col -= 1;
+            let line = &mut self.lines[self.row];
+            line.remove(byte_index(line, self.col));
+        } else if self.row > 0 {
+            self.checkpoint(EditKind::Other);
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.col < self.line_len(self.row) {
+            self.checkpoint(EditKind::Delete);
+            let line = &mut self.lines[self.row];
+            line.remove(byte_index(line, self.col));
+        } else if self.row + 1 < self.lines.len() {
+            self.checkpoint(EditKind::Other);
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        }
+    }
+
+    /// Delete to the end of the line (or the line break if already there) into the register.
+    fn kill_line(&mut self) {
+        let len = self.line_len(self.row);
+        if self.col < len {
+            self.checkpoint(EditKind::Other);
+            let line = &mut self.lines[self.row];
+            let killed = line.split_off(byte_index(line, self.col));
+            self.register = Some((killed, false));
+        } else if self.row + 1 < self.lines.len() {
+            self.delete();
+            self.register = Some(("\n".to_string(), false));
+        }
+        self.clamp_col();
+    }
+
+    fn delete_whole_line(&mut self) {
+        self.checkpoint(EditKind::Other);
+        let line = if self.lines.len() > 1 { self.lines.remove(self.row) } else { std::mem::take(&mut self.lines[0]) };
+        self.register = Some((line, true));
+        self.move_to(self.row, 0);
+    }
+
+    fn paste(&mut self, after: bool) {
+        let Some((text, linewise)) = self.register.clone() else {
+            return;
+        };
+        if linewise {
+            self.checkpoint(EditKind::Other);
+            let row = if after { self.row + 1 } else { self.row };
+            self.lines.insert(row.min(self.lines.len()), text);
+            self.move_to(row, 0);
+        } else {
+            if after && self.line_len(self.row) > 0 {
+                self.col += 1;
+            }
+            self.insert_str(&text);
+            if self.vim_mode() == Some(VimMode::Normal) {
+                self.col = self.col.saturating_sub(1);
+            }
+        }
+    }
+
+    fn word_forward(&mut self) {
+        let chars: Vec<char> = self.lines[self.row].chars().collect();
+        let mut i = self.col;
+        while i < chars.len() && chars[i].is_alphanumeric() {
+            i += 1;
+        }
+        while i < chars.len() && !chars[i].is_alphanumeric() {
+            i += 1;
+        }
+        if i >= chars.len() && self.row + 1 < self.lines.len() {
+            self.move_to(self.row + 1, 0);
+        } else {
+            self.move_to(self.row, i);
+        }
+    }
+
+    fn word_back(&mut self) {
+        if self.col == 0 && self.row > 0 {
+            let row = self.row - 1;
+            self.move_to(row, self.line_len(row));
+            return;
+        }
+        let chars: Vec<char> = self.lines[self.row].chars().collect();
+        let mut i = self.col.min(chars.len());
+        while i > 0 && !chars[i - 1].is_alphanumeric() {
+            i -= 1;
+        }
+        while i > 0 && chars[i - 1].is_alphanumeric() {
+            i -= 1;
+        }
+        self.move_to(self.row, i);
+    }
+
+    fn standard_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Left if ctrl => self.word_back(),
+            KeyCode::Right if ctrl => self.word_forward(),
+            KeyCode::Home if ctrl => self.move_to(0, 0),
+            KeyCode::End if ctrl => self.move_to(usize::MAX, usize::MAX),
+            KeyCode::Left => self.move_col(-1),
+            KeyCode::Right => self.move_col(1),
+            KeyCode::Up => self.move_row(-1),
+            KeyCode::Down => self.move_row(1),
+            KeyCode::Home => self.move_to(self.row, 0),
+            KeyCode::End => self.move_to(self.row, usize::MAX),
+            KeyCode::PageUp => self.move_row(-self.page()),
+            KeyCode::PageDown => self.move_row(self.page()),
+            KeyCode::Enter => self.type_char('\n'),
+            KeyCode::Tab => self.insert_str("    "),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Char('z') if ctrl => {
+                self.undo();
+            }
+            KeyCode::Char('y') if ctrl && self.mode == KeyMode::Standard => {
+                self.redo();
+            }
+            KeyCode::Char(c) if !ctrl && !key.modifiers.contains(KeyModifiers::ALT) => self.type_char(c),
+            _ => return false,
+        }
+        true
+    }
+
+    fn emacs_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        match key.code {
+            KeyCode::Char('f') if ctrl => self.move_col(1),
+            KeyCode::Char('b') if ctrl => self.move_col(-1),
+            KeyCode::Char('n') if ctrl => self.move_row(1),
+            KeyCode::Char('p') if ctrl => self.move_row(-1),
+            KeyCode::Char('a') if ctrl => self.move_to(self.row, 0),
+            KeyCode::Char('e') if ctrl => self.move_to(self.row, usize::MAX),
+            KeyCode::Char('d') if ctrl => self.delete(),
+            KeyCode::Char('k') if ctrl => self.kill_line(),
+            KeyCode::Char('y') if ctrl => self.paste(false),
+            KeyCode::Char('/') | KeyCode::Char('_') if ctrl => {
+                self.undo();
+            }
+            // Alt+Right/Left too, for terminals remapping Alt+f/b (see `TerminalProfile`)
+            KeyCode::Char('f') | KeyCode::Right if alt => self.word_forward(),
+            KeyCode::Char('b') | KeyCode::Left if alt => self.word_back(),
+            KeyCode::Char('<') if alt => self.move_to(0, 0),
+            KeyCode::Char('>') if alt => self.move_to(usize::MAX, usize::MAX),
+            _ => return false,
+        }
+        true
+    }
+
+    fn vim_normal_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        if let Some(pending) = self.pending.take() {
+            match (pending, key.code) {
+                ('g', KeyCode::Char('g')) => self.move_to(0, 0),
+                ('d', KeyCode::Char('d')) => self.delete_whole_line(),
+                _ => {}
+            }
+            return true;
+        }
+        match key.code {
+            KeyCode::Char('r') if ctrl => {
+                self.redo();
+            }
+            _ if ctrl => return false,
+            KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace => self.move_to(self.row, self.col.saturating_sub(1)),
+            KeyCode::Char('l') | KeyCode::Right => self.move_to(self.row, self.col + 1),
+            KeyCode::Char('j') | KeyCode::Down => self.move_row(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_row(-1),
+            KeyCode::Char('w') => self.word_forward(),
+            KeyCode::Char('b') => self.word_back(),
+            KeyCode::Char('0') | KeyCode::Home => self.move_to(self.row, 0),
+            KeyCode::Char('$') | KeyCode::End => self.move_to(self.row, usize::MAX),
+            KeyCode::Char('G') => self.move_to(usize::MAX, 0),
+            KeyCode::PageUp => self.move_row(-self.page()),
+            KeyCode::PageDown => self.move_row(self.page()),
+            KeyCode::Char(c @ ('g' | 'd')) => self.pending = Some(c),
+            KeyCode::Char('x') | KeyCode::Delete => {
+                if self.line_len(self.row) > 0 {
+                    self.delete();
+                    self.clamp_col();
+                }
+            }
+            KeyCode::Char('D') => self.kill_line(),
+            KeyCode::Char('p') => self.paste(true),
+            KeyCode::Char('P') => self.paste(false),
+            KeyCode::Char('u') => {
+                self.undo();
+            }
+            KeyCode::Char('i') => self.vim = VimMode::Insert,
+            KeyCode::Char('a') => {
+                self.vim = VimMode::Insert;
+                self.col = (self.col + 1).min(self.line_len(self.row));
+            }
+            KeyCode::Char('I') => {
+                self.vim = VimMode::Insert;
+                self.col = 0;
+            }
+            KeyCode::Char('A') => {
+                self.vim = VimMode::Insert;
+                self.col = self.line_len(self.row);
+            }
+            KeyCode::Char('o') => {
+                self.vim = VimMode::Insert;
+                self.col = self.line_len(self.row);
+                self.type_char('\n');
+            }
+            KeyCode::Char('O') => {
+                self.vim = VimMode::Insert;
+                self.col = 0;
+                self.type_char('\n');
+                self.row -= 1;
+            }
+            KeyCode::Esc => {}
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl Component for TextArea {
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let area = cx.area(frame);
+        self.draw(area, frame.buffer_mut());
+    }
+
+    fn refresh_on_event(&self) -> bool {
+        true
+    }
+
+    fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+        match event {
+            Event::Key(key) => {
+                self.handle_key(key);
+            }
+            Event::Mouse(mouse) => {
+                self.handle_mouse(mouse);
+            }
+            Event::Paste(text) => self.insert_str(&text),
+            _ => {}
+        }
+        None
+    }
+}
+
+fn byte_index(line: &str, col: usize) -> usize {
+    line.char_indices().nth(col).map_or(line.len(), |(i, _)| i)
+}
+
+fn width_between(line: &str, from: usize, to: usize) -> usize {
+    line.chars().skip(from).take(to.saturating_sub(from)).map(|c| c.width().unwrap_or(0)).sum()
+}
+
+/// Character index of the cell at `column`, counting from character `skip`.
+fn index_at(line: &str, skip: usize, column: u16) -> usize {
+    let mut x = 0;
+    for (i, c) in line.chars().skip(skip).enumerate() {
+        let width = c.width().unwrap_or(0) as u16;
+        if column < x + width {
+            return i;
+        }
+        x += width;
+    }
+    line.chars().count() - skip.min(line.chars().count())
+}
+
+/// Start indices of the visual rows of `line` wrapped at `width` cells. A line that
+/// exactly fills its last row gets an extra empty row for the cursor to sit on.
+fn wrap_starts(line: &str, width: usize) -> Vec<usize> {
+    let mut starts = vec![0];
+    let mut used = 0;
+    let mut len = 0;
+    for (i, c) in line.chars().enumerate() {
+        let w = c.width().unwrap_or(0);
+        if used + w > width && used > 0 {
+            starts.push(i);
+            used = 0;
+        }
+        used += w;
+        len = i + 1;
+    }
+    if used >= width && len > 0 {
+        starts.push(len);
+    }
+    starts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(area: &mut TextArea, code: KeyCode, modifiers: KeyModifiers) {
+        area.handle_key(KeyEvent::new(code, modifiers));
+    }
+
+    fn type_str(area: &mut TextArea, text: &str) {
+        for c in text.chars() {
+            press(area, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+    }
+
+    #[test]
+    fn test_standard_editing_and_undo() {
+        let mut editor = TextArea::new();
+        type_str(&mut editor, "hello");
+        press(&mut editor, KeyCode::Enter, KeyModifiers::NONE);
+        type_str(&mut editor, "world");
+        assert_eq!(editor.text(), "hello\nworld");
+        assert_eq!(editor.cursor(), (1, 5));
+
+        press(&mut editor, KeyCode::Home, KeyModifiers::NONE);
+        press(&mut editor, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(editor.text(), "helloworld");
+
+        editor.undo();
+        assert_eq!(editor.text(), "hello\nworld");
+        editor.undo();
+        assert_eq!(editor.text(), "hello\n");
+        editor.redo();
+        assert_eq!(editor.text(), "hello\nworld");
+    }
+
+    #[test]
+    fn test_vim_and_emacs_bindings() {
+        let mut vim = TextArea::new().key_mode(KeyMode::Vim).with_text("one\ntwo\nthree");
+        type_str(&mut vim, "jdd");
+        assert_eq!(vim.text(), "one\nthree");
+        type_str(&mut vim, "p");
+        assert_eq!(vim.text(), "one\nthree\ntwo");
+        type_str(&mut vim, "ggA!");
+        assert_eq!(vim.vim_mode(), Some(VimMode::Insert));
+        press(&mut vim, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(vim.text(), "one!\nthree\ntwo");
+        type_str(&mut vim, "u");
+        assert_eq!(vim.text(), "one\nthree\ntwo");
+
+        let mut emacs = TextArea::new().key_mode(KeyMode::Emacs).with_text("alpha beta");
+        press(&mut emacs, KeyCode::Char('f'), KeyModifiers::ALT);
+        press(&mut emacs, KeyCode::Char('k'), KeyModifiers::CONTROL);
+        assert_eq!(emacs.text(), "alpha ");
+        press(&mut emacs, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        press(&mut emacs, KeyCode::Char('y'), KeyModifiers::CONTROL);
+        assert_eq!(emacs.text(), "betaalpha ");
+
+        // Alt+Right/Left, as remapped from Alt+f/b, are the same word motions
+        let mut letters = TextArea::new().key_mode(KeyMode::Emacs).with_text("alpha beta");
+        let mut arrows = TextArea::new().key_mode(KeyMode::Emacs).with_text("alpha beta");
+        press(&mut letters, KeyCode::Char('f'), KeyModifiers::ALT);
+        press(&mut arrows, KeyCode::Right, KeyModifiers::ALT);
+        assert_eq!(arrows.cursor(), letters.cursor());
+        assert_ne!(arrows.cursor(), (0, 1));
+        press(&mut letters, KeyCode::Char('b'), KeyModifiers::ALT);
+        press(&mut arrows, KeyCode::Left, KeyModifiers::ALT);
+        assert_eq!(arrows.cursor(), letters.cursor());
+    }
+
+    #[test]
+    fn test_wrap_and_scroll() {
+        let mut editor = TextArea::new().with_text("abcdefgh\nxy\n1\n2\n3");
+        press(&mut editor, KeyCode::End, KeyModifiers::CONTROL);
+        let area = Rect::new(0, 0, 5, 3);
+        let mut buf = Buffer::empty(area);
+        editor.draw(area, &mut buf);
+        let rows: Vec<String> = (0..3).map(|y| (0..5).map(|x| buf[(x, y)].symbol().to_string()).collect()).collect();
+        assert_eq!(rows, vec!["1    ", "2    ", "3    "]);
+
+        press(&mut editor, KeyCode::Home, KeyModifiers::CONTROL);
+        let mut buf = Buffer::empty(area);
+        editor.draw(area, &mut buf);
+        let rows: Vec<String> = (0..3).map(|y| (0..5).map(|x| buf[(x, y)].symbol().to_string()).collect()).collect();
+        assert_eq!(rows, vec!["abcde", "fgh  ", "xy   "]);
+    }
+}
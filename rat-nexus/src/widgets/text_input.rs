@@ -0,0 +1,485 @@
+//! Single-line text input.
+
+use std::ops::Range;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use unicode_width::UnicodeWidthChar;
+
+use crate::component::traits::{Action, Component, Event};
+use crate::{Context, EventContext};
+
+type SubmitFn = Box<dyn FnMut(&str) -> Option<Action> + Send + Sync>;
+
+/// A single-line text input with a cursor, selection and paste support.
+///
+/// Usable as a `Component` (e.g. inside a `ChildView`) or driven directly with
+/// `handle_key` / `handle_mouse` / `insert_str` and drawn with `draw`.
+///
+/// Keys:
+/// - `←/→`, `Home`/`End`, `Ctrl+←/→` (by word); with `Shift` to extend the selection
+/// - `Ctrl+A` select all, `Ctrl+U`/`Ctrl+K` delete to start/end, `Ctrl+W` delete word
+/// - `Backspace`/`Delete` remove the selection or one character
+/// - `Enter` calls the `on_submit` callback
+///
+/// Clicking places the cursor, dragging selects. Pasted text (`Event::Paste`) replaces
+/// the selection with newlines removed.
+///
+/// # Example
+/// ```ignore
+/// let search = ChildView::new(
+///     TextInput::new()
+///         .placeholder("Search…")
+///         .on_submit(|query| Some(Action::Navigate(format!("results/{query}")))),
+/// );
+/// ```
+pub struct TextInput {
+    value: String,
+    cursor: usize,
+    anchor: Option<usize>,
+    scroll: usize,
+    placeholder: String,
+    style: Style,
+    placeholder_style: Style,
+    selection_style: Style,
+    focused: bool,
+    max_len: Option<usize>,
+    filter: Option<fn(char) -> bool>,
+    on_submit: Option<SubmitFn>,
+    area: Option<Rect>,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for TextInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TextInput")
+            .field("value", &self.value)
+            .field("cursor", &self.cursor)
+            .field("selection", &self.selection())
+            .finish_non_exhaustive()
+    }
+}
+
+impl TextInput {
+    /// Create an empty, focused input.
+    pub fn new() -> Self {
+        Self {
+            value: String::new(),
+            cursor: 0,
+            anchor: None,
+            scroll: 0,
+            placeholder: String::new(),
+            style: Style::default(),
+            placeholder_style: Style::default().fg(Color::DarkGray),
+            selection_style: Style::default().bg(Color::Blue).fg(Color::White),
+            focused: true,
+            max_len: None,
+            filter: None,
+            on_submit: None,
+            area: None,
+        }
+    }
+
+    /// Set the initial value, with the cursor at the end.
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Text shown while the input is empty.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Set the text style.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Change the text style after construction.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the style of selected text.
+    pub fn selection_style(mut self, style: Style) -> Self {
+        self.selection_style = style;
+        self
+    }
+
+    /// Limit the value to `max` characters.
+    pub fn max_len(mut self, max: usize) -> Self {
+        self.max_len = Some(max);
+        self
+    }
+
+    /// Only accept characters for which `accept` returns true.
+    pub fn filter(mut self, accept: fn(char) -> bool) -> Self {
+        self.filter = Some(accept);
+        self
+    }
+
+    /// Call `f(value)` when Enter is pressed; its action is returned from `handle_event`.
+    pub fn on_submit<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&str) -> Option<Action> + Send + Sync + 'static,
+    {
+        self.on_submit = Some(Box::new(f));
+        self
+    }
+
+    /// The current value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replace the value, moving the cursor to the end and clearing the selection.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.len();
+        self.anchor = None;
+    }
+
+    /// Clear the value.
+    pub fn clear(&mut self) {
+        self.set_value(String::new());
+    }
+
+    /// Cursor position in characters.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Selected character range, if any.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor.filter(|&a| a != self.cursor)?;
+        Some(anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    /// The selected text, if any.
+    pub fn selected_text(&self) -> Option<&str> {
+        let range = self.selection()?;
+        Some(&self.value[self.byte_index(range.start)..self.byte_index(range.end)])
+    }
+
+    /// Select the whole value.
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.len();
+    }
+
+    /// Whether the input shows a cursor.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Show or hide the cursor; unfocused inputs also drop their selection.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        if !focused {
+            self.anchor = None;
+        }
+    }
+
+    /// Insert text at the cursor, replacing the selection. Control characters are dropped.
+    pub fn insert_str(&mut self, text: &str) {
+        self.delete_selection();
+        for c in text.chars().filter(|c| !c.is_control()) {
+            if self.filter.is_some_and(|accept| !accept(c)) || self.max_len.is_some_and(|max| self.len() >= max) {
+                continue;
+            }
+            let at = self.byte_index(self.cursor);
+            self.value.insert(at, c);
+            self.cursor += 1;
+        }
+    }
+
+    /// Run the `on_submit` callback with the current value.
+    pub fn submit(&mut self) -> Option<Action> {
+        let on_submit = self.on_submit.as_mut()?;
+        on_submit(&self.value)
+    }
+
+    /// Handle an editing or movement key. Returns true if the key was consumed.
+    /// `Enter` is not handled here; see `submit`.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let len = self.len();
+        match key.code {
+            KeyCode::Left if ctrl => self.move_to(self.word_start(self.cursor), shift),
+            KeyCode::Right if ctrl => self.move_to(self.word_end(self.cursor), shift),
+            KeyCode::Left => match self.selection().filter(|_| !shift) {
+                Some(range) => self.move_to(range.start, false),
+                None => self.move_to(self.cursor.saturating_sub(1), shift),
+            },
+            KeyCode::Right => match self.selection().filter(|_| !shift) {
+                Some(range) => self.move_to(range.end, false),
+                None => self.move_to((self.cursor + 1).min(len), shift),
+            },
+            KeyCode::Home => self.move_to(0, shift),
+            KeyCode::End => self.move_to(len, shift),
+            KeyCode::Char('a') if ctrl => self.select_all(),
+            KeyCode::Char('u') if ctrl => self.delete_range(0..self.cursor),
+            KeyCode::Char('k') if ctrl => self.delete_range(self.cursor..len),
+            KeyCode::Char('w') if ctrl => self.delete_range(self.word_start(self.cursor)..self.cursor),
+            KeyCode::Backspace => {
+                if !self.delete_selection() && self.cursor > 0 {
+                    self.delete_range(self.cursor - 1..self.cursor);
+                }
+            }
+            KeyCode::Delete => {
+                if !self.delete_selection() && self.cursor < len {
+                    self.delete_range(self.cursor..self.cursor + 1);
+                }
+            }
+            KeyCode::Char(c) if !ctrl && !key.modifiers.contains(KeyModifiers::ALT) => {
+                let mut buf = [0; 4];
+                self.insert_str(c.encode_utf8(&mut buf));
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Place the cursor on click and select on drag, relative to the last drawn area.
+    /// Returns true if the event hit the input.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> bool {
+        let Some(area) = self.area else {
+            return false;
+        };
+        let dragging = mouse.kind == MouseEventKind::Drag(MouseButton::Left);
+        if !dragging && !area.contains(Position::new(mouse.column, mouse.row)) {
+            return false;
+        }
+        let index = self.index_at(mouse.column.saturating_sub(area.x));
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.cursor = index;
+                self.anchor = Some(index);
+            }
+            MouseEventKind::Drag(MouseButton::Left) => self.cursor = index,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Draw the input into a one-row `area`, scrolling horizontally to keep the cursor
+    /// visible. The cursor is drawn as a reversed cell while focused.
+    pub fn draw(&mut self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        if area.is_empty() {
+            return;
+        }
+        let area = Rect { height: 1, ..area };
+        buf.set_style(area, self.style);
+        if self.value.is_empty() && !self.placeholder.is_empty() {
+            buf.set_stringn(area.x, area.y, &self.placeholder, area.width as usize, self.placeholder_style);
+        }
+        self.scroll_to_cursor(area.width as usize);
+
+        let selection = self.selection();
+        let mut x = area.x;
+        for (i, c) in self.value.chars().enumerate().skip(self.scroll) {
+            let width = c.width().unwrap_or(0) as u16;
+            if x + width > area.right() {
+                break;
+            }
+            let selected = selection.as_ref().is_some_and(|r| r.contains(&i));
+            let style = if selected { self.style.patch(self.selection_style) } else { self.style };
+            buf[(x, area.y)].set_char(c).set_style(style);
+            x += width;
+        }
+
+        if self.focused {
+            let cursor_x = area.x + self.width_between(self.scroll, self.cursor) as u16;
+            if cursor_x < area.right() {
+                let cell = &mut buf[(cursor_x, area.y)];
+                cell.set_style(cell.style().add_modifier(Modifier::REVERSED));
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    fn byte_index(&self, index: usize) -> usize {
+        self.value.char_indices().nth(index).map_or(self.value.len(), |(i, _)| i)
+    }
+
+    fn move_to(&mut self, index: usize, extend: bool) {
+        if extend {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = index;
+    }
+
+    fn delete_range(&mut self, range: Range<usize>) {
+        let (start, end) = (self.byte_index(range.start), self.byte_index(range.end));
+        self.value.replace_range(start..end, "");
+        self.cursor = range.start;
+        self.anchor = None;
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some(range) => {
+                self.delete_range(range);
+                true
+            }
+            None => {
+                self.anchor = None;
+                false
+            }
+        }
+    }
+
+    fn word_start(&self, from: usize) -> usize {
+        let chars: Vec<char> = self.value.chars().collect();
+        let mut i = from;
+        while i > 0 && !chars[i - 1].is_alphanumeric() {
+            i -= 1;
+        }
+        while i > 0 && chars[i - 1].is_alphanumeric() {
+            i -= 1;
+        }
+        i
+    }
+
+    fn word_end(&self, from: usize) -> usize {
+        let chars: Vec<char> = self.value.chars().collect();
+        let mut i = from;
+        while i < chars.len() && !chars[i].is_alphanumeric() {
+            i += 1;
+        }
+        while i < chars.len() && chars[i].is_alphanumeric() {
+            i += 1;
+        }
+        i
+    }
+
+    fn width_between(&self, from: usize, to: usize) -> usize {
+        self.value.chars().skip(from).take(to.saturating_sub(from)).map(|c| c.width().unwrap_or(0)).sum()
+    }
+
+    fn scroll_to_cursor(&mut self, width: usize) {
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        }
+        // Leave one cell for the cursor itself
+        while self.scroll < self.cursor && self.width_between(self.scroll, self.cursor) + 1 > width {
+            self.scroll += 1;
+        }
+    }
+
+    fn index_at(&self, column: u16) -> usize {
+        let mut x = 0;
+        for (i, c) in self.value.chars().enumerate().skip(self.scroll) {
+            let width = c.width().unwrap_or(0) as u16;
+            if column < x + width {
+                return i;
+            }
+            x += width;
+        }
+        self.len()
+    }
+}
+
+impl Component for TextInput {
+    fn render(&mut self, frame: &mut ratatui::Frame, cx: &mut Context<Self>) {
+        let area = cx.area(frame);
+        self.draw(area, frame.buffer_mut());
+    }
+
+    fn refresh_on_event(&self) -> bool {
+        true
+    }
+
+    fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+        match event {
+            Event::Key(key) if key.code == KeyCode::Enter => self.submit(),
+            Event::Key(key) => {
+                self.handle_key(key);
+                None
+            }
+            Event::Mouse(mouse) => {
+                self.handle_mouse(mouse);
+                None
+            }
+            Event::Paste(text) => {
+                self.insert_str(&text);
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_editing_and_selection() {
+        let mut input = TextInput::new().with_value("hello world");
+        input.handle_key(key(KeyCode::Left, KeyModifiers::CONTROL | KeyModifiers::SHIFT));
+        assert_eq!(input.selected_text(), Some("world"));
+        input.insert_str("there\nfriend");
+        assert_eq!(input.value(), "hello therefriend");
+
+        input.handle_key(key(KeyCode::Home, KeyModifiers::NONE));
+        input.handle_key(key(KeyCode::Right, KeyModifiers::SHIFT));
+        input.handle_key(key(KeyCode::Char('J'), KeyModifiers::SHIFT));
+        assert_eq!(input.value(), "Jello therefriend");
+        input.handle_key(key(KeyCode::Char('w'), KeyModifiers::CONTROL));
+        assert_eq!(input.value(), "ello therefriend");
+        input.handle_key(key(KeyCode::Char('k'), KeyModifiers::CONTROL));
+        assert_eq!(input.value(), "");
+    }
+
+    #[test]
+    fn test_draw_scrolls_and_click_places_cursor() {
+        let mut input = TextInput::new().with_value("abcdefghij");
+        let area = Rect::new(2, 0, 5, 1);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 1));
+        input.draw(area, &mut buf);
+        let shown: String = (2..7).map(|x| buf[(x, 0)].symbol().to_string()).collect();
+        assert_eq!(shown, "ghij ");
+
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 3,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        };
+        assert!(input.handle_mouse(click));
+        assert_eq!(input.cursor(), 7);
+    }
+
+    #[test]
+    fn test_submit_and_filter() {
+        let mut input = TextInput::new()
+            .filter(|c| c.is_ascii_digit())
+            .max_len(3)
+            .on_submit(|v| Some(Action::Navigate(format!("item/{v}"))));
+        input.insert_str("1a2b34");
+        assert_eq!(input.value(), "123");
+        assert_eq!(input.submit(), Some(Action::Navigate("item/123".into())));
+    }
+}
@@ -0,0 +1,178 @@
+//! Background workers for CPU-bound or blocking jobs.
+//!
+//! A `Worker` runs on a thread of its own (tokio's blocking pool), so hashing files,
+//! parsing large inputs or waiting on a child process never stalls rendering. Components
+//! talk to it through a typed channel: `WorkerHandle::send` queues a command, and every
+//! response the worker sends comes back as the custom event `RESPONSE`, delivered to the
+//! whole tree like other injected events. The worker stops once every handle is dropped
+//! and the queued commands are done.
+//!
+//! # Example
+//! ```ignore
+//! #[derive(Default)]
+//! struct Hasher;
+//!
+//! struct Hashed { path: PathBuf, digest: String }
+//!
+//! impl Worker for Hasher {
+//!     type Command = PathBuf;
+//!     type Response = Hashed;
+//!
+//!     fn handle(&mut self, path: PathBuf, responses: &Responder<Hashed>) {
+//!         let digest = sha256_file(&path); // blocking is fine here
+//!         responses.send(Hashed { path, digest });
+//!     }
+//! }
+//!
+//! // in on_mount
+//! self.hasher = Some(cx.spawn_worker::<Hasher>());
+//!
+//! // in handle_event
+//! if let Some(hashed) = event.custom::<Hashed>() {
+//!     self.digests.insert(hashed.path.clone(), hashed.digest.clone());
+//! }
+//! ```
+
+use tokio::sync::mpsc;
+
+use crate::AppContext;
+
+/// Name of the custom event carrying a worker's response.
+pub const RESPONSE: &str = "rat_nexus::worker_response";
+
+/// A job run on a dedicated thread, driven by commands.
+pub trait Worker: Send + 'static {
+    /// What components ask the worker to do.
+    type Command: Send + 'static;
+    /// What the worker reports back, as the payload of a `RESPONSE` event.
+    type Response: Send + Sync + 'static;
+
+    /// Handle one command. Runs on the worker's thread, so it may block; send any
+    /// number of responses, e.g. progress followed by a result.
+    fn handle(&mut self, command: Self::Command, responses: &Responder<Self::Response>);
+}
+
+/// Sends a worker's responses back to the components.
+pub struct Responder<R> {
+    app: AppContext,
+    _response: std::marker::PhantomData<fn(R)>,
+}
+
+impl<R: Send + Sync + 'static> Responder<R> {
+    /// Deliver `response` as a `RESPONSE` event on the next loop iteration.
+    pub fn send(&self, response: R) {
+        self.app.emit_custom(RESPONSE, response);
+    }
+}
+
+/// Sends commands to a running worker. Cloning shares the same worker.
+pub struct WorkerHandle<W: Worker> {
+    commands: mpsc::UnboundedSender<W::Command>,
+}
+
+impl<W: Worker> Clone for WorkerHandle<W> {
+    fn clone(&self) -> Self {
+        Self { commands: self.commands.clone() }
+    }
+}
+
+impl<W: Worker> WorkerHandle<W> {
+    /// Queue `command`; returns false if the worker is no longer running.
+    pub fn send(&self, command: W::Command) -> bool {
+        self.commands.send(command).is_ok()
+    }
+
+    /// Whether the worker still accepts commands. False once it panicked.
+    pub fn is_running(&self) -> bool {
+        !self.commands.is_closed()
+    }
+}
+
+impl<W: Worker> std::fmt::Debug for WorkerHandle<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkerHandle")
+            .field("worker", &std::any::type_name::<W>())
+            .field("running", &self.is_running())
+            .finish()
+    }
+}
+
+/// Start `worker` on the blocking pool.
+pub(crate) fn spawn<W: Worker>(app: &AppContext, mut worker: W) -> WorkerHandle<W> {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let responder = Responder { app: AppContext::clone(app), _response: std::marker::PhantomData };
+    tokio::task::spawn_blocking(move || {
+        while let Some(command) = rx.blocking_recv() {
+            worker.handle(command, &responder);
+        }
+    });
+    WorkerHandle { commands: tx }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Squarer {
+        handled: u32,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Squared(u64, u32);
+
+    impl Worker for Squarer {
+        type Command = u64;
+        type Response = Squared;
+
+        fn handle(&mut self, n: u64, responses: &Responder<Squared>) {
+            self.handled += 1;
+            responses.send(Squared(n * n, self.handled));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_worker_responds_in_order_as_custom_events() {
+        let (app, _refresh, mut injected) = AppContext::new();
+        let worker = app.spawn_worker::<Squarer>();
+        assert!(worker.send(3));
+        assert!(worker.clone().send(4));
+
+        for expected in [Squared(9, 1), Squared(16, 2)] {
+            let event = injected.recv().await.unwrap();
+            assert!(matches!(&event, crate::Event::Custom(custom) if custom.name() == RESPONSE));
+            assert_eq!(event.custom::<Squared>(), Some(&expected));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_worker_stops_when_handles_are_dropped() {
+        struct Flagged(std::sync::Arc<std::sync::atomic::AtomicBool>);
+
+        impl Worker for Flagged {
+            type Command = ();
+            type Response = ();
+
+            fn handle(&mut self, _: (), _: &Responder<()>) {}
+        }
+
+        impl Drop for Flagged {
+            fn drop(&mut self) {
+                self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+            }
+        }
+
+        let (app, _refresh, _injected) = AppContext::new();
+        let stopped = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let worker = app.spawn_worker_with(Flagged(std::sync::Arc::clone(&stopped)));
+        assert!(worker.send(()));
+        drop(worker);
+        for _ in 0..100 {
+            if stopped.load(std::sync::atomic::Ordering::SeqCst) {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        }
+        assert!(stopped.load(std::sync::atomic::Ordering::SeqCst));
+    }
+}